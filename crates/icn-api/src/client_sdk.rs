@@ -59,15 +59,19 @@ export interface CastVoteRequest {
   vote_option: "Yes" | "No" | "Abstain";
   credential_proof?: ZkCredentialProof | null;
   revocation_proof?: ZkRevocationProof | null;
+  revision?: string | null;
 }
 
 export interface DelegateRequest {
   from_did: string;
   to_did: string;
+  scope?: string | null;
+  expires_at?: number | null;
 }
 
 export interface RevokeDelegationRequest {
   from_did: string;
+  scope?: string | null;
 }
 
 export interface Proposal {
@@ -102,6 +106,21 @@ export interface IssueCredentialRequest {
   attributes: Record<string, string>;
   schema: string;
   expiration: number;
+  credential_type: string;
+  cooperative_id: string;
+}
+
+export interface IssuanceOffer {
+  id: string;
+  issuer: string;
+  holder: string;
+  schema: string;
+  credential_type: string;
+  claims: Record<string, string>;
+  cooperative_id: string;
+  validity?: number;
+  nonce: string;
+  expires_at: number;
 }
 
 export interface CredentialResponse {
@@ -488,6 +507,186 @@ export class ICNClient {
       return this.post<string>('/governance/revoke', request);
     },
 
+    async tagProposal(proposalId: string, tags: string[]): Promise<string> {
+      return this.post<string>('/governance/tag', { proposal_id: proposalId, tags });
+    },
+
+    async getDelegations(): Promise<any[]> {
+      return this.get<any[]>('/governance/delegations');
+    },
+
+    async getProposalDelegations(proposalId: string): Promise<any> {
+      return this.get<any>(`/governance/delegations/${proposalId}`);
+    },
+
+    async startDeliberation(proposalId: string): Promise<string> {
+      return this.post<string>(`/governance/deliberation/${proposalId}/start`, {});
+    },
+
+    async getDeliberation(proposalId: string): Promise<any> {
+      return this.get<any>(`/governance/deliberation/${proposalId}`);
+    },
+
+    async reviseProposal(proposalId: string, description: string, body?: number[]): Promise<string> {
+      return this.post<string>('/governance/revise', { proposal_id: proposalId, description, body });
+    },
+
+    async proposeAmendment(proposalId: string, description: string, rationale: string, body?: number[]): Promise<string> {
+      return this.post<string>('/governance/amendments', { proposal_id: proposalId, description, rationale, body });
+    },
+
+    async voteOnAmendment(proposalId: string, amendmentId: string, voteOption: string): Promise<string> {
+      return this.post<string>('/governance/amendments/vote', { proposal_id: proposalId, amendment_id: amendmentId, vote_option: voteOption });
+    },
+
+    async resolveAmendments(proposalId: string): Promise<any[]> {
+      return this.post<any[]>(`/governance/amendments/${proposalId}/resolve`, {});
+    },
+
+    async commentOnProposal(proposalId: string, text: string, revision?: string, parent?: string): Promise<string> {
+      return this.post<string>('/governance/comments', { proposal_id: proposalId, text, revision, parent });
+    },
+
+    async getComments(proposalId: string): Promise<any[]> {
+      return this.get<any[]>(`/governance/comments/${proposalId}`);
+    },
+
+    async getParameters(): Promise<any[]> {
+      return this.get<any[]>('/governance/parameters');
+    },
+
+    async getParameterHistory(): Promise<any[]> {
+      return this.get<any[]>('/governance/parameters/history');
+    },
+
+    async proposeParameterRollback(version: number, durationSecs?: number): Promise<string> {
+      return this.post<string>('/governance/parameters/rollback', { version, duration_secs: durationSecs });
+    },
+
+    async getUpgrades(): Promise<any> {
+      return this.get<any>('/governance/upgrades');
+    },
+
+    async signalUpgradeReady(proposalId: string): Promise<any> {
+      return this.post<any>(`/governance/upgrades/${proposalId}/ready`, {});
+    },
+
+    async relayUpgradeReadiness(signal: any): Promise<any> {
+      return this.post<any>('/governance/upgrades/readiness', signal);
+    },
+
+    async registerVoterCommitment(did: string, commitment: string): Promise<any> {
+      return this.post<any>('/governance/secret-ballot/commitment', { did, commitment });
+    },
+
+    async enableSecretBallot(proposalId: string, verifyingKey: string, treeDepth: number, revealFrom: number): Promise<any> {
+      return this.post<any>(`/governance/secret-ballot/${proposalId}/enable`, {
+        verifying_key: verifyingKey,
+        tree_depth: treeDepth,
+        reveal_from: revealFrom
+      });
+    },
+
+    async getSecretBallot(proposalId: string): Promise<any> {
+      return this.get<any>(`/governance/secret-ballot/${proposalId}`);
+    },
+
+    async castSecretBallot(ballot: any): Promise<any> {
+      return this.post<any>('/governance/secret-ballot/cast', ballot);
+    },
+
+    async revealSecretBallot(reveal: any): Promise<any> {
+      return this.post<any>('/governance/secret-ballot/reveal', reveal);
+    },
+
+    async getLifecycleTimers(): Promise<any[]> {
+      return this.get<any[]>('/governance/lifecycle/timers');
+    },
+
+    async adoptCrossFederationProposal(proposal: any, localFederation: string, durationSecs: number): Promise<any> {
+      return this.post<any>('/governance/cross-federation/adopt', {
+        proposal,
+        local_federation: localFederation,
+        duration_secs: durationSecs
+      });
+    },
+
+    async ratifyCrossFederationProposal(proposalId: string): Promise<any> {
+      return this.post<any>(`/governance/cross-federation/${proposalId}/ratify`, {});
+    },
+
+    async recordFederationRatification(ratification: any): Promise<any> {
+      return this.post<any>('/governance/cross-federation/ratification', ratification);
+    },
+
+    async executeCrossFederationProposal(proposalId: string): Promise<any> {
+      return this.post<any>(`/governance/cross-federation/${proposalId}/execute`, {});
+    },
+
+    async getCrossFederationProposal(proposalId: string): Promise<any> {
+      return this.get<any>(`/governance/cross-federation/${proposalId}`);
+    },
+
+    async getConstitution(version?: number): Promise<any> {
+      return this.get<any>(version === undefined ? '/governance/constitution' : `/governance/constitution/${version}`);
+    },
+
+    async getConstitutionHistory(): Promise<any[]> {
+      return this.get<any[]>('/governance/constitution/history');
+    },
+
+    async adoptConstitution(constitution: any): Promise<any> {
+      return this.post<any>('/governance/constitution', constitution);
+    },
+
+    async fileComplaint(complaint: any): Promise<any> {
+      return this.post<any>('/governance/cases', complaint);
+    },
+
+    async listCases(): Promise<any[]> {
+      return this.get<any[]>('/governance/cases');
+    },
+
+    async getCase(caseId: string): Promise<any> {
+      return this.get<any>(`/governance/cases/${caseId}`);
+    },
+
+    async respondToComplaint(caseId: string, response: any): Promise<any> {
+      return this.post<any>(`/governance/cases/${caseId}/response`, response);
+    },
+
+    async submitPanelVerdict(caseId: string, verdict: any): Promise<any> {
+      return this.post<any>(`/governance/cases/${caseId}/verdict`, verdict);
+    },
+
+    async fileAppeal(caseId: string, appeal: any): Promise<any> {
+      return this.post<any>(`/governance/cases/${caseId}/appeal`, appeal);
+    },
+
+    async getWeighting(): Promise<any> {
+      return this.get<any>('/governance/weighting');
+    },
+
+    async setWeightRule(kind: string, rule: any | null): Promise<any> {
+      return this.post<any>('/governance/weighting', { kind, rule });
+    },
+
+    async recordCooperativeWeight(did: string): Promise<any> {
+      return this.post<any>('/governance/weighting/profiles', { did });
+    },
+
+    async recordWeightingCredential(credential: any): Promise<any> {
+      return this.post<any>('/governance/weighting/credentials', credential);
+    },
+
+    async getMemberProfile(did: string): Promise<any> {
+      return this.get<any>(`/governance/weighting/profiles/${did}`);
+    },
+
+    async getWeightedTally(proposalId: string): Promise<any> {
+      return this.get<any>(`/governance/weighting/tally/${proposalId}`);
+    },
+
     async closeProposal(proposalId: string): Promise<string> {
       return this.post<string>('/governance/close', { proposal_id: proposalId });
     },
@@ -499,8 +698,8 @@ export class ICNClient {
 
   // Identity API
   identity = {
-    async issueCredential(request: IssueCredentialRequest): Promise<CredentialResponse> {
-      return this.post<CredentialResponse>('/identity/credentials/issue', request);
+    async issueCredential(request: IssueCredentialRequest): Promise<IssuanceOffer> {
+      return this.post<IssuanceOffer>('/identity/credentials/issue', request);
     },
 
     async verifyCredential(credential: VerifiableCredential): Promise<VerificationResponse> {
@@ -515,12 +714,182 @@ export class ICNClient {
       return this.get<string[]>('/identity/credentials/schemas');
     },
 
+    async suspendCredential(cid: string): Promise<any> {
+      return this.post<any>('/identity/credentials/suspend', { cid });
+    },
+
+    async reinstateCredential(cid: string): Promise<any> {
+      return this.post<any>('/identity/credentials/reinstate', { cid });
+    },
+
+    async getStatusList(id: string, purpose: 'revocation' | 'suspension'): Promise<any> {
+      return this.get<any>(`/identity/status-lists/${id}/${purpose}`);
+    },
+
+    async exportCredential(cid: string, format: 'vc-jwt' | 'sd-jwt' | 'eddsa-rdfc-2022'): Promise<any> {
+      return this.post<any>('/identity/credentials/export', { cid, format });
+    },
+
+    async importCredential(credential: string, format: 'vc-jwt' | 'sd-jwt' | 'eddsa-rdfc-2022'): Promise<any> {
+      return this.post<any>('/identity/credentials/import', { credential, format });
+    },
+
+    async publishSchema(name: string, version: string, jsonSchema: any): Promise<any> {
+      return this.post<any>('/identity/schemas', { name, version, json_schema: jsonSchema });
+    },
+
+    async listPublishedSchemas(): Promise<any[]> {
+      return this.get<any[]>('/identity/schemas');
+    },
+
+    async getSchema(cid: string): Promise<any> {
+      return this.get<any>(`/identity/schemas/${cid}`);
+    },
+
+    async setIssuerPolicy(policy: any): Promise<any> {
+      return this.post<any>('/identity/issuance/policies', policy);
+    },
+
+    async listIssuerPolicies(cooperativeId?: string): Promise<any[]> {
+      const query = cooperativeId ? `?cooperative_id=${encodeURIComponent(cooperativeId)}` : '';
+      return this.get<any[]>(`/identity/issuance/policies${query}`);
+    },
+
+    async offerCredential(terms: any): Promise<any> {
+      return this.post<any>('/identity/issuance/offers', terms);
+    },
+
+    async getCredentialOffer(id: string): Promise<any> {
+      return this.get<any>(`/identity/issuance/offers/${id}`);
+    },
+
+    async requestCredential(request: { offer_id: string; holder: string; signature: any }): Promise<any> {
+      return this.post<any>('/identity/issuance/requests', request);
+    },
+
+    async addPersonhoodSeed(did: string): Promise<any> {
+      return this.post<any>('/identity/personhood/seeds', { did });
+    },
+
+    async vouchForPerson(vouch: any): Promise<any> {
+      return this.post<any>('/identity/personhood/vouches', vouch);
+    },
+
+    async evaluatePersonhood(): Promise<any> {
+      return this.post<any>('/identity/personhood/evaluate', {});
+    },
+
+    async revokePersonhood(did: string, reason: string): Promise<any[]> {
+      return this.post<any[]>('/identity/personhood/revoke', { did, reason });
+    },
+
+    async issuePersonhoodCredential(did: string): Promise<any> {
+      return this.post<any>('/identity/personhood/credential', { did });
+    },
+
+    async getPersonhood(did: string): Promise<any> {
+      return this.get<any>(`/identity/personhood/${did}`);
+    },
+
     async generateProof(request: GenerateProofRequest): Promise<ProofResponse> {
       return this.post<ProofResponse>('/identity/generate-proof', request);
     },
 
     async verifyProof(proof: ZkCredentialProof): Promise<VerificationResponse> {
       return this.post<VerificationResponse>('/identity/verify', proof);
+    },
+
+    async verifyProofs(proofs: ZkCredentialProof[]): Promise<any> {
+      return this.post<any>('/identity/verify/batch', { proofs });
+    },
+
+    async submitKeyEvent(event: any): Promise<any> {
+      return this.post<any>('/identity/did-icn/events', event);
+    },
+
+    async getKeyEventLog(did: string): Promise<any> {
+      return this.get<any>(`/identity/did-icn/${did}`);
+    },
+
+    async configureRecovery(config: any): Promise<any> {
+      return this.post<any>('/identity/recovery/config', config);
+    },
+
+    async getRecoveryConfig(did: string): Promise<any> {
+      return this.get<any>(`/identity/recovery/config/${did}`);
+    },
+
+    async openRecovery(
+      did: string,
+      recoveryKey: number[],
+      signature: number[],
+      guardian?: string
+    ): Promise<any> {
+      return this.post<any>('/identity/recovery/cases', {
+        did,
+        recovery_key: recoveryKey,
+        signature,
+        guardian,
+      });
+    },
+
+    async getRecoveryCase(id: string): Promise<any> {
+      return this.get<any>(`/identity/recovery/cases/${id}`);
+    },
+
+    async approveRecovery(id: string, approval: any, share: any): Promise<any> {
+      return this.post<any>(`/identity/recovery/cases/${id}/approve`, { approval, share });
+    },
+
+    async cancelRecovery(id: string, signature: number[]): Promise<any> {
+      return this.post<any>(`/identity/recovery/cases/${id}/cancel`, { signature });
+    },
+
+    async getRecoveryShares(id: string): Promise<any> {
+      return this.get<any>(`/identity/recovery/cases/${id}/shares`);
+    },
+
+    async completeRecovery(id: string, event: any): Promise<any> {
+      return this.post<any>(`/identity/recovery/cases/${id}/complete`, event);
+    }
+  };
+
+  // DIDComm API
+  didcomm = {
+    async send(request: any): Promise<any> {
+      return this.post<any>('/didcomm/send', request);
+    },
+
+    async getMessages(): Promise<any[]> {
+      return this.get<any[]>('/didcomm/messages');
+    },
+
+    async getMediated(): Promise<any[]> {
+      return this.get<any[]>('/didcomm/mediated');
+    },
+
+    async requestMediation(mediator: string): Promise<any> {
+      return this.post<any>('/didcomm/mediation', { mediator });
+    },
+
+    async pickup(mediator: string, limit?: number): Promise<any> {
+      return this.post<any>('/didcomm/pickup', { mediator, limit });
+    },
+
+    async offerCredential(request: any): Promise<any> {
+      return this.post<any>('/didcomm/credential-offer', request);
+    },
+
+    async requestCredential(offerId: string): Promise<any> {
+      return this.post<any>('/didcomm/credential-request', { offer_id: offerId });
+    },
+
+    async inviteToProposal(proposalId: string, invitees: string[], comment?: string): Promise<any[]> {
+      return this.post<any[]>('/didcomm/proposal-invitation', {
+        proposal_id: proposalId,
+        invitees,
+        comment,
+      });
     }
   };
 
@@ -540,6 +909,26 @@ export class ICNClient {
 
     async getStatus(): Promise<FederationStatus> {
       return this.get<FederationStatus>('/federation/status');
+    },
+
+    async submitApplication(application: any): Promise<any> {
+      return this.post<any>('/federation/applications', application);
+    },
+
+    async listApplications(): Promise<any[]> {
+      return this.get<any[]>('/federation/applications');
+    },
+
+    async getApplication(applicationId: string): Promise<any> {
+      return this.get<any>(`/federation/applications/${applicationId}`);
+    },
+
+    async answerChallenge(applicationId: string, response: any): Promise<any> {
+      return this.post<any>(`/federation/applications/${applicationId}/challenge`, response);
+    },
+
+    async setMembershipGate(gate: any): Promise<any> {
+      return this.post<any>('/federation/applications/gate', gate);
     }
   };
 
@@ -14,17 +14,58 @@ pub struct CastVoteRequest {
     pub credential_proof: Option<ZkCredentialProof>,
     #[serde(default)]
     pub revocation_proof: Option<ZkRevocationProof>,
+    /// Revision CID the voter read; the vote is refused if voting is bound
+    /// to a different revision.
+    #[serde(default)]
+    pub revision: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DelegateRequest {
     pub from_did: String,
     pub to_did: String,
+    /// `all` (default), `type:<proposal kind>` or `tag:<topic>`.
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// Unix timestamp after which the delegation lapses.
+    #[serde(default)]
+    pub expires_at: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct RevokeDelegationRequest {
     pub from_did: String,
+    /// Revoke only the delegation in this scope; all scopes when omitted.
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+/// Attach topic tags to a proposal so tag-scoped delegations apply.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TagProposalRequest {
+    pub proposal_id: String,
+    pub tags: Vec<String>,
+}
+
+/// A single edge of the delegation graph.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DelegationEdge {
+    pub from_did: String,
+    pub to_did: String,
+    pub scope: String,
+    pub created_at: u64,
+    pub expires_at: Option<u64>,
+}
+
+/// Delegation graph as it applies to one proposal.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProposalDelegationView {
+    pub proposal_id: String,
+    pub tags: Vec<String>,
+    /// Delegations that govern this proposal.
+    pub edges: Vec<DelegationEdge>,
+    /// Number of members each voter represents, keyed by DID.
+    pub effective_weights: std::collections::HashMap<String, usize>,
 }
 
 // Define ProposalInputType and SubmitProposalRequest as per Step 2
@@ -43,6 +84,7 @@ pub enum ProposalInputType {
         did: String,
     },
     SoftwareUpgrade {
+        /// Signed upgrade manifest encoded as JSON.
         version: String,
     }, // Matches ProposalType more closely
     GenericText {
@@ -51,6 +93,10 @@ pub enum ProposalInputType {
     Resolution {
         actions: Vec<ResolutionActionInput>,
     },
+    /// Replace the constitution with the next version.
+    ConstitutionAmendment {
+        constitution: icn_governance::Constitution,
+    },
     // Add more as needed
 }
 
@@ -90,3 +136,160 @@ pub struct CloseProposalResponse {
     pub no: usize,
     pub abstain: usize,
 }
+
+/// Request to publish a new revision of a proposal under deliberation.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReviseProposalRequest {
+    pub proposal_id: String,
+    pub description: String,
+    #[serde(default)]
+    pub body: Option<Vec<u8>>,
+}
+
+/// Request to propose an amendment to a proposal under deliberation.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProposeAmendmentRequest {
+    pub proposal_id: String,
+    pub description: String,
+    #[serde(default)]
+    pub body: Option<Vec<u8>>,
+    #[serde(default)]
+    pub rationale: String,
+}
+
+/// Request to vote on an open amendment.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AmendmentVoteRequest {
+    pub proposal_id: String,
+    pub amendment_id: String,
+    pub vote_option: String,
+}
+
+/// Request to comment on a proposal revision.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CommentRequest {
+    pub proposal_id: String,
+    /// Revision CID being discussed; the head revision when omitted.
+    #[serde(default)]
+    pub revision: Option<String>,
+    /// CID of the comment being replied to.
+    #[serde(default)]
+    pub parent: Option<String>,
+    pub text: String,
+}
+
+/// A governable runtime parameter and its current value.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ParameterView {
+    pub name: String,
+    pub description: String,
+    /// `U64`, `F64`, `Bool`, `String` or `StringList`.
+    pub value_type: String,
+    pub value: String,
+    pub default: String,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// Whether nodes apply changes without restarting.
+    pub hot_reload: bool,
+}
+
+/// One executed parameter change.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ParameterChangeView {
+    pub version: u64,
+    pub name: String,
+    pub previous: String,
+    pub value: String,
+    pub proposal_id: Option<String>,
+    pub effective_at: u64,
+}
+
+/// Request to propose undoing a parameter change.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ParameterRollbackRequest {
+    /// Version of the change to undo.
+    pub version: u64,
+    /// Voting period of the rollback proposal; one day when omitted.
+    #[serde(default)]
+    pub duration_secs: Option<u64>,
+}
+
+/// A scheduled software upgrade and the nodes ready for it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpgradeStatusView {
+    pub proposal_id: String,
+    pub version: String,
+    pub protocol_version: u32,
+    pub artifact_cid: String,
+    pub artifact_sha256: String,
+    /// `height <n>` or `time <unix seconds>`.
+    pub activation: String,
+    pub active: bool,
+    /// Names of CCL contracts migrated by this upgrade.
+    pub contracts: Vec<String>,
+    pub ready_nodes: Vec<String>,
+}
+
+/// Upgrade state reported by a node.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpgradeSummary {
+    /// Protocol version currently active on the node.
+    pub protocol_version: u32,
+    pub upgrades: Vec<UpgradeStatusView>,
+}
+
+/// Request to register a member's secret-ballot identity commitment.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VoterCommitmentRequest {
+    pub did: String,
+    /// Hex encoded identity commitment.
+    pub commitment: String,
+}
+
+/// Request to switch a proposal to secret ballots.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EnableSecretBallotRequest {
+    /// Hex encoded Groth16 verifying key for the membership circuit.
+    pub verifying_key: String,
+    pub tree_depth: usize,
+    /// Start of the reveal phase, in unix seconds.
+    pub reveal_from: u64,
+}
+
+/// Request to adopt a joint proposal as one of its participating federations.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdoptCrossFederationRequest {
+    pub proposal: icn_governance::JointProposal,
+    /// Federation this node ratifies for.
+    pub local_federation: String,
+    /// Length of the local ratification vote in seconds.
+    pub duration_secs: u64,
+}
+
+/// Request to submit a budget proposal decided by conviction voting.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SubmitConvictionProposalRequest {
+    pub beneficiary_did: String,
+    pub amount: u64,
+    pub description: String,
+}
+
+/// Request to stake (or, with `amount = 0`, withdraw) conviction support.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ConvictionStakeRequest {
+    pub staker_did: String,
+    pub proposal_id: String,
+    pub amount: u64,
+}
+
+/// Current conviction of a proposal.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ConvictionStatusResponse {
+    pub proposal_id: String,
+    pub requested: u64,
+    pub total_staked: u64,
+    pub conviction: f64,
+    /// Conviction required to pass, or `None` if the request exceeds the treasury limit.
+    pub threshold: Option<f64>,
+    pub passed: bool,
+}
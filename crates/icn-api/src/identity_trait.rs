@@ -1,11 +1,16 @@
 use async_trait::async_trait;
 use icn_common::ZkCredentialProof;
 use icn_common::{Cid, CommonError, Did, ZkRevocationProof};
-use icn_identity::{Credential as VerifiableCredential, DisclosedCredential};
+use icn_identity::{
+    Credential as VerifiableCredential, CredentialFormat, CredentialOffer, CredentialSchema,
+    DisclosedCredential, EncryptedShare, GuardianApproval, IssuanceOffer, PersonhoodRecord,
+    ProofOfPersonhood, SignatureBytes, Vouch,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
-/// Request to issue a verifiable credential.
+/// Request to issue a verifiable credential. The node answers with an
+/// [`IssuanceOffer`] the holder must sign before the credential is issued.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IssueCredentialRequest {
     pub issuer: Did,
@@ -13,6 +18,10 @@ pub struct IssueCredentialRequest {
     pub attributes: BTreeMap<String, String>,
     pub schema: Cid,
     pub expiration: u64,
+    /// Credential type the cooperative's issuer policy covers.
+    pub credential_type: String,
+    /// Cooperative the credential is issued for.
+    pub cooperative_id: String,
 }
 
 /// Response containing the issued credential.
@@ -33,6 +42,161 @@ pub struct RevokeCredentialRequest {
     pub cid: Cid,
 }
 
+/// Request to export a credential issued by the node in a standard format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportCredentialRequest {
+    pub cid: Cid,
+    pub format: CredentialFormat,
+}
+
+/// An exported credential: a compact JWT, an SD-JWT or a JSON document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportCredentialResponse {
+    pub format: CredentialFormat,
+    pub credential: String,
+}
+
+/// Request to verify and import a credential in a standard format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportCredentialRequest {
+    pub format: CredentialFormat,
+    pub credential: String,
+}
+
+/// Request to open a guardian recovery for a did:icn identifier.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenRecoveryRequest {
+    pub did: Did,
+    /// Ed25519 session key the guardians release their shares to.
+    pub recovery_key: Vec<u8>,
+    /// Guardian vouching for the request; when absent the identifier's
+    /// current key signs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guardian: Option<Did>,
+    /// Signature over `RecoveryCase::opening_bytes`.
+    pub signature: SignatureBytes,
+}
+
+/// A guardian's signed approval and their share re-sealed to the session key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApproveRecoveryRequest {
+    pub approval: GuardianApproval,
+    pub share: EncryptedShare,
+}
+
+/// Cancellation of a recovery, signed by the DID's current key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelRecoveryRequest {
+    pub signature: SignatureBytes,
+}
+
+/// Request to send a DIDComm message from the node's DID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendDidCommRequest {
+    pub to: Did,
+    /// Protocol message type URI.
+    #[serde(rename = "type")]
+    pub message_type: String,
+    #[serde(default)]
+    pub body: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thid: Option<String>,
+    /// Send with anoncrypt, hiding the node's DID from the recipient.
+    #[serde(default)]
+    pub anonymous: bool,
+    /// Mediator to route through instead of the recipient's published one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mediator: Option<Did>,
+}
+
+/// A sent DIDComm message and the hop it was relayed to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendDidCommResponse {
+    pub id: String,
+    pub next_hop: Did,
+}
+
+/// Request to mediate for the node, or to pick up its queued messages.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediatorRequest {
+    pub mediator: Did,
+    /// Maximum number of queued messages to pick up.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+/// Request to let a DID ask the node to mediate for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediationAllowRequest {
+    pub did: Did,
+}
+
+/// Request to offer a credential to a holder over DIDComm.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialOfferRequest {
+    pub holder: Did,
+    #[serde(flatten)]
+    pub offer: CredentialOffer,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mediator: Option<Did>,
+}
+
+/// Request to answer a credential offer in the node's inbox.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialOfferReply {
+    pub offer_id: String,
+}
+
+/// Request to publish a credential schema under the node's DID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishSchemaRequest {
+    pub name: String,
+    pub version: String,
+    pub json_schema: serde_json::Value,
+}
+
+/// A published credential schema and the CID credentials reference it by.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaResponse {
+    pub cid: Cid,
+    pub schema: CredentialSchema,
+}
+
+/// Request naming the DID a personhood operation applies to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonhoodRequest {
+    pub did: Did,
+}
+
+/// Request to revoke a DID's personhood as a Sybil, forfeiting its vouches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevokePersonhoodRequest {
+    pub did: Did,
+    pub reason: String,
+}
+
+/// Personhood standing of a DID and the vouches behind it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonhoodResponse {
+    pub did: Did,
+    /// Whether the DID is a seed or currently verified.
+    pub verified: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub record: Option<PersonhoodRecord>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<ProofOfPersonhood>,
+    pub vouches: Vec<Vouch>,
+}
+
+/// Request to invite members to a governance proposal over DIDComm.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalInvitationRequest {
+    pub proposal_id: String,
+    pub invitees: Vec<Did>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
 /// Request selective disclosure of certain fields.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DisclosureRequest {
@@ -82,7 +246,7 @@ pub trait IdentityApi {
     async fn issue_credential(
         &self,
         request: IssueCredentialRequest,
-    ) -> Result<CredentialResponse, CommonError>;
+    ) -> Result<IssuanceOffer, CommonError>;
 
     async fn verify_credential(
         &self,
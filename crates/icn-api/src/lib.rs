@@ -516,6 +516,9 @@ impl GovernanceApi for GovernanceApiImpl {
                     actions: core_actions,
                 })
             }
+            ProposalInputType::ConstitutionAmendment { constitution } => {
+                ProposalType::ConstitutionAmendment(constitution.to_proposal_payload()?)
+            }
         };
 
         if let Some(ref p) = request.credential_proof {
@@ -1157,6 +1160,7 @@ mod tests {
             vote_option: "yes".to_string(),
             credential_proof: None,
             revocation_proof: None,
+            revision: None,
         };
 
         let vote_res = api.cast_vote(cast_vote_req);
@@ -103,11 +103,168 @@ pub enum CredentialLifecycleCommands {
         #[clap(long, help = "Reason for revocation")]
         reason: String,
     },
+    /// Suspend a credential until it is reinstated
+    Suspend {
+        #[clap(help = "Credential CID to suspend")]
+        cid: String,
+    },
+    /// Lift a credential suspension
+    Reinstate {
+        #[clap(help = "Credential CID to reinstate")]
+        cid: String,
+    },
+    /// Fetch a signed status list published by the node
+    StatusList {
+        #[clap(help = "Status list identifier, e.g. status-list-0")]
+        id: String,
+        #[clap(long, default_value = "revocation", help = "revocation or suspension")]
+        purpose: String,
+    },
+    /// Export a node-issued credential as VC-JWT, SD-JWT or Data Integrity
+    Export {
+        #[clap(help = "Credential CID to export")]
+        cid: String,
+        #[clap(long, help = "vc-jwt, sd-jwt or eddsa-rdfc-2022")]
+        format: String,
+    },
+    /// Verify an external credential and map it to an ICN credential
+    Import {
+        #[clap(help = "File holding the credential, or - for stdin")]
+        input: String,
+        #[clap(long, help = "vc-jwt, sd-jwt or eddsa-rdfc-2022")]
+        format: String,
+    },
+    /// Publish and inspect JSON Schema credential schemas
+    Schema {
+        #[clap(subcommand)]
+        command: SchemaCommands,
+    },
+    /// Manage who may issue a cooperative's credential types
+    Policy {
+        #[clap(subcommand)]
+        command: PolicyCommands,
+    },
+    /// Offer a credential from the node to a holder
+    Offer {
+        #[clap(long, help = "Holder's DID")]
+        holder: String,
+        #[clap(long, help = "Published credential schema CID")]
+        schema: String,
+        #[clap(long, help = "Credential type, e.g. membership")]
+        credential_type: String,
+        #[clap(long = "claim", help = "Claim as name=value (repeatable)")]
+        claims: Vec<String>,
+        #[clap(long, help = "Cooperative whose issuer policy applies")]
+        cooperative: String,
+        #[clap(long, help = "Seconds the credential stays valid")]
+        validity: Option<u64>,
+    },
+    /// Accept an offer by signing it with the holder key and receive the credential
+    Accept {
+        #[clap(help = "Offer identifier")]
+        offer_id: String,
+        #[clap(long, help = "Holder's Ed25519 private key (base58)")]
+        key_bs58: String,
+    },
     /// Run example credential flows
     Example {
         #[clap(subcommand)]
         flow: ExampleFlows,
     },
+    /// BBS+ credentials with unlinkable selective disclosure
+    Bbs {
+        #[clap(subcommand)]
+        command: BbsCommands,
+    },
+}
+
+/// BBS+ credential commands
+#[derive(Subcommand, Debug)]
+pub enum BbsCommands {
+    /// Generate a BBS issuer key bound to the issuer's DID key
+    Keygen {
+        #[clap(long, help = "Issuer's Ed25519 private key (base58)")]
+        issuer_key_bs58: String,
+        #[clap(long, help = "Issuer DID (defaults to the did:key of the issuer key)")]
+        issuer: Option<String>,
+    },
+    /// Sign claims about a holder
+    Issue {
+        #[clap(long, help = "Issuer key file written by `keygen`")]
+        issuer_keys: String,
+        #[clap(long, help = "Holder's DID")]
+        holder: String,
+        #[clap(long, help = "Credential schema CID")]
+        schema: String,
+        #[clap(long = "claim", help = "Claim as name=value (repeatable)")]
+        claims: Vec<String>,
+    },
+    /// Derive a fresh proof from a held credential
+    Present {
+        #[clap(help = "Path to credential file or '-' for stdin")]
+        credential_path: String,
+        #[clap(long, help = "Nonce supplied by the verifier")]
+        nonce: String,
+        #[clap(long = "disclose", help = "Claim to reveal (repeatable)")]
+        disclose: Vec<String>,
+        #[clap(long = "at-least", help = "Prove claim >= bound, as name:bound")]
+        at_least: Vec<String>,
+        #[clap(long = "at-most", help = "Prove claim <= bound, as name:bound")]
+        at_most: Vec<String>,
+        #[clap(long, help = "Reveal the holder DID (makes presentations linkable)")]
+        disclose_holder: bool,
+        #[clap(long, help = "Wrap the proof in a verifiable presentation")]
+        presentation: bool,
+    },
+    /// Verify a derived proof or presentation with the node
+    Verify {
+        #[clap(help = "Path to proof or presentation file or '-' for stdin")]
+        proof_path: String,
+    },
+}
+
+/// Credential schema commands
+#[derive(Subcommand, Debug)]
+pub enum SchemaCommands {
+    /// Publish a JSON Schema under the node DID
+    Publish {
+        #[clap(long, help = "Schema name")]
+        name: String,
+        #[clap(long, help = "Schema version")]
+        version: String,
+        #[clap(help = "Path to the JSON Schema file or '-' for stdin")]
+        schema_path: String,
+    },
+    /// List published schemas
+    List,
+    /// Show a published schema
+    Show {
+        #[clap(help = "Schema CID")]
+        cid: String,
+    },
+}
+
+/// Issuer policy commands
+#[derive(Subcommand, Debug)]
+pub enum PolicyCommands {
+    /// Set the issuers allowed for a cooperative's credential type
+    Set {
+        #[clap(help = "Cooperative identifier")]
+        cooperative_id: String,
+        #[clap(help = "Credential type, e.g. membership")]
+        credential_type: String,
+        #[clap(long = "issuer", help = "DID allowed to issue (repeatable)")]
+        issuers: Vec<String>,
+        #[clap(long, help = "Schema CID credentials must reference")]
+        schema: Option<String>,
+        #[clap(long, help = "Longest validity in seconds an issuer may grant")]
+        max_validity: Option<u64>,
+    },
+    /// List issuer policies
+    List {
+        #[clap(long, help = "Only show this cooperative's policies")]
+        cooperative_id: Option<String>,
+    },
 }
 
 /// Credential issuance commands
@@ -118,6 +118,11 @@ enum Commands {
         #[clap(subcommand)]
         command: NetworkCommands,
     },
+    /// DIDComm messaging between DIDs
+    Didcomm {
+        #[clap(subcommand)]
+        command: DidcommCommands,
+    },
     /// Account queries
     Accounts {
         #[clap(subcommand)]
@@ -455,6 +460,545 @@ enum GovernanceCommands {
         #[clap(help = "ID of the proposal to retrieve")]
         id: String,
     },
+    /// Conviction voting for continuous budget requests
+    Conviction {
+        #[clap(subcommand)]
+        command: ConvictionCommands,
+    },
+    /// Delegate your vote to another member
+    Delegate {
+        #[clap(long, help = "DID delegating its vote")]
+        from: String,
+        #[clap(long, help = "DID receiving the delegation")]
+        to: String,
+        #[clap(
+            long,
+            default_value = "all",
+            help = "Scope: 'all', 'type:<proposal kind>' or 'tag:<topic>'"
+        )]
+        scope: String,
+        #[clap(long, help = "Unix timestamp after which the delegation lapses")]
+        expires_at: Option<u64>,
+    },
+    /// Revoke a vote delegation
+    Revoke {
+        #[clap(long, help = "DID whose delegation is revoked")]
+        from: String,
+        #[clap(long, help = "Only revoke the delegation in this scope")]
+        scope: Option<String>,
+    },
+    /// Attach topic tags to a proposal
+    Tag {
+        #[clap(help = "ID of the proposal to tag")]
+        id: String,
+        #[clap(help = "Topic tags", required = true)]
+        tags: Vec<String>,
+    },
+    /// Show the delegation graph, or how it applies to one proposal
+    Delegations {
+        #[clap(
+            long,
+            help = "Show delegations and effective weights for this proposal"
+        )]
+        proposal_id: Option<String>,
+    },
+    /// Revisions, amendments and comments while a proposal is deliberated
+    Deliberation {
+        #[clap(subcommand)]
+        command: DeliberationCommands,
+    },
+    /// Governance-controlled runtime parameters
+    Parameters {
+        #[clap(subcommand)]
+        command: ParameterCommands,
+    },
+    /// Coordinated software and contract upgrades
+    Upgrade {
+        #[clap(subcommand)]
+        command: UpgradeCommands,
+    },
+    /// Anonymous voting with zero-knowledge eligibility proofs
+    SecretBallot {
+        #[clap(subcommand)]
+        command: SecretBallotCommands,
+    },
+    /// Replay governance history under alternative rules
+    Simulate(SimulateArgs),
+    /// Show pending proposal deadlines enforced by the node
+    Timers,
+    /// Proposals ratified independently by several federations
+    CrossFederation {
+        #[clap(subcommand)]
+        command: CrossFederationCommands,
+    },
+    /// Inspect and amend the cooperative's constitution
+    Constitution {
+        #[clap(subcommand)]
+        command: ConstitutionCommands,
+    },
+    /// Due-process cases for suspending or recalling members and stewards
+    Case {
+        #[clap(subcommand)]
+        command: CaseCommands,
+    },
+    /// Weighted and multi-chamber voting rules
+    Weighting {
+        #[clap(subcommand)]
+        command: WeightingCommands,
+    },
+}
+
+#[derive(clap::Args, Debug)]
+struct SimulateArgs {
+    #[clap(
+        long,
+        conflicts_with = "synthetic",
+        help = "Governance event log (JSON lines, as written by the file event store)"
+    )]
+    events: Option<PathBuf>,
+    #[clap(
+        long,
+        help = "Generate a synthetic history recorded under the default rules"
+    )]
+    synthetic: bool,
+    #[clap(long = "member", help = "Member DID absent from the log (repeatable)")]
+    members: Vec<String>,
+    #[clap(long, help = "JSON array of delegations to resolve votes with")]
+    delegations: Option<PathBuf>,
+    #[clap(long, default_value_t = 1)]
+    quorum: usize,
+    #[clap(long, default_value_t = 0.5)]
+    threshold: f32,
+    #[clap(long, default_value_t = 1)]
+    min_sponsors: usize,
+    #[clap(
+        long,
+        default_value = "transitive",
+        help = "transitive, single-hop or disabled"
+    )]
+    delegation_rule: String,
+    #[clap(
+        long,
+        default_value = "threshold",
+        help = "threshold, threshold-of-cast or consensus"
+    )]
+    method: String,
+    #[clap(long, help = "Ignore quorum and threshold overrides set on proposals")]
+    ignore_proposal_overrides: bool,
+    #[clap(long, default_value_t = 50, help = "Synthetic members")]
+    synthetic_members: usize,
+    #[clap(long, default_value_t = 20, help = "Synthetic proposals")]
+    synthetic_proposals: usize,
+    #[clap(long, default_value_t = 0.6)]
+    turnout: f64,
+    #[clap(long, default_value_t = 0.55)]
+    support: f64,
+    #[clap(long, default_value_t = 0.2)]
+    delegation_rate: f64,
+    #[clap(long, default_value_t = 0)]
+    seed: u64,
+    #[clap(long, default_value = "json", help = "json or html")]
+    format: String,
+    #[clap(long, help = "Write the report here instead of stdout")]
+    output: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum CrossFederationCommands {
+    /// Draft and sign a joint proposal on behalf of the proposing federation
+    Draft {
+        #[clap(long, help = "ID of the proposing federation")]
+        federation: String,
+        #[clap(long)]
+        description: String,
+        #[clap(
+            long = "type",
+            help = "CrossFederationProposalType as JSON, e.g. {\"JointBudget\":{...}}"
+        )]
+        proposal_type: String,
+        #[clap(
+            long = "participant",
+            help = "Participating federation as id=did:key (repeatable)"
+        )]
+        participants: Vec<String>,
+        #[clap(
+            long = "require",
+            help = "Federation whose ratification is mandatory (repeatable, default: all)"
+        )]
+        required: Vec<String>,
+        #[clap(
+            long,
+            help = "Minimum number of ratifications (default: all participants)"
+        )]
+        min_ratifications: Option<usize>,
+        #[clap(long, help = "Unix time after which ratifications are refused")]
+        deadline: u64,
+        #[clap(long, help = "Proposing federation's Ed25519 private key (base58)")]
+        key_bs58: String,
+    },
+    /// Open this federation's vote on a signed joint proposal
+    Adopt {
+        #[clap(help = "Joint proposal JSON printed by `draft` (or '-' for stdin)")]
+        proposal_json_or_stdin: String,
+        #[clap(long, help = "Federation this node ratifies for")]
+        federation: String,
+        #[clap(long, help = "Length of the local vote in seconds")]
+        duration: u64,
+    },
+    /// Sign this federation's outcome once the local vote has closed
+    Ratify {
+        proposal_id: String,
+        #[clap(
+            long = "relay",
+            help = "Send the signed ratification to this node API URL (repeatable)"
+        )]
+        relay: Vec<String>,
+    },
+    /// Execute a ratified joint proposal
+    Execute { proposal_id: String },
+    /// Show the ratification progress of a joint proposal
+    Show { proposal_id: String },
+    /// List joint proposals adopted by this federation
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+enum ConstitutionCommands {
+    /// Show the constitution in force, or a past version
+    Show {
+        #[clap(long)]
+        version: Option<u32>,
+    },
+    /// List every adopted version
+    History,
+    /// Adopt the first constitution
+    Adopt {
+        #[clap(help = "Constitution JSON file (or '-' for stdin)")]
+        file_or_stdin: String,
+    },
+    /// Print the proposal input for amending the constitution to this document
+    Amend {
+        #[clap(help = "Constitution JSON file (or '-' for stdin)")]
+        file_or_stdin: String,
+    },
+    /// Show what changed between two versions
+    Diff {
+        from: u32,
+        #[clap(required_unless_present = "proposed")]
+        to: Option<u32>,
+        #[clap(
+            long,
+            conflicts_with = "to",
+            help = "Compare against a proposed document instead of an adopted version"
+        )]
+        proposed: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum CaseCommands {
+    /// File a signed complaint against a member or steward
+    File {
+        #[clap(long)]
+        subject: String,
+        #[clap(
+            long,
+            help = "Recall the subject from this office instead of membership"
+        )]
+        steward_of: Option<String>,
+        #[clap(long, help = "Violation type, e.g. ResourceAbuse, or free text")]
+        violation: String,
+        #[clap(long)]
+        description: String,
+        #[clap(long = "evidence", help = "CID of supporting evidence (repeatable)")]
+        evidence: Vec<String>,
+        #[clap(long, help = "Complainant's Ed25519 private key (base58)")]
+        key_bs58: String,
+    },
+    /// Respond to a complaint as its subject
+    Respond {
+        case_id: String,
+        #[clap(long)]
+        statement: String,
+        #[clap(long = "evidence", help = "CID of supporting evidence (repeatable)")]
+        evidence: Vec<String>,
+        #[clap(long, help = "Subject's Ed25519 private key (base58)")]
+        key_bs58: String,
+    },
+    /// Return a verdict as a panelist
+    Verdict {
+        case_id: String,
+        #[clap(
+            long,
+            required_unless_present = "dismiss",
+            help = "Uphold with: warning, suspension:<seconds> or removal"
+        )]
+        sanction: Option<String>,
+        #[clap(long, conflicts_with = "sanction")]
+        dismiss: bool,
+        #[clap(long)]
+        reasoning: String,
+        #[clap(long, help = "Panelist's Ed25519 private key (base58)")]
+        key_bs58: String,
+    },
+    /// Appeal a decision as a party to the case
+    Appeal {
+        case_id: String,
+        #[clap(long)]
+        grounds: String,
+        #[clap(long, help = "Appellant's Ed25519 private key (base58)")]
+        key_bs58: String,
+    },
+    /// Show a case and its record
+    Show { case_id: String },
+    /// List all cases
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+enum WeightingCommands {
+    /// Show trusted credential issuers and rules by proposal kind
+    Show,
+    /// Weight votes on a proposal kind
+    Set {
+        #[clap(long, help = "Proposal kind, e.g. budget_allocation, or * for all")]
+        kind: String,
+        #[clap(long, conflicts_with_all = ["size_cap", "custom"], help = "One vote per member")]
+        equal: bool,
+        #[clap(
+            long,
+            help = "Weight by membership size, capping each member at this share"
+        )]
+        size_cap: Option<f64>,
+        #[clap(long, help = "Strategy registered with the node under this name")]
+        custom: Option<String>,
+        #[clap(
+            long = "chamber",
+            help = "Chamber that must approve, as name:threshold[:min_voters] (repeatable)"
+        )]
+        chambers: Vec<String>,
+    },
+    /// Go back to one vote per member for a proposal kind
+    Clear {
+        #[clap(long)]
+        kind: String,
+    },
+    /// Size a member from its registered cooperative profile
+    Profile { did: String },
+    /// Record chambers and size from a membership credential
+    Credential {
+        #[clap(help = "Credential JSON file, or '-' for stdin")]
+        credential_json_or_stdin: String,
+    },
+    /// Show a member's size and chambers
+    Member { did: String },
+    /// Weighted and per-chamber totals for a proposal
+    Tally { proposal_id: String },
+}
+
+#[derive(Subcommand, Debug)]
+enum SecretBallotCommands {
+    /// Generate membership circuit keys and print the verifying key and the
+    /// hash governance must approve
+    Setup {
+        #[clap(long, default_value_t = 8, help = "Depth of the voter commitment tree")]
+        depth: usize,
+        #[clap(long, help = "Where to write the proving key")]
+        proving_key: PathBuf,
+    },
+    /// Generate a voter secret and register its commitment for the node's member
+    Register {
+        #[clap(help = "DID of the member; must be the node's identity")]
+        did: String,
+    },
+    /// Switch a proposal to secret ballots before voting opens
+    Enable {
+        proposal_id: String,
+        #[clap(long, help = "Hex encoded verifying key from `setup`")]
+        verifying_key: String,
+        #[clap(long, default_value_t = 8)]
+        depth: usize,
+        #[clap(long, help = "Unix time at which the reveal phase starts")]
+        reveal_from: u64,
+    },
+    /// Prove eligibility and submit a committed ballot; prints the reveal to keep
+    Cast {
+        proposal_id: String,
+        #[clap(help = "yes, no or abstain")]
+        option: String,
+        #[clap(long, help = "Voter secret printed by `register`")]
+        secret: String,
+        #[clap(long, help = "Proving key written by `setup`")]
+        proving_key: PathBuf,
+    },
+    /// Open a committed ballot during the reveal phase
+    Reveal {
+        #[clap(help = "Reveal JSON printed by `cast` (or '-' for stdin)")]
+        reveal_json_or_stdin: String,
+    },
+    /// Show ballots, reveals and the tally of a proposal
+    Show { proposal_id: String },
+    /// Recompute the tally locally from the anchored ballots and reveals
+    Verify { proposal_id: String },
+}
+
+#[derive(Subcommand, Debug)]
+enum UpgradeCommands {
+    /// Show the active protocol version and scheduled upgrades of each node
+    Status {
+        #[clap(long = "node", help = "Additional node API URL to query (repeatable)")]
+        nodes: Vec<String>,
+    },
+    /// Signal that the node is ready for a scheduled upgrade
+    Ready {
+        #[clap(help = "ID of the executed upgrade proposal")]
+        proposal_id: String,
+        #[clap(
+            long = "relay",
+            help = "Forward the signed signal to this node API URL (repeatable)"
+        )]
+        relay: Vec<String>,
+    },
+    /// Build and sign an upgrade manifest to submit as a SoftwareUpgrade proposal
+    Manifest {
+        #[clap(long, help = "Release version, e.g. 0.3.0")]
+        version: String,
+        #[clap(long, help = "Protocol version enabled at activation")]
+        protocol_version: u32,
+        #[clap(long, help = "Path to the release artifact")]
+        artifact: PathBuf,
+        #[clap(
+            long,
+            conflicts_with = "activate_time",
+            help = "Activate at this height"
+        )]
+        activate_height: Option<u64>,
+        #[clap(long, help = "Activate at this unix timestamp")]
+        activate_time: Option<u64>,
+        #[clap(
+            long = "contract",
+            help = "CCL contract to migrate, as name=path (repeatable)"
+        )]
+        contracts: Vec<String>,
+        #[clap(long, help = "Target CCL version for migrated contracts")]
+        ccl_version: Option<String>,
+        #[clap(
+            long,
+            help = "Publisher Ed25519 private key (base58); its did:key must be a trusted release signer"
+        )]
+        key_bs58: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ParameterCommands {
+    /// List parameters with their types, bounds and current values
+    List,
+    /// Show executed parameter changes
+    History {
+        #[clap(long, help = "Only show changes to this parameter")]
+        name: Option<String>,
+    },
+    /// Propose undoing a parameter change
+    Rollback {
+        #[clap(help = "Version of the change to undo")]
+        version: u64,
+        #[clap(long, help = "Voting period of the rollback proposal in seconds")]
+        duration_secs: Option<u64>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum DeliberationCommands {
+    /// Anchor the submitted text of a proposal as its first revision
+    Start {
+        #[clap(help = "ID of the proposal")]
+        id: String,
+    },
+    /// Show revisions, amendments and the bound revision of a proposal
+    Show {
+        #[clap(help = "ID of the proposal")]
+        id: String,
+    },
+    /// Publish a new revision of your proposal
+    Revise {
+        #[clap(help = "ID of the proposal")]
+        id: String,
+        #[clap(long, help = "Revised description")]
+        description: String,
+        #[clap(long, help = "Revised proposal body")]
+        body: Option<String>,
+    },
+    /// Propose an amendment against the current revision
+    Amend {
+        #[clap(help = "ID of the proposal")]
+        id: String,
+        #[clap(long, help = "Amended description")]
+        description: String,
+        #[clap(long, help = "Amended proposal body")]
+        body: Option<String>,
+        #[clap(long, default_value = "", help = "Why the change is needed")]
+        rationale: String,
+    },
+    /// Vote on an open amendment
+    VoteAmendment {
+        #[clap(help = "ID of the proposal")]
+        id: String,
+        #[clap(help = "ID of the amendment")]
+        amendment_id: String,
+        #[clap(help = "yes, no or abstain")]
+        vote: String,
+    },
+    /// Tally open amendments and apply the adopted ones
+    Resolve {
+        #[clap(help = "ID of the proposal")]
+        id: String,
+    },
+    /// Comment on a proposal revision
+    Comment {
+        #[clap(help = "ID of the proposal")]
+        id: String,
+        #[clap(help = "Comment text")]
+        text: String,
+        #[clap(long, help = "Revision CID (defaults to the latest revision)")]
+        revision: Option<String>,
+        #[clap(long, help = "CID of the comment being replied to")]
+        reply_to: Option<String>,
+    },
+    /// Show the threaded discussion of a proposal
+    Comments {
+        #[clap(help = "ID of the proposal")]
+        id: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ConvictionCommands {
+    /// Submit a budget request decided by conviction voting
+    Submit {
+        #[clap(long, help = "DID receiving the funds")]
+        beneficiary: String,
+        #[clap(long, help = "Requested amount of mana")]
+        amount: u64,
+        #[clap(long, help = "Purpose of the request")]
+        description: String,
+    },
+    /// Stake support on a proposal (0 withdraws support)
+    Stake {
+        #[clap(long, help = "DID staking support")]
+        staker: String,
+        #[clap(long, help = "ID of the conviction proposal")]
+        proposal_id: String,
+        #[clap(long, help = "Support units to stake")]
+        amount: u64,
+    },
+    /// Show the current conviction of a proposal
+    Status {
+        #[clap(help = "ID of the conviction proposal")]
+        id: String,
+    },
+    /// Advance conviction and execute proposals that crossed their threshold
+    Process,
 }
 
 #[derive(Subcommand, Debug)]
@@ -517,6 +1061,77 @@ enum NetworkCommands {
     Peers,
 }
 
+#[derive(Subcommand, Debug)]
+enum DidcommCommands {
+    /// Send a message from the node's DID
+    Send {
+        #[clap(help = "Recipient DID")]
+        to: String,
+        #[clap(long = "type", help = "Message type URI")]
+        message_type: String,
+        #[clap(
+            long,
+            default_value = "{}",
+            help = "Message body JSON or '-' for stdin"
+        )]
+        body: String,
+        #[clap(long, help = "Thread the message replies to")]
+        thid: Option<String>,
+        #[clap(long, help = "Encrypt without revealing the node's DID")]
+        anonymous: bool,
+        #[clap(long, help = "Mediator DID to route through")]
+        mediator: Option<String>,
+    },
+    /// List messages received by the node
+    Messages,
+    /// List DIDs the node mediates for
+    Mediated,
+    /// Ask a mediator to route messages for the node
+    Mediate {
+        #[clap(help = "Mediator DID")]
+        mediator: String,
+    },
+    /// Let a DID request mediation from the node
+    AllowMediation {
+        #[clap(help = "DID allowed to request mediation")]
+        did: String,
+    },
+    /// Pick up messages queued at the mediator
+    Pickup {
+        #[clap(help = "Mediator DID")]
+        mediator: String,
+        #[clap(long, help = "Maximum number of messages")]
+        limit: Option<usize>,
+    },
+    /// Offer a credential to a holder
+    Offer {
+        #[clap(help = "Holder DID")]
+        holder: String,
+        #[clap(long = "type", help = "Credential type")]
+        credential_type: String,
+        #[clap(long = "claim", help = "Claim as name=value (repeatable)")]
+        claims: Vec<String>,
+        #[clap(long)]
+        comment: Option<String>,
+        #[clap(long, help = "Mediator DID to route through")]
+        mediator: Option<String>,
+    },
+    /// Request the credential of an offer in the inbox
+    Request {
+        #[clap(help = "Id of the offer message")]
+        offer_id: String,
+    },
+    /// Invite members to vote on a proposal
+    Invite {
+        #[clap(help = "Proposal ID")]
+        proposal_id: String,
+        #[clap(long = "invitee", help = "Invitee DID (repeatable)")]
+        invitees: Vec<String>,
+        #[clap(long)]
+        comment: Option<String>,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum CclCommands {
     /// Compile a CCL source file
@@ -582,6 +1197,78 @@ enum FederationCommands {
         #[clap(subcommand)]
         command: FederationDidCommands,
     },
+    /// Apply for membership of a federation and track applications
+    Application {
+        #[clap(subcommand)]
+        command: FederationApplicationCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum FederationApplicationCommands {
+    /// Draft and sign a membership application for the applying cooperative
+    Draft {
+        #[clap(long, help = "Federation to join")]
+        federation: String,
+        #[clap(long, help = "Name of the applying cooperative")]
+        name: String,
+        #[clap(long)]
+        statement: String,
+        #[clap(long, help = "Peer address of the cooperative's node")]
+        node_peer: Option<String>,
+        #[clap(
+            long = "credential",
+            help = "Credential JSON file held by the cooperative (repeatable)"
+        )]
+        credentials: Vec<PathBuf>,
+        #[clap(
+            long = "attestation",
+            help = "Attestation JSON file from an existing member (repeatable)"
+        )]
+        attestations: Vec<PathBuf>,
+        #[clap(long, help = "Cooperative's Ed25519 private key (base58)")]
+        key_bs58: String,
+    },
+    /// Vouch for an applicant as an existing member
+    Attest {
+        #[clap(long)]
+        federation: String,
+        #[clap(long, help = "DID of the applying cooperative")]
+        applicant: String,
+        #[clap(long, help = "Trust level: Full, Partial, Basic or None")]
+        level: String,
+        #[clap(long, help = "Trust context, e.g. governance")]
+        context: String,
+        #[clap(long, help = "Member's Ed25519 private key (base58)")]
+        key_bs58: String,
+    },
+    /// Submit a signed application to the federation's node
+    Submit {
+        #[clap(help = "Application JSON printed by `draft` (or '-' for stdin)")]
+        application_json_or_stdin: String,
+    },
+    /// Answer every open challenge of an application
+    Answer {
+        application_id: String,
+        #[clap(long, help = "Cooperative's Ed25519 private key (base58)")]
+        key_bs58: String,
+    },
+    /// Show the status of an application
+    Status {
+        application_id: String,
+        #[clap(
+            long,
+            help = "Once accepted, add the federation membership to this DID document JSON file"
+        )]
+        did_document: Option<PathBuf>,
+    },
+    /// List applications received by the federation
+    List,
+    /// Set the trust gate applicants must pass
+    Gate {
+        #[clap(help = "FederationMembershipTrustGate JSON (or '-' for stdin)")]
+        gate_json_or_stdin: String,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -855,6 +1542,80 @@ enum TokenCommands {
 enum KeyCommands {
     /// Show node DID and public key
     Show,
+    /// Generate an Ed25519 key in a keystore or inside a PKCS#11 token
+    Generate {
+        #[clap(long, help = "Label for the new key")]
+        label: String,
+        #[clap(flatten)]
+        backend: KeyBackendArgs,
+    },
+    /// List the Ed25519 keys in a keystore or PKCS#11 token
+    List {
+        #[clap(flatten)]
+        backend: KeyBackendArgs,
+    },
+    /// Move a plaintext or legacy encrypted key file into a keystore
+    Migrate {
+        #[clap(long, help = "Plaintext base58 or legacy encrypted key file")]
+        from: PathBuf,
+        #[clap(long, help = "Keystore to add the key to (created if missing)")]
+        keystore: PathBuf,
+        #[clap(long, default_value = icn_identity::DEFAULT_KEY_LABEL)]
+        label: String,
+        #[clap(
+            long,
+            default_value = "ICN_KEY_PASSPHRASE",
+            help = "Env var holding the keystore passphrase"
+        )]
+        passphrase_env: String,
+        #[clap(
+            long,
+            help = "Env var holding the passphrase of a legacy encrypted file"
+        )]
+        legacy_passphrase_env: Option<String>,
+        #[clap(long, help = "Keep the source key file instead of deleting it")]
+        keep_source: bool,
+    },
+    /// Change the passphrase of a keystore
+    Passwd {
+        #[clap(long)]
+        keystore: PathBuf,
+        #[clap(
+            long,
+            default_value = "ICN_KEY_PASSPHRASE",
+            help = "Env var holding the current passphrase"
+        )]
+        passphrase_env: String,
+        #[clap(
+            long,
+            default_value = "ICN_NEW_KEY_PASSPHRASE",
+            help = "Env var holding the new passphrase"
+        )]
+        new_passphrase_env: String,
+    },
+}
+
+/// Where `keys` commands find their keys: a keystore file or a PKCS#11 token.
+#[derive(clap::Args, Debug)]
+struct KeyBackendArgs {
+    #[clap(long, help = "Encrypted keystore file")]
+    keystore: Option<PathBuf>,
+    #[clap(
+        long,
+        default_value = "ICN_KEY_PASSPHRASE",
+        help = "Env var holding the keystore passphrase"
+    )]
+    passphrase_env: String,
+    #[clap(long, env = "ICN_HSM_LIBRARY", help = "PKCS#11 module path")]
+    hsm_library: Option<PathBuf>,
+    #[clap(long, help = "Token slot (defaults to the first initialized token)")]
+    slot: Option<u64>,
+    #[clap(
+        long,
+        default_value = "ICN_HSM_PIN",
+        help = "Env var holding the user PIN"
+    )]
+    pin_env: String,
 }
 
 #[derive(Subcommand, Debug)]
@@ -920,8 +1681,127 @@ enum IdentityCommands {
         #[clap(help = "ZkCredentialProof JSON or '-' for stdin")]
         proof_json_or_stdin: String,
     },
-}
-
+    /// Manage did:icn identifiers, which keep their DID across key rotations
+    #[clap(name = "did-icn")]
+    DidIcn {
+        #[clap(subcommand)]
+        command: DidIcnCommands,
+    },
+    /// Recover a did:icn identifier through guardians
+    Recovery {
+        #[clap(subcommand)]
+        command: RecoveryCommands,
+    },
+    /// Vouch for new members and check their proof of personhood
+    Personhood {
+        #[clap(subcommand)]
+        command: PersonhoodCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum DidIcnCommands {
+    /// Create a did:icn identifier with a fresh signing key and next key
+    Incept,
+    /// Rotate to the committed next key and commit to a new one
+    Rotate {
+        did: String,
+        #[clap(long, help = "Committed next key (base58 private key)")]
+        next_key_bs58: String,
+    },
+    /// Retire the identifier using the committed next key
+    Deactivate {
+        did: String,
+        #[clap(long, help = "Committed next key (base58 private key)")]
+        next_key_bs58: String,
+    },
+    /// Show the key state and event log of an identifier
+    Show { did: String },
+}
+
+#[derive(Subcommand, Debug)]
+enum RecoveryCommands {
+    /// Split the committed next key among guardians
+    Setup {
+        did: String,
+        #[clap(long, help = "Current signing key (base58 private key)")]
+        key_bs58: String,
+        #[clap(long, help = "Committed next key (base58 private key)")]
+        next_key_bs58: String,
+        #[clap(long = "guardian", required = true, help = "Guardian DID (repeatable)")]
+        guardians: Vec<String>,
+        #[clap(long)]
+        threshold: u8,
+        #[clap(long, default_value_t = icn_identity::social_recovery::DEFAULT_RECOVERY_DELAY_SECONDS)]
+        delay_seconds: u64,
+    },
+    /// Start recovering an identifier whose keys were lost
+    Open {
+        did: String,
+        #[clap(long, help = "Guardian signing the request")]
+        guardian: Option<String>,
+        #[clap(
+            long,
+            help = "Guardian signing key, or the current key without --guardian (base58 private key)"
+        )]
+        key_bs58: String,
+    },
+    /// Approve a recovery as a guardian
+    Approve {
+        case_id: String,
+        #[clap(long)]
+        guardian: String,
+        #[clap(long, help = "Guardian signing key (base58 private key)")]
+        key_bs58: String,
+    },
+    /// Cancel a recovery with the identifier's current key
+    Cancel {
+        case_id: String,
+        #[clap(long, help = "Current signing key (base58 private key)")]
+        key_bs58: String,
+    },
+    /// Rebuild the next key from released shares and rotate to it
+    Complete {
+        case_id: String,
+        #[clap(long, help = "Session key printed by `open` (base58 private key)")]
+        session_key_bs58: String,
+    },
+    /// Show the approvals and status of a recovery
+    Show { case_id: String },
+}
+
+#[derive(Subcommand, Debug)]
+enum PersonhoodCommands {
+    /// Trust a DID as a verified person anchoring Sybil detection
+    Seed { did: String },
+    /// Vouch that a DID is a unique person, staking mana or reputation
+    Vouch {
+        vouchee: String,
+        #[clap(long)]
+        voucher: String,
+        #[clap(long, help = "Voucher signing key (base58 private key)")]
+        key_bs58: String,
+        #[clap(long, conflicts_with = "reputation", help = "Mana to escrow")]
+        mana: Option<u64>,
+        #[clap(long, help = "Reputation to put at risk")]
+        reputation: Option<u64>,
+        #[clap(long, default_value_t = icn_identity::DEFAULT_VOUCH_TTL_SECONDS)]
+        ttl_seconds: u64,
+    },
+    /// Show the personhood status, proof and vouches of a DID
+    Status { did: String },
+    /// Re-run Sybil detection and re-verify personhood
+    Evaluate,
+    /// Revoke a Sybil's personhood, forfeiting the stakes vouching for it
+    Revoke {
+        did: String,
+        #[clap(long)]
+        reason: String,
+    },
+    /// Issue a personhood credential to a verified person
+    Credential { did: String },
+}
+
 #[derive(Subcommand, Debug)]
 enum ZkCommands {
     /// Generate a Groth16 proving key and sign the verifying key
@@ -1088,6 +1968,62 @@ async fn run_command(cli: &Cli, client: &Client) -> Result<(), anyhow::Error> {
             GovernanceCommands::Tally { id } => handle_gov_tally(cli, client, id).await?,
             GovernanceCommands::Proposals => handle_gov_list_proposals(cli, client).await?,
             GovernanceCommands::Proposal { id } => handle_gov_get_proposal(cli, client, id).await?,
+            GovernanceCommands::Conviction { command } => match command {
+                ConvictionCommands::Submit {
+                    beneficiary,
+                    amount,
+                    description,
+                } => {
+                    handle_gov_conviction_submit(cli, client, beneficiary, *amount, description)
+                        .await?
+                }
+                ConvictionCommands::Stake {
+                    staker,
+                    proposal_id,
+                    amount,
+                } => handle_gov_conviction_stake(cli, client, staker, proposal_id, *amount).await?,
+                ConvictionCommands::Status { id } => {
+                    handle_gov_conviction_status(cli, client, id).await?
+                }
+                ConvictionCommands::Process => handle_gov_conviction_process(cli, client).await?,
+            },
+            GovernanceCommands::Delegate {
+                from,
+                to,
+                scope,
+                expires_at,
+            } => handle_gov_delegate(cli, client, from, to, scope, *expires_at).await?,
+            GovernanceCommands::Revoke { from, scope } => {
+                handle_gov_revoke(cli, client, from, scope.as_deref()).await?
+            }
+            GovernanceCommands::Tag { id, tags } => handle_gov_tag(cli, client, id, tags).await?,
+            GovernanceCommands::Delegations { proposal_id } => {
+                handle_gov_delegations(cli, client, proposal_id.as_deref()).await?
+            }
+            GovernanceCommands::Deliberation { command } => {
+                handle_gov_deliberation(cli, client, command).await?
+            }
+            GovernanceCommands::Parameters { command } => {
+                handle_gov_parameters(cli, client, command).await?
+            }
+            GovernanceCommands::Upgrade { command } => {
+                handle_gov_upgrade(cli, client, command).await?
+            }
+            GovernanceCommands::SecretBallot { command } => {
+                handle_gov_secret_ballot(cli, client, command).await?
+            }
+            GovernanceCommands::Simulate(args) => handle_gov_simulate(args)?,
+            GovernanceCommands::Timers => handle_gov_timers(cli, client).await?,
+            GovernanceCommands::CrossFederation { command } => {
+                handle_gov_cross_federation(cli, client, command).await?
+            }
+            GovernanceCommands::Constitution { command } => {
+                handle_gov_constitution(cli, client, command).await?
+            }
+            GovernanceCommands::Case { command } => handle_gov_case(cli, client, command).await?,
+            GovernanceCommands::Weighting { command } => {
+                handle_gov_weighting(cli, client, command).await?
+            }
         },
         Commands::Mesh { command } => match command {
             MeshCommands::Jobs => handle_mesh_jobs(cli, client).await?,
@@ -1110,6 +2046,7 @@ async fn run_command(cli: &Cli, client: &Client) -> Result<(), anyhow::Error> {
             NetworkCommands::Ping { peer_id } => handle_network_ping(cli, client, peer_id).await?,
             NetworkCommands::Peers => handle_network_peers(cli, client).await?,
         },
+        Commands::Didcomm { command } => handle_didcomm(cli, client, command).await?,
         Commands::Accounts { command } => match command {
             AccountCommands::Balance { did } => handle_account_balance(cli, client, did).await?,
         },
@@ -1147,6 +2084,28 @@ async fn run_command(cli: &Cli, client: &Client) -> Result<(), anyhow::Error> {
         },
         Commands::Keys { command } => match command {
             KeyCommands::Show => handle_keys_show(cli, client).await?,
+            KeyCommands::Generate { label, backend } => handle_keys_generate(label, backend)?,
+            KeyCommands::List { backend } => handle_keys_list(backend)?,
+            KeyCommands::Migrate {
+                from,
+                keystore,
+                label,
+                passphrase_env,
+                legacy_passphrase_env,
+                keep_source,
+            } => handle_keys_migrate(
+                from,
+                keystore,
+                label,
+                passphrase_env,
+                legacy_passphrase_env.as_deref(),
+                *keep_source,
+            )?,
+            KeyCommands::Passwd {
+                keystore,
+                passphrase_env,
+                new_passphrase_env,
+            } => handle_keys_passwd(keystore, passphrase_env, new_passphrase_env)?,
         },
         Commands::Reputation { command } => match command {
             ReputationCommands::Get { did } => handle_reputation_get(cli, client, did).await?,
@@ -1205,6 +2164,15 @@ async fn run_command(cli: &Cli, client: &Client) -> Result<(), anyhow::Error> {
             } => {
                 handle_identity_verify_remote(cli, client, proof_json_or_stdin).await?;
             }
+            IdentityCommands::DidIcn { command } => {
+                handle_identity_did_icn(cli, client, command).await?;
+            }
+            IdentityCommands::Recovery { command } => {
+                handle_identity_recovery(cli, client, command).await?;
+            }
+            IdentityCommands::Personhood { command } => {
+                handle_identity_personhood(cli, client, command).await?;
+            }
         },
         Commands::Ccl { command } => match command {
             CclCommands::Compile { file } => handle_ccl_compile(file)?,
@@ -1341,6 +2309,9 @@ async fn run_command(cli: &Cli, client: &Client) -> Result<(), anyhow::Error> {
                     handle_fed_did_resolve(cli, client, did, federation).await?
                 }
             },
+            FederationCommands::Application { command } => {
+                handle_fed_application(cli, client, command).await?
+            }
         },
         Commands::Aid { command } => match command {
             AidCommands::List => handle_aid_list(cli, client).await?,
@@ -1515,9 +2486,54 @@ async fn run_command(cli: &Cli, client: &Client) -> Result<(), anyhow::Error> {
             credential_lifecycle::CredentialLifecycleCommands::Revoke { cid, reason } => {
                 handle_credential_revoke(cli, client, cid, reason).await?
             }
+            credential_lifecycle::CredentialLifecycleCommands::Suspend { cid } => {
+                handle_credential_set_status(cli, client, cid, "suspend").await?
+            }
+            credential_lifecycle::CredentialLifecycleCommands::Reinstate { cid } => {
+                handle_credential_set_status(cli, client, cid, "reinstate").await?
+            }
+            credential_lifecycle::CredentialLifecycleCommands::StatusList { id, purpose } => {
+                handle_credential_status_list(cli, client, id, purpose).await?
+            }
+            credential_lifecycle::CredentialLifecycleCommands::Export { cid, format } => {
+                handle_credential_export(cli, client, cid, format).await?
+            }
+            credential_lifecycle::CredentialLifecycleCommands::Import { input, format } => {
+                handle_credential_import(cli, client, input, format).await?
+            }
+            credential_lifecycle::CredentialLifecycleCommands::Schema { command } => {
+                handle_credential_schema(cli, client, command).await?
+            }
+            credential_lifecycle::CredentialLifecycleCommands::Policy { command } => {
+                handle_credential_policy(cli, client, command).await?
+            }
+            credential_lifecycle::CredentialLifecycleCommands::Offer {
+                holder,
+                schema,
+                credential_type,
+                claims,
+                cooperative,
+                validity,
+            } => {
+                let terms = icn_identity::OfferTerms {
+                    holder: Did::from_str(holder)?,
+                    schema: icn_common::parse_cid_from_string(schema)?,
+                    credential_type: credential_type.clone(),
+                    claims: parse_claims(claims)?,
+                    cooperative_id: cooperative.clone(),
+                    validity: *validity,
+                };
+                handle_credential_offer(cli, client, &terms).await?
+            }
+            credential_lifecycle::CredentialLifecycleCommands::Accept { offer_id, key_bs58 } => {
+                handle_credential_accept(cli, client, offer_id, key_bs58).await?
+            }
             credential_lifecycle::CredentialLifecycleCommands::Example { flow } => {
                 handle_credential_example(cli, client, flow).await?
             }
+            credential_lifecycle::CredentialLifecycleCommands::Bbs { command } => {
+                handle_credential_bbs(cli, client, command).await?
+            }
         },
         Commands::Qr { command } => match command {
             QrCommands::ShareIdentity { did, size, output } => {
@@ -1979,122 +2995,1448 @@ async fn handle_gov_get_proposal(
     Ok(())
 }
 
-async fn handle_mesh_jobs(cli: &Cli, client: &Client) -> Result<(), anyhow::Error> {
-    let response: serde_json::Value =
-        get_request(&cli.api_url, client, "/mesh/jobs", cli.api_key.as_deref()).await?;
-    println!("{}", serde_json::to_string_pretty(&response)?);
+async fn handle_gov_conviction_submit(
+    cli: &Cli,
+    client: &Client,
+    beneficiary: &str,
+    amount: u64,
+    description: &str,
+) -> Result<(), anyhow::Error> {
+    let req = icn_api::governance_trait::SubmitConvictionProposalRequest {
+        beneficiary_did: beneficiary.to_string(),
+        amount,
+        description: description.to_string(),
+    };
+    let id: ProposalId = post_request(
+        &cli.api_url,
+        client,
+        "/governance/conviction/submit",
+        &req,
+        cli.api_key.as_deref(),
+    )
+    .await?;
+    println!("Submitted conviction proposal: {}", id);
     Ok(())
 }
 
-async fn handle_mesh_status(cli: &Cli, client: &Client, job_id: &str) -> Result<(), anyhow::Error> {
-    let path = format!("/mesh/jobs/{}", job_id);
-    let response: serde_json::Value =
+async fn handle_gov_conviction_stake(
+    cli: &Cli,
+    client: &Client,
+    staker: &str,
+    proposal_id: &str,
+    amount: u64,
+) -> Result<(), anyhow::Error> {
+    let req = icn_api::governance_trait::ConvictionStakeRequest {
+        staker_did: staker.to_string(),
+        proposal_id: proposal_id.to_string(),
+        amount,
+    };
+    let status: icn_api::governance_trait::ConvictionStatusResponse = post_request(
+        &cli.api_url,
+        client,
+        "/governance/conviction/stake",
+        &req,
+        cli.api_key.as_deref(),
+    )
+    .await?;
+    println!("{}", serde_json::to_string_pretty(&status)?);
+    Ok(())
+}
+
+async fn handle_gov_conviction_status(
+    cli: &Cli,
+    client: &Client,
+    proposal_id: &str,
+) -> Result<(), anyhow::Error> {
+    let path = format!("/governance/conviction/{}", proposal_id);
+    let status: icn_api::governance_trait::ConvictionStatusResponse =
         get_request(&cli.api_url, client, &path, cli.api_key.as_deref()).await?;
-    println!("{}", serde_json::to_string_pretty(&response)?);
+    println!("{}", serde_json::to_string_pretty(&status)?);
     Ok(())
 }
 
-/// Submit a new mesh job to the node
-async fn handle_mesh_submit(
+async fn handle_gov_delegate(
     cli: &Cli,
     client: &Client,
-    job_request_json_or_stdin: &str,
+    from: &str,
+    to: &str,
+    scope: &str,
+    expires_at: Option<u64>,
 ) -> Result<(), anyhow::Error> {
-    let job_json_content = if job_request_json_or_stdin == "-" {
-        let mut buffer = String::new();
-        io::stdin().read_to_string(&mut buffer)?;
-        buffer
-    } else {
-        job_request_json_or_stdin.to_string()
+    let req = icn_api::governance_trait::DelegateRequest {
+        from_did: from.to_string(),
+        to_did: to.to_string(),
+        scope: Some(scope.to_string()),
+        expires_at,
     };
-
-    let mut request_value: serde_json::Value = serde_json::from_str(&job_json_content)
-        .map_err(|e| anyhow::anyhow!("Invalid mesh job JSON: {}", e))?;
-
-    if let Some(manifest_path) = request_value
-        .get("manifest_cid")
-        .and_then(|v| v.as_str())
-        .filter(|s| s.ends_with(".ccl"))
-    {
-        let path = PathBuf::from(manifest_path);
-        let (wasm, _meta) = compile_ccl_file_to_wasm(&path).map_err(anyhow::Error::msg)?;
-        let payload = DagBlockPayload { data: wasm };
-        let cid: Cid = post_request(
-            &cli.api_url,
-            client,
-            "/dag/put",
-            &payload,
-            cli.api_key.as_deref(),
-        )
-        .await?;
-
-        if let Some(obj) = request_value.as_object_mut() {
-            obj.insert(
-                "manifest_cid".to_string(),
-                serde_json::json!(cid.to_string()),
-            );
-            if !obj.contains_key("spec_bytes") {
-                let spec = icn_mesh::JobSpec {
-                    kind: icn_mesh::JobKind::CclWasm,
-                    ..Default::default()
-                };
-                let bytes = bincode::serialize(&spec).unwrap();
-                obj.insert(
-                    "spec_bytes".to_string(),
-                    serde_json::json!(base64::engine::general_purpose::STANDARD.encode(bytes)),
-                );
-            }
-        }
-    }
-
-    let response: serde_json::Value = post_request(
+    let response: String = post_request(
         &cli.api_url,
         client,
-        "/mesh/submit",
-        &request_value,
+        "/governance/delegate",
+        &req,
         cli.api_key.as_deref(),
     )
     .await?;
-    println!("{}", serde_json::to_string_pretty(&response)?);
+    println!("{}", response);
     Ok(())
 }
 
-/// Get progress information for a mesh job
-async fn handle_mesh_progress(
+async fn handle_gov_revoke(
     cli: &Cli,
     client: &Client,
-    job_id: &str,
+    from: &str,
+    scope: Option<&str>,
 ) -> Result<(), anyhow::Error> {
-    let path = format!("/mesh/jobs/{}/progress", job_id);
-    let response: serde_json::Value =
-        get_request(&cli.api_url, client, &path, cli.api_key.as_deref()).await?;
+    let req = icn_api::governance_trait::RevokeDelegationRequest {
+        from_did: from.to_string(),
+        scope: scope.map(str::to_string),
+    };
+    let response: String = post_request(
+        &cli.api_url,
+        client,
+        "/governance/revoke",
+        &req,
+        cli.api_key.as_deref(),
+    )
+    .await?;
+    println!("{}", response);
+    Ok(())
+}
 
-    // Pretty print progress information
-    if let Some(progress) = response.get("progress") {
-        println!("Job Progress for: {}", job_id);
-        println!("================");
+async fn handle_gov_tag(
+    cli: &Cli,
+    client: &Client,
+    proposal_id: &str,
+    tags: &[String],
+) -> Result<(), anyhow::Error> {
+    let req = icn_api::governance_trait::TagProposalRequest {
+        proposal_id: proposal_id.to_string(),
+        tags: tags.to_vec(),
+    };
+    let response: String = post_request(
+        &cli.api_url,
+        client,
+        "/governance/tag",
+        &req,
+        cli.api_key.as_deref(),
+    )
+    .await?;
+    println!("{}", response);
+    Ok(())
+}
 
-        if let Some(stage) = progress.get("current_stage") {
-            println!("Current Stage: {}", stage.as_str().unwrap_or("unknown"));
+async fn handle_gov_deliberation(
+    cli: &Cli,
+    client: &Client,
+    command: &DeliberationCommands,
+) -> Result<(), anyhow::Error> {
+    use icn_api::governance_trait::{
+        AmendmentVoteRequest, CommentRequest, ProposeAmendmentRequest, ReviseProposalRequest,
+    };
+    let api_key = cli.api_key.as_deref();
+    let response: serde_json::Value = match command {
+        DeliberationCommands::Start { id } => {
+            let path = format!("/governance/deliberation/{}/start", id);
+            post_request(&cli.api_url, client, &path, &serde_json::json!({}), api_key).await?
         }
-
-        if let Some(percent) = progress.get("progress_percent") {
-            println!("Progress: {:.1}%", percent.as_f64().unwrap_or(0.0));
+        DeliberationCommands::Show { id } => {
+            let path = format!("/governance/deliberation/{}", id);
+            get_request(&cli.api_url, client, &path, api_key).await?
+        }
+        DeliberationCommands::Revise {
+            id,
+            description,
+            body,
+        } => {
+            let req = ReviseProposalRequest {
+                proposal_id: id.clone(),
+                description: description.clone(),
+                body: body.as_ref().map(|b| b.as_bytes().to_vec()),
+            };
+            post_request(&cli.api_url, client, "/governance/revise", &req, api_key).await?
+        }
+        DeliberationCommands::Amend {
+            id,
+            description,
+            body,
+            rationale,
+        } => {
+            let req = ProposeAmendmentRequest {
+                proposal_id: id.clone(),
+                description: description.clone(),
+                body: body.as_ref().map(|b| b.as_bytes().to_vec()),
+                rationale: rationale.clone(),
+            };
+            post_request(
+                &cli.api_url,
+                client,
+                "/governance/amendments",
+                &req,
+                api_key,
+            )
+            .await?
+        }
+        DeliberationCommands::VoteAmendment {
+            id,
+            amendment_id,
+            vote,
+        } => {
+            let req = AmendmentVoteRequest {
+                proposal_id: id.clone(),
+                amendment_id: amendment_id.clone(),
+                vote_option: vote.clone(),
+            };
+            post_request(
+                &cli.api_url,
+                client,
+                "/governance/amendments/vote",
+                &req,
+                api_key,
+            )
+            .await?
+        }
+        DeliberationCommands::Resolve { id } => {
+            let path = format!("/governance/amendments/{}/resolve", id);
+            post_request(&cli.api_url, client, &path, &serde_json::json!({}), api_key).await?
+        }
+        DeliberationCommands::Comment {
+            id,
+            text,
+            revision,
+            reply_to,
+        } => {
+            let req = CommentRequest {
+                proposal_id: id.clone(),
+                revision: revision.clone(),
+                parent: reply_to.clone(),
+                text: text.clone(),
+            };
+            post_request(&cli.api_url, client, "/governance/comments", &req, api_key).await?
         }
+        DeliberationCommands::Comments { id } => {
+            let path = format!("/governance/comments/{}", id);
+            get_request(&cli.api_url, client, &path, api_key).await?
+        }
+    };
+    println!("{}", serde_json::to_string_pretty(&response)?);
+    Ok(())
+}
 
-        if let Some(eta) = progress.get("eta_seconds") {
-            if !eta.is_null() {
-                let eta_secs = eta.as_u64().unwrap_or(0);
-                let minutes = eta_secs / 60;
-                let seconds = eta_secs % 60;
-                println!("ETA: {}m {}s", minutes, seconds);
+async fn handle_gov_parameters(
+    cli: &Cli,
+    client: &Client,
+    command: &ParameterCommands,
+) -> Result<(), anyhow::Error> {
+    use icn_api::governance_trait::{ParameterChangeView, ParameterRollbackRequest, ParameterView};
+    let api_key = cli.api_key.as_deref();
+    match command {
+        ParameterCommands::List => {
+            let params: Vec<ParameterView> =
+                get_request(&cli.api_url, client, "/governance/parameters", api_key).await?;
+            println!("{}", serde_json::to_string_pretty(&params)?);
+        }
+        ParameterCommands::History { name } => {
+            let mut history: Vec<ParameterChangeView> = get_request(
+                &cli.api_url,
+                client,
+                "/governance/parameters/history",
+                api_key,
+            )
+            .await?;
+            if let Some(name) = name {
+                history.retain(|c| &c.name == name);
             }
+            println!("{}", serde_json::to_string_pretty(&history)?);
         }
-
-        if let Some(message) = progress.get("message") {
-            println!("Status: {}", message.as_str().unwrap_or(""));
+        ParameterCommands::Rollback {
+            version,
+            duration_secs,
+        } => {
+            let req = ParameterRollbackRequest {
+                version: *version,
+                duration_secs: *duration_secs,
+            };
+            let proposal_id: String = post_request(
+                &cli.api_url,
+                client,
+                "/governance/parameters/rollback",
+                &req,
+                api_key,
+            )
+            .await?;
+            println!("Rollback proposal submitted: {}", proposal_id);
         }
-
+    }
+    Ok(())
+}
+
+async fn handle_gov_upgrade(
+    cli: &Cli,
+    client: &Client,
+    command: &UpgradeCommands,
+) -> Result<(), anyhow::Error> {
+    let api_key = cli.api_key.as_deref();
+    match command {
+        UpgradeCommands::Status { nodes } => {
+            let mut report = Vec::new();
+            for url in std::iter::once(&cli.api_url).chain(nodes.iter()) {
+                let entry =
+                    match get_request::<serde_json::Value>(url, client, "/info", api_key).await {
+                        Ok(info) => serde_json::json!({
+                            "node": url,
+                            "name": info["name"],
+                            "version": info["version"],
+                            "protocol_version": info["protocol_version"],
+                            "upgrades": info["upgrades"],
+                        }),
+                        Err(e) => serde_json::json!({ "node": url, "error": e.to_string() }),
+                    };
+                report.push(entry);
+            }
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        UpgradeCommands::Ready { proposal_id, relay } => {
+            let signal: serde_json::Value = post_request(
+                &cli.api_url,
+                client,
+                &format!("/governance/upgrades/{}/ready", proposal_id),
+                &serde_json::json!({}),
+                api_key,
+            )
+            .await?;
+            for url in relay {
+                if let Err(e) = post_request::<_, serde_json::Value>(
+                    url,
+                    client,
+                    "/governance/upgrades/readiness",
+                    &signal,
+                    api_key,
+                )
+                .await
+                {
+                    eprintln!("Failed to relay readiness to {}: {}", url, e);
+                }
+            }
+            println!("{}", serde_json::to_string_pretty(&signal)?);
+        }
+        UpgradeCommands::Manifest {
+            version,
+            protocol_version,
+            artifact,
+            activate_height,
+            activate_time,
+            contracts,
+            ccl_version,
+            key_bs58,
+        } => {
+            use icn_ccl::migration::{CclVersion, MigrationEngine, CURRENT_CCL_VERSION};
+            use icn_common::Signable;
+            use icn_governance::{UpgradeActivation, UpgradeManifest};
+
+            let activation = match (activate_height, activate_time) {
+                (Some(h), None) => UpgradeActivation::AtHeight(*h),
+                (None, Some(t)) => UpgradeActivation::AtTime(*t),
+                _ => anyhow::bail!("Specify one of --activate-height or --activate-time"),
+            };
+            let sk_bytes: [u8; 32] = bs58::decode(key_bs58)
+                .into_vec()?
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("Invalid private key length"))?;
+            let sk = icn_identity::SigningKey::from_bytes(&sk_bytes);
+            let publisher = Did::from_str(&icn_identity::did_key_from_verifying_key(
+                &sk.verifying_key(),
+            ))?;
+
+            let target = match ccl_version {
+                Some(v) => CclVersion::parse(v)?,
+                None => CURRENT_CCL_VERSION.clone(),
+            };
+            let engine = MigrationEngine::new();
+            let mut contract_upgrades = Vec::new();
+            for spec in contracts {
+                let (name, path) = spec
+                    .split_once('=')
+                    .ok_or_else(|| anyhow::anyhow!("Expected name=path, got {}", spec))?;
+                let source = std::fs::read_to_string(path)?;
+                let (upgrade, _) = engine.prepare_upgrade(name, &source, &target)?;
+                contract_upgrades.push(upgrade);
+            }
+
+            let artifact_bytes = std::fs::read(artifact)?;
+            let artifact_cid = Cid::new_v1_sha256(0x55, &artifact_bytes);
+            let mut manifest = UpgradeManifest {
+                version: version.clone(),
+                protocol_version: *protocol_version,
+                artifact_sha256: hex::encode(&artifact_cid.hash_bytes),
+                artifact_cid,
+                activation,
+                contract_upgrades,
+                publisher,
+                signature: None,
+            };
+            manifest.signature = Some(manifest.sign(&sk)?);
+            let proposal = icn_api::governance_trait::ProposalInputType::SoftwareUpgrade {
+                version: manifest.to_proposal_payload()?,
+            };
+            println!("{}", serde_json::to_string_pretty(&proposal)?);
+        }
+    }
+    Ok(())
+}
+
+fn handle_gov_simulate(args: &SimulateArgs) -> Result<(), anyhow::Error> {
+    use icn_eventstore::{EventStore, FileEventStore};
+    use icn_governance::{
+        DelegationRegistry, GovernanceEvent, GovernanceSimulator, SimulationConfig,
+        SyntheticPopulation,
+    };
+
+    let config = SimulationConfig {
+        quorum: args.quorum,
+        threshold: args.threshold,
+        min_sponsors: args.min_sponsors,
+        delegation: args.delegation_rule.parse()?,
+        method: args.method.parse()?,
+        honor_proposal_overrides: !args.ignore_proposal_overrides,
+    };
+    let simulator = match (&args.events, args.synthetic) {
+        (Some(path), false) => {
+            let store = FileEventStore::<GovernanceEvent>::new(path.clone());
+            let mut registry = DelegationRegistry::new();
+            if let Some(path) = &args.delegations {
+                let edges: Vec<icn_governance::Delegation> =
+                    serde_json::from_str(&std::fs::read_to_string(path)?)?;
+                for edge in edges {
+                    registry.delegate(edge)?;
+                }
+            }
+            let members = args
+                .members
+                .iter()
+                .map(|m| Did::from_str(m))
+                .collect::<Result<Vec<_>, _>>()?;
+            GovernanceSimulator::from_events(store.query(None)?)
+                .with_members(members)
+                .with_delegations(registry)
+        }
+        (None, true) => SyntheticPopulation {
+            members: args.synthetic_members,
+            proposals: args.synthetic_proposals,
+            turnout: args.turnout,
+            support: args.support,
+            delegation_rate: args.delegation_rate,
+            sponsors: 1,
+            seed: args.seed,
+        }
+        .generate(&SimulationConfig::default()),
+        _ => anyhow::bail!("Specify one of --events or --synthetic"),
+    };
+
+    let report = simulator.run(&config);
+    let rendered = match args.format.as_str() {
+        "json" => serde_json::to_string_pretty(&report)?,
+        "html" => report.to_html(),
+        other => anyhow::bail!("Unknown format: {}", other),
+    };
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, rendered)?;
+            println!(
+                "{} of {} outcomes would change; report written to {}",
+                report.changed,
+                report.proposals.len(),
+                path.display()
+            );
+        }
+        None => println!("{}", rendered),
+    }
+    Ok(())
+}
+
+async fn handle_gov_cross_federation(
+    cli: &Cli,
+    client: &Client,
+    command: &CrossFederationCommands,
+) -> Result<(), anyhow::Error> {
+    use icn_governance::{
+        CrossFederationState, FederationParticipant, FederationRatification, JointProposal,
+        RatificationSet,
+    };
+
+    let api_key = cli.api_key.as_deref();
+    match command {
+        CrossFederationCommands::Draft {
+            federation,
+            description,
+            proposal_type,
+            participants,
+            required,
+            min_ratifications,
+            deadline,
+            key_bs58,
+        } => {
+            use icn_common::Signable;
+
+            let sk_bytes: [u8; 32] = bs58::decode(key_bs58)
+                .into_vec()?
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("Invalid private key length"))?;
+            let sk = icn_identity::SigningKey::from_bytes(&sk_bytes);
+            let proposer = Did::from_str(&icn_identity::did_key_from_verifying_key(
+                &sk.verifying_key(),
+            ))?;
+            let participants = participants
+                .iter()
+                .map(|spec| {
+                    let (id, did) = spec
+                        .split_once('=')
+                        .ok_or_else(|| anyhow::anyhow!("Expected id=did, got {}", spec))?;
+                    Ok(FederationParticipant {
+                        federation_id: id.to_string(),
+                        signer: Did::from_str(did)?,
+                    })
+                })
+                .collect::<Result<Vec<_>, anyhow::Error>>()?;
+            let mut ratification = RatificationSet::unanimous(participants);
+            if !required.is_empty() {
+                ratification.required = required.clone();
+            }
+            if let Some(min) = min_ratifications {
+                ratification.min_ratifications = *min;
+            }
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs();
+            let mut proposal = JointProposal::new(
+                federation.clone(),
+                serde_json::from_str(proposal_type)?,
+                description.clone(),
+                ratification,
+                now,
+                *deadline,
+                proposer,
+            )?;
+            proposal.signature = Some(proposal.sign(&sk)?);
+            println!("{}", serde_json::to_string_pretty(&proposal)?);
+        }
+        CrossFederationCommands::Adopt {
+            proposal_json_or_stdin,
+            federation,
+            duration,
+        } => {
+            let json = if proposal_json_or_stdin == "-" {
+                let mut buffer = String::new();
+                io::stdin().read_to_string(&mut buffer)?;
+                buffer
+            } else {
+                proposal_json_or_stdin.clone()
+            };
+            let request = icn_api::governance_trait::AdoptCrossFederationRequest {
+                proposal: serde_json::from_str(&json)?,
+                local_federation: federation.clone(),
+                duration_secs: *duration,
+            };
+            let response: serde_json::Value = post_request(
+                &cli.api_url,
+                client,
+                "/governance/cross-federation/adopt",
+                &request,
+                api_key,
+            )
+            .await?;
+            println!("Local proposal: {}", response["local_proposal_id"]);
+        }
+        CrossFederationCommands::Ratify { proposal_id, relay } => {
+            let ratification: FederationRatification = post_request(
+                &cli.api_url,
+                client,
+                &format!("/governance/cross-federation/{}/ratify", proposal_id),
+                &serde_json::json!({}),
+                api_key,
+            )
+            .await?;
+            for url in relay {
+                if let Err(e) = post_request::<_, serde_json::Value>(
+                    url,
+                    client,
+                    "/governance/cross-federation/ratification",
+                    &ratification,
+                    api_key,
+                )
+                .await
+                {
+                    eprintln!("Failed to relay ratification to {}: {}", url, e);
+                }
+            }
+            println!("{}", serde_json::to_string_pretty(&ratification)?);
+        }
+        CrossFederationCommands::Execute { proposal_id } => {
+            let response: serde_json::Value = post_request(
+                &cli.api_url,
+                client,
+                &format!("/governance/cross-federation/{}/execute", proposal_id),
+                &serde_json::json!({}),
+                api_key,
+            )
+            .await?;
+            println!("Executed {}", response["executed"]);
+        }
+        CrossFederationCommands::Show { proposal_id } => {
+            let state: CrossFederationState = get_request(
+                &cli.api_url,
+                client,
+                &format!("/governance/cross-federation/{}", proposal_id),
+                api_key,
+            )
+            .await?;
+            println!("{}", serde_json::to_string_pretty(&state)?);
+            let pending = state.pending_federations();
+            if !pending.is_empty() {
+                println!("Waiting for: {}", pending.join(", "));
+            }
+        }
+        CrossFederationCommands::List => {
+            let states: Vec<CrossFederationState> = get_request(
+                &cli.api_url,
+                client,
+                "/governance/cross-federation",
+                api_key,
+            )
+            .await?;
+            for state in states {
+                println!(
+                    "{}\t{:?}\t{}/{} ratified",
+                    state.proposal.id,
+                    state.status,
+                    state
+                        .ratifications
+                        .iter()
+                        .filter(|r| r.decision == icn_governance::RatificationDecision::Ratified)
+                        .count(),
+                    state.proposal.ratification.min_ratifications
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn handle_gov_timers(cli: &Cli, client: &Client) -> Result<(), anyhow::Error> {
+    let timers: Vec<icn_governance::LifecycleTimer> = get_request(
+        &cli.api_url,
+        client,
+        "/governance/lifecycle/timers",
+        cli.api_key.as_deref(),
+    )
+    .await?;
+    if timers.is_empty() {
+        println!("No pending lifecycle steps.");
+    } else {
+        for timer in timers {
+            println!("{}\t{}\t{}", timer.due_at, timer.step, timer.proposal_id);
+        }
+    }
+    Ok(())
+}
+
+fn parse_sanction(spec: &str) -> Result<icn_governance::Sanction, anyhow::Error> {
+    use icn_governance::Sanction;
+    match spec.split_once(':') {
+        Some(("suspension", secs)) => Ok(Sanction::Suspension {
+            duration_secs: secs.parse()?,
+        }),
+        None if spec == "warning" => Ok(Sanction::Warning),
+        None if spec == "removal" => Ok(Sanction::Removal),
+        _ => anyhow::bail!(
+            "Unknown sanction {}; use warning, suspension:<seconds> or removal",
+            spec
+        ),
+    }
+}
+
+async fn handle_gov_case(
+    cli: &Cli,
+    client: &Client,
+    command: &CaseCommands,
+) -> Result<(), anyhow::Error> {
+    use icn_common::Signable;
+    use icn_governance::{
+        Appeal, CaseStatus, Complaint, DueProcessCase, Finding, PanelVerdict, SubjectResponse,
+        SubjectRole,
+    };
+
+    let api_key = cli.api_key.as_deref();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs();
+    let parse_evidence = |evidence: &[String]| {
+        evidence
+            .iter()
+            .map(|cid| Cid::from_str(cid).map_err(anyhow::Error::from))
+            .collect::<Result<Vec<_>, _>>()
+    };
+    let case = match command {
+        CaseCommands::File {
+            subject,
+            steward_of,
+            violation,
+            description,
+            evidence,
+            key_bs58,
+        } => {
+            let sk = signing_key_from_bs58(key_bs58)?;
+            let role = match steward_of {
+                Some(body) => SubjectRole::Steward { body: body.clone() },
+                None => SubjectRole::Member,
+            };
+            let violation =
+                serde_json::from_value(serde_json::json!(violation)).unwrap_or_else(|_| {
+                    icn_governance::federation_governance::ViolationType::Custom(violation.clone())
+                });
+            let mut complaint = Complaint::new(
+                Did::from_str(&icn_identity::did_key_from_verifying_key(
+                    &sk.verifying_key(),
+                ))?,
+                Did::from_str(subject)?,
+                role,
+                violation,
+                description.clone(),
+                parse_evidence(evidence)?,
+                now,
+            )?;
+            complaint.signature = Some(complaint.sign(&sk)?);
+            post_request::<_, DueProcessCase>(
+                &cli.api_url,
+                client,
+                "/governance/cases",
+                &complaint,
+                api_key,
+            )
+            .await?
+        }
+        CaseCommands::Respond {
+            case_id,
+            statement,
+            evidence,
+            key_bs58,
+        } => {
+            let mut response = SubjectResponse {
+                case_id: ProposalId(case_id.clone()),
+                statement: statement.clone(),
+                evidence: parse_evidence(evidence)?,
+                responded_at: now,
+                signature: None,
+            };
+            response.signature = Some(response.sign(&signing_key_from_bs58(key_bs58)?)?);
+            post_request(
+                &cli.api_url,
+                client,
+                &format!("/governance/cases/{}/response", case_id),
+                &response,
+                api_key,
+            )
+            .await?
+        }
+        CaseCommands::Verdict {
+            case_id,
+            sanction,
+            dismiss: _,
+            reasoning,
+            key_bs58,
+        } => {
+            let case: DueProcessCase = get_request(
+                &cli.api_url,
+                client,
+                &format!("/governance/cases/{}", case_id),
+                api_key,
+            )
+            .await?;
+            let CaseStatus::Hearing { stage, .. } = case.status else {
+                anyhow::bail!("Case {} is not being heard ({:?})", case_id, case.status);
+            };
+            let sk = signing_key_from_bs58(key_bs58)?;
+            let sanction = sanction.as_deref().map(parse_sanction).transpose()?;
+            let mut verdict = PanelVerdict {
+                case_id: ProposalId(case_id.clone()),
+                stage,
+                panelist: Did::from_str(&icn_identity::did_key_from_verifying_key(
+                    &sk.verifying_key(),
+                ))?,
+                finding: if sanction.is_some() {
+                    Finding::Upheld
+                } else {
+                    Finding::Dismissed
+                },
+                sanction,
+                reasoning: reasoning.clone(),
+                decided_at: now,
+                signature: None,
+            };
+            verdict.signature = Some(verdict.sign(&sk)?);
+            post_request(
+                &cli.api_url,
+                client,
+                &format!("/governance/cases/{}/verdict", case_id),
+                &verdict,
+                api_key,
+            )
+            .await?
+        }
+        CaseCommands::Appeal {
+            case_id,
+            grounds,
+            key_bs58,
+        } => {
+            let sk = signing_key_from_bs58(key_bs58)?;
+            let mut appeal = Appeal {
+                case_id: ProposalId(case_id.clone()),
+                appellant: Did::from_str(&icn_identity::did_key_from_verifying_key(
+                    &sk.verifying_key(),
+                ))?,
+                grounds: grounds.clone(),
+                filed_at: now,
+                signature: None,
+            };
+            appeal.signature = Some(appeal.sign(&sk)?);
+            post_request(
+                &cli.api_url,
+                client,
+                &format!("/governance/cases/{}/appeal", case_id),
+                &appeal,
+                api_key,
+            )
+            .await?
+        }
+        CaseCommands::Show { case_id } => {
+            let case: DueProcessCase = get_request(
+                &cli.api_url,
+                client,
+                &format!("/governance/cases/{}", case_id),
+                api_key,
+            )
+            .await?;
+            println!("{}", serde_json::to_string_pretty(&case)?);
+            return Ok(());
+        }
+        CaseCommands::List => {
+            let cases: Vec<DueProcessCase> =
+                get_request(&cli.api_url, client, "/governance/cases", api_key).await?;
+            for case in cases {
+                println!(
+                    "{}\t{}\t{:?}",
+                    case.complaint.id, case.complaint.subject, case.status
+                );
+            }
+            return Ok(());
+        }
+    };
+    println!("Case {}: {:?}", case.complaint.id, case.status);
+    Ok(())
+}
+
+fn parse_chamber_requirement(
+    spec: &str,
+) -> Result<icn_governance::ChamberRequirement, anyhow::Error> {
+    let mut parts = spec.split(':');
+    let (Some(chamber), Some(threshold)) = (parts.next(), parts.next()) else {
+        anyhow::bail!("Chamber must be name:threshold[:min_voters], got {}", spec);
+    };
+    Ok(icn_governance::ChamberRequirement {
+        chamber: chamber.to_string(),
+        threshold: threshold.parse()?,
+        min_voters: parts.next().map(str::parse).transpose()?.unwrap_or(1),
+    })
+}
+
+async fn handle_gov_weighting(
+    cli: &Cli,
+    client: &Client,
+    command: &WeightingCommands,
+) -> Result<(), anyhow::Error> {
+    use icn_governance::{MemberProfile, WeightRule, WeightedTally, WeightingScheme};
+
+    let api_key = cli.api_key.as_deref();
+    let profile: MemberProfile = match command {
+        WeightingCommands::Show => {
+            let overview: serde_json::Value =
+                get_request(&cli.api_url, client, "/governance/weighting", api_key).await?;
+            println!("{}", serde_json::to_string_pretty(&overview)?);
+            return Ok(());
+        }
+        WeightingCommands::Set {
+            kind,
+            equal: _,
+            size_cap,
+            custom,
+            chambers,
+        } => {
+            let scheme = match (size_cap, custom) {
+                (Some(cap), _) => WeightingScheme::MembershipSize { cap: *cap },
+                (None, Some(name)) => WeightingScheme::Custom(name.clone()),
+                (None, None) => WeightingScheme::Equal,
+            };
+            let rule = WeightRule {
+                scheme,
+                chambers: chambers
+                    .iter()
+                    .map(|c| parse_chamber_requirement(c))
+                    .collect::<Result<_, _>>()?,
+            };
+            let _: serde_json::Value = post_request(
+                &cli.api_url,
+                client,
+                "/governance/weighting",
+                &serde_json::json!({ "kind": kind, "rule": rule }),
+                api_key,
+            )
+            .await?;
+            println!("Weight rule set for {}", kind);
+            return Ok(());
+        }
+        WeightingCommands::Clear { kind } => {
+            let _: serde_json::Value = post_request(
+                &cli.api_url,
+                client,
+                "/governance/weighting",
+                &serde_json::json!({ "kind": kind, "rule": null }),
+                api_key,
+            )
+            .await?;
+            println!("Weight rule cleared for {}", kind);
+            return Ok(());
+        }
+        WeightingCommands::Profile { did } => {
+            post_request(
+                &cli.api_url,
+                client,
+                "/governance/weighting/profiles",
+                &serde_json::json!({ "did": did }),
+                api_key,
+            )
+            .await?
+        }
+        WeightingCommands::Credential {
+            credential_json_or_stdin,
+        } => {
+            let json = if credential_json_or_stdin == "-" {
+                let mut buffer = String::new();
+                io::stdin().read_to_string(&mut buffer)?;
+                buffer
+            } else {
+                std::fs::read_to_string(credential_json_or_stdin)?
+            };
+            let credential: icn_identity::Credential = serde_json::from_str(&json)?;
+            post_request(
+                &cli.api_url,
+                client,
+                "/governance/weighting/credentials",
+                &credential,
+                api_key,
+            )
+            .await?
+        }
+        WeightingCommands::Member { did } => {
+            get_request(
+                &cli.api_url,
+                client,
+                &format!("/governance/weighting/profiles/{}", did),
+                api_key,
+            )
+            .await?
+        }
+        WeightingCommands::Tally { proposal_id } => {
+            let tally: WeightedTally = get_request(
+                &cli.api_url,
+                client,
+                &format!("/governance/weighting/tally/{}", proposal_id),
+                api_key,
+            )
+            .await?;
+            println!(
+                "Overall: yes {:.3} / no {:.3} / abstain {:.3} from {} voters",
+                tally.yes, tally.no, tally.abstain, tally.voters
+            );
+            for chamber in &tally.chambers {
+                println!(
+                    "{}: yes {:.3} / no {:.3} / abstain {:.3} from {} voters ({})",
+                    chamber.chamber,
+                    chamber.yes,
+                    chamber.no,
+                    chamber.abstain,
+                    chamber.voters,
+                    if chamber.approved {
+                        "approves"
+                    } else {
+                        "does not approve"
+                    }
+                );
+            }
+            println!(
+                "{}",
+                if tally.approved {
+                    "Approved"
+                } else {
+                    "Not approved"
+                }
+            );
+            return Ok(());
+        }
+    };
+    let chambers: Vec<&str> = profile.chambers.iter().map(String::as_str).collect();
+    println!(
+        "{}: {} members, chambers [{}]",
+        profile.did,
+        profile.member_count,
+        chambers.join(", ")
+    );
+    Ok(())
+}
+
+fn read_constitution(file_or_stdin: &str) -> Result<icn_governance::Constitution, anyhow::Error> {
+    let json = if file_or_stdin == "-" {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        buffer
+    } else {
+        std::fs::read_to_string(file_or_stdin)?
+    };
+    let constitution: icn_governance::Constitution = serde_json::from_str(&json)?;
+    constitution.validate()?;
+    Ok(constitution)
+}
+
+async fn handle_gov_constitution(
+    cli: &Cli,
+    client: &Client,
+    command: &ConstitutionCommands,
+) -> Result<(), anyhow::Error> {
+    use icn_governance::ConstitutionRecord;
+
+    let api_key = cli.api_key.as_deref();
+    let fetch = |version: Option<u32>| {
+        let path = match version {
+            Some(v) => format!("/governance/constitution/{}", v),
+            None => "/governance/constitution".to_string(),
+        };
+        async move { get_request::<ConstitutionRecord>(&cli.api_url, client, &path, api_key).await }
+    };
+    match command {
+        ConstitutionCommands::Show { version } => {
+            let record = fetch(*version).await?;
+            println!("{}", serde_json::to_string_pretty(&record)?);
+        }
+        ConstitutionCommands::History => {
+            let history: Vec<ConstitutionRecord> = get_request(
+                &cli.api_url,
+                client,
+                "/governance/constitution/history",
+                api_key,
+            )
+            .await?;
+            for record in history {
+                println!(
+                    "v{}\t{}\t{}\t{}",
+                    record.constitution.version,
+                    record.adopted_at,
+                    record.cid,
+                    record
+                        .adopted_by
+                        .map(|p| p.to_string())
+                        .unwrap_or_else(|| "genesis".to_string())
+                );
+            }
+        }
+        ConstitutionCommands::Adopt { file_or_stdin } => {
+            let constitution = read_constitution(file_or_stdin)?;
+            let response: serde_json::Value = post_request(
+                &cli.api_url,
+                client,
+                "/governance/constitution",
+                &constitution,
+                api_key,
+            )
+            .await?;
+            println!("Adopted constitution v{}", constitution.version);
+            println!("{}", serde_json::to_string_pretty(&response)?);
+        }
+        ConstitutionCommands::Amend { file_or_stdin } => {
+            let constitution = read_constitution(file_or_stdin)?;
+            let proposal = icn_api::governance_trait::ProposalInputType::ConstitutionAmendment {
+                constitution,
+            };
+            println!("{}", serde_json::to_string_pretty(&proposal)?);
+        }
+        ConstitutionCommands::Diff { from, to, proposed } => {
+            let before = fetch(Some(*from)).await?.constitution;
+            let after = match proposed {
+                Some(file) => read_constitution(file)?,
+                None => fetch(*to).await?.constitution,
+            };
+            let changes = before.diff(&after)?;
+            if changes.is_empty() {
+                println!("No changes.");
+            }
+            for change in changes {
+                println!("{}", change.path);
+                if let Some(old) = change.before {
+                    println!("  - {}", old);
+                }
+                if let Some(new) = change.after {
+                    println!("  + {}", new);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn secret_ballot_rng() -> ark_std::rand::rngs::StdRng {
+    use ark_std::rand::SeedableRng;
+    ark_std::rand::rngs::StdRng::from_seed(rand::random())
+}
+
+async fn handle_gov_secret_ballot(
+    cli: &Cli,
+    client: &Client,
+    command: &SecretBallotCommands,
+) -> Result<(), anyhow::Error> {
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use icn_governance::{BallotReveal, SecretBallotState, SecretTally};
+
+    let api_key = cli.api_key.as_deref();
+    match command {
+        SecretBallotCommands::Setup { depth, proving_key } => {
+            let pk = icn_zk::setup(
+                icn_zk::AnonymousMembershipCircuit::blank(*depth),
+                &mut secret_ballot_rng(),
+            )?;
+            let mut file = std::fs::File::create(proving_key)?;
+            pk.serialize_compressed(&mut file)?;
+            let mut vk_bytes = Vec::new();
+            pk.vk.serialize_compressed(&mut vk_bytes)?;
+            let output = serde_json::json!({
+                "proving_key_path": proving_key.to_string_lossy(),
+                "tree_depth": depth,
+                "verifying_key_hash": icn_governance::secret_ballot::verifying_key_hash(
+                    &hex::encode(&vk_bytes)
+                )?,
+                "verifying_key": hex::encode(vk_bytes),
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+            eprintln!(
+                "Propose `{}` = verifying_key_hash before enabling secret ballots.",
+                icn_governance::parameters::SECRET_BALLOT_VERIFYING_KEY_HASH
+            );
+        }
+        SecretBallotCommands::Register { did } => {
+            use icn_zk::membership::{field_to_hex, identity_commitment, random_secret};
+
+            let secret = random_secret(&mut secret_ballot_rng());
+            let commitment = field_to_hex(&identity_commitment(secret));
+            let request = icn_api::governance_trait::VoterCommitmentRequest {
+                did: did.clone(),
+                commitment: commitment.clone(),
+            };
+            let _: serde_json::Value = post_request(
+                &cli.api_url,
+                client,
+                "/governance/secret-ballot/commitment",
+                &request,
+                api_key,
+            )
+            .await?;
+            let output = serde_json::json!({
+                "did": did,
+                "commitment": commitment,
+                "secret": field_to_hex(&secret),
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+            eprintln!("Keep the secret private; it is needed to cast secret ballots.");
+        }
+        SecretBallotCommands::Enable {
+            proposal_id,
+            verifying_key,
+            depth,
+            reveal_from,
+        } => {
+            let request = icn_api::governance_trait::EnableSecretBallotRequest {
+                verifying_key: verifying_key.clone(),
+                tree_depth: *depth,
+                reveal_from: *reveal_from,
+            };
+            let config: serde_json::Value = post_request(
+                &cli.api_url,
+                client,
+                &format!("/governance/secret-ballot/{}/enable", proposal_id),
+                &request,
+                api_key,
+            )
+            .await?;
+            println!("{}", serde_json::to_string_pretty(&config)?);
+        }
+        SecretBallotCommands::Cast {
+            proposal_id,
+            option,
+            secret,
+            proving_key,
+        } => {
+            let option = match option.to_lowercase().as_str() {
+                "yes" => icn_governance::VoteOption::Yes,
+                "no" => icn_governance::VoteOption::No,
+                "abstain" => icn_governance::VoteOption::Abstain,
+                other => anyhow::bail!("Invalid vote option: {}", other),
+            };
+            let status: serde_json::Value = get_request(
+                &cli.api_url,
+                client,
+                &format!("/governance/secret-ballot/{}", proposal_id),
+                api_key,
+            )
+            .await?;
+            let state: SecretBallotState = serde_json::from_value(status["state"].clone())?;
+            let pk_bytes = std::fs::read(proving_key)?;
+            let pk =
+                ark_groth16::ProvingKey::<ark_bn254::Bn254>::deserialize_compressed(&pk_bytes[..])?;
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs();
+            let (ballot, reveal) = icn_governance::prepare_secret_ballot(
+                &pk,
+                &state.proposal_id,
+                &state.config,
+                secret,
+                option,
+                now,
+                &mut secret_ballot_rng(),
+            )?;
+            let anchored: serde_json::Value = post_request(
+                &cli.api_url,
+                client,
+                "/governance/secret-ballot/cast",
+                &ballot,
+                api_key,
+            )
+            .await?;
+            eprintln!("Ballot anchored as {}", anchored["cid"]);
+            eprintln!("Keep the reveal below and submit it once the reveal phase starts.");
+            println!("{}", serde_json::to_string_pretty(&reveal)?);
+        }
+        SecretBallotCommands::Reveal {
+            reveal_json_or_stdin,
+        } => {
+            let json = if reveal_json_or_stdin == "-" {
+                let mut buffer = String::new();
+                io::stdin().read_to_string(&mut buffer)?;
+                buffer
+            } else {
+                reveal_json_or_stdin.clone()
+            };
+            let reveal: BallotReveal = serde_json::from_str(&json)?;
+            let anchored: serde_json::Value = post_request(
+                &cli.api_url,
+                client,
+                "/governance/secret-ballot/reveal",
+                &reveal,
+                api_key,
+            )
+            .await?;
+            println!("Reveal anchored as {}", anchored["cid"]);
+        }
+        SecretBallotCommands::Show { proposal_id } => {
+            let status: serde_json::Value = get_request(
+                &cli.api_url,
+                client,
+                &format!("/governance/secret-ballot/{}", proposal_id),
+                api_key,
+            )
+            .await?;
+            println!("{}", serde_json::to_string_pretty(&status)?);
+        }
+        SecretBallotCommands::Verify { proposal_id } => {
+            let status: serde_json::Value = get_request(
+                &cli.api_url,
+                client,
+                &format!("/governance/secret-ballot/{}", proposal_id),
+                api_key,
+            )
+            .await?;
+            let state: SecretBallotState = serde_json::from_value(status["state"].clone())?;
+            let reported: SecretTally = serde_json::from_value(status["tally"].clone())?;
+            let tally =
+                icn_governance::verify_secret_tally(&state.config, &state.ballots, &state.reveals)?;
+            let output = serde_json::json!({
+                "proposal_id": proposal_id,
+                "tally": tally,
+                "matches_node": tally == reported,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+            if tally != reported {
+                anyhow::bail!("Tally reported by the node does not match the anchored ballots");
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn handle_gov_delegations(
+    cli: &Cli,
+    client: &Client,
+    proposal_id: Option<&str>,
+) -> Result<(), anyhow::Error> {
+    match proposal_id {
+        Some(id) => {
+            let path = format!("/governance/delegations/{}", id);
+            let view: icn_api::governance_trait::ProposalDelegationView =
+                get_request(&cli.api_url, client, &path, cli.api_key.as_deref()).await?;
+            println!("{}", serde_json::to_string_pretty(&view)?);
+        }
+        None => {
+            let edges: Vec<icn_api::governance_trait::DelegationEdge> = get_request(
+                &cli.api_url,
+                client,
+                "/governance/delegations",
+                cli.api_key.as_deref(),
+            )
+            .await?;
+            println!("{}", serde_json::to_string_pretty(&edges)?);
+        }
+    }
+    Ok(())
+}
+
+async fn handle_gov_conviction_process(cli: &Cli, client: &Client) -> Result<(), anyhow::Error> {
+    let accepted: Vec<String> = post_request(
+        &cli.api_url,
+        client,
+        "/governance/conviction/process",
+        &(),
+        cli.api_key.as_deref(),
+    )
+    .await?;
+    if accepted.is_empty() {
+        println!("No conviction proposals crossed their threshold.");
+    } else {
+        println!("Accepted: {}", accepted.join(", "));
+    }
+    Ok(())
+}
+
+async fn handle_mesh_jobs(cli: &Cli, client: &Client) -> Result<(), anyhow::Error> {
+    let response: serde_json::Value =
+        get_request(&cli.api_url, client, "/mesh/jobs", cli.api_key.as_deref()).await?;
+    println!("{}", serde_json::to_string_pretty(&response)?);
+    Ok(())
+}
+
+async fn handle_mesh_status(cli: &Cli, client: &Client, job_id: &str) -> Result<(), anyhow::Error> {
+    let path = format!("/mesh/jobs/{}", job_id);
+    let response: serde_json::Value =
+        get_request(&cli.api_url, client, &path, cli.api_key.as_deref()).await?;
+    println!("{}", serde_json::to_string_pretty(&response)?);
+    Ok(())
+}
+
+/// Submit a new mesh job to the node
+async fn handle_mesh_submit(
+    cli: &Cli,
+    client: &Client,
+    job_request_json_or_stdin: &str,
+) -> Result<(), anyhow::Error> {
+    let job_json_content = if job_request_json_or_stdin == "-" {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        buffer
+    } else {
+        job_request_json_or_stdin.to_string()
+    };
+
+    let mut request_value: serde_json::Value = serde_json::from_str(&job_json_content)
+        .map_err(|e| anyhow::anyhow!("Invalid mesh job JSON: {}", e))?;
+
+    if let Some(manifest_path) = request_value
+        .get("manifest_cid")
+        .and_then(|v| v.as_str())
+        .filter(|s| s.ends_with(".ccl"))
+    {
+        let path = PathBuf::from(manifest_path);
+        let (wasm, _meta) = compile_ccl_file_to_wasm(&path).map_err(anyhow::Error::msg)?;
+        let payload = DagBlockPayload { data: wasm };
+        let cid: Cid = post_request(
+            &cli.api_url,
+            client,
+            "/dag/put",
+            &payload,
+            cli.api_key.as_deref(),
+        )
+        .await?;
+
+        if let Some(obj) = request_value.as_object_mut() {
+            obj.insert(
+                "manifest_cid".to_string(),
+                serde_json::json!(cid.to_string()),
+            );
+            if !obj.contains_key("spec_bytes") {
+                let spec = icn_mesh::JobSpec {
+                    kind: icn_mesh::JobKind::CclWasm,
+                    ..Default::default()
+                };
+                let bytes = bincode::serialize(&spec).unwrap();
+                obj.insert(
+                    "spec_bytes".to_string(),
+                    serde_json::json!(base64::engine::general_purpose::STANDARD.encode(bytes)),
+                );
+            }
+        }
+    }
+
+    let response: serde_json::Value = post_request(
+        &cli.api_url,
+        client,
+        "/mesh/submit",
+        &request_value,
+        cli.api_key.as_deref(),
+    )
+    .await?;
+    println!("{}", serde_json::to_string_pretty(&response)?);
+    Ok(())
+}
+
+/// Get progress information for a mesh job
+async fn handle_mesh_progress(
+    cli: &Cli,
+    client: &Client,
+    job_id: &str,
+) -> Result<(), anyhow::Error> {
+    let path = format!("/mesh/jobs/{}/progress", job_id);
+    let response: serde_json::Value =
+        get_request(&cli.api_url, client, &path, cli.api_key.as_deref()).await?;
+
+    // Pretty print progress information
+    if let Some(progress) = response.get("progress") {
+        println!("Job Progress for: {}", job_id);
+        println!("================");
+
+        if let Some(stage) = progress.get("current_stage") {
+            println!("Current Stage: {}", stage.as_str().unwrap_or("unknown"));
+        }
+
+        if let Some(percent) = progress.get("progress_percent") {
+            println!("Progress: {:.1}%", percent.as_f64().unwrap_or(0.0));
+        }
+
+        if let Some(eta) = progress.get("eta_seconds") {
+            if !eta.is_null() {
+                let eta_secs = eta.as_u64().unwrap_or(0);
+                let minutes = eta_secs / 60;
+                let seconds = eta_secs % 60;
+                println!("ETA: {}m {}s", minutes, seconds);
+            }
+        }
+
+        if let Some(message) = progress.get("message") {
+            println!("Status: {}", message.as_str().unwrap_or(""));
+        }
+
         if let Some(completed) = progress.get("completed_stages") {
             if let Some(stages) = completed.as_array() {
                 if !stages.is_empty() {
@@ -2344,6 +4686,153 @@ async fn handle_network_peers(cli: &Cli, _client: &Client) -> Result<(), anyhow:
     Ok(())
 }
 
+async fn handle_didcomm(
+    cli: &Cli,
+    client: &Client,
+    command: &DidcommCommands,
+) -> Result<(), anyhow::Error> {
+    let api_key = cli.api_key.as_deref();
+    let result: serde_json::Value = match command {
+        DidcommCommands::Send {
+            to,
+            message_type,
+            body,
+            thid,
+            anonymous,
+            mediator,
+        } => {
+            let body = if body == "-" {
+                let mut buffer = String::new();
+                io::stdin().read_to_string(&mut buffer)?;
+                buffer
+            } else {
+                body.clone()
+            };
+            let request = icn_api::identity_trait::SendDidCommRequest {
+                to: Did::from_str(to)?,
+                message_type: message_type.clone(),
+                body: serde_json::from_str(&body)
+                    .map_err(|e| anyhow::anyhow!("Invalid message body JSON: {}", e))?,
+                thid: thid.clone(),
+                anonymous: *anonymous,
+                mediator: mediator.as_deref().map(Did::from_str).transpose()?,
+            };
+            post_request(&cli.api_url, client, "/didcomm/send", &request, api_key).await?
+        }
+        DidcommCommands::Messages => {
+            get_request(&cli.api_url, client, "/didcomm/messages", api_key).await?
+        }
+        DidcommCommands::Mediated => {
+            get_request(&cli.api_url, client, "/didcomm/mediated", api_key).await?
+        }
+        DidcommCommands::Mediate { mediator } => {
+            let request = icn_api::identity_trait::MediatorRequest {
+                mediator: Did::from_str(mediator)?,
+                limit: None,
+            };
+            post_request(
+                &cli.api_url,
+                client,
+                "/didcomm/mediation",
+                &request,
+                api_key,
+            )
+            .await?
+        }
+        DidcommCommands::AllowMediation { did } => {
+            let request = icn_api::identity_trait::MediationAllowRequest {
+                did: Did::from_str(did)?,
+            };
+            post_request(
+                &cli.api_url,
+                client,
+                "/didcomm/mediation/allowed",
+                &request,
+                api_key,
+            )
+            .await?
+        }
+        DidcommCommands::Pickup { mediator, limit } => {
+            let request = icn_api::identity_trait::MediatorRequest {
+                mediator: Did::from_str(mediator)?,
+                limit: *limit,
+            };
+            post_request(&cli.api_url, client, "/didcomm/pickup", &request, api_key).await?
+        }
+        DidcommCommands::Offer {
+            holder,
+            credential_type,
+            claims,
+            comment,
+            mediator,
+        } => {
+            let claims = claims
+                .iter()
+                .map(|claim| {
+                    claim
+                        .split_once('=')
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .ok_or_else(|| anyhow::anyhow!("Expected name=value, got '{}'", claim))
+                })
+                .collect::<Result<_, _>>()?;
+            let request = icn_api::identity_trait::CredentialOfferRequest {
+                holder: Did::from_str(holder)?,
+                offer: icn_identity::CredentialOffer {
+                    credential_type: credential_type.clone(),
+                    claims,
+                    comment: comment.clone(),
+                },
+                mediator: mediator.as_deref().map(Did::from_str).transpose()?,
+            };
+            post_request(
+                &cli.api_url,
+                client,
+                "/didcomm/credential-offer",
+                &request,
+                api_key,
+            )
+            .await?
+        }
+        DidcommCommands::Request { offer_id } => {
+            let request = icn_api::identity_trait::CredentialOfferReply {
+                offer_id: offer_id.clone(),
+            };
+            post_request(
+                &cli.api_url,
+                client,
+                "/didcomm/credential-request",
+                &request,
+                api_key,
+            )
+            .await?
+        }
+        DidcommCommands::Invite {
+            proposal_id,
+            invitees,
+            comment,
+        } => {
+            let request = icn_api::identity_trait::ProposalInvitationRequest {
+                proposal_id: proposal_id.clone(),
+                invitees: invitees
+                    .iter()
+                    .map(|did| Did::from_str(did))
+                    .collect::<Result<_, _>>()?,
+                comment: comment.clone(),
+            };
+            post_request(
+                &cli.api_url,
+                client,
+                "/didcomm/proposal-invitation",
+                &request,
+                api_key,
+            )
+            .await?
+        }
+    };
+    println!("{}", serde_json::to_string_pretty(&result)?);
+    Ok(())
+}
+
 fn handle_ccl_compile(file: &str) -> Result<(), anyhow::Error> {
     let source_path = PathBuf::from(file);
     let wasm_path = source_path.with_extension("wasm");
@@ -2514,6 +5003,138 @@ async fn handle_keys_show(cli: &Cli, client: &Client) -> Result<(), anyhow::Erro
     Ok(())
 }
 
+fn env_secret(var: &str, what: &str) -> Result<String, anyhow::Error> {
+    std::env::var(var).map_err(|_| anyhow::anyhow!("set the {} in {}", what, var))
+}
+
+fn open_pkcs11_token(
+    hsm_library: &std::path::Path,
+    backend: &KeyBackendArgs,
+) -> Result<icn_runtime::context::Pkcs11Token, anyhow::Error> {
+    let pin = env_secret(&backend.pin_env, "token user PIN")?;
+    Ok(icn_runtime::context::Pkcs11Token::open(
+        hsm_library,
+        backend.slot,
+        &pin,
+    )?)
+}
+
+fn key_json(label: &str, did: &str, public_key: &[u8]) -> serde_json::Value {
+    serde_json::json!({
+        "label": label,
+        "did": did,
+        "public_key_bs58": bs58::encode(public_key).into_string(),
+    })
+}
+
+fn handle_keys_generate(label: &str, backend: &KeyBackendArgs) -> Result<(), anyhow::Error> {
+    let key = if let Some(path) = &backend.keystore {
+        let passphrase = env_secret(&backend.passphrase_env, "keystore passphrase")?;
+        let mut keystore = if path.exists() {
+            icn_identity::Keystore::open(path, passphrase.as_bytes())?
+        } else {
+            icn_identity::Keystore::create(path, passphrase.as_bytes())?
+        };
+        let entry = keystore.generate(label)?;
+        key_json(&entry.label, &entry.did, &entry.public_key)
+    } else if let Some(library) = &backend.hsm_library {
+        let token = open_pkcs11_token(library, backend)?;
+        let key = token.generate_ed25519(label)?;
+        let mut json = key_json(&key.label, &key.did.to_string(), key.public_key.as_bytes());
+        json["slot"] = token.slot_id().into();
+        json
+    } else {
+        anyhow::bail!("pass --keystore or --hsm-library");
+    };
+    println!("{}", serde_json::to_string_pretty(&key)?);
+    Ok(())
+}
+
+fn handle_keys_list(backend: &KeyBackendArgs) -> Result<(), anyhow::Error> {
+    let keys: Vec<_> = if let Some(path) = &backend.keystore {
+        icn_identity::Keystore::list(path)?
+            .iter()
+            .map(|k| key_json(&k.label, &k.did, &k.public_key))
+            .collect()
+    } else if let Some(library) = &backend.hsm_library {
+        open_pkcs11_token(library, backend)?
+            .list_ed25519()?
+            .iter()
+            .map(|k| key_json(&k.label, &k.did.to_string(), k.public_key.as_bytes()))
+            .collect()
+    } else {
+        anyhow::bail!("pass --keystore or --hsm-library");
+    };
+    println!("{}", serde_json::to_string_pretty(&keys)?);
+    Ok(())
+}
+
+fn handle_keys_migrate(
+    from: &std::path::Path,
+    keystore_path: &std::path::Path,
+    label: &str,
+    passphrase_env: &str,
+    legacy_passphrase_env: Option<&str>,
+    keep_source: bool,
+) -> Result<(), anyhow::Error> {
+    let signing_key = match legacy_passphrase_env {
+        Some(var) => icn_runtime::context::signers::decrypt_legacy_key_file(
+            from,
+            env_secret(var, "legacy key passphrase")?.as_bytes(),
+        )?,
+        None => signing_key_from_bs58(std::fs::read_to_string(from)?.trim())?,
+    };
+    let passphrase = env_secret(passphrase_env, "keystore passphrase")?;
+    let mut keystore = if keystore_path.exists() {
+        icn_identity::Keystore::open(keystore_path, passphrase.as_bytes())?
+    } else {
+        icn_identity::Keystore::create(keystore_path, passphrase.as_bytes())?
+    };
+    let entry = keystore.add(label, &signing_key)?;
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&key_json(&entry.label, &entry.did, &entry.public_key))?
+    );
+    if keep_source {
+        eprintln!(
+            "Key stored in {}; delete {} once the node starts from the keystore",
+            keystore_path.display(),
+            from.display()
+        );
+    } else {
+        std::fs::remove_file(from).map_err(|e| {
+            anyhow::anyhow!(
+                "key stored in {} but {} could not be deleted: {e}",
+                keystore_path.display(),
+                from.display()
+            )
+        })?;
+        eprintln!(
+            "Key stored in {}; deleted {}",
+            keystore_path.display(),
+            from.display()
+        );
+    }
+    Ok(())
+}
+
+fn handle_keys_passwd(
+    keystore_path: &std::path::Path,
+    passphrase_env: &str,
+    new_passphrase_env: &str,
+) -> Result<(), anyhow::Error> {
+    let passphrase = env_secret(passphrase_env, "current keystore passphrase")?;
+    let new_passphrase = env_secret(new_passphrase_env, "new keystore passphrase")?;
+    let mut keystore = icn_identity::Keystore::open(keystore_path, passphrase.as_bytes())?;
+    keystore.change_passphrase(new_passphrase.as_bytes())?;
+    println!(
+        "Re-encrypted {} keys in {}",
+        keystore.keys().len(),
+        keystore_path.display()
+    );
+    Ok(())
+}
+
 async fn handle_reputation_get(cli: &Cli, client: &Client, did: &str) -> Result<(), anyhow::Error> {
     let path = format!("/reputation/{}", did);
     let v: serde_json::Value =
@@ -2610,6 +5231,7 @@ fn parse_backend(s: &str) -> ZkProofType {
     match s.to_ascii_lowercase().as_str() {
         "groth16" => ZkProofType::Groth16,
         "bulletproofs" => ZkProofType::Bulletproofs,
+        "bbs" => ZkProofType::Bbs,
         other => ZkProofType::Other(other.to_string()),
     }
 }
@@ -4196,32 +6818,610 @@ async fn handle_fed_did_generate(
     Ok(())
 }
 
-async fn handle_fed_did_verify(
-    _cli: &Cli,
-    _client: &Client,
-    _document_json_or_stdin: &str,
+async fn handle_fed_did_verify(
+    _cli: &Cli,
+    _client: &Client,
+    _document_json_or_stdin: &str,
+) -> Result<(), anyhow::Error> {
+    println!("🚧 Federation DID verification not yet implemented");
+    Ok(())
+}
+
+async fn handle_fed_did_publish(
+    _cli: &Cli,
+    _client: &Client,
+    _federation_id: &str,
+    _document_json_or_stdin: &str,
+) -> Result<(), anyhow::Error> {
+    println!("🚧 Federation DID publishing not yet implemented");
+    Ok(())
+}
+
+async fn handle_fed_did_resolve(
+    _cli: &Cli,
+    _client: &Client,
+    _did: &str,
+    _federation: &Option<String>,
+) -> Result<(), anyhow::Error> {
+    println!("🚧 Federation DID resolution not yet implemented");
+    Ok(())
+}
+
+fn signing_key_from_bs58(key_bs58: &str) -> Result<icn_identity::SigningKey, anyhow::Error> {
+    let sk_bytes: [u8; 32] = bs58::decode(key_bs58)
+        .into_vec()?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Invalid private key length"))?;
+    Ok(icn_identity::SigningKey::from_bytes(&sk_bytes))
+}
+
+async fn handle_identity_did_icn(
+    cli: &Cli,
+    client: &Client,
+    command: &DidIcnCommands,
+) -> Result<(), anyhow::Error> {
+    use icn_identity::{KeyEvent, KeyState};
+
+    let api_key = cli.api_key.as_deref();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs();
+    let current_state = |did: &str| {
+        let path = format!("/identity/did-icn/{}", did);
+        async move {
+            let response: serde_json::Value =
+                get_request(&cli.api_url, client, &path, api_key).await?;
+            Ok::<KeyState, anyhow::Error>(serde_json::from_value(response["state"].clone())?)
+        }
+    };
+    let (event, new_next_key) = match command {
+        DidIcnCommands::Incept => {
+            let (sk, _) = generate_ed25519_keypair();
+            let (next_sk, next_pk) = generate_ed25519_keypair();
+            println!("Signing key: {}", bs58::encode(sk.to_bytes()).into_string());
+            (KeyEvent::inception(&sk, Some(&next_pk), now), Some(next_sk))
+        }
+        DidIcnCommands::Rotate { did, next_key_bs58 } => {
+            let state = current_state(did).await?;
+            let revealed = signing_key_from_bs58(next_key_bs58)?;
+            let (next_sk, next_pk) = generate_ed25519_keypair();
+            (
+                KeyEvent::rotation(&state, &revealed, Some(&next_pk), now),
+                Some(next_sk),
+            )
+        }
+        DidIcnCommands::Deactivate { did, next_key_bs58 } => {
+            let state = current_state(did).await?;
+            let revealed = signing_key_from_bs58(next_key_bs58)?;
+            (KeyEvent::deactivation(&state, &revealed, now), None)
+        }
+        DidIcnCommands::Show { did } => {
+            let response: serde_json::Value = get_request(
+                &cli.api_url,
+                client,
+                &format!("/identity/did-icn/{}", did),
+                api_key,
+            )
+            .await?;
+            println!("{}", serde_json::to_string_pretty(&response)?);
+            return Ok(());
+        }
+    };
+    let state: KeyState = post_request(
+        &cli.api_url,
+        client,
+        "/identity/did-icn/events",
+        &event,
+        api_key,
+    )
+    .await?;
+    println!("DID: {} (event {})", state.did, state.sequence);
+    if let Some(next_sk) = new_next_key {
+        println!(
+            "Next key (keep offline until the next rotation): {}",
+            bs58::encode(next_sk.to_bytes()).into_string()
+        );
+    }
+    if state.deactivated {
+        println!("Identifier deactivated");
+    }
+    Ok(())
+}
+
+async fn handle_identity_recovery(
+    cli: &Cli,
+    client: &Client,
+    command: &RecoveryCommands,
 ) -> Result<(), anyhow::Error> {
-    println!("🚧 Federation DID verification not yet implemented");
+    use icn_identity::{
+        recover_next_key, sign_message, KeyEvent, KeyState, RecoveryCase, SignatureBytes,
+        SocialRecoveryConfig,
+    };
+
+    let api_key = cli.api_key.as_deref();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs();
+    let current_state = |did: String| async move {
+        let response: serde_json::Value = get_request(
+            &cli.api_url,
+            client,
+            &format!("/identity/did-icn/{}", did),
+            api_key,
+        )
+        .await?;
+        Ok::<KeyState, anyhow::Error>(serde_json::from_value(response["state"].clone())?)
+    };
+    let get_case = |case_id: String| async move {
+        get_request::<RecoveryCase>(
+            &cli.api_url,
+            client,
+            &format!("/identity/recovery/cases/{}", case_id),
+            api_key,
+        )
+        .await
+    };
+    let case: RecoveryCase = match command {
+        RecoveryCommands::Setup {
+            did,
+            key_bs58,
+            next_key_bs58,
+            guardians,
+            threshold,
+            delay_seconds,
+        } => {
+            let state = current_state(did.clone()).await?;
+            let mut guardian_keys = Vec::new();
+            for guardian in guardians {
+                let guardian = Did::from_str(guardian)?;
+                let key = if guardian.method == icn_identity::DID_ICN_METHOD {
+                    current_state(guardian.to_string()).await?.verifying_key()?
+                } else {
+                    icn_identity::verifying_key_from_did_key(&guardian)?
+                };
+                guardian_keys.push((guardian, key));
+            }
+            let config = SocialRecoveryConfig::create(
+                &state,
+                &signing_key_from_bs58(key_bs58)?,
+                &signing_key_from_bs58(next_key_bs58)?,
+                &guardian_keys,
+                *threshold,
+                *delay_seconds,
+                now,
+            )?;
+            let config: SocialRecoveryConfig = post_request(
+                &cli.api_url,
+                client,
+                "/identity/recovery/config",
+                &config,
+                api_key,
+            )
+            .await?;
+            println!(
+                "{} of {} guardians can recover {} after a {}s delay",
+                config.threshold,
+                config.shares.len(),
+                config.did,
+                config.delay_seconds
+            );
+            return Ok(());
+        }
+        RecoveryCommands::Open {
+            did,
+            guardian,
+            key_bs58,
+        } => {
+            let (session_sk, session_pk) = generate_ed25519_keypair();
+            let signer = signing_key_from_bs58(key_bs58)?;
+            let signature = SignatureBytes::from_ed_signature(sign_message(
+                &signer,
+                &RecoveryCase::opening_bytes(&Did::from_str(did)?, &session_pk),
+            ));
+            let case: RecoveryCase = post_request(
+                &cli.api_url,
+                client,
+                "/identity/recovery/cases",
+                &serde_json::json!({
+                    "did": did,
+                    "recovery_key": session_pk.as_bytes().to_vec(),
+                    "guardian": guardian,
+                    "signature": signature,
+                }),
+                api_key,
+            )
+            .await?;
+            println!(
+                "Session key (needed to complete): {}",
+                bs58::encode(session_sk.to_bytes()).into_string()
+            );
+            case
+        }
+        RecoveryCommands::Approve {
+            case_id,
+            guardian,
+            key_bs58,
+        } => {
+            let case = get_case(case_id.clone()).await?;
+            let config: SocialRecoveryConfig = get_request(
+                &cli.api_url,
+                client,
+                &format!("/identity/recovery/config/{}", case.did),
+                api_key,
+            )
+            .await?;
+            let (approval, share) = case.approve(
+                &config,
+                &Did::from_str(guardian)?,
+                &signing_key_from_bs58(key_bs58)?,
+                now,
+            )?;
+            post_request(
+                &cli.api_url,
+                client,
+                &format!("/identity/recovery/cases/{}/approve", case_id),
+                &serde_json::json!({ "approval": approval, "share": share }),
+                api_key,
+            )
+            .await?
+        }
+        RecoveryCommands::Cancel { case_id, key_bs58 } => {
+            let case = get_case(case_id.clone()).await?;
+            let signature = SignatureBytes::from_ed_signature(sign_message(
+                &signing_key_from_bs58(key_bs58)?,
+                &case.cancellation_bytes(),
+            ));
+            post_request(
+                &cli.api_url,
+                client,
+                &format!("/identity/recovery/cases/{}/cancel", case_id),
+                &serde_json::json!({ "signature": signature }),
+                api_key,
+            )
+            .await?
+        }
+        RecoveryCommands::Complete {
+            case_id,
+            session_key_bs58,
+        } => {
+            let case: RecoveryCase = get_request(
+                &cli.api_url,
+                client,
+                &format!("/identity/recovery/cases/{}/shares", case_id),
+                api_key,
+            )
+            .await?;
+            let state = current_state(case.did.to_string()).await?;
+            let commitment = state
+                .next_key_commitment
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("{} has no committed next key", state.did))?;
+            let recovered = recover_next_key(
+                &case,
+                &commitment,
+                &signing_key_from_bs58(session_key_bs58)?,
+            )?;
+            let (next_sk, next_pk) = generate_ed25519_keypair();
+            let event = KeyEvent::rotation(&state, &recovered, Some(&next_pk), now);
+            let state: KeyState = post_request(
+                &cli.api_url,
+                client,
+                &format!("/identity/recovery/cases/{}/complete", case_id),
+                &event,
+                api_key,
+            )
+            .await?;
+            println!("Recovered {} (event {})", state.did, state.sequence);
+            println!(
+                "Signing key: {}",
+                bs58::encode(recovered.to_bytes()).into_string()
+            );
+            println!(
+                "Next key (keep offline until the next rotation): {}",
+                bs58::encode(next_sk.to_bytes()).into_string()
+            );
+            println!("Run `recovery setup` again to protect the new next key");
+            return Ok(());
+        }
+        RecoveryCommands::Show { case_id } => get_case(case_id.clone()).await?,
+    };
+    println!("{}", serde_json::to_string_pretty(&case)?);
     Ok(())
 }
 
-async fn handle_fed_did_publish(
-    _cli: &Cli,
-    _client: &Client,
-    _federation_id: &str,
-    _document_json_or_stdin: &str,
+async fn handle_identity_personhood(
+    cli: &Cli,
+    client: &Client,
+    command: &PersonhoodCommands,
 ) -> Result<(), anyhow::Error> {
-    println!("🚧 Federation DID publishing not yet implemented");
+    use icn_identity::{Vouch, VouchStake};
+
+    let api_key = cli.api_key.as_deref();
+    let response: serde_json::Value = match command {
+        PersonhoodCommands::Seed { did } => {
+            post_request(
+                &cli.api_url,
+                client,
+                "/identity/personhood/seeds",
+                &serde_json::json!({ "did": did }),
+                api_key,
+            )
+            .await?
+        }
+        PersonhoodCommands::Vouch {
+            vouchee,
+            voucher,
+            key_bs58,
+            mana,
+            reputation,
+            ttl_seconds,
+        } => {
+            let stake = match (mana, reputation) {
+                (Some(amount), _) => VouchStake::Mana(*amount),
+                (None, Some(amount)) => VouchStake::Reputation(*amount),
+                (None, None) => anyhow::bail!("Specify a stake with --mana or --reputation"),
+            };
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs();
+            let mut vouch = Vouch::new(
+                Did::from_str(voucher)?,
+                Did::from_str(vouchee)?,
+                stake,
+                now,
+                *ttl_seconds,
+            );
+            vouch.sign(&signing_key_from_bs58(key_bs58)?)?;
+            post_request(
+                &cli.api_url,
+                client,
+                "/identity/personhood/vouches",
+                &vouch,
+                api_key,
+            )
+            .await?
+        }
+        PersonhoodCommands::Status { did } => {
+            get_request(
+                &cli.api_url,
+                client,
+                &format!("/identity/personhood/{}", did),
+                api_key,
+            )
+            .await?
+        }
+        PersonhoodCommands::Evaluate => {
+            post_request(
+                &cli.api_url,
+                client,
+                "/identity/personhood/evaluate",
+                &serde_json::json!({}),
+                api_key,
+            )
+            .await?
+        }
+        PersonhoodCommands::Revoke { did, reason } => {
+            post_request(
+                &cli.api_url,
+                client,
+                "/identity/personhood/revoke",
+                &serde_json::json!({ "did": did, "reason": reason }),
+                api_key,
+            )
+            .await?
+        }
+        PersonhoodCommands::Credential { did } => {
+            post_request(
+                &cli.api_url,
+                client,
+                "/identity/personhood/credential",
+                &serde_json::json!({ "did": did }),
+                api_key,
+            )
+            .await?
+        }
+    };
+    println!("{}", serde_json::to_string_pretty(&response)?);
     Ok(())
 }
 
-async fn handle_fed_did_resolve(
-    _cli: &Cli,
-    _client: &Client,
-    _did: &str,
-    _federation: &Option<String>,
+async fn handle_fed_application(
+    cli: &Cli,
+    client: &Client,
+    command: &FederationApplicationCommands,
 ) -> Result<(), anyhow::Error> {
-    println!("🚧 Federation DID resolution not yet implemented");
+    use icn_common::Signable;
+    use icn_governance::{
+        ApplicationStatus, ChallengeResponse, MembershipApplication, MembershipApplicationState,
+        MembershipAttestation,
+    };
+
+    let api_key = cli.api_key.as_deref();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs();
+    match command {
+        FederationApplicationCommands::Draft {
+            federation,
+            name,
+            statement,
+            node_peer,
+            credentials,
+            attestations,
+            key_bs58,
+        } => {
+            let sk = signing_key_from_bs58(key_bs58)?;
+            let applicant = Did::from_str(&icn_identity::did_key_from_verifying_key(
+                &sk.verifying_key(),
+            ))?;
+            let credentials = credentials
+                .iter()
+                .map(|path| Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?))
+                .collect::<Result<Vec<icn_identity::Credential>, anyhow::Error>>()?;
+            let attestations = attestations
+                .iter()
+                .map(|path| Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?))
+                .collect::<Result<Vec<MembershipAttestation>, anyhow::Error>>()?;
+            let mut application = MembershipApplication::new(
+                federation.clone(),
+                applicant,
+                name.clone(),
+                statement.clone(),
+                node_peer.clone(),
+                credentials,
+                attestations,
+                now,
+            )?;
+            application.signature = Some(application.sign(&sk)?);
+            println!("{}", serde_json::to_string_pretty(&application)?);
+        }
+        FederationApplicationCommands::Attest {
+            federation,
+            applicant,
+            level,
+            context,
+            key_bs58,
+        } => {
+            let sk = signing_key_from_bs58(key_bs58)?;
+            let mut attestation = MembershipAttestation {
+                applicant: Did::from_str(applicant)?,
+                federation: federation.clone(),
+                attestor: Did::from_str(&icn_identity::did_key_from_verifying_key(
+                    &sk.verifying_key(),
+                ))?,
+                trust_level: serde_json::from_value(serde_json::json!(level))?,
+                context: icn_identity::TrustContext::from_str(context),
+                issued_at: now,
+                signature: None,
+            };
+            attestation.signature = Some(attestation.sign(&sk)?);
+            println!("{}", serde_json::to_string_pretty(&attestation)?);
+        }
+        FederationApplicationCommands::Submit {
+            application_json_or_stdin,
+        } => {
+            let json = if application_json_or_stdin == "-" {
+                let mut buffer = String::new();
+                io::stdin().read_to_string(&mut buffer)?;
+                buffer
+            } else {
+                application_json_or_stdin.clone()
+            };
+            let application: MembershipApplication = serde_json::from_str(&json)?;
+            let state: MembershipApplicationState = post_request(
+                &cli.api_url,
+                client,
+                "/federation/applications",
+                &application,
+                api_key,
+            )
+            .await?;
+            println!("Application {}: {:?}", state.application.id, state.status);
+            for challenge in &state.challenges {
+                println!(
+                    "Challenge {} ({:?}) due by {}",
+                    challenge.challenge_id, challenge.challenge_type, challenge.deadline
+                );
+            }
+        }
+        FederationApplicationCommands::Answer {
+            application_id,
+            key_bs58,
+        } => {
+            let sk = signing_key_from_bs58(key_bs58)?;
+            let state: MembershipApplicationState = get_request(
+                &cli.api_url,
+                client,
+                &format!("/federation/applications/{}", application_id),
+                api_key,
+            )
+            .await?;
+            let mut latest = state.clone();
+            for challenge in state.challenges.iter().filter(|c| !c.completed) {
+                let message = icn_governance::challenge_message(&state.application.id, challenge);
+                let response = ChallengeResponse {
+                    application_id: state.application.id.clone(),
+                    challenge_id: challenge.challenge_id.clone(),
+                    signature: icn_common::SignatureBytes(
+                        icn_identity::sign_message(&sk, &message)
+                            .to_bytes()
+                            .to_vec(),
+                    ),
+                };
+                latest = post_request(
+                    &cli.api_url,
+                    client,
+                    &format!("/federation/applications/{}/challenge", application_id),
+                    &response,
+                    api_key,
+                )
+                .await?;
+                println!("Answered {}", challenge.challenge_id);
+            }
+            println!("Application {}: {:?}", application_id, latest.status);
+        }
+        FederationApplicationCommands::Status {
+            application_id,
+            did_document,
+        } => {
+            let state: MembershipApplicationState = get_request(
+                &cli.api_url,
+                client,
+                &format!("/federation/applications/{}", application_id),
+                api_key,
+            )
+            .await?;
+            println!("{}", serde_json::to_string_pretty(&state)?);
+            if let Some(path) = did_document {
+                let grant = match (&state.status, &state.grant) {
+                    (ApplicationStatus::Accepted, Some(grant)) => grant,
+                    _ => anyhow::bail!("Application {} has not been accepted", application_id),
+                };
+                let mut document: icn_identity::DidDocument =
+                    serde_json::from_str(&std::fs::read_to_string(path)?)?;
+                document.add_federation_membership(grant.did_document_entry());
+                std::fs::write(path, serde_json::to_string_pretty(&document)?)?;
+                println!(
+                    "Added {} membership to {}",
+                    grant.federation_did(),
+                    path.display()
+                );
+            }
+        }
+        FederationApplicationCommands::List => {
+            let states: Vec<MembershipApplicationState> =
+                get_request(&cli.api_url, client, "/federation/applications", api_key).await?;
+            for state in states {
+                println!(
+                    "{}\t{}\t{}\t{:?}",
+                    state.application.id,
+                    state.application.applicant,
+                    state.application.name,
+                    state.status
+                );
+            }
+        }
+        FederationApplicationCommands::Gate { gate_json_or_stdin } => {
+            let json = if gate_json_or_stdin == "-" {
+                let mut buffer = String::new();
+                io::stdin().read_to_string(&mut buffer)?;
+                buffer
+            } else {
+                gate_json_or_stdin.clone()
+            };
+            let gate: icn_governance::federation_governance::FederationMembershipTrustGate =
+                serde_json::from_str(&json)?;
+            let response: serde_json::Value = post_request(
+                &cli.api_url,
+                client,
+                "/federation/applications/gate",
+                &gate,
+                api_key,
+            )
+            .await?;
+            println!("Membership gate set for {}", response["federation"]);
+        }
+    }
     Ok(())
 }
 
@@ -4285,6 +7485,304 @@ async fn handle_credential_list(
     Ok(())
 }
 
+/// Issuer key material written by `credential bbs keygen`.
+#[derive(Serialize, Deserialize)]
+struct BbsIssuerKeys {
+    secret_key_bs58: String,
+    key_binding: icn_identity::BbsKeyBinding,
+}
+
+fn read_file_or_stdin(path: &str) -> Result<String, anyhow::Error> {
+    if path == "-" {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        Ok(buffer)
+    } else {
+        Ok(std::fs::read_to_string(path)?)
+    }
+}
+
+/// Parse repeated `name=value` claim arguments.
+fn parse_claims<C: FromIterator<(String, String)>>(claims: &[String]) -> Result<C, anyhow::Error> {
+    claims
+        .iter()
+        .map(|claim| {
+            claim
+                .split_once('=')
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .ok_or_else(|| anyhow::anyhow!("Expected name=value, got '{}'", claim))
+        })
+        .collect()
+}
+
+fn parse_bbs_bounds(
+    specs: &[String],
+    predicate: fn(String, u64) -> icn_identity::BbsPredicate,
+) -> Result<Vec<icn_identity::BbsPredicate>, anyhow::Error> {
+    specs
+        .iter()
+        .map(|spec| {
+            let (claim, bound) = spec
+                .rsplit_once(':')
+                .ok_or_else(|| anyhow::anyhow!("Expected name:bound, got '{}'", spec))?;
+            Ok(predicate(claim.to_string(), bound.parse()?))
+        })
+        .collect()
+}
+
+async fn handle_credential_bbs(
+    cli: &Cli,
+    client: &Client,
+    command: &credential_lifecycle::BbsCommands,
+) -> Result<(), anyhow::Error> {
+    use credential_lifecycle::BbsCommands;
+    use icn_identity::{
+        BbsCredential, BbsKeyBinding, BbsPredicate, BbsPresentation, BbsPresentationRequest,
+        BbsSecretKey, VerifiablePresentation,
+    };
+
+    match command {
+        BbsCommands::Keygen {
+            issuer_key_bs58,
+            issuer,
+        } => {
+            let signer = signing_key_from_bs58(issuer_key_bs58)?;
+            let issuer = match issuer {
+                Some(did) => Did::from_str(did)?,
+                None => Did::from_str(&icn_identity::did_key_from_verifying_key(
+                    &signer.verifying_key(),
+                ))?,
+            };
+            let key = BbsSecretKey::generate();
+            let keys = BbsIssuerKeys {
+                secret_key_bs58: bs58::encode(key.to_bytes()).into_string(),
+                key_binding: BbsKeyBinding::sign(issuer, &key.public_key(), &signer),
+            };
+            println!("{}", serde_json::to_string_pretty(&keys)?);
+        }
+        BbsCommands::Issue {
+            issuer_keys,
+            holder,
+            schema,
+            claims,
+        } => {
+            let keys: BbsIssuerKeys = serde_json::from_str(&read_file_or_stdin(issuer_keys)?)?;
+            let key = BbsSecretKey::from_bytes(&bs58::decode(&keys.secret_key_bs58).into_vec()?)?;
+            let credential = BbsCredential::issue(
+                keys.key_binding.issuer.clone(),
+                Did::from_str(holder)?,
+                icn_common::parse_cid_from_string(schema)?,
+                parse_claims(claims)?,
+                &key,
+                keys.key_binding,
+            )?;
+            println!("{}", serde_json::to_string_pretty(&credential)?);
+        }
+        BbsCommands::Present {
+            credential_path,
+            nonce,
+            disclose,
+            at_least,
+            at_most,
+            disclose_holder,
+            presentation,
+        } => {
+            let credential: BbsCredential =
+                serde_json::from_str(&read_file_or_stdin(credential_path)?)?;
+            let mut predicates = parse_bbs_bounds(at_least, |claim, bound| {
+                BbsPredicate::AtLeast { claim, bound }
+            })?;
+            predicates.extend(parse_bbs_bounds(at_most, |claim, bound| {
+                BbsPredicate::AtMost { claim, bound }
+            })?);
+            let derived = credential.present(&BbsPresentationRequest {
+                disclose: disclose.clone(),
+                predicates,
+                disclose_holder: *disclose_holder,
+                nonce: nonce.clone(),
+            })?;
+            if *presentation {
+                let holder = derived.holder.clone();
+                let vp = VerifiablePresentation::from_bbs(vec![derived], holder);
+                println!("{}", serde_json::to_string_pretty(&vp)?);
+            } else {
+                println!("{}", serde_json::to_string_pretty(&derived.to_zk_proof()?)?);
+            }
+        }
+        BbsCommands::Verify { proof_path } => {
+            let value: JsonValue = serde_json::from_str(&read_file_or_stdin(proof_path)?)?;
+            let proofs = if value.get("derived_credentials").is_some() {
+                let vp: VerifiablePresentation = serde_json::from_value(value)?;
+                vp.derived_credentials
+                    .iter()
+                    .map(BbsPresentation::to_zk_proof)
+                    .collect::<Result<Vec<_>, _>>()?
+            } else if value.get("backend").is_some() {
+                vec![serde_json::from_value::<ZkCredentialProof>(value)?]
+            } else {
+                vec![serde_json::from_value::<BbsPresentation>(value)?.to_zk_proof()?]
+            };
+            let resp: BatchVerificationResponse = post_request(
+                &cli.api_url,
+                client,
+                "/identity/verify/batch",
+                &VerifyProofsRequest { proofs },
+                cli.api_key.as_deref(),
+            )
+            .await?;
+            println!("{}", serde_json::to_string_pretty(&resp)?);
+        }
+    }
+    Ok(())
+}
+
+async fn handle_credential_schema(
+    cli: &Cli,
+    client: &Client,
+    command: &credential_lifecycle::SchemaCommands,
+) -> Result<(), anyhow::Error> {
+    use credential_lifecycle::SchemaCommands;
+    use icn_api::identity_trait::{PublishSchemaRequest, SchemaResponse};
+
+    match command {
+        SchemaCommands::Publish {
+            name,
+            version,
+            schema_path,
+        } => {
+            let request = PublishSchemaRequest {
+                name: name.clone(),
+                version: version.clone(),
+                json_schema: serde_json::from_str(&read_file_or_stdin(schema_path)?)?,
+            };
+            let resp: SchemaResponse = post_request(
+                &cli.api_url,
+                client,
+                "/identity/schemas",
+                &request,
+                cli.api_key.as_deref(),
+            )
+            .await?;
+            println!("{}", serde_json::to_string_pretty(&resp)?);
+        }
+        SchemaCommands::List => {
+            let resp: Vec<SchemaResponse> = get_request(
+                &cli.api_url,
+                client,
+                "/identity/schemas",
+                cli.api_key.as_deref(),
+            )
+            .await?;
+            println!("{}", serde_json::to_string_pretty(&resp)?);
+        }
+        SchemaCommands::Show { cid } => {
+            let resp: SchemaResponse = get_request(
+                &cli.api_url,
+                client,
+                &format!("/identity/schemas/{cid}"),
+                cli.api_key.as_deref(),
+            )
+            .await?;
+            println!("{}", serde_json::to_string_pretty(&resp)?);
+        }
+    }
+    Ok(())
+}
+
+async fn handle_credential_policy(
+    cli: &Cli,
+    client: &Client,
+    command: &credential_lifecycle::PolicyCommands,
+) -> Result<(), anyhow::Error> {
+    use credential_lifecycle::PolicyCommands;
+
+    match command {
+        PolicyCommands::Set {
+            cooperative_id,
+            credential_type,
+            issuers,
+            schema,
+            max_validity,
+        } => {
+            let policy = icn_identity::IssuerPolicy {
+                cooperative_id: cooperative_id.clone(),
+                credential_type: credential_type.clone(),
+                issuers: issuers
+                    .iter()
+                    .map(|did| Did::from_str(did))
+                    .collect::<Result<_, _>>()?,
+                schema: schema
+                    .as_deref()
+                    .map(icn_common::parse_cid_from_string)
+                    .transpose()?,
+                max_validity: *max_validity,
+            };
+            let resp: Option<icn_identity::IssuerPolicy> = post_request(
+                &cli.api_url,
+                client,
+                "/identity/issuance/policies",
+                &policy,
+                cli.api_key.as_deref(),
+            )
+            .await?;
+            println!("{}", serde_json::to_string_pretty(&resp)?);
+        }
+        PolicyCommands::List { cooperative_id } => {
+            let path = match cooperative_id {
+                Some(id) => format!("/identity/issuance/policies?cooperative_id={id}"),
+                None => "/identity/issuance/policies".to_string(),
+            };
+            let resp: Vec<icn_identity::IssuerPolicy> =
+                get_request(&cli.api_url, client, &path, cli.api_key.as_deref()).await?;
+            println!("{}", serde_json::to_string_pretty(&resp)?);
+        }
+    }
+    Ok(())
+}
+
+async fn handle_credential_offer(
+    cli: &Cli,
+    client: &Client,
+    terms: &icn_identity::OfferTerms,
+) -> Result<(), anyhow::Error> {
+    let offer: icn_identity::IssuanceOffer = post_request(
+        &cli.api_url,
+        client,
+        "/identity/issuance/offers",
+        terms,
+        cli.api_key.as_deref(),
+    )
+    .await?;
+    println!("{}", serde_json::to_string_pretty(&offer)?);
+    Ok(())
+}
+
+async fn handle_credential_accept(
+    cli: &Cli,
+    client: &Client,
+    offer_id: &str,
+    key_bs58: &str,
+) -> Result<(), anyhow::Error> {
+    let offer: icn_identity::IssuanceOffer = get_request(
+        &cli.api_url,
+        client,
+        &format!("/identity/issuance/offers/{offer_id}"),
+        cli.api_key.as_deref(),
+    )
+    .await?;
+    let request = icn_identity::IssuanceRequest::sign(&offer, &signing_key_from_bs58(key_bs58)?);
+    let resp: icn_api::identity_trait::CredentialResponse = post_request(
+        &cli.api_url,
+        client,
+        "/identity/issuance/requests",
+        &request,
+        cli.api_key.as_deref(),
+    )
+    .await?;
+    println!("{}", serde_json::to_string_pretty(&resp)?);
+    Ok(())
+}
+
 async fn handle_credential_revoke(
     cli: &Cli,
     client: &Client,
@@ -4307,6 +7805,102 @@ async fn handle_credential_revoke(
     Ok(())
 }
 
+async fn handle_credential_set_status(
+    cli: &Cli,
+    client: &Client,
+    cid: &str,
+    action: &str,
+) -> Result<(), anyhow::Error> {
+    let cid = icn_common::parse_cid_from_string(cid)?;
+    let request = icn_api::identity_trait::RevokeCredentialRequest { cid };
+    let resp: serde_json::Value = post_request(
+        &cli.api_url,
+        client,
+        &format!("/identity/credentials/{action}"),
+        &request,
+        cli.api_key.as_deref(),
+    )
+    .await?;
+    println!("{}", serde_json::to_string_pretty(&resp)?);
+    Ok(())
+}
+
+async fn handle_credential_status_list(
+    cli: &Cli,
+    client: &Client,
+    id: &str,
+    purpose: &str,
+) -> Result<(), anyhow::Error> {
+    if !matches!(purpose, "revocation" | "suspension") {
+        return Err(anyhow::anyhow!(
+            "Unknown status purpose '{}': expected revocation or suspension",
+            purpose
+        ));
+    }
+    let list: icn_identity::StatusListCredential = get_request(
+        &cli.api_url,
+        client,
+        &format!("/identity/status-lists/{id}/{purpose}"),
+        cli.api_key.as_deref(),
+    )
+    .await?;
+    let set = (0..list.encoded_list.len() as u64)
+        .filter(|i| list.encoded_list.get(*i) == Some(true))
+        .count();
+    println!("{}", serde_json::to_string_pretty(&list)?);
+    eprintln!(
+        "{} of {} entries set (version {})",
+        set,
+        list.encoded_list.len(),
+        list.version
+    );
+    Ok(())
+}
+
+async fn handle_credential_export(
+    cli: &Cli,
+    client: &Client,
+    cid: &str,
+    format: &str,
+) -> Result<(), anyhow::Error> {
+    let request = icn_api::identity_trait::ExportCredentialRequest {
+        cid: icn_common::parse_cid_from_string(cid)?,
+        format: icn_identity::CredentialFormat::from_str(format)?,
+    };
+    let resp: icn_api::identity_trait::ExportCredentialResponse = post_request(
+        &cli.api_url,
+        client,
+        "/identity/credentials/export",
+        &request,
+        cli.api_key.as_deref(),
+    )
+    .await?;
+    println!("{}", resp.credential);
+    Ok(())
+}
+
+async fn handle_credential_import(
+    cli: &Cli,
+    client: &Client,
+    input: &str,
+    format: &str,
+) -> Result<(), anyhow::Error> {
+    let request = icn_api::identity_trait::ImportCredentialRequest {
+        format: icn_identity::CredentialFormat::from_str(format)?,
+        credential: read_file_or_stdin(input)?,
+    };
+    let imported: icn_identity::ImportedCredential = post_request(
+        &cli.api_url,
+        client,
+        "/identity/credentials/import",
+        &request,
+        cli.api_key.as_deref(),
+    )
+    .await?;
+    println!("{}", serde_json::to_string_pretty(&imported)?);
+    Ok(())
+}
+
 async fn handle_credential_example(
     _cli: &Cli,
     _client: &Client,
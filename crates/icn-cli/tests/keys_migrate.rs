@@ -0,0 +1,46 @@
+use std::process::Command;
+
+#[test]
+fn keys_migrate_moves_plaintext_key_into_keystore() {
+    let bin = env!("CARGO_BIN_EXE_icn-cli");
+    let dir = tempfile::tempdir().unwrap();
+    let keystore = dir.path().join("node.keystore.json");
+    let (sk, pk) = icn_identity::generate_ed25519_keypair();
+    let did = icn_identity::did_key_from_verifying_key(&pk);
+    let write_key = |name: &str| {
+        let path = dir.path().join(name);
+        std::fs::write(&path, bs58::encode(sk.to_bytes()).into_string()).unwrap();
+        path
+    };
+    let migrate = |from: &std::path::Path, label: &str, extra: &[&str]| {
+        Command::new(bin)
+            .args(["keys", "migrate", "--from"])
+            .arg(from)
+            .arg("--keystore")
+            .arg(&keystore)
+            .args(["--label", label])
+            .args(extra)
+            .env("ICN_KEY_PASSPHRASE", "migrate")
+            .output()
+            .unwrap()
+    };
+
+    let plaintext = write_key("node.key");
+    let output = migrate(&plaintext, "node", &[]);
+    assert!(output.status.success(), "{output:?}");
+    let entry: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(entry["did"], did);
+    assert!(!plaintext.exists());
+
+    let kept = write_key("backup.key");
+    let output = migrate(&kept, "backup", &["--keep-source"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(kept.exists());
+
+    let keystore = icn_identity::Keystore::open(&keystore, b"migrate").unwrap();
+    assert_eq!(
+        keystore.signing_key("node").unwrap().to_bytes(),
+        sk.to_bytes()
+    );
+    assert!(keystore.entry("backup").is_some());
+}
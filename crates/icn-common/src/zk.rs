@@ -9,6 +9,8 @@ pub enum ZkProofType {
     Groth16,
     /// Bulletproofs proving system
     Bulletproofs,
+    /// BBS signatures over BLS12-381 with derived selective-disclosure proofs
+    Bbs,
     /// Catch-all for custom or future proof systems
     Other(String),
 }
@@ -1113,6 +1113,7 @@ impl GovernanceAutomationEngine {
                 ProposalType::NewMemberInvitation(_) => 50,      // Lower reputation for invitations
                 ProposalType::RemoveMember(_) => 70,             // Higher reputation for removal
                 ProposalType::Resolution(_) => 60, // Medium reputation for resolutions
+                ProposalType::ConstitutionAmendment(_) => 80, // High reputation for amendments
                 ProposalType::GenericText(_) => 25, // Basic reputation for text proposals
             };
 
@@ -1124,6 +1125,7 @@ impl GovernanceAutomationEngine {
                 ProposalType::NewMemberInvitation(_) => 200,       // Lower stake for invitations
                 ProposalType::RemoveMember(_) => 800,              // Higher stake for removal
                 ProposalType::Resolution(_) => 400,                // Medium stake for resolutions
+                ProposalType::ConstitutionAmendment(_) => 1000,    // High stake for amendments
                 ProposalType::GenericText(_) => 50, // Minimal stake for text proposals
             };
 
@@ -1216,6 +1218,7 @@ impl GovernanceAutomationEngine {
             ProposalType::GenericText(_) => "generic_text",
             ProposalType::BudgetAllocation(_, _, _) => "budget_allocation",
             ProposalType::Resolution(_) => "resolution",
+            ProposalType::ConstitutionAmendment(_) => "constitution_amendment",
         }
     }
 
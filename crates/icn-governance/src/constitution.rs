@@ -0,0 +1,770 @@
+//! Constitution and bylaws
+//!
+//! A [`Constitution`] collects the rules a cooperative governs itself by:
+//! who may join, how each [`ProposalClass`] is decided and what it takes to
+//! amend the document itself. Each version is anchored as a DAG block linked
+//! to the version it replaces, so the full history can be verified.
+//!
+//! Once a constitution is adopted the [`GovernanceModule`] enforces it:
+//!
+//! * proposals get at least the quorum, threshold and voting period of their
+//!   class, whatever the submitter asked for;
+//! * [`VotingMethod::Consensus`] classes fail on any `No` vote and
+//!   [`VotingMethod::SecretBallot`] classes refuse public votes;
+//! * member invitations are refused once `max_members` is reached;
+//! * [`ProposalType::ConstitutionAmendment`] proposals carry the next version
+//!   as JSON and are decided under the document's own
+//!   [`AmendmentProcedure`].
+//!
+//! The rules that apply to a proposal are those of the version in force when
+//! it was submitted.
+
+use crate::deliberation::anchor;
+use crate::{GovernanceModule, Proposal, ProposalId, ProposalSubmission, ProposalType};
+use icn_common::{Cid, CommonError, DagBlock, DagLink, Did};
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Proposal categories that a constitution can govern differently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ProposalClass {
+    ParameterChange,
+    MemberAdmission,
+    MemberRemoval,
+    SoftwareUpgrade,
+    Text,
+    Budget,
+    Resolution,
+    Amendment,
+}
+
+impl ProposalClass {
+    /// Class a proposal type belongs to.
+    pub fn of(proposal_type: &ProposalType) -> Self {
+        match proposal_type {
+            ProposalType::SystemParameterChange(_, _) => ProposalClass::ParameterChange,
+            ProposalType::NewMemberInvitation(_) => ProposalClass::MemberAdmission,
+            ProposalType::RemoveMember(_) => ProposalClass::MemberRemoval,
+            ProposalType::SoftwareUpgrade(_) => ProposalClass::SoftwareUpgrade,
+            ProposalType::GenericText(_) => ProposalClass::Text,
+            ProposalType::BudgetAllocation(_, _, _) => ProposalClass::Budget,
+            ProposalType::Resolution(_) => ProposalClass::Resolution,
+            ProposalType::ConstitutionAmendment(_) => ProposalClass::Amendment,
+        }
+    }
+}
+
+/// How votes on a proposal are counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum VotingMethod {
+    /// `Yes` votes must reach the threshold share of all votes.
+    #[default]
+    Threshold,
+    /// As `Threshold`, but a single `No` vote blocks the proposal.
+    Consensus,
+    /// As `Threshold`, with anonymous ballots only.
+    SecretBallot,
+}
+
+impl fmt::Display for VotingMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VotingMethod::Threshold => write!(f, "threshold"),
+            VotingMethod::Consensus => write!(f, "consensus"),
+            VotingMethod::SecretBallot => write!(f, "secret_ballot"),
+        }
+    }
+}
+
+/// Minimum requirements for deciding a proposal.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DecisionRule {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub method: VotingMethod,
+    pub quorum: usize,
+    /// Share of all votes that must be `Yes`, from 0 to 1.
+    pub threshold: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min_voting_period_secs: u64,
+}
+
+/// Decision rule for one proposal class.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClassRule {
+    pub class: ProposalClass,
+    pub rule: DecisionRule,
+}
+
+/// Who may belong to the cooperative.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MembershipRules {
+    /// Sponsors a proposal needs before deliberation.
+    pub min_sponsors: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_members: Option<usize>,
+}
+
+/// What it takes to amend the constitution.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AmendmentProcedure {
+    pub rule: DecisionRule,
+    /// Time-lock between acceptance and the amendment taking effect.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub timelock_secs: u64,
+}
+
+/// A version of the cooperative's bylaws.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Constitution {
+    /// Starts at 1 and increases by one with every amendment.
+    pub version: u32,
+    pub title: String,
+    /// Human readable bylaws text.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub text: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub membership: MembershipRules,
+    /// Rule for classes without an entry in `classes`.
+    pub default_rule: DecisionRule,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub classes: Vec<ClassRule>,
+    pub amendment: AmendmentProcedure,
+}
+
+fn validate_rule(rule: &DecisionRule, context: &str) -> Result<(), CommonError> {
+    if !(0.0..=1.0).contains(&rule.threshold) {
+        return Err(CommonError::InvalidInputError(format!(
+            "{}: threshold must be between 0 and 1",
+            context
+        )));
+    }
+    if rule.quorum == 0 {
+        return Err(CommonError::InvalidInputError(format!(
+            "{}: quorum must be at least 1",
+            context
+        )));
+    }
+    Ok(())
+}
+
+impl Constitution {
+    /// Check the document is internally consistent.
+    pub fn validate(&self) -> Result<(), CommonError> {
+        validate_rule(&self.default_rule, "default rule")?;
+        validate_rule(&self.amendment.rule, "amendment rule")?;
+        for (i, entry) in self.classes.iter().enumerate() {
+            validate_rule(&entry.rule, &format!("rule for {:?}", entry.class))?;
+            if self.classes[..i].iter().any(|c| c.class == entry.class) {
+                return Err(CommonError::InvalidInputError(format!(
+                    "Class {:?} has more than one rule",
+                    entry.class
+                )));
+            }
+        }
+        if self
+            .classes
+            .iter()
+            .any(|c| c.class == ProposalClass::Amendment)
+        {
+            return Err(CommonError::InvalidInputError(
+                "Amendments are governed by the amendment procedure, not a class rule".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Rule that decides proposals of `class`.
+    pub fn rule_for(&self, class: ProposalClass) -> &DecisionRule {
+        if class == ProposalClass::Amendment {
+            return &self.amendment.rule;
+        }
+        self.classes
+            .iter()
+            .find(|c| c.class == class)
+            .map(|c| &c.rule)
+            .unwrap_or(&self.default_rule)
+    }
+
+    /// Decode the constitution carried by an amendment proposal.
+    pub fn from_proposal_payload(payload: &str) -> Result<Self, CommonError> {
+        serde_json::from_str(payload).map_err(|e| {
+            CommonError::DeserializationError(format!("Invalid constitution document: {}", e))
+        })
+    }
+
+    /// Encode as the payload of a [`ProposalType::ConstitutionAmendment`].
+    pub fn to_proposal_payload(&self) -> Result<String, CommonError> {
+        serde_json::to_string(self).map_err(|e| {
+            CommonError::SerializationError(format!("Failed to encode constitution: {}", e))
+        })
+    }
+
+    /// Field-level differences from `self` to `other`.
+    pub fn diff(&self, other: &Constitution) -> Result<Vec<ConstitutionChange>, CommonError> {
+        let encode = |c: &Constitution| {
+            serde_json::to_value(c).map_err(|e| {
+                CommonError::SerializationError(format!("Failed to encode constitution: {}", e))
+            })
+        };
+        let mut before = Vec::new();
+        let mut after = Vec::new();
+        flatten("", &encode(self)?, &mut before);
+        flatten("", &encode(other)?, &mut after);
+
+        let mut changes = Vec::new();
+        for (path, old) in &before {
+            let new = after.iter().find(|(p, _)| p == path).map(|(_, v)| v);
+            if new != Some(old) {
+                changes.push(ConstitutionChange {
+                    path: path.clone(),
+                    before: Some(old.clone()),
+                    after: new.cloned(),
+                });
+            }
+        }
+        for (path, new) in &after {
+            if !before.iter().any(|(p, _)| p == path) {
+                changes.push(ConstitutionChange {
+                    path: path.clone(),
+                    before: None,
+                    after: Some(new.clone()),
+                });
+            }
+        }
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(changes)
+    }
+}
+
+/// Flatten a JSON value into `path = value` leaves. Class rules are keyed by
+/// class name rather than position so reordering them is not a change.
+fn flatten(path: &str, value: &serde_json::Value, out: &mut Vec<(String, String)>) {
+    let join = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    };
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, v) in map {
+                flatten(&join(key), v, out);
+            }
+        }
+        serde_json::Value::Array(items) if path == "classes" => {
+            for item in items {
+                let class = item["class"].as_str().unwrap_or_default();
+                flatten(&join(class), &item["rule"], out);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (i, v) in items.iter().enumerate() {
+                flatten(&join(&i.to_string()), v, out);
+            }
+        }
+        serde_json::Value::String(s) => out.push((path.to_string(), s.clone())),
+        other => out.push((path.to_string(), other.to_string())),
+    }
+}
+
+/// A single field that differs between two constitution versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConstitutionChange {
+    /// Dotted path of the field, e.g. `classes.budget.threshold`.
+    pub path: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// An adopted constitution version and where it is anchored.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConstitutionRecord {
+    pub constitution: Constitution,
+    pub cid: Cid,
+    /// CID of the version this one replaced.
+    pub previous: Option<Cid>,
+    pub adopted_at: u64,
+    /// Amendment proposal that adopted this version; `None` for the first.
+    pub adopted_by: Option<ProposalId>,
+    pub author: Did,
+}
+
+/// Adopted constitution versions, oldest first.
+#[derive(Debug, Default)]
+pub struct ConstitutionRegistry {
+    versions: Vec<ConstitutionRecord>,
+    #[cfg(feature = "persist-sled")]
+    tree: Option<sled::Tree>,
+}
+
+const STATE_KEY: &[u8] = b"constitution";
+
+fn constitution_block(
+    constitution: &Constitution,
+    previous: Option<&Cid>,
+    author: &Did,
+    adopted_at: u64,
+) -> Result<DagBlock, CommonError> {
+    let links = previous
+        .map(|cid| {
+            vec![DagLink {
+                cid: cid.clone(),
+                name: "previous".to_string(),
+                size: 0,
+            }]
+        })
+        .unwrap_or_default();
+    anchor(constitution, links, author, adopted_at, None)
+}
+
+impl ConstitutionRegistry {
+    /// Registry whose versions persist in `tree`.
+    #[cfg(feature = "persist-sled")]
+    pub fn sled(tree: sled::Tree) -> Result<Self, CommonError> {
+        let mut registry = Self::default();
+        if let Some(bytes) = tree.get(STATE_KEY).map_err(|e| {
+            CommonError::DatabaseError(format!("Failed to load constitution: {}", e))
+        })? {
+            registry.versions = bincode::deserialize(&bytes).map_err(|e| {
+                CommonError::DeserializationError(format!(
+                    "Failed to deserialize constitution: {}",
+                    e
+                ))
+            })?;
+        }
+        registry.tree = Some(tree);
+        Ok(registry)
+    }
+
+    /// Version currently in force.
+    pub fn current(&self) -> Option<&ConstitutionRecord> {
+        self.versions.last()
+    }
+
+    /// Version in force at unix time `at`.
+    pub fn in_force_at(&self, at: u64) -> Option<&ConstitutionRecord> {
+        self.versions.iter().rev().find(|r| r.adopted_at <= at)
+    }
+
+    /// A specific version.
+    pub fn version(&self, version: u32) -> Option<&ConstitutionRecord> {
+        self.versions
+            .iter()
+            .find(|r| r.constitution.version == version)
+    }
+
+    /// Every adopted version, oldest first.
+    pub fn history(&self) -> &[ConstitutionRecord] {
+        &self.versions
+    }
+
+    /// DAG block anchoring `version`.
+    pub fn block(&self, version: u32) -> Result<Option<DagBlock>, CommonError> {
+        self.version(version)
+            .map(|r| {
+                constitution_block(
+                    &r.constitution,
+                    r.previous.as_ref(),
+                    &r.author,
+                    r.adopted_at,
+                )
+            })
+            .transpose()
+    }
+
+    fn adopt(
+        &mut self,
+        constitution: Constitution,
+        adopted_by: Option<ProposalId>,
+        author: Did,
+        adopted_at: u64,
+    ) -> Result<DagBlock, CommonError> {
+        constitution.validate()?;
+        let expected = self.current().map_or(1, |c| c.constitution.version + 1);
+        if constitution.version != expected {
+            return Err(CommonError::InvalidInputError(format!(
+                "Expected constitution version {}, got {}",
+                expected, constitution.version
+            )));
+        }
+        let previous = self.current().map(|c| c.cid.clone());
+        let block = constitution_block(&constitution, previous.as_ref(), &author, adopted_at)?;
+        self.versions.push(ConstitutionRecord {
+            constitution,
+            cid: block.cid.clone(),
+            previous,
+            adopted_at,
+            adopted_by,
+            author,
+        });
+        if let Err(e) = self.persist() {
+            self.versions.pop();
+            return Err(e);
+        }
+        Ok(block)
+    }
+
+    fn persist(&self) -> Result<(), CommonError> {
+        #[cfg(feature = "persist-sled")]
+        if let Some(tree) = &self.tree {
+            let encoded = bincode::serialize(&self.versions).map_err(|e| {
+                CommonError::SerializationError(format!("Failed to serialize constitution: {}", e))
+            })?;
+            tree.insert(STATE_KEY, encoded).map_err(|e| {
+                CommonError::DatabaseError(format!("Failed to persist constitution: {}", e))
+            })?;
+            tree.flush().map_err(|e| {
+                CommonError::DatabaseError(format!("Failed to flush constitution: {}", e))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for VotingMethod {
+    type Err = CommonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "threshold" => Ok(VotingMethod::Threshold),
+            "consensus" => Ok(VotingMethod::Consensus),
+            "secret_ballot" | "secret-ballot" => Ok(VotingMethod::SecretBallot),
+            other => Err(CommonError::InvalidInputError(format!(
+                "Unknown voting method {}",
+                other
+            ))),
+        }
+    }
+}
+
+impl GovernanceModule {
+    /// The constitution versions adopted by this cooperative.
+    pub fn constitution(&self) -> &ConstitutionRegistry {
+        &self.constitution
+    }
+
+    /// Adopt the first constitution. Later versions must go through a
+    /// [`ProposalType::ConstitutionAmendment`] proposal.
+    ///
+    /// Returns the block anchoring the document.
+    pub fn adopt_constitution(
+        &mut self,
+        constitution: Constitution,
+        adopted_by: Did,
+        now: u64,
+    ) -> Result<DagBlock, CommonError> {
+        if self.constitution.current().is_some() {
+            return Err(CommonError::PolicyDenied(
+                "A constitution is already in force; propose an amendment instead".to_string(),
+            ));
+        }
+        let block = self
+            .constitution
+            .adopt(constitution, None, adopted_by, now)?;
+        self.sync_constitution();
+        Ok(block)
+    }
+
+    /// Decision rule that applies to `proposal`, if a constitution was in
+    /// force when it was submitted.
+    pub fn constitutional_rule(&self, proposal: &Proposal) -> Option<&DecisionRule> {
+        self.constitution.in_force_at(proposal.created_at).map(|r| {
+            r.constitution
+                .rule_for(ProposalClass::of(&proposal.proposal_type))
+        })
+    }
+
+    /// Raise a submission to the requirements of the constitution in force.
+    pub(crate) fn apply_constitution(
+        &self,
+        mut submission: ProposalSubmission,
+    ) -> Result<ProposalSubmission, CommonError> {
+        let Some(current) = self.constitution.current() else {
+            if let ProposalType::ConstitutionAmendment(_) = submission.proposal_type {
+                return Err(CommonError::PolicyDenied(
+                    "No constitution has been adopted yet".to_string(),
+                ));
+            }
+            return Ok(submission);
+        };
+        let constitution = &current.constitution;
+        let class = ProposalClass::of(&submission.proposal_type);
+        match &submission.proposal_type {
+            ProposalType::ConstitutionAmendment(payload) => {
+                let proposed = Constitution::from_proposal_payload(payload)?;
+                proposed.validate()?;
+                if proposed.version != constitution.version + 1 {
+                    return Err(CommonError::InvalidInputError(format!(
+                        "An amendment to version {} must propose version {}",
+                        constitution.version,
+                        constitution.version + 1
+                    )));
+                }
+                submission.timelock_delay = Some(
+                    submission
+                        .timelock_delay
+                        .unwrap_or(0)
+                        .max(constitution.amendment.timelock_secs),
+                );
+            }
+            ProposalType::NewMemberInvitation(_) => {
+                if let Some(max) = constitution.membership.max_members {
+                    if self.members.len() >= max {
+                        return Err(CommonError::PolicyDenied(format!(
+                            "The constitution limits membership to {}",
+                            max
+                        )));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        let rule = constitution.rule_for(class);
+        if submission.duration_secs < rule.min_voting_period_secs {
+            return Err(CommonError::PolicyDenied(format!(
+                "{:?} proposals must be open for at least {} seconds",
+                class, rule.min_voting_period_secs
+            )));
+        }
+        submission.quorum = Some(submission.quorum.unwrap_or(0).max(rule.quorum));
+        submission.threshold = Some(submission.threshold.unwrap_or(0.0).max(rule.threshold));
+        Ok(submission)
+    }
+
+    /// Adopt the version carried by an executed amendment proposal.
+    pub(crate) fn apply_constitution_amendment(
+        &mut self,
+        proposal: &Proposal,
+    ) -> Result<Option<DagBlock>, CommonError> {
+        let ProposalType::ConstitutionAmendment(payload) = &proposal.proposal_type else {
+            return Ok(None);
+        };
+        let constitution = Constitution::from_proposal_payload(payload)?;
+        let block = self.constitution.adopt(
+            constitution,
+            Some(proposal.id.clone()),
+            proposal.proposer.clone(),
+            proposal.voting_deadline,
+        )?;
+        self.sync_constitution();
+        Ok(Some(block))
+    }
+
+    /// Apply constitutional membership rules to this module.
+    pub(crate) fn sync_constitution(&mut self) {
+        if let Some(current) = self.constitution.current() {
+            self.config.min_sponsors = current.constitution.membership.min_sponsors;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ProposalStatus, VoteOption};
+    use icn_common::FixedTimeProvider;
+
+    fn rule(quorum: usize, threshold: f32) -> DecisionRule {
+        DecisionRule {
+            method: VotingMethod::Threshold,
+            quorum,
+            threshold,
+            min_voting_period_secs: 0,
+        }
+    }
+
+    fn bylaws() -> Constitution {
+        Constitution {
+            version: 1,
+            title: "Bylaws".to_string(),
+            text: "We cooperate.".to_string(),
+            membership: MembershipRules {
+                min_sponsors: 1,
+                max_members: Some(3),
+            },
+            default_rule: rule(1, 0.5),
+            classes: vec![ClassRule {
+                class: ProposalClass::Budget,
+                rule: DecisionRule {
+                    method: VotingMethod::Consensus,
+                    ..rule(2, 0.5)
+                },
+            }],
+            amendment: AmendmentProcedure {
+                rule: rule(3, 0.75),
+                timelock_secs: 0,
+            },
+        }
+    }
+
+    fn did(name: &str) -> Did {
+        Did::from_str(&format!("did:example:{}", name)).unwrap()
+    }
+
+    fn submit(gov: &mut GovernanceModule, proposal_type: ProposalType, at: u64) -> ProposalId {
+        gov.submit_proposal(
+            ProposalSubmission {
+                proposer: did("alice"),
+                proposal_type,
+                description: format!("proposal at {}", at),
+                duration_secs: 100,
+                quorum: Some(1),
+                threshold: Some(0.1),
+                content_cid: None,
+                timelock_delay: None,
+            },
+            &FixedTimeProvider::new(at),
+        )
+        .unwrap()
+    }
+
+    fn decide(gov: &mut GovernanceModule, pid: &ProposalId, votes: &[(&str, VoteOption)]) {
+        gov.open_voting(pid).unwrap();
+        for (voter, option) in votes {
+            gov.cast_vote(did(voter), pid, *option, &FixedTimeProvider::new(50))
+                .unwrap();
+        }
+        gov.close_voting_period(pid, &FixedTimeProvider::new(50))
+            .unwrap();
+    }
+
+    #[test]
+    fn class_rules_override_submissions_and_consensus_blocks() {
+        let mut gov = GovernanceModule::new();
+        for name in ["alice", "bob", "carol"] {
+            gov.add_member(did(name));
+        }
+        gov.adopt_constitution(bylaws(), did("alice"), 0).unwrap();
+
+        let budget = submit(
+            &mut gov,
+            ProposalType::BudgetAllocation(did("bob"), 10, "tools".into()),
+            1,
+        );
+        let proposal = gov.get_proposal(&budget).unwrap().unwrap();
+        assert_eq!((proposal.quorum, proposal.threshold), (Some(2), Some(0.5)));
+        decide(
+            &mut gov,
+            &budget,
+            &[
+                ("alice", VoteOption::Yes),
+                ("bob", VoteOption::Yes),
+                ("carol", VoteOption::No),
+            ],
+        );
+        assert_eq!(
+            gov.get_proposal(&budget).unwrap().unwrap().status,
+            ProposalStatus::Rejected
+        );
+
+        // Membership is capped at three.
+        assert!(gov
+            .submit_proposal(
+                ProposalSubmission {
+                    proposer: did("alice"),
+                    proposal_type: ProposalType::NewMemberInvitation(did("dave")),
+                    description: "invite dave".into(),
+                    duration_secs: 100,
+                    quorum: None,
+                    threshold: None,
+                    content_cid: None,
+                    timelock_delay: None,
+                },
+                &FixedTimeProvider::new(2),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn amendments_need_the_supermajority_they_specify() {
+        let mut gov = GovernanceModule::new();
+        for name in ["alice", "bob", "carol"] {
+            gov.add_member(did(name));
+        }
+        let genesis = gov.adopt_constitution(bylaws(), did("alice"), 0).unwrap();
+        assert!(gov.adopt_constitution(bylaws(), did("alice"), 1).is_err());
+
+        let mut next = bylaws();
+        next.version = 2;
+        next.membership.max_members = Some(10);
+        let payload = next.to_proposal_payload().unwrap();
+
+        let failed = submit(
+            &mut gov,
+            ProposalType::ConstitutionAmendment(payload.clone()),
+            1,
+        );
+        decide(
+            &mut gov,
+            &failed,
+            &[
+                ("alice", VoteOption::Yes),
+                ("bob", VoteOption::Yes),
+                ("carol", VoteOption::No),
+            ],
+        );
+        assert_eq!(
+            gov.get_proposal(&failed).unwrap().unwrap().status,
+            ProposalStatus::Rejected
+        );
+
+        let passed = submit(&mut gov, ProposalType::ConstitutionAmendment(payload), 2);
+        decide(
+            &mut gov,
+            &passed,
+            &[
+                ("alice", VoteOption::Yes),
+                ("bob", VoteOption::Yes),
+                ("carol", VoteOption::Yes),
+            ],
+        );
+        gov.execute_proposal(&passed).unwrap();
+
+        let current = gov.constitution().current().unwrap();
+        assert_eq!(current.constitution.version, 2);
+        assert_eq!(current.previous, Some(genesis.cid));
+        assert_eq!(current.adopted_by, Some(passed));
+        let block = gov.constitution().block(2).unwrap().unwrap();
+        assert_eq!(block.cid, current.cid);
+        assert_eq!(block.links[0].cid, current.previous.clone().unwrap());
+
+        let changes = gov
+            .constitution()
+            .version(1)
+            .unwrap()
+            .constitution
+            .diff(&current.constitution)
+            .unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                ConstitutionChange {
+                    path: "membership.max_members".into(),
+                    before: Some("3".into()),
+                    after: Some("10".into()),
+                },
+                ConstitutionChange {
+                    path: "version".into(),
+                    before: Some("1".into()),
+                    after: Some("2".into()),
+                },
+            ]
+        );
+    }
+}
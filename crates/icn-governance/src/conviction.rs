@@ -0,0 +1,607 @@
+//! Conviction voting for continuous funding proposals
+//!
+//! Instead of a fixed voting window, members stake support on
+//! `BudgetAllocation` proposals for as long as they like. Each proposal
+//! accumulates *conviction* that grows while support stays in place and
+//! decays once it is withdrawn:
+//!
+//! ```text
+//! y(t + n) = αⁿ · y(t) + x · (1 − αⁿ) / (1 − α)
+//! ```
+//!
+//! where `α` is [`ConvictionConfig::decay`], `x` the currently staked support
+//! and `n` the number of elapsed periods. A proposal passes automatically once
+//! its conviction crosses
+//!
+//! ```text
+//! threshold = ρ · S / ((1 − α) · (β − r / R)²)
+//! ```
+//!
+//! with `ρ` = [`ConvictionConfig::weight`], `β` = [`ConvictionConfig::max_ratio`],
+//! `S` the total support supply, `r` the requested amount and `R` the
+//! treasury balance. Requests at or above `β · R` can never pass.
+
+use crate::parameters::CONVICTION_TREASURY_DID;
+use crate::state_store::ProposalStateStore;
+use crate::{GovernanceEvent, GovernanceModule, Proposal, ProposalId, ProposalStatus};
+use crate::{LifecycleStep, LifecycleTransition, ProposalSubmission, ProposalType};
+use icn_common::{CommonError, Did, TimeProvider};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Parameters of the conviction curve.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConvictionConfig {
+    /// Fraction of conviction retained each period (`α`, between 0 and 1).
+    pub decay: f64,
+    /// Length of one conviction period in seconds.
+    pub period_secs: u64,
+    /// Maximum share of the treasury a single proposal may request (`β`).
+    pub max_ratio: f64,
+    /// Threshold weight (`ρ`).
+    pub weight: f64,
+    /// Support units each member can distribute across proposals.
+    pub member_budget: u64,
+    /// Seconds after which an unfunded conviction proposal expires.
+    pub max_lifetime_secs: u64,
+}
+
+impl Default for ConvictionConfig {
+    fn default() -> Self {
+        Self {
+            decay: 0.9,
+            period_secs: 3600,
+            max_ratio: 0.2,
+            weight: 0.002,
+            member_budget: 100,
+            max_lifetime_secs: 90 * 24 * 3600,
+        }
+    }
+}
+
+impl ConvictionConfig {
+    /// Check that the curve is well defined: `0 < decay < 1`,
+    /// `0 < max_ratio ≤ 1` and a non-zero period.
+    pub fn validate(&self) -> Result<(), CommonError> {
+        if !(self.decay > 0.0 && self.decay < 1.0) {
+            return Err(CommonError::InvalidInputError(format!(
+                "Conviction decay must be between 0 and 1, got {}",
+                self.decay
+            )));
+        }
+        if !(self.max_ratio > 0.0 && self.max_ratio <= 1.0) {
+            return Err(CommonError::InvalidInputError(format!(
+                "Conviction max_ratio must be in (0, 1], got {}",
+                self.max_ratio
+            )));
+        }
+        if self.period_secs == 0 {
+            return Err(CommonError::InvalidInputError(
+                "Conviction period must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Conviction required for a request of `requested` out of `treasury`,
+    /// given a total support supply of `total_supply`.
+    ///
+    /// Returns `None` when the request can never pass.
+    pub fn threshold(&self, requested: u64, treasury: u64, total_supply: u64) -> Option<f64> {
+        if treasury == 0 {
+            return None;
+        }
+        let share = requested as f64 / treasury as f64;
+        if share >= self.max_ratio {
+            return None;
+        }
+        let gap = self.max_ratio - share;
+        Some(self.weight * total_supply as f64 / ((1.0 - self.decay) * gap * gap))
+    }
+}
+
+/// Conviction bookkeeping for a single proposal.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConvictionState {
+    pub proposal_id: ProposalId,
+    pub beneficiary: Did,
+    pub requested: u64,
+    /// Support currently staked by each member.
+    pub stakes: HashMap<Did, u64>,
+    /// Conviction as of `last_updated`.
+    pub conviction: f64,
+    pub last_updated: u64,
+    /// Time at which the proposal crossed its threshold.
+    pub passed_at: Option<u64>,
+}
+
+impl ConvictionState {
+    /// Total support staked on this proposal.
+    pub fn total_staked(&self) -> u64 {
+        self.stakes.values().sum()
+    }
+
+    /// Advance conviction to `now` assuming stakes were constant since the
+    /// last update. Partial periods are carried over to the next update.
+    pub fn advance(&mut self, now: u64, config: &ConvictionConfig) {
+        if now <= self.last_updated || config.period_secs == 0 {
+            return;
+        }
+        let periods = (now - self.last_updated) / config.period_secs;
+        if periods == 0 {
+            return;
+        }
+        let alpha_n = config.decay.powi(periods.min(i32::MAX as u64) as i32);
+        let staked = self.total_staked() as f64;
+        self.conviction =
+            alpha_n * self.conviction + staked * (1.0 - alpha_n) / (1.0 - config.decay);
+        self.last_updated += periods * config.period_secs;
+    }
+}
+
+/// Storage for conviction state, mirroring the governance backend.
+pub(crate) type ConvictionStore = ProposalStateStore<ConvictionState>;
+
+impl GovernanceModule {
+    /// Update the conviction voting parameters after
+    /// [`ConvictionConfig::validate`].
+    pub fn set_conviction_config(&mut self, config: ConvictionConfig) -> Result<(), CommonError> {
+        config.validate()?;
+        self.conviction_config = config;
+        Ok(())
+    }
+
+    /// Account whose balance funds conviction proposals, as set by the
+    /// [`CONVICTION_TREASURY_DID`] parameter. `None` until governance sets it.
+    pub fn conviction_treasury(&self) -> Result<Option<Did>, CommonError> {
+        let treasury = self
+            .parameters
+            .get(CONVICTION_TREASURY_DID)
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        if treasury.is_empty() {
+            return Ok(None);
+        }
+        Did::from_str(&treasury).map(Some).map_err(|e| {
+            CommonError::InvalidInputError(format!("Invalid conviction treasury DID: {}", e))
+        })
+    }
+
+    /// Current conviction voting parameters.
+    pub fn conviction_config(&self) -> &ConvictionConfig {
+        &self.conviction_config
+    }
+
+    /// Total support supply across all members.
+    pub fn conviction_supply(&self) -> u64 {
+        self.members.len() as u64 * self.conviction_config.member_budget
+    }
+
+    /// Returns `true` if the proposal is governed by conviction voting.
+    pub fn is_conviction_proposal(&self, proposal_id: &ProposalId) -> bool {
+        self.conviction.contains(proposal_id)
+    }
+
+    /// Submit a budget request decided by conviction voting.
+    ///
+    /// The proposal opens for support immediately when no additional
+    /// sponsors are required.
+    pub fn submit_conviction_proposal(
+        &mut self,
+        proposer: Did,
+        beneficiary: Did,
+        amount: u64,
+        description: String,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<ProposalId, CommonError> {
+        if amount == 0 {
+            return Err(CommonError::InvalidInputError(
+                "Conviction proposals must request a positive amount".to_string(),
+            ));
+        }
+        let now = time_provider.unix_seconds();
+        let id = self.submit_proposal(
+            ProposalSubmission {
+                proposer,
+                proposal_type: ProposalType::BudgetAllocation(
+                    beneficiary.clone(),
+                    amount,
+                    description.clone(),
+                ),
+                description,
+                duration_secs: self.conviction_config.max_lifetime_secs,
+                quorum: None,
+                threshold: None,
+                content_cid: None,
+                timelock_delay: None,
+            },
+            time_provider,
+        )?;
+        self.conviction.put(
+            &id,
+            &ConvictionState {
+                proposal_id: id.clone(),
+                beneficiary,
+                requested: amount,
+                stakes: HashMap::new(),
+                conviction: 0.0,
+                last_updated: now,
+                passed_at: None,
+            },
+        )?;
+        if let Some(p) = self.get_proposal(&id)? {
+            if p.status == ProposalStatus::Deliberation {
+                self.open_voting(&id)?;
+            }
+        }
+        Ok(id)
+    }
+
+    /// Stake `amount` support units from `staker` on a conviction proposal,
+    /// replacing any previous stake. Staking zero withdraws support.
+    pub fn stake_conviction(
+        &mut self,
+        proposal_id: &ProposalId,
+        staker: Did,
+        amount: u64,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<ConvictionState, CommonError> {
+        if !self.members.contains(&staker) {
+            return Err(CommonError::PermissionDenied(
+                "Only members can stake conviction".to_string(),
+            ));
+        }
+        self.ensure_eligible_voter(&staker)?;
+        let now = time_provider.unix_seconds();
+        let proposal = self.get_proposal(proposal_id)?.ok_or_else(|| {
+            CommonError::ResourceNotFound(format!("Proposal {} not found", proposal_id.0))
+        })?;
+        let mut state = self.conviction.get(proposal_id)?.ok_or_else(|| {
+            CommonError::InvalidInputError(format!(
+                "Proposal {} does not use conviction voting",
+                proposal_id.0
+            ))
+        })?;
+        if proposal.status != ProposalStatus::VotingOpen || state.passed_at.is_some() {
+            return Err(CommonError::InvalidInputError(format!(
+                "Proposal {} is not accepting support, current status: {:?}",
+                proposal_id.0, proposal.status
+            )));
+        }
+
+        let mut staked_elsewhere: u64 = 0;
+        for other in self.conviction.list()? {
+            if &other.proposal_id == proposal_id || other.passed_at.is_some() {
+                continue;
+            }
+            let Some(stake) = other.stakes.get(&staker) else {
+                continue;
+            };
+            // Stakes on closed proposals are released by `process_conviction`
+            // and no longer count against the budget.
+            if !self.conviction_open(&other.proposal_id)? {
+                continue;
+            }
+            staked_elsewhere = staked_elsewhere.checked_add(*stake).ok_or_else(|| {
+                CommonError::InvalidInputError("Staked support overflows".to_string())
+            })?;
+        }
+        let total = staked_elsewhere.checked_add(amount).ok_or_else(|| {
+            CommonError::InvalidInputError(format!("Stake of {} overflows", amount))
+        })?;
+        if total > self.conviction_config.member_budget {
+            return Err(CommonError::PolicyDenied(format!(
+                "Stake of {} exceeds remaining budget of {}",
+                amount,
+                self.conviction_config
+                    .member_budget
+                    .saturating_sub(staked_elsewhere)
+            )));
+        }
+
+        state.advance(now, &self.conviction_config);
+        if amount == 0 {
+            state.stakes.remove(&staker);
+        } else {
+            state.stakes.insert(staker, amount);
+        }
+        self.conviction.put(proposal_id, &state)?;
+        Ok(state)
+    }
+
+    /// Fetch the conviction state of a proposal, advanced to `now`.
+    pub fn conviction_state(
+        &self,
+        proposal_id: &ProposalId,
+        now: u64,
+    ) -> Result<Option<ConvictionState>, CommonError> {
+        Ok(self.conviction.get(proposal_id)?.map(|mut s| {
+            if s.passed_at.is_none() {
+                s.advance(now, &self.conviction_config);
+            }
+            s
+        }))
+    }
+
+    /// List all conviction proposals.
+    pub fn list_conviction_states(&self) -> Result<Vec<ConvictionState>, CommonError> {
+        self.conviction.list()
+    }
+
+    fn conviction_open(&self, proposal_id: &ProposalId) -> Result<bool, CommonError> {
+        Ok(matches!(
+            self.get_proposal(proposal_id)?,
+            Some(Proposal {
+                status: ProposalStatus::VotingOpen,
+                ..
+            })
+        ))
+    }
+
+    /// Advance conviction on every open proposal and accept those that cross
+    /// their threshold against the given treasury balance.
+    ///
+    /// Stakes on accepted proposals, and on proposals that closed without
+    /// passing, are released so members can redirect their support. Returns
+    /// a [`LifecycleStep::ConvictionPassed`] transition for every proposal
+    /// accepted in this pass. The runtime calls this from its governance
+    /// lifecycle scheduler with the balance of [`Self::conviction_treasury`].
+    pub fn process_conviction(
+        &mut self,
+        treasury_balance: u64,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<Vec<LifecycleTransition>, CommonError> {
+        let now = time_provider.unix_seconds();
+        self.expire_proposals(now)?;
+        let supply = self.conviction_supply();
+        let mut states = self.conviction.list()?;
+        states.sort_by(|a, b| a.proposal_id.0.cmp(&b.proposal_id.0));
+
+        let mut accepted = Vec::new();
+        let mut remaining_treasury = treasury_balance;
+        for mut state in states {
+            if state.passed_at.is_some() {
+                continue;
+            }
+            let open = self.conviction_open(&state.proposal_id)?;
+            state.advance(now, &self.conviction_config);
+            if !open {
+                state.stakes.clear();
+            } else {
+                let threshold =
+                    self.conviction_config
+                        .threshold(state.requested, remaining_treasury, supply);
+                if matches!(threshold, Some(t) if state.conviction >= t) {
+                    state.passed_at = Some(now);
+                    state.stakes.clear();
+                    remaining_treasury = remaining_treasury.saturating_sub(state.requested);
+                    let timelocked = self.config.timelock_delay_secs > 0
+                        || self.config.veto_grace_period_secs > 0;
+                    let status = if timelocked {
+                        ProposalStatus::AcceptedTimelock
+                    } else {
+                        ProposalStatus::Accepted
+                    };
+                    self.update_proposal(&state.proposal_id, |p| {
+                        p.status = status.clone();
+                        if timelocked {
+                            p.accepted_at = Some(now);
+                        }
+                    })?;
+                    if let Some(store) = &self.event_store {
+                        store
+                            .lock()
+                            .unwrap()
+                            .append(&GovernanceEvent::StatusUpdated(
+                                state.proposal_id.clone(),
+                                status.clone(),
+                            ))?;
+                    }
+                    accepted.push(LifecycleTransition {
+                        proposal_id: state.proposal_id.clone(),
+                        step: LifecycleStep::ConvictionPassed,
+                        from: ProposalStatus::VotingOpen,
+                        to: status,
+                        at: now,
+                    });
+                }
+            }
+            let id = state.proposal_id.clone();
+            self.conviction.put(&id, &state)?;
+        }
+        Ok(accepted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use icn_common::FixedTimeProvider;
+    use std::str::FromStr;
+
+    fn did(name: &str) -> Did {
+        Did::from_str(&format!("did:example:{name}")).unwrap()
+    }
+
+    fn module_with_members(n: usize) -> GovernanceModule {
+        let mut gov = GovernanceModule::new();
+        for i in 0..n {
+            gov.add_member(did(&format!("m{i}")));
+        }
+        gov
+    }
+
+    #[test]
+    fn conviction_builds_and_decays() {
+        let config = ConvictionConfig {
+            decay: 0.5,
+            period_secs: 10,
+            ..Default::default()
+        };
+        let mut state = ConvictionState {
+            proposal_id: ProposalId("p".into()),
+            beneficiary: did("b"),
+            requested: 1,
+            stakes: HashMap::from([(did("a"), 10)]),
+            conviction: 0.0,
+            last_updated: 0,
+            passed_at: None,
+        };
+        state.advance(10, &config);
+        assert!((state.conviction - 10.0).abs() < 1e-9);
+        state.advance(25, &config);
+        assert!((state.conviction - 15.0).abs() < 1e-9);
+        assert_eq!(state.last_updated, 20);
+
+        state.stakes.clear();
+        state.advance(30, &config);
+        assert!((state.conviction - 7.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn threshold_scales_with_request() {
+        let config = ConvictionConfig::default();
+        let small = config.threshold(10, 1_000, 500).unwrap();
+        let large = config.threshold(150, 1_000, 500).unwrap();
+        assert!(large > small);
+        assert!(config.threshold(200, 1_000, 500).is_none());
+        assert!(config.threshold(1, 0, 500).is_none());
+    }
+
+    #[test]
+    fn config_and_treasury_are_validated() {
+        let mut gov = module_with_members(1);
+        for config in [
+            ConvictionConfig {
+                decay: 1.0,
+                ..Default::default()
+            },
+            ConvictionConfig {
+                max_ratio: 0.0,
+                ..Default::default()
+            },
+            ConvictionConfig {
+                max_ratio: 1.5,
+                ..Default::default()
+            },
+            ConvictionConfig {
+                period_secs: 0,
+                ..Default::default()
+            },
+        ] {
+            assert!(gov.set_conviction_config(config).is_err());
+        }
+        gov.set_conviction_config(ConvictionConfig {
+            max_ratio: 1.0,
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(gov.conviction_treasury().unwrap(), None);
+        gov.parameters
+            .apply(
+                &[(
+                    CONVICTION_TREASURY_DID.to_string(),
+                    did("treasury").to_string(),
+                )],
+                None,
+                0,
+            )
+            .unwrap();
+        assert_eq!(gov.conviction_treasury().unwrap(), Some(did("treasury")));
+    }
+
+    #[test]
+    fn proposal_passes_once_conviction_crosses_threshold() {
+        let mut gov = module_with_members(5);
+        let start = FixedTimeProvider::new(1_000);
+        let pid = gov
+            .submit_conviction_proposal(did("m0"), did("coop"), 50, "tools".into(), &start)
+            .unwrap();
+        assert_eq!(
+            gov.get_proposal(&pid).unwrap().unwrap().status,
+            ProposalStatus::VotingOpen
+        );
+        for i in 0..3 {
+            gov.stake_conviction(&pid, did(&format!("m{i}")), 100, &start)
+                .unwrap();
+        }
+        assert!(gov.process_conviction(1_000, &start).unwrap().is_empty());
+
+        let later = FixedTimeProvider::new(1_000 + 3600 * 24);
+        let accepted = gov.process_conviction(1_000, &later).unwrap();
+        assert_eq!(accepted.len(), 1);
+        assert_eq!(accepted[0].proposal_id, pid);
+        assert_eq!(accepted[0].step, LifecycleStep::ConvictionPassed);
+        assert_eq!(accepted[0].to, ProposalStatus::Accepted);
+        assert_eq!(
+            gov.get_proposal(&pid).unwrap().unwrap().status,
+            ProposalStatus::Accepted
+        );
+        assert!(gov
+            .conviction_state(&pid, later.unix_seconds())
+            .unwrap()
+            .unwrap()
+            .stakes
+            .is_empty());
+    }
+
+    #[test]
+    fn stakes_are_limited_by_member_budget() {
+        let mut gov = module_with_members(2);
+        let tp = FixedTimeProvider::new(0);
+        let p1 = gov
+            .submit_conviction_proposal(did("m0"), did("a"), 10, "one".into(), &tp)
+            .unwrap();
+        let p2 = gov
+            .submit_conviction_proposal(did("m1"), did("b"), 10, "two".into(), &tp)
+            .unwrap();
+        gov.stake_conviction(&p1, did("m0"), 60, &tp).unwrap();
+        assert!(gov.stake_conviction(&p2, did("m0"), 50, &tp).is_err());
+        gov.stake_conviction(&p2, did("m0"), 40, &tp).unwrap();
+        assert!(gov.stake_conviction(&p1, did("outsider"), 1, &tp).is_err());
+        assert!(gov.stake_conviction(&p2, did("m0"), u64::MAX, &tp).is_err());
+    }
+
+    #[test]
+    fn stakes_on_closed_proposals_are_released() {
+        let mut gov = module_with_members(2);
+        let tp = FixedTimeProvider::new(0);
+        let p1 = gov
+            .submit_conviction_proposal(did("m0"), did("a"), 10, "one".into(), &tp)
+            .unwrap();
+        let p2 = gov
+            .submit_conviction_proposal(did("m1"), did("b"), 10, "two".into(), &tp)
+            .unwrap();
+        gov.stake_conviction(&p1, did("m0"), 100, &tp).unwrap();
+        assert!(gov.stake_conviction(&p2, did("m0"), 50, &tp).is_err());
+
+        gov.update_proposal(&p1, |p| p.status = ProposalStatus::Rejected)
+            .unwrap();
+        gov.stake_conviction(&p2, did("m0"), 100, &tp).unwrap();
+        gov.process_conviction(1_000, &tp).unwrap();
+        assert!(gov
+            .conviction_state(&p1, 0)
+            .unwrap()
+            .unwrap()
+            .stakes
+            .is_empty());
+    }
+
+    #[test]
+    fn conviction_proposals_reject_regular_votes() {
+        let mut gov = module_with_members(1);
+        let tp = FixedTimeProvider::new(0);
+        let pid = gov
+            .submit_conviction_proposal(did("m0"), did("a"), 10, "x".into(), &tp)
+            .unwrap();
+        assert!(gov
+            .cast_vote(did("m0"), &pid, crate::VoteOption::Yes, &tp)
+            .is_err());
+    }
+}
@@ -0,0 +1,822 @@
+//! Cross-federation proposals
+//!
+//! A [`JointProposal`] is signed by the federation that drafts it and names
+//! every participating federation together with the `did:key` that signs on
+//! its behalf. Each participant adopts the joint proposal into its own
+//! [`GovernanceModule`], which opens an ordinary local proposal decided under
+//! that federation's members, quorum and threshold. Once the local vote is
+//! over, the federation publishes a signed [`FederationRatification`] and
+//! every participant records the ratifications it receives.
+//!
+//! The [`RatificationSet`] decides when the joint proposal may be executed:
+//! all `required` federations and at least `min_ratifications` in total must
+//! ratify before the deadline. A rejection or failed local execution by a
+//! required federation, or enough of them that the minimum can no longer be
+//! met, fails the proposal early;
+//! reaching the deadline first times it out. Only
+//! [`CrossFederationStatus::Ratified`] proposals can be marked executed.
+
+use crate::deliberation::anchor;
+use crate::{GovernanceModule, ProposalId, ProposalStatus, ProposalSubmission, ProposalType};
+use icn_common::{CommonError, DagBlock, Did, Signable, SignatureBytes, TimeProvider};
+use icn_identity::{verifying_key_from_did_key, CrossFederationProposalType};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A federation taking part in a joint proposal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FederationParticipant {
+    pub federation_id: String,
+    /// `did:key` that signs the federation's ratification.
+    pub signer: Did,
+}
+
+/// Which ratifications a joint proposal needs before it can be executed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RatificationSet {
+    pub participants: Vec<FederationParticipant>,
+    /// Federations whose ratification is mandatory.
+    pub required: Vec<String>,
+    /// Minimum number of ratifying federations, including `required`.
+    pub min_ratifications: usize,
+}
+
+impl RatificationSet {
+    /// Require every participant to ratify.
+    pub fn unanimous(participants: Vec<FederationParticipant>) -> Self {
+        RatificationSet {
+            required: participants
+                .iter()
+                .map(|p| p.federation_id.clone())
+                .collect(),
+            min_ratifications: participants.len(),
+            participants,
+        }
+    }
+
+    /// Look up a participating federation.
+    pub fn participant(&self, federation_id: &str) -> Option<&FederationParticipant> {
+        self.participants
+            .iter()
+            .find(|p| p.federation_id == federation_id)
+    }
+
+    fn validate(&self) -> Result<(), CommonError> {
+        let mut seen = HashSet::new();
+        if self.participants.is_empty() {
+            return Err(CommonError::InvalidInputError(
+                "A joint proposal needs at least one participating federation".to_string(),
+            ));
+        }
+        for participant in &self.participants {
+            if !seen.insert(participant.federation_id.as_str()) {
+                return Err(CommonError::InvalidInputError(format!(
+                    "Federation {} is listed twice",
+                    participant.federation_id
+                )));
+            }
+        }
+        if let Some(unknown) = self.required.iter().find(|r| !seen.contains(r.as_str())) {
+            return Err(CommonError::InvalidInputError(format!(
+                "Required federation {} is not a participant",
+                unknown
+            )));
+        }
+        if self.min_ratifications == 0 || self.min_ratifications > self.participants.len() {
+            return Err(CommonError::InvalidInputError(format!(
+                "min_ratifications must be between 1 and {}",
+                self.participants.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A proposal ratified independently by several federations.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct JointProposal {
+    /// Derived from the proposal contents, see [`JointProposal::new`].
+    pub id: ProposalId,
+    pub proposing_federation: String,
+    pub proposal_type: CrossFederationProposalType,
+    pub description: String,
+    pub ratification: RatificationSet,
+    pub created_at: u64,
+    /// Ratifications decided after this time are not accepted.
+    pub ratification_deadline: u64,
+    /// `did:key` of the proposing federation's signer.
+    pub proposer: Did,
+    pub signature: Option<SignatureBytes>,
+}
+
+impl Signable for JointProposal {
+    fn to_signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(self.proposing_federation.as_bytes());
+        // `serde_json::Value` keeps object keys sorted, so maps in the
+        // proposal type encode the same way on every node.
+        let proposal_type = serde_json::to_value(&self.proposal_type)
+            .and_then(|v| serde_json::to_vec(&v))
+            .map_err(|e| {
+                CommonError::SerializationError(format!("Failed to encode proposal type: {}", e))
+            })?;
+        bytes.extend_from_slice(&proposal_type);
+        bytes.extend_from_slice(self.description.as_bytes());
+        for participant in &self.ratification.participants {
+            bytes.extend_from_slice(participant.federation_id.as_bytes());
+            bytes.extend_from_slice(participant.signer.to_string().as_bytes());
+        }
+        for required in &self.ratification.required {
+            bytes.extend_from_slice(required.as_bytes());
+        }
+        bytes.extend_from_slice(&(self.ratification.min_ratifications as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.created_at.to_le_bytes());
+        bytes.extend_from_slice(&self.ratification_deadline.to_le_bytes());
+        bytes.extend_from_slice(self.proposer.to_string().as_bytes());
+        Ok(bytes)
+    }
+}
+
+impl JointProposal {
+    /// Draft an unsigned joint proposal; its id commits to the contents.
+    pub fn new(
+        proposing_federation: String,
+        proposal_type: CrossFederationProposalType,
+        description: String,
+        ratification: RatificationSet,
+        created_at: u64,
+        ratification_deadline: u64,
+        proposer: Did,
+    ) -> Result<Self, CommonError> {
+        let mut proposal = JointProposal {
+            id: ProposalId(String::new()),
+            proposing_federation,
+            proposal_type,
+            description,
+            ratification,
+            created_at,
+            ratification_deadline,
+            proposer,
+            signature: None,
+        };
+        proposal.id = proposal.derived_id()?;
+        Ok(proposal)
+    }
+
+    fn derived_id(&self) -> Result<ProposalId, CommonError> {
+        let digest = Sha256::digest(self.to_signable_bytes()?);
+        Ok(ProposalId(format!("xfed:{}", hex::encode(&digest[..16]))))
+    }
+
+    /// Check the id and the proposing federation's signature.
+    pub fn verify_signature(&self) -> Result<(), CommonError> {
+        if self.id != self.derived_id()? {
+            return Err(CommonError::InvalidInputError(
+                "Joint proposal id does not match its contents".to_string(),
+            ));
+        }
+        let signature = self
+            .signature
+            .as_ref()
+            .ok_or_else(|| CommonError::CryptoError("Joint proposal is not signed".to_string()))?;
+        let key = verifying_key_from_did_key(&self.proposer)?;
+        self.verify(signature, &key)
+    }
+}
+
+/// How a federation decided on a joint proposal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RatificationDecision {
+    Ratified,
+    Rejected,
+    /// The local vote passed but executing the local proposal failed.
+    Failed,
+}
+
+/// A federation's signed outcome for a joint proposal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FederationRatification {
+    pub joint_proposal_id: ProposalId,
+    pub federation_id: String,
+    /// The local proposal the federation voted on.
+    pub local_proposal_id: ProposalId,
+    pub decision: RatificationDecision,
+    pub yes: usize,
+    pub no: usize,
+    pub abstain: usize,
+    pub decided_at: u64,
+    pub signer: Did,
+    pub signature: Option<SignatureBytes>,
+}
+
+impl Signable for FederationRatification {
+    fn to_signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        fn put(bytes: &mut Vec<u8>, field: &[u8]) {
+            bytes.extend_from_slice(&(field.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(field);
+        }
+
+        let mut bytes = Vec::new();
+        put(&mut bytes, self.joint_proposal_id.0.as_bytes());
+        put(&mut bytes, self.federation_id.as_bytes());
+        put(&mut bytes, self.local_proposal_id.0.as_bytes());
+        bytes.push(match self.decision {
+            RatificationDecision::Ratified => 1,
+            RatificationDecision::Rejected => 0,
+            RatificationDecision::Failed => 2,
+        });
+        for count in [self.yes, self.no, self.abstain] {
+            bytes.extend_from_slice(&(count as u64).to_le_bytes());
+        }
+        bytes.extend_from_slice(&self.decided_at.to_le_bytes());
+        put(&mut bytes, self.signer.to_string().as_bytes());
+        Ok(bytes)
+    }
+}
+
+/// Progress of a joint proposal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CrossFederationStatus {
+    /// Waiting for ratifications.
+    Collecting,
+    /// The ratification set is satisfied.
+    Ratified,
+    /// The ratification set can no longer be satisfied.
+    Rejected {
+        reason: String,
+    },
+    /// The deadline passed before the ratification set was satisfied.
+    TimedOut,
+    Executed,
+}
+
+/// A joint proposal as seen by one participating federation.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CrossFederationState {
+    pub proposal: JointProposal,
+    /// Federation this node belongs to.
+    pub local_federation: String,
+    pub local_proposal_id: ProposalId,
+    pub ratifications: Vec<FederationRatification>,
+    pub status: CrossFederationStatus,
+}
+
+impl CrossFederationState {
+    /// Federations that have not published a ratification yet.
+    pub fn pending_federations(&self) -> Vec<String> {
+        self.proposal
+            .ratification
+            .participants
+            .iter()
+            .filter(|p| {
+                !self
+                    .ratifications
+                    .iter()
+                    .any(|r| r.federation_id == p.federation_id)
+            })
+            .map(|p| p.federation_id.clone())
+            .collect()
+    }
+
+    fn evaluate(&self) -> CrossFederationStatus {
+        let set = &self.proposal.ratification;
+        let decided = |decision| {
+            self.ratifications
+                .iter()
+                .filter(move |r| r.decision == decision)
+                .map(|r| r.federation_id.as_str())
+                .collect::<HashSet<_>>()
+        };
+        let ratified = decided(RatificationDecision::Ratified);
+        let rejected = decided(RatificationDecision::Rejected);
+        let failed = decided(RatificationDecision::Failed);
+        if let Some(blocker) = set.required.iter().find(|r| rejected.contains(r.as_str())) {
+            return CrossFederationStatus::Rejected {
+                reason: format!("Required federation {} rejected", blocker),
+            };
+        }
+        if let Some(blocker) = set.required.iter().find(|r| failed.contains(r.as_str())) {
+            return CrossFederationStatus::Rejected {
+                reason: format!("Required federation {} failed to execute", blocker),
+            };
+        }
+        let still_possible = set.participants.len() - rejected.len() - failed.len();
+        if still_possible < set.min_ratifications {
+            return CrossFederationStatus::Rejected {
+                reason: format!(
+                    "Only {} of the {} required ratifications remain possible",
+                    still_possible, set.min_ratifications
+                ),
+            };
+        }
+        if set.required.iter().all(|r| ratified.contains(r.as_str()))
+            && ratified.len() >= set.min_ratifications
+        {
+            return CrossFederationStatus::Ratified;
+        }
+        CrossFederationStatus::Collecting
+    }
+}
+
+/// Storage for joint proposals, mirroring the governance backend.
+pub(crate) type CrossFederationStore = crate::state_store::ProposalStateStore<CrossFederationState>;
+
+impl GovernanceModule {
+    /// Adopt a signed joint proposal as a participant `local_federation`.
+    ///
+    /// Opens a local proposal submitted by `submitter` that members vote on
+    /// for `duration_secs`, which must end before the ratification deadline.
+    /// Returns the local proposal id and a block anchoring the joint proposal.
+    pub fn adopt_cross_federation_proposal(
+        &mut self,
+        proposal: JointProposal,
+        local_federation: &str,
+        submitter: Did,
+        duration_secs: u64,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<(ProposalId, DagBlock), CommonError> {
+        proposal.verify_signature()?;
+        proposal.ratification.validate()?;
+        if proposal
+            .ratification
+            .participant(local_federation)
+            .is_none()
+        {
+            return Err(CommonError::PolicyDenied(format!(
+                "Federation {} does not participate in {}",
+                local_federation, proposal.id
+            )));
+        }
+        if self.cross_federation.contains(&proposal.id) {
+            return Err(CommonError::InvalidInputError(format!(
+                "Joint proposal {} was already adopted",
+                proposal.id
+            )));
+        }
+        let now = time_provider.unix_seconds();
+        if now.saturating_add(duration_secs) > proposal.ratification_deadline {
+            return Err(CommonError::InvalidInputError(
+                "Local voting would end after the ratification deadline".to_string(),
+            ));
+        }
+
+        let local_proposal_id = self.submit_proposal(
+            ProposalSubmission {
+                proposer: submitter,
+                proposal_type: ProposalType::GenericText(format!(
+                    "Ratify cross-federation proposal {} from {}",
+                    proposal.id, proposal.proposing_federation
+                )),
+                description: proposal.description.clone(),
+                duration_secs,
+                quorum: None,
+                threshold: None,
+                content_cid: None,
+                timelock_delay: None,
+            },
+            time_provider,
+        )?;
+        let block = anchor(
+            &proposal,
+            Vec::new(),
+            &proposal.proposer,
+            proposal.created_at,
+            proposal.signature.clone(),
+        )?;
+        let state = CrossFederationState {
+            local_federation: local_federation.to_string(),
+            local_proposal_id: local_proposal_id.clone(),
+            ratifications: Vec::new(),
+            status: CrossFederationStatus::Collecting,
+            proposal,
+        };
+        self.cross_federation.put(&state.proposal.id, &state)?;
+        Ok((local_proposal_id, block))
+    }
+
+    /// Build this federation's unsigned ratification from its closed local
+    /// vote, ready to be signed by `signer`.
+    pub fn prepare_federation_ratification(
+        &self,
+        joint_proposal_id: &ProposalId,
+        signer: Did,
+    ) -> Result<FederationRatification, CommonError> {
+        let state = self.load_cross_federation(joint_proposal_id)?;
+        let local = self
+            .get_proposal(&state.local_proposal_id)?
+            .ok_or_else(|| {
+                CommonError::ResourceNotFound(format!(
+                    "Local proposal {} not found",
+                    state.local_proposal_id
+                ))
+            })?;
+        let decision = match local.status {
+            ProposalStatus::Accepted
+            | ProposalStatus::AcceptedTimelock
+            | ProposalStatus::Executed => RatificationDecision::Ratified,
+            ProposalStatus::Rejected => RatificationDecision::Rejected,
+            ProposalStatus::Failed => RatificationDecision::Failed,
+            ref other => {
+                return Err(CommonError::PolicyDenied(format!(
+                    "Local proposal {} is still {:?}",
+                    local.id, other
+                )))
+            }
+        };
+        let (yes, no, abstain) = self.tally_votes(&local);
+        Ok(FederationRatification {
+            joint_proposal_id: joint_proposal_id.clone(),
+            federation_id: state.local_federation,
+            local_proposal_id: local.id,
+            decision,
+            yes,
+            no,
+            abstain,
+            decided_at: local.voting_deadline,
+            signer,
+            signature: None,
+        })
+    }
+
+    /// Record a signed ratification from any participating federation,
+    /// including this one.
+    ///
+    /// Returns a block anchoring the ratification.
+    pub fn record_federation_ratification(
+        &mut self,
+        ratification: FederationRatification,
+    ) -> Result<DagBlock, CommonError> {
+        let mut state = self.load_cross_federation(&ratification.joint_proposal_id)?;
+        if state.status != CrossFederationStatus::Collecting {
+            return Err(CommonError::PolicyDenied(format!(
+                "Joint proposal {} is no longer collecting ratifications ({:?})",
+                state.proposal.id, state.status
+            )));
+        }
+        let participant = state
+            .proposal
+            .ratification
+            .participant(&ratification.federation_id)
+            .ok_or_else(|| {
+                CommonError::PolicyDenied(format!(
+                    "Federation {} does not participate in {}",
+                    ratification.federation_id, state.proposal.id
+                ))
+            })?;
+        if participant.signer != ratification.signer {
+            return Err(CommonError::PolicyDenied(format!(
+                "{} does not sign for federation {}",
+                ratification.signer, ratification.federation_id
+            )));
+        }
+        let signature = ratification
+            .signature
+            .clone()
+            .ok_or_else(|| CommonError::CryptoError("Ratifications must be signed".to_string()))?;
+        let key = verifying_key_from_did_key(&ratification.signer)?;
+        ratification.verify(&signature, &key)?;
+        if ratification.decided_at > state.proposal.ratification_deadline {
+            return Err(CommonError::PolicyDenied(format!(
+                "Ratification from {} was decided after the deadline",
+                ratification.federation_id
+            )));
+        }
+        if state
+            .ratifications
+            .iter()
+            .any(|r| r.federation_id == ratification.federation_id)
+        {
+            return Err(CommonError::InvalidInputError(format!(
+                "Federation {} has already ratified {}",
+                ratification.federation_id, state.proposal.id
+            )));
+        }
+
+        let block = anchor(
+            &ratification,
+            Vec::new(),
+            &ratification.signer,
+            ratification.decided_at,
+            Some(signature),
+        )?;
+        state.ratifications.push(ratification);
+        state.status = state.evaluate();
+        self.cross_federation.put(&state.proposal.id, &state)?;
+        Ok(block)
+    }
+
+    /// Time out joint proposals whose deadline passed before they were
+    /// ratified. Returns the ids that timed out.
+    pub fn expire_cross_federation_proposals(
+        &mut self,
+        now: u64,
+    ) -> Result<Vec<ProposalId>, CommonError> {
+        let mut expired = Vec::new();
+        for mut state in self.cross_federation.list()? {
+            if state.status == CrossFederationStatus::Collecting
+                && now > state.proposal.ratification_deadline
+            {
+                state.status = CrossFederationStatus::TimedOut;
+                self.cross_federation.put(&state.proposal.id, &state)?;
+                expired.push(state.proposal.id);
+            }
+        }
+        Ok(expired)
+    }
+
+    /// Mark a ratified joint proposal as executed and return its state so
+    /// the caller can carry out its terms.
+    pub fn execute_cross_federation_proposal(
+        &mut self,
+        joint_proposal_id: &ProposalId,
+    ) -> Result<CrossFederationState, CommonError> {
+        let mut state = self.load_cross_federation(joint_proposal_id)?;
+        if state.status != CrossFederationStatus::Ratified {
+            return Err(CommonError::PolicyDenied(format!(
+                "Joint proposal {} is not ratified ({:?})",
+                joint_proposal_id, state.status
+            )));
+        }
+        state.status = CrossFederationStatus::Executed;
+        self.cross_federation.put(joint_proposal_id, &state)?;
+        Ok(state)
+    }
+
+    /// State of a joint proposal adopted by this federation.
+    pub fn cross_federation_proposal(
+        &self,
+        joint_proposal_id: &ProposalId,
+    ) -> Result<Option<CrossFederationState>, CommonError> {
+        self.cross_federation.get(joint_proposal_id)
+    }
+
+    /// All joint proposals adopted by this federation.
+    pub fn cross_federation_proposals(&self) -> Result<Vec<CrossFederationState>, CommonError> {
+        self.cross_federation.list()
+    }
+
+    fn load_cross_federation(
+        &self,
+        joint_proposal_id: &ProposalId,
+    ) -> Result<CrossFederationState, CommonError> {
+        self.cross_federation
+            .get(joint_proposal_id)?
+            .ok_or_else(|| {
+                CommonError::ResourceNotFound(format!(
+                    "Joint proposal {} has not been adopted",
+                    joint_proposal_id
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Proposal, ProposalCallback, VoteOption};
+    use icn_common::FixedTimeProvider;
+    use icn_identity::{did_key_from_verifying_key, generate_ed25519_keypair, SigningKey};
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    struct Federation {
+        id: String,
+        key: SigningKey,
+        signer: Did,
+        gov: GovernanceModule,
+    }
+
+    fn federation(id: &str) -> Federation {
+        let (key, pk) = generate_ed25519_keypair();
+        let signer = Did::from_str(&did_key_from_verifying_key(&pk)).unwrap();
+        let mut gov = GovernanceModule::new();
+        gov.add_member(Did::from_str(&format!("did:example:{}-member", id)).unwrap());
+        Federation {
+            id: id.to_string(),
+            key,
+            signer,
+            gov,
+        }
+    }
+
+    fn participant(f: &Federation) -> FederationParticipant {
+        FederationParticipant {
+            federation_id: f.id.clone(),
+            signer: f.signer.clone(),
+        }
+    }
+
+    fn joint_budget(proposer: &Federation, set: RatificationSet) -> JointProposal {
+        let mut proposal = JointProposal::new(
+            proposer.id.clone(),
+            CrossFederationProposalType::JointBudget {
+                purpose: "shared compute cluster".to_string(),
+                contributions: HashMap::from([("a".to_string(), 100), ("b".to_string(), 50)]),
+            },
+            "Fund a shared compute cluster".to_string(),
+            set,
+            0,
+            1_000,
+            proposer.signer.clone(),
+        )
+        .unwrap();
+        proposal.signature = Some(proposal.sign(&proposer.key).unwrap());
+        proposal
+    }
+
+    /// Adopt, vote and close locally, then return the signed ratification.
+    fn decide(
+        f: &mut Federation,
+        proposal: &JointProposal,
+        vote: VoteOption,
+    ) -> FederationRatification {
+        let member = Did::from_str(&format!("did:example:{}-member", f.id)).unwrap();
+        let (local, _) = f
+            .gov
+            .adopt_cross_federation_proposal(
+                proposal.clone(),
+                &f.id,
+                member.clone(),
+                100,
+                &FixedTimeProvider::new(10),
+            )
+            .unwrap();
+        f.gov.open_voting(&local).unwrap();
+        f.gov
+            .cast_vote(member, &local, vote, &FixedTimeProvider::new(20))
+            .unwrap();
+        f.gov
+            .close_voting_period(&local, &FixedTimeProvider::new(110))
+            .unwrap();
+        let mut ratification = f
+            .gov
+            .prepare_federation_ratification(&proposal.id, f.signer.clone())
+            .unwrap();
+        ratification.signature = Some(ratification.sign(&f.key).unwrap());
+        ratification
+    }
+
+    #[test]
+    fn executes_once_every_federation_ratifies() {
+        let mut a = federation("a");
+        let mut b = federation("b");
+        let proposal = joint_budget(
+            &a,
+            RatificationSet::unanimous(vec![participant(&a), participant(&b)]),
+        );
+
+        let from_a = decide(&mut a, &proposal, VoteOption::Yes);
+        let from_b = decide(&mut b, &proposal, VoteOption::Yes);
+        a.gov
+            .record_federation_ratification(from_a.clone())
+            .unwrap();
+        assert!(a
+            .gov
+            .execute_cross_federation_proposal(&proposal.id)
+            .is_err());
+
+        // A ratification signed by the wrong key is refused.
+        let mut forged = from_b.clone();
+        forged.signature = Some(forged.sign(&a.key).unwrap());
+        assert!(a.gov.record_federation_ratification(forged).is_err());
+
+        a.gov.record_federation_ratification(from_b).unwrap();
+        let state = a
+            .gov
+            .cross_federation_proposal(&proposal.id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(state.status, CrossFederationStatus::Ratified);
+        assert!(state.pending_federations().is_empty());
+        a.gov
+            .execute_cross_federation_proposal(&proposal.id)
+            .unwrap();
+        assert!(a
+            .gov
+            .execute_cross_federation_proposal(&proposal.id)
+            .is_err());
+    }
+
+    #[test]
+    fn required_rejection_fails_early_and_silence_times_out() {
+        let mut a = federation("a");
+        let mut b = federation("b");
+        let c = federation("c");
+        let set = RatificationSet {
+            participants: vec![participant(&a), participant(&b), participant(&c)],
+            required: vec!["b".to_string()],
+            min_ratifications: 2,
+        };
+        let proposal = joint_budget(&a, set);
+        let from_a = decide(&mut a, &proposal, VoteOption::Yes);
+        let from_b = decide(&mut b, &proposal, VoteOption::No);
+        a.gov.record_federation_ratification(from_a).unwrap();
+        a.gov.record_federation_ratification(from_b).unwrap();
+        let state = a
+            .gov
+            .cross_federation_proposal(&proposal.id)
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            state.status,
+            CrossFederationStatus::Rejected { .. }
+        ));
+
+        // Without the other federations the proposal times out.
+        a.gov = GovernanceModule::new();
+        a.gov
+            .add_member(Did::from_str("did:example:a-member").unwrap());
+        let from_a = decide(&mut a, &proposal, VoteOption::Yes);
+        a.gov.record_federation_ratification(from_a).unwrap();
+        assert!(a
+            .gov
+            .expire_cross_federation_proposals(1_000)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            a.gov.expire_cross_federation_proposals(1_001).unwrap(),
+            vec![proposal.id.clone()]
+        );
+        assert_eq!(
+            a.gov
+                .cross_federation_proposal(&proposal.id)
+                .unwrap()
+                .unwrap()
+                .pending_federations(),
+            vec!["b".to_string(), "c".to_string()]
+        );
+    }
+
+    struct FailingCallback;
+
+    impl ProposalCallback for FailingCallback {
+        fn on_execute(&self, _proposal: &Proposal) -> Result<(), CommonError> {
+            Err(CommonError::InternalError("execution failed".to_string()))
+        }
+    }
+
+    #[test]
+    fn failed_local_execution_is_reported_as_failed() {
+        let mut a = federation("a");
+        let mut b = federation("b");
+        let proposal = joint_budget(
+            &a,
+            RatificationSet::unanimous(vec![participant(&a), participant(&b)]),
+        );
+        let from_a = decide(&mut a, &proposal, VoteOption::Yes);
+
+        b.gov.set_callback(FailingCallback);
+        let mut from_b = decide(&mut b, &proposal, VoteOption::Yes);
+        let _ = b.gov.execute_proposal(&from_b.local_proposal_id);
+        from_b = b
+            .gov
+            .prepare_federation_ratification(&proposal.id, b.signer.clone())
+            .unwrap();
+        assert_eq!(from_b.decision, RatificationDecision::Failed);
+        from_b.signature = Some(from_b.sign(&b.key).unwrap());
+
+        a.gov.record_federation_ratification(from_a).unwrap();
+        a.gov.record_federation_ratification(from_b).unwrap();
+        let state = a
+            .gov
+            .cross_federation_proposal(&proposal.id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            state.status,
+            CrossFederationStatus::Rejected {
+                reason: "Required federation b failed to execute".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn ratification_signature_covers_field_boundaries() {
+        let mut a = federation("a");
+        let proposal = joint_budget(&a, RatificationSet::unanimous(vec![participant(&a)]));
+        let ratification = decide(&mut a, &proposal, VoteOption::Yes);
+
+        // Moving bytes between adjacent fields changes the signed message.
+        let mut shifted = ratification.clone();
+        shifted.joint_proposal_id = ProposalId(format!("{}a", ratification.joint_proposal_id));
+        shifted.federation_id = String::new();
+        assert_ne!(
+            shifted.to_signable_bytes().unwrap(),
+            ratification.to_signable_bytes().unwrap()
+        );
+        let key = verifying_key_from_did_key(&a.signer).unwrap();
+        assert!(shifted
+            .verify(ratification.signature.as_ref().unwrap(), &key)
+            .is_err());
+    }
+}
@@ -0,0 +1,561 @@
+//! Liquid democracy: topic-scoped, transitive vote delegation
+//!
+//! Members may hand their vote to another member for every proposal, for a
+//! single kind of [`ProposalType`], or for proposals carrying a tag. When a
+//! proposal is tallied each member's vote is resolved as follows:
+//!
+//! 1. A direct vote always wins, overriding any delegation for that proposal.
+//! 2. Otherwise the most specific active delegation (tag, then proposal type,
+//!    then all proposals) is followed, hop by hop, until a member who voted is
+//!    reached.
+//! 3. Chains that loop back on themselves or run out of delegations count as
+//!    not voting.
+//!
+//! Delegations are evaluated at the proposal's voting deadline so that a
+//! re-tally always yields the same weights; delegations made after the
+//! deadline do not count.
+//!
+//! The governance module persists delegations and proposal tags in
+//! [`ProposalStateStore`]s and rebuilds the registry from them on startup.
+
+use crate::state_store::ProposalStateStore;
+use crate::{Proposal, ProposalId, ProposalType, VoteOption};
+use icn_common::{CommonError, Did};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Proposal kinds accepted by [`DelegationScope::ProposalKind`].
+pub const PROPOSAL_KINDS: &[&str] = &[
+    "system_parameter_change",
+    "new_member_invitation",
+    "remove_member",
+    "software_upgrade",
+    "generic_text",
+    "budget_allocation",
+    "resolution",
+    "constitution_amendment",
+];
+
+/// Stable name of a proposal's type, used for scoping delegations.
+pub fn proposal_kind(proposal_type: &ProposalType) -> &'static str {
+    match proposal_type {
+        ProposalType::SystemParameterChange(_, _) => "system_parameter_change",
+        ProposalType::NewMemberInvitation(_) => "new_member_invitation",
+        ProposalType::RemoveMember(_) => "remove_member",
+        ProposalType::SoftwareUpgrade(_) => "software_upgrade",
+        ProposalType::GenericText(_) => "generic_text",
+        ProposalType::BudgetAllocation(_, _, _) => "budget_allocation",
+        ProposalType::Resolution(_) => "resolution",
+        ProposalType::ConstitutionAmendment(_) => "constitution_amendment",
+    }
+}
+
+/// Which proposals a delegation applies to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DelegationScope {
+    /// Every proposal.
+    All,
+    /// Proposals of one kind, named as in [`PROPOSAL_KINDS`].
+    ProposalKind(String),
+    /// Proposals tagged with the given topic.
+    Tag(String),
+}
+
+impl DelegationScope {
+    fn specificity(&self) -> u8 {
+        match self {
+            DelegationScope::All => 0,
+            DelegationScope::ProposalKind(_) => 1,
+            DelegationScope::Tag(_) => 2,
+        }
+    }
+
+    /// Whether this scope covers `proposal` carrying `tags`.
+    pub fn matches(&self, proposal: &Proposal, tags: &[String]) -> bool {
+        match self {
+            DelegationScope::All => true,
+            DelegationScope::ProposalKind(kind) => proposal_kind(&proposal.proposal_type) == kind,
+            DelegationScope::Tag(tag) => tags.iter().any(|t| t == tag),
+        }
+    }
+}
+
+impl fmt::Display for DelegationScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DelegationScope::All => write!(f, "all"),
+            DelegationScope::ProposalKind(kind) => write!(f, "type:{}", kind),
+            DelegationScope::Tag(tag) => write!(f, "tag:{}", tag),
+        }
+    }
+}
+
+impl FromStr for DelegationScope {
+    type Err = CommonError;
+
+    /// Parses `all`, `type:<kind>` or `tag:<topic>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(DelegationScope::All);
+        }
+        if let Some(kind) = s.strip_prefix("type:") {
+            if !PROPOSAL_KINDS.contains(&kind) {
+                return Err(CommonError::InvalidInputError(format!(
+                    "Unknown proposal type '{}'",
+                    kind
+                )));
+            }
+            return Ok(DelegationScope::ProposalKind(kind.to_string()));
+        }
+        if let Some(tag) = s.strip_prefix("tag:") {
+            if tag.is_empty() {
+                return Err(CommonError::InvalidInputError(
+                    "Delegation tag must not be empty".to_string(),
+                ));
+            }
+            return Ok(DelegationScope::Tag(tag.to_string()));
+        }
+        Err(CommonError::InvalidInputError(format!(
+            "Invalid delegation scope '{}'",
+            s
+        )))
+    }
+}
+
+/// A single delegation edge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Delegation {
+    pub delegator: Did,
+    pub delegate: Did,
+    pub scope: DelegationScope,
+    pub created_at: u64,
+    /// Unix timestamp after which the delegation no longer applies.
+    pub expires_at: Option<u64>,
+}
+
+impl Delegation {
+    /// Whether the delegation is in force at `at`.
+    pub fn is_active(&self, at: u64) -> bool {
+        self.expires_at.map(|exp| at < exp).unwrap_or(true)
+    }
+}
+
+/// How a member's vote on a proposal was resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DelegationResolution {
+    /// The member voted directly.
+    Direct(VoteOption),
+    /// The vote was inherited along `path`, which starts with the member and
+    /// ends with the delegate who actually voted.
+    Delegated { option: VoteOption, path: Vec<Did> },
+    /// The delegation chain loops back to a member already on `path`.
+    Cycle(Vec<Did>),
+    /// Neither the member nor anyone on their chain voted.
+    NoVote,
+}
+
+impl DelegationResolution {
+    /// The vote counted for the member, if any.
+    pub fn option(&self) -> Option<VoteOption> {
+        match self {
+            DelegationResolution::Direct(option) => Some(*option),
+            DelegationResolution::Delegated { option, .. } => Some(*option),
+            _ => None,
+        }
+    }
+}
+
+/// Delegations of one member, keyed by the delegator's DID.
+pub(crate) type DelegationStore = ProposalStateStore<Vec<Delegation>>;
+/// Topic tags of a proposal.
+pub(crate) type ProposalTagStore = ProposalStateStore<Vec<String>>;
+
+/// Key of `delegator`'s entry in a [`DelegationStore`].
+pub(crate) fn delegator_key(delegator: &Did) -> ProposalId {
+    ProposalId(delegator.to_string())
+}
+
+/// Delegations and proposal tags held by a [`GovernanceModule`](crate::GovernanceModule).
+#[derive(Debug, Clone, Default)]
+pub struct DelegationRegistry {
+    delegations: HashMap<Did, Vec<Delegation>>,
+    proposal_tags: HashMap<ProposalId, Vec<String>>,
+}
+
+impl DelegationRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rebuild a registry from persisted delegations and proposal tags.
+    #[cfg(feature = "persist-sled")]
+    pub(crate) fn from_stores(
+        delegations: &DelegationStore,
+        tags: &ProposalTagStore,
+    ) -> Result<Self, CommonError> {
+        let mut registry = Self::new();
+        for entries in delegations.list()? {
+            if let Some(first) = entries.first() {
+                registry
+                    .delegations
+                    .insert(first.delegator.clone(), entries);
+            }
+        }
+        registry.proposal_tags = tags.entries()?.into_iter().collect();
+        Ok(registry)
+    }
+
+    /// Current delegations of `delegator`.
+    pub(crate) fn delegations_of(&self, delegator: &Did) -> Vec<Delegation> {
+        self.delegations.get(delegator).cloned().unwrap_or_default()
+    }
+
+    /// Add or replace the delegation for `delegation.delegator` in its scope.
+    ///
+    /// Fails if the new edge would close a loop among delegations that could
+    /// apply to the same proposal.
+    pub fn delegate(&mut self, delegation: Delegation) -> Result<(), CommonError> {
+        if delegation.delegator == delegation.delegate {
+            return Err(CommonError::InvalidInputError(
+                "Cannot delegate a vote to oneself".to_string(),
+            ));
+        }
+        if self.reaches(
+            &delegation.delegate,
+            &delegation.delegator,
+            &delegation.scope,
+        ) {
+            return Err(CommonError::PolicyDenied(format!(
+                "Delegating from {} to {} in scope {} would create a cycle",
+                delegation.delegator, delegation.delegate, delegation.scope
+            )));
+        }
+        let entries = self
+            .delegations
+            .entry(delegation.delegator.clone())
+            .or_default();
+        entries.retain(|d| d.scope != delegation.scope);
+        entries.push(delegation);
+        Ok(())
+    }
+
+    /// Remove the delegation of `delegator` in `scope`, or all of them when
+    /// `scope` is `None`.
+    pub fn revoke(&mut self, delegator: &Did, scope: Option<&DelegationScope>) {
+        match scope {
+            Some(scope) => {
+                if let Some(entries) = self.delegations.get_mut(delegator) {
+                    entries.retain(|d| &d.scope != scope);
+                    if entries.is_empty() {
+                        self.delegations.remove(delegator);
+                    }
+                }
+            }
+            None => {
+                self.delegations.remove(delegator);
+            }
+        }
+    }
+
+    /// Attach topic tags to a proposal.
+    pub fn set_tags(&mut self, proposal_id: ProposalId, tags: Vec<String>) {
+        self.proposal_tags.insert(proposal_id, tags);
+    }
+
+    /// Tags attached to a proposal.
+    pub fn tags(&self, proposal_id: &ProposalId) -> &[String] {
+        self.proposal_tags
+            .get(proposal_id)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// All delegation edges, sorted by delegator and scope.
+    pub fn edges(&self) -> Vec<Delegation> {
+        let mut edges: Vec<Delegation> = self.delegations.values().flatten().cloned().collect();
+        edges.sort_by_key(|d| (d.delegator.to_string(), d.scope.to_string()));
+        edges
+    }
+
+    /// Drop delegations that expired before `now`.
+    pub fn prune_expired(&mut self, now: u64) {
+        for entries in self.delegations.values_mut() {
+            entries.retain(|d| d.is_active(now));
+        }
+        self.delegations.retain(|_, entries| !entries.is_empty());
+    }
+
+    /// The delegation of `delegator` that governs `proposal`, if any. Only
+    /// delegations made before the voting deadline count.
+    pub fn applicable(&self, delegator: &Did, proposal: &Proposal) -> Option<&Delegation> {
+        let at = proposal.voting_deadline;
+        let tags = self.tags(&proposal.id);
+        self.delegations
+            .get(delegator)?
+            .iter()
+            .filter(|d| d.created_at < at && d.is_active(at) && d.scope.matches(proposal, tags))
+            .max_by_key(|d| (d.scope.specificity(), d.created_at))
+    }
+
+    /// Every delegator's governing delegation for `proposal`.
+    pub fn applicable_to(&self, proposal: &Proposal) -> Vec<Delegation> {
+        let mut edges: Vec<Delegation> = self
+            .delegations
+            .keys()
+            .filter_map(|delegator| self.applicable(delegator, proposal).cloned())
+            .collect();
+        edges.sort_by_key(|d| d.delegator.to_string());
+        edges
+    }
+
+    /// Resolve the vote counted for `member` on `proposal`.
+    pub fn resolve(&self, member: &Did, proposal: &Proposal) -> DelegationResolution {
+        if let Some(vote) = proposal.votes.get(member) {
+            return DelegationResolution::Direct(vote.option);
+        }
+        let mut path = vec![member.clone()];
+        let mut current = member;
+        while let Some(delegation) = self.applicable(current, proposal) {
+            let next = &delegation.delegate;
+            if path.contains(next) {
+                path.push(next.clone());
+                return DelegationResolution::Cycle(path);
+            }
+            path.push(next.clone());
+            if let Some(vote) = proposal.votes.get(next) {
+                return DelegationResolution::Delegated {
+                    option: vote.option,
+                    path,
+                };
+            }
+            current = next;
+        }
+        DelegationResolution::NoVote
+    }
+
+    /// Number of members whose vote each voter carries on `proposal`,
+    /// including their own.
+    pub fn effective_weights(
+        &self,
+        members: &HashSet<Did>,
+        proposal: &Proposal,
+    ) -> HashMap<Did, usize> {
+        let mut weights = HashMap::new();
+        for member in members {
+            let voter = match self.resolve(member, proposal) {
+                DelegationResolution::Direct(_) => member.clone(),
+                DelegationResolution::Delegated { path, .. } => {
+                    path.last().cloned().unwrap_or_else(|| member.clone())
+                }
+                _ => continue,
+            };
+            *weights.entry(voter).or_insert(0) += 1;
+        }
+        weights
+    }
+
+    /// Whether `target` can be reached from `start` through delegations that
+    /// might apply to the same proposal as a delegation in `scope`.
+    fn reaches(&self, start: &Did, target: &Did, scope: &DelegationScope) -> bool {
+        let mut queue = VecDeque::from([start.clone()]);
+        let mut seen = HashSet::new();
+        while let Some(did) = queue.pop_front() {
+            if &did == target {
+                return true;
+            }
+            if !seen.insert(did.clone()) {
+                continue;
+            }
+            if let Some(entries) = self.delegations.get(&did) {
+                for d in entries {
+                    if d.scope == DelegationScope::All
+                        || *scope == DelegationScope::All
+                        || d.scope == *scope
+                    {
+                        queue.push_back(d.delegate.clone());
+                    }
+                }
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ProposalSponsorship, ProposalStatus, Vote};
+
+    fn did(name: &str) -> Did {
+        Did::from_str(&format!("did:example:{name}")).unwrap()
+    }
+
+    fn delegation(from: &str, to: &str, scope: DelegationScope) -> Delegation {
+        Delegation {
+            delegator: did(from),
+            delegate: did(to),
+            scope,
+            created_at: 0,
+            expires_at: None,
+        }
+    }
+
+    fn proposal(kind: ProposalType, votes: &[(&str, VoteOption)]) -> Proposal {
+        let id = ProposalId("p1".into());
+        Proposal {
+            id: id.clone(),
+            proposer: did("proposer"),
+            proposal_type: kind,
+            description: String::new(),
+            created_at: 0,
+            voting_deadline: 100,
+            status: ProposalStatus::VotingOpen,
+            votes: votes
+                .iter()
+                .map(|(voter, option)| {
+                    (
+                        did(voter),
+                        Vote {
+                            voter: did(voter),
+                            proposal_id: id.clone(),
+                            option: *option,
+                            voted_at: 0,
+                        },
+                    )
+                })
+                .collect(),
+            quorum: None,
+            threshold: None,
+            content_cid: None,
+            sponsorship: ProposalSponsorship::new(),
+            accepted_at: None,
+            timelock_delay: None,
+            veto: None,
+        }
+    }
+
+    #[test]
+    fn transitive_chain_and_direct_override() {
+        let mut reg = DelegationRegistry::new();
+        reg.delegate(delegation("a", "b", DelegationScope::All))
+            .unwrap();
+        reg.delegate(delegation("b", "c", DelegationScope::All))
+            .unwrap();
+        let p = proposal(
+            ProposalType::GenericText("x".into()),
+            &[("c", VoteOption::Yes)],
+        );
+        assert_eq!(
+            reg.resolve(&did("a"), &p),
+            DelegationResolution::Delegated {
+                option: VoteOption::Yes,
+                path: vec![did("a"), did("b"), did("c")],
+            }
+        );
+
+        let p = proposal(
+            ProposalType::GenericText("x".into()),
+            &[("c", VoteOption::Yes), ("b", VoteOption::No)],
+        );
+        assert_eq!(reg.resolve(&did("a"), &p).option(), Some(VoteOption::No));
+        let members: HashSet<Did> = ["a", "b", "c"].iter().map(|n| did(n)).collect();
+        let weights = reg.effective_weights(&members, &p);
+        assert_eq!(weights[&did("b")], 2);
+        assert_eq!(weights[&did("c")], 1);
+    }
+
+    #[test]
+    fn cycles_are_rejected_or_unresolved() {
+        let mut reg = DelegationRegistry::new();
+        reg.delegate(delegation("a", "b", DelegationScope::All))
+            .unwrap();
+        assert!(reg
+            .delegate(delegation("b", "a", DelegationScope::Tag("x".into())))
+            .is_err());
+
+        // Loops formed by scopes that never apply together are allowed but
+        // resolve to no vote if a proposal ends up matching both.
+        reg.delegate(delegation("c", "d", DelegationScope::Tag("x".into())))
+            .unwrap();
+        reg.delegate(delegation(
+            "d",
+            "c",
+            DelegationScope::ProposalKind("generic_text".into()),
+        ))
+        .unwrap();
+        reg.set_tags(ProposalId("p1".into()), vec!["x".into()]);
+        let p = proposal(ProposalType::GenericText("x".into()), &[]);
+        assert!(matches!(
+            reg.resolve(&did("c"), &p),
+            DelegationResolution::Cycle(_)
+        ));
+    }
+
+    #[test]
+    fn scope_specificity_and_expiry() {
+        let mut reg = DelegationRegistry::new();
+        reg.delegate(delegation("a", "general", DelegationScope::All))
+            .unwrap();
+        reg.delegate(delegation(
+            "a",
+            "treasurer",
+            DelegationScope::ProposalKind("budget_allocation".into()),
+        ))
+        .unwrap();
+        let mut tagged = delegation("a", "ecologist", DelegationScope::Tag("climate".into()));
+        tagged.expires_at = Some(50);
+        reg.delegate(tagged).unwrap();
+
+        let budget = proposal(
+            ProposalType::BudgetAllocation(did("x"), 10, "y".into()),
+            &[("treasurer", VoteOption::No), ("general", VoteOption::Yes)],
+        );
+        assert_eq!(
+            reg.resolve(&did("a"), &budget).option(),
+            Some(VoteOption::No)
+        );
+
+        // The tag delegation has expired by the voting deadline.
+        reg.set_tags(ProposalId("p1".into()), vec!["climate".into()]);
+        assert_eq!(
+            reg.applicable(&did("a"), &budget).unwrap().delegate,
+            did("treasurer")
+        );
+
+        let text = proposal(
+            ProposalType::GenericText("x".into()),
+            &[("general", VoteOption::Yes)],
+        );
+        assert_eq!(
+            reg.resolve(&did("a"), &text).option(),
+            Some(VoteOption::Yes)
+        );
+
+        // Delegations made after the voting deadline do not count.
+        let mut late = delegation("b", "general", DelegationScope::All);
+        late.created_at = text.voting_deadline;
+        reg.delegate(late).unwrap();
+        assert_eq!(reg.resolve(&did("b"), &text), DelegationResolution::NoVote);
+    }
+
+    #[test]
+    fn scope_parsing_round_trips() {
+        for s in [
+            "all",
+            "type:budget_allocation",
+            "type:constitution_amendment",
+            "tag:climate",
+        ] {
+            assert_eq!(DelegationScope::from_str(s).unwrap().to_string(), s);
+        }
+        assert!(DelegationScope::from_str("type:unknown").is_err());
+        assert!(DelegationScope::from_str("bogus").is_err());
+    }
+}
@@ -0,0 +1,802 @@
+//! Structured deliberation: revisions, amendments and threaded comments
+//!
+//! While a proposal is in [`ProposalStatus::Deliberation`] it can evolve:
+//!
+//! - **Revisions.** Every version of a proposal is a [`ProposalRevision`]
+//!   anchored as a DAG block that links to its predecessor, so the full
+//!   history can be walked from the latest CID.
+//! - **Amendments.** Any member may propose an [`Amendment`] against the
+//!   current revision. Amendments are voted on before the main vote and
+//!   adopted ones produce a new revision. An amendment drafted against a
+//!   revision that has since been replaced is marked superseded.
+//! - **Comments.** Members discuss a revision through [`Comment`]s signed
+//!   with their `did:key`. Replies link to their parent, forming threads.
+//!
+//! When voting opens the proposal is bound to the head revision, and
+//! [`GovernanceModule::cast_vote_on_revision`] rejects ballots cast against
+//! any other CID.
+//!
+//! Methods that create revisions or comments return the [`DagBlock`]s to be
+//! stored by the caller; the module keeps an index of them alongside the
+//! proposal.
+
+use crate::{GovernanceModule, ProposalId, ProposalStatus, VoteOption};
+use icn_common::{
+    compute_merkle_cid, Cid, CommonError, DagBlock, DagLink, Did, Signable, SignatureBytes,
+    TimeProvider,
+};
+use icn_identity::verifying_key_from_did_key;
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// One version of a proposal's text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProposalRevision {
+    pub proposal_id: ProposalId,
+    /// Zero for the text as submitted.
+    pub number: u32,
+    pub description: String,
+    pub body_cid: Option<Cid>,
+    pub author: Did,
+    /// Amendment this revision was produced by, if any.
+    pub amendment_id: Option<String>,
+    pub previous: Option<Cid>,
+    pub created_at: u64,
+}
+
+impl ProposalRevision {
+    /// Decode a revision from its anchored block.
+    pub fn from_block(block: &DagBlock) -> Result<Self, CommonError> {
+        serde_json::from_slice(&block.data).map_err(|e| {
+            CommonError::DeserializationError(format!("Failed to decode revision: {}", e))
+        })
+    }
+}
+
+/// A revision together with the CID of its block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RevisionRecord {
+    pub cid: Cid,
+    pub revision: ProposalRevision,
+}
+
+/// Lifecycle of an amendment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AmendmentStatus {
+    /// Accepting votes.
+    Open,
+    /// Passed and applied as a new revision.
+    Adopted,
+    /// Did not reach quorum or threshold.
+    Rejected,
+    /// Passed, but the revision it amends was replaced first.
+    Superseded,
+}
+
+/// Proposed change to a proposal under deliberation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Amendment {
+    pub id: String,
+    pub proposal_id: ProposalId,
+    pub proposer: Did,
+    /// Revision the amendment was drafted against.
+    pub base_revision: Cid,
+    /// Replacement text for the proposal.
+    pub description: String,
+    pub body_cid: Option<Cid>,
+    pub rationale: String,
+    pub votes: HashMap<Did, VoteOption>,
+    pub status: AmendmentStatus,
+    pub created_at: u64,
+    /// Revision produced when the amendment was adopted.
+    pub result_revision: Option<Cid>,
+}
+
+/// Signed discussion entry on a proposal revision.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Comment {
+    pub proposal_id: ProposalId,
+    /// Revision the comment refers to.
+    pub revision: Cid,
+    /// Comment being replied to, if any.
+    pub parent: Option<Cid>,
+    pub author: Did,
+    pub text: String,
+    pub created_at: u64,
+    pub signature: Option<SignatureBytes>,
+}
+
+impl Signable for Comment {
+    fn to_signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(self.proposal_id.0.as_bytes());
+        bytes.extend_from_slice(self.revision.to_string().as_bytes());
+        if let Some(parent) = &self.parent {
+            bytes.extend_from_slice(parent.to_string().as_bytes());
+        }
+        bytes.extend_from_slice(self.author.to_string().as_bytes());
+        bytes.extend_from_slice(self.text.as_bytes());
+        bytes.extend_from_slice(&self.created_at.to_le_bytes());
+        Ok(bytes)
+    }
+}
+
+impl Comment {
+    /// Decode a comment from its anchored block.
+    pub fn from_block(block: &DagBlock) -> Result<Self, CommonError> {
+        serde_json::from_slice(&block.data).map_err(|e| {
+            CommonError::DeserializationError(format!("Failed to decode comment: {}", e))
+        })
+    }
+}
+
+/// A comment together with the CID of its block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CommentRecord {
+    pub cid: Cid,
+    pub comment: Comment,
+}
+
+/// A comment and its replies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CommentThread {
+    pub cid: Cid,
+    pub comment: Comment,
+    pub replies: Vec<CommentThread>,
+}
+
+/// Deliberation bookkeeping for a single proposal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeliberationState {
+    pub proposal_id: ProposalId,
+    /// Revisions, oldest first. The last entry is the head.
+    pub revisions: Vec<RevisionRecord>,
+    pub amendments: Vec<Amendment>,
+    pub comments: Vec<CommentRecord>,
+    /// Revision the main vote is bound to once voting opens.
+    pub bound_revision: Option<Cid>,
+}
+
+impl DeliberationState {
+    /// The latest revision; an error if a corrupt record has none.
+    pub fn head(&self) -> Result<&RevisionRecord, CommonError> {
+        self.revisions.last().ok_or_else(|| {
+            CommonError::DeserializationError(format!(
+                "Deliberation for proposal {} has no revisions",
+                self.proposal_id.0
+            ))
+        })
+    }
+
+    /// Comments arranged into reply threads, oldest first.
+    pub fn threads(&self) -> Vec<CommentThread> {
+        fn build(records: &[CommentRecord], parent: Option<&Cid>) -> Vec<CommentThread> {
+            records
+                .iter()
+                .filter(|r| r.comment.parent.as_ref() == parent)
+                .map(|r| CommentThread {
+                    cid: r.cid.clone(),
+                    comment: r.comment.clone(),
+                    replies: build(records, Some(&r.cid)),
+                })
+                .collect()
+        }
+        build(&self.comments, None)
+    }
+
+    fn has_open_amendments(&self) -> bool {
+        self.amendments
+            .iter()
+            .any(|a| a.status == AmendmentStatus::Open)
+    }
+}
+
+/// Storage for deliberation state, mirroring the governance backend.
+pub(crate) type DeliberationStore = crate::state_store::ProposalStateStore<DeliberationState>;
+
+pub(crate) fn anchor<T: serde::Serialize>(
+    value: &T,
+    links: Vec<DagLink>,
+    author: &Did,
+    timestamp: u64,
+    signature: Option<SignatureBytes>,
+) -> Result<DagBlock, CommonError> {
+    let data = serde_json::to_vec(value)
+        .map_err(|e| CommonError::SerializationError(format!("Failed to encode block: {}", e)))?;
+    let cid = compute_merkle_cid(0x71, &data, &links, timestamp, author, &signature, &None);
+    Ok(DagBlock {
+        cid,
+        data,
+        links,
+        timestamp,
+        author_did: author.clone(),
+        signature,
+        scope: None,
+    })
+}
+
+fn link(cid: &Cid, name: &str) -> DagLink {
+    DagLink {
+        cid: cid.clone(),
+        name: name.to_string(),
+        size: 0,
+    }
+}
+
+fn revision_block(revision: &ProposalRevision) -> Result<DagBlock, CommonError> {
+    let mut links = Vec::new();
+    if let Some(prev) = &revision.previous {
+        links.push(link(prev, "previous"));
+    }
+    if let Some(body) = &revision.body_cid {
+        links.push(link(body, "body"));
+    }
+    anchor(revision, links, &revision.author, revision.created_at, None)
+}
+
+impl GovernanceModule {
+    /// Record the submitted text of a proposal as revision zero.
+    ///
+    /// Returns the block to anchor. Calling this again is an error.
+    pub fn start_deliberation(
+        &mut self,
+        proposal_id: &ProposalId,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<DagBlock, CommonError> {
+        if self.deliberation.contains(proposal_id) {
+            return Err(CommonError::InvalidInputError(format!(
+                "Deliberation for proposal {} has already started",
+                proposal_id.0
+            )));
+        }
+        let proposal = self.deliberating_proposal(proposal_id)?;
+        let revision = ProposalRevision {
+            proposal_id: proposal_id.clone(),
+            number: 0,
+            description: proposal.description,
+            body_cid: proposal.content_cid,
+            author: proposal.proposer,
+            amendment_id: None,
+            previous: None,
+            created_at: time_provider.unix_seconds(),
+        };
+        let block = revision_block(&revision)?;
+        self.deliberation.put(
+            proposal_id,
+            &DeliberationState {
+                proposal_id: proposal_id.clone(),
+                revisions: vec![RevisionRecord {
+                    cid: block.cid.clone(),
+                    revision,
+                }],
+                amendments: Vec::new(),
+                comments: Vec::new(),
+                bound_revision: None,
+            },
+        )?;
+        Ok(block)
+    }
+
+    /// Deliberation state of a proposal, if deliberation has started.
+    pub fn deliberation(
+        &self,
+        proposal_id: &ProposalId,
+    ) -> Result<Option<DeliberationState>, CommonError> {
+        self.deliberation.get(proposal_id)
+    }
+
+    /// Replace the proposal text with a new revision authored by the proposer.
+    pub fn revise_proposal(
+        &mut self,
+        proposal_id: &ProposalId,
+        author: &Did,
+        description: String,
+        body_cid: Option<Cid>,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<DagBlock, CommonError> {
+        let proposal = self.deliberating_proposal(proposal_id)?;
+        if &proposal.proposer != author {
+            return Err(CommonError::PermissionDenied(
+                "Only the proposer can revise a proposal".to_string(),
+            ));
+        }
+        let mut state = self.deliberation_state(proposal_id)?;
+        if state.has_open_amendments() {
+            return Err(CommonError::PolicyDenied(format!(
+                "Proposal {} has open amendments; resolve them first",
+                proposal_id.0
+            )));
+        }
+        let block = self.push_revision(
+            &mut state,
+            author.clone(),
+            description,
+            body_cid,
+            None,
+            time_provider.unix_seconds(),
+        )?;
+        self.deliberation.put(proposal_id, &state)?;
+        Ok(block)
+    }
+
+    /// Propose an amendment against the current head revision.
+    pub fn propose_amendment(
+        &mut self,
+        proposal_id: &ProposalId,
+        proposer: Did,
+        description: String,
+        body_cid: Option<Cid>,
+        rationale: String,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<String, CommonError> {
+        if !self.members.contains(&proposer) {
+            return Err(CommonError::PermissionDenied(format!(
+                "{} is not a member and cannot propose amendments",
+                proposer
+            )));
+        }
+        self.deliberating_proposal(proposal_id)?;
+        let mut state = self.deliberation_state(proposal_id)?;
+        let id = format!("{}-a{}", proposal_id.0, state.amendments.len() + 1);
+        state.amendments.push(Amendment {
+            id: id.clone(),
+            proposal_id: proposal_id.clone(),
+            proposer,
+            base_revision: state.head()?.cid.clone(),
+            description,
+            body_cid,
+            rationale,
+            votes: HashMap::new(),
+            status: AmendmentStatus::Open,
+            created_at: time_provider.unix_seconds(),
+            result_revision: None,
+        });
+        self.deliberation.put(proposal_id, &state)?;
+        Ok(id)
+    }
+
+    /// Cast or change a member's vote on an open amendment.
+    pub fn vote_on_amendment(
+        &mut self,
+        proposal_id: &ProposalId,
+        amendment_id: &str,
+        voter: Did,
+        option: VoteOption,
+    ) -> Result<(), CommonError> {
+        if !self.members.contains(&voter) {
+            return Err(CommonError::PermissionDenied(format!(
+                "{} is not a member and cannot vote",
+                voter
+            )));
+        }
+        self.deliberating_proposal(proposal_id)?;
+        let mut state = self.deliberation_state(proposal_id)?;
+        let amendment = state
+            .amendments
+            .iter_mut()
+            .find(|a| a.id == amendment_id)
+            .ok_or_else(|| {
+                CommonError::ResourceNotFound(format!("Amendment {} not found", amendment_id))
+            })?;
+        if amendment.status != AmendmentStatus::Open {
+            return Err(CommonError::InvalidInputError(format!(
+                "Amendment {} is no longer open",
+                amendment_id
+            )));
+        }
+        amendment.votes.insert(voter, option);
+        self.deliberation.put(proposal_id, &state)
+    }
+
+    /// Tally all open amendments in the order they were proposed.
+    ///
+    /// Amendments pass under the proposal's quorum and threshold. Each adopted
+    /// amendment becomes a new revision; the returned blocks must be anchored.
+    pub fn resolve_amendments(
+        &mut self,
+        proposal_id: &ProposalId,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<(Vec<Amendment>, Vec<DagBlock>), CommonError> {
+        let proposal = self.deliberating_proposal(proposal_id)?;
+        let quorum = proposal.quorum.unwrap_or(self.quorum);
+        let threshold = proposal.threshold.unwrap_or(self.threshold);
+        let now = time_provider.unix_seconds();
+        let mut state = self.deliberation_state(proposal_id)?;
+        let mut blocks = Vec::new();
+        let mut resolved = Vec::new();
+
+        for idx in 0..state.amendments.len() {
+            if state.amendments[idx].status != AmendmentStatus::Open {
+                continue;
+            }
+            let (mut yes, mut total) = (0usize, 0usize);
+            for (voter, option) in &state.amendments[idx].votes {
+                if self.members.contains(voter) {
+                    total += 1;
+                    if *option == VoteOption::Yes {
+                        yes += 1;
+                    }
+                }
+            }
+            let passed = total >= quorum && (yes as f32) >= (total as f32 * threshold);
+            let status = if !passed {
+                AmendmentStatus::Rejected
+            } else if state.amendments[idx].base_revision != state.head()?.cid {
+                AmendmentStatus::Superseded
+            } else {
+                let amendment = state.amendments[idx].clone();
+                let block = self.push_revision(
+                    &mut state,
+                    amendment.proposer,
+                    amendment.description,
+                    amendment.body_cid,
+                    Some(amendment.id),
+                    now,
+                )?;
+                state.amendments[idx].result_revision = Some(block.cid.clone());
+                blocks.push(block);
+                AmendmentStatus::Adopted
+            };
+            state.amendments[idx].status = status;
+            resolved.push(state.amendments[idx].clone());
+        }
+        self.deliberation.put(proposal_id, &state)?;
+        Ok((resolved, blocks))
+    }
+
+    /// Add a signed comment to a proposal's discussion.
+    ///
+    /// The author must be a member identified by a `did:key` whose key
+    /// produced `comment.signature`.
+    pub fn add_comment(&mut self, comment: Comment) -> Result<DagBlock, CommonError> {
+        if !self.members.contains(&comment.author) {
+            return Err(CommonError::PermissionDenied(format!(
+                "{} is not a member and cannot comment",
+                comment.author
+            )));
+        }
+        let signature = comment.signature.clone().ok_or_else(|| {
+            CommonError::CryptoError("Comments must be signed by their author".to_string())
+        })?;
+        let key = verifying_key_from_did_key(&comment.author)?;
+        comment.verify(&signature, &key)?;
+
+        let proposal = self.get_proposal(&comment.proposal_id)?.ok_or_else(|| {
+            CommonError::ResourceNotFound(format!("Proposal {} not found", comment.proposal_id.0))
+        })?;
+        if !matches!(
+            proposal.status,
+            ProposalStatus::Deliberation | ProposalStatus::VotingOpen
+        ) {
+            return Err(CommonError::PolicyDenied(format!(
+                "Proposal {} is closed for discussion",
+                proposal.id.0
+            )));
+        }
+        let mut state = self.deliberation_state(&comment.proposal_id)?;
+        if !state.revisions.iter().any(|r| r.cid == comment.revision) {
+            return Err(CommonError::InvalidInputError(format!(
+                "Revision {} does not belong to proposal {}",
+                comment.revision, comment.proposal_id.0
+            )));
+        }
+        let mut links = vec![link(&comment.revision, "revision")];
+        if let Some(parent) = &comment.parent {
+            if !state.comments.iter().any(|c| &c.cid == parent) {
+                return Err(CommonError::ResourceNotFound(format!(
+                    "Parent comment {} not found",
+                    parent
+                )));
+            }
+            links.push(link(parent, "parent"));
+        }
+        let block = anchor(
+            &comment,
+            links,
+            &comment.author,
+            comment.created_at,
+            Some(signature),
+        )?;
+        state.comments.push(CommentRecord {
+            cid: block.cid.clone(),
+            comment,
+        });
+        self.deliberation.put(&proposal.id, &state)?;
+        Ok(block)
+    }
+
+    /// Cast a vote that is only accepted if voting is bound to `revision`.
+    pub fn cast_vote_on_revision(
+        &mut self,
+        voter: Did,
+        proposal_id: &ProposalId,
+        option: VoteOption,
+        revision: &Cid,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<(), CommonError> {
+        let bound = self
+            .deliberation
+            .get(proposal_id)?
+            .and_then(|s| s.bound_revision);
+        if bound.as_ref() != Some(revision) {
+            return Err(CommonError::PolicyDenied(format!(
+                "Proposal {} is not being voted on at revision {}",
+                proposal_id.0, revision
+            )));
+        }
+        self.cast_vote(voter, proposal_id, option, time_provider)
+    }
+
+    /// Refuse to open voting while amendments are pending.
+    pub(crate) fn check_ready_for_vote(&self, proposal_id: &ProposalId) -> Result<(), CommonError> {
+        if let Some(state) = self.deliberation.get(proposal_id)? {
+            if state.has_open_amendments() {
+                return Err(CommonError::PolicyDenied(format!(
+                    "Proposal {} has open amendments; resolve them before voting",
+                    proposal_id.0
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Bind the main vote to the head revision once voting has opened.
+    pub(crate) fn bind_vote_revision(
+        &mut self,
+        proposal_id: &ProposalId,
+    ) -> Result<(), CommonError> {
+        if let Some(mut state) = self.deliberation.get(proposal_id)? {
+            state.bound_revision = Some(state.head()?.cid.clone());
+            self.deliberation.put(proposal_id, &state)?;
+        }
+        Ok(())
+    }
+
+    fn deliberating_proposal(
+        &self,
+        proposal_id: &ProposalId,
+    ) -> Result<crate::Proposal, CommonError> {
+        let proposal = self.get_proposal(proposal_id)?.ok_or_else(|| {
+            CommonError::ResourceNotFound(format!("Proposal {} not found", proposal_id.0))
+        })?;
+        if proposal.status != ProposalStatus::Deliberation {
+            return Err(CommonError::PolicyDenied(format!(
+                "Proposal {} is not under deliberation (status {:?})",
+                proposal_id.0, proposal.status
+            )));
+        }
+        Ok(proposal)
+    }
+
+    fn deliberation_state(
+        &self,
+        proposal_id: &ProposalId,
+    ) -> Result<DeliberationState, CommonError> {
+        self.deliberation.get(proposal_id)?.ok_or_else(|| {
+            CommonError::ResourceNotFound(format!(
+                "Deliberation for proposal {} has not started",
+                proposal_id.0
+            ))
+        })
+    }
+
+    fn push_revision(
+        &mut self,
+        state: &mut DeliberationState,
+        author: Did,
+        description: String,
+        body_cid: Option<Cid>,
+        amendment_id: Option<String>,
+        now: u64,
+    ) -> Result<DagBlock, CommonError> {
+        let head = state.head()?;
+        let revision = ProposalRevision {
+            proposal_id: state.proposal_id.clone(),
+            number: head.revision.number + 1,
+            description: description.clone(),
+            body_cid: body_cid.clone(),
+            author,
+            amendment_id,
+            previous: Some(head.cid.clone()),
+            created_at: now,
+        };
+        let block = revision_block(&revision)?;
+        state.revisions.push(RevisionRecord {
+            cid: block.cid.clone(),
+            revision,
+        });
+        self.update_proposal(&state.proposal_id, |p| {
+            p.description = description;
+            p.content_cid = body_cid;
+        })?;
+        Ok(block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProposalSubmission;
+    use crate::ProposalType;
+    use icn_common::FixedTimeProvider;
+    use icn_dag::{InMemoryDagStore, StorageService};
+    use icn_identity::{did_key_from_verifying_key, generate_ed25519_keypair, SigningKey};
+    use std::str::FromStr;
+
+    fn member(gov: &mut GovernanceModule) -> (Did, SigningKey) {
+        let (sk, pk) = generate_ed25519_keypair();
+        let did = Did::from_str(&did_key_from_verifying_key(&pk)).unwrap();
+        gov.add_member(did.clone());
+        (did, sk)
+    }
+
+    fn setup() -> (GovernanceModule, ProposalId, Vec<(Did, SigningKey)>) {
+        let mut gov = GovernanceModule::new();
+        let members: Vec<_> = (0..3).map(|_| member(&mut gov)).collect();
+        gov.set_quorum(2);
+        gov.set_threshold(0.5);
+        let pid = gov
+            .submit_proposal(
+                ProposalSubmission {
+                    proposer: members[0].0.clone(),
+                    proposal_type: ProposalType::GenericText("garden".into()),
+                    description: "Build a garden".into(),
+                    duration_secs: 600,
+                    quorum: None,
+                    threshold: None,
+                    content_cid: None,
+                    timelock_delay: None,
+                },
+                &FixedTimeProvider::new(100),
+            )
+            .unwrap();
+        (gov, pid, members)
+    }
+
+    #[test]
+    fn amendments_create_linked_revisions() {
+        let (mut gov, pid, m) = setup();
+        let tp = FixedTimeProvider::new(200);
+        let mut dag = InMemoryDagStore::new();
+        let genesis = gov.start_deliberation(&pid, &tp).unwrap();
+        dag.put(&genesis).unwrap();
+
+        let a1 = gov
+            .propose_amendment(
+                &pid,
+                m[1].0.clone(),
+                "Build a community garden".into(),
+                None,
+                "clarity".into(),
+                &tp,
+            )
+            .unwrap();
+        let a2 = gov
+            .propose_amendment(
+                &pid,
+                m[2].0.clone(),
+                "Build a park".into(),
+                None,
+                "".into(),
+                &tp,
+            )
+            .unwrap();
+        for (did, _) in &m {
+            gov.vote_on_amendment(&pid, &a1, did.clone(), VoteOption::Yes)
+                .unwrap();
+            gov.vote_on_amendment(&pid, &a2, did.clone(), VoteOption::Yes)
+                .unwrap();
+        }
+        assert!(gov.open_voting(&pid).is_err());
+
+        let (resolved, blocks) = gov.resolve_amendments(&pid, &tp).unwrap();
+        assert_eq!(resolved[0].status, AmendmentStatus::Adopted);
+        assert_eq!(resolved[1].status, AmendmentStatus::Superseded);
+        assert_eq!(blocks.len(), 1);
+        dag.put(&blocks[0]).unwrap();
+
+        let stored = dag.get(&blocks[0].cid).unwrap().unwrap();
+        let revision = ProposalRevision::from_block(&stored).unwrap();
+        assert_eq!(revision.previous, Some(genesis.cid.clone()));
+        assert_eq!(stored.links[0].cid, genesis.cid);
+        assert_eq!(
+            gov.get_proposal(&pid).unwrap().unwrap().description,
+            "Build a community garden"
+        );
+
+        gov.open_voting(&pid).unwrap();
+        let head = blocks[0].cid.clone();
+        assert!(gov
+            .cast_vote_on_revision(m[0].0.clone(), &pid, VoteOption::Yes, &genesis.cid, &tp)
+            .is_err());
+        gov.cast_vote_on_revision(m[0].0.clone(), &pid, VoteOption::Yes, &head, &tp)
+            .unwrap();
+        assert_eq!(
+            gov.deliberation(&pid).unwrap().unwrap().bound_revision,
+            Some(head)
+        );
+    }
+
+    #[test]
+    fn only_proposer_revises_and_revisions_block_on_open_amendments() {
+        let (mut gov, pid, m) = setup();
+        let tp = FixedTimeProvider::new(200);
+        gov.start_deliberation(&pid, &tp).unwrap();
+        assert!(gov
+            .revise_proposal(&pid, &m[1].0, "hijack".into(), None, &tp)
+            .is_err());
+        gov.revise_proposal(&pid, &m[0].0, "Build two gardens".into(), None, &tp)
+            .unwrap();
+        gov.propose_amendment(&pid, m[1].0.clone(), "x".into(), None, "".into(), &tp)
+            .unwrap();
+        assert!(gov
+            .revise_proposal(&pid, &m[0].0, "again".into(), None, &tp)
+            .is_err());
+        let (resolved, _) = gov.resolve_amendments(&pid, &tp).unwrap();
+        assert_eq!(resolved[0].status, AmendmentStatus::Rejected);
+        assert_eq!(gov.deliberation(&pid).unwrap().unwrap().revisions.len(), 2);
+    }
+
+    #[test]
+    fn comments_are_signed_and_threaded() {
+        let (mut gov, pid, m) = setup();
+        let tp = FixedTimeProvider::new(200);
+        let genesis = gov.start_deliberation(&pid, &tp).unwrap();
+
+        let mut root = Comment {
+            proposal_id: pid.clone(),
+            revision: genesis.cid.clone(),
+            parent: None,
+            author: m[1].0.clone(),
+            text: "Where would it go?".into(),
+            created_at: 300,
+            signature: None,
+        };
+        assert!(gov.add_comment(root.clone()).is_err());
+        root.signature = Some(root.sign(&m[2].1).unwrap());
+        assert!(gov.add_comment(root.clone()).is_err());
+        root.signature = Some(root.sign(&m[1].1).unwrap());
+        let root_block = gov.add_comment(root).unwrap();
+
+        let mut reply = Comment {
+            proposal_id: pid.clone(),
+            revision: genesis.cid.clone(),
+            parent: Some(root_block.cid.clone()),
+            author: m[0].0.clone(),
+            text: "Behind the hall".into(),
+            created_at: 310,
+            signature: None,
+        };
+        reply.signature = Some(reply.sign(&m[0].1).unwrap());
+        let reply_block = gov.add_comment(reply).unwrap();
+        assert!(reply_block.links.iter().any(|l| l.cid == root_block.cid));
+
+        let threads = gov.deliberation(&pid).unwrap().unwrap().threads();
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].replies[0].comment.text, "Behind the hall");
+    }
+    #[test]
+    fn head_of_state_without_revisions_is_an_error() {
+        let state = DeliberationState {
+            proposal_id: ProposalId("p".into()),
+            revisions: Vec::new(),
+            amendments: Vec::new(),
+            comments: Vec::new(),
+            bound_revision: None,
+        };
+        assert!(matches!(
+            state.head(),
+            Err(CommonError::DeserializationError(_))
+        ));
+    }
+}
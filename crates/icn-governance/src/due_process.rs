@@ -0,0 +1,1136 @@
+//! Due process for recalling and suspending members and stewards
+//!
+//! Sanctions are only applied after a case has run its course:
+//!
+//! 1. A member files a signed [`Complaint`] with evidence. The subject is
+//!    notified at once and has `response_window_secs` to submit a signed
+//!    [`SubjectResponse`].
+//! 2. A hearing panel is drawn by sortition from members who are neither
+//!    party to the case nor sat on an earlier panel for it. Panelists submit
+//!    signed [`PanelVerdict`]s.
+//! 3. A majority finding decides the case. When the complaint is upheld the
+//!    panel imposes the most severe [`Sanction`] a majority of the panel
+//!    supports, so one harsh panelist cannot escalate it.
+//! 4. Either party may [`Appeal`] within `appeal_window_secs`. A larger panel
+//!    then hears the case again and its decision is final.
+//! 5. The sanction takes effect once the decision is final. Suspensions end
+//!    with automatic reinstatement; removal takes the member out of the
+//!    federation, or recalls a steward from their office.
+//!
+//! Each step returns a DAG block linked to the previous one, so the full
+//! record of a case can be audited. Sanctions in force are enforced by
+//! [`GovernanceModule::sanction_in_force`]: suspended or removed members
+//! cannot propose or vote, and the runtime and node use it to block mana
+//! spending and pause credentials.
+
+use crate::deliberation::anchor;
+use crate::federation_governance::{TrustSanction, ViolationType};
+use crate::sortition::{select_members, RandomnessSource, RandomnessTrust, SortitionDraw};
+use crate::{GovernanceModule, ProposalId};
+use icn_common::{
+    Cid, CommonError, DagBlock, DagLink, Did, Signable, SignatureBytes, TimeProvider,
+};
+use icn_identity::verifying_key_from_did_key;
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Windows and panel sizes for due-process cases.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DueProcessConfig {
+    /// Time the subject has to respond to a complaint.
+    pub response_window_secs: u64,
+    /// Time a panel has to reach a majority before it is redrawn.
+    pub hearing_window_secs: u64,
+    /// Time either party has to appeal a decision.
+    pub appeal_window_secs: u64,
+    pub panel_size: usize,
+    pub appeal_panel_size: usize,
+}
+
+impl Default for DueProcessConfig {
+    fn default() -> Self {
+        Self {
+            response_window_secs: 7 * 24 * 3600,
+            hearing_window_secs: 14 * 24 * 3600,
+            appeal_window_secs: 7 * 24 * 3600,
+            panel_size: 3,
+            appeal_panel_size: 5,
+        }
+    }
+}
+
+/// Who a complaint is about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SubjectRole {
+    /// A member of the federation.
+    Member,
+    /// A member holding office in `body`, e.g. a stewards' council. Sanctions
+    /// apply to the office, not to membership.
+    Steward { body: String },
+}
+
+/// Graduated sanctions, from least to most severe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Sanction {
+    Warning,
+    /// Suspension with automatic reinstatement after `duration_secs`.
+    Suspension {
+        duration_secs: u64,
+    },
+    /// Removal from the federation, or recall from office for a steward.
+    Removal,
+}
+
+impl Sanction {
+    fn severity(&self) -> (u8, u64) {
+        match self {
+            Sanction::Warning => (0, 0),
+            Sanction::Suspension { duration_secs } => (1, *duration_secs),
+            Sanction::Removal => (2, 0),
+        }
+    }
+
+    /// Whether the sanction takes away rights while in force.
+    pub fn restricts(&self) -> bool {
+        !matches!(self, Sanction::Warning)
+    }
+
+    /// The equivalent [`TrustSanction`] for federation trust records.
+    pub fn to_trust_sanction(&self, reason: String, decided_at: u64) -> TrustSanction {
+        match self {
+            Sanction::Warning => TrustSanction::Warning {
+                reason,
+                issued_at: decided_at,
+                expires_at: None,
+            },
+            Sanction::Suspension { duration_secs } => TrustSanction::FederationSuspension {
+                duration_seconds: *duration_secs,
+                reason,
+            },
+            Sanction::Removal => TrustSanction::Expulsion { reason, decided_at },
+        }
+    }
+
+    fn bytes(sanction: &Option<Sanction>) -> Vec<u8> {
+        match sanction {
+            None => vec![0],
+            Some(Sanction::Warning) => vec![1],
+            Some(Sanction::Suspension { duration_secs }) => {
+                let mut bytes = vec![2];
+                bytes.extend_from_slice(&duration_secs.to_le_bytes());
+                bytes
+            }
+            Some(Sanction::Removal) => vec![3],
+        }
+    }
+}
+
+/// A signed complaint opening a case.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Complaint {
+    /// Derived from the complaint contents, see [`Complaint::new`].
+    pub id: ProposalId,
+    pub complainant: Did,
+    pub subject: Did,
+    pub role: SubjectRole,
+    pub violation: ViolationType,
+    pub description: String,
+    /// CIDs of supporting documents.
+    pub evidence: Vec<Cid>,
+    pub filed_at: u64,
+    pub signature: Option<SignatureBytes>,
+}
+
+impl Signable for Complaint {
+    fn to_signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(self.complainant.to_string().as_bytes());
+        bytes.extend_from_slice(self.subject.to_string().as_bytes());
+        if let SubjectRole::Steward { body } = &self.role {
+            bytes.extend_from_slice(b"steward:");
+            bytes.extend_from_slice(body.as_bytes());
+        }
+        bytes.extend_from_slice(format!("{:?}", self.violation).as_bytes());
+        bytes.extend_from_slice(self.description.as_bytes());
+        for cid in &self.evidence {
+            bytes.extend_from_slice(cid.to_string().as_bytes());
+        }
+        bytes.extend_from_slice(&self.filed_at.to_le_bytes());
+        Ok(bytes)
+    }
+}
+
+impl Complaint {
+    /// Draft an unsigned complaint; its id commits to the contents.
+    pub fn new(
+        complainant: Did,
+        subject: Did,
+        role: SubjectRole,
+        violation: ViolationType,
+        description: String,
+        evidence: Vec<Cid>,
+        filed_at: u64,
+    ) -> Result<Self, CommonError> {
+        let mut complaint = Complaint {
+            id: ProposalId(String::new()),
+            complainant,
+            subject,
+            role,
+            violation,
+            description,
+            evidence,
+            filed_at,
+            signature: None,
+        };
+        complaint.id = complaint.derived_id()?;
+        Ok(complaint)
+    }
+
+    fn derived_id(&self) -> Result<ProposalId, CommonError> {
+        let digest = Sha256::digest(self.to_signable_bytes()?);
+        Ok(ProposalId(format!("case:{}", hex::encode(&digest[..16]))))
+    }
+}
+
+/// The subject's answer to a complaint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SubjectResponse {
+    pub case_id: ProposalId,
+    pub statement: String,
+    pub evidence: Vec<Cid>,
+    pub responded_at: u64,
+    pub signature: Option<SignatureBytes>,
+}
+
+impl Signable for SubjectResponse {
+    fn to_signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        let mut bytes = self.case_id.0.as_bytes().to_vec();
+        bytes.extend_from_slice(self.statement.as_bytes());
+        for cid in &self.evidence {
+            bytes.extend_from_slice(cid.to_string().as_bytes());
+        }
+        bytes.extend_from_slice(&self.responded_at.to_le_bytes());
+        Ok(bytes)
+    }
+}
+
+/// A request by either party to rehear a decided case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Appeal {
+    pub case_id: ProposalId,
+    pub appellant: Did,
+    pub grounds: String,
+    pub filed_at: u64,
+    pub signature: Option<SignatureBytes>,
+}
+
+impl Signable for Appeal {
+    fn to_signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        let mut bytes = self.case_id.0.as_bytes().to_vec();
+        bytes.extend_from_slice(self.appellant.to_string().as_bytes());
+        bytes.extend_from_slice(self.grounds.as_bytes());
+        bytes.extend_from_slice(&self.filed_at.to_le_bytes());
+        Ok(bytes)
+    }
+}
+
+/// Which hearing of a case a panel sits for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HearingStage {
+    Hearing,
+    Appeal,
+}
+
+/// Outcome a panelist finds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Finding {
+    Upheld,
+    Dismissed,
+}
+
+/// A panelist's signed verdict. Upheld verdicts name a sanction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PanelVerdict {
+    pub case_id: ProposalId,
+    pub stage: HearingStage,
+    pub panelist: Did,
+    pub finding: Finding,
+    pub sanction: Option<Sanction>,
+    pub reasoning: String,
+    pub decided_at: u64,
+    pub signature: Option<SignatureBytes>,
+}
+
+impl Signable for PanelVerdict {
+    fn to_signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        let mut bytes = self.case_id.0.as_bytes().to_vec();
+        bytes.push(self.stage as u8);
+        bytes.extend_from_slice(self.panelist.to_string().as_bytes());
+        bytes.push(self.finding as u8);
+        bytes.extend_from_slice(&Sanction::bytes(&self.sanction));
+        bytes.extend_from_slice(self.reasoning.as_bytes());
+        bytes.extend_from_slice(&self.decided_at.to_le_bytes());
+        Ok(bytes)
+    }
+}
+
+/// A panel and the verdicts it has returned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Hearing {
+    pub stage: HearingStage,
+    pub draw: SortitionDraw,
+    pub verdicts: Vec<PanelVerdict>,
+    pub decide_by: u64,
+}
+
+/// A panel's majority decision.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CaseDecision {
+    pub stage: HearingStage,
+    pub finding: Finding,
+    pub sanction: Option<Sanction>,
+    pub decided_at: u64,
+}
+
+/// Where a case stands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CaseStatus {
+    /// The subject has been notified and may respond.
+    Noticed {
+        respond_by: u64,
+    },
+    /// Waiting for a panel to be drawn.
+    AwaitingPanel {
+        stage: HearingStage,
+    },
+    Hearing {
+        stage: HearingStage,
+        decide_by: u64,
+    },
+    /// Decided at first hearing; either party may appeal.
+    AppealWindow {
+        appeal_by: u64,
+    },
+    /// The final decision upheld the complaint. `until` is set for
+    /// suspensions.
+    Enforced {
+        sanction: Sanction,
+        effective_at: u64,
+        until: Option<u64>,
+    },
+    /// A suspension ended.
+    Reinstated {
+        at: u64,
+    },
+    /// The final decision dismissed the complaint.
+    Dismissed {
+        at: u64,
+    },
+}
+
+/// A due-process case and its full record.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DueProcessCase {
+    pub complaint: Complaint,
+    pub status: CaseStatus,
+    pub response: Option<SubjectResponse>,
+    pub hearings: Vec<Hearing>,
+    pub decisions: Vec<CaseDecision>,
+    pub appeal: Option<Appeal>,
+    /// CIDs of the blocks anchoring each step, oldest first.
+    pub record: Vec<Cid>,
+    pub updated_at: u64,
+}
+
+impl DueProcessCase {
+    /// The sanction restricting the subject at `now`, if any.
+    pub fn sanction_at(&self, now: u64) -> Option<&Sanction> {
+        match &self.status {
+            CaseStatus::Enforced {
+                sanction,
+                effective_at,
+                until,
+            } if sanction.restricts()
+                && *effective_at <= now
+                && until.is_none_or(|until| now < until) =>
+            {
+                Some(sanction)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Storage for cases, mirroring the governance backend.
+pub(crate) type CaseStore = crate::state_store::ProposalStateStore<DueProcessCase>;
+
+fn verify_signed<T: Signable>(
+    value: &T,
+    signature: &Option<SignatureBytes>,
+    signer: &Did,
+) -> Result<(), CommonError> {
+    let signature = signature
+        .as_ref()
+        .ok_or_else(|| CommonError::CryptoError(format!("Missing signature from {}", signer)))?;
+    let key = verifying_key_from_did_key(signer)?;
+    value.verify(signature, &key)
+}
+
+impl GovernanceModule {
+    /// Update the due-process windows and panel sizes.
+    pub fn set_due_process_config(&mut self, config: DueProcessConfig) {
+        self.due_process_config = config;
+    }
+
+    /// Current due-process windows and panel sizes.
+    pub fn due_process_config(&self) -> &DueProcessConfig {
+        &self.due_process_config
+    }
+
+    /// Open a case from a signed complaint and notify the subject.
+    pub fn file_complaint(
+        &mut self,
+        complaint: Complaint,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<(DueProcessCase, DagBlock), CommonError> {
+        if complaint.id != complaint.derived_id()? {
+            return Err(CommonError::InvalidInputError(
+                "Complaint id does not match its contents".to_string(),
+            ));
+        }
+        verify_signed(&complaint, &complaint.signature, &complaint.complainant)?;
+        if complaint.complainant == complaint.subject {
+            return Err(CommonError::InvalidInputError(
+                "Members cannot file complaints about themselves".to_string(),
+            ));
+        }
+        for did in [&complaint.complainant, &complaint.subject] {
+            if !self.members.contains(did) {
+                return Err(CommonError::PolicyDenied(format!(
+                    "{} is not a member",
+                    did
+                )));
+            }
+        }
+        if self.cases.contains(&complaint.id) {
+            return Err(CommonError::InvalidInputError(format!(
+                "Complaint {} was already filed",
+                complaint.id
+            )));
+        }
+        let now = time_provider.unix_seconds();
+        let complainant = complaint.complainant.clone();
+        let signature = complaint.signature.clone();
+        let mut case = DueProcessCase {
+            status: CaseStatus::Noticed {
+                respond_by: now + self.due_process_config.response_window_secs,
+            },
+            complaint: complaint.clone(),
+            response: None,
+            hearings: Vec::new(),
+            decisions: Vec::new(),
+            appeal: None,
+            record: Vec::new(),
+            updated_at: now,
+        };
+        let block = self.record_case_step(&mut case, &complaint, &complainant, now, signature)?;
+        Ok((case, block))
+    }
+
+    /// Record the subject's signed response while the notice is open.
+    pub fn respond_to_complaint(
+        &mut self,
+        response: SubjectResponse,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<(DueProcessCase, DagBlock), CommonError> {
+        let mut case = self.load_case(&response.case_id)?;
+        let now = time_provider.unix_seconds();
+        match case.status {
+            CaseStatus::Noticed { respond_by } if now <= respond_by => {}
+            _ => {
+                return Err(CommonError::PolicyDenied(format!(
+                    "Case {} is not open for a response ({:?})",
+                    case.complaint.id, case.status
+                )))
+            }
+        }
+        let subject = case.complaint.subject.clone();
+        verify_signed(&response, &response.signature, &subject)?;
+        case.status = CaseStatus::AwaitingPanel {
+            stage: HearingStage::Hearing,
+        };
+        let signature = response.signature.clone();
+        case.response = Some(response.clone());
+        let block = self.record_case_step(&mut case, &response, &subject, now, signature)?;
+        Ok((case, block))
+    }
+
+    /// Draw the panel for a case waiting for one. `source` must be accepted
+    /// by `trust`; `drawn_by` authors the anchored draw.
+    pub fn draw_hearing_panel(
+        &mut self,
+        case_id: &ProposalId,
+        source: RandomnessSource,
+        trust: &RandomnessTrust,
+        drawn_by: &Did,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<(DueProcessCase, DagBlock), CommonError> {
+        let mut case = self.load_case(case_id)?;
+        let CaseStatus::AwaitingPanel { stage } = case.status else {
+            return Err(CommonError::PolicyDenied(format!(
+                "Case {} is not waiting for a panel ({:?})",
+                case_id, case.status
+            )));
+        };
+        let seats = match stage {
+            HearingStage::Hearing => self.due_process_config.panel_size,
+            HearingStage::Appeal => self.due_process_config.appeal_panel_size,
+        };
+        let mut pool: Vec<Did> = self
+            .members
+            .iter()
+            .filter(|did| {
+                **did != case.complaint.subject
+                    && **did != case.complaint.complainant
+                    && !case.hearings.iter().any(|h| h.draw.selected.contains(did))
+                    && self
+                        .sanction_in_force(did, time_provider.unix_seconds())
+                        .is_none()
+            })
+            .cloned()
+            .collect();
+        pool.sort_by_key(|did| did.to_string());
+        if seats == 0 || pool.len() < seats {
+            return Err(CommonError::PolicyDenied(format!(
+                "Only {} eligible members for a panel of {}",
+                pool.len(),
+                seats
+            )));
+        }
+        let now = time_provider.unix_seconds();
+        let seed = source.seed(trust)?;
+        let selected = select_members(&seed, &pool, seats);
+        let draw = SortitionDraw {
+            draw_id: format!("{}:panel:{}", case_id, case.hearings.len()),
+            seats,
+            pool,
+            source,
+            selected,
+            timestamp: now,
+        };
+        let decide_by = now + self.due_process_config.hearing_window_secs;
+        case.hearings.push(Hearing {
+            stage,
+            draw: draw.clone(),
+            verdicts: Vec::new(),
+            decide_by,
+        });
+        case.status = CaseStatus::Hearing { stage, decide_by };
+        let block = self.record_case_step(&mut case, &draw, drawn_by, now, None)?;
+        Ok((case, block))
+    }
+
+    /// Record a panelist's verdict and decide the case once a majority of the
+    /// panel agrees.
+    pub fn submit_panel_verdict(
+        &mut self,
+        verdict: PanelVerdict,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<(DueProcessCase, DagBlock), CommonError> {
+        let mut case = self.load_case(&verdict.case_id)?;
+        let now = time_provider.unix_seconds();
+        match case.status {
+            CaseStatus::Hearing { stage, decide_by }
+                if stage == verdict.stage && now <= decide_by => {}
+            _ => {
+                return Err(CommonError::PolicyDenied(format!(
+                    "Case {} is not being heard at stage {:?} ({:?})",
+                    case.complaint.id, verdict.stage, case.status
+                )))
+            }
+        }
+        match (verdict.finding, &verdict.sanction) {
+            (Finding::Upheld, None) => {
+                return Err(CommonError::InvalidInputError(
+                    "An upheld verdict must name a sanction".to_string(),
+                ))
+            }
+            (Finding::Dismissed, Some(_)) => {
+                return Err(CommonError::InvalidInputError(
+                    "A dismissal cannot carry a sanction".to_string(),
+                ))
+            }
+            _ => {}
+        }
+        verify_signed(&verdict, &verdict.signature, &verdict.panelist)?;
+        let hearing = case
+            .hearings
+            .last_mut()
+            .ok_or_else(|| CommonError::InternalError("Hearing without a panel".to_string()))?;
+        if !hearing.draw.selected.contains(&verdict.panelist) {
+            return Err(CommonError::PolicyDenied(format!(
+                "{} does not sit on the panel",
+                verdict.panelist
+            )));
+        }
+        if hearing
+            .verdicts
+            .iter()
+            .any(|v| v.panelist == verdict.panelist)
+        {
+            return Err(CommonError::InvalidInputError(format!(
+                "{} already returned a verdict",
+                verdict.panelist
+            )));
+        }
+        hearing.verdicts.push(verdict.clone());
+        let decision = Self::panel_decision(hearing, now);
+
+        if let Some(decision) = decision {
+            case.decisions.push(decision.clone());
+            match decision.stage {
+                HearingStage::Hearing => {
+                    case.status = CaseStatus::AppealWindow {
+                        appeal_by: now + self.due_process_config.appeal_window_secs,
+                    };
+                }
+                HearingStage::Appeal => self.enforce_decision(&mut case, &decision, now),
+            }
+        }
+        let signature = verdict.signature.clone();
+        let block =
+            self.record_case_step(&mut case, &verdict, &verdict.panelist, now, signature)?;
+        Ok((case, block))
+    }
+
+    /// Majority finding of a panel. An upheld case carries the most severe
+    /// sanction that a majority of the panel supports.
+    fn panel_decision(hearing: &Hearing, now: u64) -> Option<CaseDecision> {
+        let majority = hearing.draw.selected.len() / 2 + 1;
+        let mut upheld: Vec<&Sanction> = hearing
+            .verdicts
+            .iter()
+            .filter_map(|v| v.sanction.as_ref())
+            .collect();
+        let dismissed = hearing
+            .verdicts
+            .iter()
+            .filter(|v| v.finding == Finding::Dismissed)
+            .count();
+        if upheld.len() >= majority {
+            upheld.sort_by_key(|s| std::cmp::Reverse(s.severity()));
+            return Some(CaseDecision {
+                stage: hearing.stage,
+                finding: Finding::Upheld,
+                sanction: Some(upheld[majority - 1].clone()),
+                decided_at: now,
+            });
+        }
+        if dismissed >= majority {
+            return Some(CaseDecision {
+                stage: hearing.stage,
+                finding: Finding::Dismissed,
+                sanction: None,
+                decided_at: now,
+            });
+        }
+        None
+    }
+
+    fn enforce_decision(&mut self, case: &mut DueProcessCase, decision: &CaseDecision, now: u64) {
+        case.status = match &decision.sanction {
+            None => CaseStatus::Dismissed { at: now },
+            Some(sanction) => {
+                if *sanction == Sanction::Removal && case.complaint.role == SubjectRole::Member {
+                    self.members.remove(&case.complaint.subject);
+                }
+                CaseStatus::Enforced {
+                    sanction: sanction.clone(),
+                    effective_at: now,
+                    until: match sanction {
+                        Sanction::Suspension { duration_secs } => Some(now + duration_secs),
+                        _ => None,
+                    },
+                }
+            }
+        };
+    }
+
+    /// Appeal a first-hearing decision. Either party may appeal once.
+    pub fn file_appeal(
+        &mut self,
+        appeal: Appeal,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<(DueProcessCase, DagBlock), CommonError> {
+        let mut case = self.load_case(&appeal.case_id)?;
+        let now = time_provider.unix_seconds();
+        match case.status {
+            CaseStatus::AppealWindow { appeal_by } if now <= appeal_by => {}
+            _ => {
+                return Err(CommonError::PolicyDenied(format!(
+                    "Case {} cannot be appealed ({:?})",
+                    case.complaint.id, case.status
+                )))
+            }
+        }
+        if appeal.appellant != case.complaint.subject
+            && appeal.appellant != case.complaint.complainant
+        {
+            return Err(CommonError::PolicyDenied(format!(
+                "{} is not a party to case {}",
+                appeal.appellant, case.complaint.id
+            )));
+        }
+        verify_signed(&appeal, &appeal.signature, &appeal.appellant)?;
+        case.status = CaseStatus::AwaitingPanel {
+            stage: HearingStage::Appeal,
+        };
+        case.appeal = Some(appeal.clone());
+        let signature = appeal.signature.clone();
+        let block = self.record_case_step(&mut case, &appeal, &appeal.appellant, now, signature)?;
+        Ok((case, block))
+    }
+
+    /// Move cases along as their windows pass: open panels after the notice,
+    /// redraw panels that missed their deadline, enforce unappealed decisions
+    /// and reinstate members whose suspension ended. Returns the changed
+    /// cases.
+    pub fn refresh_due_process(&mut self, now: u64) -> Result<Vec<DueProcessCase>, CommonError> {
+        let mut changed = Vec::new();
+        for mut case in self.cases.list()? {
+            let before = case.status.clone();
+            match case.status {
+                CaseStatus::Noticed { respond_by } if now > respond_by => {
+                    case.status = CaseStatus::AwaitingPanel {
+                        stage: HearingStage::Hearing,
+                    };
+                }
+                CaseStatus::Hearing { stage, decide_by } if now > decide_by => {
+                    case.status = CaseStatus::AwaitingPanel { stage };
+                }
+                CaseStatus::AppealWindow { appeal_by } if now > appeal_by => {
+                    if let Some(decision) = case.decisions.last().cloned() {
+                        self.enforce_decision(&mut case, &decision, appeal_by);
+                    }
+                }
+                CaseStatus::Enforced {
+                    until: Some(until), ..
+                } if now >= until => {
+                    case.status = CaseStatus::Reinstated { at: until };
+                }
+                _ => {}
+            }
+            if case.status != before {
+                case.updated_at = now;
+                self.cases.put(&case.complaint.id, &case)?;
+                changed.push(case);
+            }
+        }
+        Ok(changed)
+    }
+
+    /// The most severe sanction restricting `did` as a member at `now`.
+    pub fn sanction_in_force(&self, did: &Did, now: u64) -> Option<Sanction> {
+        self.cases
+            .list()
+            .ok()?
+            .iter()
+            .filter(|c| c.complaint.subject == *did && c.complaint.role == SubjectRole::Member)
+            .filter_map(|c| c.sanction_at(now).cloned())
+            .max_by_key(|s| s.severity())
+    }
+
+    /// The most severe sanction restricting `did` in the office `body`.
+    pub fn steward_sanction(&self, did: &Did, body: &str, now: u64) -> Option<Sanction> {
+        self.cases
+            .list()
+            .ok()?
+            .iter()
+            .filter(|c| {
+                c.complaint.subject == *did
+                    && matches!(&c.complaint.role, SubjectRole::Steward { body: b } if b == body)
+            })
+            .filter_map(|c| c.sanction_at(now).cloned())
+            .max_by_key(|s| s.severity())
+    }
+
+    /// Cases whose sanction restricts the subject at `now`.
+    pub fn enforced_cases(&self, now: u64) -> Result<Vec<DueProcessCase>, CommonError> {
+        Ok(self
+            .cases
+            .list()?
+            .into_iter()
+            .filter(|c| c.sanction_at(now).is_some())
+            .collect())
+    }
+
+    /// Refuse governance participation while a restricting sanction is in
+    /// force.
+    pub(crate) fn ensure_good_standing(&self, did: &Did, now: u64) -> Result<(), CommonError> {
+        match self.sanction_in_force(did, now) {
+            Some(sanction) => Err(CommonError::PolicyDenied(format!(
+                "{} is under sanction ({:?})",
+                did, sanction
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// A due-process case.
+    pub fn due_process_case(
+        &self,
+        case_id: &ProposalId,
+    ) -> Result<Option<DueProcessCase>, CommonError> {
+        self.cases.get(case_id)
+    }
+
+    /// All due-process cases.
+    pub fn due_process_cases(&self) -> Result<Vec<DueProcessCase>, CommonError> {
+        self.cases.list()
+    }
+
+    fn load_case(&self, case_id: &ProposalId) -> Result<DueProcessCase, CommonError> {
+        self.cases.get(case_id)?.ok_or_else(|| {
+            CommonError::ResourceNotFound(format!("Due-process case {} not found", case_id))
+        })
+    }
+
+    /// Anchor `step` linked to the previous step of the case and persist it.
+    fn record_case_step<T: serde::Serialize>(
+        &mut self,
+        case: &mut DueProcessCase,
+        step: &T,
+        author: &Did,
+        timestamp: u64,
+        signature: Option<SignatureBytes>,
+    ) -> Result<DagBlock, CommonError> {
+        let links = case
+            .record
+            .last()
+            .map(|cid| DagLink {
+                cid: cid.clone(),
+                name: "previous".to_string(),
+                size: 0,
+            })
+            .into_iter()
+            .collect();
+        let block = anchor(step, links, author, timestamp, signature)?;
+        case.record.push(block.cid.clone());
+        case.updated_at = timestamp;
+        self.cases.put(&case.complaint.id, case)?;
+        Ok(block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sortition::{BeaconCommitment, BeaconReveal};
+    use crate::voting::BallotAnchoringService;
+    use crate::{ProposalSubmission, ProposalType};
+    use icn_common::FixedTimeProvider;
+    use icn_dag::InMemoryDagStore;
+    use icn_identity::{did_key_from_verifying_key, generate_ed25519_keypair, SigningKey};
+    use std::collections::HashMap;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    fn federation(size: usize) -> (GovernanceModule, HashMap<Did, SigningKey>, Vec<Did>) {
+        let mut gov = GovernanceModule::new();
+        let mut keys = HashMap::new();
+        let mut dids = Vec::new();
+        for _ in 0..size {
+            let (sk, vk) = generate_ed25519_keypair();
+            let did = Did::from_str(&did_key_from_verifying_key(&vk)).unwrap();
+            gov.add_member(did.clone());
+            keys.insert(did.clone(), sk);
+            dids.push(did);
+        }
+        (gov, keys, dids)
+    }
+
+    fn source(
+        participant: &Did,
+        key: &SigningKey,
+        secret: &[u8],
+    ) -> (RandomnessSource, RandomnessTrust) {
+        let mut commitment = BeaconCommitment::new("round", participant.clone(), secret);
+        commitment.signature = Some(commitment.sign(key).unwrap());
+        let mut anchoring = BallotAnchoringService::new(InMemoryDagStore::new());
+        let commitment_cid = anchoring.anchor_beacon_commitment(&commitment, 0).unwrap();
+        let source = RandomnessSource::CommitReveal {
+            round_id: "round".into(),
+            reveals: vec![BeaconReveal {
+                participant: participant.clone(),
+                commitment_cid,
+                secret: secret.to_vec(),
+            }],
+            missing: Vec::new(),
+        };
+        let trust = RandomnessTrust::new(Arc::new(anchoring.storage))
+            .with_beacon_participants(vec![participant.clone()]);
+        (source, trust)
+    }
+
+    fn verdict(
+        case: &DueProcessCase,
+        stage: HearingStage,
+        panelist: &Did,
+        key: &SigningKey,
+        sanction: Option<Sanction>,
+        at: u64,
+    ) -> PanelVerdict {
+        let mut verdict = PanelVerdict {
+            case_id: case.complaint.id.clone(),
+            stage,
+            panelist: panelist.clone(),
+            finding: if sanction.is_some() {
+                Finding::Upheld
+            } else {
+                Finding::Dismissed
+            },
+            sanction,
+            reasoning: "reviewed the evidence".into(),
+            decided_at: at,
+            signature: None,
+        };
+        verdict.signature = Some(verdict.sign(key).unwrap());
+        verdict
+    }
+
+    fn open_case(
+        gov: &mut GovernanceModule,
+        keys: &HashMap<Did, SigningKey>,
+        complainant: &Did,
+        subject: &Did,
+    ) -> DueProcessCase {
+        let mut complaint = Complaint::new(
+            complainant.clone(),
+            subject.clone(),
+            SubjectRole::Member,
+            ViolationType::ResourceAbuse,
+            "Repeatedly exhausted shared compute".into(),
+            Vec::new(),
+            1,
+        )
+        .unwrap();
+        complaint.signature = Some(complaint.sign(&keys[complainant]).unwrap());
+        gov.file_complaint(complaint, &FixedTimeProvider::new(10))
+            .unwrap()
+            .0
+    }
+
+    #[test]
+    fn suspension_is_enforced_after_appeal_window_and_lifted() {
+        let (mut gov, keys, dids) = federation(6);
+        let (complainant, subject) = (dids[0].clone(), dids[1].clone());
+        let case = open_case(&mut gov, &keys, &complainant, &subject);
+        let respond_by = match case.status {
+            CaseStatus::Noticed { respond_by } => respond_by,
+            ref other => panic!("unexpected status {:?}", other),
+        };
+
+        // The subject stays silent; the panel opens after the notice.
+        gov.refresh_due_process(respond_by + 1).unwrap();
+        let at = respond_by + 2;
+        let (beacon, trust) = source(&complainant, &keys[&complainant], b"secret");
+        let (case, _) = gov
+            .draw_hearing_panel(
+                &case.complaint.id,
+                beacon,
+                &trust,
+                &complainant,
+                &FixedTimeProvider::new(at),
+            )
+            .unwrap();
+        let panel = case.hearings[0].draw.selected.clone();
+        assert_eq!(panel.len(), 3);
+        assert!(!panel.contains(&subject) && !panel.contains(&complainant));
+        let eligible: Vec<Did> = dids
+            .iter()
+            .filter(|d| **d != subject && **d != complainant)
+            .cloned()
+            .collect();
+        assert!(case.hearings[0].draw.verify(&eligible, &trust).unwrap());
+
+        let sanctions = [
+            Some(Sanction::Removal),
+            Some(Sanction::Suspension { duration_secs: 100 }),
+            None,
+        ];
+        let mut latest = case.clone();
+        for (panelist, sanction) in panel.iter().zip(sanctions) {
+            latest = gov
+                .submit_panel_verdict(
+                    verdict(
+                        &case,
+                        HearingStage::Hearing,
+                        panelist,
+                        &keys[panelist],
+                        sanction,
+                        at,
+                    ),
+                    &FixedTimeProvider::new(at),
+                )
+                .unwrap()
+                .0;
+            if matches!(latest.status, CaseStatus::AppealWindow { .. }) {
+                break;
+            }
+        }
+        let CaseStatus::AppealWindow { appeal_by } = latest.status else {
+            panic!("expected an appeal window, got {:?}", latest.status);
+        };
+        assert_eq!(
+            latest.decisions[0].sanction,
+            Some(Sanction::Suspension { duration_secs: 100 })
+        );
+        assert_eq!(latest.record.len(), 4);
+        assert!(gov.sanction_in_force(&subject, appeal_by).is_none());
+
+        gov.refresh_due_process(appeal_by + 1).unwrap();
+        assert!(gov.sanction_in_force(&subject, appeal_by + 1).is_some());
+        let submission = ProposalSubmission {
+            proposer: subject.clone(),
+            proposal_type: ProposalType::GenericText("hello".into()),
+            description: "while suspended".into(),
+            duration_secs: 60,
+            quorum: None,
+            threshold: None,
+            content_cid: None,
+            timelock_delay: None,
+        };
+        assert!(gov
+            .submit_proposal(submission.clone(), &FixedTimeProvider::new(appeal_by + 1))
+            .is_err());
+
+        let changed = gov.refresh_due_process(appeal_by + 101).unwrap();
+        assert_eq!(
+            changed[0].status,
+            CaseStatus::Reinstated {
+                at: appeal_by + 100
+            }
+        );
+        assert!(gov.sanction_in_force(&subject, appeal_by + 101).is_none());
+        assert!(gov
+            .submit_proposal(submission, &FixedTimeProvider::new(appeal_by + 101))
+            .is_ok());
+    }
+
+    #[test]
+    fn appeal_panel_decides_finally_and_removal_revokes_membership() {
+        let (mut gov, keys, dids) = federation(10);
+        let (complainant, subject) = (dids[0].clone(), dids[1].clone());
+        let case = open_case(&mut gov, &keys, &complainant, &subject);
+
+        let mut response = SubjectResponse {
+            case_id: case.complaint.id.clone(),
+            statement: "The usage was approved".into(),
+            evidence: Vec::new(),
+            responded_at: 11,
+            signature: None,
+        };
+        response.signature = Some(response.sign(&keys[&complainant]).unwrap());
+        assert!(gov
+            .respond_to_complaint(response.clone(), &FixedTimeProvider::new(11))
+            .is_err());
+        response.signature = Some(response.sign(&keys[&subject]).unwrap());
+        gov.respond_to_complaint(response, &FixedTimeProvider::new(11))
+            .unwrap();
+
+        let (beacon, trust) = source(&complainant, &keys[&complainant], b"first");
+        let (case, _) = gov
+            .draw_hearing_panel(
+                &case.complaint.id,
+                beacon,
+                &trust,
+                &complainant,
+                &FixedTimeProvider::new(12),
+            )
+            .unwrap();
+        let first_panel = case.hearings[0].draw.selected.clone();
+        for panelist in first_panel.iter().take(2) {
+            gov.submit_panel_verdict(
+                verdict(
+                    &case,
+                    HearingStage::Hearing,
+                    panelist,
+                    &keys[panelist],
+                    None,
+                    13,
+                ),
+                &FixedTimeProvider::new(13),
+            )
+            .unwrap();
+        }
+
+        let mut appeal = Appeal {
+            case_id: case.complaint.id.clone(),
+            appellant: complainant.clone(),
+            grounds: "Evidence was overlooked".into(),
+            filed_at: 14,
+            signature: None,
+        };
+        appeal.signature = Some(appeal.sign(&keys[&complainant]).unwrap());
+        gov.file_appeal(appeal, &FixedTimeProvider::new(14))
+            .unwrap();
+        let (beacon, trust) = source(&complainant, &keys[&complainant], b"second");
+        let (case, _) = gov
+            .draw_hearing_panel(
+                &case.complaint.id,
+                beacon,
+                &trust,
+                &complainant,
+                &FixedTimeProvider::new(15),
+            )
+            .unwrap();
+        let appeal_panel = case.hearings[1].draw.selected.clone();
+        assert_eq!(appeal_panel.len(), 5);
+        assert!(appeal_panel.iter().all(|p| !first_panel.contains(p)));
+
+        let mut latest = case.clone();
+        for panelist in appeal_panel.iter().take(3) {
+            latest = gov
+                .submit_panel_verdict(
+                    verdict(
+                        &case,
+                        HearingStage::Appeal,
+                        panelist,
+                        &keys[panelist],
+                        Some(Sanction::Removal),
+                        16,
+                    ),
+                    &FixedTimeProvider::new(16),
+                )
+                .unwrap()
+                .0;
+        }
+        assert!(matches!(
+            latest.status,
+            CaseStatus::Enforced {
+                sanction: Sanction::Removal,
+                ..
+            }
+        ));
+        assert!(!gov.members().contains(&subject));
+        assert_eq!(
+            gov.sanction_in_force(&subject, 1_000),
+            Some(Sanction::Removal)
+        );
+    }
+}
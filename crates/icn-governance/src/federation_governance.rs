@@ -4,7 +4,7 @@
 //! It provides governance mechanisms that respect federation trust contexts and inheritance.
 
 use crate::ProposalId;
-use icn_common::{CommonError, Did, TimeProvider};
+use icn_common::{CommonError, Did, DidDocument, TimeProvider};
 use icn_identity::{
     FederationId, TrustContext, TrustLevel, TrustPolicyEngine, TrustValidationResult,
 };
@@ -607,6 +607,63 @@ impl FederationGovernanceEngine {
         Ok(())
     }
 
+    /// Replace the membership of a trust committee with the outcome of `draw`.
+    ///
+    /// The draw is replayed with `service` over `candidates`, so it must have
+    /// been made from the pool they yield. The first selected member becomes
+    /// chair. Previous members that were not drawn are kept on record but
+    /// marked inactive.
+    pub fn seat_committee_from_draw(
+        &mut self,
+        committee_id: &str,
+        draw: &crate::sortition::SortitionDraw,
+        service: &crate::sortition::SortitionService,
+        candidates: &[DidDocument],
+        time_provider: &dyn TimeProvider,
+    ) -> Result<(), FederationGovernanceError> {
+        if !service
+            .verify_draw(draw, candidates)
+            .map_err(|e| FederationGovernanceError::TrustValidationFailed(e.to_string()))?
+        {
+            return Err(FederationGovernanceError::TrustValidationFailed(format!(
+                "Sortition draw {} does not verify",
+                draw.draw_id
+            )));
+        }
+        let committee = self.trust_committees.get_mut(committee_id).ok_or_else(|| {
+            FederationGovernanceError::CommitteeNotFound(committee_id.to_string())
+        })?;
+        if committee.status != CommitteeStatus::Active {
+            return Err(FederationGovernanceError::PolicyValidationFailed(
+                "Cannot seat members on inactive committee".to_string(),
+            ));
+        }
+
+        for member in committee.members.values_mut() {
+            member.active = false;
+        }
+        let now = time_provider.unix_seconds();
+        for (rank, did) in draw.selected.iter().enumerate() {
+            let role = if rank == 0 {
+                TrustCommitteeRole::Chair
+            } else {
+                TrustCommitteeRole::Member
+            };
+            committee.members.insert(
+                did.clone(),
+                TrustCommitteeMember {
+                    did: did.clone(),
+                    role,
+                    contexts: committee.managed_contexts.clone(),
+                    voting_weight: 1.0,
+                    joined_at: now,
+                    active: true,
+                },
+            );
+        }
+        Ok(())
+    }
+
     /// Set trust threshold policy
     pub fn set_threshold_policy(&mut self, activity: String, policy: TrustThresholdPolicy) {
         self.threshold_policies.insert(activity, policy);
@@ -846,70 +903,28 @@ impl FederationGovernanceEngine {
             ))
         })?;
 
-        // Check minimum attestations
-        if attestations.len() < gate.min_attestations {
-            return Ok(MembershipApplicationResult::Rejected {
-                reason: format!(
-                    "Need {} attestations, got {}",
-                    gate.min_attestations,
-                    attestations.len()
-                ),
-                can_reapply_after: Some(time_provider.unix_seconds() + 2592000), // 30 days
-            });
-        }
-
-        // Check trust levels and contexts
-        let mut valid_contexts = HashSet::new();
-        for (_attestor, trust_level, context) in &attestations {
-            if gate.required_contexts.contains(context)
-                && self.meets_minimum_trust(trust_level, &gate.min_trust_level)
-            {
-                valid_contexts.insert(context.clone());
-            }
-        }
-
-        if valid_contexts.len() < gate.required_contexts.len() {
-            return Ok(MembershipApplicationResult::Rejected {
-                reason: "Insufficient trust attestations for required contexts".to_string(),
-                can_reapply_after: Some(time_provider.unix_seconds() + 2592000), // 30 days
-            });
-        }
+        let now = time_provider.unix_seconds();
+        let attestations: Vec<(TrustLevel, TrustContext)> = attestations
+            .into_iter()
+            .map(|(_attestor, level, context)| (level, context))
+            .collect();
+        let result = gate.evaluate(&attestations, now);
 
         // If we have a committee, check approval
         if let Some(_committee) = self.get_committee_for_federation(federation) {
             // For now, automatically approve if basic requirements are met
             // In practice, this would trigger committee review process
-            return Ok(MembershipApplicationResult::Approved {
-                probationary_until: gate
-                    .probationary_period
-                    .map(|period| time_provider.unix_seconds() + period),
-                granted_contexts: valid_contexts,
-            });
+            return Ok(result);
         }
 
-        Ok(MembershipApplicationResult::Approved {
-            probationary_until: gate
-                .probationary_period
-                .map(|period| time_provider.unix_seconds() + period),
-            granted_contexts: valid_contexts,
-        })
+        Ok(result)
     }
 
     // === Helper Methods ===
 
     /// Check if trust level meets minimum requirement
     fn meets_minimum_trust(&self, actual: &TrustLevel, required: &TrustLevel) -> bool {
-        use TrustLevel::*;
-        matches!(
-            (actual, required),
-            (Full, _)
-                | (Partial, Partial)
-                | (Partial, Basic)
-                | (Partial, None)
-                | (Basic, Basic)
-                | (Basic, None)
-                | (None, None)
-        )
+        meets_minimum_trust(actual, required)
     }
 
     /// Get committee responsible for a trust context
@@ -1173,6 +1188,65 @@ pub struct FederationMembershipTrustGate {
     pub additional_requirements: HashMap<String, String>,
 }
 
+impl FederationMembershipTrustGate {
+    /// Evaluate the `(trust level, context)` attestations an applicant
+    /// received from existing members.
+    pub fn evaluate(
+        &self,
+        attestations: &[(TrustLevel, TrustContext)],
+        now: u64,
+    ) -> MembershipApplicationResult {
+        // Check minimum attestations
+        if attestations.len() < self.min_attestations {
+            return MembershipApplicationResult::Rejected {
+                reason: format!(
+                    "Need {} attestations, got {}",
+                    self.min_attestations,
+                    attestations.len()
+                ),
+                can_reapply_after: Some(now + 2592000), // 30 days
+            };
+        }
+
+        // Check trust levels and contexts
+        let mut valid_contexts = HashSet::new();
+        for (trust_level, context) in attestations {
+            if self.required_contexts.contains(context)
+                && meets_minimum_trust(trust_level, &self.min_trust_level)
+            {
+                valid_contexts.insert(context.clone());
+            }
+        }
+
+        if valid_contexts.len() < self.required_contexts.len() {
+            return MembershipApplicationResult::Rejected {
+                reason: "Insufficient trust attestations for required contexts".to_string(),
+                can_reapply_after: Some(now + 2592000), // 30 days
+            };
+        }
+
+        MembershipApplicationResult::Approved {
+            probationary_until: self.probationary_period.map(|period| now + period),
+            granted_contexts: valid_contexts,
+        }
+    }
+}
+
+/// Check if trust level meets minimum requirement
+fn meets_minimum_trust(actual: &TrustLevel, required: &TrustLevel) -> bool {
+    use TrustLevel::*;
+    matches!(
+        (actual, required),
+        (Full, _)
+            | (Partial, Partial)
+            | (Partial, Basic)
+            | (Partial, None)
+            | (Basic, Basic)
+            | (Basic, None)
+            | (None, None)
+    )
+}
+
 /// Result of membership application evaluation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MembershipApplicationResult {
@@ -307,6 +307,7 @@ impl GovernanceConflictResolver {
             ProposalType::SoftwareUpgrade(_) => "software_upgrade".to_string(),
             ProposalType::BudgetAllocation(recipient, _, _) => format!("budget:{}", recipient),
             ProposalType::Resolution(_) => "resolution".to_string(),
+            ProposalType::ConstitutionAmendment(_) => "constitution".to_string(),
             ProposalType::GenericText(_) => "generic".to_string(),
         }
     }
@@ -676,6 +677,13 @@ mod tests {
         let bob = Did::from_str("did:example:bob").unwrap();
         governance.add_member(alice.clone());
         governance.add_member(bob.clone());
+        governance
+            .register_parameter(crate::ParameterSpec::new(
+                "max_users",
+                "Maximum users",
+                crate::ParameterValue::U64(10),
+            ))
+            .unwrap();
 
         // Create conflicting proposals
         let proposal1 = ProposalSubmission {
@@ -743,6 +751,13 @@ mod tests {
         let mut governance = GovernanceModule::new();
         let alice = Did::from_str("did:example:alice").unwrap();
         governance.add_member(alice.clone());
+        governance
+            .register_parameter(crate::ParameterSpec::new(
+                "max_users",
+                "Maximum users",
+                crate::ParameterValue::U64(10),
+            ))
+            .unwrap();
 
         // Create a proposal that contradicts the policy
         let proposal = ProposalSubmission {
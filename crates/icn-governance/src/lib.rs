@@ -33,16 +33,32 @@ use serde::{Deserialize, Serialize};
 
 pub mod automation;
 pub mod budgeting;
+pub mod constitution;
+pub mod conviction;
 pub mod crdt_proposal_state;
+pub mod cross_federation;
+pub mod delegation;
+pub mod deliberation;
+pub mod due_process;
 pub mod federation_governance;
 //pub mod federation_sync;
 pub mod governance_conflict_resolver;
+pub mod lifecycle;
+pub mod membership_application;
 pub mod metrics;
+pub mod parameters;
 pub mod policy_testing;
+pub mod quadratic;
 pub mod ranked_choice;
 pub mod scoped_policy;
+pub mod secret_ballot;
 pub mod security;
+pub mod simulation;
+pub mod sortition;
+mod state_store;
+pub mod upgrade;
 pub mod voting;
+pub mod weighting;
 pub use security::{
     BallotValidationStats, GovernanceSecurityAudit, GovernanceSecurityConfig,
     GovernanceSecurityIssue, SecureBallotSigner, SecureBallotValidator,
@@ -60,26 +76,78 @@ pub use automation::{
     GovernanceEvent as AutomationGovernanceEvent, ReminderType,
 };
 pub use budgeting::{apply_budget_allocation, BudgetProposal};
+pub use constitution::{
+    AmendmentProcedure, ClassRule, Constitution, ConstitutionChange, ConstitutionRecord,
+    ConstitutionRegistry, DecisionRule, MembershipRules, ProposalClass, VotingMethod,
+};
+pub use conviction::{ConvictionConfig, ConvictionState};
 pub use crdt_proposal_state::{
     CRDTProposalState, CRDTProposalStateConfig, CRDTProposalStateStats, ProposalCRDT, ProposalInfo,
     ProposalMetadata, ProposalStatus as CRDTProposalStatus, Vote as CRDTVote, VoteDecision,
     VoteTally,
 };
+pub use cross_federation::{
+    CrossFederationState, CrossFederationStatus, FederationParticipant, FederationRatification,
+    JointProposal, RatificationDecision, RatificationSet,
+};
+pub use delegation::{Delegation, DelegationRegistry, DelegationResolution, DelegationScope};
+pub use deliberation::{
+    Amendment, AmendmentStatus, Comment, CommentRecord, CommentThread, DeliberationState,
+    ProposalRevision, RevisionRecord,
+};
+pub use due_process::{
+    Appeal, CaseDecision, CaseStatus, Complaint, DueProcessCase, DueProcessConfig, Finding,
+    Hearing, HearingStage, PanelVerdict, Sanction, SubjectResponse, SubjectRole,
+};
 pub use governance_conflict_resolver::{
     ConflictEvidence, ConflictSeverity, GovernanceConflict, GovernanceConflictConfig,
     GovernanceConflictResolver, GovernanceConflictType, GovernanceResolution,
     GovernanceResolutionStatus,
 };
+pub use lifecycle::{LifecycleStep, LifecycleTimer, LifecycleTransition, LIFECYCLE_RETRY_SECS};
+pub use membership_application::{
+    challenge_message, ApplicationStatus, ChallengeResponse, MembershipApplication,
+    MembershipApplicationState, MembershipAttestation, MembershipGrant, CHALLENGE_WINDOW_SECS,
+    MEMBERSHIP_VOTE_SECS,
+};
+pub use parameters::{
+    ParameterChange, ParameterRegistry, ParameterSpec, ParameterType, ParameterValue,
+};
 pub use policy_testing::{
     ExpectedOutcome, PolicyTestResult, PolicyTestScenario, PolicyTestingFramework, TestAction,
     TestExecutionStep, TestState,
 };
+pub use quadratic::{
+    Contribution, CredentialSybilGuard, FundingAllocation, FundingProject, OpenSybilGuard,
+    PersonhoodSybilGuard, QuadraticAllocation, QuadraticBallot, QuadraticFundingResult,
+    QuadraticFundingRound, QuadraticOutcome, QuadraticTally, QuadraticVotingSystem, SybilGuard,
+};
 pub use ranked_choice::{RankedChoiceBallotValidator, RankedChoiceVotingSystem};
+pub use secret_ballot::{
+    ballot_commitment, prepare_secret_ballot, verify_secret_tally, BallotReveal, SecretBallot,
+    SecretBallotConfig, SecretBallotState, SecretTally,
+};
+pub use simulation::{
+    DelegationRule, GovernanceSimulator, ProposalSimulation, SimulatedOutcome, SimulationConfig,
+    SimulationMethod, SimulationReport, SyntheticPopulation,
+};
+pub use sortition::{
+    BeaconCommitment, BeaconReveal, CommitRevealBeacon, RandomnessSource, RandomnessTrust,
+    SortitionDraw, SortitionService,
+};
+pub use upgrade::{
+    ReadinessSignal, ScheduledUpgrade, UpgradeActivation, UpgradeManifest, BASE_PROTOCOL_VERSION,
+};
 pub use voting::{
     BallotAnchoringService, BallotId, BallotValidator, Candidate, CandidateId, Election,
     ElectionId, EligibilityRules, RankedChoiceBallot, RankedChoiceResult, RankedChoiceRound,
     Signature, VotingError, VotingPeriod, VotingSystem,
 };
+pub use weighting::{
+    ChamberRequirement, ChamberTally, EqualWeights, MemberProfile, MembershipSizeWeights,
+    WeightRule, WeightedTally, WeightingConfig, WeightingScheme, WeightingStrategy,
+    DEFAULT_RULE_KIND,
+};
 
 /// Trait for governance execution hooks.
 ///
@@ -139,6 +207,7 @@ pub enum ProposalType {
     GenericText(String),                   // For general purpose proposals
     BudgetAllocation(Did, u64, String),    // recipient, amount, purpose
     Resolution(ResolutionProposal),        // Dispute or remediation actions
+    ConstitutionAmendment(String),         // JSON of the proposed constitution version
 }
 
 /// Specific remediation actions for dispute resolution.
@@ -196,6 +265,17 @@ pub struct GovernanceConfig {
     pub require_anti_spam_fees: bool,
     /// Mana cost for submitting a proposal (if anti-spam fees enabled)
     pub proposal_fee: u64,
+    /// Seconds a proposal may wait for sponsors before it is rejected (0
+    /// means no limit)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sponsorship_timeout_secs: u64,
+    /// Seconds of deliberation after which the lifecycle scheduler opens
+    /// voting; `None` leaves opening to an explicit call
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub deliberation_period_secs: Option<u64>,
+    /// Whether the lifecycle scheduler executes accepted proposals
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub auto_execute: bool,
 }
 
 impl Default for GovernanceConfig {
@@ -207,6 +287,9 @@ impl Default for GovernanceConfig {
             veto_members: HashSet::new(),
             require_anti_spam_fees: false,
             proposal_fee: 0,
+            sponsorship_timeout_secs: 0,
+            deliberation_period_secs: None,
+            auto_execute: false,
         }
     }
 }
@@ -315,6 +398,8 @@ pub enum GovernanceEvent {
     ProposalSubmitted(Proposal),
     VoteCast(Vote),
     StatusUpdated(ProposalId, ProposalStatus),
+    /// A member sponsored a proposal awaiting sponsorship.
+    ProposalSponsored(ProposalId, Did),
 }
 
 // Define the Backend enum
@@ -337,7 +422,9 @@ enum Backend {
 pub struct GovernanceModule {
     backend: Backend,
     members: HashSet<Did>,
-    delegations: HashMap<Did, Did>,
+    delegations: DelegationRegistry,
+    delegation_store: delegation::DelegationStore,
+    proposal_tags: delegation::ProposalTagStore,
     quorum: usize,
     threshold: f32,
     config: GovernanceConfig,
@@ -345,6 +432,25 @@ pub struct GovernanceModule {
     proposal_callbacks: Vec<Box<dyn ProposalCallback>>,
     #[allow(clippy::type_complexity)]
     event_store: Option<std::sync::Mutex<Box<dyn icn_eventstore::EventStore<GovernanceEvent>>>>,
+    conviction: conviction::ConvictionStore,
+    conviction_config: ConvictionConfig,
+    deliberation: deliberation::DeliberationStore,
+    parameters: ParameterRegistry,
+    upgrades: upgrade::UpgradeStore,
+    voter_commitments: secret_ballot::VoterCommitmentStore,
+    secret_ballots: secret_ballot::SecretBallotStore,
+    lifecycle_timers: lifecycle::TimerStore,
+    cross_federation: cross_federation::CrossFederationStore,
+    constitution: ConstitutionRegistry,
+    membership_applications: membership_application::MembershipApplicationStore,
+    membership_gates: membership_application::MembershipGateStore,
+    cases: due_process::CaseStore,
+    due_process_config: DueProcessConfig,
+    weight_rules: weighting::WeightRuleStore,
+    member_profiles: weighting::MemberProfileStore,
+    weighting_config: WeightingConfig,
+    weighting_strategies: HashMap<String, std::sync::Arc<dyn WeightingStrategy>>,
+    sybil_guard: Option<std::sync::Arc<dyn SybilGuard>>,
 }
 
 /// Parameters for submitting a new proposal
@@ -368,12 +474,39 @@ impl GovernanceModule {
                 proposals: HashMap::new(),
             },
             members: HashSet::new(),
-            delegations: HashMap::new(),
+            delegations: DelegationRegistry::new(),
+            delegation_store: delegation::DelegationStore::in_memory("delegation"),
+            proposal_tags: delegation::ProposalTagStore::in_memory("proposal tag"),
             quorum: 1,
             threshold: 0.5,
             config: GovernanceConfig::default(),
             proposal_callbacks: Vec::new(),
             event_store: None,
+            conviction: conviction::ConvictionStore::in_memory("conviction"),
+            conviction_config: ConvictionConfig::default(),
+            deliberation: deliberation::DeliberationStore::in_memory("deliberation"),
+            parameters: ParameterRegistry::standard(),
+            upgrades: upgrade::UpgradeStore::in_memory("upgrade"),
+            voter_commitments: secret_ballot::VoterCommitmentStore::in_memory("voter commitment"),
+            secret_ballots: secret_ballot::SecretBallotStore::in_memory("secret ballot"),
+            lifecycle_timers: lifecycle::TimerStore::in_memory("lifecycle timer"),
+            cross_federation: cross_federation::CrossFederationStore::in_memory(
+                "cross-federation proposal",
+            ),
+            constitution: ConstitutionRegistry::default(),
+            membership_applications: membership_application::MembershipApplicationStore::in_memory(
+                "membership application",
+            ),
+            membership_gates: membership_application::MembershipGateStore::in_memory(
+                "membership gate",
+            ),
+            cases: due_process::CaseStore::in_memory("due-process case"),
+            due_process_config: DueProcessConfig::default(),
+            weight_rules: weighting::WeightRuleStore::in_memory("weight rule"),
+            member_profiles: weighting::MemberProfileStore::in_memory("member profile"),
+            weighting_config: WeightingConfig::default(),
+            weighting_strategies: HashMap::new(),
+            sybil_guard: None,
         }
     }
 
@@ -384,12 +517,39 @@ impl GovernanceModule {
                 proposals: HashMap::new(),
             },
             members: HashSet::new(),
-            delegations: HashMap::new(),
+            delegations: DelegationRegistry::new(),
+            delegation_store: delegation::DelegationStore::in_memory("delegation"),
+            proposal_tags: delegation::ProposalTagStore::in_memory("proposal tag"),
             quorum: 1,
             threshold: 0.5,
             config,
             proposal_callbacks: Vec::new(),
             event_store: None,
+            conviction: conviction::ConvictionStore::in_memory("conviction"),
+            conviction_config: ConvictionConfig::default(),
+            deliberation: deliberation::DeliberationStore::in_memory("deliberation"),
+            parameters: ParameterRegistry::standard(),
+            upgrades: upgrade::UpgradeStore::in_memory("upgrade"),
+            voter_commitments: secret_ballot::VoterCommitmentStore::in_memory("voter commitment"),
+            secret_ballots: secret_ballot::SecretBallotStore::in_memory("secret ballot"),
+            lifecycle_timers: lifecycle::TimerStore::in_memory("lifecycle timer"),
+            cross_federation: cross_federation::CrossFederationStore::in_memory(
+                "cross-federation proposal",
+            ),
+            constitution: ConstitutionRegistry::default(),
+            membership_applications: membership_application::MembershipApplicationStore::in_memory(
+                "membership application",
+            ),
+            membership_gates: membership_application::MembershipGateStore::in_memory(
+                "membership gate",
+            ),
+            cases: due_process::CaseStore::in_memory("due-process case"),
+            due_process_config: DueProcessConfig::default(),
+            weight_rules: weighting::WeightRuleStore::in_memory("weight rule"),
+            member_profiles: weighting::MemberProfileStore::in_memory("member profile"),
+            weighting_config: WeightingConfig::default(),
+            weighting_strategies: HashMap::new(),
+            sybil_guard: None,
         }
     }
 
@@ -437,6 +597,13 @@ impl GovernanceModule {
                     }
                 }
             }
+            GovernanceEvent::ProposalSponsored(id, sponsor) => {
+                if let Backend::InMemory { proposals } = &mut self.backend {
+                    if let Some(prop) = proposals.get_mut(&id) {
+                        prop.sponsorship.sponsors.insert(sponsor);
+                    }
+                }
+            }
         }
     }
 
@@ -449,20 +616,120 @@ impl GovernanceModule {
 
         let proposals_tree_name = "proposals_v1".to_string(); // versioned tree name
                                                               // sled automatically creates trees when first accessed, so no explicit creation needed here.
+        let conviction_tree = db.open_tree("conviction_v1").map_err(|e| {
+            CommonError::DatabaseError(format!("Failed to open conviction tree: {}", e))
+        })?;
+        let deliberation_tree = db.open_tree("deliberation_v1").map_err(|e| {
+            CommonError::DatabaseError(format!("Failed to open deliberation tree: {}", e))
+        })?;
+        let parameters_tree = db.open_tree("parameters_v1").map_err(|e| {
+            CommonError::DatabaseError(format!("Failed to open parameters tree: {}", e))
+        })?;
+        let upgrades_tree = db.open_tree("upgrades_v1").map_err(|e| {
+            CommonError::DatabaseError(format!("Failed to open upgrades tree: {}", e))
+        })?;
+        let secret_ballots_tree = db.open_tree("secret_ballots_v1").map_err(|e| {
+            CommonError::DatabaseError(format!("Failed to open secret ballots tree: {}", e))
+        })?;
+        let voter_commitments_tree = db.open_tree("voter_commitments_v1").map_err(|e| {
+            CommonError::DatabaseError(format!("Failed to open voter commitments tree: {}", e))
+        })?;
+        let lifecycle_tree = db.open_tree("lifecycle_timers_v1").map_err(|e| {
+            CommonError::DatabaseError(format!("Failed to open lifecycle timers tree: {}", e))
+        })?;
+        let cross_federation_tree = db.open_tree("cross_federation_v1").map_err(|e| {
+            CommonError::DatabaseError(format!("Failed to open cross-federation tree: {}", e))
+        })?;
+        let constitution_tree = db.open_tree("constitution_v1").map_err(|e| {
+            CommonError::DatabaseError(format!("Failed to open constitution tree: {}", e))
+        })?;
+        let membership_applications_tree =
+            db.open_tree("membership_applications_v1").map_err(|e| {
+                CommonError::DatabaseError(format!(
+                    "Failed to open membership applications tree: {}",
+                    e
+                ))
+            })?;
+        let membership_gates_tree = db.open_tree("membership_gates_v1").map_err(|e| {
+            CommonError::DatabaseError(format!("Failed to open membership gates tree: {}", e))
+        })?;
+        let cases_tree = db.open_tree("due_process_cases_v1").map_err(|e| {
+            CommonError::DatabaseError(format!("Failed to open due-process cases tree: {}", e))
+        })?;
+        let weight_rules_tree = db.open_tree("voting_weights_v1").map_err(|e| {
+            CommonError::DatabaseError(format!("Failed to open voting weights tree: {}", e))
+        })?;
+        let member_profiles_tree = db.open_tree("member_profiles_v1").map_err(|e| {
+            CommonError::DatabaseError(format!("Failed to open member profiles tree: {}", e))
+        })?;
+        let delegation_store = delegation::DelegationStore::sled(
+            "delegation",
+            db.open_tree("delegations_v1").map_err(|e| {
+                CommonError::DatabaseError(format!("Failed to open delegations tree: {}", e))
+            })?,
+        );
+        let proposal_tags = delegation::ProposalTagStore::sled(
+            "proposal tag",
+            db.open_tree("proposal_tags_v1").map_err(|e| {
+                CommonError::DatabaseError(format!("Failed to open proposal tags tree: {}", e))
+            })?,
+        );
 
-        Ok(GovernanceModule {
+        let mut module = GovernanceModule {
             backend: Backend::Sled {
                 db,
                 proposals_tree_name,
             },
             members: HashSet::new(),
-            delegations: HashMap::new(),
+            delegations: DelegationRegistry::from_stores(&delegation_store, &proposal_tags)?,
+            delegation_store,
+            proposal_tags,
             quorum: 1,
             threshold: 0.5,
             config: GovernanceConfig::default(),
             proposal_callbacks: Vec::new(),
             event_store: None,
-        })
+            conviction: conviction::ConvictionStore::sled("conviction", conviction_tree),
+            conviction_config: ConvictionConfig::default(),
+            deliberation: deliberation::DeliberationStore::sled("deliberation", deliberation_tree),
+            parameters: ParameterRegistry::sled(parameters_tree)?,
+            upgrades: upgrade::UpgradeStore::sled("upgrade", upgrades_tree),
+            voter_commitments: secret_ballot::VoterCommitmentStore::sled(
+                "voter commitment",
+                voter_commitments_tree,
+            ),
+            secret_ballots: secret_ballot::SecretBallotStore::sled(
+                "secret ballot",
+                secret_ballots_tree,
+            ),
+            lifecycle_timers: lifecycle::TimerStore::sled("lifecycle timer", lifecycle_tree),
+            cross_federation: cross_federation::CrossFederationStore::sled(
+                "cross-federation proposal",
+                cross_federation_tree,
+            ),
+            constitution: ConstitutionRegistry::sled(constitution_tree)?,
+            membership_applications: membership_application::MembershipApplicationStore::sled(
+                "membership application",
+                membership_applications_tree,
+            ),
+            membership_gates: membership_application::MembershipGateStore::sled(
+                "membership gate",
+                membership_gates_tree,
+            ),
+            cases: due_process::CaseStore::sled("due-process case", cases_tree),
+            due_process_config: DueProcessConfig::default(),
+            weight_rules: weighting::WeightRuleStore::sled("weight rule", weight_rules_tree),
+            member_profiles: weighting::MemberProfileStore::sled(
+                "member profile",
+                member_profiles_tree,
+            ),
+            weighting_config: WeightingConfig::default(),
+            weighting_strategies: HashMap::new(),
+            sybil_guard: None,
+        };
+        module.sync_governance_parameters();
+        module.sync_constitution();
+        Ok(module)
     }
 
     /// Create and store a new proposal in the governance module.
@@ -472,7 +739,11 @@ impl GovernanceModule {
         time_provider: &dyn TimeProvider,
     ) -> Result<ProposalId, CommonError> {
         metrics::SUBMIT_PROPOSAL_CALLS.inc();
+        self.validate_parameter_proposal(&submission.proposal_type)?;
+        self.validate_upgrade_proposal(&submission.proposal_type)?;
+        let submission = self.apply_constitution(submission)?;
         let now = time_provider.unix_seconds();
+        self.ensure_good_standing(&submission.proposer, now)?;
         let desc_hash_part = submission.description.chars().take(10).collect::<String>();
         let proposal_id_str = format!(
             "prop:{}:{}:{}",
@@ -577,6 +848,7 @@ impl GovernanceModule {
 
     /// Transition a proposal from `Deliberation` to `VotingOpen`.
     pub fn open_voting(&mut self, proposal_id: &ProposalId) -> Result<(), CommonError> {
+        self.check_ready_for_vote(proposal_id)?;
         match &mut self.backend {
             Backend::InMemory { proposals } => {
                 let proposal = proposals.get_mut(proposal_id).ok_or_else(|| {
@@ -673,6 +945,7 @@ impl GovernanceModule {
                     ProposalStatus::VotingOpen,
                 ))?;
         }
+        self.bind_vote_revision(proposal_id)?;
         Ok(())
     }
 
@@ -689,6 +962,31 @@ impl GovernanceModule {
 
         // expire outdated proposals before attempting to cast a vote
         self.expire_proposals(now)?;
+        self.ensure_good_standing(&voter, now)?;
+        self.ensure_eligible_voter(&voter)?;
+
+        if self.conviction.contains(proposal_id) {
+            return Err(CommonError::InvalidInputError(format!(
+                "Proposal {} uses conviction voting; stake support instead",
+                proposal_id.0
+            )));
+        }
+        if self.uses_secret_ballot(proposal_id) {
+            return Err(CommonError::InvalidInputError(format!(
+                "Proposal {} uses secret ballots; cast an anonymous ballot instead",
+                proposal_id.0
+            )));
+        }
+        if let Some(proposal) = self.get_proposal(proposal_id)? {
+            if self.constitutional_rule(&proposal).map(|r| r.method)
+                == Some(VotingMethod::SecretBallot)
+            {
+                return Err(CommonError::PolicyDenied(format!(
+                    "The constitution requires a secret ballot for proposal {}",
+                    proposal_id.0
+                )));
+            }
+        }
 
         match &mut self.backend {
             Backend::InMemory { proposals } => {
@@ -846,6 +1144,79 @@ impl GovernanceModule {
         }
     }
 
+    /// Load a proposal, apply `update` and persist the result.
+    pub(crate) fn update_proposal<F>(
+        &mut self,
+        proposal_id: &ProposalId,
+        update: F,
+    ) -> Result<Proposal, CommonError>
+    where
+        F: FnOnce(&mut Proposal),
+    {
+        match &mut self.backend {
+            Backend::InMemory { proposals } => {
+                let proposal = proposals.get_mut(proposal_id).ok_or_else(|| {
+                    CommonError::ResourceNotFound(format!(
+                        "Proposal with ID {} not found",
+                        proposal_id.0
+                    ))
+                })?;
+                update(proposal);
+                Ok(proposal.clone())
+            }
+            #[cfg(feature = "persist-sled")]
+            Backend::Sled {
+                db,
+                proposals_tree_name,
+            } => {
+                let tree = db.open_tree(proposals_tree_name).map_err(|e| {
+                    CommonError::DatabaseError(format!("Failed to open proposals tree: {}", e))
+                })?;
+                let key = proposal_id.0.as_bytes();
+                let bytes = tree
+                    .get(key)
+                    .map_err(|e| {
+                        CommonError::DatabaseError(format!(
+                            "Failed to get proposal {} from sled: {}",
+                            proposal_id.0, e
+                        ))
+                    })?
+                    .ok_or_else(|| {
+                        CommonError::ResourceNotFound(format!(
+                            "Proposal with ID {} not found",
+                            proposal_id.0
+                        ))
+                    })?;
+                let mut proposal: Proposal = bincode::deserialize(&bytes).map_err(|e| {
+                    CommonError::DeserializationError(format!(
+                        "Failed to deserialize proposal {}: {}",
+                        proposal_id.0, e
+                    ))
+                })?;
+                update(&mut proposal);
+                let encoded = bincode::serialize(&proposal).map_err(|e| {
+                    CommonError::SerializationError(format!(
+                        "Failed to serialize updated proposal {}: {}",
+                        proposal_id.0, e
+                    ))
+                })?;
+                tree.insert(key, encoded).map_err(|e| {
+                    CommonError::DatabaseError(format!(
+                        "Failed to persist proposal {}: {}",
+                        proposal_id.0, e
+                    ))
+                })?;
+                tree.flush().map_err(|e| {
+                    CommonError::DatabaseError(format!(
+                        "Failed to flush sled tree for proposal {}: {}",
+                        proposal_id.0, e
+                    ))
+                })?;
+                Ok(proposal)
+            }
+        }
+    }
+
     /// Return all currently stored proposals.
     pub fn list_proposals(&self) -> Result<Vec<Proposal>, CommonError> {
         match &self.backend {
@@ -894,20 +1265,115 @@ impl GovernanceModule {
         self.members.remove(did);
     }
 
-    /// Delegate `from` member's vote to `to` member.
+    /// Delegate `from` member's vote to `to` member for all proposals.
     pub fn delegate_vote(&mut self, from: Did, to: Did) -> Result<(), CommonError> {
+        self.delegate_vote_scoped(from, to, DelegationScope::All, None, 0)
+    }
+
+    /// Delegate `from` member's vote to `to` member for proposals in `scope`,
+    /// optionally until `expires_at`. Replaces any earlier delegation by
+    /// `from` in the same scope.
+    pub fn delegate_vote_scoped(
+        &mut self,
+        from: Did,
+        to: Did,
+        scope: DelegationScope,
+        expires_at: Option<u64>,
+        now: u64,
+    ) -> Result<(), CommonError> {
         if !self.members.contains(&from) || !self.members.contains(&to) {
             return Err(CommonError::InvalidInputError(
                 "Both delegator and delegatee must be members".to_string(),
             ));
         }
-        self.delegations.insert(from, to);
-        Ok(())
+        if matches!(expires_at, Some(exp) if exp <= now) {
+            return Err(CommonError::InvalidInputError(
+                "Delegation expiry must be in the future".to_string(),
+            ));
+        }
+        self.delegations.delegate(Delegation {
+            delegator: from.clone(),
+            delegate: to,
+            scope,
+            created_at: now,
+            expires_at,
+        })?;
+        self.persist_delegations(&from)
     }
 
     /// Revoke any delegation for `from`.
-    pub fn revoke_delegation(&mut self, from: Did) {
-        self.delegations.remove(&from);
+    pub fn revoke_delegation(&mut self, from: Did) -> Result<(), CommonError> {
+        self.delegations.revoke(&from, None);
+        self.persist_delegations(&from)
+    }
+
+    /// Revoke `from`'s delegation in a single scope.
+    pub fn revoke_scoped_delegation(
+        &mut self,
+        from: &Did,
+        scope: &DelegationScope,
+    ) -> Result<(), CommonError> {
+        self.delegations.revoke(from, Some(scope));
+        self.persist_delegations(from)
+    }
+
+    /// Store `delegator`'s current delegations, or drop them once none remain.
+    fn persist_delegations(&mut self, delegator: &Did) -> Result<(), CommonError> {
+        let key = delegation::delegator_key(delegator);
+        let entries = self.delegations.delegations_of(delegator);
+        if entries.is_empty() {
+            self.delegation_store.remove(&key)
+        } else {
+            self.delegation_store.put(&key, &entries)
+        }
+    }
+
+    /// All current delegation edges.
+    pub fn delegation_graph(&self) -> Vec<Delegation> {
+        self.delegations.edges()
+    }
+
+    /// Delegations that govern `proposal`, one per delegator at most.
+    pub fn applicable_delegations(&self, proposal: &Proposal) -> Vec<Delegation> {
+        self.delegations.applicable_to(proposal)
+    }
+
+    /// Attach topic tags to a proposal so tag-scoped delegations apply to it.
+    pub fn tag_proposal(
+        &mut self,
+        proposal_id: &ProposalId,
+        tags: Vec<String>,
+    ) -> Result<(), CommonError> {
+        if self.get_proposal(proposal_id)?.is_none() {
+            return Err(CommonError::ResourceNotFound(format!(
+                "Proposal {} not found",
+                proposal_id.0
+            )));
+        }
+        self.proposal_tags.put(proposal_id, &tags)?;
+        self.delegations.set_tags(proposal_id.clone(), tags);
+        Ok(())
+    }
+
+    /// Topic tags attached to a proposal.
+    pub fn proposal_tags(&self, proposal_id: &ProposalId) -> Vec<String> {
+        self.delegations.tags(proposal_id).to_vec()
+    }
+
+    /// How `member`'s vote on `proposal` resolves through delegations.
+    pub fn resolve_vote(&self, member: &Did, proposal: &Proposal) -> DelegationResolution {
+        self.delegations.resolve(member, proposal)
+    }
+
+    /// Number of members each voter effectively represents on a proposal.
+    pub fn effective_voting_weights(
+        &self,
+        proposal_id: &ProposalId,
+    ) -> Result<HashMap<Did, usize>, CommonError> {
+        let proposal = self.get_proposal(proposal_id)?.ok_or_else(|| {
+            CommonError::ResourceNotFound(format!("Proposal {} not found", proposal_id.0))
+        })?;
+        Ok(self.delegations.effective_weights(&self.members, &proposal))
     }
 
     /// Returns a reference to the current member set.
@@ -918,11 +1384,25 @@ impl GovernanceModule {
     /// Sets the minimum number of votes required for a proposal to be valid.
     pub fn set_quorum(&mut self, quorum: usize) {
         self.quorum = quorum;
+        self.set_parameter_baseline(
+            parameters::GOVERNANCE_QUORUM,
+            ParameterValue::U64(quorum as u64),
+        );
     }
 
     /// Sets the fraction of `Yes` votes required for acceptance.
     pub fn set_threshold(&mut self, threshold: f32) {
         self.threshold = threshold;
+        self.set_parameter_baseline(
+            parameters::GOVERNANCE_THRESHOLD,
+            ParameterValue::F64(threshold as f64),
+        );
+    }
+
+    fn set_parameter_baseline(&mut self, name: &str, value: ParameterValue) {
+        if let Err(e) = self.parameters.set_baseline(name, value) {
+            log::warn!("Parameter registry not updated for {}: {}", name, e);
+        }
     }
 
     /// Register a callback executed when proposals are run via [`GovernanceModule::execute_proposal`].
@@ -943,6 +1423,54 @@ impl GovernanceModule {
         &self.config
     }
 
+    /// Use `guard`, e.g. a [`PersonhoodSybilGuard`], to admit voters once
+    /// governance sets [`parameters::GOVERNANCE_REQUIRE_PERSONHOOD`].
+    pub fn set_sybil_guard(&mut self, guard: std::sync::Arc<dyn SybilGuard>) {
+        self.sybil_guard = Some(guard);
+    }
+
+    /// Refuse `voter` unless it passes the Sybil guard, when governance
+    /// requires personhood. Without a guard every voter is refused.
+    pub(crate) fn ensure_eligible_voter(&self, voter: &Did) -> Result<(), CommonError> {
+        let required = self
+            .parameters
+            .get(parameters::GOVERNANCE_REQUIRE_PERSONHOOD)
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        if !required {
+            return Ok(());
+        }
+        match &self.sybil_guard {
+            Some(guard) if guard.is_unique_member(voter) => Ok(()),
+            Some(_) => Err(CommonError::PolicyDenied(format!(
+                "{} is not a verified person",
+                voter
+            ))),
+            None => Err(CommonError::PolicyDenied(
+                "Personhood required but no Sybil guard configured".to_string(),
+            )),
+        }
+    }
+
+    /// DIDs governance approved as personhood seeds through the
+    /// [`parameters::PERSONHOOD_SEEDS`] parameter.
+    pub fn personhood_seeds(&self) -> Result<Vec<Did>, CommonError> {
+        match self.parameters.get(parameters::PERSONHOOD_SEEDS) {
+            Some(ParameterValue::StringList(seeds)) => seeds
+                .iter()
+                .map(|seed| {
+                    seed.parse::<Did>().map_err(|e| {
+                        CommonError::InvalidInputError(format!(
+                            "Invalid personhood seed {}: {}",
+                            seed, e
+                        ))
+                    })
+                })
+                .collect(),
+            _ => Ok(Vec::new()),
+        }
+    }
+
     /// Sponsor a proposal (add sponsor support for advancement to voting)
     pub fn sponsor_proposal(
         &mut self,
@@ -977,7 +1505,16 @@ impl GovernanceModule {
 
                 proposal
                     .sponsorship
-                    .add_sponsor(sponsor, now, self.config.min_sponsors);
+                    .add_sponsor(sponsor.clone(), now, self.config.min_sponsors);
+                if let Some(store) = &self.event_store {
+                    store
+                        .lock()
+                        .unwrap()
+                        .append(&GovernanceEvent::ProposalSponsored(
+                            proposal_id.clone(),
+                            sponsor,
+                        ))?;
+                }
 
                 // If sponsorship requirement is met, advance to Deliberation
                 if proposal
@@ -1259,26 +1796,21 @@ impl GovernanceModule {
     }
 
     /// Counts yes/no/abstain votes for a proposal, considering only current members.
+    /// Members who did not vote inherit the vote of their resolved delegate.
     pub fn tally_votes(&self, proposal: &Proposal) -> (usize, usize, usize) {
         Self::tally_votes_static(&self.members, &self.delegations, proposal)
     }
 
     fn tally_votes_static(
         members: &HashSet<Did>,
-        delegations: &HashMap<Did, Did>,
+        delegations: &DelegationRegistry,
         proposal: &Proposal,
     ) -> (usize, usize, usize) {
         let mut yes = 0;
         let mut no = 0;
         let mut abstain = 0;
         for member in members {
-            let mut option = proposal.votes.get(member).map(|v| v.option);
-            if option.is_none() {
-                if let Some(delegate) = delegations.get(member) {
-                    option = proposal.votes.get(delegate).map(|v| v.option);
-                }
-            }
-            match option {
+            match delegations.resolve(member, proposal).option() {
                 Some(VoteOption::Yes) => yes += 1,
                 Some(VoteOption::No) => no += 1,
                 Some(VoteOption::Abstain) => abstain += 1,
@@ -1290,12 +1822,23 @@ impl GovernanceModule {
 
     /// Mark any proposals past their deadline as `Rejected` without tallying votes.
     pub fn expire_proposals(&mut self, now: u64) -> Result<(), CommonError> {
+        self.expire_proposals_except(now, None)
+    }
+
+    /// [`expire_proposals`](Self::expire_proposals), leaving `skip` to be
+    /// tallied by its caller.
+    fn expire_proposals_except(
+        &mut self,
+        now: u64,
+        skip: Option<&ProposalId>,
+    ) -> Result<(), CommonError> {
         match &mut self.backend {
             Backend::InMemory { proposals } => {
                 for proposal in proposals.values_mut() {
                     if (proposal.status == ProposalStatus::VotingOpen
                         || proposal.status == ProposalStatus::Deliberation)
                         && proposal.voting_deadline <= now
+                        && Some(&proposal.id) != skip
                     {
                         proposal.status = ProposalStatus::Rejected;
                     }
@@ -1330,6 +1873,7 @@ impl GovernanceModule {
                     if (prop.status == ProposalStatus::VotingOpen
                         || prop.status == ProposalStatus::Deliberation)
                         && prop.voting_deadline <= now
+                        && Some(&prop.id) != skip
                     {
                         prop.status = ProposalStatus::Rejected;
                         updates.push((key, prop));
@@ -1522,8 +2066,37 @@ impl GovernanceModule {
     ) -> Result<(ProposalStatus, (usize, usize, usize)), CommonError> {
         let now = time_provider.unix_seconds();
 
-        // expire any proposals that have passed their deadline before closing
-        self.expire_proposals(now)?;
+        // expire other proposals that have passed their deadline; this one is
+        // tallied even when it is closed after its deadline
+        self.expire_proposals_except(now, Some(proposal_id))?;
+        self.tally_and_close(proposal_id, now)
+    }
+
+    /// Tally a `VotingOpen` proposal and record the outcome as of `now`.
+    pub(crate) fn tally_and_close(
+        &mut self,
+        proposal_id: &ProposalId,
+        now: u64,
+    ) -> Result<(ProposalStatus, (usize, usize, usize)), CommonError> {
+        let secret_tally = self
+            .secret_ballot_tally(proposal_id)?
+            .map(|t| (t.yes, t.no, t.abstain));
+        // Under a consensus rule a single objection blocks the proposal.
+        let consensus = self.get_proposal(proposal_id)?.is_some_and(|p| {
+            self.constitutional_rule(&p).map(|r| r.method) == Some(VotingMethod::Consensus)
+        });
+        // Weight rules replace the headcount threshold, except for secret
+        // ballots whose voters are unknown.
+        let weighted = match secret_tally {
+            Some(_) => None,
+            None => self
+                .get_proposal(proposal_id)?
+                .filter(|p| p.status == ProposalStatus::VotingOpen)
+                .map(|_| self.weighted_tally(proposal_id))
+                .transpose()?
+                .flatten()
+                .map(|t| t.approved),
+        };
 
         match &mut self.backend {
             Backend::InMemory { proposals } => {
@@ -1539,13 +2112,16 @@ impl GovernanceModule {
                 }
                 let members = self.members.clone();
                 let delegations = self.delegations.clone();
-                let (yes, no, abstain) = Self::tally_votes_static(&members, &delegations, proposal);
+                let (yes, no, abstain) = secret_tally
+                    .unwrap_or_else(|| Self::tally_votes_static(&members, &delegations, proposal));
                 let total = yes + no + abstain;
                 let quorum = proposal.quorum.unwrap_or(self.quorum);
                 let threshold = proposal.threshold.unwrap_or(self.threshold);
                 if total < quorum {
                     proposal.status = ProposalStatus::Rejected;
-                } else if (yes as f32) >= (total as f32 * threshold) {
+                } else if weighted.unwrap_or((yes as f32) >= (total as f32 * threshold))
+                    && !(consensus && no > 0)
+                {
                     // Handle time-lock logic
                     let timelock_delay = proposal
                         .timelock_delay
@@ -1609,14 +2185,16 @@ impl GovernanceModule {
                 }
                 let members = self.members.clone();
                 let delegations = self.delegations.clone();
-                let (yes, no, abstain) =
-                    Self::tally_votes_static(&members, &delegations, &proposal);
+                let (yes, no, abstain) = secret_tally
+                    .unwrap_or_else(|| Self::tally_votes_static(&members, &delegations, &proposal));
                 let total = yes + no + abstain;
                 let quorum = proposal.quorum.unwrap_or(self.quorum);
                 let threshold = proposal.threshold.unwrap_or(self.threshold);
                 if total < quorum {
                     proposal.status = ProposalStatus::Rejected;
-                } else if (yes as f32) >= (total as f32 * threshold) {
+                } else if weighted.unwrap_or((yes as f32) >= (total as f32 * threshold))
+                    && !(consensus && no > 0)
+                {
                     // Handle time-lock logic
                     let timelock_delay = proposal
                         .timelock_delay
@@ -1662,9 +2240,28 @@ impl GovernanceModule {
         }
     }
 
-    /// Executes an accepted proposal. New members are added when executed.
+    /// Executes an accepted proposal. New members are added when executed,
+    /// parameter changes are recorded in the [`ParameterRegistry`],
+    /// constitution amendments are adopted and membership applications are
+    /// accepted.
     pub fn execute_proposal(&mut self, proposal_id: &ProposalId) -> Result<(), CommonError> {
         metrics::EXECUTE_PROPOSAL_CALLS.inc();
+        if let Some(proposal) = self.get_proposal(proposal_id)? {
+            if proposal.status == ProposalStatus::Accepted {
+                self.validate_parameter_proposal(&proposal.proposal_type)?;
+            }
+        }
+        self.execute_proposal_actions(proposal_id)?;
+        if let Some(proposal) = self.get_proposal(proposal_id)? {
+            self.apply_parameter_proposal(&proposal)?;
+            self.schedule_upgrade(&proposal)?;
+            self.apply_constitution_amendment(&proposal)?;
+            self.complete_membership_application(&proposal)?;
+        }
+        Ok(())
+    }
+
+    fn execute_proposal_actions(&mut self, proposal_id: &ProposalId) -> Result<(), CommonError> {
         match &mut self.backend {
             Backend::InMemory { proposals } => {
                 let proposal = proposals.get_mut(proposal_id).ok_or_else(|| {
@@ -1991,4 +2588,47 @@ mod tests {
         let proposal = gov.get_proposal(&proposal_id).unwrap().unwrap();
         assert_eq!(proposal.status, ProposalStatus::Rejected);
     }
+
+    #[test]
+    fn personhood_requirement_and_seeds_follow_parameters() {
+        use icn_identity::{PersonhoodConfig, PersonhoodRegistry};
+        use std::str::FromStr;
+
+        let mut gov = GovernanceModule::new();
+        let member = Did::from_str("did:key:zPersonhoodMember").unwrap();
+        let stranger = Did::from_str("did:key:zPersonhoodStranger").unwrap();
+        assert!(gov.ensure_eligible_voter(&stranger).is_ok());
+        assert!(gov.personhood_seeds().unwrap().is_empty());
+
+        gov.parameters
+            .apply(
+                &[
+                    (
+                        parameters::GOVERNANCE_REQUIRE_PERSONHOOD.to_string(),
+                        "true".to_string(),
+                    ),
+                    (parameters::PERSONHOOD_SEEDS.to_string(), member.to_string()),
+                ],
+                None,
+                0,
+            )
+            .unwrap();
+        assert_eq!(gov.personhood_seeds().unwrap(), vec![member.clone()]);
+        // Without a guard nobody can vote once personhood is required.
+        assert!(gov.ensure_eligible_voter(&member).is_err());
+
+        let registry = PersonhoodRegistry::in_memory(PersonhoodConfig::default());
+        registry.add_seed(member.clone()).unwrap();
+        gov.set_sybil_guard(std::sync::Arc::new(PersonhoodSybilGuard::new(
+            registry,
+            std::sync::Arc::new(FixedTimeProvider::new(0)),
+        )));
+        assert!(gov.ensure_eligible_voter(&member).is_ok());
+        assert!(matches!(
+            gov.ensure_eligible_voter(&stranger),
+            Err(CommonError::PolicyDenied(_))
+        ));
+        gov.add_member(stranger.clone());
+        assert!(gov.register_voter_commitment(stranger, "1").is_err());
+    }
 }
@@ -0,0 +1,443 @@
+//! Proposal lifecycle scheduling
+//!
+//! Every proposal state with a deadline has a [`LifecycleTimer`]:
+//!
+//! | Status               | Step                 | Due                                               |
+//! |----------------------|----------------------|---------------------------------------------------|
+//! | `PendingSponsorship` | `SponsorshipTimeout` | `created_at + sponsorship_timeout_secs`           |
+//! | `Deliberation`       | `OpenVoting`         | sponsorship complete + `deliberation_period_secs` |
+//! | `Deliberation`       | `CloseVoting`        | voting deadline, if voting never opened           |
+//! | `VotingOpen`         | `CloseVoting`        | voting deadline                                   |
+//! | `AcceptedTimelock`   | `ReleaseTimelock`    | end of the time-lock and veto window              |
+//! | `Accepted`           | `Execute`            | acceptance, when `auto_execute` is set            |
+//!
+//! Timers are persisted next to the proposals. Once scheduled, a timer keeps
+//! its due time until its step is done, so a configuration change does not
+//! move deadlines that are already running.
+//!
+//! [`GovernanceModule::advance_lifecycle`] fires every timer due at the
+//! current time, earliest first, and evaluates each transition *as of its due
+//! time* rather than the time of the call. A node that was offline or whose
+//! clock jumped forward therefore reaches the same states, with the same
+//! `accepted_at` timestamps, as one that ticked every second. When the clock
+//! moves backwards nothing becomes due, so no transition is repeated or
+//! undone.
+//!
+//! Execution has side effects outside governance, so `Execute` timers are
+//! only reported by [`GovernanceModule::due_executions`] for the runtime to
+//! act on.
+
+use crate::state_store::ProposalStateStore;
+use crate::{GovernanceEvent, GovernanceModule, Proposal, ProposalId, ProposalStatus};
+use icn_common::{CommonError, TimeProvider};
+use std::collections::HashSet;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Delay before a lifecycle step that failed is attempted again.
+pub const LIFECYCLE_RETRY_SECS: u64 = 300;
+
+/// A scheduled lifecycle transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LifecycleStep {
+    /// Reject a proposal that did not gather enough sponsors.
+    SponsorshipTimeout,
+    /// Open voting after deliberation.
+    OpenVoting,
+    /// Tally a proposal at its voting deadline, or expire it if voting never
+    /// opened.
+    CloseVoting,
+    /// Mark a time-locked proposal ready once the time-lock and veto window
+    /// have passed.
+    ReleaseTimelock,
+    /// Execute an accepted proposal.
+    Execute,
+    /// Accept a conviction proposal whose conviction crossed its threshold.
+    /// Reported by [`GovernanceModule::process_conviction`]; never scheduled.
+    ConvictionPassed,
+}
+
+impl fmt::Display for LifecycleStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LifecycleStep::SponsorshipTimeout => write!(f, "sponsorship_timeout"),
+            LifecycleStep::OpenVoting => write!(f, "open_voting"),
+            LifecycleStep::CloseVoting => write!(f, "close_voting"),
+            LifecycleStep::ReleaseTimelock => write!(f, "release_timelock"),
+            LifecycleStep::Execute => write!(f, "execute"),
+            LifecycleStep::ConvictionPassed => write!(f, "conviction_passed"),
+        }
+    }
+}
+
+/// The next step due for a proposal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LifecycleTimer {
+    pub proposal_id: ProposalId,
+    pub step: LifecycleStep,
+    pub due_at: u64,
+}
+
+/// A status change made by the scheduler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LifecycleTransition {
+    pub proposal_id: ProposalId,
+    pub step: LifecycleStep,
+    pub from: ProposalStatus,
+    pub to: ProposalStatus,
+    /// Due time of the step; the transition takes effect as of this time.
+    pub at: u64,
+}
+
+pub(crate) type TimerStore = ProposalStateStore<LifecycleTimer>;
+
+fn timer_order(timer: &LifecycleTimer) -> (u64, String, LifecycleStep) {
+    (timer.due_at, timer.proposal_id.0.clone(), timer.step)
+}
+
+impl GovernanceModule {
+    /// Step due next for `proposal` and when; `observed_at` is used for steps
+    /// that fall due as soon as the proposal is seen in its state.
+    fn lifecycle_due(&self, proposal: &Proposal, observed_at: u64) -> Option<(LifecycleStep, u64)> {
+        match proposal.status {
+            ProposalStatus::PendingSponsorship if self.config.sponsorship_timeout_secs > 0 => {
+                Some((
+                    LifecycleStep::SponsorshipTimeout,
+                    proposal
+                        .created_at
+                        .saturating_add(self.config.sponsorship_timeout_secs),
+                ))
+            }
+            ProposalStatus::Deliberation => {
+                let opens_at = self.config.deliberation_period_secs.map(|period| {
+                    proposal
+                        .sponsorship
+                        .sponsorship_complete_at
+                        .unwrap_or(proposal.created_at)
+                        .saturating_add(period)
+                });
+                match opens_at {
+                    Some(at) if at < proposal.voting_deadline => {
+                        Some((LifecycleStep::OpenVoting, at))
+                    }
+                    _ => Some((LifecycleStep::CloseVoting, proposal.voting_deadline)),
+                }
+            }
+            ProposalStatus::VotingOpen => {
+                Some((LifecycleStep::CloseVoting, proposal.voting_deadline))
+            }
+            ProposalStatus::AcceptedTimelock => proposal.accepted_at.map(|accepted_at| {
+                let delay = proposal
+                    .timelock_delay
+                    .unwrap_or(self.config.timelock_delay_secs)
+                    .max(self.config.veto_grace_period_secs);
+                (
+                    LifecycleStep::ReleaseTimelock,
+                    accepted_at.saturating_add(delay),
+                )
+            }),
+            ProposalStatus::Accepted if self.config.auto_execute => {
+                Some((LifecycleStep::Execute, observed_at))
+            }
+            _ => None,
+        }
+    }
+
+    /// Bring the persisted timers in line with proposal states and return
+    /// them, earliest first.
+    fn refresh_lifecycle_timers(
+        &mut self,
+        observed_at: u64,
+    ) -> Result<Vec<LifecycleTimer>, CommonError> {
+        let mut timers = Vec::new();
+        for proposal in self.list_proposals()? {
+            let existing = self.lifecycle_timers.get(&proposal.id)?;
+            match self.lifecycle_due(&proposal, observed_at) {
+                Some((step, due_at)) => match existing {
+                    Some(timer) if timer.step == step => timers.push(timer),
+                    _ => {
+                        let timer = LifecycleTimer {
+                            proposal_id: proposal.id.clone(),
+                            step,
+                            due_at,
+                        };
+                        self.lifecycle_timers.put(&proposal.id, &timer)?;
+                        timers.push(timer);
+                    }
+                },
+                None if existing.is_some() => self.lifecycle_timers.remove(&proposal.id)?,
+                None => {}
+            }
+        }
+        timers.sort_by_key(timer_order);
+        Ok(timers)
+    }
+
+    /// Pending lifecycle timers, earliest first.
+    pub fn lifecycle_timers(&self) -> Result<Vec<LifecycleTimer>, CommonError> {
+        let mut timers = self.lifecycle_timers.list()?;
+        timers.sort_by_key(timer_order);
+        Ok(timers)
+    }
+
+    /// Postpone the pending step of a proposal, for example after it failed.
+    pub fn defer_lifecycle_timer(
+        &mut self,
+        proposal_id: &ProposalId,
+        due_at: u64,
+    ) -> Result<(), CommonError> {
+        if let Some(mut timer) = self.lifecycle_timers.get(proposal_id)? {
+            timer.due_at = due_at;
+            self.lifecycle_timers.put(proposal_id, &timer)?;
+        }
+        Ok(())
+    }
+
+    /// Fire every lifecycle timer due at the current time, except `Execute`,
+    /// in due order. Returns the transitions made.
+    ///
+    /// A step that fails is logged and retried after
+    /// [`LIFECYCLE_RETRY_SECS`].
+    pub fn advance_lifecycle(
+        &mut self,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<Vec<LifecycleTransition>, CommonError> {
+        let now = time_provider.unix_seconds();
+        let mut transitions = Vec::new();
+        let mut attempted = HashSet::new();
+        let mut observed_at = now;
+        loop {
+            let next = self
+                .refresh_lifecycle_timers(observed_at)?
+                .into_iter()
+                .find(|t| {
+                    t.due_at <= now
+                        && t.step != LifecycleStep::Execute
+                        && !attempted.contains(&(t.proposal_id.clone(), t.step))
+                });
+            let Some(timer) = next else {
+                break;
+            };
+            attempted.insert((timer.proposal_id.clone(), timer.step));
+
+            let Some(proposal) = self.get_proposal(&timer.proposal_id)? else {
+                continue;
+            };
+            if let Err(e) = self.fire_lifecycle_step(&proposal, &timer) {
+                log::warn!(
+                    "Lifecycle step {} for proposal {} failed: {}",
+                    timer.step,
+                    timer.proposal_id,
+                    e
+                );
+                self.defer_lifecycle_timer(&timer.proposal_id, now + LIFECYCLE_RETRY_SECS)?;
+                continue;
+            }
+            if let Some(updated) = self.get_proposal(&timer.proposal_id)? {
+                if updated.status != proposal.status {
+                    transitions.push(LifecycleTransition {
+                        proposal_id: timer.proposal_id.clone(),
+                        step: timer.step,
+                        from: proposal.status,
+                        to: updated.status,
+                        at: timer.due_at,
+                    });
+                }
+            }
+            observed_at = timer.due_at;
+        }
+        Ok(transitions)
+    }
+
+    /// Accepted proposals whose `Execute` timer is due at `now`, with the due
+    /// time.
+    pub fn due_executions(&mut self, now: u64) -> Result<Vec<(ProposalId, u64)>, CommonError> {
+        Ok(self
+            .refresh_lifecycle_timers(now)?
+            .into_iter()
+            .filter(|t| t.step == LifecycleStep::Execute && t.due_at <= now)
+            .map(|t| (t.proposal_id, t.due_at))
+            .collect())
+    }
+
+    fn fire_lifecycle_step(
+        &mut self,
+        proposal: &Proposal,
+        timer: &LifecycleTimer,
+    ) -> Result<(), CommonError> {
+        match (timer.step, &proposal.status) {
+            (LifecycleStep::SponsorshipTimeout, ProposalStatus::PendingSponsorship)
+            | (LifecycleStep::CloseVoting, ProposalStatus::Deliberation) => {
+                self.set_scheduled_status(&proposal.id, ProposalStatus::Rejected)
+            }
+            (LifecycleStep::OpenVoting, ProposalStatus::Deliberation) => {
+                self.open_voting(&proposal.id)
+            }
+            (LifecycleStep::CloseVoting, ProposalStatus::VotingOpen) => {
+                self.tally_and_close(&proposal.id, timer.due_at).map(|_| ())
+            }
+            (LifecycleStep::ReleaseTimelock, ProposalStatus::AcceptedTimelock) => {
+                self.set_scheduled_status(&proposal.id, ProposalStatus::Accepted)
+            }
+            (step, status) => Err(CommonError::InvalidInputError(format!(
+                "Step {} does not apply to proposal {} in status {:?}",
+                step, proposal.id, status
+            ))),
+        }
+    }
+
+    fn set_scheduled_status(
+        &mut self,
+        proposal_id: &ProposalId,
+        status: ProposalStatus,
+    ) -> Result<(), CommonError> {
+        let recorded = status.clone();
+        self.update_proposal(proposal_id, |p| p.status = status)?;
+        if let Some(store) = self.event_store() {
+            store
+                .lock()
+                .unwrap()
+                .append(&GovernanceEvent::StatusUpdated(
+                    proposal_id.clone(),
+                    recorded,
+                ))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GovernanceConfig, ProposalSubmission, ProposalType, VoteOption};
+    use icn_common::{Did, FixedTimeProvider};
+    use std::str::FromStr;
+
+    fn submit(gov: &mut GovernanceModule, text: &str, at: u64) -> ProposalId {
+        gov.submit_proposal(
+            ProposalSubmission {
+                proposer: Did::from_str("did:example:alice").unwrap(),
+                proposal_type: ProposalType::GenericText(text.into()),
+                description: text.into(),
+                duration_secs: 1_000,
+                quorum: None,
+                threshold: None,
+                content_cid: None,
+                timelock_delay: None,
+            },
+            &FixedTimeProvider::new(at),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn clock_jump_fires_steps_in_order_as_of_their_due_time() {
+        let mut gov = GovernanceModule::with_config(GovernanceConfig {
+            deliberation_period_secs: Some(100),
+            timelock_delay_secs: 500,
+            auto_execute: true,
+            ..GovernanceConfig::default()
+        });
+        let alice = Did::from_str("did:example:alice").unwrap();
+        gov.add_member(alice.clone());
+        let pid = submit(&mut gov, "scheduled", 0);
+        let ignored = submit(&mut gov, "ignored", 10);
+
+        let first = gov.advance_lifecycle(&FixedTimeProvider::new(150)).unwrap();
+        assert_eq!(first.len(), 2);
+        assert!(first.iter().all(|t| t.step == LifecycleStep::OpenVoting));
+        gov.cast_vote(alice, &pid, VoteOption::Yes, &FixedTimeProvider::new(200))
+            .unwrap();
+
+        // Jump well past the deadline and the time-lock in one tick.
+        let jumped = gov
+            .advance_lifecycle(&FixedTimeProvider::new(10_000))
+            .unwrap();
+        let steps: Vec<_> = jumped
+            .iter()
+            .map(|t| (t.proposal_id.clone(), t.step, t.to.clone(), t.at))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                (
+                    pid.clone(),
+                    LifecycleStep::CloseVoting,
+                    ProposalStatus::AcceptedTimelock,
+                    1_000
+                ),
+                (
+                    ignored.clone(),
+                    LifecycleStep::CloseVoting,
+                    ProposalStatus::Rejected,
+                    1_010
+                ),
+                (
+                    pid.clone(),
+                    LifecycleStep::ReleaseTimelock,
+                    ProposalStatus::Accepted,
+                    1_500
+                ),
+            ]
+        );
+        let proposal = gov.get_proposal(&pid).unwrap().unwrap();
+        assert_eq!(proposal.accepted_at, Some(1_000));
+        assert_eq!(
+            gov.due_executions(10_000).unwrap(),
+            vec![(pid.clone(), 1_500)]
+        );
+
+        // A clock that moves backwards changes nothing.
+        assert!(gov
+            .advance_lifecycle(&FixedTimeProvider::new(5))
+            .unwrap()
+            .is_empty());
+        gov.execute_proposal(&pid).unwrap();
+        assert!(gov.due_executions(10_000).unwrap().is_empty());
+        assert!(gov.lifecycle_timers().unwrap().is_empty());
+    }
+
+    #[test]
+    fn unsponsored_proposals_time_out_and_timers_persist() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = GovernanceConfig {
+            min_sponsors: 2,
+            sponsorship_timeout_secs: 60,
+            ..GovernanceConfig::default()
+        };
+        let mut gov = GovernanceModule::new_sled(dir.path().to_path_buf()).unwrap();
+        gov.set_config(config.clone());
+        gov.add_member(Did::from_str("did:example:alice").unwrap());
+        let pid = submit(&mut gov, "lonely", 0);
+        assert!(gov
+            .advance_lifecycle(&FixedTimeProvider::new(30))
+            .unwrap()
+            .is_empty());
+
+        // The persisted timer keeps its due time even though the limit changed.
+        gov.set_config(GovernanceConfig {
+            sponsorship_timeout_secs: 3_600,
+            ..config
+        });
+        assert_eq!(
+            gov.lifecycle_timers().unwrap(),
+            vec![LifecycleTimer {
+                proposal_id: pid.clone(),
+                step: LifecycleStep::SponsorshipTimeout,
+                due_at: 60,
+            }]
+        );
+        let fired = gov.advance_lifecycle(&FixedTimeProvider::new(61)).unwrap();
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].to, ProposalStatus::Rejected);
+        assert_eq!(
+            gov.get_proposal(&pid).unwrap().unwrap().status,
+            ProposalStatus::Rejected
+        );
+    }
+}
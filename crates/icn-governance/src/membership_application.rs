@@ -0,0 +1,930 @@
+//! Federation membership applications
+//!
+//! A cooperative asks to join a federation with a signed
+//! [`MembershipApplication`]. The application carries signed
+//! [`MembershipAttestation`]s from existing members and any credentials the
+//! federation asks for. It then moves through these steps:
+//!
+//! 1. The federation's [`FederationMembershipTrustGate`] screens the
+//!    attestations. Its `additional_requirements` name credential claims and
+//!    the values they must have. Applications that fall short are rejected,
+//!    with the time after which the cooperative may reapply.
+//! 2. The applicant answers a [`VerificationChallenge`] to prove it controls
+//!    the applicant DID.
+//! 3. Members vote on a [`ProposalType::NewMemberInvitation`] proposal.
+//! 4. Executing that proposal grants membership. The runtime then issues a
+//!    membership credential, and the node adds the applicant's peer to its
+//!    federation.
+//!
+//! Every step is recorded in the application's
+//! [`MembershipApplicationState`], so both sides can track its progress.
+
+use crate::constitution::ProposalClass;
+use crate::deliberation::anchor;
+use crate::federation_governance::{FederationMembershipTrustGate, MembershipApplicationResult};
+use crate::{
+    GovernanceModule, Proposal, ProposalId, ProposalStatus, ProposalSubmission, ProposalType,
+};
+use icn_common::{CommonError, DagBlock, Did, Signable, SignatureBytes, TimeProvider};
+use icn_identity::did_document::{FederationMembership, FederationRole, MembershipStatus};
+use icn_identity::{
+    verifying_key_from_did_key, ChallengeType, Credential, EdSignature, TrustContext, TrustLevel,
+    VerificationChallenge,
+};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Time an applicant has to answer its challenges.
+pub const CHALLENGE_WINDOW_SECS: u64 = 86_400;
+/// Shortest membership vote; a longer constitutional minimum wins.
+pub const MEMBERSHIP_VOTE_SECS: u64 = 604_800;
+/// Wait before reapplying after a failed membership vote.
+const REAPPLY_AFTER_SECS: u64 = 2_592_000;
+
+/// An existing member vouching for an applicant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MembershipAttestation {
+    pub applicant: Did,
+    pub federation: String,
+    /// `did:key` of the attesting member.
+    pub attestor: Did,
+    pub trust_level: TrustLevel,
+    pub context: TrustContext,
+    pub issued_at: u64,
+    pub signature: Option<SignatureBytes>,
+}
+
+impl Signable for MembershipAttestation {
+    fn to_signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(self.applicant.to_string().as_bytes());
+        bytes.extend_from_slice(self.federation.as_bytes());
+        bytes.extend_from_slice(self.attestor.to_string().as_bytes());
+        bytes.extend_from_slice(self.trust_level.as_str().as_bytes());
+        bytes.extend_from_slice(self.context.as_str().as_bytes());
+        bytes.extend_from_slice(&self.issued_at.to_le_bytes());
+        Ok(bytes)
+    }
+}
+
+/// A cooperative's request to join a federation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MembershipApplication {
+    /// Derived from the application contents, see [`MembershipApplication::new`].
+    pub id: ProposalId,
+    pub federation: String,
+    /// `did:key` of the applying cooperative.
+    pub applicant: Did,
+    pub name: String,
+    pub statement: String,
+    /// Peer address of the applicant's node, added to the federation on
+    /// acceptance.
+    pub node_peer: Option<String>,
+    pub credentials: Vec<Credential>,
+    pub attestations: Vec<MembershipAttestation>,
+    pub submitted_at: u64,
+    pub signature: Option<SignatureBytes>,
+}
+
+/// Encode a credential independently of map iteration order.
+fn credential_bytes(credential: &Credential) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(credential.issuer.to_string().as_bytes());
+    bytes.extend_from_slice(credential.holder.to_string().as_bytes());
+    let mut claims: Vec<_> = credential.claims.iter().collect();
+    claims.sort();
+    for (name, value) in claims {
+        bytes.extend_from_slice(name.as_bytes());
+        bytes.extend_from_slice(value.as_bytes());
+        if let Some(signature) = credential.signatures.get(name) {
+            bytes.extend_from_slice(&signature.0);
+        }
+    }
+    if let Some(schema) = &credential.schema {
+        bytes.extend_from_slice(schema.to_string().as_bytes());
+    }
+    if let Some(expires_at) = credential.expires_at {
+        bytes.extend_from_slice(&expires_at.to_le_bytes());
+    }
+    bytes
+}
+
+impl Signable for MembershipApplication {
+    fn to_signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(self.federation.as_bytes());
+        bytes.extend_from_slice(self.applicant.to_string().as_bytes());
+        bytes.extend_from_slice(self.name.as_bytes());
+        bytes.extend_from_slice(self.statement.as_bytes());
+        if let Some(peer) = &self.node_peer {
+            bytes.extend_from_slice(peer.as_bytes());
+        }
+        for credential in &self.credentials {
+            bytes.extend_from_slice(&credential_bytes(credential));
+        }
+        for attestation in &self.attestations {
+            bytes.extend_from_slice(&attestation.to_signable_bytes()?);
+            if let Some(signature) = &attestation.signature {
+                bytes.extend_from_slice(&signature.0);
+            }
+        }
+        bytes.extend_from_slice(&self.submitted_at.to_le_bytes());
+        Ok(bytes)
+    }
+}
+
+impl MembershipApplication {
+    /// Draft an unsigned application; its id commits to the contents.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        federation: String,
+        applicant: Did,
+        name: String,
+        statement: String,
+        node_peer: Option<String>,
+        credentials: Vec<Credential>,
+        attestations: Vec<MembershipAttestation>,
+        submitted_at: u64,
+    ) -> Result<Self, CommonError> {
+        let mut application = MembershipApplication {
+            id: ProposalId(String::new()),
+            federation,
+            applicant,
+            name,
+            statement,
+            node_peer,
+            credentials,
+            attestations,
+            submitted_at,
+            signature: None,
+        };
+        application.id = application.derived_id()?;
+        Ok(application)
+    }
+
+    fn derived_id(&self) -> Result<ProposalId, CommonError> {
+        let digest = Sha256::digest(self.to_signable_bytes()?);
+        Ok(ProposalId(format!("app:{}", hex::encode(&digest[..16]))))
+    }
+
+    /// Check the id and the applicant's signature.
+    pub fn verify_signature(&self) -> Result<(), CommonError> {
+        if self.id != self.derived_id()? {
+            return Err(CommonError::InvalidInputError(
+                "Application id does not match its contents".to_string(),
+            ));
+        }
+        let signature = self
+            .signature
+            .as_ref()
+            .ok_or_else(|| CommonError::CryptoError("Application is not signed".to_string()))?;
+        let key = verifying_key_from_did_key(&self.applicant)?;
+        self.verify(signature, &key)
+    }
+}
+
+/// Bytes the applicant signs to answer `challenge`.
+pub fn challenge_message(
+    application_id: &ProposalId,
+    challenge: &VerificationChallenge,
+) -> Vec<u8> {
+    let mut bytes = b"icn-membership-challenge".to_vec();
+    bytes.extend_from_slice(application_id.0.as_bytes());
+    bytes.extend_from_slice(challenge.challenge_id.as_bytes());
+    bytes.extend_from_slice(&challenge.challenge_data);
+    bytes
+}
+
+/// The applicant's signed answer to a challenge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ChallengeResponse {
+    pub application_id: ProposalId,
+    pub challenge_id: String,
+    /// Signature over [`challenge_message`].
+    pub signature: SignatureBytes,
+}
+
+/// Where an application stands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ApplicationStatus {
+    /// Waiting for the applicant to answer its challenges.
+    Challenged,
+    /// Members are voting on the application.
+    Voting {
+        proposal_id: ProposalId,
+    },
+    Accepted,
+    Rejected {
+        reason: String,
+        /// `None` when the cooperative may reapply straight away.
+        can_reapply_after: Option<u64>,
+    },
+}
+
+/// Membership granted to an accepted applicant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MembershipGrant {
+    pub federation: String,
+    pub member: Did,
+    /// Trust contexts the gate granted.
+    pub contexts: Vec<TrustContext>,
+    pub granted_at: u64,
+    pub probationary_until: Option<u64>,
+    /// The membership vote.
+    pub proposal_id: ProposalId,
+}
+
+impl MembershipGrant {
+    /// DID the federation is known by in DID documents.
+    pub fn federation_did(&self) -> Did {
+        Did::new("federation", &self.federation)
+    }
+
+    /// Entry for the member's DID document.
+    pub fn did_document_entry(&self) -> FederationMembership {
+        FederationMembership {
+            federation_id: self.federation_did(),
+            role: FederationRole::Member,
+            status: MembershipStatus::Active,
+            granted_at: self.granted_at,
+            expires_at: None,
+        }
+    }
+}
+
+/// An application as tracked by the federation.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MembershipApplicationState {
+    pub application: MembershipApplication,
+    pub status: ApplicationStatus,
+    pub challenges: Vec<VerificationChallenge>,
+    /// Trust contexts the gate would grant.
+    pub granted_contexts: Vec<TrustContext>,
+    pub grant: Option<MembershipGrant>,
+    /// Membership credential issued after acceptance.
+    pub credential: Option<Credential>,
+    pub updated_at: u64,
+}
+
+/// Storage for applications, mirroring the governance backend.
+pub(crate) type MembershipApplicationStore =
+    crate::state_store::ProposalStateStore<MembershipApplicationState>;
+
+/// Trust gates keyed by federation id.
+pub(crate) type MembershipGateStore =
+    crate::state_store::ProposalStateStore<FederationMembershipTrustGate>;
+
+impl GovernanceModule {
+    /// Accept applications to `gate.federation` under `gate`.
+    pub fn set_membership_gate(
+        &mut self,
+        gate: FederationMembershipTrustGate,
+    ) -> Result<(), CommonError> {
+        if !(0.0..=1.0).contains(&gate.committee_approval_threshold) {
+            return Err(CommonError::InvalidInputError(
+                "committee_approval_threshold must be between 0 and 1".to_string(),
+            ));
+        }
+        let key = ProposalId(gate.federation.as_str().to_string());
+        self.membership_gates.put(&key, &gate)
+    }
+
+    /// Trust gate for applications to `federation`, if it accepts any.
+    pub fn membership_gate(
+        &self,
+        federation: &str,
+    ) -> Result<Option<FederationMembershipTrustGate>, CommonError> {
+        self.membership_gates
+            .get(&ProposalId(federation.to_string()))
+    }
+
+    /// Screen a signed application and challenge the applicant.
+    ///
+    /// `nonce` must be unpredictable; it becomes the challenge data.
+    /// Applications the gate turns down are recorded as rejected rather than
+    /// returned as errors so the applicant can see why. Returns the state and
+    /// a block anchoring the application.
+    pub fn submit_membership_application(
+        &mut self,
+        application: MembershipApplication,
+        nonce: [u8; 32],
+        time_provider: &dyn TimeProvider,
+    ) -> Result<(MembershipApplicationState, DagBlock), CommonError> {
+        application.verify_signature()?;
+        let gate = self
+            .membership_gate(&application.federation)?
+            .ok_or_else(|| {
+                CommonError::PolicyDenied(format!(
+                    "Federation {} is not accepting applications",
+                    application.federation
+                ))
+            })?;
+        if self.members.contains(&application.applicant) {
+            return Err(CommonError::InvalidInputError(format!(
+                "{} is already a member",
+                application.applicant
+            )));
+        }
+        if self.membership_applications.contains(&application.id) {
+            return Err(CommonError::InvalidInputError(format!(
+                "Application {} was already submitted",
+                application.id
+            )));
+        }
+        let now = time_provider.unix_seconds();
+        for earlier in self.membership_applications.list()? {
+            if earlier.application.applicant != application.applicant
+                || earlier.application.federation != application.federation
+            {
+                continue;
+            }
+            match earlier.status {
+                ApplicationStatus::Challenged | ApplicationStatus::Voting { .. } => {
+                    return Err(CommonError::PolicyDenied(format!(
+                        "Application {} is still pending",
+                        earlier.application.id
+                    )));
+                }
+                ApplicationStatus::Rejected {
+                    can_reapply_after: Some(after),
+                    ..
+                } if after > now => {
+                    return Err(CommonError::PolicyDenied(format!(
+                        "{} may not reapply before {}",
+                        application.applicant, after
+                    )));
+                }
+                _ => {}
+            }
+        }
+
+        let attestations = self.verify_membership_attestations(&application)?;
+        let missing = Self::missing_credential_claims(&application, &gate, now)?;
+        let result = if let Some(claim) = missing {
+            MembershipApplicationResult::Rejected {
+                reason: format!("No valid credential asserts {}", claim),
+                can_reapply_after: None,
+            }
+        } else {
+            gate.evaluate(&attestations, now)
+        };
+
+        let mut state = MembershipApplicationState {
+            application,
+            status: ApplicationStatus::Challenged,
+            challenges: Vec::new(),
+            granted_contexts: Vec::new(),
+            grant: None,
+            credential: None,
+            updated_at: now,
+        };
+        match result {
+            MembershipApplicationResult::Approved {
+                granted_contexts, ..
+            } => {
+                let mut contexts: Vec<_> = granted_contexts.into_iter().collect();
+                contexts.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+                state.granted_contexts = contexts;
+                state.challenges.push(VerificationChallenge {
+                    challenge_id: format!("{}:did-ownership", state.application.id),
+                    challenge_type: ChallengeType::DidOwnership,
+                    challenge_data: nonce.to_vec(),
+                    expected_response: None,
+                    deadline: now + CHALLENGE_WINDOW_SECS,
+                    completed: false,
+                });
+            }
+            MembershipApplicationResult::Rejected {
+                reason,
+                can_reapply_after,
+            } => {
+                state.status = ApplicationStatus::Rejected {
+                    reason,
+                    can_reapply_after,
+                };
+            }
+            MembershipApplicationResult::Pending {
+                required_actions, ..
+            } => {
+                state.status = ApplicationStatus::Rejected {
+                    reason: format!("Further review required: {}", required_actions.join(", ")),
+                    can_reapply_after: None,
+                };
+            }
+        }
+
+        let block = anchor(
+            &state.application,
+            Vec::new(),
+            &state.application.applicant,
+            state.application.submitted_at,
+            state.application.signature.clone(),
+        )?;
+        self.membership_applications
+            .put(&state.application.id, &state)?;
+        Ok((state, block))
+    }
+
+    /// Verify attestations and return the `(level, context)` pairs the gate
+    /// evaluates, counting each attestor once per context.
+    fn verify_membership_attestations(
+        &self,
+        application: &MembershipApplication,
+    ) -> Result<Vec<(TrustLevel, TrustContext)>, CommonError> {
+        let mut seen = HashSet::new();
+        let mut attestations = Vec::new();
+        for attestation in &application.attestations {
+            if attestation.applicant != application.applicant
+                || attestation.federation != application.federation
+            {
+                return Err(CommonError::InvalidInputError(format!(
+                    "Attestation by {} is for a different application",
+                    attestation.attestor
+                )));
+            }
+            if !self.members.contains(&attestation.attestor) {
+                return Err(CommonError::PolicyDenied(format!(
+                    "Attestor {} is not a member",
+                    attestation.attestor
+                )));
+            }
+            let signature = attestation.signature.as_ref().ok_or_else(|| {
+                CommonError::CryptoError(format!(
+                    "Attestation by {} is not signed",
+                    attestation.attestor
+                ))
+            })?;
+            let key = verifying_key_from_did_key(&attestation.attestor)?;
+            attestation.verify(signature, &key)?;
+            if seen.insert((attestation.attestor.clone(), attestation.context.clone())) {
+                attestations.push((attestation.trust_level.clone(), attestation.context.clone()));
+            }
+        }
+        Ok(attestations)
+    }
+
+    /// Verify the application's credentials and return the first claim
+    /// required by the gate that none of them asserts.
+    fn missing_credential_claims(
+        application: &MembershipApplication,
+        gate: &FederationMembershipTrustGate,
+        now: u64,
+    ) -> Result<Option<String>, CommonError> {
+        let mut valid = Vec::new();
+        for credential in &application.credentials {
+            if credential.holder != application.applicant {
+                return Err(CommonError::InvalidInputError(format!(
+                    "Credential from {} is held by {}, not the applicant",
+                    credential.issuer, credential.holder
+                )));
+            }
+            if credential.expires_at.is_some_and(|at| at <= now) {
+                continue;
+            }
+            let key = verifying_key_from_did_key(&credential.issuer)?;
+            for claim in credential.claims.keys() {
+                credential.verify_claim(claim, &key)?;
+            }
+            valid.push(credential);
+        }
+        let mut required: Vec<_> = gate.additional_requirements.iter().collect();
+        required.sort();
+        Ok(required
+            .into_iter()
+            .find(|(claim, value)| {
+                !valid
+                    .iter()
+                    .any(|c| c.claims.get(*claim).is_some_and(|v| v == *value))
+            })
+            .map(|(claim, value)| format!("{}={}", claim, value)))
+    }
+
+    /// Record the applicant's answer to a challenge. Once every challenge is
+    /// answered, `submitter` opens the membership vote.
+    pub fn answer_membership_challenge(
+        &mut self,
+        response: ChallengeResponse,
+        submitter: Did,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<MembershipApplicationState, CommonError> {
+        let mut state = self.load_membership_application(&response.application_id)?;
+        if state.status != ApplicationStatus::Challenged {
+            return Err(CommonError::PolicyDenied(format!(
+                "Application {} is not waiting for challenge responses ({:?})",
+                state.application.id, state.status
+            )));
+        }
+        let now = time_provider.unix_seconds();
+        let application_id = state.application.id.clone();
+        let key = verifying_key_from_did_key(&state.application.applicant)?;
+        let challenge = state
+            .challenges
+            .iter_mut()
+            .find(|c| c.challenge_id == response.challenge_id)
+            .ok_or_else(|| {
+                CommonError::ResourceNotFound(format!(
+                    "Challenge {} not found",
+                    response.challenge_id
+                ))
+            })?;
+        if now > challenge.deadline {
+            return Err(CommonError::PolicyDenied(format!(
+                "Challenge {} expired at {}",
+                challenge.challenge_id, challenge.deadline
+            )));
+        }
+        let signature: EdSignature = (&response.signature).try_into()?;
+        key.verify_strict(&challenge_message(&application_id, challenge), &signature)
+            .map_err(|_| {
+                CommonError::CryptoError("Challenge response signature is invalid".to_string())
+            })?;
+        challenge.completed = true;
+        state.updated_at = now;
+
+        if state.challenges.iter().all(|c| c.completed) {
+            let duration_secs = self
+                .constitution()
+                .current()
+                .map(|c| {
+                    c.constitution
+                        .rule_for(ProposalClass::MemberAdmission)
+                        .min_voting_period_secs
+                })
+                .unwrap_or(0)
+                .max(MEMBERSHIP_VOTE_SECS);
+            let proposal_id = self.submit_proposal(
+                ProposalSubmission {
+                    proposer: submitter,
+                    proposal_type: ProposalType::NewMemberInvitation(
+                        state.application.applicant.clone(),
+                    ),
+                    description: format!(
+                        "Membership application {} from {}\n\n{}",
+                        state.application.id, state.application.name, state.application.statement
+                    ),
+                    duration_secs,
+                    quorum: None,
+                    threshold: None,
+                    content_cid: None,
+                    timelock_delay: None,
+                },
+                time_provider,
+            )?;
+            state.status = ApplicationStatus::Voting { proposal_id };
+        }
+        self.membership_applications
+            .put(&state.application.id, &state)?;
+        Ok(state)
+    }
+
+    /// Grant membership for an executed membership vote.
+    pub(crate) fn complete_membership_application(
+        &mut self,
+        proposal: &Proposal,
+    ) -> Result<Option<MembershipApplicationState>, CommonError> {
+        if proposal.status != ProposalStatus::Executed {
+            return Ok(None);
+        }
+        let ProposalType::NewMemberInvitation(_) = proposal.proposal_type else {
+            return Ok(None);
+        };
+        let Some(mut state) = self.membership_applications.list()?.into_iter().find(|s| {
+            s.status
+                == ApplicationStatus::Voting {
+                    proposal_id: proposal.id.clone(),
+                }
+        }) else {
+            return Ok(None);
+        };
+        let probation = self
+            .membership_gate(&state.application.federation)?
+            .and_then(|g| g.probationary_period);
+        let granted_at = proposal.voting_deadline;
+        state.grant = Some(MembershipGrant {
+            federation: state.application.federation.clone(),
+            member: state.application.applicant.clone(),
+            contexts: state.granted_contexts.clone(),
+            granted_at,
+            probationary_until: probation.map(|p| granted_at + p),
+            proposal_id: proposal.id.clone(),
+        });
+        state.status = ApplicationStatus::Accepted;
+        state.updated_at = granted_at;
+        self.membership_applications
+            .put(&state.application.id, &state)?;
+        Ok(Some(state))
+    }
+
+    /// Reject applications whose challenges expired or whose vote failed,
+    /// and accept those whose vote was executed. Returns the changed states.
+    pub fn refresh_membership_applications(
+        &mut self,
+        now: u64,
+    ) -> Result<Vec<MembershipApplicationState>, CommonError> {
+        let mut changed = Vec::new();
+        for mut state in self.membership_applications.list()? {
+            match &state.status {
+                ApplicationStatus::Challenged => {
+                    if state
+                        .challenges
+                        .iter()
+                        .any(|c| !c.completed && now > c.deadline)
+                    {
+                        state.status = ApplicationStatus::Rejected {
+                            reason: "Challenge was not answered in time".to_string(),
+                            can_reapply_after: None,
+                        };
+                        state.updated_at = now;
+                        self.membership_applications
+                            .put(&state.application.id, &state)?;
+                        changed.push(state);
+                    }
+                }
+                ApplicationStatus::Voting { proposal_id } => {
+                    let Some(proposal) = self.get_proposal(proposal_id)? else {
+                        continue;
+                    };
+                    match proposal.status {
+                        ProposalStatus::Rejected | ProposalStatus::Failed => {
+                            state.status = ApplicationStatus::Rejected {
+                                reason: "The membership vote did not pass".to_string(),
+                                can_reapply_after: Some(now + REAPPLY_AFTER_SECS),
+                            };
+                            state.updated_at = now;
+                            self.membership_applications
+                                .put(&state.application.id, &state)?;
+                            changed.push(state);
+                        }
+                        ProposalStatus::Executed => {
+                            changed.extend(self.complete_membership_application(&proposal)?);
+                        }
+                        _ => {}
+                    }
+                }
+                ApplicationStatus::Accepted | ApplicationStatus::Rejected { .. } => {}
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Accepted applications still waiting for their membership credential.
+    pub fn pending_membership_onboarding(
+        &self,
+    ) -> Result<Vec<MembershipApplicationState>, CommonError> {
+        Ok(self
+            .membership_applications
+            .list()?
+            .into_iter()
+            .filter(|s| s.status == ApplicationStatus::Accepted && s.credential.is_none())
+            .collect())
+    }
+
+    /// Attach the membership credential issued to an accepted applicant.
+    pub fn record_membership_credential(
+        &mut self,
+        application_id: &ProposalId,
+        credential: Credential,
+    ) -> Result<MembershipApplicationState, CommonError> {
+        let mut state = self.load_membership_application(application_id)?;
+        if state.status != ApplicationStatus::Accepted {
+            return Err(CommonError::PolicyDenied(format!(
+                "Application {} has not been accepted",
+                application_id
+            )));
+        }
+        if credential.holder != state.application.applicant {
+            return Err(CommonError::InvalidInputError(
+                "Membership credential must be held by the applicant".to_string(),
+            ));
+        }
+        state.credential = Some(credential);
+        self.membership_applications.put(application_id, &state)?;
+        Ok(state)
+    }
+
+    /// State of a membership application.
+    pub fn membership_application(
+        &self,
+        application_id: &ProposalId,
+    ) -> Result<Option<MembershipApplicationState>, CommonError> {
+        self.membership_applications.get(application_id)
+    }
+
+    /// All membership applications received by this federation.
+    pub fn membership_applications(&self) -> Result<Vec<MembershipApplicationState>, CommonError> {
+        self.membership_applications.list()
+    }
+
+    fn load_membership_application(
+        &self,
+        application_id: &ProposalId,
+    ) -> Result<MembershipApplicationState, CommonError> {
+        self.membership_applications
+            .get(application_id)?
+            .ok_or_else(|| {
+                CommonError::ResourceNotFound(format!(
+                    "Membership application {} not found",
+                    application_id
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VoteOption;
+    use icn_common::FixedTimeProvider;
+    use icn_identity::{did_key_from_verifying_key, generate_ed25519_keypair, FederationId};
+    use icn_identity::{Signer, SigningKey};
+    use std::collections::{HashMap, HashSet};
+    use std::str::FromStr;
+
+    fn keyed() -> (SigningKey, Did) {
+        let (sk, vk) = generate_ed25519_keypair();
+        let did = Did::from_str(&did_key_from_verifying_key(&vk)).unwrap();
+        (sk, did)
+    }
+
+    fn gate() -> FederationMembershipTrustGate {
+        FederationMembershipTrustGate {
+            federation: FederationId::new("fed".into()),
+            min_trust_level: TrustLevel::Partial,
+            required_contexts: HashSet::from([TrustContext::Governance]),
+            min_attestations: 1,
+            committee_approval_threshold: 0.5,
+            probationary_period: Some(100),
+            additional_requirements: HashMap::from([(
+                "legal_form".to_string(),
+                "cooperative".to_string(),
+            )]),
+        }
+    }
+
+    fn application(
+        applicant: (&SigningKey, &Did),
+        attestor: (&SigningKey, &Did),
+        legal_form: &str,
+    ) -> MembershipApplication {
+        let mut attestation = MembershipAttestation {
+            applicant: applicant.1.clone(),
+            federation: "fed".into(),
+            attestor: attestor.1.clone(),
+            trust_level: TrustLevel::Full,
+            context: TrustContext::Governance,
+            issued_at: 1,
+            signature: None,
+        };
+        attestation.signature = Some(attestation.sign(attestor.0).unwrap());
+        let mut credential = Credential::new(
+            attestor.1.clone(),
+            applicant.1.clone(),
+            HashMap::from([("legal_form".to_string(), legal_form.to_string())]),
+            None,
+        );
+        credential.sign_claims(attestor.0);
+        let mut application = MembershipApplication::new(
+            "fed".into(),
+            applicant.1.clone(),
+            "Bakery Co-op".into(),
+            "We bake.".into(),
+            Some("/ip4/10.0.0.2/tcp/4001".into()),
+            vec![credential],
+            vec![attestation],
+            2,
+        )
+        .unwrap();
+        application.signature = Some(application.sign(applicant.0).unwrap());
+        application
+    }
+
+    #[test]
+    fn accepted_application_grants_membership() {
+        let (member_sk, member) = keyed();
+        let (applicant_sk, applicant) = keyed();
+        let mut gov = GovernanceModule::new();
+        gov.add_member(member.clone());
+        gov.set_membership_gate(gate()).unwrap();
+
+        let app = application(
+            (&applicant_sk, &applicant),
+            (&member_sk, &member),
+            "cooperative",
+        );
+        let (state, block) = gov
+            .submit_membership_application(app, [7; 32], &FixedTimeProvider::new(10))
+            .unwrap();
+        assert_eq!(state.status, ApplicationStatus::Challenged);
+        assert_eq!(state.granted_contexts, vec![TrustContext::Governance]);
+        assert_eq!(block.author_did, applicant);
+
+        // A wrong key cannot answer the challenge.
+        let challenge = state.challenges[0].clone();
+        let message = challenge_message(&state.application.id, &challenge);
+        let forged = ChallengeResponse {
+            application_id: state.application.id.clone(),
+            challenge_id: challenge.challenge_id.clone(),
+            signature: SignatureBytes(member_sk.sign(&message).to_bytes().to_vec()),
+        };
+        assert!(gov
+            .answer_membership_challenge(forged, member.clone(), &FixedTimeProvider::new(11))
+            .is_err());
+
+        let response = ChallengeResponse {
+            application_id: state.application.id.clone(),
+            challenge_id: challenge.challenge_id,
+            signature: SignatureBytes(applicant_sk.sign(&message).to_bytes().to_vec()),
+        };
+        let state = gov
+            .answer_membership_challenge(response, member.clone(), &FixedTimeProvider::new(11))
+            .unwrap();
+        let ApplicationStatus::Voting { proposal_id } = state.status.clone() else {
+            panic!("expected a membership vote, got {:?}", state.status);
+        };
+
+        gov.open_voting(&proposal_id).unwrap();
+        gov.cast_vote(
+            member.clone(),
+            &proposal_id,
+            VoteOption::Yes,
+            &FixedTimeProvider::new(12),
+        )
+        .unwrap();
+        gov.close_voting_period(&proposal_id, &FixedTimeProvider::new(12))
+            .unwrap();
+        gov.execute_proposal(&proposal_id).unwrap();
+
+        let state = gov
+            .membership_application(&state.application.id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(state.status, ApplicationStatus::Accepted);
+        assert!(gov.members().contains(&applicant));
+        let grant = state.grant.clone().unwrap();
+        assert_eq!(grant.probationary_until, Some(grant.granted_at + 100));
+        let entry = grant.did_document_entry();
+        assert_eq!(entry.federation_id.to_string(), "did:federation:fed");
+        assert_eq!(gov.pending_membership_onboarding().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn gate_failures_are_recorded_and_block_reapplying_early() {
+        let (member_sk, member) = keyed();
+        let (applicant_sk, applicant) = keyed();
+        let mut gov = GovernanceModule::new();
+        gov.add_member(member.clone());
+
+        let app = application(
+            (&applicant_sk, &applicant),
+            (&member_sk, &member),
+            "company",
+        );
+        assert!(gov
+            .submit_membership_application(app.clone(), [1; 32], &FixedTimeProvider::new(10))
+            .is_err());
+
+        gov.set_membership_gate(gate()).unwrap();
+        let (state, _) = gov
+            .submit_membership_application(app, [1; 32], &FixedTimeProvider::new(10))
+            .unwrap();
+        assert!(matches!(state.status, ApplicationStatus::Rejected { .. }));
+        assert!(state.challenges.is_empty());
+
+        let mut strict = gate();
+        strict.additional_requirements.clear();
+        strict.min_attestations = 2;
+        gov.set_membership_gate(strict).unwrap();
+        let app = application(
+            (&applicant_sk, &applicant),
+            (&member_sk, &member),
+            "cooperative",
+        );
+        let (state, _) = gov
+            .submit_membership_application(app.clone(), [1; 32], &FixedTimeProvider::new(20))
+            .unwrap();
+        let ApplicationStatus::Rejected {
+            can_reapply_after: Some(after),
+            ..
+        } = state.status
+        else {
+            panic!("expected a rejection with a waiting period");
+        };
+
+        let mut retry = app;
+        retry.submitted_at = 30;
+        retry.id = retry.derived_id().unwrap();
+        retry.signature = Some(retry.sign(&applicant_sk).unwrap());
+        assert!(gov
+            .submit_membership_application(retry, [1; 32], &FixedTimeProvider::new(after - 1))
+            .is_err());
+    }
+}
@@ -0,0 +1,670 @@
+//! Typed registry of governance-controlled runtime parameters
+//!
+//! Every parameter a [`ProposalType::SystemParameterChange`] may touch is
+//! declared by a [`ParameterSpec`]: its type, default, numeric bounds and
+//! whether nodes can apply it without a restart. Proposals naming unknown
+//! parameters or out-of-range values are rejected when submitted, and
+//! executed changes are recorded in an append-only history of
+//! [`ParameterChange`]s.
+//!
+//! Changes take effect at the proposal's voting deadline rather than at the
+//! wall-clock time each node happens to execute it, so every node derives the
+//! same history. A change can be undone by executing the proposal returned
+//! from [`ParameterRegistry::rollback_proposal`], which restores the value
+//! that was in force before it.
+
+use crate::{GovernanceModule, Proposal, ProposalId, ProposalType};
+use icn_common::CommonError;
+use std::collections::HashMap;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Value type of a parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ParameterType {
+    U64,
+    F64,
+    Bool,
+    String,
+    /// Comma separated list of strings.
+    StringList,
+}
+
+/// A typed parameter value.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ParameterValue {
+    U64(u64),
+    F64(f64),
+    Bool(bool),
+    String(String),
+    StringList(Vec<String>),
+}
+
+impl ParameterValue {
+    /// Parse `raw` as a value of type `ty`.
+    pub fn parse(ty: ParameterType, raw: &str) -> Result<Self, CommonError> {
+        let raw = raw.trim();
+        let invalid = |e: String| {
+            CommonError::InvalidInputError(format!("Invalid {:?} value '{}': {}", ty, raw, e))
+        };
+        Ok(match ty {
+            ParameterType::U64 => Self::U64(raw.parse().map_err(|e| invalid(format!("{e}")))?),
+            ParameterType::F64 => {
+                let v: f64 = raw.parse().map_err(|e| invalid(format!("{e}")))?;
+                if !v.is_finite() {
+                    return Err(invalid("not a finite number".into()));
+                }
+                Self::F64(v)
+            }
+            ParameterType::Bool => Self::Bool(raw.parse().map_err(|e| invalid(format!("{e}")))?),
+            ParameterType::String => Self::String(raw.to_string()),
+            ParameterType::StringList => Self::StringList(
+                raw.split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            ),
+        })
+    }
+
+    pub fn parameter_type(&self) -> ParameterType {
+        match self {
+            Self::U64(_) => ParameterType::U64,
+            Self::F64(_) => ParameterType::F64,
+            Self::Bool(_) => ParameterType::Bool,
+            Self::String(_) => ParameterType::String,
+            Self::StringList(_) => ParameterType::StringList,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Self::U64(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::F64(v) => Some(*v),
+            Self::U64(v) => Some(*v as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    fn numeric(&self) -> Option<f64> {
+        match self {
+            Self::U64(v) => Some(*v as f64),
+            Self::F64(v) => Some(*v),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParameterValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::U64(v) => write!(f, "{v}"),
+            Self::F64(v) => write!(f, "{v}"),
+            Self::Bool(v) => write!(f, "{v}"),
+            Self::String(v) => write!(f, "{v}"),
+            Self::StringList(v) => write!(f, "{}", v.join(",")),
+        }
+    }
+}
+
+/// Declaration of a governable parameter.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParameterSpec {
+    pub name: String,
+    pub description: String,
+    pub default: ParameterValue,
+    /// Inclusive lower bound for numeric parameters.
+    pub min: Option<f64>,
+    /// Inclusive upper bound for numeric parameters.
+    pub max: Option<f64>,
+    /// Whether running nodes apply the change immediately. Otherwise it takes
+    /// effect on the next restart.
+    pub hot_reload: bool,
+}
+
+impl ParameterSpec {
+    /// Declare a hot-reloadable parameter without bounds.
+    pub fn new(name: &str, description: &str, default: ParameterValue) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            default,
+            min: None,
+            max: None,
+            hot_reload: true,
+        }
+    }
+
+    pub fn with_bounds(mut self, min: f64, max: f64) -> Self {
+        self.min = Some(min);
+        self.max = Some(max);
+        self
+    }
+
+    pub fn requires_restart(mut self) -> Self {
+        self.hot_reload = false;
+        self
+    }
+
+    pub fn parameter_type(&self) -> ParameterType {
+        self.default.parameter_type()
+    }
+
+    /// Parse and bounds-check a raw value for this parameter.
+    pub fn validate(&self, raw: &str) -> Result<ParameterValue, CommonError> {
+        let value = ParameterValue::parse(self.parameter_type(), raw)?;
+        if let Some(v) = value.numeric() {
+            if self.min.is_some_and(|min| v < min) || self.max.is_some_and(|max| v > max) {
+                return Err(CommonError::InvalidInputError(format!(
+                    "Value {} for {} is outside [{}, {}]",
+                    value,
+                    self.name,
+                    self.min.map_or("-inf".into(), |m| m.to_string()),
+                    self.max.map_or("inf".into(), |m| m.to_string()),
+                )));
+            }
+        }
+        Ok(value)
+    }
+}
+
+/// Maximum mana an account can regenerate up to.
+pub const MANA_MAX_CAPACITY: &str = "mana_max_capacity";
+/// Base mana regenerated per account each regeneration tick.
+pub const MANA_REGENERATION_RATE: &str = "mana_regeneration_rate";
+/// Mana charged for submitting a proposal.
+pub const PROPOSAL_COST_MANA: &str = "proposal_cost_mana";
+/// Mana charged for casting a vote.
+pub const VOTE_COST_MANA: &str = "vote_cost_mana";
+/// Default number of votes required for a proposal to be decided.
+pub const GOVERNANCE_QUORUM: &str = "governance_quorum";
+/// Default fraction of `Yes` votes required to accept a proposal.
+pub const GOVERNANCE_THRESHOLD: &str = "governance_threshold";
+/// Requests per minute allowed for unauthenticated HTTP clients.
+pub const OPEN_RATE_LIMIT: &str = "open_rate_limit";
+/// Executor selection weight on bid price.
+pub const EXECUTOR_WEIGHT_PRICE: &str = "executor_weight_price";
+/// Executor selection weight on reputation.
+pub const EXECUTOR_WEIGHT_REPUTATION: &str = "executor_weight_reputation";
+/// Executor selection weight on offered resources.
+pub const EXECUTOR_WEIGHT_RESOURCES: &str = "executor_weight_resources";
+/// Executor selection weight on network latency.
+pub const EXECUTOR_WEIGHT_LATENCY: &str = "executor_weight_latency";
+/// Capabilities advertised in this node's bids.
+pub const EXECUTOR_CAPABILITIES: &str = "executor_capabilities";
+/// Federations this node executes jobs for.
+pub const EXECUTOR_FEDERATIONS: &str = "executor_federations";
+/// Trust scope advertised in this node's bids.
+pub const EXECUTOR_TRUST_SCOPE: &str = "executor_trust_scope";
+/// Maximum number of connected peers.
+pub const NETWORK_MAX_PEERS: &str = "network_max_peers";
+/// SHA-256 of the Groth16 verifying key approved for secret ballots.
+pub const SECRET_BALLOT_VERIFYING_KEY_HASH: &str = "secret_ballot_verifying_key_hash";
+/// DID of the account whose mana funds conviction voting proposals.
+pub const CONVICTION_TREASURY_DID: &str = "conviction_treasury_did";
+/// Whether voters must pass the governance module's Sybil guard.
+pub const GOVERNANCE_REQUIRE_PERSONHOOD: &str = "governance_require_personhood";
+/// DIDs trusted as verified persons without vouches.
+pub const PERSONHOOD_SEEDS: &str = "personhood_seeds";
+/// `did:key`s trusted to sign software upgrade manifests.
+pub const RELEASE_SIGNERS: &str = "release_signers";
+
+/// One executed parameter change.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParameterChange {
+    /// Position in the history, starting at 1.
+    pub version: u64,
+    pub name: String,
+    pub previous: ParameterValue,
+    pub value: ParameterValue,
+    /// Proposal that made the change.
+    pub proposal_id: Option<ProposalId>,
+    /// Unix time the change took effect.
+    pub effective_at: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct RegistryState {
+    values: HashMap<String, ParameterValue>,
+    history: Vec<ParameterChange>,
+}
+
+/// Current values and change history of all governable parameters.
+#[derive(Debug)]
+pub struct ParameterRegistry {
+    specs: HashMap<String, ParameterSpec>,
+    state: RegistryState,
+    #[cfg(feature = "persist-sled")]
+    tree: Option<sled::Tree>,
+}
+
+const STATE_KEY: &[u8] = b"registry";
+
+impl Default for ParameterRegistry {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl ParameterRegistry {
+    /// Registry without any parameters declared.
+    pub fn empty() -> Self {
+        Self {
+            specs: HashMap::new(),
+            state: RegistryState::default(),
+            #[cfg(feature = "persist-sled")]
+            tree: None,
+        }
+    }
+
+    /// Registry declaring the parameters understood by ICN nodes.
+    pub fn standard() -> Self {
+        use ParameterValue::*;
+        let mut registry = Self::empty();
+        for spec in [
+            ParameterSpec::new(
+                MANA_MAX_CAPACITY,
+                "Maximum regenerated mana balance",
+                U64(10_000),
+            )
+            .with_bounds(1.0, 1e12),
+            ParameterSpec::new(
+                MANA_REGENERATION_RATE,
+                "Base mana regenerated per tick",
+                U64(10),
+            )
+            .with_bounds(0.0, 1e9),
+            ParameterSpec::new(PROPOSAL_COST_MANA, "Mana charged per proposal", U64(10))
+                .with_bounds(0.0, 1e9),
+            ParameterSpec::new(VOTE_COST_MANA, "Mana charged per vote", U64(1))
+                .with_bounds(0.0, 1e9),
+            ParameterSpec::new(GOVERNANCE_QUORUM, "Default proposal quorum", U64(1))
+                .with_bounds(1.0, 1e6),
+            ParameterSpec::new(
+                GOVERNANCE_THRESHOLD,
+                "Default acceptance threshold",
+                F64(0.5),
+            )
+            .with_bounds(0.0, 1.0),
+            ParameterSpec::new(
+                OPEN_RATE_LIMIT,
+                "Unauthenticated requests per minute",
+                U64(60),
+            )
+            .with_bounds(0.0, 1e6),
+            ParameterSpec::new(
+                EXECUTOR_WEIGHT_PRICE,
+                "Executor selection weight: price",
+                F64(1.0),
+            )
+            .with_bounds(0.0, 1000.0),
+            ParameterSpec::new(
+                EXECUTOR_WEIGHT_REPUTATION,
+                "Executor selection weight: reputation",
+                F64(50.0),
+            )
+            .with_bounds(0.0, 1000.0),
+            ParameterSpec::new(
+                EXECUTOR_WEIGHT_RESOURCES,
+                "Executor selection weight: resources",
+                F64(1.0),
+            )
+            .with_bounds(0.0, 1000.0),
+            ParameterSpec::new(
+                EXECUTOR_WEIGHT_LATENCY,
+                "Executor selection weight: latency",
+                F64(1.0),
+            )
+            .with_bounds(0.0, 1000.0),
+            ParameterSpec::new(
+                EXECUTOR_CAPABILITIES,
+                "Capabilities advertised in bids",
+                StringList(Vec::new()),
+            ),
+            ParameterSpec::new(
+                EXECUTOR_FEDERATIONS,
+                "Federations served by this executor",
+                StringList(Vec::new()),
+            ),
+            ParameterSpec::new(
+                EXECUTOR_TRUST_SCOPE,
+                "Trust scope advertised in bids",
+                String(std::string::String::new()),
+            ),
+            ParameterSpec::new(NETWORK_MAX_PEERS, "Maximum connected peers", U64(50))
+                .with_bounds(1.0, 10_000.0)
+                .requires_restart(),
+            ParameterSpec::new(
+                SECRET_BALLOT_VERIFYING_KEY_HASH,
+                "Hash of the approved secret-ballot verifying key",
+                String(std::string::String::new()),
+            ),
+            ParameterSpec::new(
+                CONVICTION_TREASURY_DID,
+                "Account funding conviction voting proposals",
+                String(std::string::String::new()),
+            ),
+            ParameterSpec::new(
+                GOVERNANCE_REQUIRE_PERSONHOOD,
+                "Require voters to be verified persons",
+                Bool(false),
+            ),
+            ParameterSpec::new(
+                PERSONHOOD_SEEDS,
+                "Seed members anchoring personhood verification",
+                StringList(Vec::new()),
+            ),
+            ParameterSpec::new(
+                RELEASE_SIGNERS,
+                "Publishers trusted to sign upgrade manifests",
+                StringList(Vec::new()),
+            ),
+        ] {
+            registry.specs.insert(spec.name.clone(), spec);
+        }
+        registry
+    }
+
+    /// Standard registry whose values and history persist in `tree`.
+    #[cfg(feature = "persist-sled")]
+    pub fn sled(tree: sled::Tree) -> Result<Self, CommonError> {
+        let mut registry = Self::standard();
+        if let Some(bytes) = tree.get(STATE_KEY).map_err(|e| {
+            CommonError::DatabaseError(format!("Failed to load parameter registry: {}", e))
+        })? {
+            registry.state = bincode::deserialize(&bytes).map_err(|e| {
+                CommonError::DeserializationError(format!(
+                    "Failed to deserialize parameter registry: {}",
+                    e
+                ))
+            })?;
+        }
+        registry.tree = Some(tree);
+        Ok(registry)
+    }
+
+    /// Declare an additional parameter.
+    pub fn register(&mut self, spec: ParameterSpec) -> Result<(), CommonError> {
+        if self.specs.contains_key(&spec.name) {
+            return Err(CommonError::InvalidInputError(format!(
+                "Parameter {} is already registered",
+                spec.name
+            )));
+        }
+        self.specs.insert(spec.name.clone(), spec);
+        Ok(())
+    }
+
+    pub fn spec(&self, name: &str) -> Option<&ParameterSpec> {
+        self.specs.get(name)
+    }
+
+    /// All declared parameters, sorted by name.
+    pub fn specs(&self) -> Vec<&ParameterSpec> {
+        let mut specs: Vec<_> = self.specs.values().collect();
+        specs.sort_by(|a, b| a.name.cmp(&b.name));
+        specs
+    }
+
+    /// Current value of a parameter, falling back to its default.
+    pub fn get(&self, name: &str) -> Option<&ParameterValue> {
+        self.state
+            .values
+            .get(name)
+            .or_else(|| self.specs.get(name).map(|s| &s.default))
+    }
+
+    /// Parse and bounds-check a value for a declared parameter.
+    pub fn validate(&self, name: &str, raw: &str) -> Result<ParameterValue, CommonError> {
+        self.specs
+            .get(name)
+            .ok_or_else(|| CommonError::InvalidInputError(format!("Unknown parameter {}", name)))?
+            .validate(raw)
+    }
+
+    /// Set the current value without recording a governance change, e.g. when
+    /// an operator configures the node at startup.
+    pub fn set_baseline(&mut self, name: &str, value: ParameterValue) -> Result<(), CommonError> {
+        let spec = self
+            .specs
+            .get(name)
+            .ok_or_else(|| CommonError::InvalidInputError(format!("Unknown parameter {}", name)))?;
+        spec.validate(&value.to_string())?;
+        self.state.values.insert(name.to_string(), value);
+        self.persist()
+    }
+
+    /// Apply a set of changes atomically. Every value is validated before any
+    /// is applied; if persisting fails the in-memory state is left untouched.
+    pub fn apply(
+        &mut self,
+        changes: &[(String, String)],
+        proposal_id: Option<ProposalId>,
+        effective_at: u64,
+    ) -> Result<Vec<ParameterChange>, CommonError> {
+        let validated = changes
+            .iter()
+            .map(|(name, raw)| Ok((name.clone(), self.validate(name, raw)?)))
+            .collect::<Result<Vec<_>, CommonError>>()?;
+
+        let snapshot = self.state.clone();
+        let mut applied = Vec::new();
+        for (name, value) in validated {
+            let previous = self.get(&name).cloned().expect("validated parameter");
+            let change = ParameterChange {
+                version: self.state.history.len() as u64 + 1,
+                name: name.clone(),
+                previous,
+                value: value.clone(),
+                proposal_id: proposal_id.clone(),
+                effective_at,
+            };
+            self.state.values.insert(name, value);
+            self.state.history.push(change.clone());
+            applied.push(change);
+        }
+        if let Err(e) = self.persist() {
+            self.state = snapshot;
+            return Err(e);
+        }
+        Ok(applied)
+    }
+
+    /// Every executed change, oldest first.
+    pub fn history(&self) -> &[ParameterChange] {
+        &self.state.history
+    }
+
+    /// Changes made by a particular proposal.
+    pub fn changes_by(&self, proposal_id: &ProposalId) -> Vec<ParameterChange> {
+        self.state
+            .history
+            .iter()
+            .filter(|c| c.proposal_id.as_ref() == Some(proposal_id))
+            .cloned()
+            .collect()
+    }
+
+    /// Proposal type that restores the value replaced by change `version`.
+    pub fn rollback_proposal(&self, version: u64) -> Result<ProposalType, CommonError> {
+        let change = self
+            .state
+            .history
+            .iter()
+            .find(|c| c.version == version)
+            .ok_or_else(|| {
+                CommonError::ResourceNotFound(format!("Parameter change {} not found", version))
+            })?;
+        Ok(ProposalType::SystemParameterChange(
+            change.name.clone(),
+            change.previous.to_string(),
+        ))
+    }
+
+    fn persist(&self) -> Result<(), CommonError> {
+        #[cfg(feature = "persist-sled")]
+        if let Some(tree) = &self.tree {
+            let encoded = bincode::serialize(&self.state).map_err(|e| {
+                CommonError::SerializationError(format!(
+                    "Failed to serialize parameter registry: {}",
+                    e
+                ))
+            })?;
+            tree.insert(STATE_KEY, encoded).map_err(|e| {
+                CommonError::DatabaseError(format!("Failed to persist parameter registry: {}", e))
+            })?;
+            tree.flush().map_err(|e| {
+                CommonError::DatabaseError(format!("Failed to flush parameter registry: {}", e))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl GovernanceModule {
+    /// The typed parameter registry.
+    pub fn parameters(&self) -> &ParameterRegistry {
+        &self.parameters
+    }
+
+    /// Declare an additional governable parameter.
+    pub fn register_parameter(&mut self, spec: ParameterSpec) -> Result<(), CommonError> {
+        self.parameters.register(spec)
+    }
+
+    /// Reject parameter change proposals the registry would not accept.
+    pub(crate) fn validate_parameter_proposal(
+        &self,
+        proposal_type: &ProposalType,
+    ) -> Result<(), CommonError> {
+        if let ProposalType::SystemParameterChange(name, value) = proposal_type {
+            self.parameters.validate(name, value)?;
+        }
+        Ok(())
+    }
+
+    /// Record the change made by an executed parameter proposal and apply
+    /// governance parameters to this module.
+    pub(crate) fn apply_parameter_proposal(
+        &mut self,
+        proposal: &Proposal,
+    ) -> Result<Vec<ParameterChange>, CommonError> {
+        let ProposalType::SystemParameterChange(name, value) = &proposal.proposal_type else {
+            return Ok(Vec::new());
+        };
+        let changes = self.parameters.apply(
+            &[(name.clone(), value.clone())],
+            Some(proposal.id.clone()),
+            proposal.voting_deadline,
+        )?;
+        self.sync_governance_parameters();
+        Ok(changes)
+    }
+
+    /// Load quorum and threshold from the registry.
+    pub(crate) fn sync_governance_parameters(&mut self) {
+        if let Some(q) = self
+            .parameters
+            .get(GOVERNANCE_QUORUM)
+            .and_then(|v| v.as_u64())
+        {
+            self.quorum = q as usize;
+        }
+        if let Some(t) = self
+            .parameters
+            .get(GOVERNANCE_THRESHOLD)
+            .and_then(|v| v.as_f64())
+        {
+            self.threshold = t as f32;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_type_and_bounds() {
+        let reg = ParameterRegistry::standard();
+        assert_eq!(
+            reg.validate(OPEN_RATE_LIMIT, "5").unwrap(),
+            ParameterValue::U64(5)
+        );
+        assert!(reg.validate(OPEN_RATE_LIMIT, "-1").is_err());
+        assert!(reg.validate(GOVERNANCE_THRESHOLD, "1.5").is_err());
+        assert!(reg.validate("no_such_parameter", "1").is_err());
+        assert_eq!(
+            reg.validate(EXECUTOR_CAPABILITIES, "gpu, wasm").unwrap(),
+            ParameterValue::StringList(vec!["gpu".into(), "wasm".into()])
+        );
+        assert!(!reg.spec(NETWORK_MAX_PEERS).unwrap().hot_reload);
+    }
+
+    #[test]
+    fn batch_is_all_or_nothing_and_rollback_restores_previous() {
+        let mut reg = ParameterRegistry::standard();
+        let bad = [
+            (OPEN_RATE_LIMIT.to_string(), "5".to_string()),
+            (GOVERNANCE_THRESHOLD.to_string(), "2".to_string()),
+        ];
+        assert!(reg.apply(&bad, None, 10).is_err());
+        assert!(reg.history().is_empty());
+        assert_eq!(reg.get(OPEN_RATE_LIMIT), Some(&ParameterValue::U64(60)));
+
+        let pid = ProposalId("p1".into());
+        let changes = reg
+            .apply(
+                &[(OPEN_RATE_LIMIT.to_string(), "5".to_string())],
+                Some(pid.clone()),
+                10,
+            )
+            .unwrap();
+        assert_eq!(changes[0].version, 1);
+        assert_eq!(changes[0].previous, ParameterValue::U64(60));
+        assert_eq!(reg.changes_by(&pid).len(), 1);
+
+        match reg.rollback_proposal(1).unwrap() {
+            ProposalType::SystemParameterChange(name, value) => {
+                assert_eq!(name, OPEN_RATE_LIMIT);
+                assert_eq!(value, "60");
+            }
+            other => panic!("unexpected proposal type {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "persist-sled")]
+    #[test]
+    fn history_survives_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = sled::open(dir.path()).unwrap();
+        let mut reg = ParameterRegistry::sled(db.open_tree("parameters_v1").unwrap()).unwrap();
+        reg.apply(&[(VOTE_COST_MANA.to_string(), "3".to_string())], None, 1)
+            .unwrap();
+        drop(reg);
+        let reg = ParameterRegistry::sled(db.open_tree("parameters_v1").unwrap()).unwrap();
+        assert_eq!(reg.get(VOTE_COST_MANA), Some(&ParameterValue::U64(3)));
+        assert_eq!(reg.history().len(), 1);
+    }
+}
@@ -239,6 +239,13 @@ impl ReputationStore for MockReputationStore {
     fn record_proof_attempt(&self, _did: &Did, _success: bool) {
         // Mock implementation - in real system this would record proof attempts
     }
+
+    fn slash(&self, did: &Did, amount: u64) -> u64 {
+        let mut scores = self.scores.write().unwrap();
+        let score = scores.entry(did.to_string()).or_insert(50);
+        *score = score.saturating_sub(amount);
+        *score
+    }
 }
 
 // Helper methods for the mock store
@@ -0,0 +1,1040 @@
+//! Quadratic voting and quadratic funding for ICN governance
+//!
+//! Quadratic methods let members express *how strongly* they care about an
+//! outcome rather than only which outcome they prefer.
+//!
+//! # Quadratic Voting
+//!
+//! Each eligible member receives a budget of voice credits for a voting
+//! session. Casting `n` votes on a proposal costs `n²` credits, so spreading
+//! influence across many proposals is cheap while concentrating it on a single
+//! proposal quickly becomes expensive. Votes may be negative to express
+//! opposition.
+//!
+//! # Quadratic Funding
+//!
+//! A [`QuadraticFundingRound`] distributes a treasury matching pool across
+//! `BudgetAllocation` proposals. A project's ideal match is
+//! `(Σ √cᵢ)² − Σ cᵢ` over its individual contributions `cᵢ`, which rewards
+//! broad support over large single donations. When the ideal matches exceed
+//! the pool they are scaled down proportionally.
+//!
+//! # Sybil Resistance
+//!
+//! Both mechanisms assume one person per DID. Eligibility is delegated to a
+//! [`SybilGuard`]; [`CredentialSybilGuard`] admits only DIDs holding a
+//! credential with a required claim signed by a trusted issuer, and
+//! [`PersonhoodSybilGuard`] admits DIDs currently verified by vouching.
+//!
+//! # Verifiability
+//!
+//! Ballots are signed by the voter's `did:key`. Only signed ballots can be
+//! anchored with [`BallotAnchoringService::anchor_quadratic_ballot`] or
+//! counted, and any observer can recompute the tally from the anchored CIDs
+//! using [`QuadraticVotingSystem::verify_anchored_tally`].
+
+use crate::budgeting::BudgetProposal;
+use crate::voting::{BallotAnchoringService, BallotId, VotingError, VotingSystem};
+use crate::{Proposal, ProposalId, ProposalType};
+use icn_common::{Cid, CommonError, DagBlock, Did, Signable, SignatureBytes, TimeProvider};
+use icn_dag::StorageService;
+use icn_economics::ManaLedger;
+use icn_identity::{verifying_key_from_did_key, Credential, PersonhoodRegistry, VerifyingKey};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Determines whether a DID represents a unique, eligible participant.
+pub trait SybilGuard: Send + Sync {
+    /// Returns `true` if `did` may receive voice credits or matching funds.
+    fn is_unique_member(&self, did: &Did) -> bool;
+}
+
+/// Sybil guard that accepts every DID. Useful for tests and closed groups
+/// whose membership is already curated.
+#[derive(Debug, Default, Clone)]
+pub struct OpenSybilGuard;
+
+impl SybilGuard for OpenSybilGuard {
+    fn is_unique_member(&self, _did: &Did) -> bool {
+        true
+    }
+}
+
+/// Sybil guard backed by `icn-identity` credentials.
+///
+/// A DID is considered unique when it holds a non-expired credential issued by
+/// one of the trusted issuers whose `required_claim` verifies against the
+/// issuer's key.
+pub struct CredentialSybilGuard {
+    required_claim: String,
+    trusted_issuers: HashMap<Did, VerifyingKey>,
+    credentials: HashMap<Did, Vec<Credential>>,
+}
+
+impl CredentialSybilGuard {
+    /// Create a guard requiring `required_claim` (e.g. `"personhood"`).
+    pub fn new(required_claim: impl Into<String>) -> Self {
+        Self {
+            required_claim: required_claim.into(),
+            trusted_issuers: HashMap::new(),
+            credentials: HashMap::new(),
+        }
+    }
+
+    /// Trust credentials signed by `issuer`.
+    pub fn add_trusted_issuer(&mut self, issuer: Did, key: VerifyingKey) {
+        self.trusted_issuers.insert(issuer, key);
+    }
+
+    /// Register a credential presented by its holder.
+    pub fn register_credential(&mut self, credential: Credential) {
+        self.credentials
+            .entry(credential.holder.clone())
+            .or_default()
+            .push(credential);
+    }
+}
+
+impl SybilGuard for CredentialSybilGuard {
+    fn is_unique_member(&self, did: &Did) -> bool {
+        let Some(creds) = self.credentials.get(did) else {
+            return false;
+        };
+        creds.iter().any(|cred| {
+            self.trusted_issuers
+                .get(&cred.issuer)
+                .map(|key| cred.verify_claim(&self.required_claim, key).is_ok())
+                .unwrap_or(false)
+        })
+    }
+}
+
+/// Sybil guard backed by an `icn-identity` [`PersonhoodRegistry`].
+///
+/// Admits the registry's seeds and DIDs whose vouched personhood has not
+/// lapsed at the current time.
+pub struct PersonhoodSybilGuard {
+    registry: PersonhoodRegistry,
+    time_provider: Arc<dyn TimeProvider>,
+}
+
+impl PersonhoodSybilGuard {
+    pub fn new(registry: PersonhoodRegistry, time_provider: Arc<dyn TimeProvider>) -> Self {
+        Self {
+            registry,
+            time_provider,
+        }
+    }
+}
+
+impl SybilGuard for PersonhoodSybilGuard {
+    fn is_unique_member(&self, did: &Did) -> bool {
+        self.registry
+            .is_verified(did, self.time_provider.unix_seconds())
+            .unwrap_or(false)
+    }
+}
+
+/// Votes allocated by a voter to a single proposal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuadraticAllocation {
+    pub proposal_id: ProposalId,
+    /// Positive values support the proposal, negative values oppose it.
+    pub votes: i64,
+}
+
+impl QuadraticAllocation {
+    /// Voice credits consumed by this allocation.
+    pub fn cost(&self) -> u64 {
+        let n = self.votes.unsigned_abs();
+        n.saturating_mul(n)
+    }
+}
+
+/// A signed quadratic voting ballot covering one or more proposals.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuadraticBallot {
+    pub ballot_id: BallotId,
+    pub voter: Did,
+    /// Identifier of the voting session the credits belong to.
+    pub session_id: String,
+    pub allocations: Vec<QuadraticAllocation>,
+    pub timestamp: u64,
+    pub signature: Option<SignatureBytes>,
+}
+
+impl QuadraticBallot {
+    /// Total voice credits spent by this ballot.
+    pub fn total_cost(&self) -> u64 {
+        self.allocations
+            .iter()
+            .fold(0u64, |acc, a| acc.saturating_add(a.cost()))
+    }
+
+    /// Sign the ballot with the voter's key.
+    pub fn sign_with(&mut self, key: &icn_identity::SigningKey) -> Result<(), CommonError> {
+        self.signature = Some(self.sign(key)?);
+        Ok(())
+    }
+
+    /// Verify the ballot signature against the voter's key.
+    pub fn verify_signature(&self, key: &VerifyingKey) -> Result<(), CommonError> {
+        let sig = self
+            .signature
+            .as_ref()
+            .ok_or_else(|| CommonError::CryptoError("ballot is not signed".into()))?;
+        self.verify(sig, key)
+    }
+
+    /// Verify the ballot signature against the key embedded in the voter's
+    /// `did:key`.
+    pub fn verify_voter_signature(&self) -> Result<(), VotingError> {
+        let key =
+            verifying_key_from_did_key(&self.voter).map_err(|_| VotingError::InvalidSignature)?;
+        self.verify_signature(&key)
+            .map_err(|_| VotingError::InvalidSignature)
+    }
+}
+
+impl Signable for QuadraticBallot {
+    fn to_signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        fn put(bytes: &mut Vec<u8>, field: &[u8]) {
+            bytes.extend_from_slice(&(field.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(field);
+        }
+        let mut bytes = Vec::new();
+        put(&mut bytes, self.ballot_id.0.as_bytes());
+        put(&mut bytes, self.voter.to_string().as_bytes());
+        put(&mut bytes, self.session_id.as_bytes());
+        bytes.extend_from_slice(&(self.allocations.len() as u64).to_le_bytes());
+        for alloc in &self.allocations {
+            put(&mut bytes, alloc.proposal_id.0.as_bytes());
+            bytes.extend_from_slice(&alloc.votes.to_le_bytes());
+        }
+        bytes.extend_from_slice(&self.timestamp.to_le_bytes());
+        Ok(bytes)
+    }
+}
+
+/// Aggregated quadratic votes for a single proposal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuadraticOutcome {
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub credits_spent: u64,
+    pub voters: usize,
+}
+
+impl QuadraticOutcome {
+    /// Net support (`votes_for - votes_against`), or `None` if it does not
+    /// fit in an `i64`.
+    pub fn net_votes(&self) -> Option<i64> {
+        let votes_for = i64::try_from(self.votes_for).ok()?;
+        let votes_against = i64::try_from(self.votes_against).ok()?;
+        votes_for.checked_sub(votes_against)
+    }
+
+    /// Whether the proposal received more support than opposition.
+    pub fn passed(&self) -> bool {
+        self.votes_for > self.votes_against
+    }
+}
+
+/// Result of counting a set of quadratic ballots.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuadraticTally {
+    pub session_id: String,
+    pub outcomes: HashMap<ProposalId, QuadraticOutcome>,
+    pub total_ballots: usize,
+    pub total_credits_spent: u64,
+}
+
+/// Quadratic voting system enforcing voice credit budgets.
+pub struct QuadraticVotingSystem {
+    /// Voice credits each eligible voter receives per session.
+    voice_credits: u64,
+    sybil_guard: Arc<dyn SybilGuard>,
+}
+
+impl QuadraticVotingSystem {
+    /// Create a new system granting `voice_credits` to each eligible voter.
+    pub fn new(voice_credits: u64, sybil_guard: Arc<dyn SybilGuard>) -> Self {
+        Self {
+            voice_credits,
+            sybil_guard,
+        }
+    }
+
+    /// Voice credits granted per voter.
+    pub fn voice_credits(&self) -> u64 {
+        self.voice_credits
+    }
+
+    /// Maximum number of votes a voter can place on a single proposal.
+    pub fn max_votes_per_proposal(&self) -> u64 {
+        (self.voice_credits as f64).sqrt().floor() as u64
+    }
+
+    /// Count ballots and verify the result matches `claimed`, reading ballots
+    /// back from the DAG so the check relies only on anchored data. Each
+    /// anchored ballot must carry a valid voter signature.
+    pub fn verify_anchored_tally<S>(
+        &self,
+        anchoring: &BallotAnchoringService<S>,
+        ballot_cids: &[Cid],
+        claimed: &QuadraticTally,
+    ) -> Result<bool, VotingError>
+    where
+        S: StorageService<DagBlock>,
+    {
+        let mut ballots = Vec::with_capacity(ballot_cids.len());
+        for cid in ballot_cids {
+            let ballot = anchoring.retrieve_quadratic_ballot(cid)?.ok_or_else(|| {
+                VotingError::InvalidBallot(format!("Anchored ballot {} not found", cid))
+            })?;
+            ballot.verify_voter_signature()?;
+            ballots.push(ballot);
+        }
+        let recomputed = self.count_votes(ballots)?;
+        Ok(&recomputed == claimed)
+    }
+}
+
+impl VotingSystem for QuadraticVotingSystem {
+    type Ballot = QuadraticBallot;
+    type Result = QuadraticTally;
+    type Error = VotingError;
+
+    fn validate_ballot(&self, ballot: &QuadraticBallot) -> Result<(), VotingError> {
+        ballot.verify_voter_signature()?;
+        if !self.sybil_guard.is_unique_member(&ballot.voter) {
+            return Err(VotingError::IneligibleVoter(format!(
+                "{} has no valid personhood credential",
+                ballot.voter
+            )));
+        }
+        if ballot.allocations.is_empty() {
+            return Err(VotingError::InvalidBallot(
+                "Ballot allocates no votes".to_string(),
+            ));
+        }
+        let mut seen = HashSet::new();
+        for alloc in &ballot.allocations {
+            if !seen.insert(&alloc.proposal_id) {
+                return Err(VotingError::DuplicatePreferences);
+            }
+        }
+        let cost = ballot.total_cost();
+        if cost > self.voice_credits {
+            return Err(VotingError::InvalidBallot(format!(
+                "Ballot spends {} voice credits but only {} are available",
+                cost, self.voice_credits
+            )));
+        }
+        Ok(())
+    }
+
+    fn count_votes(&self, ballots: Vec<QuadraticBallot>) -> Result<QuadraticTally, VotingError> {
+        let session_id = match ballots.first() {
+            Some(b) => b.session_id.clone(),
+            None => {
+                return Err(VotingError::InvalidBallot(
+                    "No ballots provided".to_string(),
+                ))
+            }
+        };
+
+        let mut voters = HashSet::new();
+        let mut tally = QuadraticTally {
+            session_id: session_id.clone(),
+            ..Default::default()
+        };
+        for ballot in &ballots {
+            if ballot.session_id != session_id {
+                return Err(VotingError::InvalidBallot(format!(
+                    "Ballot {} belongs to session {}, expected {}",
+                    ballot.ballot_id, ballot.session_id, session_id
+                )));
+            }
+            if !voters.insert(ballot.voter.clone()) {
+                return Err(VotingError::DuplicateVote);
+            }
+            self.validate_ballot(ballot)?;
+
+            let overflow = || VotingError::InvalidBallot("Tally overflows".to_string());
+            for alloc in &ballot.allocations {
+                let outcome = tally.outcomes.entry(alloc.proposal_id.clone()).or_default();
+                if alloc.votes >= 0 {
+                    outcome.votes_for = outcome
+                        .votes_for
+                        .checked_add(alloc.votes as u64)
+                        .ok_or_else(overflow)?;
+                } else {
+                    outcome.votes_against = outcome
+                        .votes_against
+                        .checked_add(alloc.votes.unsigned_abs())
+                        .ok_or_else(overflow)?;
+                }
+                outcome.credits_spent = outcome
+                    .credits_spent
+                    .checked_add(alloc.cost())
+                    .ok_or_else(overflow)?;
+                outcome.voters += 1;
+            }
+            tally.total_credits_spent = tally
+                .total_credits_spent
+                .checked_add(ballot.total_cost())
+                .ok_or_else(overflow)?;
+        }
+        tally.total_ballots = ballots.len();
+        Ok(tally)
+    }
+
+    fn is_eligible_voter(&self, voter_id: &str) -> Result<bool, VotingError> {
+        let did = Did::from_str(voter_id)
+            .map_err(|e| VotingError::IneligibleVoter(format!("Invalid DID: {}", e)))?;
+        Ok(self.sybil_guard.is_unique_member(&did))
+    }
+}
+
+impl<S> BallotAnchoringService<S>
+where
+    S: StorageService<DagBlock>,
+{
+    /// Anchor a quadratic ballot in the DAG. The ballot must be signed by its
+    /// voter.
+    pub fn anchor_quadratic_ballot(
+        &mut self,
+        ballot: &QuadraticBallot,
+    ) -> Result<Cid, VotingError> {
+        use icn_common::compute_merkle_cid;
+
+        ballot.verify_voter_signature()?;
+        let data = serde_json::to_vec(ballot).map_err(|e| {
+            VotingError::InvalidBallot(format!("Failed to serialize ballot: {}", e))
+        })?;
+        let cid = compute_merkle_cid(
+            0x71,
+            &data,
+            &[],
+            ballot.timestamp,
+            &ballot.voter,
+            &None,
+            &None,
+        );
+        let block = DagBlock {
+            cid: cid.clone(),
+            data,
+            links: vec![],
+            timestamp: ballot.timestamp,
+            author_did: ballot.voter.clone(),
+            // The ballot carries the voter's signature
+            signature: None,
+            scope: None,
+        };
+        self.storage.put(&block).map_err(|e| {
+            VotingError::InvalidBallot(format!("Failed to store ballot in DAG: {}", e))
+        })?;
+        Ok(cid)
+    }
+
+    /// Retrieve a previously anchored quadratic ballot.
+    pub fn retrieve_quadratic_ballot(
+        &self,
+        cid: &Cid,
+    ) -> Result<Option<QuadraticBallot>, VotingError> {
+        let block = self.storage.get(cid).map_err(|e| {
+            VotingError::InvalidBallot(format!("Failed to retrieve from DAG: {}", e))
+        })?;
+        match block {
+            Some(block) => serde_json::from_slice(&block.data).map(Some).map_err(|e| {
+                VotingError::InvalidBallot(format!("Failed to deserialize ballot: {}", e))
+            }),
+            None => Ok(None),
+        }
+    }
+}
+
+/// A `BudgetAllocation` proposal participating in a funding round.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FundingProject {
+    pub proposal_id: ProposalId,
+    pub recipient: Did,
+    pub purpose: String,
+}
+
+impl FundingProject {
+    /// Build a project from a `BudgetAllocation` proposal.
+    pub fn from_proposal(proposal: &Proposal) -> Option<Self> {
+        match &proposal.proposal_type {
+            ProposalType::BudgetAllocation(recipient, _, purpose) => Some(Self {
+                proposal_id: proposal.id.clone(),
+                recipient: recipient.clone(),
+                purpose: purpose.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// A single member contribution towards a project.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Contribution {
+    pub contributor: Did,
+    pub project: ProposalId,
+    pub amount: u64,
+}
+
+/// Funds assigned to a project at the end of a round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FundingAllocation {
+    pub project: ProposalId,
+    pub recipient: Did,
+    /// Sum of eligible direct contributions.
+    pub contributed: u64,
+    /// Amount matched from the treasury pool.
+    pub matched: u64,
+    pub contributors: usize,
+}
+
+/// Outcome of a quadratic funding round.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuadraticFundingResult {
+    pub round_id: String,
+    pub allocations: Vec<FundingAllocation>,
+    pub total_matched: u64,
+    /// Part of the pool left unallocated due to rounding or lack of support.
+    pub pool_remaining: u64,
+    /// Contributors excluded by the sybil guard.
+    pub excluded_contributors: Vec<Did>,
+}
+
+impl QuadraticFundingResult {
+    /// Express the result as budget proposals crediting each recipient.
+    pub fn to_budget_proposals(
+        &self,
+        purpose_prefix: &str,
+    ) -> Result<Vec<BudgetProposal>, CommonError> {
+        let mut proposals = Vec::new();
+        for a in &self.allocations {
+            let amount = a.contributed.checked_add(a.matched).ok_or_else(|| {
+                CommonError::InvalidInputError(format!(
+                    "Funding for project {} overflows",
+                    a.project
+                ))
+            })?;
+            if amount > 0 {
+                proposals.push(BudgetProposal {
+                    recipient: a.recipient.clone(),
+                    amount,
+                    purpose: format!("{}: {}", purpose_prefix, a.project),
+                });
+            }
+        }
+        Ok(proposals)
+    }
+}
+
+/// A quadratic funding round drawing its matching pool from a treasury account.
+pub struct QuadraticFundingRound {
+    pub round_id: String,
+    /// Treasury account supplying the matching pool.
+    pub treasury: Did,
+    pub matching_pool: u64,
+    projects: HashMap<ProposalId, FundingProject>,
+    contributions: Vec<Contribution>,
+    sybil_guard: Arc<dyn SybilGuard>,
+}
+
+impl QuadraticFundingRound {
+    /// Create a new funding round.
+    pub fn new(
+        round_id: impl Into<String>,
+        treasury: Did,
+        matching_pool: u64,
+        sybil_guard: Arc<dyn SybilGuard>,
+    ) -> Self {
+        Self {
+            round_id: round_id.into(),
+            treasury,
+            matching_pool,
+            projects: HashMap::new(),
+            contributions: Vec::new(),
+            sybil_guard,
+        }
+    }
+
+    /// Register a `BudgetAllocation` proposal as a fundable project.
+    pub fn add_project(&mut self, proposal: &Proposal) -> Result<(), CommonError> {
+        let project = FundingProject::from_proposal(proposal).ok_or_else(|| {
+            CommonError::InvalidInputError(format!(
+                "Proposal {} is not a budget allocation",
+                proposal.id
+            ))
+        })?;
+        self.projects.insert(project.proposal_id.clone(), project);
+        Ok(())
+    }
+
+    /// Record a contribution to a registered project.
+    pub fn contribute(&mut self, contribution: Contribution) -> Result<(), CommonError> {
+        if !self.projects.contains_key(&contribution.project) {
+            return Err(CommonError::ResourceNotFound(format!(
+                "Project {} is not part of round {}",
+                contribution.project, self.round_id
+            )));
+        }
+        if contribution.amount == 0 {
+            return Err(CommonError::InvalidInputError(
+                "Contribution amount must be positive".to_string(),
+            ));
+        }
+        self.contributions.push(contribution);
+        Ok(())
+    }
+
+    /// Compute matching amounts using the CLR formula.
+    ///
+    /// Fails if contribution or matching totals overflow.
+    pub fn compute(&self) -> Result<QuadraticFundingResult, CommonError> {
+        let overflow =
+            || CommonError::InvalidInputError(format!("Round {} totals overflow", self.round_id));
+        let mut excluded = HashSet::new();
+        // Aggregate per (project, contributor) so splitting one donation into
+        // many small ones does not inflate the match.
+        let mut per_project: HashMap<&ProposalId, HashMap<&Did, u64>> = HashMap::new();
+        for c in &self.contributions {
+            if !self.sybil_guard.is_unique_member(&c.contributor) {
+                excluded.insert(c.contributor.clone());
+                continue;
+            }
+            let total = per_project
+                .entry(&c.project)
+                .or_default()
+                .entry(&c.contributor)
+                .or_default();
+            *total = total.checked_add(c.amount).ok_or_else(overflow)?;
+        }
+
+        let mut project_ids: Vec<&ProposalId> = self.projects.keys().collect();
+        project_ids.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut ideal = Vec::with_capacity(project_ids.len());
+        for id in &project_ids {
+            let contribs = per_project.get(id);
+            let mut sum_sqrt = 0f64;
+            let mut sum = 0u64;
+            for v in contribs.into_iter().flat_map(|m| m.values()) {
+                sum_sqrt += (*v as f64).sqrt();
+                sum = sum.checked_add(*v).ok_or_else(overflow)?;
+            }
+            let ideal_match = (sum_sqrt * sum_sqrt - sum as f64).max(0.0);
+            ideal.push((
+                *id,
+                sum,
+                contribs.map(|m| m.len()).unwrap_or(0),
+                ideal_match,
+            ));
+        }
+
+        let total_ideal: f64 = ideal.iter().map(|(_, _, _, m)| m).sum();
+        let scale = if total_ideal > self.matching_pool as f64 {
+            self.matching_pool as f64 / total_ideal
+        } else {
+            1.0
+        };
+
+        let mut allocations = Vec::with_capacity(ideal.len());
+        let mut total_matched = 0u64;
+        for (id, contributed, contributors, ideal_match) in ideal {
+            let matched = (ideal_match * scale).floor() as u64;
+            total_matched = total_matched.checked_add(matched).ok_or_else(overflow)?;
+            // Recipients are credited `contributed + matched`.
+            contributed.checked_add(matched).ok_or_else(overflow)?;
+            allocations.push(FundingAllocation {
+                project: id.clone(),
+                recipient: self.projects[id].recipient.clone(),
+                contributed,
+                matched,
+                contributors,
+            });
+        }
+
+        let mut excluded_contributors: Vec<Did> = excluded.into_iter().collect();
+        excluded_contributors.sort_by_key(|d| d.to_string());
+
+        Ok(QuadraticFundingResult {
+            round_id: self.round_id.clone(),
+            allocations,
+            total_matched,
+            pool_remaining: self.matching_pool.saturating_sub(total_matched),
+            excluded_contributors,
+        })
+    }
+
+    /// Settle the round: debit contributors and the treasury, then credit
+    /// each project recipient.
+    ///
+    /// Balances are checked up front so the ledger is left untouched when any
+    /// account cannot cover its share.
+    pub fn settle<M: ManaLedger>(&self, ledger: &M) -> Result<QuadraticFundingResult, CommonError> {
+        let result = self.compute()?;
+        let overflow =
+            || CommonError::InvalidInputError(format!("Round {} debits overflow", self.round_id));
+
+        let mut debits: HashMap<Did, u64> = HashMap::new();
+        for c in &self.contributions {
+            if self.sybil_guard.is_unique_member(&c.contributor) {
+                let debit = debits.entry(c.contributor.clone()).or_default();
+                *debit = debit.checked_add(c.amount).ok_or_else(overflow)?;
+            }
+        }
+        let debit = debits.entry(self.treasury.clone()).or_default();
+        *debit = debit
+            .checked_add(result.total_matched)
+            .ok_or_else(overflow)?;
+
+        for (did, amount) in &debits {
+            let balance = ledger.get_balance(did);
+            if balance < *amount {
+                return Err(CommonError::PolicyDenied(format!(
+                    "{} has {} mana but round {} requires {}",
+                    did, balance, self.round_id, amount
+                )));
+            }
+        }
+        for (did, amount) in &debits {
+            if *amount > 0 {
+                ledger.spend(did, *amount)?;
+            }
+        }
+        for proposal in result.to_budget_proposals(&self.round_id)? {
+            crate::apply_budget_allocation(ledger, &proposal)?;
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ProposalSponsorship, ProposalStatus};
+    use icn_dag::InMemoryDagStore;
+    use icn_identity::{did_key_from_verifying_key, generate_ed25519_keypair, SigningKey};
+    use std::sync::Mutex;
+
+    fn did(name: &str) -> Did {
+        Did::from_str(&format!("did:example:{name}")).unwrap()
+    }
+
+    fn voter_key(name: &str) -> SigningKey {
+        let mut seed = [0u8; 32];
+        seed[..name.len()].copy_from_slice(name.as_bytes());
+        SigningKey::from_bytes(&seed)
+    }
+
+    fn voter(name: &str) -> Did {
+        Did::from_str(&did_key_from_verifying_key(
+            &voter_key(name).verifying_key(),
+        ))
+        .unwrap()
+    }
+
+    fn ballot(name: &str, allocs: &[(&str, i64)]) -> QuadraticBallot {
+        let mut ballot = unsigned_ballot(name, allocs);
+        ballot.sign_with(&voter_key(name)).unwrap();
+        ballot
+    }
+
+    fn unsigned_ballot(name: &str, allocs: &[(&str, i64)]) -> QuadraticBallot {
+        QuadraticBallot {
+            ballot_id: BallotId(format!("b-{name}")),
+            voter: voter(name),
+            session_id: "s1".into(),
+            allocations: allocs
+                .iter()
+                .map(|(p, v)| QuadraticAllocation {
+                    proposal_id: ProposalId(p.to_string()),
+                    votes: *v,
+                })
+                .collect(),
+            timestamp: 1_700_000_000,
+            signature: None,
+        }
+    }
+
+    fn budget_proposal(id: &str, recipient: &str) -> Proposal {
+        Proposal {
+            id: ProposalId(id.into()),
+            proposer: did("proposer"),
+            proposal_type: ProposalType::BudgetAllocation(did(recipient), 0, "work".into()),
+            description: String::new(),
+            created_at: 0,
+            voting_deadline: 0,
+            status: ProposalStatus::VotingOpen,
+            votes: HashMap::new(),
+            quorum: None,
+            threshold: None,
+            content_cid: None,
+            sponsorship: ProposalSponsorship::new(),
+            accepted_at: None,
+            timelock_delay: None,
+            veto: None,
+        }
+    }
+
+    #[derive(Default)]
+    struct TestLedger(Mutex<HashMap<Did, u64>>);
+
+    impl ManaLedger for TestLedger {
+        fn get_balance(&self, did: &Did) -> u64 {
+            *self.0.lock().unwrap().get(did).unwrap_or(&0)
+        }
+        fn set_balance(&self, did: &Did, amount: u64) -> Result<(), CommonError> {
+            self.0.lock().unwrap().insert(did.clone(), amount);
+            Ok(())
+        }
+        fn spend(&self, did: &Did, amount: u64) -> Result<(), CommonError> {
+            let mut map = self.0.lock().unwrap();
+            let bal = map.entry(did.clone()).or_default();
+            *bal = bal
+                .checked_sub(amount)
+                .ok_or_else(|| CommonError::PolicyDenied("insufficient".into()))?;
+            Ok(())
+        }
+        fn credit(&self, did: &Did, amount: u64) -> Result<(), CommonError> {
+            *self.0.lock().unwrap().entry(did.clone()).or_default() += amount;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn quadratic_cost_enforced() {
+        let system = QuadraticVotingSystem::new(100, Arc::new(OpenSybilGuard));
+        assert_eq!(system.max_votes_per_proposal(), 10);
+        assert!(system.validate_ballot(&ballot("a", &[("p1", 10)])).is_ok());
+        assert!(system
+            .validate_ballot(&ballot("a", &[("p1", 8), ("p2", -7)]))
+            .is_err());
+    }
+
+    #[test]
+    fn ballots_need_the_voters_signature() {
+        let system = QuadraticVotingSystem::new(100, Arc::new(OpenSybilGuard));
+        let unsigned = unsigned_ballot("a", &[("p1", 3)]);
+        assert!(matches!(
+            system.validate_ballot(&unsigned),
+            Err(VotingError::InvalidSignature)
+        ));
+
+        let mut forged = unsigned.clone();
+        forged.sign_with(&voter_key("b")).unwrap();
+        assert!(matches!(
+            system.count_votes(vec![forged.clone()]),
+            Err(VotingError::InvalidSignature)
+        ));
+
+        let mut anchoring = BallotAnchoringService::new(InMemoryDagStore::new());
+        assert!(anchoring.anchor_quadratic_ballot(&unsigned).is_err());
+        assert!(anchoring.anchor_quadratic_ballot(&forged).is_err());
+
+        // Field boundaries are part of the signed bytes.
+        let mut shifted = unsigned.clone();
+        shifted.ballot_id = BallotId(format!("{}s", unsigned.ballot_id.0));
+        shifted.session_id = "1".into();
+        assert_ne!(
+            shifted.to_signable_bytes().unwrap(),
+            unsigned.to_signable_bytes().unwrap()
+        );
+    }
+
+    #[test]
+    fn tally_counts_intensity() {
+        let system = QuadraticVotingSystem::new(100, Arc::new(OpenSybilGuard));
+        let tally = system
+            .count_votes(vec![
+                ballot("a", &[("p1", 9), ("p2", 3)]),
+                ballot("b", &[("p1", -2), ("p2", 5)]),
+            ])
+            .unwrap();
+        let p1 = &tally.outcomes[&ProposalId("p1".into())];
+        assert_eq!(p1.votes_for, 9);
+        assert_eq!(p1.votes_against, 2);
+        assert!(p1.passed());
+        assert_eq!(p1.net_votes(), Some(7));
+        assert_eq!(tally.total_credits_spent, 81 + 9 + 4 + 25);
+        let lopsided = QuadraticOutcome {
+            votes_for: u64::MAX,
+            ..QuadraticOutcome::default()
+        };
+        assert_eq!(lopsided.net_votes(), None);
+        assert!(lopsided.passed());
+
+        let dup = system.count_votes(vec![ballot("a", &[("p1", 1)]), ballot("a", &[("p1", 1)])]);
+        assert!(matches!(dup, Err(VotingError::DuplicateVote)));
+    }
+
+    #[test]
+    fn credential_guard_requires_trusted_claim() {
+        let (sk, pk) = generate_ed25519_keypair();
+        let issuer = did("issuer");
+        let mut guard = CredentialSybilGuard::new("personhood");
+        guard.add_trusted_issuer(issuer.clone(), pk);
+
+        let mut claims = HashMap::new();
+        claims.insert("personhood".to_string(), "verified".to_string());
+        let mut cred = Credential::new(issuer, voter("alice"), claims, None);
+        cred.sign_claims(&sk);
+        guard.register_credential(cred);
+
+        let system = QuadraticVotingSystem::new(25, Arc::new(guard));
+        assert!(system
+            .validate_ballot(&ballot("alice", &[("p", 5)]))
+            .is_ok());
+        assert!(matches!(
+            system.validate_ballot(&ballot("mallory", &[("p", 5)])),
+            Err(VotingError::IneligibleVoter(_))
+        ));
+    }
+
+    #[test]
+    fn anchored_ballots_reproduce_tally() {
+        let system = QuadraticVotingSystem::new(100, Arc::new(OpenSybilGuard));
+        let ballots = vec![ballot("a", &[("p1", 4)]), ballot("b", &[("p1", -3)])];
+        let mut anchoring = BallotAnchoringService::new(InMemoryDagStore::new());
+        let cids: Vec<Cid> = ballots
+            .iter()
+            .map(|b| anchoring.anchor_quadratic_ballot(b).unwrap())
+            .collect();
+        let tally = system.count_votes(ballots).unwrap();
+        assert!(system
+            .verify_anchored_tally(&anchoring, &cids, &tally)
+            .unwrap());
+
+        let mut forged = tally.clone();
+        forged
+            .outcomes
+            .get_mut(&ProposalId("p1".into()))
+            .unwrap()
+            .votes_for = 40;
+        assert!(!system
+            .verify_anchored_tally(&anchoring, &cids, &forged)
+            .unwrap());
+    }
+
+    #[test]
+    fn funding_favours_broad_support() {
+        let mut round =
+            QuadraticFundingRound::new("r1", did("treasury"), 1_000, Arc::new(OpenSybilGuard));
+        round
+            .add_project(&budget_proposal("broad", "coop-a"))
+            .unwrap();
+        round
+            .add_project(&budget_proposal("narrow", "coop-b"))
+            .unwrap();
+        for i in 0..4 {
+            round
+                .contribute(Contribution {
+                    contributor: did(&format!("m{i}")),
+                    project: ProposalId("broad".into()),
+                    amount: 25,
+                })
+                .unwrap();
+        }
+        round
+            .contribute(Contribution {
+                contributor: did("whale"),
+                project: ProposalId("narrow".into()),
+                amount: 100,
+            })
+            .unwrap();
+
+        let result = round.compute().unwrap();
+        let broad = result
+            .allocations
+            .iter()
+            .find(|a| a.project.0 == "broad")
+            .unwrap();
+        let narrow = result
+            .allocations
+            .iter()
+            .find(|a| a.project.0 == "narrow")
+            .unwrap();
+        // (4 * 5)^2 - 100 = 300 for broad support, zero match for a single donor.
+        assert_eq!(broad.matched, 300);
+        assert_eq!(narrow.matched, 0);
+        assert_eq!(result.pool_remaining, 700);
+    }
+
+    #[test]
+    fn funding_scales_to_pool_and_settles() {
+        let ledger = TestLedger::default();
+        let treasury = did("treasury");
+        ledger.set_balance(&treasury, 150).unwrap();
+        let mut round =
+            QuadraticFundingRound::new("r2", treasury.clone(), 150, Arc::new(OpenSybilGuard));
+        round.add_project(&budget_proposal("p", "coop")).unwrap();
+        for i in 0..4 {
+            let member = did(&format!("m{i}"));
+            ledger.set_balance(&member, 25).unwrap();
+            round
+                .contribute(Contribution {
+                    contributor: member,
+                    project: ProposalId("p".into()),
+                    amount: 25,
+                })
+                .unwrap();
+        }
+
+        let result = round.settle(&ledger).unwrap();
+        assert_eq!(result.total_matched, 150);
+        assert_eq!(ledger.get_balance(&treasury), 0);
+        assert_eq!(ledger.get_balance(&did("coop")), 250);
+        assert_eq!(ledger.get_balance(&did("m0")), 0);
+    }
+
+    #[test]
+    fn funding_overflow_is_an_error() {
+        let mut round =
+            QuadraticFundingRound::new("r3", did("treasury"), 0, Arc::new(OpenSybilGuard));
+        round.add_project(&budget_proposal("p", "coop")).unwrap();
+        for _ in 0..2 {
+            round
+                .contribute(Contribution {
+                    contributor: did("m0"),
+                    project: ProposalId("p".into()),
+                    amount: u64::MAX,
+                })
+                .unwrap();
+        }
+        assert!(round.compute().is_err());
+        assert!(round.settle(&TestLedger::default()).is_err());
+
+        let result = QuadraticFundingResult {
+            round_id: "r3".into(),
+            allocations: vec![FundingAllocation {
+                project: ProposalId("p".into()),
+                recipient: did("coop"),
+                contributed: u64::MAX,
+                matched: 1,
+                contributors: 1,
+            }],
+            total_matched: 1,
+            pool_remaining: 0,
+            excluded_contributors: Vec::new(),
+        };
+        assert!(result.to_budget_proposals("r3").is_err());
+    }
+}
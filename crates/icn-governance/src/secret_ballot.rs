@@ -0,0 +1,713 @@
+//! Secret ballots with zero-knowledge eligibility proofs
+//!
+//! Ordinary [`Vote`](crate::Vote)s name the voter. For decisions that need
+//! ballot secrecy a proposal can switch to secret ballots before voting opens:
+//!
+//! 1. **Registration.** Members register an identity commitment derived from a
+//!    secret only they hold ([`icn_zk::membership::identity_commitment`]).
+//! 2. **Enabling.** [`GovernanceModule::enable_secret_ballot`] snapshots the
+//!    commitments of current members into a Merkle tree and records the root,
+//!    the published leaves and the Groth16 verifying key for
+//!    [`AnonymousMembershipCircuit`] at that depth. The key must be the one
+//!    whose [`verifying_key_hash`] governance approved in the
+//!    [`SECRET_BALLOT_VERIFYING_KEY_HASH`] parameter.
+//! 3. **Commit phase.** Until `reveal_from` voters submit a [`SecretBallot`]:
+//!    a commitment to their choice plus a proof that they own one of the
+//!    leaves. The proof exposes a per-proposal nullifier so each member votes
+//!    once, and is bound to the commitment so it cannot be reused.
+//! 4. **Reveal phase.** From `reveal_from` voters publish a [`BallotReveal`]
+//!    keyed only by nullifier. Ballots never revealed are not counted.
+//!
+//! Every ballot and reveal is anchored as a DAG block. [`verify_secret_tally`]
+//! recomputes the result from those records and the configuration alone, so
+//! anyone can check the outcome without trusting the node that closed the
+//! vote.
+
+use crate::deliberation::anchor;
+use crate::parameters::SECRET_BALLOT_VERIFYING_KEY_HASH;
+use crate::{GovernanceModule, ProposalId, ProposalStatus, VoteOption};
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::{Proof, ProvingKey, VerifyingKey};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::{CryptoRng, RngCore};
+use icn_common::{Cid, CommonError, DagBlock, Did, TimeProvider};
+use icn_zk::membership::{field_from_bytes, field_from_hex, field_to_hex, identity_commitment};
+use icn_zk::{AnonymousMembershipCircuit, MembershipTree};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Parameters fixed when a proposal switches to secret ballots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SecretBallotConfig {
+    pub tree_depth: usize,
+    /// Hex encoded root of the voter commitment tree.
+    pub membership_root: String,
+    /// Voter commitments in tree order, published so members can build paths.
+    pub voter_commitments: Vec<String>,
+    /// Hex encoded compressed Groth16 verifying key.
+    pub verifying_key: String,
+    /// Ballots are committed before this time and revealed from it on.
+    pub reveal_from: u64,
+}
+
+/// An anonymous, committed ballot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SecretBallot {
+    pub proposal_id: ProposalId,
+    /// Hex encoded nullifier; unique per voter and proposal.
+    pub nullifier: String,
+    /// Commitment to the vote, see [`ballot_commitment`].
+    pub commitment: String,
+    /// Hex encoded compressed Groth16 proof.
+    pub proof: String,
+    pub cast_at: u64,
+}
+
+/// Opening of a [`SecretBallot`] during the reveal phase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BallotReveal {
+    pub proposal_id: ProposalId,
+    pub nullifier: String,
+    pub option: VoteOption,
+    /// Random salt chosen when the ballot was committed.
+    pub salt: String,
+}
+
+/// Ballots, reveals and anchors of one secret-ballot proposal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SecretBallotState {
+    pub proposal_id: ProposalId,
+    pub config: SecretBallotConfig,
+    pub ballots: Vec<SecretBallot>,
+    pub reveals: Vec<BallotReveal>,
+    /// CIDs of the anchored ballot and reveal blocks.
+    pub anchors: Vec<Cid>,
+}
+
+/// Result of counting revealed ballots.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SecretTally {
+    pub yes: usize,
+    pub no: usize,
+    pub abstain: usize,
+    /// Committed ballots that were never revealed.
+    pub unrevealed: usize,
+}
+
+pub(crate) type SecretBallotStore = crate::state_store::ProposalStateStore<SecretBallotState>;
+
+/// Registered voter commitments, keyed by member DID.
+pub(crate) type VoterCommitmentStore = crate::state_store::ProposalStateStore<String>;
+
+/// Hex encoded SHA-256 of a hex encoded verifying key, as approved in the
+/// [`SECRET_BALLOT_VERIFYING_KEY_HASH`] parameter.
+pub fn verifying_key_hash(hex_key: &str) -> Result<String, CommonError> {
+    let bytes = hex::decode(hex_key.trim())
+        .map_err(|e| CommonError::InvalidInputError(format!("Invalid verifying key: {e}")))?;
+    Ok(hex::encode(Sha256::digest(bytes)))
+}
+
+/// Hex encoded commitment to `option` under `salt` for the given nullifier.
+pub fn ballot_commitment(option: VoteOption, salt: &str, nullifier: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update([match option {
+        VoteOption::Yes => 0u8,
+        VoteOption::No => 1,
+        VoteOption::Abstain => 2,
+    }]);
+    hasher.update(salt.as_bytes());
+    hasher.update(nullifier.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+fn proposal_scope(proposal_id: &ProposalId) -> Fr {
+    field_from_bytes(proposal_id.0.as_bytes())
+}
+
+fn parse_field(value: &str, what: &str) -> Result<Fr, CommonError> {
+    field_from_hex(value)
+        .ok_or_else(|| CommonError::InvalidInputError(format!("Invalid {}: {}", what, value)))
+}
+
+/// Parse a nullifier, accepting only the canonical encoding produced by
+/// [`field_to_hex`] so one nullifier cannot be spelled two ways.
+fn parse_nullifier(value: &str) -> Result<Fr, CommonError> {
+    let field = parse_field(value, "nullifier")?;
+    if field_to_hex(&field) != value {
+        return Err(CommonError::InvalidInputError(format!(
+            "Nullifier {} is not canonically encoded",
+            value
+        )));
+    }
+    Ok(field)
+}
+
+fn commitment_tree(config: &SecretBallotConfig) -> Result<MembershipTree, CommonError> {
+    let leaves = config
+        .voter_commitments
+        .iter()
+        .map(|c| parse_field(c, "voter commitment"))
+        .collect::<Result<Vec<_>, _>>()?;
+    MembershipTree::new(config.tree_depth, &leaves).ok_or_else(|| {
+        CommonError::InvalidInputError(format!(
+            "{} voters do not fit a tree of depth {}",
+            leaves.len(),
+            config.tree_depth
+        ))
+    })
+}
+
+fn decode_verifying_key(hex_key: &str) -> Result<VerifyingKey<Bn254>, CommonError> {
+    let bytes = hex::decode(hex_key)
+        .map_err(|e| CommonError::InvalidInputError(format!("Invalid verifying key: {}", e)))?;
+    VerifyingKey::<Bn254>::deserialize_compressed(&bytes[..])
+        .map_err(|e| CommonError::DeserializationError(format!("Invalid verifying key: {}", e)))
+}
+
+fn check_ballot_proof(
+    vk: &ark_groth16::PreparedVerifyingKey<Bn254>,
+    root: Fr,
+    ballot: &SecretBallot,
+) -> Result<(), CommonError> {
+    let proof_bytes = hex::decode(&ballot.proof)
+        .map_err(|e| CommonError::InvalidInputError(format!("Invalid ballot proof: {}", e)))?;
+    let proof = Proof::<Bn254>::deserialize_compressed(&proof_bytes[..])
+        .map_err(|e| CommonError::DeserializationError(format!("Invalid ballot proof: {}", e)))?;
+    let commitment = hex::decode(&ballot.commitment)
+        .map_err(|e| CommonError::InvalidInputError(format!("Invalid ballot commitment: {}", e)))?;
+    let inputs = [
+        root,
+        proposal_scope(&ballot.proposal_id),
+        parse_nullifier(&ballot.nullifier)?,
+        field_from_bytes(&commitment),
+    ];
+    match icn_zk::verify(vk, &proof, &inputs) {
+        Ok(true) => Ok(()),
+        _ => Err(CommonError::CryptoError(format!(
+            "Eligibility proof for nullifier {} does not verify",
+            ballot.nullifier
+        ))),
+    }
+}
+
+/// Recompute a secret-ballot result from its published records.
+///
+/// Checks that the membership root matches the published commitments, that
+/// every ballot carries a valid proof and a distinct nullifier, and that each
+/// counted reveal opens its ballot's commitment.
+pub fn verify_secret_tally(
+    config: &SecretBallotConfig,
+    ballots: &[SecretBallot],
+    reveals: &[BallotReveal],
+) -> Result<SecretTally, CommonError> {
+    let root = parse_field(&config.membership_root, "membership root")?;
+    if commitment_tree(config)?.root() != root {
+        return Err(CommonError::CryptoError(
+            "Membership root does not match the published voter commitments".to_string(),
+        ));
+    }
+    let vk = icn_zk::prepare_verifying_key(&decode_verifying_key(&config.verifying_key)?);
+
+    let mut committed = HashMap::new();
+    for ballot in ballots {
+        check_ballot_proof(&vk, root, ballot)?;
+        if committed
+            .insert(ballot.nullifier.as_str(), ballot.commitment.as_str())
+            .is_some()
+        {
+            return Err(CommonError::InvalidInputError(format!(
+                "Nullifier {} was used twice",
+                ballot.nullifier
+            )));
+        }
+    }
+
+    let mut tally = SecretTally::default();
+    let mut revealed = HashSet::new();
+    for reveal in reveals {
+        let commitment = committed.get(reveal.nullifier.as_str()).ok_or_else(|| {
+            CommonError::InvalidInputError(format!(
+                "Reveal for unknown nullifier {}",
+                reveal.nullifier
+            ))
+        })?;
+        if ballot_commitment(reveal.option, &reveal.salt, &reveal.nullifier) != *commitment {
+            return Err(CommonError::CryptoError(format!(
+                "Reveal does not open the ballot for nullifier {}",
+                reveal.nullifier
+            )));
+        }
+        if !revealed.insert(reveal.nullifier.as_str()) {
+            continue;
+        }
+        match reveal.option {
+            VoteOption::Yes => tally.yes += 1,
+            VoteOption::No => tally.no += 1,
+            VoteOption::Abstain => tally.abstain += 1,
+        }
+    }
+    tally.unrevealed = ballots.len() - revealed.len();
+    Ok(tally)
+}
+
+/// Build a ballot and its opening for a voter holding `secret`.
+///
+/// The reveal must be kept private until the reveal phase.
+pub fn prepare_secret_ballot<R: RngCore + CryptoRng>(
+    proving_key: &ProvingKey<Bn254>,
+    proposal_id: &ProposalId,
+    config: &SecretBallotConfig,
+    secret: &str,
+    option: VoteOption,
+    cast_at: u64,
+    rng: &mut R,
+) -> Result<(SecretBallot, BallotReveal), CommonError> {
+    let secret = parse_field(secret, "voter secret")?;
+    let own = field_to_hex(&identity_commitment(secret));
+    let index = config
+        .voter_commitments
+        .iter()
+        .position(|c| *c == own)
+        .ok_or_else(|| {
+            CommonError::PermissionDenied("Voter commitment is not part of this ballot".to_string())
+        })?;
+    let tree = commitment_tree(config)?;
+    let path = tree.path(index).ok_or_else(|| {
+        CommonError::InternalError("Voter commitment is outside the tree".to_string())
+    })?;
+
+    let scope = proposal_scope(proposal_id);
+    let nullifier = field_to_hex(&icn_zk::membership::nullifier(secret, scope));
+    let salt = field_to_hex(&icn_zk::membership::random_secret(rng));
+    let commitment = ballot_commitment(option, &salt, &nullifier);
+    let signal = field_from_bytes(&hex::decode(&commitment).expect("commitment is hex"));
+
+    let circuit = AnonymousMembershipCircuit {
+        secret,
+        path,
+        root: tree.root(),
+        scope,
+        nullifier: parse_field(&nullifier, "nullifier")?,
+        signal,
+    };
+    let proof = icn_zk::prove(proving_key, circuit, rng)
+        .map_err(|e| CommonError::CryptoError(format!("Failed to prove eligibility: {}", e)))?;
+    let mut proof_bytes = Vec::new();
+    proof
+        .serialize_compressed(&mut proof_bytes)
+        .map_err(|e| CommonError::SerializationError(format!("Failed to encode proof: {}", e)))?;
+
+    Ok((
+        SecretBallot {
+            proposal_id: proposal_id.clone(),
+            nullifier: nullifier.clone(),
+            commitment,
+            proof: hex::encode(proof_bytes),
+            cast_at,
+        },
+        BallotReveal {
+            proposal_id: proposal_id.clone(),
+            nullifier,
+            option,
+            salt,
+        },
+    ))
+}
+
+fn anonymous_author() -> Did {
+    Did::new("icn", "anonymous")
+}
+
+impl GovernanceModule {
+    /// Register the identity commitment a member will vote with in secret
+    /// ballots. Registering again replaces the previous commitment.
+    pub fn register_voter_commitment(
+        &mut self,
+        member: Did,
+        commitment: &str,
+    ) -> Result<(), CommonError> {
+        if !self.members.contains(&member) {
+            return Err(CommonError::PermissionDenied(format!(
+                "{} is not a member",
+                member
+            )));
+        }
+        self.ensure_eligible_voter(&member)?;
+        parse_field(commitment, "voter commitment")?;
+        self.voter_commitments
+            .put(&ProposalId(member.to_string()), &commitment.to_string())
+    }
+
+    /// Identity commitment registered by `member`, if any.
+    pub fn voter_commitment(&self, member: &Did) -> Result<Option<String>, CommonError> {
+        self.voter_commitments.get(&ProposalId(member.to_string()))
+    }
+
+    /// Switch a proposal that has not opened for voting to secret ballots.
+    ///
+    /// The voter set is the registered commitments of current members, in DID
+    /// order. `verifying_key` must hash to the approved
+    /// [`SECRET_BALLOT_VERIFYING_KEY_HASH`].
+    pub fn enable_secret_ballot(
+        &mut self,
+        proposal_id: &ProposalId,
+        verifying_key: &str,
+        tree_depth: usize,
+        reveal_from: u64,
+    ) -> Result<SecretBallotConfig, CommonError> {
+        let proposal = self.get_proposal(proposal_id)?.ok_or_else(|| {
+            CommonError::ResourceNotFound(format!("Proposal {} not found", proposal_id.0))
+        })?;
+        if !matches!(
+            proposal.status,
+            ProposalStatus::Deliberation | ProposalStatus::PendingSponsorship
+        ) {
+            return Err(CommonError::PolicyDenied(format!(
+                "Proposal {} has already opened for voting",
+                proposal_id.0
+            )));
+        }
+        if self.conviction.contains(proposal_id) || self.secret_ballots.contains(proposal_id) {
+            return Err(CommonError::InvalidInputError(format!(
+                "Proposal {} already uses another voting mode",
+                proposal_id.0
+            )));
+        }
+        if reveal_from >= proposal.voting_deadline {
+            return Err(CommonError::InvalidInputError(
+                "The reveal phase must start before the voting deadline".to_string(),
+            ));
+        }
+        decode_verifying_key(verifying_key)?;
+        let approved = self
+            .parameters
+            .get(SECRET_BALLOT_VERIFYING_KEY_HASH)
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        if approved.is_empty() {
+            return Err(CommonError::PolicyDenied(
+                "No secret-ballot verifying key has been approved".to_string(),
+            ));
+        }
+        if !verifying_key_hash(verifying_key)?.eq_ignore_ascii_case(&approved) {
+            return Err(CommonError::PolicyDenied(
+                "Verifying key does not match the approved secret-ballot setup".to_string(),
+            ));
+        }
+
+        let mut voters: Vec<(String, String)> = Vec::new();
+        for member in &self.members {
+            if let Some(commitment) = self
+                .voter_commitments
+                .get(&ProposalId(member.to_string()))?
+            {
+                voters.push((member.to_string(), commitment));
+            }
+        }
+        if voters.is_empty() {
+            return Err(CommonError::PolicyDenied(
+                "No member has registered a voter commitment".to_string(),
+            ));
+        }
+        voters.sort();
+
+        let mut config = SecretBallotConfig {
+            tree_depth,
+            membership_root: String::new(),
+            voter_commitments: voters.into_iter().map(|(_, c)| c).collect(),
+            verifying_key: verifying_key.to_string(),
+            reveal_from,
+        };
+        config.membership_root = field_to_hex(&commitment_tree(&config)?.root());
+        let state = SecretBallotState {
+            proposal_id: proposal_id.clone(),
+            config: config.clone(),
+            ballots: Vec::new(),
+            reveals: Vec::new(),
+            anchors: Vec::new(),
+        };
+        self.secret_ballots.put(proposal_id, &state)?;
+        Ok(config)
+    }
+
+    /// Secret-ballot records of a proposal, if it uses secret ballots.
+    pub fn secret_ballot(
+        &self,
+        proposal_id: &ProposalId,
+    ) -> Result<Option<SecretBallotState>, CommonError> {
+        self.secret_ballots.get(proposal_id)
+    }
+
+    fn open_secret_ballot(
+        &self,
+        proposal_id: &ProposalId,
+        now: u64,
+    ) -> Result<SecretBallotState, CommonError> {
+        let state = self.secret_ballots.get(proposal_id)?.ok_or_else(|| {
+            CommonError::InvalidInputError(format!(
+                "Proposal {} does not use secret ballots",
+                proposal_id.0
+            ))
+        })?;
+        let proposal = self.get_proposal(proposal_id)?.ok_or_else(|| {
+            CommonError::ResourceNotFound(format!("Proposal {} not found", proposal_id.0))
+        })?;
+        if proposal.status != ProposalStatus::VotingOpen || now > proposal.voting_deadline {
+            return Err(CommonError::PolicyDenied(format!(
+                "Proposal {} is not open for voting",
+                proposal_id.0
+            )));
+        }
+        Ok(state)
+    }
+
+    /// Accept an anonymous ballot during the commit phase.
+    ///
+    /// Returns the block anchoring the ballot.
+    pub fn cast_secret_ballot(
+        &mut self,
+        ballot: SecretBallot,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<DagBlock, CommonError> {
+        let now = time_provider.unix_seconds();
+        let mut state = self.open_secret_ballot(&ballot.proposal_id, now)?;
+        if now >= state.config.reveal_from {
+            return Err(CommonError::PolicyDenied(
+                "The commit phase has ended".to_string(),
+            ));
+        }
+        if state
+            .ballots
+            .iter()
+            .any(|b| b.nullifier == ballot.nullifier)
+        {
+            return Err(CommonError::PolicyDenied(
+                "A ballot with this nullifier has already been cast".to_string(),
+            ));
+        }
+        let vk = icn_zk::prepare_verifying_key(&decode_verifying_key(&state.config.verifying_key)?);
+        let root = parse_field(&state.config.membership_root, "membership root")?;
+        check_ballot_proof(&vk, root, &ballot)?;
+
+        let block = anchor(&ballot, Vec::new(), &anonymous_author(), now, None)?;
+        state.anchors.push(block.cid.clone());
+        state.ballots.push(ballot);
+        self.secret_ballots.put(&state.proposal_id, &state)?;
+        Ok(block)
+    }
+
+    /// Accept the opening of a committed ballot during the reveal phase.
+    ///
+    /// Returns the block anchoring the reveal.
+    pub fn reveal_secret_ballot(
+        &mut self,
+        reveal: BallotReveal,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<DagBlock, CommonError> {
+        let now = time_provider.unix_seconds();
+        let mut state = self.open_secret_ballot(&reveal.proposal_id, now)?;
+        if now < state.config.reveal_from {
+            return Err(CommonError::PolicyDenied(
+                "The reveal phase has not started".to_string(),
+            ));
+        }
+        let ballot = state
+            .ballots
+            .iter()
+            .find(|b| b.nullifier == reveal.nullifier)
+            .ok_or_else(|| {
+                CommonError::ResourceNotFound(format!(
+                    "No ballot with nullifier {}",
+                    reveal.nullifier
+                ))
+            })?;
+        if ballot_commitment(reveal.option, &reveal.salt, &reveal.nullifier) != ballot.commitment {
+            return Err(CommonError::CryptoError(
+                "Reveal does not match the committed ballot".to_string(),
+            ));
+        }
+        if state
+            .reveals
+            .iter()
+            .any(|r| r.nullifier == reveal.nullifier)
+        {
+            return Err(CommonError::PolicyDenied(
+                "This ballot has already been revealed".to_string(),
+            ));
+        }
+
+        let block = anchor(&reveal, Vec::new(), &anonymous_author(), now, None)?;
+        state.anchors.push(block.cid.clone());
+        state.reveals.push(reveal);
+        self.secret_ballots.put(&state.proposal_id, &state)?;
+        Ok(block)
+    }
+
+    /// Verified tally of a secret-ballot proposal; `None` for ordinary
+    /// proposals.
+    pub fn secret_ballot_tally(
+        &self,
+        proposal_id: &ProposalId,
+    ) -> Result<Option<SecretTally>, CommonError> {
+        self.secret_ballots
+            .get(proposal_id)?
+            .map(|s| verify_secret_tally(&s.config, &s.ballots, &s.reveals))
+            .transpose()
+    }
+
+    pub(crate) fn uses_secret_ballot(&self, proposal_id: &ProposalId) -> bool {
+        self.secret_ballots.contains(proposal_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ProposalSubmission, ProposalType};
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use icn_common::FixedTimeProvider;
+    use std::str::FromStr;
+
+    fn state_ballot(gov: &GovernanceModule, pid: &ProposalId, index: usize) -> SecretBallot {
+        gov.secret_ballot(pid).unwrap().unwrap().ballots[index].clone()
+    }
+
+    #[test]
+    fn secret_ballot_round_trip() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut gov = GovernanceModule::new();
+        gov.set_quorum(2);
+        let mut secrets = Vec::new();
+        for i in 0..3 {
+            let did = Did::from_str(&format!("did:example:voter{i}")).unwrap();
+            gov.add_member(did.clone());
+            let secret = icn_zk::membership::random_secret(&mut rng);
+            gov.register_voter_commitment(did, &field_to_hex(&identity_commitment(secret)))
+                .unwrap();
+            secrets.push(field_to_hex(&secret));
+        }
+        let proposer = Did::from_str("did:example:voter0").unwrap();
+        let pid = gov
+            .submit_proposal(
+                ProposalSubmission {
+                    proposer: proposer.clone(),
+                    proposal_type: ProposalType::GenericText("recall".into()),
+                    description: "Recall the treasurer".into(),
+                    duration_secs: 1_000,
+                    quorum: None,
+                    threshold: None,
+                    content_cid: None,
+                    timelock_delay: None,
+                },
+                &FixedTimeProvider::new(100),
+            )
+            .unwrap();
+
+        let pk = icn_zk::setup(AnonymousMembershipCircuit::blank(2), &mut rng).unwrap();
+        let mut vk = Vec::new();
+        pk.vk.serialize_compressed(&mut vk).unwrap();
+        let vk = hex::encode(vk);
+        assert!(matches!(
+            gov.enable_secret_ballot(&pid, &vk, 2, 600),
+            Err(CommonError::PolicyDenied(_))
+        ));
+        let rogue = icn_zk::setup(AnonymousMembershipCircuit::blank(2), &mut rng).unwrap();
+        let mut rogue_vk = Vec::new();
+        rogue.vk.serialize_compressed(&mut rogue_vk).unwrap();
+        gov.parameters
+            .apply(
+                &[(
+                    SECRET_BALLOT_VERIFYING_KEY_HASH.to_string(),
+                    verifying_key_hash(&vk).unwrap(),
+                )],
+                None,
+                100,
+            )
+            .unwrap();
+        assert!(gov
+            .enable_secret_ballot(&pid, &hex::encode(rogue_vk), 2, 600)
+            .is_err());
+        let config = gov.enable_secret_ballot(&pid, &vk, 2, 600).unwrap();
+        gov.open_voting(&pid).unwrap();
+
+        let commit_time = FixedTimeProvider::new(200);
+        assert!(gov
+            .cast_vote(proposer, &pid, VoteOption::Yes, &commit_time)
+            .is_err());
+
+        let mut reveals = Vec::new();
+        for (secret, option) in secrets[..2].iter().zip([VoteOption::Yes, VoteOption::No]) {
+            let (ballot, reveal) =
+                prepare_secret_ballot(&pk, &pid, &config, secret, option, 200, &mut rng).unwrap();
+            gov.cast_secret_ballot(ballot.clone(), &commit_time)
+                .unwrap();
+            assert!(gov.cast_secret_ballot(ballot, &commit_time).is_err());
+            reveals.push(reveal);
+        }
+        let (mut forged, _) = prepare_secret_ballot(
+            &pk,
+            &pid,
+            &config,
+            &secrets[2],
+            VoteOption::Yes,
+            200,
+            &mut rng,
+        )
+        .unwrap();
+        forged.commitment = ballot_commitment(VoteOption::No, "00", &forged.nullifier);
+        assert!(gov.cast_secret_ballot(forged, &commit_time).is_err());
+
+        let mut recased = state_ballot(&gov, &pid, 0);
+        recased.nullifier = recased.nullifier.to_uppercase();
+        assert!(matches!(
+            gov.cast_secret_ballot(recased.clone(), &commit_time),
+            Err(CommonError::InvalidInputError(_))
+        ));
+        let mut padded = state_ballot(&gov, &pid, 0);
+        padded.nullifier.push_str("00");
+        assert!(gov.cast_secret_ballot(padded, &commit_time).is_err());
+
+        assert!(gov
+            .reveal_secret_ballot(reveals[0].clone(), &commit_time)
+            .is_err());
+        let reveal_time = FixedTimeProvider::new(700);
+        let mut wrong = reveals[0].clone();
+        wrong.option = VoteOption::No;
+        assert!(gov.reveal_secret_ballot(wrong, &reveal_time).is_err());
+        gov.reveal_secret_ballot(reveals[0].clone(), &reveal_time)
+            .unwrap();
+
+        let tally = gov.secret_ballot_tally(&pid).unwrap().unwrap();
+        assert_eq!(
+            tally,
+            SecretTally {
+                yes: 1,
+                no: 0,
+                abstain: 0,
+                unrevealed: 1
+            }
+        );
+        gov.reveal_secret_ballot(reveals[1].clone(), &reveal_time)
+            .unwrap();
+
+        let state = gov.secret_ballot(&pid).unwrap().unwrap();
+        assert_eq!(state.anchors.len(), 4);
+        let verified = verify_secret_tally(&state.config, &state.ballots, &state.reveals).unwrap();
+        assert_eq!((verified.yes, verified.no, verified.unrevealed), (1, 1, 0));
+        let mut doubled = state.ballots.clone();
+        doubled.push(recased);
+        assert!(verify_secret_tally(&state.config, &doubled, &state.reveals).is_err());
+
+        let (status, counts) = gov.close_voting_period(&pid, &reveal_time).unwrap();
+        assert_eq!(counts, (1, 1, 0));
+        assert_eq!(status, ProposalStatus::Accepted);
+    }
+}
@@ -0,0 +1,731 @@
+//! Governance outcome simulation
+//!
+//! [`GovernanceSimulator`] replays a recorded [`GovernanceEvent`] stream and
+//! decides every finished proposal again under an alternative
+//! [`SimulationConfig`]: a different quorum, threshold or sponsorship
+//! requirement, another way of following delegations, or another decision
+//! rule. The resulting [`SimulationReport`] lists which past outcomes would
+//! have changed, so a federation can see the effect of a rule change before
+//! voting on it.
+//!
+//! Federations without much history can use [`SyntheticPopulation`] to
+//! generate a reproducible stream of members, delegations and votes instead.
+//!
+//! Membership and delegations are not part of the event stream; the
+//! simulator uses the snapshot it is given, plus every member seen proposing,
+//! sponsoring or voting.
+
+use crate::delegation::{proposal_kind, DelegationRegistry};
+use crate::{
+    Delegation, DelegationScope, GovernanceEvent, GovernanceModule, Proposal, ProposalId,
+    ProposalSponsorship, ProposalStatus, ProposalType, Vote, VoteOption,
+};
+use ark_std::rand::{rngs::StdRng, Rng, SeedableRng};
+use icn_common::{CommonError, Did};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How delegated votes are counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DelegationRule {
+    /// Follow delegation chains to the first member who voted.
+    #[default]
+    Transitive,
+    /// Only count a delegate's own vote; chains longer than one hop are
+    /// ignored.
+    SingleHop,
+    /// Only direct votes count.
+    Disabled,
+}
+
+/// Rule deciding whether a proposal that met quorum passes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SimulationMethod {
+    /// `yes >= threshold × (yes + no + abstain)`, as used by
+    /// [`GovernanceModule::close_voting_period`].
+    #[default]
+    Threshold,
+    /// `yes >= threshold × (yes + no)`; abstentions only count towards
+    /// quorum.
+    ThresholdOfCast,
+    /// Passes with at least one `yes` and no `no` votes.
+    Consensus,
+}
+
+impl FromStr for DelegationRule {
+    type Err = CommonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "transitive" => Ok(DelegationRule::Transitive),
+            "single-hop" => Ok(DelegationRule::SingleHop),
+            "disabled" => Ok(DelegationRule::Disabled),
+            other => Err(CommonError::InvalidInputError(format!(
+                "Unknown delegation rule '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+impl FromStr for SimulationMethod {
+    type Err = CommonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "threshold" => Ok(SimulationMethod::Threshold),
+            "threshold-of-cast" => Ok(SimulationMethod::ThresholdOfCast),
+            "consensus" => Ok(SimulationMethod::Consensus),
+            other => Err(CommonError::InvalidInputError(format!(
+                "Unknown voting method '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Rules a history is replayed under.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SimulationConfig {
+    pub quorum: usize,
+    pub threshold: f32,
+    pub min_sponsors: usize,
+    pub delegation: DelegationRule,
+    pub method: SimulationMethod,
+    /// Apply quorum and threshold overrides recorded on individual
+    /// proposals instead of the values above.
+    pub honor_proposal_overrides: bool,
+}
+
+impl Default for SimulationConfig {
+    /// The defaults of a fresh [`GovernanceModule`].
+    fn default() -> Self {
+        Self {
+            quorum: 1,
+            threshold: 0.5,
+            min_sponsors: 1,
+            delegation: DelegationRule::Transitive,
+            method: SimulationMethod::Threshold,
+            honor_proposal_overrides: true,
+        }
+    }
+}
+
+/// Outcome of a proposal, recorded or simulated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SimulatedOutcome {
+    /// The vote passed. Later vetoes or execution failures do not change
+    /// this.
+    Passed,
+    /// The vote failed or the proposal expired.
+    Rejected,
+    /// The proposal never gathered enough sponsors to be voted on.
+    Unsponsored,
+    /// The proposal has not been decided yet.
+    Open,
+}
+
+impl SimulatedOutcome {
+    fn recorded(status: &ProposalStatus) -> Self {
+        match status {
+            ProposalStatus::AcceptedTimelock
+            | ProposalStatus::Accepted
+            | ProposalStatus::Vetoed
+            | ProposalStatus::Executed
+            | ProposalStatus::Failed => SimulatedOutcome::Passed,
+            ProposalStatus::Rejected => SimulatedOutcome::Rejected,
+            ProposalStatus::Deliberation
+            | ProposalStatus::PendingSponsorship
+            | ProposalStatus::VotingOpen => SimulatedOutcome::Open,
+        }
+    }
+}
+
+impl fmt::Display for SimulatedOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimulatedOutcome::Passed => write!(f, "passed"),
+            SimulatedOutcome::Rejected => write!(f, "rejected"),
+            SimulatedOutcome::Unsponsored => write!(f, "unsponsored"),
+            SimulatedOutcome::Open => write!(f, "open"),
+        }
+    }
+}
+
+/// One proposal decided again under a [`SimulationConfig`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProposalSimulation {
+    pub proposal_id: ProposalId,
+    pub description: String,
+    /// Proposal kind as in [`PROPOSAL_KINDS`](crate::delegation::PROPOSAL_KINDS).
+    pub kind: String,
+    pub recorded: SimulatedOutcome,
+    pub simulated: SimulatedOutcome,
+    pub yes: usize,
+    pub no: usize,
+    pub abstain: usize,
+    pub sponsors: usize,
+}
+
+impl ProposalSimulation {
+    /// Whether the simulated outcome differs from the recorded one.
+    pub fn changed(&self) -> bool {
+        self.recorded != self.simulated
+    }
+}
+
+/// Result of replaying a history under a [`SimulationConfig`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SimulationReport {
+    pub config: SimulationConfig,
+    /// Proposals in submission order.
+    pub proposals: Vec<ProposalSimulation>,
+    /// Number of proposals whose outcome would have changed.
+    pub changed: usize,
+}
+
+impl SimulationReport {
+    /// Proposals whose outcome would have changed.
+    pub fn changed_outcomes(&self) -> impl Iterator<Item = &ProposalSimulation> {
+        self.proposals.iter().filter(|p| p.changed())
+    }
+
+    /// Self-contained HTML page summarising the report.
+    pub fn to_html(&self) -> String {
+        let c = &self.config;
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">\
+             <title>Governance simulation</title><style>\
+             body{font-family:sans-serif;margin:2em}\
+             table{border-collapse:collapse}\
+             td,th{border:1px solid #ccc;padding:4px 8px;text-align:left}\
+             tr.changed{background:#fff3cd}\
+             </style></head><body>\n<h1>Governance simulation</h1>\n",
+        );
+        html.push_str(&format!(
+            "<p>Quorum {}, threshold {}, minimum sponsors {}, delegation {:?}, \
+             method {:?}{}.</p>\n",
+            c.quorum,
+            c.threshold,
+            c.min_sponsors,
+            c.delegation,
+            c.method,
+            if c.honor_proposal_overrides {
+                ", per-proposal overrides honoured"
+            } else {
+                ""
+            }
+        ));
+        html.push_str(&format!(
+            "<p><strong>{} of {}</strong> outcomes would have changed.</p>\n",
+            self.changed,
+            self.proposals.len()
+        ));
+        html.push_str(
+            "<table>\n<tr><th>Proposal</th><th>Kind</th><th>Description</th>\
+             <th>Yes</th><th>No</th><th>Abstain</th><th>Sponsors</th>\
+             <th>Recorded</th><th>Simulated</th></tr>\n",
+        );
+        for p in &self.proposals {
+            html.push_str(&format!(
+                "<tr{}><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td>\
+                 <td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                if p.changed() {
+                    " class=\"changed\""
+                } else {
+                    ""
+                },
+                escape_html(&p.proposal_id.0),
+                escape_html(&p.kind),
+                escape_html(&p.description),
+                p.yes,
+                p.no,
+                p.abstain,
+                p.sponsors,
+                p.recorded,
+                p.simulated
+            ));
+        }
+        html.push_str("</table>\n</body></html>\n");
+        html
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[derive(Debug, Clone)]
+struct ProposalHistory {
+    proposal: Proposal,
+    status: ProposalStatus,
+}
+
+/// Replays recorded governance history under alternative rules.
+#[derive(Debug, Clone, Default)]
+pub struct GovernanceSimulator {
+    history: Vec<ProposalHistory>,
+    members: HashSet<Did>,
+    delegations: DelegationRegistry,
+}
+
+impl GovernanceSimulator {
+    /// Build a simulator from an event stream in recorded order.
+    pub fn from_events(events: impl IntoIterator<Item = GovernanceEvent>) -> Self {
+        let mut sim = Self::default();
+        let mut index: HashMap<ProposalId, usize> = HashMap::new();
+        for event in events {
+            match event {
+                GovernanceEvent::ProposalSubmitted(proposal) => {
+                    sim.members.insert(proposal.proposer.clone());
+                    sim.members
+                        .extend(proposal.sponsorship.sponsors.iter().cloned());
+                    index.insert(proposal.id.clone(), sim.history.len());
+                    sim.history.push(ProposalHistory {
+                        status: proposal.status.clone(),
+                        proposal,
+                    });
+                }
+                GovernanceEvent::VoteCast(vote) => {
+                    if let Some(&i) = index.get(&vote.proposal_id) {
+                        sim.members.insert(vote.voter.clone());
+                        sim.history[i]
+                            .proposal
+                            .votes
+                            .insert(vote.voter.clone(), vote);
+                    }
+                }
+                GovernanceEvent::StatusUpdated(id, status) => {
+                    if let Some(&i) = index.get(&id) {
+                        sim.history[i].status = status;
+                    }
+                }
+                GovernanceEvent::ProposalSponsored(id, sponsor) => {
+                    if let Some(&i) = index.get(&id) {
+                        sim.members.insert(sponsor.clone());
+                        sim.history[i].proposal.sponsorship.sponsors.insert(sponsor);
+                    }
+                }
+            }
+        }
+        sim
+    }
+
+    /// Build a simulator from everything in `store`.
+    pub fn from_event_store(
+        store: &dyn icn_eventstore::EventStore<GovernanceEvent>,
+    ) -> Result<Self, CommonError> {
+        Ok(Self::from_events(store.query(None)?))
+    }
+
+    /// Add members who may not appear in the event stream, such as members
+    /// who only ever delegated.
+    pub fn with_members(mut self, members: impl IntoIterator<Item = Did>) -> Self {
+        self.members.extend(members);
+        self
+    }
+
+    /// Use these delegations when resolving votes.
+    pub fn with_delegations(mut self, delegations: DelegationRegistry) -> Self {
+        self.delegations = delegations;
+        self
+    }
+
+    /// Number of proposals in the replayed history.
+    pub fn proposal_count(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Decide every proposal again under `config`.
+    pub fn run(&self, config: &SimulationConfig) -> SimulationReport {
+        let proposals: Vec<ProposalSimulation> = self
+            .history
+            .iter()
+            .map(|h| self.simulate(h, config))
+            .collect();
+        let changed = proposals.iter().filter(|p| p.changed()).count();
+        SimulationReport {
+            config: config.clone(),
+            proposals,
+            changed,
+        }
+    }
+
+    fn simulate(&self, history: &ProposalHistory, config: &SimulationConfig) -> ProposalSimulation {
+        let proposal = &history.proposal;
+        let recorded = SimulatedOutcome::recorded(&history.status);
+        let (yes, no, abstain) = self.tally(proposal, config.delegation);
+        let sponsors = proposal.sponsorship.sponsors.len();
+        let simulated = if recorded == SimulatedOutcome::Open {
+            SimulatedOutcome::Open
+        } else if sponsors < config.min_sponsors {
+            SimulatedOutcome::Unsponsored
+        } else {
+            decide(proposal, config, yes, no, abstain)
+        };
+        ProposalSimulation {
+            proposal_id: proposal.id.clone(),
+            description: proposal.description.clone(),
+            kind: proposal_kind(&proposal.proposal_type).to_string(),
+            recorded,
+            simulated,
+            yes,
+            no,
+            abstain,
+            sponsors,
+        }
+    }
+
+    fn tally(&self, proposal: &Proposal, rule: DelegationRule) -> (usize, usize, usize) {
+        let (mut yes, mut no, mut abstain) = (0, 0, 0);
+        let voters = self.members.iter().chain(
+            proposal
+                .votes
+                .keys()
+                .filter(|voter| !self.members.contains(*voter)),
+        );
+        for member in voters {
+            let option = match rule {
+                DelegationRule::Transitive => self.delegations.resolve(member, proposal).option(),
+                DelegationRule::SingleHop => {
+                    proposal.votes.get(member).map(|v| v.option).or_else(|| {
+                        self.delegations
+                            .applicable(member, proposal)
+                            .and_then(|d| proposal.votes.get(&d.delegate))
+                            .map(|v| v.option)
+                    })
+                }
+                DelegationRule::Disabled => proposal.votes.get(member).map(|v| v.option),
+            };
+            match option {
+                Some(VoteOption::Yes) => yes += 1,
+                Some(VoteOption::No) => no += 1,
+                Some(VoteOption::Abstain) => abstain += 1,
+                None => {}
+            }
+        }
+        (yes, no, abstain)
+    }
+}
+
+fn decide(
+    proposal: &Proposal,
+    config: &SimulationConfig,
+    yes: usize,
+    no: usize,
+    abstain: usize,
+) -> SimulatedOutcome {
+    let (quorum, threshold) = if config.honor_proposal_overrides {
+        (
+            proposal.quorum.unwrap_or(config.quorum),
+            proposal.threshold.unwrap_or(config.threshold),
+        )
+    } else {
+        (config.quorum, config.threshold)
+    };
+    let total = yes + no + abstain;
+    let passed = total >= quorum
+        && match config.method {
+            SimulationMethod::Threshold => yes as f32 >= total as f32 * threshold,
+            SimulationMethod::ThresholdOfCast => {
+                yes + no > 0 && yes as f32 >= (yes + no) as f32 * threshold
+            }
+            SimulationMethod::Consensus => yes > 0 && no == 0,
+        };
+    if passed {
+        SimulatedOutcome::Passed
+    } else {
+        SimulatedOutcome::Rejected
+    }
+}
+
+/// Parameters for generating a synthetic governance history.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SyntheticPopulation {
+    pub members: usize,
+    pub proposals: usize,
+    /// Probability that a member votes directly on a proposal.
+    pub turnout: f64,
+    /// Average probability that a direct vote is `Yes`; each proposal draws
+    /// its own level of support around it.
+    pub support: f64,
+    /// Probability that a member delegates their vote to another member.
+    pub delegation_rate: f64,
+    /// Sponsors gathered by each proposal, including the proposer.
+    pub sponsors: usize,
+    pub seed: u64,
+}
+
+impl Default for SyntheticPopulation {
+    fn default() -> Self {
+        Self {
+            members: 50,
+            proposals: 20,
+            turnout: 0.6,
+            support: 0.55,
+            delegation_rate: 0.2,
+            sponsors: 1,
+            seed: 0,
+        }
+    }
+}
+
+impl SyntheticPopulation {
+    /// Generate a history whose recorded outcomes follow `rules`.
+    ///
+    /// The same parameters and seed always produce the same history.
+    pub fn generate(&self, rules: &SimulationConfig) -> GovernanceSimulator {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let members: Vec<Did> = (0..self.members)
+            .map(|i| Did::new("key", &format!("synthetic{i}")))
+            .collect();
+
+        let mut delegations = DelegationRegistry::new();
+        if members.len() > 1 {
+            for (i, member) in members.iter().enumerate() {
+                if !rng.gen_bool(self.delegation_rate.clamp(0.0, 1.0)) {
+                    continue;
+                }
+                let mut target = rng.gen_range(0..members.len() - 1);
+                if target >= i {
+                    target += 1;
+                }
+                // Edges that would close a loop are rejected; the member
+                // simply does not delegate.
+                let _ = delegations.delegate(Delegation {
+                    delegator: member.clone(),
+                    delegate: members[target].clone(),
+                    scope: DelegationScope::All,
+                    created_at: 0,
+                    expires_at: None,
+                });
+            }
+        }
+
+        let mut events = Vec::new();
+        for n in 0..self.proposals {
+            if members.is_empty() {
+                break;
+            }
+            let created_at = n as u64 * 86_400;
+            let proposer = &members[rng.gen_range(0..members.len())];
+            let mut sponsorship = ProposalSponsorship::new();
+            sponsorship.add_sponsor(proposer.clone(), created_at, 1);
+            while sponsorship.sponsors.len() < self.sponsors.min(members.len()) {
+                let sponsor = &members[rng.gen_range(0..members.len())];
+                sponsorship.add_sponsor(sponsor.clone(), created_at, 1);
+            }
+            let proposal = Proposal {
+                id: ProposalId(format!("synthetic-{n}")),
+                proposer: proposer.clone(),
+                proposal_type: ProposalType::GenericText(format!("synthetic proposal {n}")),
+                description: format!("Synthetic proposal {n}"),
+                created_at,
+                voting_deadline: created_at + 86_400,
+                status: ProposalStatus::VotingOpen,
+                votes: HashMap::new(),
+                quorum: None,
+                threshold: None,
+                content_cid: None,
+                sponsorship,
+                timelock_delay: None,
+                veto: None,
+                accepted_at: None,
+            };
+            let id = proposal.id.clone();
+            events.push(GovernanceEvent::ProposalSubmitted(proposal));
+
+            let support = (self.support + rng.gen_range(-0.25..0.25)).clamp(0.0, 1.0);
+            for voter in &members {
+                if !rng.gen_bool(self.turnout.clamp(0.0, 1.0)) {
+                    continue;
+                }
+                let option = if rng.gen_bool(support) {
+                    VoteOption::Yes
+                } else if rng.gen_bool(0.9) {
+                    VoteOption::No
+                } else {
+                    VoteOption::Abstain
+                };
+                events.push(GovernanceEvent::VoteCast(Vote {
+                    voter: voter.clone(),
+                    proposal_id: id.clone(),
+                    option,
+                    voted_at: created_at + 1,
+                }));
+            }
+        }
+
+        // Record the outcomes the generated votes produce under `rules`.
+        let mut sim = GovernanceSimulator::from_events(events)
+            .with_members(members)
+            .with_delegations(delegations);
+        for i in 0..sim.history.len() {
+            sim.history[i].status = ProposalStatus::Rejected;
+            let outcome = sim.simulate(&sim.history[i], rules).simulated;
+            sim.history[i].status = match outcome {
+                SimulatedOutcome::Passed => ProposalStatus::Accepted,
+                SimulatedOutcome::Unsponsored => ProposalStatus::PendingSponsorship,
+                _ => ProposalStatus::Rejected,
+            };
+        }
+        sim
+    }
+}
+
+impl GovernanceModule {
+    /// Simulator over this module's recorded events, current members and
+    /// delegations. Fails if the module has no event store.
+    pub fn simulator(&self) -> Result<GovernanceSimulator, CommonError> {
+        let store = self.event_store().ok_or_else(|| {
+            CommonError::InvalidInputError(
+                "Governance module has no event store to replay".to_string(),
+            )
+        })?;
+        let events = store
+            .lock()
+            .map_err(|_| CommonError::InternalError("Event store lock poisoned".to_string()))?
+            .query(None)?;
+        Ok(GovernanceSimulator::from_events(events)
+            .with_members(self.members.iter().cloned())
+            .with_delegations(self.delegations.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProposalSubmission;
+    use icn_common::FixedTimeProvider;
+    use icn_eventstore::MemoryEventStore;
+
+    fn did(name: &str) -> Did {
+        Did::from_str(&format!("did:example:{name}")).unwrap()
+    }
+
+    #[test]
+    fn replay_reports_outcomes_changed_by_new_rules() {
+        let mut gov = GovernanceModule::with_event_store(Box::new(MemoryEventStore::new()));
+        for name in ["alice", "bob", "carol", "dave"] {
+            gov.add_member(did(name));
+        }
+        gov.delegate_vote(did("dave"), did("alice")).unwrap();
+        let tp = FixedTimeProvider::new(1_000);
+
+        let submit = |gov: &mut GovernanceModule, text: &str, votes: &[(&str, VoteOption)]| {
+            let pid = gov
+                .submit_proposal(
+                    ProposalSubmission {
+                        proposer: did("alice"),
+                        proposal_type: ProposalType::GenericText(text.into()),
+                        description: text.into(),
+                        duration_secs: 60,
+                        quorum: None,
+                        threshold: None,
+                        content_cid: None,
+                        timelock_delay: None,
+                    },
+                    &tp,
+                )
+                .unwrap();
+            gov.open_voting(&pid).unwrap();
+            for (voter, option) in votes {
+                gov.cast_vote(did(voter), &pid, *option, &tp).unwrap();
+            }
+            gov.close_voting_period(&pid, &tp).unwrap();
+            pid
+        };
+        // 2 yes (alice + dave via delegation), 1 no, 1 abstain: passes at 0.5.
+        let contested = submit(
+            &mut gov,
+            "contested",
+            &[
+                ("alice", VoteOption::Yes),
+                ("bob", VoteOption::No),
+                ("carol", VoteOption::Abstain),
+            ],
+        );
+        // 3 yes out of 4.
+        let popular = submit(
+            &mut gov,
+            "popular",
+            &[
+                ("alice", VoteOption::Yes),
+                ("bob", VoteOption::Yes),
+                ("carol", VoteOption::No),
+            ],
+        );
+
+        let sim = gov.simulator().unwrap();
+        let baseline = sim.run(&SimulationConfig::default());
+        assert_eq!(baseline.changed, 0);
+        assert!(baseline
+            .proposals
+            .iter()
+            .all(|p| p.recorded == SimulatedOutcome::Passed));
+
+        let no_delegation = sim.run(&SimulationConfig {
+            delegation: DelegationRule::Disabled,
+            threshold: 0.6,
+            ..SimulationConfig::default()
+        });
+        let changed: Vec<_> = no_delegation
+            .changed_outcomes()
+            .map(|p| p.proposal_id.clone())
+            .collect();
+        assert_eq!(changed, vec![contested.clone()]);
+
+        let consensus = sim.run(&SimulationConfig {
+            method: SimulationMethod::Consensus,
+            ..SimulationConfig::default()
+        });
+        assert_eq!(consensus.changed, 2);
+
+        let sponsors = sim.run(&SimulationConfig {
+            min_sponsors: 2,
+            ..SimulationConfig::default()
+        });
+        assert!(sponsors
+            .proposals
+            .iter()
+            .all(|p| p.simulated == SimulatedOutcome::Unsponsored));
+        assert!(sponsors.to_html().contains(&popular.0));
+    }
+
+    #[test]
+    fn synthetic_population_is_reproducible() {
+        let population = SyntheticPopulation {
+            seed: 42,
+            ..SyntheticPopulation::default()
+        };
+        let rules = SimulationConfig::default();
+        let first = population.generate(&rules).run(&rules);
+        let second = population.generate(&rules).run(&rules);
+        assert_eq!(first, second);
+        assert_eq!(first.proposals.len(), population.proposals);
+        assert_eq!(first.changed, 0);
+
+        let strict = population.generate(&rules).run(&SimulationConfig {
+            threshold: 0.9,
+            ..rules.clone()
+        });
+        assert!(strict
+            .changed_outcomes()
+            .all(|p| p.recorded == SimulatedOutcome::Passed
+                && p.simulated == SimulatedOutcome::Rejected));
+    }
+}
@@ -0,0 +1,1031 @@
+//! Sortition: verifiable random selection of committees and stewards
+//!
+//! Rotating governance bodies (see the `ROTATING_STEWARDS`, `ROTATING_COUNCIL`
+//! and `ROTATING_ASSEMBLY` templates in `icn-templates`) need a fair way to
+//! pick who serves. This module draws members from an eligible pool using
+//! randomness that every observer can recompute.
+//!
+//! # Randomness Sources
+//!
+//! - **Commit-reveal beacon** ([`CommitRevealBeacon`]): federation nodes first
+//!   anchor a signed [`BeaconCommitment`] to `sha256(participant || secret)` in
+//!   the DAG and later reveal `secret`. Each reveal names the CID of its
+//!   anchored commitment, and the seed is only accepted when every revealer is
+//!   an expected participant whose commitment is in the DAG
+//!   ([`RandomnessTrust`]). The seed hashes all valid reveals in DID order, so a
+//!   single honest participant is enough to make it unpredictable. Participants
+//!   that commit but never reveal are listed in the result so they can be
+//!   sanctioned.
+//! - **Checkpoint VRF** ([`RandomnessSource::CheckpointVrf`]): a designated
+//!   signer produces an Ed25519 signature over the latest DAG checkpoint. Ed25519
+//!   signatures are deterministic, so the hash of the signature acts as the VRF
+//!   output and the signature itself is the proof. The seed is only accepted
+//!   from a configured signer over a checkpoint whose DAG root is in the local
+//!   DAG ([`RandomnessTrust`]). A signer could still bias the draw by
+//!   withholding, so this source suits low-stakes rotations.
+//!
+//! # Selection
+//!
+//! Each eligible DID is ranked by `sha256(domain || seed || did)` and the
+//! lowest scores take the seats. Ranking is independent of the order in which
+//! candidates were supplied. [`SortitionDraw::verify`] replays a draw against a
+//! pool the verifier rebuilt itself, e.g. with
+//! [`SortitionService::verify_draw`], so a drawer cannot shape the outcome by
+//! reporting a different pool.
+
+use crate::quadratic::SybilGuard;
+use crate::voting::{BallotAnchoringService, EligibilityRules, FederationRegistry, VotingError};
+use icn_common::{
+    Cid, CommonError, DagBlock, Did, DidDocument, Signable, SignatureBytes, TimeProvider,
+};
+use icn_dag::{Checkpoint, StorageService};
+use icn_identity::{verify_signature, verifying_key_from_did_key, EdSignature, SIGNATURE_LENGTH};
+use icn_reputation::ReputationStore;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+const COMMIT_DOMAIN: &[u8] = b"icn-sortition-commit-v1";
+const BEACON_DOMAIN: &[u8] = b"icn-sortition-beacon-v1";
+const VRF_DOMAIN: &[u8] = b"icn-sortition-vrf-v1";
+const RANK_DOMAIN: &[u8] = b"icn-sortition-rank-v1";
+
+/// Compute the commitment a beacon participant publishes for `secret`.
+pub fn beacon_commitment(participant: &Did, secret: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(COMMIT_DOMAIN);
+    hasher.update(participant.to_string().as_bytes());
+    hasher.update(secret);
+    hasher.finalize().into()
+}
+
+/// Build the message a checkpoint VRF signer must sign.
+pub fn checkpoint_vrf_input(
+    checkpoint_id: &str,
+    epoch: u64,
+    state_root: &Cid,
+    dag_root: &Cid,
+) -> Vec<u8> {
+    let mut input = Vec::new();
+    input.extend_from_slice(VRF_DOMAIN);
+    input.extend_from_slice(checkpoint_id.as_bytes());
+    input.extend_from_slice(&epoch.to_be_bytes());
+    input.extend_from_slice(state_root.to_string().as_bytes());
+    input.extend_from_slice(dag_root.to_string().as_bytes());
+    input
+}
+
+/// A beacon participant's commitment, signed by its `did:key` and anchored in
+/// the DAG before the reveal phase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BeaconCommitment {
+    pub round_id: String,
+    pub participant: Did,
+    pub commitment: [u8; 32],
+    pub signature: Option<SignatureBytes>,
+}
+
+impl Signable for BeaconCommitment {
+    fn to_signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        let mut bytes = COMMIT_DOMAIN.to_vec();
+        for field in [
+            self.round_id.as_bytes(),
+            self.participant.to_string().as_bytes(),
+        ] {
+            bytes.extend_from_slice(&(field.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(field);
+        }
+        bytes.extend_from_slice(&self.commitment);
+        Ok(bytes)
+    }
+}
+
+impl BeaconCommitment {
+    /// Unsigned commitment by `participant` to `secret`.
+    pub fn new(round_id: impl Into<String>, participant: Did, secret: &[u8]) -> Self {
+        Self {
+            round_id: round_id.into(),
+            commitment: beacon_commitment(&participant, secret),
+            participant,
+            signature: None,
+        }
+    }
+
+    /// Check the signature against the participant's `did:key`.
+    pub fn verify_signature(&self) -> Result<(), CommonError> {
+        let signature = self.signature.as_ref().ok_or_else(|| {
+            CommonError::CryptoError(format!(
+                "Beacon commitment from {} is not signed",
+                self.participant
+            ))
+        })?;
+        self.verify(signature, &verifying_key_from_did_key(&self.participant)?)
+    }
+
+    /// DAG block anchoring this commitment, authored by the participant.
+    pub fn to_dag_block(&self, timestamp: u64) -> Result<DagBlock, CommonError> {
+        let data =
+            serde_json::to_vec(self).map_err(|e| CommonError::SerializationError(e.to_string()))?;
+        let cid = icn_common::compute_merkle_cid(
+            0x71,
+            &data,
+            &[],
+            timestamp,
+            &self.participant,
+            &None,
+            &None,
+        );
+        Ok(DagBlock {
+            cid,
+            data,
+            links: vec![],
+            timestamp,
+            author_did: self.participant.clone(),
+            signature: None,
+            scope: None,
+        })
+    }
+}
+
+/// A revealed beacon contribution together with the CID of its anchored
+/// [`BeaconCommitment`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BeaconReveal {
+    pub participant: Did,
+    pub commitment_cid: Cid,
+    pub secret: Vec<u8>,
+}
+
+/// Trust anchors a verifier needs to accept a [`RandomnessSource`]: the
+/// expected beacon participants, the configured checkpoint signers and the
+/// DAG holding beacon commitments and checkpoints.
+#[derive(Clone)]
+pub struct RandomnessTrust {
+    beacon_participants: Vec<Did>,
+    checkpoint_signers: Vec<Did>,
+    dag: Arc<dyn StorageService<DagBlock>>,
+}
+
+impl RandomnessTrust {
+    /// Trust nothing yet; commitments and checkpoints are looked up in `dag`.
+    pub fn new(dag: Arc<dyn StorageService<DagBlock>>) -> Self {
+        Self {
+            beacon_participants: Vec::new(),
+            checkpoint_signers: Vec::new(),
+            dag,
+        }
+    }
+
+    /// Accept commit-reveal seeds whose reveals all come from `participants`.
+    pub fn with_beacon_participants(mut self, participants: Vec<Did>) -> Self {
+        self.beacon_participants = participants;
+        self
+    }
+
+    /// Accept checkpoint VRF evaluations by `signers`.
+    pub fn with_checkpoint_signers(mut self, signers: Vec<Did>) -> Self {
+        self.checkpoint_signers = signers;
+        self
+    }
+
+    fn check_reveal(&self, round_id: &str, reveal: &BeaconReveal) -> Result<(), CommonError> {
+        if !self.beacon_participants.contains(&reveal.participant) {
+            return Err(CommonError::PermissionDenied(format!(
+                "{} is not an expected beacon participant",
+                reveal.participant
+            )));
+        }
+        let block = self.dag.get(&reveal.commitment_cid)?.ok_or_else(|| {
+            CommonError::ResourceNotFound(format!(
+                "Beacon commitment {} is not in the DAG",
+                reveal.commitment_cid
+            ))
+        })?;
+        let commitment: BeaconCommitment = serde_json::from_slice(&block.data)
+            .map_err(|e| CommonError::DeserializationError(e.to_string()))?;
+        if commitment.round_id != round_id || commitment.participant != reveal.participant {
+            return Err(CommonError::InvalidInputError(format!(
+                "Commitment {} does not belong to {} in round {}",
+                reveal.commitment_cid, reveal.participant, round_id
+            )));
+        }
+        commitment.verify_signature()?;
+        if beacon_commitment(&reveal.participant, &reveal.secret) != commitment.commitment {
+            return Err(CommonError::CryptoError(format!(
+                "Reveal from {} does not match its commitment",
+                reveal.participant
+            )));
+        }
+        Ok(())
+    }
+
+    fn check_checkpoint(&self, signer: &Did, dag_root: &Cid) -> Result<(), CommonError> {
+        if !self.checkpoint_signers.contains(signer) {
+            return Err(CommonError::PermissionDenied(format!(
+                "{} is not a configured checkpoint signer",
+                signer
+            )));
+        }
+        if self.dag.get(dag_root)?.is_none() {
+            return Err(CommonError::ResourceNotFound(format!(
+                "Checkpoint DAG root {} is not in the DAG",
+                dag_root
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Verifiable origin of the seed used for a sortition draw.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RandomnessSource {
+    /// Seed produced by a finalized [`CommitRevealBeacon`] round.
+    CommitReveal {
+        round_id: String,
+        reveals: Vec<BeaconReveal>,
+        /// Participants that committed but did not reveal.
+        missing: Vec<Did>,
+    },
+    /// Seed derived from a signature over a DAG checkpoint.
+    CheckpointVrf {
+        checkpoint_id: String,
+        epoch: u64,
+        state_root: Cid,
+        dag_root: Cid,
+        /// `did:key` of the signer.
+        signer: Did,
+        signature: Vec<u8>,
+    },
+}
+
+impl RandomnessSource {
+    /// Evaluate the checkpoint VRF for `checkpoint` with `signing_key`.
+    pub fn from_checkpoint(
+        checkpoint: &Checkpoint,
+        signer: Did,
+        signing_key: &icn_identity::SigningKey,
+    ) -> Self {
+        let input = checkpoint_vrf_input(
+            &checkpoint.checkpoint_id.0,
+            checkpoint.epoch,
+            &checkpoint.state_root,
+            &checkpoint.dag_root,
+        );
+        let signature = icn_identity::sign_message(signing_key, &input);
+        RandomnessSource::CheckpointVrf {
+            checkpoint_id: checkpoint.checkpoint_id.0.clone(),
+            epoch: checkpoint.epoch,
+            state_root: checkpoint.state_root.clone(),
+            dag_root: checkpoint.dag_root.clone(),
+            signer,
+            signature: signature.to_bytes().to_vec(),
+        }
+    }
+
+    /// Verify the source against `trust` and derive the 32-byte seed.
+    pub fn seed(&self, trust: &RandomnessTrust) -> Result<[u8; 32], CommonError> {
+        match self {
+            RandomnessSource::CommitReveal {
+                round_id, reveals, ..
+            } => {
+                if reveals.is_empty() {
+                    return Err(CommonError::InvalidInputError(
+                        "Beacon round has no reveals".to_string(),
+                    ));
+                }
+                let mut ordered: BTreeMap<String, &BeaconReveal> = BTreeMap::new();
+                for reveal in reveals {
+                    trust.check_reveal(round_id, reveal)?;
+                    if ordered
+                        .insert(reveal.participant.to_string(), reveal)
+                        .is_some()
+                    {
+                        return Err(CommonError::InvalidInputError(format!(
+                            "Duplicate reveal from {}",
+                            reveal.participant
+                        )));
+                    }
+                }
+                let mut hasher = Sha256::new();
+                hasher.update(BEACON_DOMAIN);
+                hasher.update(round_id.as_bytes());
+                for (participant, reveal) in ordered {
+                    hasher.update(participant.as_bytes());
+                    hasher.update(Sha256::digest(&reveal.secret));
+                }
+                Ok(hasher.finalize().into())
+            }
+            RandomnessSource::CheckpointVrf {
+                checkpoint_id,
+                epoch,
+                state_root,
+                dag_root,
+                signer,
+                signature,
+            } => {
+                trust.check_checkpoint(signer, dag_root)?;
+                let key = verifying_key_from_did_key(signer)?;
+                let bytes: [u8; SIGNATURE_LENGTH] =
+                    signature.as_slice().try_into().map_err(|_| {
+                        CommonError::CryptoError("Invalid VRF signature length".to_string())
+                    })?;
+                let sig = EdSignature::from_bytes(&bytes);
+                let input = checkpoint_vrf_input(checkpoint_id, *epoch, state_root, dag_root);
+                if !verify_signature(&key, &input, &sig) {
+                    return Err(CommonError::CryptoError(format!(
+                        "VRF proof from {} does not verify",
+                        signer
+                    )));
+                }
+                Ok(Sha256::digest(signature).into())
+            }
+        }
+    }
+}
+
+/// Commit-reveal randomness beacon run among federation nodes.
+#[derive(Debug, Clone)]
+pub struct CommitRevealBeacon {
+    round_id: String,
+    participants: Vec<Did>,
+    min_reveals: usize,
+    commitments: HashMap<Did, ([u8; 32], Cid)>,
+    reveals: HashMap<Did, Vec<u8>>,
+    commit_phase_closed: bool,
+}
+
+impl CommitRevealBeacon {
+    /// Start a beacon round among `participants`, requiring at least
+    /// `min_reveals` valid reveals before it can be finalized.
+    pub fn new(round_id: impl Into<String>, participants: Vec<Did>, min_reveals: usize) -> Self {
+        Self {
+            round_id: round_id.into(),
+            participants,
+            min_reveals: min_reveals.max(1),
+            commitments: HashMap::new(),
+            reveals: HashMap::new(),
+            commit_phase_closed: false,
+        }
+    }
+
+    /// Identifier of this round.
+    pub fn round_id(&self) -> &str {
+        &self.round_id
+    }
+
+    /// Record a participant's signed commitment, anchored in the DAG at `cid`.
+    pub fn commit(&mut self, commitment: &BeaconCommitment, cid: Cid) -> Result<(), CommonError> {
+        let participant = &commitment.participant;
+        if commitment.round_id != self.round_id {
+            return Err(CommonError::InvalidInputError(format!(
+                "Commitment is for round {}, not {}",
+                commitment.round_id, self.round_id
+            )));
+        }
+        if self.commit_phase_closed {
+            return Err(CommonError::PolicyDenied(format!(
+                "Commit phase of beacon round {} is closed",
+                self.round_id
+            )));
+        }
+        if !self.participants.contains(participant) {
+            return Err(CommonError::PermissionDenied(format!(
+                "{} is not a participant in beacon round {}",
+                participant, self.round_id
+            )));
+        }
+        if self.commitments.contains_key(participant) {
+            return Err(CommonError::InvalidInputError(format!(
+                "{} already committed",
+                participant
+            )));
+        }
+        commitment.verify_signature()?;
+        self.commitments
+            .insert(participant.clone(), (commitment.commitment, cid));
+        Ok(())
+    }
+
+    /// Close the commit phase; no further commitments are accepted.
+    pub fn close_commits(&mut self) {
+        self.commit_phase_closed = true;
+    }
+
+    /// Reveal the secret behind an earlier commitment.
+    pub fn reveal(&mut self, participant: &Did, secret: Vec<u8>) -> Result<(), CommonError> {
+        if !self.commit_phase_closed {
+            return Err(CommonError::PolicyDenied(
+                "Reveals are only accepted after the commit phase closes".to_string(),
+            ));
+        }
+        let (commitment, _) = self.commitments.get(participant).ok_or_else(|| {
+            CommonError::ResourceNotFound(format!("No commitment from {}", participant))
+        })?;
+        if beacon_commitment(participant, &secret) != *commitment {
+            return Err(CommonError::CryptoError(format!(
+                "Reveal from {} does not match its commitment",
+                participant
+            )));
+        }
+        self.reveals.insert(participant.clone(), secret);
+        Ok(())
+    }
+
+    /// Finish the round and return a verifiable randomness source.
+    pub fn finalize(&self) -> Result<RandomnessSource, CommonError> {
+        if self.reveals.len() < self.min_reveals {
+            return Err(CommonError::PolicyDenied(format!(
+                "Beacon round {} has {} reveals, {} required",
+                self.round_id,
+                self.reveals.len(),
+                self.min_reveals
+            )));
+        }
+        let mut reveals: Vec<BeaconReveal> = self
+            .reveals
+            .iter()
+            .map(|(participant, secret)| BeaconReveal {
+                participant: participant.clone(),
+                commitment_cid: self.commitments[participant].1.clone(),
+                secret: secret.clone(),
+            })
+            .collect();
+        reveals.sort_by_key(|r| r.participant.to_string());
+        let mut missing: Vec<Did> = self
+            .commitments
+            .keys()
+            .filter(|did| !self.reveals.contains_key(*did))
+            .cloned()
+            .collect();
+        missing.sort_by_key(|did| did.to_string());
+        Ok(RandomnessSource::CommitReveal {
+            round_id: self.round_id.clone(),
+            reveals,
+            missing,
+        })
+    }
+}
+
+/// Rank `pool` with `seed` and return the first `seats` members.
+pub fn select_members(seed: &[u8; 32], pool: &[Did], seats: usize) -> Vec<Did> {
+    let mut ranked: Vec<([u8; 32], &Did)> = pool
+        .iter()
+        .map(|did| {
+            let mut hasher = Sha256::new();
+            hasher.update(RANK_DOMAIN);
+            hasher.update(seed);
+            hasher.update(did.to_string().as_bytes());
+            (hasher.finalize().into(), did)
+        })
+        .collect();
+    ranked.sort_by_key(|(score, _)| *score);
+    ranked
+        .into_iter()
+        .take(seats)
+        .map(|(_, did)| did.clone())
+        .collect()
+}
+
+/// Record of a completed draw, suitable for anchoring in the DAG.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SortitionDraw {
+    pub draw_id: String,
+    pub seats: usize,
+    /// Eligible members the draw was made from, sorted by DID.
+    pub pool: Vec<Did>,
+    pub source: RandomnessSource,
+    /// Selected members in rank order.
+    pub selected: Vec<Did>,
+    pub timestamp: u64,
+}
+
+impl SortitionDraw {
+    /// Check that the draw was made from `pool`, rebuilt by the verifier, and
+    /// recompute the selection from the source.
+    pub fn verify(&self, pool: &[Did], trust: &RandomnessTrust) -> Result<bool, CommonError> {
+        let mut expected = pool.to_vec();
+        expected.sort_by_key(|did| did.to_string());
+        expected.dedup();
+        if expected != self.pool {
+            return Ok(false);
+        }
+        let seed = self.source.seed(trust)?;
+        Ok(select_members(&seed, &self.pool, self.seats) == self.selected)
+    }
+
+    /// Seed-derived rotation offset for the rotating CCL templates, which take
+    /// a `cycle` index into their member array.
+    pub fn rotation_cycle(&self, trust: &RandomnessTrust) -> Result<u64, CommonError> {
+        let seed = self.source.seed(trust)?;
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&seed[..8]);
+        Ok(u64::from_be_bytes(bytes))
+    }
+}
+
+/// Draws committee members from the eligible subset of a candidate pool.
+pub struct SortitionService {
+    rules: EligibilityRules,
+    reputation_store: Option<Arc<dyn ReputationStore>>,
+    federation_registry: Option<Arc<dyn FederationRegistry + Send + Sync>>,
+    sybil_guard: Option<Arc<dyn SybilGuard>>,
+    randomness_trust: Option<RandomnessTrust>,
+}
+
+impl SortitionService {
+    /// Create a service applying `rules` to every candidate.
+    pub fn new(rules: EligibilityRules) -> Self {
+        Self {
+            rules,
+            reputation_store: None,
+            federation_registry: None,
+            sybil_guard: None,
+            randomness_trust: None,
+        }
+    }
+
+    /// Use `store` to evaluate `min_reputation` requirements.
+    pub fn with_reputation_store(mut self, store: Arc<dyn ReputationStore>) -> Self {
+        self.reputation_store = Some(store);
+        self
+    }
+
+    /// Use `registry` to evaluate federation membership requirements.
+    pub fn with_federation_registry(
+        mut self,
+        registry: Arc<dyn FederationRegistry + Send + Sync>,
+    ) -> Self {
+        self.federation_registry = Some(registry);
+        self
+    }
+
+    /// Additionally require candidates to pass `guard`, e.g. a
+    /// [`CredentialSybilGuard`](crate::CredentialSybilGuard).
+    pub fn with_sybil_guard(mut self, guard: Arc<dyn SybilGuard>) -> Self {
+        self.sybil_guard = Some(guard);
+        self
+    }
+
+    /// Accept randomness sources vouched for by `trust`. Draws fail until
+    /// this is set.
+    pub fn with_randomness_trust(mut self, trust: RandomnessTrust) -> Self {
+        self.randomness_trust = Some(trust);
+        self
+    }
+
+    fn randomness_trust(&self) -> Result<&RandomnessTrust, CommonError> {
+        self.randomness_trust.as_ref().ok_or_else(|| {
+            CommonError::PolicyDenied("No randomness trust configured for sortition".to_string())
+        })
+    }
+
+    /// Filter `candidates` down to the eligible pool, sorted and deduplicated.
+    pub fn eligible_pool(&self, candidates: &[DidDocument]) -> Vec<Did> {
+        let mut pool: Vec<Did> = candidates
+            .iter()
+            .filter(|doc| {
+                self.rules
+                    .validate_voter_with_sybil_guard(
+                        doc,
+                        self.reputation_store.as_deref(),
+                        None,
+                        self.federation_registry
+                            .as_deref()
+                            .map(|r| r as &dyn FederationRegistry),
+                        self.sybil_guard.as_deref(),
+                    )
+                    .unwrap_or(false)
+            })
+            .map(|doc| doc.id.clone())
+            .collect();
+        pool.sort_by_key(|did| did.to_string());
+        pool.dedup();
+        pool
+    }
+
+    /// Draw `seats` members from the eligible candidates.
+    pub fn draw(
+        &self,
+        draw_id: impl Into<String>,
+        candidates: &[DidDocument],
+        seats: usize,
+        source: RandomnessSource,
+        time_provider: &dyn TimeProvider,
+    ) -> Result<SortitionDraw, CommonError> {
+        if seats == 0 {
+            return Err(CommonError::InvalidInputError(
+                "A draw needs at least one seat".to_string(),
+            ));
+        }
+        let pool = self.eligible_pool(candidates);
+        if pool.len() < seats {
+            return Err(CommonError::PolicyDenied(format!(
+                "Only {} eligible members for {} seats",
+                pool.len(),
+                seats
+            )));
+        }
+        let seed = source.seed(self.randomness_trust()?)?;
+        let selected = select_members(&seed, &pool, seats);
+        Ok(SortitionDraw {
+            draw_id: draw_id.into(),
+            seats,
+            pool,
+            source,
+            selected,
+            timestamp: time_provider.unix_seconds(),
+        })
+    }
+
+    /// Replay `draw` over the pool this service derives from `candidates`.
+    pub fn verify_draw(
+        &self,
+        draw: &SortitionDraw,
+        candidates: &[DidDocument],
+    ) -> Result<bool, CommonError> {
+        draw.verify(&self.eligible_pool(candidates), self.randomness_trust()?)
+    }
+}
+
+impl<S: StorageService<DagBlock>> BallotAnchoringService<S> {
+    /// Anchor a sortition draw so that it can be re-verified later.
+    pub fn anchor_sortition_draw(
+        &mut self,
+        draw: &SortitionDraw,
+        author: &Did,
+    ) -> Result<Cid, VotingError> {
+        use icn_common::compute_merkle_cid;
+
+        let data = serde_json::to_vec(draw)
+            .map_err(|e| VotingError::InvalidBallot(format!("Failed to serialize draw: {}", e)))?;
+        let cid = compute_merkle_cid(0x71, &data, &[], draw.timestamp, author, &None, &None);
+        let block = DagBlock {
+            cid: cid.clone(),
+            data,
+            links: vec![],
+            timestamp: draw.timestamp,
+            author_did: author.clone(),
+            signature: None,
+            scope: None,
+        };
+        self.storage.put(&block).map_err(|e| {
+            VotingError::InvalidBallot(format!("Failed to store draw in DAG: {}", e))
+        })?;
+        Ok(cid)
+    }
+
+    /// Anchor a signed beacon commitment; the returned CID goes into the
+    /// participant's [`BeaconReveal`].
+    pub fn anchor_beacon_commitment(
+        &mut self,
+        commitment: &BeaconCommitment,
+        timestamp: u64,
+    ) -> Result<Cid, VotingError> {
+        commitment
+            .verify_signature()
+            .map_err(|_| VotingError::InvalidSignature)?;
+        let block = commitment
+            .to_dag_block(timestamp)
+            .map_err(|e| VotingError::InvalidBallot(e.to_string()))?;
+        self.storage.put(&block).map_err(|e| {
+            VotingError::InvalidBallot(format!("Failed to store commitment in DAG: {}", e))
+        })?;
+        Ok(block.cid)
+    }
+
+    /// Retrieve a previously anchored sortition draw.
+    pub fn retrieve_sortition_draw(&self, cid: &Cid) -> Result<Option<SortitionDraw>, VotingError> {
+        let block = self.storage.get(cid).map_err(|e| {
+            VotingError::InvalidBallot(format!("Failed to retrieve from DAG: {}", e))
+        })?;
+        match block {
+            Some(block) => serde_json::from_slice(&block.data).map(Some).map_err(|e| {
+                VotingError::InvalidBallot(format!("Failed to deserialize draw: {}", e))
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Load an anchored draw and replay it with `service` over `candidates`.
+    pub fn verify_anchored_draw(
+        &self,
+        cid: &Cid,
+        service: &SortitionService,
+        candidates: &[DidDocument],
+    ) -> Result<bool, VotingError> {
+        let draw = self
+            .retrieve_sortition_draw(cid)?
+            .ok_or_else(|| VotingError::InvalidBallot(format!("Draw {} not found in DAG", cid)))?;
+        service
+            .verify_draw(&draw, candidates)
+            .map_err(|e| VotingError::InvalidBallot(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::federation_governance::{
+        FederationGovernanceEngine, TrustCommitteeMember, TrustCommitteeRole,
+    };
+    use icn_common::FixedTimeProvider;
+    use icn_dag::InMemoryDagStore;
+    use icn_identity::{did_key_from_verifying_key, generate_ed25519_keypair, TrustPolicyEngine};
+    use std::collections::HashSet;
+    use std::str::FromStr;
+
+    fn did(name: &str) -> Did {
+        Did::from_str(&format!("did:example:{name}")).unwrap()
+    }
+
+    fn doc(name: &str) -> DidDocument {
+        DidDocument {
+            id: did(name),
+            public_key: vec![0u8; 32],
+        }
+    }
+
+    fn node_key(name: &str) -> icn_identity::SigningKey {
+        let mut seed = [0u8; 32];
+        seed[..name.len()].copy_from_slice(name.as_bytes());
+        icn_identity::SigningKey::from_bytes(&seed)
+    }
+
+    fn node(name: &str) -> Did {
+        Did::from_str(&did_key_from_verifying_key(&node_key(name).verifying_key())).unwrap()
+    }
+
+    fn signed_commitment(round_id: &str, name: &str, secret: &[u8]) -> BeaconCommitment {
+        let mut commitment = BeaconCommitment::new(round_id, node(name), secret);
+        commitment.signature = Some(commitment.sign(&node_key(name)).unwrap());
+        commitment
+    }
+
+    /// Run a full beacon round among `nodes` and return its source with a
+    /// trust expecting exactly those nodes.
+    fn beacon_source(nodes: &[&str]) -> (RandomnessSource, RandomnessTrust) {
+        let participants: Vec<Did> = nodes.iter().map(|n| node(n)).collect();
+        let mut anchoring = BallotAnchoringService::new(InMemoryDagStore::new());
+        let mut beacon = CommitRevealBeacon::new("round-1", participants.clone(), nodes.len());
+        for n in nodes {
+            let commitment = signed_commitment("round-1", n, n.as_bytes());
+            let cid = anchoring.anchor_beacon_commitment(&commitment, 0).unwrap();
+            beacon.commit(&commitment, cid).unwrap();
+        }
+        beacon.close_commits();
+        for n in nodes {
+            beacon.reveal(&node(n), n.as_bytes().to_vec()).unwrap();
+        }
+        let trust = RandomnessTrust::new(Arc::new(anchoring.storage))
+            .with_beacon_participants(participants);
+        (beacon.finalize().unwrap(), trust)
+    }
+
+    fn service_for(rules: EligibilityRules, trust: RandomnessTrust) -> SortitionService {
+        SortitionService::new(rules).with_randomness_trust(trust)
+    }
+
+    #[test]
+    fn beacon_rejects_mismatched_reveal_and_reports_missing() {
+        let (a, b) = (node("a"), node("b"));
+        let mut beacon = CommitRevealBeacon::new("r", vec![a.clone(), b.clone()], 1);
+        let cid = |tag: &[u8]| Cid::new_v1_sha256(0x71, tag);
+        let mut unsigned = signed_commitment("r", "a", b"secret-a");
+        unsigned.signature = None;
+        assert!(beacon.commit(&unsigned, cid(b"a")).is_err());
+        assert!(beacon
+            .commit(&signed_commitment("other", "a", b"secret-a"), cid(b"a"))
+            .is_err());
+        beacon
+            .commit(&signed_commitment("r", "a", b"secret-a"), cid(b"a"))
+            .unwrap();
+        beacon
+            .commit(&signed_commitment("r", "b", b"secret-b"), cid(b"b"))
+            .unwrap();
+        assert!(beacon.reveal(&a, b"secret-a".to_vec()).is_err());
+        beacon.close_commits();
+        assert!(beacon
+            .commit(&signed_commitment("r", "c", b"secret-c"), cid(b"c"))
+            .is_err());
+        assert!(beacon.reveal(&a, b"wrong".to_vec()).is_err());
+        beacon.reveal(&a, b"secret-a".to_vec()).unwrap();
+
+        match beacon.finalize().unwrap() {
+            RandomnessSource::CommitReveal {
+                missing, reveals, ..
+            } => {
+                assert_eq!(missing, vec![b]);
+                assert_eq!(reveals.len(), 1);
+                assert_eq!(reveals[0].commitment_cid, cid(b"a"));
+            }
+            other => panic!("unexpected source {other:?}"),
+        }
+    }
+
+    #[test]
+    fn beacon_seed_needs_expected_participants_with_anchored_commitments() {
+        let (source, trust) = beacon_source(&["n1", "n2"]);
+        assert!(source.seed(&trust).is_ok());
+
+        // A drawer cannot add a participant of its own choosing, even with a
+        // correctly anchored and signed commitment.
+        let RandomnessSource::CommitReveal {
+            round_id,
+            mut reveals,
+            missing,
+        } = source
+        else {
+            unreachable!()
+        };
+        let mut anchoring = BallotAnchoringService::new(InMemoryDagStore::new());
+        let extra = signed_commitment(&round_id, "grinder", b"chosen");
+        let extra_cid = anchoring.anchor_beacon_commitment(&extra, 0).unwrap();
+        let mut invented = reveals.clone();
+        invented.push(BeaconReveal {
+            participant: node("grinder"),
+            commitment_cid: extra_cid,
+            secret: b"chosen".to_vec(),
+        });
+        let invented = RandomnessSource::CommitReveal {
+            round_id: round_id.clone(),
+            reveals: invented,
+            missing: missing.clone(),
+        };
+        assert!(matches!(
+            invented.seed(&trust),
+            Err(CommonError::PermissionDenied(_))
+        ));
+
+        // Nor swap in a secret that was never committed in the DAG.
+        reveals[0].secret = b"regrind".to_vec();
+        let regrind = RandomnessSource::CommitReveal {
+            round_id: round_id.clone(),
+            reveals: reveals.clone(),
+            missing: missing.clone(),
+        };
+        assert!(regrind.seed(&trust).is_err());
+        reveals[0].commitment_cid = Cid::new_v1_sha256(0x71, b"unanchored");
+        let unanchored = RandomnessSource::CommitReveal {
+            round_id,
+            reveals,
+            missing,
+        };
+        assert!(matches!(
+            unanchored.seed(&trust),
+            Err(CommonError::ResourceNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn draw_is_order_independent_and_verifiable() {
+        let candidates: Vec<DidDocument> =
+            ["a", "b", "c", "d", "e"].iter().map(|n| doc(n)).collect();
+        let mut reversed = candidates.clone();
+        reversed.reverse();
+        let tp = FixedTimeProvider(1_700_000_000);
+        let (source, trust) = beacon_source(&["n1", "n2", "n3"]);
+        assert!(SortitionService::new(EligibilityRules::open_to_all())
+            .draw("d1", &candidates, 3, source.clone(), &tp)
+            .is_err());
+        let service = service_for(EligibilityRules::open_to_all(), trust.clone());
+
+        let draw = service
+            .draw("d1", &candidates, 3, source.clone(), &tp)
+            .unwrap();
+        let again = service.draw("d1", &reversed, 3, source, &tp).unwrap();
+        assert_eq!(draw.selected, again.selected);
+        assert_eq!(draw.selected.len(), 3);
+        assert!(service.verify_draw(&draw, &reversed).unwrap());
+
+        let mut tampered = draw.clone();
+        tampered.selected.swap(0, 2);
+        assert!(!service.verify_draw(&tampered, &candidates).unwrap());
+
+        // A drawer reporting a narrower pool is caught by the rebuilt pool.
+        let mut narrowed = draw.clone();
+        narrowed.pool.retain(|d| draw.selected.contains(d));
+        narrowed.selected =
+            select_members(&narrowed.source.seed(&trust).unwrap(), &narrowed.pool, 3);
+        assert!(narrowed.verify(&narrowed.pool, &trust).unwrap());
+        assert!(!service.verify_draw(&narrowed, &candidates).unwrap());
+    }
+
+    #[test]
+    fn eligibility_rules_filter_pool() {
+        let rules = EligibilityRules::federation_members_only("coop".into());
+        let (source, trust) = beacon_source(&["n1"]);
+        let service = service_for(rules, trust);
+        let candidates = vec![doc("coop-alice"), doc("bob"), doc("coop-carol")];
+        let tp = FixedTimeProvider(0);
+        let draw = service
+            .draw("d", &candidates, 2, source.clone(), &tp)
+            .unwrap();
+        assert_eq!(draw.pool, vec![did("coop-alice"), did("coop-carol")]);
+        assert!(service.draw("d", &candidates, 3, source, &tp).is_err());
+    }
+
+    #[test]
+    fn checkpoint_vrf_verifies_and_detects_forgery() {
+        let vrf = |sk: &icn_identity::SigningKey, signer: &Did, root: &Cid| {
+            let input = checkpoint_vrf_input("fed:7", 7, root, root);
+            RandomnessSource::CheckpointVrf {
+                checkpoint_id: "fed:7".into(),
+                epoch: 7,
+                state_root: root.clone(),
+                dag_root: root.clone(),
+                signer: signer.clone(),
+                signature: icn_identity::sign_message(sk, &input).to_bytes().to_vec(),
+            }
+        };
+        let (sk, pk) = generate_ed25519_keypair();
+        let signer = Did::from_str(&did_key_from_verifying_key(&pk)).unwrap();
+        let (rogue_sk, rogue_pk) = generate_ed25519_keypair();
+        let rogue = Did::from_str(&did_key_from_verifying_key(&rogue_pk)).unwrap();
+        let block = DagBlock {
+            cid: icn_common::compute_merkle_cid(0x71, b"root", &[], 0, &did("n1"), &None, &None),
+            data: b"root".to_vec(),
+            links: vec![],
+            timestamp: 0,
+            author_did: did("n1"),
+            signature: None,
+            scope: None,
+        };
+        let mut dag = InMemoryDagStore::new();
+        dag.put(&block).unwrap();
+        let dag: Arc<dyn StorageService<DagBlock>> = Arc::new(dag);
+        let trust = RandomnessTrust::new(dag.clone()).with_checkpoint_signers(vec![signer.clone()]);
+        let root = block.cid;
+
+        let source = vrf(&sk, &signer, &root);
+        assert!(source.seed(&trust).is_ok());
+        assert!(source.seed(&RandomnessTrust::new(dag)).is_err());
+        assert!(vrf(&rogue_sk, &rogue, &root).seed(&trust).is_err());
+        let unanchored = Cid::new_v1_sha256(0x71, b"elsewhere");
+        assert!(matches!(
+            vrf(&sk, &signer, &unanchored).seed(&trust),
+            Err(CommonError::ResourceNotFound(_))
+        ));
+
+        let forged = RandomnessSource::CheckpointVrf {
+            checkpoint_id: "fed:8".into(),
+            epoch: 8,
+            state_root: root.clone(),
+            dag_root: root,
+            signer,
+            signature: vec![0u8; SIGNATURE_LENGTH],
+        };
+        assert!(forged.seed(&trust).is_err());
+    }
+
+    #[test]
+    fn anchored_draw_seats_trust_committee() {
+        let (source, trust) = beacon_source(&["n1", "n2"]);
+        let service = service_for(EligibilityRules::open_to_all(), trust);
+        let candidates: Vec<DidDocument> = ["a", "b", "c", "d"].iter().map(|n| doc(n)).collect();
+        let tp = FixedTimeProvider(42);
+        let draw = service
+            .draw("council-1", &candidates, 2, source, &tp)
+            .unwrap();
+
+        let mut anchoring = BallotAnchoringService::new(InMemoryDagStore::new());
+        let cid = anchoring.anchor_sortition_draw(&draw, &did("n1")).unwrap();
+        assert!(anchoring
+            .verify_anchored_draw(&cid, &service, &candidates)
+            .unwrap());
+
+        let federation = icn_identity::FederationId::new("coop".to_string());
+        let mut engine = FederationGovernanceEngine::new(TrustPolicyEngine::new(), None);
+        engine
+            .create_trust_committee(
+                "council".into(),
+                federation,
+                did("founder"),
+                HashSet::new(),
+                &tp,
+            )
+            .unwrap();
+        assert!(engine
+            .seat_committee_from_draw("council", &draw, &service, &candidates[..3], &tp)
+            .is_err());
+        engine
+            .seat_committee_from_draw("council", &draw, &service, &candidates, &tp)
+            .unwrap();
+
+        let committee = engine.get_committee("council").unwrap();
+        let active: Vec<&TrustCommitteeMember> =
+            committee.members.values().filter(|m| m.active).collect();
+        assert_eq!(active.len(), 2);
+        assert_eq!(
+            committee.members[&draw.selected[0]].role,
+            TrustCommitteeRole::Chair
+        );
+        assert!(!committee.members[&did("founder")].active);
+    }
+}
@@ -0,0 +1,198 @@
+//! Per-proposal state storage shared by governance extensions.
+//!
+//! Mechanisms such as conviction voting and deliberation keep their own
+//! bookkeeping next to the proposal. [`ProposalStateStore`] mirrors the
+//! governance backend: a `HashMap` in memory, or a dedicated sled tree when
+//! proposals are persisted.
+
+use crate::ProposalId;
+use icn_common::CommonError;
+use std::collections::HashMap;
+
+/// Values that can live in a [`ProposalStateStore`].
+#[cfg(feature = "persist-sled")]
+pub(crate) trait StoredState:
+    Clone + serde::Serialize + serde::de::DeserializeOwned
+{
+}
+#[cfg(feature = "persist-sled")]
+impl<T: Clone + serde::Serialize + serde::de::DeserializeOwned> StoredState for T {}
+
+/// Values that can live in a [`ProposalStateStore`].
+#[cfg(not(feature = "persist-sled"))]
+pub(crate) trait StoredState: Clone {}
+#[cfg(not(feature = "persist-sled"))]
+impl<T: Clone> StoredState for T {}
+
+#[derive(Debug)]
+enum StoreBackend<T> {
+    InMemory(HashMap<ProposalId, T>),
+    #[cfg(feature = "persist-sled")]
+    Sled(sled::Tree),
+}
+
+/// Keyed state for proposals, labelled for error messages.
+#[derive(Debug)]
+pub(crate) struct ProposalStateStore<T> {
+    label: &'static str,
+    backend: StoreBackend<T>,
+}
+
+impl<T: StoredState> ProposalStateStore<T> {
+    pub(crate) fn in_memory(label: &'static str) -> Self {
+        Self {
+            label,
+            backend: StoreBackend::InMemory(HashMap::new()),
+        }
+    }
+
+    #[cfg(feature = "persist-sled")]
+    pub(crate) fn sled(label: &'static str, tree: sled::Tree) -> Self {
+        Self {
+            label,
+            backend: StoreBackend::Sled(tree),
+        }
+    }
+
+    pub(crate) fn get(&self, id: &ProposalId) -> Result<Option<T>, CommonError> {
+        match &self.backend {
+            StoreBackend::InMemory(map) => Ok(map.get(id).cloned()),
+            #[cfg(feature = "persist-sled")]
+            StoreBackend::Sled(tree) => {
+                let bytes = tree.get(id.0.as_bytes()).map_err(|e| {
+                    CommonError::DatabaseError(format!(
+                        "Failed to get {} state {}: {}",
+                        self.label, id.0, e
+                    ))
+                })?;
+                bytes
+                    .map(|b| {
+                        bincode::deserialize(&b).map_err(|e| {
+                            CommonError::DeserializationError(format!(
+                                "Failed to deserialize {} state {}: {}",
+                                self.label, id.0, e
+                            ))
+                        })
+                    })
+                    .transpose()
+            }
+        }
+    }
+
+    pub(crate) fn put(&mut self, id: &ProposalId, state: &T) -> Result<(), CommonError> {
+        match &mut self.backend {
+            StoreBackend::InMemory(map) => {
+                map.insert(id.clone(), state.clone());
+                Ok(())
+            }
+            #[cfg(feature = "persist-sled")]
+            StoreBackend::Sled(tree) => {
+                let encoded = bincode::serialize(state).map_err(|e| {
+                    CommonError::SerializationError(format!(
+                        "Failed to serialize {} state {}: {}",
+                        self.label, id.0, e
+                    ))
+                })?;
+                tree.insert(id.0.as_bytes(), encoded).map_err(|e| {
+                    CommonError::DatabaseError(format!(
+                        "Failed to persist {} state {}: {}",
+                        self.label, id.0, e
+                    ))
+                })?;
+                tree.flush().map_err(|e| {
+                    CommonError::DatabaseError(format!(
+                        "Failed to flush {} tree for {}: {}",
+                        self.label, id.0, e
+                    ))
+                })?;
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn list(&self) -> Result<Vec<T>, CommonError> {
+        match &self.backend {
+            StoreBackend::InMemory(map) => Ok(map.values().cloned().collect()),
+            #[cfg(feature = "persist-sled")]
+            StoreBackend::Sled(tree) => {
+                let mut states = Vec::new();
+                for item in tree.iter() {
+                    let (_, bytes) = item.map_err(|e| {
+                        CommonError::DatabaseError(format!(
+                            "Failed to iterate {} states: {}",
+                            self.label, e
+                        ))
+                    })?;
+                    states.push(bincode::deserialize(&bytes).map_err(|e| {
+                        CommonError::DeserializationError(format!(
+                            "Failed to deserialize {} state: {}",
+                            self.label, e
+                        ))
+                    })?);
+                }
+                Ok(states)
+            }
+        }
+    }
+
+    /// Every stored state with the id it is stored under.
+    #[cfg(feature = "persist-sled")]
+    pub(crate) fn entries(&self) -> Result<Vec<(ProposalId, T)>, CommonError> {
+        match &self.backend {
+            StoreBackend::InMemory(map) => {
+                Ok(map.iter().map(|(id, s)| (id.clone(), s.clone())).collect())
+            }
+            StoreBackend::Sled(tree) => {
+                let mut entries = Vec::new();
+                for item in tree.iter() {
+                    let (key, bytes) = item.map_err(|e| {
+                        CommonError::DatabaseError(format!(
+                            "Failed to iterate {} states: {}",
+                            self.label, e
+                        ))
+                    })?;
+                    let state = bincode::deserialize(&bytes).map_err(|e| {
+                        CommonError::DeserializationError(format!(
+                            "Failed to deserialize {} state: {}",
+                            self.label, e
+                        ))
+                    })?;
+                    entries.push((
+                        ProposalId(String::from_utf8_lossy(&key).into_owned()),
+                        state,
+                    ));
+                }
+                Ok(entries)
+            }
+        }
+    }
+
+    pub(crate) fn remove(&mut self, id: &ProposalId) -> Result<(), CommonError> {
+        match &mut self.backend {
+            StoreBackend::InMemory(map) => {
+                map.remove(id);
+                Ok(())
+            }
+            #[cfg(feature = "persist-sled")]
+            StoreBackend::Sled(tree) => {
+                tree.remove(id.0.as_bytes()).map_err(|e| {
+                    CommonError::DatabaseError(format!(
+                        "Failed to remove {} state {}: {}",
+                        self.label, id.0, e
+                    ))
+                })?;
+                tree.flush().map_err(|e| {
+                    CommonError::DatabaseError(format!(
+                        "Failed to flush {} tree for {}: {}",
+                        self.label, id.0, e
+                    ))
+                })?;
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn contains(&self, id: &ProposalId) -> bool {
+        matches!(self.get(id), Ok(Some(_)))
+    }
+}
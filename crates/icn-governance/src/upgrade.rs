@@ -0,0 +1,571 @@
+//! Coordinated software upgrades
+//!
+//! A [`ProposalType::SoftwareUpgrade`] proposal may carry a signed
+//! [`UpgradeManifest`] as its payload instead of a bare version string. The
+//! manifest pins the release artifact by CID and SHA-256, names the protocol
+//! version it introduces and says when that version activates, either at a
+//! block height or at a unix timestamp. CCL contract migrations prepared with
+//! [`icn_ccl::migration::MigrationEngine::prepare_upgrade`] ride along in the
+//! same manifest so contracts switch over at the same activation point.
+//!
+//! Once the proposal is executed the upgrade is scheduled. Nodes then
+//! publish signed [`ReadinessSignal`]s once they run a build that supports
+//! the new version, which lets operators see how much of the federation is
+//! ready before activation. Feature gates use
+//! [`GovernanceModule::is_protocol_active`] so new behaviour switches on
+//! everywhere at the same point.
+//!
+//! Every `SoftwareUpgrade` payload must be a manifest signed by one of the
+//! release signers governance trusts through the
+//! [`RELEASE_SIGNERS`](crate::parameters::RELEASE_SIGNERS) parameter.
+
+use crate::parameters::RELEASE_SIGNERS;
+use crate::{GovernanceModule, ParameterValue, Proposal, ProposalId, ProposalType};
+use icn_ccl::migration::ContractUpgrade;
+use icn_common::{Cid, CommonError, Did, Signable, SignatureBytes};
+use icn_identity::verifying_key_from_did_key;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Protocol version spoken by nodes before any upgrade has activated.
+pub const BASE_PROTOCOL_VERSION: u32 = 1;
+
+/// When a scheduled upgrade takes effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UpgradeActivation {
+    /// Activate once the chain reaches this height.
+    AtHeight(u64),
+    /// Activate at this unix timestamp (seconds).
+    AtTime(u64),
+}
+
+impl UpgradeActivation {
+    /// Whether the activation point has been reached.
+    pub fn is_reached(&self, height: u64, now: u64) -> bool {
+        match self {
+            UpgradeActivation::AtHeight(h) => height >= *h,
+            UpgradeActivation::AtTime(t) => now >= *t,
+        }
+    }
+}
+
+impl std::fmt::Display for UpgradeActivation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpgradeActivation::AtHeight(h) => write!(f, "height {h}"),
+            UpgradeActivation::AtTime(t) => write!(f, "time {t}"),
+        }
+    }
+}
+
+/// Signed description of a release, carried as a `SoftwareUpgrade` payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UpgradeManifest {
+    /// Human readable release version, e.g. `0.3.0`.
+    pub version: String,
+    /// Protocol version enabled at activation.
+    pub protocol_version: u32,
+    /// CID of the release artifact.
+    pub artifact_cid: Cid,
+    /// Hex encoded SHA-256 of the release artifact.
+    pub artifact_sha256: String,
+    pub activation: UpgradeActivation,
+    /// CCL contracts migrated as part of this upgrade.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub contract_upgrades: Vec<ContractUpgrade>,
+    /// `did:key` of the release publisher.
+    pub publisher: Did,
+    pub signature: Option<SignatureBytes>,
+}
+
+impl Signable for UpgradeManifest {
+    fn to_signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        fn put(bytes: &mut Vec<u8>, field: &[u8]) {
+            bytes.extend_from_slice(&(field.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(field);
+        }
+        let mut bytes = Vec::new();
+        put(&mut bytes, self.version.as_bytes());
+        bytes.extend_from_slice(&self.protocol_version.to_le_bytes());
+        put(&mut bytes, self.artifact_cid.to_string().as_bytes());
+        put(&mut bytes, self.artifact_sha256.as_bytes());
+        match self.activation {
+            UpgradeActivation::AtHeight(h) => {
+                bytes.push(0);
+                bytes.extend_from_slice(&h.to_le_bytes());
+            }
+            UpgradeActivation::AtTime(t) => {
+                bytes.push(1);
+                bytes.extend_from_slice(&t.to_le_bytes());
+            }
+        }
+        bytes.extend_from_slice(&(self.contract_upgrades.len() as u64).to_le_bytes());
+        for upgrade in &self.contract_upgrades {
+            put(&mut bytes, upgrade.contract.as_bytes());
+            put(&mut bytes, upgrade.to_version.to_string().as_bytes());
+            put(&mut bytes, upgrade.source_cid.to_string().as_bytes());
+            match &upgrade.wasm_cid {
+                Some(wasm) => {
+                    bytes.push(1);
+                    put(&mut bytes, wasm.to_string().as_bytes());
+                }
+                None => bytes.push(0),
+            }
+        }
+        put(&mut bytes, self.publisher.to_string().as_bytes());
+        Ok(bytes)
+    }
+}
+
+impl UpgradeManifest {
+    /// Check that the publisher is one of `trusted_signers` and signed the
+    /// manifest.
+    pub fn verify_signature(&self, trusted_signers: &[Did]) -> Result<(), CommonError> {
+        if !trusted_signers.contains(&self.publisher) {
+            return Err(CommonError::PolicyDenied(format!(
+                "{} is not a trusted release signer",
+                self.publisher
+            )));
+        }
+        let signature = self.signature.as_ref().ok_or_else(|| {
+            CommonError::CryptoError("Upgrade manifest is not signed".to_string())
+        })?;
+        let key = verifying_key_from_did_key(&self.publisher)?;
+        self.verify(signature, &key)
+    }
+
+    /// Check that `artifact` is the release described by this manifest.
+    pub fn verify_artifact(&self, artifact: &[u8]) -> Result<(), CommonError> {
+        use sha2::{Digest, Sha256};
+        let digest = Sha256::digest(artifact);
+        if hex::encode(digest) != self.artifact_sha256.to_lowercase() {
+            return Err(CommonError::InvalidInputError(format!(
+                "Artifact hash does not match manifest for {}",
+                self.version
+            )));
+        }
+        if self.artifact_cid.hash_bytes != digest.as_slice() {
+            return Err(CommonError::InvalidInputError(format!(
+                "Artifact CID {} does not match manifest hash",
+                self.artifact_cid
+            )));
+        }
+        Ok(())
+    }
+
+    /// Encode the manifest as a `SoftwareUpgrade` proposal payload.
+    pub fn to_proposal_payload(&self) -> Result<String, CommonError> {
+        serde_json::to_string(self).map_err(|e| {
+            CommonError::SerializationError(format!("Failed to encode upgrade manifest: {}", e))
+        })
+    }
+
+    /// Decode a `SoftwareUpgrade` payload.
+    pub fn from_proposal_payload(payload: &str) -> Result<Self, CommonError> {
+        serde_json::from_str(payload).map_err(|e| {
+            CommonError::DeserializationError(format!("Invalid upgrade manifest: {}", e))
+        })
+    }
+}
+
+/// A node's signed statement that it can run an upgrade.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReadinessSignal {
+    pub node: Did,
+    pub proposal_id: ProposalId,
+    /// Release version the node is running or has staged.
+    pub version: String,
+    pub signed_at: u64,
+    pub signature: Option<SignatureBytes>,
+}
+
+impl Signable for ReadinessSignal {
+    fn to_signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(self.node.to_string().as_bytes());
+        bytes.extend_from_slice(self.proposal_id.0.as_bytes());
+        bytes.extend_from_slice(self.version.as_bytes());
+        bytes.extend_from_slice(&self.signed_at.to_le_bytes());
+        Ok(bytes)
+    }
+}
+
+/// An accepted upgrade awaiting or past its activation point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScheduledUpgrade {
+    pub proposal_id: ProposalId,
+    pub manifest: UpgradeManifest,
+    pub scheduled_at: u64,
+    /// Latest readiness signal from each node.
+    pub readiness: Vec<ReadinessSignal>,
+}
+
+impl ScheduledUpgrade {
+    /// Whether the upgrade has activated.
+    pub fn is_active(&self, height: u64, now: u64) -> bool {
+        self.manifest.activation.is_reached(height, now)
+    }
+
+    /// Nodes that have signalled readiness.
+    pub fn ready_nodes(&self) -> Vec<Did> {
+        self.readiness.iter().map(|r| r.node.clone()).collect()
+    }
+}
+
+pub(crate) type UpgradeStore = crate::state_store::ProposalStateStore<ScheduledUpgrade>;
+
+impl GovernanceModule {
+    /// Release signers governance trusts, from the [`RELEASE_SIGNERS`]
+    /// parameter.
+    pub fn release_signers(&self) -> Result<Vec<Did>, CommonError> {
+        match self.parameters.get(RELEASE_SIGNERS) {
+            Some(ParameterValue::StringList(signers)) => signers
+                .iter()
+                .map(|signer| {
+                    signer.parse::<Did>().map_err(|e| {
+                        CommonError::InvalidInputError(format!(
+                            "Invalid release signer {}: {}",
+                            signer, e
+                        ))
+                    })
+                })
+                .collect(),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Reject `SoftwareUpgrade` proposals that do not carry a well-formed
+    /// manifest signed by a trusted release signer.
+    pub(crate) fn validate_upgrade_proposal(
+        &self,
+        proposal_type: &ProposalType,
+    ) -> Result<(), CommonError> {
+        let ProposalType::SoftwareUpgrade(payload) = proposal_type else {
+            return Ok(());
+        };
+        let manifest = UpgradeManifest::from_proposal_payload(payload)?;
+        manifest.verify_signature(&self.release_signers()?)?;
+        if manifest.protocol_version <= BASE_PROTOCOL_VERSION {
+            return Err(CommonError::InvalidInputError(format!(
+                "Upgrade {} must raise the protocol version above {}",
+                manifest.version, BASE_PROTOCOL_VERSION
+            )));
+        }
+        if hex::decode(&manifest.artifact_sha256).map(|h| h.len()) != Ok(32) {
+            return Err(CommonError::InvalidInputError(
+                "artifact_sha256 must be a hex encoded SHA-256 digest".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Schedule the upgrade carried by an executed proposal.
+    pub(crate) fn schedule_upgrade(
+        &mut self,
+        proposal: &Proposal,
+    ) -> Result<Option<ScheduledUpgrade>, CommonError> {
+        let ProposalType::SoftwareUpgrade(payload) = &proposal.proposal_type else {
+            return Ok(None);
+        };
+        let manifest = UpgradeManifest::from_proposal_payload(payload)?;
+        let scheduled = ScheduledUpgrade {
+            proposal_id: proposal.id.clone(),
+            manifest,
+            scheduled_at: proposal.voting_deadline,
+            readiness: Vec::new(),
+        };
+        self.upgrades.put(&proposal.id, &scheduled)?;
+        Ok(Some(scheduled))
+    }
+
+    /// Upgrade scheduled by the given proposal, if any.
+    pub fn scheduled_upgrade(
+        &self,
+        proposal_id: &ProposalId,
+    ) -> Result<Option<ScheduledUpgrade>, CommonError> {
+        self.upgrades.get(proposal_id)
+    }
+
+    /// All scheduled upgrades, ordered by protocol version.
+    pub fn scheduled_upgrades(&self) -> Result<Vec<ScheduledUpgrade>, CommonError> {
+        let mut upgrades = self.upgrades.list()?;
+        upgrades.sort_by_key(|u| u.manifest.protocol_version);
+        Ok(upgrades)
+    }
+
+    /// Record a node's signed readiness for a scheduled upgrade.
+    ///
+    /// A newer signal from the same node replaces the previous one.
+    pub fn signal_upgrade_readiness(
+        &mut self,
+        signal: ReadinessSignal,
+    ) -> Result<ScheduledUpgrade, CommonError> {
+        let signature = signal.signature.clone().ok_or_else(|| {
+            CommonError::CryptoError("Readiness signals must be signed".to_string())
+        })?;
+        let key = verifying_key_from_did_key(&signal.node)?;
+        signal.verify(&signature, &key)?;
+
+        let mut upgrade = self.upgrades.get(&signal.proposal_id)?.ok_or_else(|| {
+            CommonError::ResourceNotFound(format!(
+                "No upgrade scheduled by proposal {}",
+                signal.proposal_id.0
+            ))
+        })?;
+        if signal.version != upgrade.manifest.version {
+            return Err(CommonError::InvalidInputError(format!(
+                "Node reports version {} but upgrade is {}",
+                signal.version, upgrade.manifest.version
+            )));
+        }
+        match upgrade.readiness.iter_mut().find(|r| r.node == signal.node) {
+            Some(existing) if existing.signed_at >= signal.signed_at => {}
+            Some(existing) => *existing = signal,
+            None => upgrade.readiness.push(signal),
+        }
+        self.upgrades.put(&upgrade.proposal_id, &upgrade)?;
+        Ok(upgrade)
+    }
+
+    /// Highest protocol version whose activation point has been reached.
+    pub fn active_protocol_version(&self, height: u64, now: u64) -> Result<u32, CommonError> {
+        Ok(self
+            .upgrades
+            .list()?
+            .iter()
+            .filter(|u| u.is_active(height, now))
+            .map(|u| u.manifest.protocol_version)
+            .fold(BASE_PROTOCOL_VERSION, u32::max))
+    }
+
+    /// Feature gate: whether behaviour introduced in `protocol_version`
+    /// may be used at the given height and time.
+    pub fn is_protocol_active(
+        &self,
+        protocol_version: u32,
+        height: u64,
+        now: u64,
+    ) -> Result<bool, CommonError> {
+        Ok(protocol_version <= self.active_protocol_version(height, now)?)
+    }
+
+    /// Contract upgrades from every activated software upgrade.
+    pub fn active_contract_upgrades(
+        &self,
+        height: u64,
+        now: u64,
+    ) -> Result<Vec<ContractUpgrade>, CommonError> {
+        Ok(self
+            .scheduled_upgrades()?
+            .into_iter()
+            .filter(|u| u.is_active(height, now))
+            .flat_map(|u| u.manifest.contract_upgrades)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ProposalSubmission, VoteOption};
+    use icn_ccl::migration::{CclVersion, MigrationEngine};
+    use icn_common::FixedTimeProvider;
+    use icn_identity::{did_key_from_verifying_key, generate_ed25519_keypair, SigningKey};
+    use std::str::FromStr;
+
+    fn keyed_did() -> (SigningKey, Did) {
+        let (sk, pk) = generate_ed25519_keypair();
+        let did = Did::from_str(&did_key_from_verifying_key(&pk)).unwrap();
+        (sk, did)
+    }
+
+    fn signed_manifest(sk: &SigningKey, publisher: &Did, activate_at: u64) -> UpgradeManifest {
+        let artifact = b"icn-node 0.3.0";
+        let (contract, _) = MigrationEngine::new()
+            .prepare_upgrade(
+                "billing",
+                "rule bill when true then charge(1)",
+                &CclVersion::new(0, 2, 0),
+            )
+            .unwrap();
+        let mut manifest = UpgradeManifest {
+            version: "0.3.0".into(),
+            protocol_version: 2,
+            artifact_cid: Cid::new_v1_sha256(0x55, artifact),
+            artifact_sha256: {
+                use sha2::{Digest, Sha256};
+                hex::encode(Sha256::digest(artifact))
+            },
+            activation: UpgradeActivation::AtTime(activate_at),
+            contract_upgrades: vec![contract],
+            publisher: publisher.clone(),
+            signature: None,
+        };
+        manifest.signature = Some(manifest.sign(sk).unwrap());
+        manifest
+    }
+
+    fn trust(gov: &mut GovernanceModule, signer: &Did) {
+        gov.parameters
+            .apply(
+                &[(RELEASE_SIGNERS.to_string(), signer.to_string())],
+                None,
+                0,
+            )
+            .unwrap();
+    }
+
+    fn submit(gov: &mut GovernanceModule, proposer: &Did, payload: String) -> CommonError {
+        gov.submit_proposal(
+            ProposalSubmission {
+                proposer: proposer.clone(),
+                proposal_type: ProposalType::SoftwareUpgrade(payload),
+                description: "upgrade".into(),
+                duration_secs: 60,
+                quorum: None,
+                threshold: None,
+                content_cid: None,
+                timelock_delay: None,
+            },
+            &FixedTimeProvider::new(1_000),
+        )
+        .unwrap_err()
+    }
+
+    fn accept(gov: &mut GovernanceModule, voter: &Did, payload: String) -> ProposalId {
+        let time = FixedTimeProvider::new(1_000);
+        let pid = gov
+            .submit_proposal(
+                ProposalSubmission {
+                    proposer: voter.clone(),
+                    description: format!("upgrade {payload}"),
+                    proposal_type: ProposalType::SoftwareUpgrade(payload),
+                    duration_secs: 60,
+                    quorum: None,
+                    threshold: None,
+                    content_cid: None,
+                    timelock_delay: None,
+                },
+                &time,
+            )
+            .unwrap();
+        gov.open_voting(&pid).unwrap();
+        gov.cast_vote(voter.clone(), &pid, VoteOption::Yes, &time)
+            .unwrap();
+        gov.close_voting_period(&pid, &time).unwrap();
+        gov.execute_proposal(&pid).unwrap();
+        pid
+    }
+
+    #[test]
+    fn unsigned_manifest_is_rejected_at_submission() {
+        let (sk, publisher) = keyed_did();
+        let mut gov = GovernanceModule::new();
+        gov.add_member(publisher.clone());
+        trust(&mut gov, &publisher);
+        let mut manifest = signed_manifest(&sk, &publisher, 5_000);
+        manifest.signature = None;
+        let err = submit(
+            &mut gov,
+            &publisher,
+            manifest.to_proposal_payload().unwrap(),
+        );
+        assert!(matches!(err, CommonError::CryptoError(_)));
+    }
+
+    #[test]
+    fn only_manifests_from_trusted_signers_are_accepted() {
+        let (sk, publisher) = keyed_did();
+        let mut gov = GovernanceModule::new();
+        gov.add_member(publisher.clone());
+        let payload = signed_manifest(&sk, &publisher, 5_000)
+            .to_proposal_payload()
+            .unwrap();
+        let err = submit(&mut gov, &publisher, payload);
+        assert!(matches!(err, CommonError::PolicyDenied(_)));
+
+        trust(&mut gov, &publisher);
+        let err = submit(&mut gov, &publisher, "0.2.9".into());
+        assert!(matches!(err, CommonError::DeserializationError(_)));
+    }
+
+    #[test]
+    fn manifest_signature_covers_field_boundaries() {
+        let (sk, publisher) = keyed_did();
+        let manifest = signed_manifest(&sk, &publisher, 5_000);
+        let mut shifted = manifest.clone();
+        shifted.version = "0.3.".into();
+        shifted.artifact_sha256 = format!("0{}", manifest.artifact_sha256);
+        assert_ne!(
+            shifted.to_signable_bytes().unwrap(),
+            manifest.to_signable_bytes().unwrap()
+        );
+        let mut dropped = manifest.clone();
+        dropped.contract_upgrades.clear();
+        assert!(dropped.verify_signature(&[publisher]).is_err());
+    }
+
+    #[test]
+    fn executed_upgrade_gates_protocol_version_at_activation() {
+        let (sk, publisher) = keyed_did();
+        let mut gov = GovernanceModule::new();
+        gov.add_member(publisher.clone());
+        gov.set_quorum(1);
+        trust(&mut gov, &publisher);
+        let manifest = signed_manifest(&sk, &publisher, 5_000);
+        assert!(manifest.verify_artifact(b"icn-node 0.3.0").is_ok());
+        assert!(manifest.verify_artifact(b"tampered").is_err());
+
+        let pid = accept(
+            &mut gov,
+            &publisher,
+            manifest.to_proposal_payload().unwrap(),
+        );
+        assert!(gov.scheduled_upgrade(&pid).unwrap().is_some());
+
+        assert_eq!(gov.active_protocol_version(0, 4_999).unwrap(), 1);
+        assert!(!gov.is_protocol_active(2, 0, 4_999).unwrap());
+        assert!(gov.active_contract_upgrades(0, 4_999).unwrap().is_empty());
+        assert!(gov.is_protocol_active(2, 0, 5_000).unwrap());
+        assert_eq!(gov.active_contract_upgrades(0, 5_000).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn readiness_signals_are_signed_and_deduplicated() {
+        let (sk, publisher) = keyed_did();
+        let (node_sk, node) = keyed_did();
+        let mut gov = GovernanceModule::new();
+        gov.add_member(publisher.clone());
+        gov.set_quorum(1);
+        trust(&mut gov, &publisher);
+        let pid = accept(
+            &mut gov,
+            &publisher,
+            signed_manifest(&sk, &publisher, 5_000)
+                .to_proposal_payload()
+                .unwrap(),
+        );
+
+        let mut signal = ReadinessSignal {
+            node: node.clone(),
+            proposal_id: pid.clone(),
+            version: "0.3.0".into(),
+            signed_at: 3_000,
+            signature: None,
+        };
+        assert!(gov.signal_upgrade_readiness(signal.clone()).is_err());
+        signal.signature = Some(signal.sign(&node_sk).unwrap());
+        gov.signal_upgrade_readiness(signal.clone()).unwrap();
+
+        signal.signed_at = 3_100;
+        signal.signature = Some(signal.sign(&node_sk).unwrap());
+        let upgrade = gov.signal_upgrade_readiness(signal).unwrap();
+        assert_eq!(upgrade.ready_nodes(), vec![node]);
+        assert_eq!(upgrade.readiness[0].signed_at, 3_100);
+    }
+}
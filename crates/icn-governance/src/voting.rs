@@ -52,6 +52,7 @@
 //! assert_eq!(ballot.first_choice(), Some(&CandidateId("alice".to_string())));
 //! ```
 
+use crate::quadratic::SybilGuard;
 use icn_common::{Cid, Did, DidDocument, Signable, TimeProvider};
 use icn_dag::StorageService;
 use std::any::Any;
@@ -225,6 +226,9 @@ pub struct EligibilityRules {
     pub required_federation: Option<String>,
     /// Custom eligibility criteria stored in DAG
     pub custom_rules: Option<Cid>,
+    /// Require voters to pass a [`SybilGuard`] such as a personhood registry
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub require_personhood: bool,
 }
 
 impl EligibilityRules {
@@ -235,6 +239,7 @@ impl EligibilityRules {
             min_reputation: None,
             required_federation: None,
             custom_rules: None,
+            require_personhood: false,
         }
     }
 
@@ -245,6 +250,7 @@ impl EligibilityRules {
             min_reputation: None,
             required_federation: Some(federation_id),
             custom_rules: None,
+            require_personhood: false,
         }
     }
 
@@ -255,15 +261,23 @@ impl EligibilityRules {
             min_reputation: Some(min_score),
             required_federation: None,
             custom_rules: None,
+            require_personhood: false,
         }
     }
 
+    /// Additionally require voters to be verified persons
+    pub fn with_personhood(mut self) -> Self {
+        self.require_personhood = true;
+        self
+    }
+
     /// Check if the rules have any restrictions
     pub fn has_restrictions(&self) -> bool {
         !self.required_credentials.is_empty()
             || self.min_reputation.is_some()
             || self.required_federation.is_some()
             || self.custom_rules.is_some()
+            || self.require_personhood
     }
 
     /// Validate a voter's DID document against these eligibility rules
@@ -278,6 +292,26 @@ impl EligibilityRules {
         reputation_store: Option<&dyn icn_reputation::ReputationStore>,
         dag_storage: Option<&dyn icn_dag::StorageService<icn_common::DagBlock>>,
         federation_registry: Option<&dyn FederationRegistry>,
+    ) -> Result<bool, VotingError> {
+        self.validate_voter_with_sybil_guard(
+            voter_did_doc,
+            reputation_store,
+            dag_storage,
+            federation_registry,
+            None,
+        )
+    }
+
+    /// Validate a voter like [`Self::validate_voter_with_context`], also
+    /// requiring the voter to pass `sybil_guard` when one is given. Rules that
+    /// require personhood reject every voter without a guard.
+    pub fn validate_voter_with_sybil_guard(
+        &self,
+        voter_did_doc: &DidDocument,
+        reputation_store: Option<&dyn icn_reputation::ReputationStore>,
+        dag_storage: Option<&dyn icn_dag::StorageService<icn_common::DagBlock>>,
+        federation_registry: Option<&dyn FederationRegistry>,
+        sybil_guard: Option<&dyn SybilGuard>,
     ) -> Result<bool, VotingError> {
         // Check required credentials (basic implementation)
         if !self.required_credentials.is_empty() {
@@ -335,6 +369,21 @@ impl EligibilityRules {
             }
         }
 
+        // Check proof of personhood
+        match sybil_guard {
+            Some(guard) if !guard.is_unique_member(&voter_did_doc.id) => {
+                return Err(VotingError::IneligibleVoter(
+                    "Voter is not a verified person".to_string(),
+                ));
+            }
+            None if self.require_personhood => {
+                return Err(VotingError::IneligibleVoter(
+                    "Personhood required but no Sybil guard provided for validation".to_string(),
+                ));
+            }
+            _ => {}
+        }
+
         // Check custom rules stored in DAG
         if let Some(ref custom_rules_cid) = self.custom_rules {
             if let Some(storage) = dag_storage {
@@ -578,7 +627,7 @@ pub struct BallotAnchoringService<S>
 where
     S: StorageService<icn_common::DagBlock>,
 {
-    storage: S,
+    pub(crate) storage: S,
 }
 
 impl<S> BallotAnchoringService<S>
@@ -1180,4 +1229,39 @@ mod enhanced_tests {
         assert!(result.is_ok());
         assert!(result.unwrap());
     }
+
+    #[test]
+    fn test_personhood_requirement_uses_sybil_guard() {
+        use crate::quadratic::PersonhoodSybilGuard;
+        use icn_common::FixedTimeProvider;
+        use icn_identity::{PersonhoodConfig, PersonhoodRegistry};
+
+        let member = Did::from_str("did:key:zPersonhoodMember").unwrap();
+        let stranger = Did::from_str("did:key:zPersonhoodStranger").unwrap();
+        let doc = |id: &Did| DidDocument {
+            id: id.clone(),
+            public_key: vec![0u8; 32],
+        };
+        let rules = EligibilityRules::open_to_all().with_personhood();
+        assert!(rules.has_restrictions());
+
+        // Without a guard personhood cannot be checked, so voters are rejected
+        assert!(matches!(
+            rules.validate_voter(&doc(&member)),
+            Err(VotingError::IneligibleVoter(_))
+        ));
+
+        let registry = PersonhoodRegistry::in_memory(PersonhoodConfig::default());
+        registry.add_seed(member.clone()).unwrap();
+        let guard =
+            PersonhoodSybilGuard::new(registry, std::sync::Arc::new(FixedTimeProvider::new(0)));
+        let validate = |did: &Did| {
+            rules.validate_voter_with_sybil_guard(&doc(did), None, None, None, Some(&guard))
+        };
+        assert!(validate(&member).unwrap());
+        assert!(matches!(
+            validate(&stranger),
+            Err(VotingError::IneligibleVoter(_))
+        ));
+    }
 }
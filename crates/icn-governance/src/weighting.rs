@@ -0,0 +1,572 @@
+//! Weighted and multi-chamber voting
+//!
+//! By default every member DID casts one vote. A federation of cooperatives
+//! can instead attach a [`WeightRule`] to a proposal kind (as named by
+//! [`proposal_kind`]), or to every kind with [`DEFAULT_RULE_KIND`]. A rule
+//! picks a [`WeightingScheme`] and, for multi-stakeholder bodies, the
+//! chambers that must each approve.
+//!
+//! Weights are computed from [`MemberProfile`]s. A profile takes its member
+//! count from the cooperative's [`CooperativeProfile`], and its chambers
+//! from membership credentials signed by an issuer listed in
+//! [`WeightingConfig::trusted_issuers`]. Members without a profile count as
+//! a cooperative of one that sits in no chamber.
+//!
+//! Secret ballots are always tallied by headcount, because their voters are
+//! not known.
+
+use crate::delegation::proposal_kind;
+use crate::{GovernanceModule, Proposal, ProposalId, VoteOption};
+use icn_common::{CommonError, Did};
+use icn_identity::{verifying_key_from_did_key, CooperativeProfile, Credential};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Proposal kind under which the rule for every other kind is stored.
+pub const DEFAULT_RULE_KIND: &str = "*";
+/// Credential claim listing a member's chambers, separated by commas.
+pub const CHAMBER_CLAIM: &str = "chamber";
+/// Credential claim carrying a member cooperative's size.
+pub const MEMBER_COUNT_CLAIM: &str = "member_count";
+
+/// What weighting strategies know about a voting member.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MemberProfile {
+    pub did: Did,
+    /// Number of people in the member cooperative.
+    pub member_count: u64,
+    /// Stakeholder chambers the member votes in, such as `workers`.
+    pub chambers: BTreeSet<String>,
+}
+
+impl MemberProfile {
+    /// Profile of a member with no recorded size or chambers.
+    pub fn new(did: Did) -> Self {
+        Self {
+            did,
+            member_count: 1,
+            chambers: BTreeSet::new(),
+        }
+    }
+
+    /// Profile sized from a cooperative's published profile.
+    pub fn from_cooperative_profile(profile: &CooperativeProfile) -> Self {
+        Self {
+            member_count: profile.member_count.map_or(1, u64::from).max(1),
+            ..Self::new(profile.did.clone())
+        }
+    }
+
+    /// Take chambers and size from a membership credential.
+    ///
+    /// Only the claims named [`CHAMBER_CLAIM`] and [`MEMBER_COUNT_CLAIM`] are
+    /// read, and each must carry a valid issuer signature.
+    pub fn apply_credential(&mut self, credential: &Credential) -> Result<(), CommonError> {
+        if credential.holder != self.did {
+            return Err(CommonError::InvalidInputError(format!(
+                "credential is held by {}, not {}",
+                credential.holder, self.did
+            )));
+        }
+        let key = verifying_key_from_did_key(&credential.issuer)?;
+        if let Some(chambers) = credential.claims.get(CHAMBER_CLAIM) {
+            credential.verify_claim(CHAMBER_CLAIM, &key)?;
+            self.chambers = chambers
+                .split(',')
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+        if let Some(count) = credential.claims.get(MEMBER_COUNT_CLAIM) {
+            credential.verify_claim(MEMBER_COUNT_CLAIM, &key)?;
+            self.member_count = count
+                .parse::<u64>()
+                .map_err(|e| {
+                    CommonError::InvalidInputError(format!("invalid {MEMBER_COUNT_CLAIM}: {e}"))
+                })?
+                .max(1);
+        }
+        Ok(())
+    }
+}
+
+/// Assigns relative voting weights to a set of voters.
+pub trait WeightingStrategy: Send + Sync {
+    /// Weight of each profile, in the same order. Weights are relative and
+    /// need not sum to one.
+    fn weights(&self, profiles: &[MemberProfile]) -> Vec<f64>;
+}
+
+/// One vote per member cooperative.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EqualWeights;
+
+impl WeightingStrategy for EqualWeights {
+    fn weights(&self, profiles: &[MemberProfile]) -> Vec<f64> {
+        vec![1.0; profiles.len()]
+    }
+}
+
+/// Weight by membership size, with no member holding more than `cap` of
+/// the total.
+///
+/// Weight taken from capped members is shared among the rest in proportion
+/// to their size. If the cap cannot be met (fewer than `1 / cap` voters),
+/// every voter ends up at the cap and so weighs the same.
+#[derive(Debug, Clone, Copy)]
+pub struct MembershipSizeWeights {
+    pub cap: f64,
+}
+
+impl WeightingStrategy for MembershipSizeWeights {
+    fn weights(&self, profiles: &[MemberProfile]) -> Vec<f64> {
+        let cap = self.cap.clamp(0.0, 1.0);
+        let sizes: Vec<f64> = profiles.iter().map(|p| p.member_count as f64).collect();
+        let mut capped = vec![false; sizes.len()];
+        loop {
+            let free: f64 = (0..sizes.len())
+                .filter(|&i| !capped[i])
+                .map(|i| sizes[i])
+                .sum();
+            let held = cap * capped.iter().filter(|c| **c).count() as f64;
+            let remaining = (1.0 - held).max(0.0);
+            let over = (0..sizes.len()).find(|&i| {
+                !capped[i] && free > 0.0 && sizes[i] / free * remaining > cap + f64::EPSILON
+            });
+            match over {
+                Some(i) => capped[i] = true,
+                None => {
+                    return (0..sizes.len())
+                        .map(|i| {
+                            if capped[i] {
+                                cap
+                            } else if free > 0.0 {
+                                sizes[i] / free * remaining
+                            } else {
+                                0.0
+                            }
+                        })
+                        .collect()
+                }
+            }
+        }
+    }
+}
+
+/// Serializable choice of [`WeightingStrategy`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WeightingScheme {
+    /// [`EqualWeights`].
+    Equal,
+    /// [`MembershipSizeWeights`] with the given cap, as a fraction of the total.
+    MembershipSize { cap: f64 },
+    /// A strategy registered with
+    /// [`GovernanceModule::register_weighting_strategy`].
+    Custom(String),
+}
+
+/// A chamber whose members must approve separately.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ChamberRequirement {
+    pub chamber: String,
+    /// Share of the chamber's cast weight that must vote yes.
+    pub threshold: f64,
+    /// Fewest chamber members that must vote.
+    pub min_voters: usize,
+}
+
+/// How votes on a proposal kind are weighted and approved.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WeightRule {
+    pub scheme: WeightingScheme,
+    /// Chambers that must each approve, on top of the overall threshold.
+    pub chambers: Vec<ChamberRequirement>,
+}
+
+/// Parameters for accepting weighting credentials.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WeightingConfig {
+    /// Issuers whose chamber and size claims are accepted.
+    pub trusted_issuers: Vec<Did>,
+}
+
+/// Weighted totals within one chamber.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ChamberTally {
+    pub chamber: String,
+    pub yes: f64,
+    pub no: f64,
+    pub abstain: f64,
+    pub voters: usize,
+    pub approved: bool,
+}
+
+/// Outcome of a weighted vote.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WeightedTally {
+    pub proposal_id: ProposalId,
+    pub rule: WeightRule,
+    pub yes: f64,
+    pub no: f64,
+    pub abstain: f64,
+    pub voters: usize,
+    pub chambers: Vec<ChamberTally>,
+    /// Whether the overall threshold and every chamber requirement are met.
+    pub approved: bool,
+}
+
+/// Rules stored with the proposal kind they cover.
+pub(crate) type WeightRuleStore = crate::state_store::ProposalStateStore<(String, WeightRule)>;
+pub(crate) type MemberProfileStore = crate::state_store::ProposalStateStore<MemberProfile>;
+
+/// Weighted yes, no and abstain totals.
+fn sum_votes(
+    strategy: &dyn WeightingStrategy,
+    ballots: &[(MemberProfile, VoteOption)],
+) -> (f64, f64, f64) {
+    let profiles: Vec<MemberProfile> = ballots.iter().map(|(p, _)| p.clone()).collect();
+    let weights = strategy.weights(&profiles);
+    let mut totals = (0.0, 0.0, 0.0);
+    for ((_, option), weight) in ballots.iter().zip(weights) {
+        match option {
+            VoteOption::Yes => totals.0 += weight,
+            VoteOption::No => totals.1 += weight,
+            VoteOption::Abstain => totals.2 += weight,
+        }
+    }
+    totals
+}
+
+fn share_met(yes: f64, no: f64, abstain: f64, threshold: f64) -> bool {
+    let cast = yes + no + abstain;
+    cast > 0.0 && yes >= cast * threshold
+}
+
+impl GovernanceModule {
+    /// Update how weighting credentials are accepted.
+    pub fn set_weighting_config(&mut self, config: WeightingConfig) {
+        self.weighting_config = config;
+    }
+
+    /// Current weighting credential parameters.
+    pub fn weighting_config(&self) -> &WeightingConfig {
+        &self.weighting_config
+    }
+
+    /// Make a custom strategy available as [`WeightingScheme::Custom`].
+    pub fn register_weighting_strategy(
+        &mut self,
+        name: impl Into<String>,
+        strategy: Arc<dyn WeightingStrategy>,
+    ) {
+        self.weighting_strategies.insert(name.into(), strategy);
+    }
+
+    /// Weight votes on `kind` proposals by `rule`. Use
+    /// [`DEFAULT_RULE_KIND`] to cover every kind without its own rule.
+    pub fn set_weight_rule(&mut self, kind: &str, rule: WeightRule) -> Result<(), CommonError> {
+        if kind != DEFAULT_RULE_KIND && !crate::delegation::PROPOSAL_KINDS.contains(&kind) {
+            return Err(CommonError::InvalidInputError(format!(
+                "unknown proposal kind {kind}"
+            )));
+        }
+        if let WeightingScheme::MembershipSize { cap } = rule.scheme {
+            if !(cap > 0.0 && cap <= 1.0) {
+                return Err(CommonError::InvalidInputError(format!(
+                    "membership size cap must be in (0, 1], got {cap}"
+                )));
+            }
+        }
+        if let WeightingScheme::Custom(name) = &rule.scheme {
+            if !self.weighting_strategies.contains_key(name) {
+                return Err(CommonError::InvalidInputError(format!(
+                    "no weighting strategy registered as {name}"
+                )));
+            }
+        }
+        if let Some(c) = rule
+            .chambers
+            .iter()
+            .find(|c| !(0.0..=1.0).contains(&c.threshold))
+        {
+            return Err(CommonError::InvalidInputError(format!(
+                "chamber {} threshold must be in [0, 1]",
+                c.chamber
+            )));
+        }
+        self.weight_rules
+            .put(&ProposalId(kind.to_string()), &(kind.to_string(), rule))
+    }
+
+    /// Return `kind` proposals to one vote per member, or the default rule.
+    pub fn clear_weight_rule(&mut self, kind: &str) -> Result<(), CommonError> {
+        self.weight_rules.remove(&ProposalId(kind.to_string()))
+    }
+
+    /// Rules keyed by proposal kind.
+    pub fn weight_rules(&self) -> Result<Vec<(String, WeightRule)>, CommonError> {
+        let mut rules = self.weight_rules.list()?;
+        rules.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(rules)
+    }
+
+    /// Rule that weights votes on `proposal`, if any.
+    pub fn weight_rule_for(&self, proposal: &Proposal) -> Result<Option<WeightRule>, CommonError> {
+        let kind = proposal_kind(&proposal.proposal_type);
+        let rule = match self.weight_rules.get(&ProposalId(kind.to_string()))? {
+            Some(rule) => Some(rule),
+            None => self
+                .weight_rules
+                .get(&ProposalId(DEFAULT_RULE_KIND.to_string()))?,
+        };
+        Ok(rule.map(|(_, rule)| rule))
+    }
+
+    /// Size a member's profile from its cooperative profile, keeping any
+    /// chambers already recorded.
+    pub fn record_cooperative_profile(
+        &mut self,
+        profile: &CooperativeProfile,
+    ) -> Result<MemberProfile, CommonError> {
+        self.ensure_weighted_member(&profile.did)?;
+        let mut member = self.member_profile(&profile.did)?;
+        member.member_count = MemberProfile::from_cooperative_profile(profile).member_count;
+        self.member_profiles
+            .put(&ProposalId(member.did.to_string()), &member)?;
+        Ok(member)
+    }
+
+    /// Update a member's chambers and size from a membership credential
+    /// signed by a trusted issuer.
+    pub fn record_weighting_credential(
+        &mut self,
+        credential: &Credential,
+    ) -> Result<MemberProfile, CommonError> {
+        if !self
+            .weighting_config
+            .trusted_issuers
+            .contains(&credential.issuer)
+        {
+            return Err(CommonError::PermissionDenied(format!(
+                "{} is not a trusted weighting credential issuer",
+                credential.issuer
+            )));
+        }
+        self.ensure_weighted_member(&credential.holder)?;
+        let mut member = self.member_profile(&credential.holder)?;
+        member.apply_credential(credential)?;
+        self.member_profiles
+            .put(&ProposalId(member.did.to_string()), &member)?;
+        Ok(member)
+    }
+
+    /// Recorded profile of `did`, or the one-person default.
+    pub fn member_profile(&self, did: &Did) -> Result<MemberProfile, CommonError> {
+        Ok(self
+            .member_profiles
+            .get(&ProposalId(did.to_string()))?
+            .unwrap_or_else(|| MemberProfile::new(did.clone())))
+    }
+
+    /// Weighted outcome of a proposal's current votes, or `None` when no
+    /// rule applies to it.
+    pub fn weighted_tally(
+        &self,
+        proposal_id: &ProposalId,
+    ) -> Result<Option<WeightedTally>, CommonError> {
+        let proposal = self.get_proposal(proposal_id)?.ok_or_else(|| {
+            CommonError::ResourceNotFound(format!("Proposal {} not found", proposal_id.0))
+        })?;
+        let Some(rule) = self.weight_rule_for(&proposal)? else {
+            return Ok(None);
+        };
+        let strategy: Arc<dyn WeightingStrategy> = match &rule.scheme {
+            WeightingScheme::Equal => Arc::new(EqualWeights),
+            WeightingScheme::MembershipSize { cap } => {
+                Arc::new(MembershipSizeWeights { cap: *cap })
+            }
+            WeightingScheme::Custom(name) => self
+                .weighting_strategies
+                .get(name)
+                .cloned()
+                .ok_or_else(|| {
+                    CommonError::InvalidInputError(format!(
+                        "no weighting strategy registered as {name}"
+                    ))
+                })?,
+        };
+
+        let mut members: Vec<&Did> = self.members.iter().collect();
+        members.sort_by_key(|d| d.to_string());
+        let mut ballots = Vec::new();
+        for member in members {
+            if let Some(option) = self.delegations.resolve(member, &proposal).option() {
+                ballots.push((self.member_profile(member)?, option));
+            }
+        }
+
+        let threshold = f64::from(proposal.threshold.unwrap_or(self.threshold));
+        let (yes, no, abstain) = sum_votes(strategy.as_ref(), &ballots);
+        let mut approved = share_met(yes, no, abstain, threshold);
+        let mut chambers = Vec::new();
+        for requirement in &rule.chambers {
+            let in_chamber: Vec<_> = ballots
+                .iter()
+                .filter(|(p, _)| p.chambers.contains(&requirement.chamber))
+                .cloned()
+                .collect();
+            let (yes, no, abstain) = sum_votes(strategy.as_ref(), &in_chamber);
+            let chamber_approved = in_chamber.len() >= requirement.min_voters
+                && share_met(yes, no, abstain, requirement.threshold);
+            approved &= chamber_approved;
+            chambers.push(ChamberTally {
+                chamber: requirement.chamber.clone(),
+                yes,
+                no,
+                abstain,
+                voters: in_chamber.len(),
+                approved: chamber_approved,
+            });
+        }
+
+        Ok(Some(WeightedTally {
+            proposal_id: proposal_id.clone(),
+            rule,
+            yes,
+            no,
+            abstain,
+            voters: ballots.len(),
+            chambers,
+            approved,
+        }))
+    }
+
+    fn ensure_weighted_member(&self, did: &Did) -> Result<(), CommonError> {
+        if self.members.contains(did) {
+            Ok(())
+        } else {
+            Err(CommonError::InvalidInputError(format!(
+                "{did} is not a federation member"
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ProposalStatus, ProposalSubmission, ProposalType};
+    use icn_common::FixedTimeProvider;
+    use icn_identity::{did_key_from_verifying_key, generate_ed25519_keypair};
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    fn sized(did: &str, count: u64) -> MemberProfile {
+        MemberProfile {
+            member_count: count,
+            ..MemberProfile::new(Did::from_str(did).unwrap())
+        }
+    }
+
+    #[test]
+    fn membership_size_weights_respect_cap() {
+        let profiles = vec![
+            sized("did:icn:big", 900),
+            sized("did:icn:mid", 60),
+            sized("did:icn:small", 40),
+        ];
+        let weights = MembershipSizeWeights { cap: 0.5 }.weights(&profiles);
+        let total: f64 = weights.iter().sum();
+        assert!((weights[0] / total - 0.5).abs() < 1e-9);
+        assert!((weights[1] / weights[2] - 1.5).abs() < 1e-9);
+
+        // Two voters cannot stay under a 40% cap, so both sit at the cap.
+        let weights = MembershipSizeWeights { cap: 0.4 }.weights(&profiles[..2]);
+        assert_eq!(weights[0], weights[1]);
+    }
+
+    #[test]
+    fn chambers_must_each_approve() {
+        let mut gov = GovernanceModule::new();
+        let (issuer_sk, issuer_vk) = generate_ed25519_keypair();
+        let issuer = Did::from_str(&did_key_from_verifying_key(&issuer_vk)).unwrap();
+        gov.set_weighting_config(WeightingConfig {
+            trusted_issuers: vec![issuer.clone()],
+        });
+        let voters: Vec<Did> = ["workers", "workers", "consumers"]
+            .iter()
+            .enumerate()
+            .map(|(i, chamber)| {
+                let did = Did::from_str(&format!("did:icn:coop{i}")).unwrap();
+                gov.add_member(did.clone());
+                let mut credential = Credential::new(
+                    issuer.clone(),
+                    did.clone(),
+                    HashMap::from([(CHAMBER_CLAIM.to_string(), chamber.to_string())]),
+                    None,
+                );
+                credential.sign_claims(&issuer_sk);
+                gov.record_weighting_credential(&credential).unwrap();
+                did
+            })
+            .collect();
+        gov.set_weight_rule(
+            DEFAULT_RULE_KIND,
+            WeightRule {
+                scheme: WeightingScheme::Equal,
+                chambers: ["workers", "consumers"]
+                    .iter()
+                    .map(|c| ChamberRequirement {
+                        chamber: c.to_string(),
+                        threshold: 0.5,
+                        min_voters: 1,
+                    })
+                    .collect(),
+            },
+        )
+        .unwrap();
+
+        let tp = FixedTimeProvider::new(1_000);
+        let pid = gov
+            .submit_proposal(
+                ProposalSubmission {
+                    proposer: voters[0].clone(),
+                    proposal_type: ProposalType::GenericText("adopt".into()),
+                    description: "adopt".into(),
+                    duration_secs: 60,
+                    quorum: None,
+                    threshold: None,
+                    content_cid: None,
+                    timelock_delay: None,
+                },
+                &tp,
+            )
+            .unwrap();
+        gov.open_voting(&pid).unwrap();
+        gov.cast_vote(voters[0].clone(), &pid, VoteOption::Yes, &tp)
+            .unwrap();
+        gov.cast_vote(voters[1].clone(), &pid, VoteOption::Yes, &tp)
+            .unwrap();
+        gov.cast_vote(voters[2].clone(), &pid, VoteOption::No, &tp)
+            .unwrap();
+
+        let tally = gov.weighted_tally(&pid).unwrap().unwrap();
+        assert!(tally.yes > tally.no);
+        assert!(tally.chambers[0].approved);
+        assert!(!tally.chambers[1].approved);
+        assert!(!tally.approved);
+        let (status, _) = gov.close_voting_period(&pid, &tp).unwrap();
+        assert_eq!(status, ProposalStatus::Rejected);
+    }
+}
@@ -1,6 +1,6 @@
 use icn_common::{Did, FixedTimeProvider};
 use icn_governance::{
-    GovernanceModule, ProposalStatus, ProposalSubmission, ProposalType, VoteOption,
+    DelegationScope, GovernanceModule, ProposalStatus, ProposalSubmission, ProposalType, VoteOption,
 };
 use std::str::FromStr;
 
@@ -61,7 +61,7 @@ fn delegation_affects_tally() {
         .unwrap();
     gov.open_voting(&pid2).unwrap();
     gov.delegate_vote(alice.clone(), bob.clone()).unwrap();
-    gov.revoke_delegation(alice.clone());
+    gov.revoke_delegation(alice.clone()).unwrap();
     gov.cast_vote(bob.clone(), &pid2, VoteOption::Yes, &time_provider)
         .unwrap();
     gov.cast_vote(carol.clone(), &pid2, VoteOption::No, &time_provider)
@@ -70,3 +70,72 @@ fn delegation_affects_tally() {
     let (status2, _) = gov.close_voting_period(&pid2, &time_provider).unwrap();
     assert_eq!(status2, ProposalStatus::Rejected);
 }
+
+#[test]
+fn scoped_transitive_delegation_weights() {
+    let time_provider = FixedTimeProvider::new(1640995200);
+    let mut gov = GovernanceModule::new();
+    let dids: Vec<Did> = ["alice", "bob", "carol", "dave"]
+        .iter()
+        .map(|n| Did::from_str(&format!("did:example:{n}")).unwrap())
+        .collect();
+    for did in &dids {
+        gov.add_member(did.clone());
+    }
+    let (alice, bob, carol, dave) = (&dids[0], &dids[1], &dids[2], &dids[3]);
+    gov.set_quorum(3);
+    gov.set_threshold(0.5);
+
+    let pid = gov
+        .submit_proposal(
+            ProposalSubmission {
+                proposer: alice.clone(),
+                proposal_type: ProposalType::GenericText("climate plan".into()),
+                description: "desc".into(),
+                duration_secs: 60,
+                quorum: None,
+                threshold: None,
+                content_cid: None,
+                timelock_delay: None,
+            },
+            &time_provider,
+        )
+        .unwrap();
+    gov.tag_proposal(&pid, vec!["climate".into()]).unwrap();
+    gov.open_voting(&pid).unwrap();
+
+    // alice -> bob (everything), bob -> carol (climate only), so alice's
+    // vote follows the chain to carol.
+    gov.delegate_vote(alice.clone(), bob.clone()).unwrap();
+    gov.delegate_vote_scoped(
+        bob.clone(),
+        carol.clone(),
+        DelegationScope::Tag("climate".into()),
+        Some(1640995200 + 3600),
+        1640995200,
+    )
+    .unwrap();
+    assert!(gov
+        .delegate_vote_scoped(
+            carol.clone(),
+            alice.clone(),
+            DelegationScope::Tag("climate".into()),
+            None,
+            1640995200,
+        )
+        .is_err());
+
+    gov.cast_vote(carol.clone(), &pid, VoteOption::Yes, &time_provider)
+        .unwrap();
+    gov.cast_vote(dave.clone(), &pid, VoteOption::No, &time_provider)
+        .unwrap();
+
+    let weights = gov.effective_voting_weights(&pid).unwrap();
+    assert_eq!(weights[carol], 3);
+    assert_eq!(weights[dave], 1);
+    assert_eq!(gov.delegation_graph().len(), 2);
+
+    let (status, (yes, no, _)) = gov.close_voting_period(&pid, &time_provider).unwrap();
+    assert_eq!((yes, no), (3, 1));
+    assert_eq!(status, ProposalStatus::Accepted);
+}
@@ -177,4 +177,58 @@ mod tests {
         let prop = gov3.get_proposal(&pid).unwrap().unwrap();
         assert_eq!(prop.votes.len(), 1);
     }
+    #[tokio::test]
+    async fn sled_voter_commitments_persist() {
+        let dir = tempdir().unwrap();
+        let member = Did::from_str("did:example:alice").unwrap();
+        let commitment = "0".repeat(64);
+        let mut gov = GovernanceModule::new_sled(dir.path().to_path_buf()).unwrap();
+        gov.add_member(member.clone());
+        gov.register_voter_commitment(member.clone(), &commitment)
+            .unwrap();
+        drop(gov);
+
+        let gov2 = GovernanceModule::new_sled(dir.path().to_path_buf()).unwrap();
+        assert_eq!(gov2.voter_commitment(&member).unwrap(), Some(commitment));
+    }
+
+    #[tokio::test]
+    async fn sled_delegations_and_tags_persist() {
+        let time_provider = FixedTimeProvider::new(1640995200);
+        let dir = tempdir().unwrap();
+        let alice = Did::from_str("did:example:alice").unwrap();
+        let bob = Did::from_str("did:example:bob").unwrap();
+        let carol = Did::from_str("did:example:carol").unwrap();
+        let mut gov = GovernanceModule::new_sled(dir.path().to_path_buf()).unwrap();
+        for did in [&alice, &bob, &carol] {
+            gov.add_member(did.clone());
+        }
+        let pid = gov
+            .submit_proposal(
+                ProposalSubmission {
+                    proposer: alice.clone(),
+                    proposal_type: ProposalType::GenericText("tagged".into()),
+                    description: "desc".into(),
+                    duration_secs: 60,
+                    quorum: None,
+                    threshold: None,
+                    content_cid: None,
+                    timelock_delay: None,
+                },
+                &time_provider,
+            )
+            .unwrap();
+        gov.tag_proposal(&pid, vec!["climate".into()]).unwrap();
+        gov.delegate_vote(alice.clone(), bob.clone()).unwrap();
+        gov.delegate_vote(carol.clone(), bob.clone()).unwrap();
+        gov.revoke_delegation(carol.clone()).unwrap();
+        drop(gov);
+
+        let gov2 = GovernanceModule::new_sled(dir.path().to_path_buf()).unwrap();
+        assert_eq!(gov2.proposal_tags(&pid), vec!["climate".to_string()]);
+        let graph = gov2.delegation_graph();
+        assert_eq!(graph.len(), 1);
+        assert_eq!(graph[0].delegator, alice);
+        assert_eq!(graph[0].delegate, bob);
+    }
 }
@@ -9,7 +9,7 @@ use icn_common::{Cid, CommonError, Did};
 use crate::zk::Groth16KeyManager;
 use crate::zk::{Groth16Circuit, Groth16Prover};
 use crate::zk::{ZkError, ZkProver};
-use crate::{sign_message, verify_signature, SignatureBytes};
+use crate::{sign_message, verify_signature, SignatureBytes, StatusListEntry};
 use icn_common::ZkCredentialProof;
 
 /// A verifiable credential issued by a DID subject to selective disclosure.
@@ -29,6 +29,63 @@ pub struct Credential {
     pub private_fields: HashSet<String>,
     /// Optional expiration timestamp as seconds since the Unix epoch.
     pub expires_at: Option<u64>,
+    /// Optional position in the issuer's revocation and suspension lists.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<StatusListEntry>,
+    /// Cooperative policy the credential was issued under.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issued_under: Option<IssuanceScope>,
+}
+
+/// The cooperative and credential type whose issuer policy a credential was
+/// issued under.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssuanceScope {
+    pub cooperative_id: String,
+    pub credential_type: String,
+}
+
+impl IssuanceScope {
+    /// Length-prefixed bytes covered by claim signatures, together with the
+    /// schema the policy was checked against.
+    fn signable_bytes(&self, schema: Option<&Cid>) -> Vec<u8> {
+        let schema = schema.map(|c| c.to_string()).unwrap_or_default();
+        let mut bytes = Vec::new();
+        for part in [self.cooperative_id.as_str(), &self.credential_type, &schema] {
+            bytes.extend_from_slice(&(part.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(part.as_bytes());
+        }
+        bytes
+    }
+}
+
+/// Bytes signed for one claim. Expiry, status entry and issuance scope are
+/// covered so none can be stripped or altered.
+#[allow(clippy::too_many_arguments)]
+fn claim_signing_bytes(
+    issuer: &Did,
+    holder: &Did,
+    claim: &str,
+    value: &str,
+    expires_at: Option<u64>,
+    status: Option<&StatusListEntry>,
+    issued_under: Option<&IssuanceScope>,
+    schema: Option<&Cid>,
+) -> Vec<u8> {
+    let mut bytes = issuer.to_string().into_bytes();
+    bytes.extend_from_slice(holder.to_string().as_bytes());
+    bytes.extend_from_slice(claim.as_bytes());
+    bytes.extend_from_slice(value.as_bytes());
+    if let Some(expires_at) = expires_at {
+        bytes.extend_from_slice(&expires_at.to_le_bytes());
+    }
+    if let Some(status) = status {
+        bytes.extend_from_slice(&status.signable_bytes());
+    }
+    if let Some(scope) = issued_under {
+        bytes.extend_from_slice(&scope.signable_bytes(schema));
+    }
+    bytes
 }
 
 impl Credential {
@@ -47,20 +104,29 @@ impl Credential {
             signatures: HashMap::new(),
             private_fields: HashSet::new(),
             expires_at: None,
+            status: None,
+            issued_under: None,
         }
     }
 
+    /// Bytes the issuer signs for `claim` with the given `value`.
+    pub fn claim_signing_bytes(&self, claim: &str, value: &str) -> Vec<u8> {
+        claim_signing_bytes(
+            &self.issuer,
+            &self.holder,
+            claim,
+            value,
+            self.expires_at,
+            self.status.as_ref(),
+            self.issued_under.as_ref(),
+            self.schema.as_ref(),
+        )
+    }
+
     /// Sign all claims individually using the issuer's key.
     pub fn sign_claims(&mut self, key: &SigningKey) {
         for (k, v) in &self.claims {
-            let mut bytes = self.issuer.to_string().into_bytes();
-            bytes.extend_from_slice(self.holder.to_string().as_bytes());
-            bytes.extend_from_slice(k.as_bytes());
-            bytes.extend_from_slice(v.as_bytes());
-            // Include expires_at in the signature to prevent tampering
-            if let Some(expires_at) = self.expires_at {
-                bytes.extend_from_slice(&expires_at.to_le_bytes());
-            }
+            let bytes = self.claim_signing_bytes(k, v);
             let sig = sign_message(key, &bytes);
             self.signatures
                 .insert(k.clone(), SignatureBytes::from_ed_signature(sig));
@@ -95,14 +161,7 @@ impl Credential {
         let sig = self.signatures.get(claim).ok_or_else(|| {
             CommonError::IdentityError(format!("missing signature for claim: {claim}"))
         })?;
-        let mut bytes = self.issuer.to_string().into_bytes();
-        bytes.extend_from_slice(self.holder.to_string().as_bytes());
-        bytes.extend_from_slice(claim.as_bytes());
-        bytes.extend_from_slice(value.as_bytes());
-        // Include expires_at in signature verification to prevent tampering
-        if let Some(expires_at) = self.expires_at {
-            bytes.extend_from_slice(&expires_at.to_le_bytes());
-        }
+        let bytes = self.claim_signing_bytes(claim, value);
         let ed = sig.to_ed_signature()?;
         if verify_signature(key, &bytes, &ed) {
             Ok(())
@@ -130,6 +189,8 @@ impl Credential {
             schema: self.schema.clone(),
             signatures,
             expires_at: self.expires_at,
+            status: self.status.clone(),
+            issued_under: self.issued_under.clone(),
         }
     }
 
@@ -169,6 +230,12 @@ pub struct DisclosedCredential {
     pub signatures: HashMap<String, SignatureBytes>,
     /// Optional expiration timestamp carried from the original credential.
     pub expires_at: Option<u64>,
+    /// Status list entry carried from the original credential.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<StatusListEntry>,
+    /// Issuance scope carried from the original credential.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issued_under: Option<IssuanceScope>,
 }
 
 impl DisclosedCredential {
@@ -185,14 +252,16 @@ impl DisclosedCredential {
             let sig = self.signatures.get(k).ok_or_else(|| {
                 CommonError::IdentityError(format!("missing signature for claim: {k}"))
             })?;
-            let mut bytes = self.issuer.to_string().into_bytes();
-            bytes.extend_from_slice(self.holder.to_string().as_bytes());
-            bytes.extend_from_slice(k.as_bytes());
-            bytes.extend_from_slice(v.as_bytes());
-            // Include expires_at in signature verification to prevent tampering
-            if let Some(expires_at) = self.expires_at {
-                bytes.extend_from_slice(&expires_at.to_le_bytes());
-            }
+            let bytes = claim_signing_bytes(
+                &self.issuer,
+                &self.holder,
+                k,
+                v,
+                self.expires_at,
+                self.status.as_ref(),
+                self.issued_under.as_ref(),
+                self.schema.as_ref(),
+            );
             let ed = sig.to_ed_signature()?;
             if !verify_signature(key, &bytes, &ed) {
                 return Err(CommonError::IdentityError(format!(
@@ -260,6 +329,10 @@ impl CredentialIssuer {
         private_fields: Option<&[&str]>,
     ) -> Result<(Credential, Option<ZkCredentialProof>), ZkError> {
         let mut cred = Credential::new(self.did.clone(), holder, claims, schema);
+        cred.status = self
+            .revocation_registry
+            .as_ref()
+            .and_then(|reg| reg.allocate_status());
         if let Some(pf) = private_fields {
             cred.private_fields = pf.iter().map(|f| f.to_string()).collect();
         }
@@ -280,7 +353,10 @@ impl CredentialIssuer {
         if let Some(ref reg) = self.revocation_registry {
             if let Ok(bytes) = serde_json::to_vec(&cred) {
                 let cid = Cid::new_v1_sha256(0x71, &bytes);
-                reg.record(cid);
+                match &cred.status {
+                    Some(status) => reg.record_with_status(cid, status),
+                    None => reg.record(cid),
+                }
             }
         }
         crate::metrics::CREDENTIALS_ISSUED.inc();
@@ -393,4 +469,34 @@ mod tests {
         );
         assert!(verifier.verify(&proof).unwrap());
     }
+
+    #[test]
+    fn issued_credentials_carry_a_signed_status_entry() {
+        use crate::{RevocationRegistry, StatusListRegistry};
+
+        let (sk, pk) = generate_ed25519_keypair();
+        let issuer_did = Did::new("key", "issuer");
+        let registry = Arc::new(StatusListRegistry::in_memory(issuer_did.clone()));
+        let issuer =
+            CredentialIssuer::new(issuer_did, sk).with_revocation_registry(registry.clone());
+        let mut claims = HashMap::new();
+        claims.insert("role".to_string(), "member".to_string());
+        let (cred, _) = issuer
+            .issue(Did::new("key", "holder"), claims, None, None, None, None)
+            .unwrap();
+
+        let status = cred.status.clone().expect("status entry");
+        cred.verify_claim("role", &pk).unwrap();
+        let cid = Cid::new_v1_sha256(0x71, &serde_json::to_vec(&cred).unwrap());
+        assert_eq!(registry.entry(&cid), Some(status.clone()));
+        assert!(registry.revoke(&cid));
+
+        // Moving the credential to another slot breaks its signatures.
+        let mut moved = cred;
+        moved.status = Some(StatusListEntry {
+            index: status.index ^ 1,
+            ..status
+        });
+        assert!(moved.verify_claim("role", &pk).is_err());
+    }
 }
@@ -0,0 +1,1359 @@
+//! Standards-based credential interchange
+//!
+//! ICN credentials carry per-claim Ed25519 signatures that only ICN software
+//! understands. This module maps [`Credential`] to and from the W3C
+//! Verifiable Credentials 2.0 data model and secures the result in one of
+//! three standard formats:
+//!
+//! - **VC-JWT**: the credential is the payload of an EdDSA-signed JWS.
+//! - **SD-JWT VC**: each claim is disclosed separately, so a holder can
+//!   reveal a subset and optionally bind the presentation to their key.
+//! - **Data Integrity** with the `eddsa-rdfc-2022` cryptosuite: an embedded
+//!   proof over the RDF-canonicalized credential.
+//!
+//! On import the proof is checked against the issuer's key before the
+//! credential is mapped. The mapped [`Credential`] has no ICN claim
+//! signatures, so [`ImportedCredential`] keeps the verified original with it.
+//!
+//! Data Integrity canonicalization supports the subset of JSON-LD that
+//! credentials use when they rely only on the VC 2.0 context. Documents with
+//! other contexts are rejected rather than canonicalized incorrectly.
+
+use crate::{
+    sign_message, verify_signature, Credential, DidResolver, SignatureBytes, SigningKey,
+    StatusListEntry, VerifyingKey,
+};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use icn_common::{parse_cid_from_string, CommonError, Did};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+/// Base context of W3C Verifiable Credentials 2.0.
+pub const VC_V2_CONTEXT: &str = "https://www.w3.org/ns/credentials/v2";
+
+/// Data Integrity context, already included in [`VC_V2_CONTEXT`].
+const DATA_INTEGRITY_CONTEXT: &str = "https://w3id.org/security/data-integrity/v2";
+
+/// Cryptosuite implemented by [`add_data_integrity_proof`].
+pub const EDDSA_RDFC_2022: &str = "eddsa-rdfc-2022";
+
+/// `vct` of SD-JWT credentials without a schema.
+const DEFAULT_VCT: &str = "VerifiableCredential";
+
+/// Payload claims of an SD-JWT that are never mapped to credential claims.
+const SD_JWT_RESERVED: &[&str] = &[
+    "iss", "sub", "iat", "nbf", "exp", "vct", "status", "cnf", "_sd", "_sd_alg",
+];
+
+/// Standard formats a credential can be exported to or imported from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CredentialFormat {
+    #[serde(rename = "vc-jwt")]
+    VcJwt,
+    #[serde(rename = "sd-jwt")]
+    SdJwt,
+    #[serde(rename = "eddsa-rdfc-2022")]
+    DataIntegrity,
+}
+
+impl fmt::Display for CredentialFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CredentialFormat::VcJwt => "vc-jwt",
+            CredentialFormat::SdJwt => "sd-jwt",
+            CredentialFormat::DataIntegrity => EDDSA_RDFC_2022,
+        })
+    }
+}
+
+impl FromStr for CredentialFormat {
+    type Err = CommonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "vc-jwt" | "jwt" => Ok(CredentialFormat::VcJwt),
+            "sd-jwt" => Ok(CredentialFormat::SdJwt),
+            "eddsa-rdfc-2022" | "data-integrity" => Ok(CredentialFormat::DataIntegrity),
+            other => Err(CommonError::InvalidInputError(format!(
+                "unknown credential format '{other}': expected vc-jwt, sd-jwt or eddsa-rdfc-2022"
+            ))),
+        }
+    }
+}
+
+/// A credential whose external proof has been verified.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportedCredential {
+    pub format: CredentialFormat,
+    /// The credential mapped to the ICN model, without claim signatures.
+    pub credential: Credential,
+    /// The verified W3C document or disclosed SD-JWT payload.
+    pub document: Value,
+}
+
+/// Verification method used for `did`'s signing key.
+pub fn verification_method(did: &Did) -> String {
+    let mut did = did.clone();
+    did.fragment = None;
+    if did.method == "key" {
+        format!("{did}#{}", did.id_string)
+    } else {
+        format!("{did}#key1")
+    }
+}
+
+/// Map a credential to the W3C data model, leaving out private claims.
+pub fn to_w3c(credential: &Credential, issued_at: u64) -> Result<Value, CommonError> {
+    let claims = credential
+        .claims
+        .iter()
+        .filter(|(k, _)| !credential.private_fields.contains(*k))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    w3c_document(credential, claims, issued_at)
+}
+
+fn w3c_document(
+    credential: &Credential,
+    claims: BTreeMap<String, String>,
+    issued_at: u64,
+) -> Result<Value, CommonError> {
+    let mut subject = Map::new();
+    subject.insert("id".into(), json!(credential.holder.to_string()));
+    for (k, v) in claims {
+        if k == "id" {
+            return Err(CommonError::InvalidInputError(
+                "claim name 'id' is reserved".into(),
+            ));
+        }
+        subject.insert(k, json!(v));
+    }
+    let mut document = json!({
+        "@context": [VC_V2_CONTEXT],
+        "type": ["VerifiableCredential"],
+        "issuer": credential.issuer.to_string(),
+        "validFrom": rfc3339(issued_at)?,
+        "credentialSubject": subject,
+    });
+    if let Some(expires_at) = credential.expires_at {
+        document["validUntil"] = json!(rfc3339(expires_at)?);
+    }
+    if let Some(schema) = &credential.schema {
+        document["credentialSchema"] = json!({
+            "id": format!("urn:cid:{schema}"),
+            "type": "JsonSchema",
+        });
+    }
+    if let Some(status) = &credential.status {
+        document["credentialStatus"] = json!(["revocation", "suspension"]
+            .iter()
+            .map(|purpose| json!({
+                "type": "BitstringStatusListEntry",
+                "statusPurpose": purpose,
+                "statusListIndex": status.index.to_string(),
+                "statusListCredential": status_list_url(&credential.issuer, &status.status_list, purpose),
+            }))
+            .collect::<Vec<_>>());
+    }
+    Ok(document)
+}
+
+/// Map a W3C credential (VC 1.1 or 2.0) to the ICN model.
+///
+/// Claim values that are not strings are kept as their JSON encoding.
+pub fn from_w3c(document: &Value) -> Result<Credential, CommonError> {
+    let issuer = match &document["issuer"] {
+        Value::String(s) => parse_did(s)?,
+        Value::Object(o) => parse_did(o.get("id").and_then(Value::as_str).unwrap_or_default())?,
+        _ => return Err(invalid("credential has no issuer")),
+    };
+    let subject = match &document["credentialSubject"] {
+        Value::Array(subjects) if subjects.len() == 1 => &subjects[0],
+        Value::Object(_) => &document["credentialSubject"],
+        _ => return Err(invalid("credential must have exactly one subject")),
+    };
+    let holder = parse_did(
+        subject["id"]
+            .as_str()
+            .ok_or_else(|| invalid("credential subject has no id"))?,
+    )?;
+    let claims = subject
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(k, _)| k.as_str() != "id")
+        .map(|(k, v)| (k.clone(), claim_string(v)))
+        .collect();
+    let schema = first(&document["credentialSchema"])
+        .and_then(|s| s["id"].as_str())
+        .and_then(|id| id.strip_prefix("urn:cid:"))
+        .map(parse_cid_from_string)
+        .transpose()?;
+    let mut credential = Credential::new(issuer, holder, claims, schema);
+    credential.expires_at = document["validUntil"]
+        .as_str()
+        .or_else(|| document["expirationDate"].as_str())
+        .map(parse_rfc3339)
+        .transpose()?;
+    credential.status = status_from_w3c(&credential.issuer, &document["credentialStatus"])?;
+    Ok(credential)
+}
+
+fn status_from_w3c(issuer: &Did, status: &Value) -> Result<Option<StatusListEntry>, CommonError> {
+    let entries = match status {
+        Value::Array(entries) => entries.iter().collect::<Vec<_>>(),
+        Value::Null => return Ok(None),
+        entry => vec![entry],
+    };
+    let prefix = format!("{issuer}/status-lists/");
+    for entry in entries {
+        let Some(list) = entry["statusListCredential"]
+            .as_str()
+            .and_then(|url| url.strip_prefix(&prefix))
+        else {
+            continue;
+        };
+        let status_list = list.split('/').next().unwrap_or(list).to_string();
+        let index = match &entry["statusListIndex"] {
+            Value::String(s) => s.parse().ok(),
+            Value::Number(n) => n.as_u64(),
+            _ => None,
+        }
+        .ok_or_else(|| invalid("invalid statusListIndex"))?;
+        return Ok(Some(StatusListEntry { status_list, index }));
+    }
+    Ok(None)
+}
+
+/// Sign a credential in `format` with the issuer's key.
+pub fn export_credential(
+    credential: &Credential,
+    format: CredentialFormat,
+    issued_at: u64,
+    key: &SigningKey,
+) -> Result<String, CommonError> {
+    export_credential_with(credential, format, issued_at, |bytes| {
+        Ok(sign_message(key, bytes).to_bytes().to_vec())
+    })
+}
+
+/// Like [`export_credential`], with signing delegated to `sign`, which must
+/// produce an Ed25519 signature by the issuer's key.
+pub fn export_credential_with<S>(
+    credential: &Credential,
+    format: CredentialFormat,
+    issued_at: u64,
+    sign: S,
+) -> Result<String, CommonError>
+where
+    S: Fn(&[u8]) -> Result<Vec<u8>, CommonError>,
+{
+    match format {
+        CredentialFormat::VcJwt => encode_vc_jwt(&to_w3c(credential, issued_at)?, sign),
+        CredentialFormat::SdJwt => Ok(SdJwt::issue(credential, issued_at, sign)?.serialize()),
+        CredentialFormat::DataIntegrity => {
+            let document = add_data_integrity_proof(
+                to_w3c(credential, issued_at)?,
+                &verification_method(&credential.issuer),
+                issued_at,
+                sign,
+            )?;
+            serde_json::to_string_pretty(&document)
+                .map_err(|e| CommonError::SerializationError(e.to_string()))
+        }
+    }
+}
+
+/// Verify a credential in `format` and map it to the ICN model.
+///
+/// Expired or not-yet-valid credentials are rejected. SD-JWT credentials
+/// are accepted with or without key binding.
+pub fn import_credential(
+    encoded: &str,
+    format: CredentialFormat,
+    resolver: &dyn DidResolver,
+    now: u64,
+) -> Result<ImportedCredential, CommonError> {
+    let (credential, document) = match format {
+        CredentialFormat::VcJwt => {
+            let document = decode_vc_jwt(encoded.trim(), resolver, now)?;
+            (from_w3c(&document)?, document)
+        }
+        CredentialFormat::SdJwt => {
+            let document = SdJwt::parse(encoded.trim())?.verify(resolver, now, None)?;
+            (credential_from_sd_jwt(&document)?, document)
+        }
+        CredentialFormat::DataIntegrity => {
+            let document: Value = serde_json::from_str(encoded)
+                .map_err(|e| CommonError::DeserializationError(e.to_string()))?;
+            verify_data_integrity(&document, resolver)?;
+            check_validity_period(
+                document["validFrom"]
+                    .as_str()
+                    .map(parse_rfc3339)
+                    .transpose()?,
+                document["validUntil"]
+                    .as_str()
+                    .map(parse_rfc3339)
+                    .transpose()?,
+                now,
+            )?;
+            (from_w3c(&document)?, document)
+        }
+    };
+    Ok(ImportedCredential {
+        format,
+        credential,
+        document,
+    })
+}
+
+// --- VC-JWT ---
+
+/// Secure a W3C credential as a VC-JWT signed by its issuer.
+///
+/// The payload is the credential itself, as in VC-JOSE-COSE, with the
+/// registered `iss`, `sub`, `nbf` and `exp` claims added for verifiers that
+/// expect VC Data Model 1.1 JWTs.
+pub fn encode_vc_jwt<S>(document: &Value, sign: S) -> Result<String, CommonError>
+where
+    S: Fn(&[u8]) -> Result<Vec<u8>, CommonError>,
+{
+    let issuer = from_w3c(document)?.issuer;
+    let mut payload = document.clone();
+    payload["iss"] = json!(issuer.to_string());
+    if let Some(subject) = document["credentialSubject"]["id"].as_str() {
+        payload["sub"] = json!(subject);
+    }
+    if let Some(valid_from) = document["validFrom"].as_str() {
+        payload["nbf"] = json!(parse_rfc3339(valid_from)?);
+    }
+    if let Some(valid_until) = document["validUntil"].as_str() {
+        payload["exp"] = json!(parse_rfc3339(valid_until)?);
+    }
+    let header = json!({
+        "alg": "EdDSA",
+        "typ": "vc+jwt",
+        "cty": "vc",
+        "kid": verification_method(&issuer),
+    });
+    encode_jws(&header, &payload, sign)
+}
+
+/// Verify a VC-JWT and return the credential it carries.
+///
+/// Both VC-JOSE-COSE payloads and VC 1.1 payloads with a `vc` claim are
+/// accepted. The signing key must belong to the credential's issuer.
+pub fn decode_vc_jwt(
+    token: &str,
+    resolver: &dyn DidResolver,
+    now: u64,
+) -> Result<Value, CommonError> {
+    let jws = Jws::parse(token)?;
+    let mut document = match jws.payload.get("vc") {
+        Some(vc) => {
+            let mut vc = vc.clone();
+            if let Some(iss) = jws.payload.get("iss") {
+                if vc["issuer"].is_null() {
+                    vc["issuer"] = iss.clone();
+                }
+            }
+            if let Some(sub) = jws.payload.get("sub") {
+                if vc["credentialSubject"]["id"].is_null() && vc["credentialSubject"].is_object() {
+                    vc["credentialSubject"]["id"] = sub.clone();
+                }
+            }
+            vc
+        }
+        None => jws.payload.clone(),
+    };
+    let issuer = from_w3c(&document)?.issuer;
+    if let Some(iss) = jws.payload["iss"].as_str() {
+        if parse_did(iss)? != issuer {
+            return Err(invalid("JWT iss does not match the credential issuer"));
+        }
+    }
+    jws.verify(&issuer, resolver)?;
+    check_validity_period(
+        jws.payload["nbf"].as_u64(),
+        jws.payload["exp"].as_u64(),
+        now,
+    )?;
+    if let Some(obj) = document.as_object_mut() {
+        for claim in ["iss", "sub", "nbf", "exp", "iat", "jti"] {
+            obj.remove(claim);
+        }
+    }
+    Ok(document)
+}
+
+struct Jws {
+    header: Value,
+    payload: Value,
+    signing_input: String,
+    signature: Vec<u8>,
+}
+
+impl Jws {
+    fn parse(token: &str) -> Result<Self, CommonError> {
+        let mut parts = token.split('.');
+        let (Some(header), Some(payload), Some(signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid("JWT must have three parts"));
+        };
+        Ok(Self {
+            header: decode_json(header)?,
+            payload: decode_json(payload)?,
+            signing_input: format!("{header}.{payload}"),
+            signature: decode_b64(signature)?,
+        })
+    }
+
+    /// Check the signature against `signer`'s key; `kid`, if present, must
+    /// name a verification method of `signer`.
+    fn verify(&self, signer: &Did, resolver: &dyn DidResolver) -> Result<(), CommonError> {
+        match self.header["alg"].as_str() {
+            Some("EdDSA") | Some("Ed25519") => {}
+            other => {
+                return Err(invalid(&format!(
+                    "unsupported JWS algorithm {}",
+                    other.unwrap_or("(none)")
+                )))
+            }
+        }
+        if let Some(kid) = self.header["kid"].as_str() {
+            if &did_of_method(kid)? != signer {
+                return Err(invalid("JWS kid does not belong to the signer"));
+            }
+        }
+        verify_ed25519(
+            &resolver.resolve(signer)?,
+            self.signing_input.as_bytes(),
+            &self.signature,
+        )
+    }
+}
+
+fn encode_jws<S>(header: &Value, payload: &Value, sign: S) -> Result<String, CommonError>
+where
+    S: Fn(&[u8]) -> Result<Vec<u8>, CommonError>,
+{
+    let signing_input = format!("{}.{}", encode_json(header)?, encode_json(payload)?);
+    let signature = sign(signing_input.as_bytes())?;
+    Ok(format!(
+        "{signing_input}.{}",
+        URL_SAFE_NO_PAD.encode(signature)
+    ))
+}
+
+// --- SD-JWT VC ---
+
+/// An SD-JWT: the issuer-signed JWT, the disclosures a holder chose to
+/// reveal and an optional key binding JWT.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SdJwt {
+    pub jwt: String,
+    pub disclosures: Vec<String>,
+    pub key_binding: Option<String>,
+}
+
+impl SdJwt {
+    /// Issue an SD-JWT VC in which every claim is selectively disclosable.
+    pub fn issue<S>(credential: &Credential, issued_at: u64, sign: S) -> Result<Self, CommonError>
+    where
+        S: Fn(&[u8]) -> Result<Vec<u8>, CommonError>,
+    {
+        let mut disclosures = Vec::new();
+        let mut digests = Vec::new();
+        let claims: BTreeMap<_, _> = credential.claims.iter().collect();
+        for (name, value) in claims {
+            if SD_JWT_RESERVED.contains(&name.as_str()) {
+                return Err(CommonError::InvalidInputError(format!(
+                    "claim name '{name}' is reserved in SD-JWT"
+                )));
+            }
+            let mut salt = [0u8; 16];
+            rand::thread_rng().fill_bytes(&mut salt);
+            let disclosure = encode_json(&json!([URL_SAFE_NO_PAD.encode(salt), name, value]))?;
+            digests.push(disclosure_digest(&disclosure));
+            disclosures.push(disclosure);
+        }
+        digests.sort();
+        let mut payload = json!({
+            "iss": credential.issuer.to_string(),
+            "sub": credential.holder.to_string(),
+            "iat": issued_at,
+            "vct": credential
+                .schema
+                .as_ref()
+                .map(|s| format!("urn:cid:{s}"))
+                .unwrap_or_else(|| DEFAULT_VCT.to_string()),
+            "_sd": digests,
+            "_sd_alg": "sha-256",
+        });
+        if let Some(expires_at) = credential.expires_at {
+            payload["exp"] = json!(expires_at);
+        }
+        if let Some(status) = &credential.status {
+            payload["status"] = json!({
+                "status_list": {
+                    "idx": status.index,
+                    "uri": status_list_url(&credential.issuer, &status.status_list, "revocation"),
+                }
+            });
+        }
+        let header = json!({
+            "alg": "EdDSA",
+            "typ": "dc+sd-jwt",
+            "kid": verification_method(&credential.issuer),
+        });
+        Ok(Self {
+            jwt: encode_jws(&header, &payload, sign)?,
+            disclosures,
+            key_binding: None,
+        })
+    }
+
+    /// Parse the `<jwt>~<disclosure>~...~[<kb-jwt>]` serialization.
+    pub fn parse(serialized: &str) -> Result<Self, CommonError> {
+        let mut parts: Vec<&str> = serialized.split('~').collect();
+        if parts.len() < 2 {
+            return Err(invalid("SD-JWT must end with '~' or a key binding JWT"));
+        }
+        let key_binding = parts.pop().filter(|kb| !kb.is_empty()).map(str::to_string);
+        let jwt = parts.remove(0).to_string();
+        Ok(Self {
+            jwt,
+            disclosures: parts.into_iter().map(str::to_string).collect(),
+            key_binding,
+        })
+    }
+
+    /// Compact serialization.
+    pub fn serialize(&self) -> String {
+        let mut out = self.presentation_prefix();
+        if let Some(kb) = &self.key_binding {
+            out.push_str(kb);
+        }
+        out
+    }
+
+    fn presentation_prefix(&self) -> String {
+        let mut out = format!("{}~", self.jwt);
+        for disclosure in &self.disclosures {
+            out.push_str(disclosure);
+            out.push('~');
+        }
+        out
+    }
+
+    /// Keep only the disclosures of `claims`, dropping any key binding.
+    pub fn present(&self, claims: &[&str]) -> Result<Self, CommonError> {
+        let mut disclosures = Vec::new();
+        for disclosure in &self.disclosures {
+            let (_, name, _) = parse_disclosure(disclosure)?;
+            if claims.contains(&name.as_str()) {
+                disclosures.push(disclosure.clone());
+            }
+        }
+        Ok(Self {
+            jwt: self.jwt.clone(),
+            disclosures,
+            key_binding: None,
+        })
+    }
+
+    /// Bind the presentation to `audience` and `nonce` with the holder's key.
+    pub fn bind(
+        mut self,
+        holder_key: &SigningKey,
+        audience: &str,
+        nonce: &str,
+        issued_at: u64,
+    ) -> Result<Self, CommonError> {
+        let header = json!({ "alg": "EdDSA", "typ": "kb+jwt" });
+        let payload = json!({
+            "iat": issued_at,
+            "aud": audience,
+            "nonce": nonce,
+            "sd_hash": disclosure_digest(&self.presentation_prefix()),
+        });
+        self.key_binding = Some(encode_jws(&header, &payload, |bytes| {
+            Ok(sign_message(holder_key, bytes).to_bytes().to_vec())
+        })?);
+        Ok(self)
+    }
+
+    /// Verify the issuer signature, disclosures and key binding, returning
+    /// the payload with the disclosed claims in place of `_sd`.
+    ///
+    /// With `expected_binding` set to `(audience, nonce)`, a key binding JWT
+    /// signed by the `sub` DID is required.
+    pub fn verify(
+        &self,
+        resolver: &dyn DidResolver,
+        now: u64,
+        expected_binding: Option<(&str, &str)>,
+    ) -> Result<Value, CommonError> {
+        let jws = Jws::parse(&self.jwt)?;
+        match jws.header["typ"].as_str() {
+            Some("dc+sd-jwt") | Some("vc+sd-jwt") => {}
+            _ => return Err(invalid("not an SD-JWT VC")),
+        }
+        let issuer = parse_did(
+            jws.payload["iss"]
+                .as_str()
+                .ok_or_else(|| invalid("SD-JWT has no iss"))?,
+        )?;
+        jws.verify(&issuer, resolver)?;
+        check_validity_period(
+            jws.payload["nbf"].as_u64(),
+            jws.payload["exp"].as_u64(),
+            now,
+        )?;
+        match jws.payload["_sd_alg"].as_str() {
+            None | Some("sha-256") => {}
+            Some(other) => return Err(invalid(&format!("unsupported _sd_alg {other}"))),
+        }
+
+        let digests: BTreeSet<&str> = jws.payload["_sd"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        let mut payload = jws.payload.clone();
+        let object = payload
+            .as_object_mut()
+            .ok_or_else(|| invalid("SD-JWT payload is not an object"))?;
+        let mut seen = BTreeSet::new();
+        for disclosure in &self.disclosures {
+            let digest = disclosure_digest(disclosure);
+            if !digests.contains(digest.as_str()) {
+                return Err(invalid("disclosure is not committed to by the issuer"));
+            }
+            if !seen.insert(digest) {
+                return Err(invalid("disclosure repeated"));
+            }
+            let (_, name, value) = parse_disclosure(disclosure)?;
+            if SD_JWT_RESERVED.contains(&name.as_str()) || object.contains_key(&name) {
+                return Err(invalid(&format!("disclosure overwrites claim '{name}'")));
+            }
+            object.insert(name, value);
+        }
+        object.remove("_sd");
+        object.remove("_sd_alg");
+
+        match (&self.key_binding, expected_binding) {
+            (Some(kb), expected) => self.verify_key_binding(kb, &payload, resolver, expected)?,
+            (None, Some(_)) => return Err(invalid("key binding required")),
+            (None, None) => {}
+        }
+        Ok(payload)
+    }
+
+    fn verify_key_binding(
+        &self,
+        kb: &str,
+        payload: &Value,
+        resolver: &dyn DidResolver,
+        expected: Option<(&str, &str)>,
+    ) -> Result<(), CommonError> {
+        let holder = parse_did(
+            payload["sub"]
+                .as_str()
+                .ok_or_else(|| invalid("key binding needs a sub claim"))?,
+        )?;
+        let jws = Jws::parse(kb)?;
+        if jws.header["typ"].as_str() != Some("kb+jwt") {
+            return Err(invalid("not a key binding JWT"));
+        }
+        jws.verify(&holder, resolver)?;
+        if jws.payload["sd_hash"].as_str() != Some(&disclosure_digest(&self.presentation_prefix()))
+        {
+            return Err(invalid("key binding does not cover this presentation"));
+        }
+        if let Some((audience, nonce)) = expected {
+            if jws.payload["aud"].as_str() != Some(audience)
+                || jws.payload["nonce"].as_str() != Some(nonce)
+            {
+                return Err(invalid("key binding audience or nonce mismatch"));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn credential_from_sd_jwt(payload: &Value) -> Result<Credential, CommonError> {
+    let issuer = parse_did(payload["iss"].as_str().unwrap_or_default())?;
+    let holder = parse_did(
+        payload["sub"]
+            .as_str()
+            .ok_or_else(|| invalid("SD-JWT has no sub"))?,
+    )?;
+    let claims: HashMap<String, String> = payload
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(k, _)| !SD_JWT_RESERVED.contains(&k.as_str()))
+        .map(|(k, v)| (k.clone(), claim_string(v)))
+        .collect();
+    let schema = payload["vct"]
+        .as_str()
+        .and_then(|vct| vct.strip_prefix("urn:cid:"))
+        .map(parse_cid_from_string)
+        .transpose()?;
+    let mut credential = Credential::new(issuer, holder, claims, schema);
+    credential.expires_at = payload["exp"].as_u64();
+    let status_list = &payload["status"]["status_list"];
+    credential.status = status_from_w3c(
+        &credential.issuer,
+        &json!({
+            "statusListCredential": status_list["uri"],
+            "statusListIndex": status_list["idx"],
+        }),
+    )?;
+    Ok(credential)
+}
+
+fn disclosure_digest(disclosure: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(disclosure.as_bytes()))
+}
+
+fn parse_disclosure(disclosure: &str) -> Result<(String, String, Value), CommonError> {
+    match decode_json(disclosure)? {
+        Value::Array(parts) if parts.len() == 3 => match (&parts[0], &parts[1]) {
+            (Value::String(salt), Value::String(name)) => {
+                Ok((salt.clone(), name.clone(), parts[2].clone()))
+            }
+            _ => Err(invalid("malformed disclosure")),
+        },
+        _ => Err(invalid("only object property disclosures are supported")),
+    }
+}
+
+// --- Data Integrity (eddsa-rdfc-2022) ---
+
+/// Add an `eddsa-rdfc-2022` proof to an unsecured W3C credential.
+pub fn add_data_integrity_proof<S>(
+    mut document: Value,
+    verification_method: &str,
+    created: u64,
+    sign: S,
+) -> Result<Value, CommonError>
+where
+    S: Fn(&[u8]) -> Result<Vec<u8>, CommonError>,
+{
+    if document.get("proof").is_some() {
+        return Err(invalid("document is already secured"));
+    }
+    let mut proof = json!({
+        "type": "DataIntegrityProof",
+        "cryptosuite": EDDSA_RDFC_2022,
+        "created": rfc3339(created)?,
+        "verificationMethod": verification_method,
+        "proofPurpose": "assertionMethod",
+    });
+    let hash = data_integrity_hash(&document, &proof)?;
+    proof["proofValue"] = json!(multibase::encode(multibase::Base::Base58Btc, sign(&hash)?));
+    document["proof"] = proof;
+    Ok(document)
+}
+
+/// Verify the `eddsa-rdfc-2022` proof of a secured W3C credential.
+///
+/// The verification method must belong to the credential's issuer.
+pub fn verify_data_integrity(
+    document: &Value,
+    resolver: &dyn DidResolver,
+) -> Result<(), CommonError> {
+    let mut unsecured = document.clone();
+    let mut proof = unsecured
+        .as_object_mut()
+        .and_then(|o| o.remove("proof"))
+        .ok_or_else(|| invalid("document has no proof"))?;
+    if proof["type"] != "DataIntegrityProof" || proof["cryptosuite"] != EDDSA_RDFC_2022 {
+        return Err(invalid(
+            "proof is not an eddsa-rdfc-2022 Data Integrity proof",
+        ));
+    }
+    if proof["proofPurpose"] != "assertionMethod" {
+        return Err(invalid("proof purpose must be assertionMethod"));
+    }
+    let proof_value = proof
+        .as_object_mut()
+        .and_then(|p| p.remove("proofValue"))
+        .and_then(|v| v.as_str().map(str::to_string))
+        .ok_or_else(|| invalid("proof has no proofValue"))?;
+    let (base, signature) =
+        multibase::decode(&proof_value).map_err(|e| invalid(&format!("bad proofValue: {e}")))?;
+    if base != multibase::Base::Base58Btc {
+        return Err(invalid("proofValue must be base58btc"));
+    }
+    let signer = did_of_method(
+        proof["verificationMethod"]
+            .as_str()
+            .ok_or_else(|| invalid("proof has no verificationMethod"))?,
+    )?;
+    if signer != from_w3c(&unsecured)?.issuer {
+        return Err(invalid("proof is not made by the credential issuer"));
+    }
+    let hash = data_integrity_hash(&unsecured, &proof)?;
+    verify_ed25519(&resolver.resolve(&signer)?, &hash, &signature)
+}
+
+/// `SHA-256(canonical proof config) || SHA-256(canonical document)`.
+fn data_integrity_hash(document: &Value, proof: &Value) -> Result<Vec<u8>, CommonError> {
+    let mut config = proof.clone();
+    config["@context"] = document["@context"].clone();
+    let mut hash = Sha256::digest(canonicalize(&config)?.as_bytes()).to_vec();
+    hash.extend_from_slice(&Sha256::digest(canonicalize(document)?.as_bytes()));
+    Ok(hash)
+}
+
+/// Canonical N-Quads (RDFC-1.0) of a JSON-LD document using the VC 2.0
+/// context.
+///
+/// Blank nodes are labelled by their first-degree hash; documents in which
+/// two blank nodes cannot be told apart that way are rejected.
+pub fn canonicalize(document: &Value) -> Result<String, CommonError> {
+    let object = document
+        .as_object()
+        .ok_or_else(|| invalid("JSON-LD document must be an object"))?;
+    check_context(object.get("@context"))?;
+    let mut graph = RdfGraph::default();
+    graph.node(object, true)?;
+    graph.canonical_nquads()
+}
+
+fn check_context(context: Option<&Value>) -> Result<(), CommonError> {
+    let contexts = match context {
+        Some(Value::Array(contexts)) => contexts.iter().collect::<Vec<_>>(),
+        Some(context) => vec![context],
+        None => vec![],
+    };
+    if contexts.first().and_then(|c| c.as_str()) != Some(VC_V2_CONTEXT) {
+        return Err(invalid("document must use the VC 2.0 context first"));
+    }
+    for context in contexts {
+        match context.as_str() {
+            Some(VC_V2_CONTEXT) | Some(DATA_INTEGRITY_CONTEXT) => {}
+            _ => return Err(invalid(&format!("unsupported JSON-LD context {context}"))),
+        }
+    }
+    Ok(())
+}
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+const XSD_DATE_TIME: &str = "http://www.w3.org/2001/XMLSchema#dateTime";
+const CRED: &str = "https://www.w3.org/2018/credentials#";
+const SEC: &str = "https://w3id.org/security#";
+const STATUS: &str = "https://www.w3.org/ns/credentials/status#";
+const ISSUER_DEPENDENT: &str = "https://www.w3.org/ns/credentials/issuer-dependent#";
+
+/// How a term's string values are interpreted.
+#[derive(Clone, Copy)]
+enum Coercion {
+    None,
+    Id,
+    Vocab,
+    Typed(&'static str),
+}
+
+/// Term definitions of the VC 2.0 context used by credentials and proofs.
+fn term(term: &str) -> Option<(String, Coercion)> {
+    let (iri, coercion) = match term {
+        "VerifiableCredential" => (format!("{CRED}VerifiableCredential"), Coercion::None),
+        "JsonSchema" => (format!("{CRED}JsonSchema"), Coercion::None),
+        "credentialSubject" => (format!("{CRED}credentialSubject"), Coercion::Id),
+        "credentialSchema" => (format!("{CRED}credentialSchema"), Coercion::Id),
+        "credentialStatus" => (format!("{CRED}credentialStatus"), Coercion::Id),
+        "issuer" => (format!("{CRED}issuer"), Coercion::Id),
+        "validFrom" => (format!("{CRED}validFrom"), Coercion::Typed(XSD_DATE_TIME)),
+        "validUntil" => (format!("{CRED}validUntil"), Coercion::Typed(XSD_DATE_TIME)),
+        "name" => ("https://schema.org/name".into(), Coercion::None),
+        "description" => ("https://schema.org/description".into(), Coercion::None),
+        "BitstringStatusListEntry" => (format!("{STATUS}BitstringStatusListEntry"), Coercion::None),
+        "statusPurpose" => (format!("{STATUS}statusPurpose"), Coercion::None),
+        "statusListIndex" => (format!("{STATUS}statusListIndex"), Coercion::None),
+        "statusListCredential" => (format!("{STATUS}statusListCredential"), Coercion::Id),
+        "DataIntegrityProof" => (format!("{SEC}DataIntegrityProof"), Coercion::None),
+        "cryptosuite" => (
+            format!("{SEC}cryptosuite"),
+            Coercion::Typed("https://w3id.org/security#cryptosuiteString"),
+        ),
+        "created" => (
+            "http://purl.org/dc/terms/created".into(),
+            Coercion::Typed(XSD_DATE_TIME),
+        ),
+        "verificationMethod" => (format!("{SEC}verificationMethod"), Coercion::Id),
+        "proofPurpose" => (format!("{SEC}proofPurpose"), Coercion::Vocab),
+        "assertionMethod" => (format!("{SEC}assertionMethod"), Coercion::Id),
+        "proofValue" => (
+            format!("{SEC}proofValue"),
+            Coercion::Typed("https://w3id.org/security#multibase"),
+        ),
+        _ => return None,
+    };
+    Some((iri, coercion))
+}
+
+/// Expand a term or type name against the VC 2.0 context and its `@vocab`.
+fn expand(name: &str) -> Result<(String, Coercion), CommonError> {
+    let (iri, coercion) = match term(name) {
+        Some(defined) => defined,
+        None if name.contains(':') => (name.to_string(), Coercion::None),
+        None => (format!("{ISSUER_DEPENDENT}{name}"), Coercion::None),
+    };
+    check_iri(&iri)?;
+    Ok((iri, coercion))
+}
+
+fn check_iri(iri: &str) -> Result<(), CommonError> {
+    if !iri.contains(':')
+        || iri
+            .chars()
+            .any(|c| c <= ' ' || matches!(c, '<' | '>' | '"' | '{' | '}' | '|' | '^' | '`' | '\\'))
+    {
+        return Err(invalid(&format!("invalid IRI {iri:?}")));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum RdfTerm {
+    Iri(String),
+    Blank(usize),
+    Literal(String, &'static str),
+}
+
+#[derive(Default)]
+struct RdfGraph {
+    quads: Vec<[RdfTerm; 3]>,
+    blank_nodes: usize,
+}
+
+impl RdfGraph {
+    fn node(&mut self, object: &Map<String, Value>, top: bool) -> Result<RdfTerm, CommonError> {
+        let subject = match object.get("id") {
+            Some(Value::String(id)) => {
+                check_iri(id)?;
+                RdfTerm::Iri(id.clone())
+            }
+            Some(_) => return Err(invalid("id must be a string")),
+            None => {
+                self.blank_nodes += 1;
+                RdfTerm::Blank(self.blank_nodes - 1)
+            }
+        };
+        for (key, value) in object {
+            match key.as_str() {
+                "id" => {}
+                "@context" if top => {}
+                "type" => {
+                    for t in values(value) {
+                        let t = t.as_str().ok_or_else(|| invalid("type must be a string"))?;
+                        let (iri, _) = expand(t)?;
+                        self.quads.push([
+                            subject.clone(),
+                            RdfTerm::Iri(RDF_TYPE.into()),
+                            RdfTerm::Iri(iri),
+                        ]);
+                    }
+                }
+                k if k.starts_with('@') => {
+                    return Err(invalid(&format!("unsupported JSON-LD keyword {k}")))
+                }
+                k => {
+                    let (predicate, coercion) = expand(k)?;
+                    for v in values(value) {
+                        if let Some(object) = self.value(v, coercion)? {
+                            self.quads.push([
+                                subject.clone(),
+                                RdfTerm::Iri(predicate.clone()),
+                                object,
+                            ]);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(subject)
+    }
+
+    fn value(&mut self, value: &Value, coercion: Coercion) -> Result<Option<RdfTerm>, CommonError> {
+        Ok(Some(match value {
+            Value::Null => return Ok(None),
+            Value::String(s) => match coercion {
+                Coercion::Id => {
+                    check_iri(s)?;
+                    RdfTerm::Iri(s.clone())
+                }
+                Coercion::Vocab => RdfTerm::Iri(expand(s)?.0),
+                Coercion::Typed(datatype) => RdfTerm::Literal(s.clone(), datatype),
+                Coercion::None => RdfTerm::Literal(s.clone(), XSD_STRING),
+            },
+            Value::Bool(b) => RdfTerm::Literal(b.to_string(), XSD_BOOLEAN),
+            Value::Number(n) => match (n.as_i64(), n.as_u64(), n.as_f64()) {
+                (Some(i), _, _) => RdfTerm::Literal(i.to_string(), XSD_INTEGER),
+                (_, Some(u), _) => RdfTerm::Literal(u.to_string(), XSD_INTEGER),
+                (_, _, Some(f)) if f.fract() == 0.0 && f.abs() < 1e21 => {
+                    RdfTerm::Literal(format!("{f:.0}"), XSD_INTEGER)
+                }
+                (_, _, Some(f)) => RdfTerm::Literal(canonical_double(f), XSD_DOUBLE),
+                _ => return Err(invalid("unsupported number")),
+            },
+            Value::Object(object) => self.node(object, false)?,
+            Value::Array(_) => return Err(invalid("nested arrays are not supported")),
+        }))
+    }
+
+    fn canonical_nquads(self) -> Result<String, CommonError> {
+        let mut hashes = BTreeMap::new();
+        for blank in 0..self.blank_nodes {
+            let mut lines: Vec<String> = self
+                .quads
+                .iter()
+                .filter(|q| q.contains(&RdfTerm::Blank(blank)))
+                .map(|q| {
+                    nquad(q, &|b| {
+                        if b == blank {
+                            "_:a".to_string()
+                        } else {
+                            "_:z".to_string()
+                        }
+                    })
+                })
+                .collect();
+            lines.sort();
+            let hash = hex_digest(lines.concat().as_bytes());
+            if hashes.insert(hash, blank).is_some() {
+                return Err(invalid(
+                    "document has blank nodes that canonicalization cannot tell apart",
+                ));
+            }
+        }
+        let labels: HashMap<usize, String> = hashes
+            .values()
+            .enumerate()
+            .map(|(i, blank)| (*blank, format!("_:c14n{i}")))
+            .collect();
+        let lines: BTreeSet<String> = self
+            .quads
+            .iter()
+            .map(|q| nquad(q, &|b| labels[&b].clone()))
+            .collect();
+        Ok(lines.into_iter().collect())
+    }
+}
+
+fn nquad(quad: &[RdfTerm; 3], label: &dyn Fn(usize) -> String) -> String {
+    let term = |t: &RdfTerm| match t {
+        RdfTerm::Iri(iri) => format!("<{iri}>"),
+        RdfTerm::Blank(b) => label(*b),
+        RdfTerm::Literal(value, XSD_STRING) => format!("\"{}\"", escape_literal(value)),
+        RdfTerm::Literal(value, datatype) => {
+            format!("\"{}\"^^<{datatype}>", escape_literal(value))
+        }
+    };
+    format!(
+        "{} {} {} .\n",
+        term(&quad[0]),
+        term(&quad[1]),
+        term(&quad[2])
+    )
+}
+
+fn escape_literal(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            c if c < ' ' || c == '\u{7f}' => out.push_str(&format!("\\u{:04X}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Canonical `xsd:double` lexical form used by JSON-LD, e.g. `1.5E0`.
+fn canonical_double(f: f64) -> String {
+    let formatted = format!("{f:E}");
+    match formatted.split_once('E') {
+        Some((mantissa, exponent)) if !mantissa.contains('.') => {
+            format!("{mantissa}.0E{exponent}")
+        }
+        _ => formatted,
+    }
+}
+
+fn values(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(values) => values.iter().collect(),
+        value => vec![value],
+    }
+}
+
+// --- helpers ---
+
+fn invalid(msg: &str) -> CommonError {
+    CommonError::IdentityError(msg.to_string())
+}
+
+fn parse_did(s: &str) -> Result<Did, CommonError> {
+    Did::from_str(s).map_err(|e| invalid(&format!("invalid DID {s:?}: {e}")))
+}
+
+/// DID controlling the verification method `method`.
+fn did_of_method(method: &str) -> Result<Did, CommonError> {
+    let mut did = parse_did(method)?;
+    did.fragment = None;
+    Ok(did)
+}
+
+fn status_list_url(issuer: &Did, status_list: &str, purpose: &str) -> String {
+    format!("{issuer}/status-lists/{status_list}/{purpose}")
+}
+
+fn first(value: &Value) -> Option<&Value> {
+    match value {
+        Value::Array(values) => values.first(),
+        Value::Null => None,
+        value => Some(value),
+    }
+}
+
+fn claim_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn check_validity_period(
+    not_before: Option<u64>,
+    expires: Option<u64>,
+    now: u64,
+) -> Result<(), CommonError> {
+    if not_before.is_some_and(|nbf| nbf > now) {
+        return Err(invalid("credential is not yet valid"));
+    }
+    if expires.is_some_and(|exp| exp <= now) {
+        return Err(invalid("credential has expired"));
+    }
+    Ok(())
+}
+
+fn verify_ed25519(key: &VerifyingKey, message: &[u8], signature: &[u8]) -> Result<(), CommonError> {
+    let signature = SignatureBytes(signature.to_vec()).to_ed_signature()?;
+    if verify_signature(key, message, &signature) {
+        Ok(())
+    } else {
+        Err(invalid("signature verification failed"))
+    }
+}
+
+fn rfc3339(secs: u64) -> Result<String, CommonError> {
+    chrono::DateTime::from_timestamp(secs as i64, 0)
+        .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+        .ok_or_else(|| invalid("timestamp out of range"))
+}
+
+fn parse_rfc3339(s: &str) -> Result<u64, CommonError> {
+    let t = chrono::DateTime::parse_from_rfc3339(s)
+        .map_err(|e| invalid(&format!("invalid date {s:?}: {e}")))?;
+    u64::try_from(t.timestamp()).map_err(|_| invalid("date before 1970"))
+}
+
+fn hex_digest(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+fn encode_json(value: &Value) -> Result<String, CommonError> {
+    serde_json::to_vec(value)
+        .map(|bytes| URL_SAFE_NO_PAD.encode(bytes))
+        .map_err(|e| CommonError::SerializationError(e.to_string()))
+}
+
+fn decode_b64(s: &str) -> Result<Vec<u8>, CommonError> {
+    URL_SAFE_NO_PAD
+        .decode(s)
+        .map_err(|e| invalid(&format!("invalid base64url: {e}")))
+}
+
+fn decode_json(s: &str) -> Result<Value, CommonError> {
+    serde_json::from_slice(&decode_b64(s)?)
+        .map_err(|e| CommonError::DeserializationError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{did_key_from_verifying_key, generate_ed25519_keypair, KeyDidResolver};
+    use icn_common::Cid;
+
+    const NOW: u64 = 1_760_000_000;
+
+    fn credential(issuer_key: &SigningKey) -> Credential {
+        let issuer =
+            Did::from_str(&did_key_from_verifying_key(&issuer_key.verifying_key())).unwrap();
+        let (_, holder_pk) = generate_ed25519_keypair();
+        let holder = Did::from_str(&did_key_from_verifying_key(&holder_pk)).unwrap();
+        let claims = HashMap::from([
+            ("role".to_string(), "steward".to_string()),
+            ("name".to_string(), "Ada \"Lovelace\"\n".to_string()),
+        ]);
+        let mut credential = Credential::new(
+            issuer,
+            holder,
+            claims,
+            Some(Cid::new_v1_sha256(0x55, b"schema")),
+        );
+        credential.expires_at = Some(NOW + 3600);
+        credential.status = Some(StatusListEntry {
+            status_list: "status-list-0".into(),
+            index: 42,
+        });
+        credential
+    }
+
+    #[test]
+    fn vc_jwt_and_data_integrity_round_trip() {
+        let (sk, _) = generate_ed25519_keypair();
+        let original = credential(&sk);
+        for format in [CredentialFormat::VcJwt, CredentialFormat::DataIntegrity] {
+            let encoded = export_credential(&original, format, NOW, &sk).unwrap();
+            let imported = import_credential(&encoded, format, &KeyDidResolver, NOW).unwrap();
+            assert_eq!(imported.credential.claims, original.claims);
+            assert_eq!(imported.credential.issuer, original.issuer);
+            assert_eq!(imported.credential.holder, original.holder);
+            assert_eq!(imported.credential.schema, original.schema);
+            assert_eq!(imported.credential.expires_at, original.expires_at);
+            assert_eq!(imported.credential.status, original.status);
+            assert!(import_credential(&encoded, format, &KeyDidResolver, NOW + 3600).is_err());
+        }
+
+        // Tampering with a claim breaks both proofs.
+        let jwt = export_credential(&original, CredentialFormat::VcJwt, NOW, &sk).unwrap();
+        let parts: Vec<&str> = jwt.split('.').collect();
+        let mut payload = decode_json(parts[1]).unwrap();
+        payload["credentialSubject"]["role"] = json!("admin");
+        let forged = format!(
+            "{}.{}.{}",
+            parts[0],
+            encode_json(&payload).unwrap(),
+            parts[2]
+        );
+        assert!(import_credential(&forged, CredentialFormat::VcJwt, &KeyDidResolver, NOW).is_err());
+
+        let di = export_credential(&original, CredentialFormat::DataIntegrity, NOW, &sk).unwrap();
+        let mut document: Value = serde_json::from_str(&di).unwrap();
+        document["credentialSubject"]["role"] = json!("admin");
+        assert!(verify_data_integrity(&document, &KeyDidResolver).is_err());
+
+        // A different key cannot sign for the issuer.
+        let (other, _) = generate_ed25519_keypair();
+        let jwt = export_credential(&original, CredentialFormat::VcJwt, NOW, &other).unwrap();
+        assert!(import_credential(&jwt, CredentialFormat::VcJwt, &KeyDidResolver, NOW).is_err());
+    }
+
+    #[test]
+    fn canonicalization_ignores_key_order_and_labels_blank_nodes() {
+        let a = json!({
+            "@context": [VC_V2_CONTEXT],
+            "type": ["VerifiableCredential"],
+            "issuer": "did:example:issuer",
+            "credentialSubject": {"id": "did:example:holder", "degree": {"type": "BachelorDegree", "name": "BSc"}},
+            "credentialStatus": [
+                {"type": "BitstringStatusListEntry", "statusPurpose": "revocation", "statusListIndex": "1", "statusListCredential": "https://example.com/1"},
+                {"type": "BitstringStatusListEntry", "statusPurpose": "suspension", "statusListIndex": "1", "statusListCredential": "https://example.com/1"}
+            ],
+        });
+        let b = json!({
+            "credentialStatus": [
+                {"statusListCredential": "https://example.com/1", "statusListIndex": "1", "statusPurpose": "suspension", "type": "BitstringStatusListEntry"},
+                {"statusListCredential": "https://example.com/1", "statusListIndex": "1", "statusPurpose": "revocation", "type": "BitstringStatusListEntry"}
+            ],
+            "credentialSubject": {"degree": {"name": "BSc", "type": "BachelorDegree"}, "id": "did:example:holder"},
+            "issuer": "did:example:issuer",
+            "type": "VerifiableCredential",
+            "@context": VC_V2_CONTEXT,
+        });
+        let canonical = canonicalize(&a).unwrap();
+        assert_eq!(canonical, canonicalize(&b).unwrap());
+        assert!(canonical.contains(
+            "<did:example:holder> <https://www.w3.org/ns/credentials/issuer-dependent#degree> _:c14n"
+        ));
+        assert!(canonical
+            .contains("<https://www.w3.org/2018/credentials#issuer> <did:example:issuer> ."));
+
+        let mut unknown = a.clone();
+        unknown["@context"] = json!([VC_V2_CONTEXT, "https://example.com/context"]);
+        assert!(canonicalize(&unknown).is_err());
+    }
+
+    #[test]
+    fn sd_jwt_discloses_selected_claims_with_key_binding() {
+        let (sk, _) = generate_ed25519_keypair();
+        let (holder_sk, holder_pk) = generate_ed25519_keypair();
+        let mut original = credential(&sk);
+        original.holder = Did::from_str(&did_key_from_verifying_key(&holder_pk)).unwrap();
+        let issued = SdJwt::issue(&original, NOW, |bytes| {
+            Ok(sign_message(&sk, bytes).to_bytes().to_vec())
+        })
+        .unwrap();
+        let issued = SdJwt::parse(&issued.serialize()).unwrap();
+        assert_eq!(issued.disclosures.len(), 2);
+
+        let presented = issued
+            .present(&["role"])
+            .unwrap()
+            .bind(&holder_sk, "did:example:verifier", "n-1", NOW)
+            .unwrap();
+        let parsed = SdJwt::parse(&presented.serialize()).unwrap();
+        let payload = parsed
+            .verify(&KeyDidResolver, NOW, Some(("did:example:verifier", "n-1")))
+            .unwrap();
+        assert_eq!(payload["role"], "steward");
+        assert!(payload.get("name").is_none());
+        assert!(payload.get("_sd").is_none());
+        let imported = credential_from_sd_jwt(&payload).unwrap();
+        assert_eq!(imported.status, original.status);
+
+        // Replayed to another verifier or with a disclosure added afterwards.
+        assert!(parsed
+            .verify(&KeyDidResolver, NOW, Some(("did:example:other", "n-1")))
+            .is_err());
+        let mut padded = parsed.clone();
+        padded.disclosures = issued.disclosures.clone();
+        assert!(padded.verify(&KeyDidResolver, NOW, None).is_err());
+
+        // Disclosures not committed to by the issuer are rejected.
+        let mut forged = issued.present(&[]).unwrap();
+        forged.disclosures = vec![encode_json(&json!(["salt", "role", "admin"])).unwrap()];
+        assert!(forged.verify(&KeyDidResolver, NOW, None).is_err());
+        assert!(issued
+            .verify(&KeyDidResolver, NOW, Some(("a", "b")))
+            .is_err());
+    }
+}
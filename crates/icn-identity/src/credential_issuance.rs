@@ -0,0 +1,945 @@
+//! Credential issuance with published schemas and holder binding
+//!
+//! Issuers describe credential claims with a JSON Schema. A
+//! [`CredentialSchema`] is signed by its author and anchored in the DAG, and
+//! credentials reference it by the CID of that block. [`SchemaRegistry`]
+//! caches published schemas so claims can be checked when a credential is
+//! issued and again when it is verified.
+//!
+//! Issuance is a three-step exchange:
+//!
+//! 1. the issuer makes an [`IssuanceOffer`] to a holder DID, carrying the
+//!    claims, the schema and a random nonce;
+//! 2. the holder answers with an [`IssuanceRequest`] signed by its DID key
+//!    over the offer, proving it controls the DID the credential is bound to;
+//! 3. the issuer checks the request, consumes the offer and signs the
+//!    credential returned by [`IssuanceOffer::credential`].
+//!
+//! Every offer is made on behalf of a cooperative, which restricts who may
+//! issue a credential type with an [`IssuerPolicy`]. Issued credentials
+//! record the cooperative and type in a signed [`IssuanceScope`] so the
+//! policy can be checked again by [`IssuanceRegistry::check_credential`]
+//! when the credential is verified. [`IssuanceRegistry`] holds the policies
+//! and the pending offers and persists both to a JSON file.
+
+use crate::credential::{Credential, IssuanceScope};
+use crate::{
+    sign_message, verify_signature, DidResolver, SignatureBytes, SigningKey, VerifyingKey,
+};
+use icn_common::{compute_merkle_cid, Cid, CommonError, DagBlock, Did};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs::{rename, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+
+/// `type` of a [`CredentialSchema`].
+pub const CREDENTIAL_SCHEMA_TYPE: &str = "JsonSchema";
+
+/// Seconds an offer stays open, long enough for holders reached through a
+/// mediator.
+pub const DEFAULT_OFFER_TTL: u64 = 7 * 24 * 60 * 60;
+
+/// Upper bound on open offers per issuer.
+pub const MAX_PENDING_OFFERS: usize = 10_000;
+
+/// Domain separator for the bytes a holder signs to accept an offer.
+const REQUEST_DOMAIN: &[u8] = b"icn-credential-request:v1";
+
+/// A JSON Schema for credential claims, signed by its author.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CredentialSchema {
+    /// Always [`CREDENTIAL_SCHEMA_TYPE`].
+    #[serde(rename = "type")]
+    pub schema_type: String,
+    pub name: String,
+    pub version: String,
+    /// DID that signed the schema.
+    pub author: Did,
+    /// JSON Schema the claims object must satisfy.
+    pub json_schema: Value,
+    pub created_at: u64,
+    /// Author signature over [`CredentialSchema::signable_bytes`].
+    pub signature: SignatureBytes,
+}
+
+impl CredentialSchema {
+    /// Unsigned schema. `json_schema` must compile and describe an object.
+    pub fn new(
+        author: Did,
+        name: &str,
+        version: &str,
+        json_schema: Value,
+        created_at: u64,
+    ) -> Result<Self, CommonError> {
+        if name.trim().is_empty() || version.trim().is_empty() {
+            return Err(CommonError::InvalidInputError(
+                "schema name and version are required".into(),
+            ));
+        }
+        if json_schema.get("type").and_then(Value::as_str) != Some("object") {
+            return Err(CommonError::InvalidInputError(
+                "credential schema must have \"type\": \"object\"".into(),
+            ));
+        }
+        compile(&json_schema)?;
+        Ok(Self {
+            schema_type: CREDENTIAL_SCHEMA_TYPE.into(),
+            name: name.into(),
+            version: version.into(),
+            author,
+            json_schema,
+            created_at,
+            signature: SignatureBytes(Vec::new()),
+        })
+    }
+
+    /// Bytes covered by the author signature.
+    pub fn signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        let mut unsigned = self.clone();
+        unsigned.signature = SignatureBytes(Vec::new());
+        serde_json::to_vec(&unsigned).map_err(|e| CommonError::SerializationError(e.to_string()))
+    }
+
+    /// Sign the schema with the author's key.
+    pub fn sign(&mut self, key: &SigningKey) -> Result<(), CommonError> {
+        let bytes = self.signable_bytes()?;
+        self.signature = SignatureBytes::from_ed_signature(sign_message(key, &bytes));
+        Ok(())
+    }
+
+    /// Check the author signature.
+    pub fn verify(&self, key: &VerifyingKey) -> Result<(), CommonError> {
+        if self.schema_type != CREDENTIAL_SCHEMA_TYPE {
+            return Err(CommonError::IdentityError(format!(
+                "not a credential schema: {}",
+                self.schema_type
+            )));
+        }
+        let signature = self.signature.to_ed_signature()?;
+        if verify_signature(key, &self.signable_bytes()?, &signature) {
+            Ok(())
+        } else {
+            Err(CommonError::IdentityError(format!(
+                "schema {}@{} is not signed by {}",
+                self.name, self.version, self.author
+            )))
+        }
+    }
+
+    /// DAG block anchoring this schema.
+    pub fn to_dag_block(&self) -> Result<DagBlock, CommonError> {
+        let data =
+            serde_json::to_vec(self).map_err(|e| CommonError::SerializationError(e.to_string()))?;
+        let cid = compute_merkle_cid(
+            0x71,
+            &data,
+            &[],
+            self.created_at,
+            &self.author,
+            &None,
+            &None,
+        );
+        Ok(DagBlock {
+            cid,
+            data,
+            links: Vec::new(),
+            timestamp: self.created_at,
+            author_did: self.author.clone(),
+            signature: None,
+            scope: None,
+        })
+    }
+
+    /// CID credentials use to reference this schema.
+    pub fn cid(&self) -> Result<Cid, CommonError> {
+        Ok(self.to_dag_block()?.cid)
+    }
+
+    /// Check `claims` against the schema.
+    ///
+    /// Claim values are strings. A value is parsed as JSON when the schema
+    /// declares a property type other than `string`, so `"42"` can satisfy
+    /// `{"type": "integer"}`.
+    pub fn validate_claims(&self, claims: &HashMap<String, String>) -> Result<(), CommonError> {
+        let validator = compile(&self.json_schema)?;
+        let instance = claims_instance(&self.json_schema, claims);
+        let errors: Vec<String> = validator
+            .iter_errors(&instance)
+            .map(|e| {
+                let path = e.instance_path.to_string();
+                if path.is_empty() {
+                    e.to_string()
+                } else {
+                    format!("{path}: {e}")
+                }
+            })
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(CommonError::ValidationError(format!(
+                "claims do not match schema {}@{}: {}",
+                self.name,
+                self.version,
+                errors.join("; ")
+            )))
+        }
+    }
+}
+
+fn compile(json_schema: &Value) -> Result<jsonschema::Validator, CommonError> {
+    jsonschema::validator_for(json_schema)
+        .map_err(|e| CommonError::InvalidInputError(format!("invalid JSON Schema: {e}")))
+}
+
+fn claims_instance(json_schema: &Value, claims: &HashMap<String, String>) -> Value {
+    let properties = json_schema.get("properties");
+    let object = claims
+        .iter()
+        .map(|(name, value)| {
+            let declared = properties
+                .and_then(|p| p.get(name))
+                .and_then(|p| p.get("type"));
+            let is_string = match declared {
+                None => true,
+                Some(Value::String(t)) => t == "string",
+                Some(Value::Array(types)) => types.iter().any(|t| t == "string"),
+                Some(_) => true,
+            };
+            let value = if is_string {
+                Value::String(value.clone())
+            } else {
+                serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.clone()))
+            };
+            (name.clone(), value)
+        })
+        .collect();
+    Value::Object(object)
+}
+
+/// Published schemas, keyed by CID. Clones share the cache.
+#[derive(Clone)]
+pub struct SchemaRegistry {
+    resolver: Arc<dyn DidResolver>,
+    schemas: Arc<RwLock<HashMap<Cid, CredentialSchema>>>,
+}
+
+impl SchemaRegistry {
+    /// Registry that resolves author keys through `resolver`.
+    pub fn new(resolver: Arc<dyn DidResolver>) -> Self {
+        Self {
+            resolver,
+            schemas: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Verify and cache a published schema, returning its CID.
+    pub fn insert(&self, schema: CredentialSchema) -> Result<Cid, CommonError> {
+        schema.verify(&self.resolver.resolve(&schema.author)?)?;
+        compile(&schema.json_schema)?;
+        let cid = schema.cid()?;
+        self.schemas
+            .write()
+            .map_err(|_| CommonError::LockError("schema registry poisoned".into()))?
+            .insert(cid.clone(), schema);
+        Ok(cid)
+    }
+
+    pub fn get(&self, cid: &Cid) -> Option<CredentialSchema> {
+        self.schemas.read().ok()?.get(cid).cloned()
+    }
+
+    /// Cached schemas ordered by name and version.
+    pub fn list(&self) -> Vec<(Cid, CredentialSchema)> {
+        let mut schemas: Vec<_> = self
+            .schemas
+            .read()
+            .map(|s| s.iter().map(|(c, s)| (c.clone(), s.clone())).collect())
+            .unwrap_or_default();
+        schemas.sort_by(|a, b| (&a.1.name, &a.1.version).cmp(&(&b.1.name, &b.1.version)));
+        schemas
+    }
+
+    /// Check a credential's claims against its published schema.
+    pub fn validate(&self, credential: &Credential) -> Result<(), CommonError> {
+        let cid = credential.schema.as_ref().ok_or_else(|| {
+            CommonError::ValidationError("credential does not reference a schema".into())
+        })?;
+        self.get(cid)
+            .ok_or_else(|| CommonError::ResourceNotFound(format!("credential schema {cid}")))?
+            .validate_claims(&credential.claims)
+    }
+
+    /// Cache schemas found in `blocks`, returning the number loaded.
+    pub fn load_blocks<'a>(&self, blocks: impl IntoIterator<Item = &'a DagBlock>) -> usize {
+        blocks
+            .into_iter()
+            .filter_map(|b| serde_json::from_slice::<CredentialSchema>(&b.data).ok())
+            .filter(|schema| schema.schema_type == CREDENTIAL_SCHEMA_TYPE)
+            .filter(|schema| self.insert(schema.clone()).is_ok())
+            .count()
+    }
+}
+
+impl fmt::Debug for SchemaRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cached = self.schemas.read().map(|s| s.len()).unwrap_or_default();
+        f.debug_struct("SchemaRegistry")
+            .field("cached", &cached)
+            .finish()
+    }
+}
+
+/// Who may issue a credential type on behalf of a cooperative.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssuerPolicy {
+    pub cooperative_id: String,
+    pub credential_type: String,
+    /// DIDs allowed to issue this type.
+    pub issuers: Vec<Did>,
+    /// Schema credentials of this type must reference.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Cid>,
+    /// Longest validity, in seconds, an issuer may grant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_validity: Option<u64>,
+}
+
+impl IssuerPolicy {
+    /// Check that `issuer` may issue under `schema` for `validity` seconds.
+    pub fn check(
+        &self,
+        issuer: &Did,
+        schema: &Cid,
+        validity: Option<u64>,
+    ) -> Result<(), CommonError> {
+        self.check_issuer(issuer, schema)?;
+        if let Some(max) = self.max_validity {
+            if validity.is_none_or(|v| v > max) {
+                return Err(CommonError::PolicyDenied(format!(
+                    "{} credentials for {} may be valid for at most {max} seconds",
+                    self.credential_type, self.cooperative_id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that `issuer` may issue under `schema`.
+    pub fn check_issuer(&self, issuer: &Did, schema: &Cid) -> Result<(), CommonError> {
+        if !self.issuers.contains(issuer) {
+            return Err(CommonError::PolicyDenied(format!(
+                "{issuer} may not issue {} credentials for {}",
+                self.credential_type, self.cooperative_id
+            )));
+        }
+        if let Some(required) = &self.schema {
+            if required != schema {
+                return Err(CommonError::PolicyDenied(format!(
+                    "{} credentials for {} must use schema {required}",
+                    self.credential_type, self.cooperative_id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What an issuer offers to a holder.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OfferTerms {
+    pub holder: Did,
+    pub schema: Cid,
+    pub credential_type: String,
+    pub claims: BTreeMap<String, String>,
+    /// Cooperative the credential is issued for, subject to its policy.
+    pub cooperative_id: String,
+    /// Seconds the credential stays valid once issued.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validity: Option<u64>,
+}
+
+/// An open offer waiting for the holder's signed request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssuanceOffer {
+    pub id: String,
+    pub issuer: Did,
+    #[serde(flatten)]
+    pub terms: OfferTerms,
+    /// Challenge the holder signs to prove control of its DID.
+    pub nonce: String,
+    pub expires_at: u64,
+}
+
+impl IssuanceOffer {
+    /// Bytes the holder signs to accept this offer.
+    pub fn request_signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = REQUEST_DOMAIN.to_vec();
+        for part in [
+            self.id.as_str(),
+            &self.issuer.to_string(),
+            &self.terms.holder.to_string(),
+            &self.terms.schema.to_string(),
+            &self.nonce,
+        ] {
+            bytes.extend_from_slice(&(part.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(part.as_bytes());
+        }
+        bytes
+    }
+
+    /// Unsigned credential issued at `now` for an accepted offer.
+    pub fn credential(&self, now: u64) -> Credential {
+        let claims = self
+            .terms
+            .claims
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        let mut credential = Credential::new(
+            self.issuer.clone(),
+            self.terms.holder.clone(),
+            claims,
+            Some(self.terms.schema.clone()),
+        );
+        credential.expires_at = self.terms.validity.map(|v| now.saturating_add(v));
+        credential.issued_under = Some(IssuanceScope {
+            cooperative_id: self.terms.cooperative_id.clone(),
+            credential_type: self.terms.credential_type.clone(),
+        });
+        credential
+    }
+}
+
+/// The holder's answer to an offer, signed with its DID key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssuanceRequest {
+    pub offer_id: String,
+    pub holder: Did,
+    pub signature: SignatureBytes,
+}
+
+impl IssuanceRequest {
+    /// Accept `offer` with the holder's key.
+    pub fn sign(offer: &IssuanceOffer, key: &SigningKey) -> Self {
+        let signature = sign_message(key, &offer.request_signing_bytes());
+        Self {
+            offer_id: offer.id.clone(),
+            holder: offer.terms.holder.clone(),
+            signature: SignatureBytes::from_ed_signature(signature),
+        }
+    }
+
+    /// Check that the request answers `offer` and is signed by `key`.
+    pub fn verify(&self, offer: &IssuanceOffer, key: &VerifyingKey) -> Result<(), CommonError> {
+        if self.offer_id != offer.id || self.holder != offer.terms.holder {
+            return Err(CommonError::PermissionDenied(format!(
+                "request does not answer offer {} to {}",
+                offer.id, offer.terms.holder
+            )));
+        }
+        let signature = self.signature.to_ed_signature()?;
+        if verify_signature(key, &offer.request_signing_bytes(), &signature) {
+            Ok(())
+        } else {
+            Err(CommonError::PermissionDenied(format!(
+                "request for offer {} is not signed by {}",
+                offer.id, self.holder
+            )))
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IssuanceState {
+    /// Policies by cooperative, then credential type.
+    policies: BTreeMap<String, BTreeMap<String, IssuerPolicy>>,
+    offers: BTreeMap<String, IssuanceOffer>,
+}
+
+/// Issuer policies and open offers, optionally persisted to a JSON file.
+/// Clones share state.
+#[derive(Debug, Clone, Default)]
+pub struct IssuanceRegistry {
+    path: Option<PathBuf>,
+    state: Arc<Mutex<IssuanceState>>,
+}
+
+impl IssuanceRegistry {
+    /// Registry that lives only in memory.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Registry persisted at `path`, loading any existing state.
+    pub fn open(path: PathBuf) -> Result<Self, CommonError> {
+        let state = if path.exists() {
+            let mut contents = String::new();
+            File::open(&path)
+                .and_then(|mut f| f.read_to_string(&mut contents))
+                .map_err(|e| {
+                    CommonError::IoError(format!("Failed to read issuance state {path:?}: {e}"))
+                })?;
+            if contents.trim().is_empty() {
+                IssuanceState::default()
+            } else {
+                serde_json::from_str(&contents).map_err(|e| {
+                    CommonError::DeserializationError(format!(
+                        "Failed to parse issuance state {path:?}: {e}"
+                    ))
+                })?
+            }
+        } else {
+            IssuanceState::default()
+        };
+        Ok(Self {
+            path: Some(path),
+            state: Arc::new(Mutex::new(state)),
+        })
+    }
+
+    /// Add or replace the policy for a cooperative's credential type.
+    pub fn set_policy(&self, mut policy: IssuerPolicy) -> Result<(), CommonError> {
+        if policy.cooperative_id.is_empty() || policy.credential_type.is_empty() {
+            return Err(CommonError::InvalidInputError(
+                "policy needs a cooperative and a credential type".into(),
+            ));
+        }
+        let mut issuers = Vec::new();
+        for issuer in policy.issuers.drain(..) {
+            if !issuers.contains(&issuer) {
+                issuers.push(issuer);
+            }
+        }
+        if issuers.is_empty() {
+            return Err(CommonError::InvalidInputError(
+                "policy must name at least one issuer".into(),
+            ));
+        }
+        policy.issuers = issuers;
+        let mut state = self.lock()?;
+        state
+            .policies
+            .entry(policy.cooperative_id.clone())
+            .or_default()
+            .insert(policy.credential_type.clone(), policy);
+        self.persist(&state)
+    }
+
+    /// Remove a policy, returning whether one existed.
+    pub fn remove_policy(
+        &self,
+        cooperative_id: &str,
+        credential_type: &str,
+    ) -> Result<bool, CommonError> {
+        let mut state = self.lock()?;
+        let removed = state
+            .policies
+            .get_mut(cooperative_id)
+            .and_then(|p| p.remove(credential_type))
+            .is_some();
+        state.policies.retain(|_, p| !p.is_empty());
+        self.persist(&state)?;
+        Ok(removed)
+    }
+
+    pub fn policy(
+        &self,
+        cooperative_id: &str,
+        credential_type: &str,
+    ) -> Result<Option<IssuerPolicy>, CommonError> {
+        Ok(self
+            .lock()?
+            .policies
+            .get(cooperative_id)
+            .and_then(|p| p.get(credential_type))
+            .cloned())
+    }
+
+    /// Policies of one cooperative, or of all of them.
+    pub fn policies(&self, cooperative_id: Option<&str>) -> Result<Vec<IssuerPolicy>, CommonError> {
+        Ok(self
+            .lock()?
+            .policies
+            .iter()
+            .filter(|(id, _)| cooperative_id.is_none_or(|c| c == id.as_str()))
+            .flat_map(|(_, p)| p.values().cloned())
+            .collect())
+    }
+
+    /// Open an offer from `issuer`, checking the claims against `schema` and
+    /// the cooperative's policy for the credential type.
+    pub fn offer(
+        &self,
+        issuer: &Did,
+        terms: OfferTerms,
+        schema: &CredentialSchema,
+        now: u64,
+    ) -> Result<IssuanceOffer, CommonError> {
+        if schema.cid()? != terms.schema {
+            return Err(CommonError::InvalidInputError(format!(
+                "schema {}@{} is not {}",
+                schema.name, schema.version, terms.schema
+            )));
+        }
+        let claims = terms
+            .claims
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        schema.validate_claims(&claims)?;
+        let mut state = self.lock()?;
+        check_policy(&state, issuer, &terms)?;
+        state.offers.retain(|_, o| o.expires_at > now);
+        if state.offers.len() >= MAX_PENDING_OFFERS {
+            return Err(CommonError::PolicyDenied("too many open offers".into()));
+        }
+        let mut nonce = [0u8; 32];
+        rand::rngs::OsRng.fill_bytes(&mut nonce);
+        let offer = IssuanceOffer {
+            id: uuid::Uuid::new_v4().to_string(),
+            issuer: issuer.clone(),
+            terms,
+            nonce: nonce.iter().map(|b| format!("{b:02x}")).collect(),
+            expires_at: now.saturating_add(DEFAULT_OFFER_TTL),
+        };
+        state.offers.insert(offer.id.clone(), offer.clone());
+        self.persist(&state)?;
+        Ok(offer)
+    }
+
+    /// Open offer with id `id`.
+    pub fn pending(&self, id: &str) -> Result<Option<IssuanceOffer>, CommonError> {
+        Ok(self.lock()?.offers.get(id).cloned())
+    }
+
+    /// Open offers, oldest expiry first.
+    pub fn offers(&self) -> Result<Vec<IssuanceOffer>, CommonError> {
+        let mut offers: Vec<_> = self.lock()?.offers.values().cloned().collect();
+        offers.sort_by_key(|o| o.expires_at);
+        Ok(offers)
+    }
+
+    /// Withdraw an offer, returning whether it was open.
+    pub fn withdraw(&self, id: &str) -> Result<bool, CommonError> {
+        let mut state = self.lock()?;
+        let removed = state.offers.remove(id).is_some();
+        self.persist(&state)?;
+        Ok(removed)
+    }
+
+    /// Consume the offer answered by `request` once the holder's signature,
+    /// the offer's expiry and the cooperative's current policy check out.
+    pub fn accept(
+        &self,
+        request: &IssuanceRequest,
+        resolver: &dyn DidResolver,
+        now: u64,
+    ) -> Result<IssuanceOffer, CommonError> {
+        let mut state = self.lock()?;
+        let offer = state
+            .offers
+            .get(&request.offer_id)
+            .cloned()
+            .ok_or_else(|| {
+                CommonError::ResourceNotFound(format!("credential offer {}", request.offer_id))
+            })?;
+        if offer.expires_at <= now {
+            state.offers.remove(&offer.id);
+            self.persist(&state)?;
+            return Err(CommonError::PolicyDenied(format!(
+                "credential offer {} expired",
+                offer.id
+            )));
+        }
+        request.verify(&offer, &resolver.resolve(&request.holder)?)?;
+        check_policy(&state, &offer.issuer, &offer.terms)?;
+        state.offers.remove(&offer.id);
+        self.persist(&state)?;
+        Ok(offer)
+    }
+
+    /// Check an issued credential against the current policy of the
+    /// cooperative and type it records. Expiry is required when the policy
+    /// bounds validity; the validity granted was checked at issuance.
+    pub fn check_credential(&self, credential: &Credential) -> Result<(), CommonError> {
+        let scope = credential.issued_under.as_ref().ok_or_else(|| {
+            CommonError::PolicyDenied("credential was not issued under an issuer policy".into())
+        })?;
+        let schema = credential.schema.as_ref().ok_or_else(|| {
+            CommonError::ValidationError("credential does not reference a schema".into())
+        })?;
+        let state = self.lock()?;
+        let policy = lookup_policy(&state, &scope.cooperative_id, &scope.credential_type)?;
+        policy.check_issuer(&credential.issuer, schema)?;
+        if policy.max_validity.is_some() && credential.expires_at.is_none() {
+            return Err(CommonError::PolicyDenied(format!(
+                "{} credentials for {} must expire",
+                scope.credential_type, scope.cooperative_id
+            )));
+        }
+        Ok(())
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, IssuanceState>, CommonError> {
+        self.state
+            .lock()
+            .map_err(|_| CommonError::LockError("issuance registry poisoned".into()))
+    }
+
+    fn persist(&self, state: &IssuanceState) -> Result<(), CommonError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let serialized = serde_json::to_string(state).map_err(|e| {
+            CommonError::SerializationError(format!("Failed to serialize issuance state: {e}"))
+        })?;
+        let tmp_path = path.with_extension("tmp");
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&tmp_path)
+            .map_err(|e| {
+                CommonError::IoError(format!("Failed to open issuance state {tmp_path:?}: {e}"))
+            })?;
+        file.write_all(serialized.as_bytes())
+            .and_then(|_| file.sync_all())
+            .map_err(|e| {
+                CommonError::IoError(format!("Failed to write issuance state {tmp_path:?}: {e}"))
+            })?;
+        drop(file);
+        rename(&tmp_path, path).map_err(|e| {
+            CommonError::IoError(format!(
+                "Failed to rename issuance state {tmp_path:?} -> {path:?}: {e}"
+            ))
+        })
+    }
+}
+
+fn lookup_policy<'a>(
+    state: &'a IssuanceState,
+    cooperative_id: &str,
+    credential_type: &str,
+) -> Result<&'a IssuerPolicy, CommonError> {
+    state
+        .policies
+        .get(cooperative_id)
+        .and_then(|p| p.get(credential_type))
+        .ok_or_else(|| {
+            CommonError::PolicyDenied(format!(
+                "{cooperative_id} has no issuer policy for {credential_type} credentials"
+            ))
+        })
+}
+
+fn check_policy(
+    state: &IssuanceState,
+    issuer: &Did,
+    terms: &OfferTerms,
+) -> Result<(), CommonError> {
+    lookup_policy(state, &terms.cooperative_id, &terms.credential_type)?.check(
+        issuer,
+        &terms.schema,
+        terms.validity,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{did_key_from_verifying_key, generate_ed25519_keypair, KeyDidResolver};
+    use serde_json::json;
+    use std::str::FromStr;
+
+    fn identity() -> (SigningKey, Did) {
+        let (sk, vk) = generate_ed25519_keypair();
+        let did = Did::from_str(&did_key_from_verifying_key(&vk)).unwrap();
+        (sk, did)
+    }
+
+    fn membership_schema(author: &Did, key: &SigningKey) -> CredentialSchema {
+        let mut schema = CredentialSchema::new(
+            author.clone(),
+            "membership",
+            "1.0",
+            json!({
+                "type": "object",
+                "properties": {
+                    "role": { "enum": ["member", "steward"] },
+                    "shares": { "type": "integer", "minimum": 1 }
+                },
+                "required": ["role", "shares"],
+                "additionalProperties": false
+            }),
+            1_000,
+        )
+        .unwrap();
+        schema.sign(key).unwrap();
+        schema
+    }
+
+    fn terms(holder: &Did, schema: &Cid, shares: &str) -> OfferTerms {
+        OfferTerms {
+            holder: holder.clone(),
+            schema: schema.clone(),
+            credential_type: "membership".into(),
+            claims: BTreeMap::from([
+                ("role".to_string(), "member".to_string()),
+                ("shares".to_string(), shares.to_string()),
+            ]),
+            cooperative_id: "bakery".into(),
+            validity: Some(3_600),
+        }
+    }
+
+    #[test]
+    fn schemas_are_signed_and_validate_claims() {
+        let (sk, author) = identity();
+        let schema = membership_schema(&author, &sk);
+        let registry = SchemaRegistry::new(Arc::new(KeyDidResolver));
+        let cid = registry.insert(schema.clone()).unwrap();
+        assert_eq!(registry.load_blocks([&schema.to_dag_block().unwrap()]), 1);
+
+        let mut credential = Credential::new(
+            author.clone(),
+            author.clone(),
+            HashMap::from([
+                ("role".to_string(), "member".to_string()),
+                ("shares".to_string(), "3".to_string()),
+            ]),
+            Some(cid.clone()),
+        );
+        registry.validate(&credential).unwrap();
+        credential.claims.insert("shares".into(), "0".into());
+        assert!(registry.validate(&credential).is_err());
+        credential.schema = Some(Cid::new_v1_sha256(0x55, b"unknown"));
+        assert!(registry.validate(&credential).is_err());
+
+        let mut forged = schema.clone();
+        forged.json_schema = json!({ "type": "object" });
+        assert!(registry.insert(forged).is_err());
+        assert!(CredentialSchema::new(author, "x", "1", json!({ "type": "string" }), 0).is_err());
+    }
+
+    #[test]
+    fn holder_must_sign_offer_within_issuer_policy() {
+        let (issuer_sk, issuer) = identity();
+        let (holder_sk, holder) = identity();
+        let (intruder_sk, _) = identity();
+        let schema = membership_schema(&issuer, &issuer_sk);
+        let cid = schema.cid().unwrap();
+        let registry = IssuanceRegistry::in_memory();
+
+        // Offers need a cooperative policy that allows the issuer.
+        assert!(registry
+            .offer(&issuer, terms(&holder, &cid, "2"), &schema, 0)
+            .is_err());
+        registry
+            .set_policy(IssuerPolicy {
+                cooperative_id: "bakery".into(),
+                credential_type: "membership".into(),
+                issuers: vec![holder.clone()],
+                schema: Some(cid.clone()),
+                max_validity: Some(86_400),
+            })
+            .unwrap();
+        assert!(registry
+            .offer(&issuer, terms(&holder, &cid, "2"), &schema, 0)
+            .is_err());
+        let mut policy = registry.policy("bakery", "membership").unwrap().unwrap();
+        policy.issuers = vec![issuer.clone(), issuer.clone()];
+        registry.set_policy(policy).unwrap();
+        assert_eq!(
+            registry.policies(Some("bakery")).unwrap()[0].issuers.len(),
+            1
+        );
+
+        // Claims are checked against the schema when the offer is made.
+        assert!(registry
+            .offer(&issuer, terms(&holder, &cid, "0"), &schema, 0)
+            .is_err());
+        let offer = registry
+            .offer(&issuer, terms(&holder, &cid, "2"), &schema, 0)
+            .unwrap();
+
+        let forged = IssuanceRequest::sign(&offer, &intruder_sk);
+        assert!(registry.accept(&forged, &KeyDidResolver, 10).is_err());
+        let request = IssuanceRequest::sign(&offer, &holder_sk);
+        let accepted = registry.accept(&request, &KeyDidResolver, 10).unwrap();
+        assert!(registry.accept(&request, &KeyDidResolver, 10).is_err());
+
+        assert_eq!(accepted.credential(10).expires_at, Some(3_610));
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let mut credential = accepted.credential(now);
+        credential.sign_claims(&issuer_sk);
+        credential
+            .verify_claim("shares", &issuer_sk.verifying_key())
+            .unwrap();
+        schema.validate_claims(&credential.claims).unwrap();
+
+        // The recorded scope is signed and checked against current policy.
+        registry.check_credential(&credential).unwrap();
+        let mut moved = credential.clone();
+        moved.issued_under.as_mut().unwrap().cooperative_id = "brewery".into();
+        assert!(moved
+            .verify_claim("shares", &issuer_sk.verifying_key())
+            .is_err());
+        let mut unscoped = credential.clone();
+        unscoped.issued_under = None;
+        assert!(registry.check_credential(&unscoped).is_err());
+        let mut policy = registry.policy("bakery", "membership").unwrap().unwrap();
+        policy.issuers = vec![holder.clone()];
+        registry.set_policy(policy.clone()).unwrap();
+        assert!(registry.check_credential(&credential).is_err());
+        policy.issuers = vec![issuer.clone()];
+        registry.set_policy(policy).unwrap();
+
+        let late = registry
+            .offer(&issuer, terms(&holder, &cid, "2"), &schema, 0)
+            .unwrap();
+        let request = IssuanceRequest::sign(&late, &holder_sk);
+        assert!(registry
+            .accept(&request, &KeyDidResolver, DEFAULT_OFFER_TTL)
+            .is_err());
+    }
+
+    #[test]
+    fn registry_persists_policies_and_offers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("issuance.json");
+        let (issuer_sk, issuer) = identity();
+        let (_, holder) = identity();
+        let schema = membership_schema(&issuer, &issuer_sk);
+        let cid = schema.cid().unwrap();
+
+        let registry = IssuanceRegistry::open(path.clone()).unwrap();
+        registry
+            .set_policy(IssuerPolicy {
+                cooperative_id: "bakery".into(),
+                credential_type: "membership".into(),
+                issuers: vec![issuer.clone()],
+                schema: None,
+                max_validity: None,
+            })
+            .unwrap();
+        let offer = registry
+            .offer(&issuer, terms(&holder, &cid, "1"), &schema, 0)
+            .unwrap();
+
+        let reopened = IssuanceRegistry::open(path).unwrap();
+        assert_eq!(reopened.pending(&offer.id).unwrap(), Some(offer.clone()));
+        assert_eq!(reopened.policies(None).unwrap().len(), 1);
+        assert!(reopened.withdraw(&offer.id).unwrap());
+        assert!(reopened.remove_policy("bakery", "membership").unwrap());
+        assert!(reopened.policies(None).unwrap().is_empty());
+    }
+}
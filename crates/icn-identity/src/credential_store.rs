@@ -1,7 +1,7 @@
 use dashmap::DashMap;
 use std::sync::Arc;
 
-use icn_common::Cid;
+use icn_common::{Cid, Did};
 
 use crate::credential::Credential;
 
@@ -30,6 +30,15 @@ impl InMemoryCredentialStore {
         self.creds.remove(cid).is_some()
     }
 
+    /// Credentials held by `holder`, with their CIDs.
+    pub fn held_by(&self, holder: &Did) -> Vec<(Cid, Credential)> {
+        self.creds
+            .iter()
+            .filter(|e| e.value().holder == *holder)
+            .map(|e| (e.key().clone(), e.value().clone()))
+            .collect()
+    }
+
     pub fn list_schemas(&self) -> Vec<Cid> {
         self.creds
             .iter()
@@ -387,6 +387,19 @@ impl DidDocument {
         self.version += 1;
     }
 
+    /// Record a federation membership, replacing any earlier entry for the
+    /// same federation
+    pub fn add_federation_membership(&mut self, membership: FederationMembership) {
+        let memberships = &mut self.icn_metadata.federation_memberships;
+        memberships.retain(|m| m.federation_id != membership.federation_id);
+        memberships.push(membership);
+        self.updated = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        self.version += 1;
+    }
+
     /// Check if DID has sufficient mana for rate limiting
     pub fn check_rate_limit(&self) -> Result<(), CommonError> {
         let current_time = std::time::SystemTime::now()
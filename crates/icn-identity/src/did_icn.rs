@@ -0,0 +1,572 @@
+//! The `did:icn` method: identifiers that survive key rotation
+//!
+//! A `did:key` identifier *is* its public key, so rotating the key yields a
+//! new DID and orphans every credential, reputation score, membership and
+//! delegation tied to the old one. A `did:icn` identifier is instead derived
+//! from a signed inception event and stays fixed while its keys change.
+//!
+//! The identifier's history is a hash-linked log of [`KeyEvent`]s, in the
+//! spirit of KERI:
+//!
+//! - **Inception** names the first signing key and commits to the next one
+//!   by publishing the SHA-256 digest of its public key. The DID is the
+//!   base58 digest of the inception event.
+//! - **Rotation** reveals the committed key, makes it the signing key and
+//!   commits to a new next key. It must be signed by the revealed key, so
+//!   stealing the current key is not enough to take over the identifier.
+//! - **Deactivation** reveals the committed key and retires the identifier.
+//!
+//! An identifier incepted without a next-key commitment cannot be rotated.
+//! Each event is anchored as a DAG block linked to the previous one, and
+//! [`IcnDidResolver`] replays the log to find the current key.
+
+use crate::{
+    sign_message, verify_signature, DidResolver, SignatureBytes, SigningKey, VerifyingKey,
+};
+use icn_common::{compute_merkle_cid, Cid, CommonError, DagBlock, DagLink, Did};
+use multibase::Base;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Method name of identifiers managed by a key event log.
+pub const DID_ICN_METHOD: &str = "icn";
+
+/// Digest that commits to `key` as the next signing key.
+pub fn next_key_commitment(key: &VerifyingKey) -> String {
+    hex_digest(key.as_bytes())
+}
+
+fn hex_digest(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+fn verifying_key(bytes: &[u8]) -> Result<VerifyingKey, CommonError> {
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| CommonError::IdentityError("key event key must be 32 bytes".into()))?;
+    VerifyingKey::from_bytes(&bytes)
+        .map_err(|e| CommonError::IdentityError(format!("invalid key event key: {e}")))
+}
+
+/// What a [`KeyEvent`] does to the identifier.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyEventKind {
+    /// Create the identifier with `key` as its signing key.
+    Inception {
+        key: Vec<u8>,
+        next_key_commitment: Option<String>,
+    },
+    /// Replace the signing key with the previously committed `key`.
+    Rotation {
+        key: Vec<u8>,
+        next_key_commitment: Option<String>,
+    },
+    /// Retire the identifier, revealing the committed `key`.
+    Deactivation { key: Vec<u8> },
+}
+
+impl KeyEventKind {
+    fn key(&self) -> &[u8] {
+        match self {
+            KeyEventKind::Inception { key, .. }
+            | KeyEventKind::Rotation { key, .. }
+            | KeyEventKind::Deactivation { key } => key,
+        }
+    }
+}
+
+/// A signed entry in a `did:icn` key event log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyEvent {
+    pub did: Did,
+    /// Position in the log; inception is `0`.
+    pub sequence: u64,
+    /// [`digest`](Self::digest) of the previous event.
+    pub previous: Option<String>,
+    pub kind: KeyEventKind,
+    pub timestamp: u64,
+    /// Signature by the key the event names or reveals.
+    pub signature: SignatureBytes,
+}
+
+impl KeyEvent {
+    /// Incept a new identifier signed by `key`, committing to `next`.
+    pub fn inception(key: &SigningKey, next: Option<&VerifyingKey>, timestamp: u64) -> Self {
+        let mut event = KeyEvent {
+            did: Did::default(),
+            sequence: 0,
+            previous: None,
+            kind: KeyEventKind::Inception {
+                key: key.verifying_key().as_bytes().to_vec(),
+                next_key_commitment: next.map(next_key_commitment),
+            },
+            timestamp,
+            signature: SignatureBytes(Vec::new()),
+        };
+        event.did = event.derived_did();
+        event.sign(key);
+        event
+    }
+
+    /// Rotate to the committed key `revealed`, committing to `next`.
+    pub fn rotation(
+        state: &KeyState,
+        revealed: &SigningKey,
+        next: Option<&VerifyingKey>,
+        timestamp: u64,
+    ) -> Self {
+        let mut event = state.next_event(
+            KeyEventKind::Rotation {
+                key: revealed.verifying_key().as_bytes().to_vec(),
+                next_key_commitment: next.map(next_key_commitment),
+            },
+            timestamp,
+        );
+        event.sign(revealed);
+        event
+    }
+
+    /// Retire the identifier with the committed key `revealed`.
+    pub fn deactivation(state: &KeyState, revealed: &SigningKey, timestamp: u64) -> Self {
+        let mut event = state.next_event(
+            KeyEventKind::Deactivation {
+                key: revealed.verifying_key().as_bytes().to_vec(),
+            },
+            timestamp,
+        );
+        event.sign(revealed);
+        event
+    }
+
+    /// Bytes covered by the signature. Inception omits the DID, which is
+    /// derived from these bytes.
+    pub fn signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        let mut bytes = b"icn-key-event".to_vec();
+        if self.sequence > 0 {
+            bytes.extend_from_slice(self.did.to_string().as_bytes());
+        }
+        bytes.extend_from_slice(&self.sequence.to_le_bytes());
+        bytes.extend_from_slice(self.previous.as_deref().unwrap_or("").as_bytes());
+        bytes.extend_from_slice(
+            &serde_json::to_vec(&self.kind)
+                .map_err(|e| CommonError::SerializationError(e.to_string()))?,
+        );
+        bytes.extend_from_slice(&self.timestamp.to_le_bytes());
+        Ok(bytes)
+    }
+
+    /// Digest of the signed event, used to chain the next event.
+    pub fn digest(&self) -> Result<String, CommonError> {
+        let mut bytes = self.signable_bytes()?;
+        bytes.extend_from_slice(&self.signature.0);
+        Ok(hex_digest(&bytes))
+    }
+
+    /// The identifier an inception event creates.
+    pub fn derived_did(&self) -> Did {
+        let digest = Sha256::digest(self.signable_bytes().unwrap_or_default());
+        Did::new(DID_ICN_METHOD, &multibase::encode(Base::Base58Btc, digest))
+    }
+
+    /// The event as a DAG block linked to the block of the previous event.
+    pub fn to_dag_block(&self, previous_block: Option<&Cid>) -> Result<DagBlock, CommonError> {
+        let data =
+            serde_json::to_vec(self).map_err(|e| CommonError::SerializationError(e.to_string()))?;
+        let links: Vec<DagLink> = previous_block
+            .map(|cid| DagLink {
+                cid: cid.clone(),
+                name: "previous".into(),
+                size: 0,
+            })
+            .into_iter()
+            .collect();
+        let cid = compute_merkle_cid(0x71, &data, &links, self.timestamp, &self.did, &None, &None);
+        Ok(DagBlock {
+            cid,
+            data,
+            links,
+            timestamp: self.timestamp,
+            author_did: self.did.clone(),
+            signature: None,
+            scope: None,
+        })
+    }
+
+    fn sign(&mut self, key: &SigningKey) {
+        let bytes = self.signable_bytes().unwrap_or_default();
+        self.signature = SignatureBytes::from_ed_signature(sign_message(key, &bytes));
+    }
+
+    fn verify_signature(&self, key: &VerifyingKey) -> Result<(), CommonError> {
+        let signature = self.signature.to_ed_signature()?;
+        if verify_signature(key, &self.signable_bytes()?, &signature) {
+            Ok(())
+        } else {
+            Err(CommonError::IdentityError(format!(
+                "key event {} of {} has an invalid signature",
+                self.sequence, self.did
+            )))
+        }
+    }
+}
+
+/// Key state of a `did:icn` identifier after replaying its log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyState {
+    pub did: Did,
+    /// Sequence number of the last event applied.
+    pub sequence: u64,
+    /// Digest of the last event applied.
+    pub last_digest: String,
+    /// Current signing key.
+    pub key: Vec<u8>,
+    /// Commitment to the key the next rotation must reveal.
+    pub next_key_commitment: Option<String>,
+    pub deactivated: bool,
+}
+
+impl KeyState {
+    /// State created by an inception event.
+    pub fn incept(event: &KeyEvent) -> Result<Self, CommonError> {
+        let KeyEventKind::Inception {
+            key,
+            next_key_commitment,
+        } = &event.kind
+        else {
+            return Err(CommonError::IdentityError(format!(
+                "key event log of {} does not start with an inception",
+                event.did
+            )));
+        };
+        if event.sequence != 0 || event.previous.is_some() {
+            return Err(CommonError::IdentityError(
+                "inception must be the first key event".into(),
+            ));
+        }
+        if event.did != event.derived_did() {
+            return Err(CommonError::IdentityError(format!(
+                "{} is not derived from its inception event",
+                event.did
+            )));
+        }
+        event.verify_signature(&verifying_key(key)?)?;
+        Ok(KeyState {
+            did: event.did.clone(),
+            sequence: 0,
+            last_digest: event.digest()?,
+            key: key.clone(),
+            next_key_commitment: next_key_commitment.clone(),
+            deactivated: false,
+        })
+    }
+
+    /// Check `event` against this state and apply it.
+    pub fn apply(&mut self, event: &KeyEvent) -> Result<(), CommonError> {
+        if event.did != self.did {
+            return Err(CommonError::IdentityError(format!(
+                "key event for {} applied to {}",
+                event.did, self.did
+            )));
+        }
+        if self.deactivated {
+            return Err(CommonError::IdentityError(format!(
+                "{} has been deactivated",
+                self.did
+            )));
+        }
+        if event.sequence != self.sequence + 1
+            || event.previous.as_deref() != Some(self.last_digest.as_str())
+        {
+            return Err(CommonError::InvalidInputError(format!(
+                "key event {} does not follow event {} of {}",
+                event.sequence, self.sequence, self.did
+            )));
+        }
+        if matches!(event.kind, KeyEventKind::Inception { .. }) {
+            return Err(CommonError::IdentityError(format!(
+                "{} was already incepted",
+                self.did
+            )));
+        }
+        let commitment = self.next_key_commitment.as_ref().ok_or_else(|| {
+            CommonError::IdentityError(format!(
+                "{} committed to no next key and cannot be rotated",
+                self.did
+            ))
+        })?;
+        let revealed = verifying_key(event.kind.key())?;
+        if &next_key_commitment(&revealed) != commitment {
+            return Err(CommonError::IdentityError(format!(
+                "key event {} of {} reveals a key it did not commit to",
+                event.sequence, self.did
+            )));
+        }
+        event.verify_signature(&revealed)?;
+
+        match &event.kind {
+            KeyEventKind::Inception { .. } => unreachable!("rejected above"),
+            KeyEventKind::Rotation {
+                key,
+                next_key_commitment,
+            } => {
+                self.key = key.clone();
+                self.next_key_commitment = next_key_commitment.clone();
+            }
+            KeyEventKind::Deactivation { .. } => {
+                self.next_key_commitment = None;
+                self.deactivated = true;
+            }
+        }
+        self.sequence = event.sequence;
+        self.last_digest = event.digest()?;
+        Ok(())
+    }
+
+    /// The current signing key.
+    pub fn verifying_key(&self) -> Result<VerifyingKey, CommonError> {
+        verifying_key(&self.key)
+    }
+
+    fn next_event(&self, kind: KeyEventKind, timestamp: u64) -> KeyEvent {
+        KeyEvent {
+            did: self.did.clone(),
+            sequence: self.sequence + 1,
+            previous: Some(self.last_digest.clone()),
+            kind,
+            timestamp,
+            signature: SignatureBytes(Vec::new()),
+        }
+    }
+}
+
+/// The ordered events of one identifier.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyEventLog {
+    pub events: Vec<KeyEvent>,
+}
+
+impl KeyEventLog {
+    /// Validate every event in order and return the resulting state.
+    pub fn replay(&self) -> Result<KeyState, CommonError> {
+        let (first, rest) = self
+            .events
+            .split_first()
+            .ok_or_else(|| CommonError::IdentityError("key event log is empty".into()))?;
+        let mut state = KeyState::incept(first)?;
+        for event in rest {
+            state.apply(event)?;
+        }
+        Ok(state)
+    }
+
+    /// CID of the DAG block anchoring the last event.
+    pub fn head_block(&self) -> Result<Option<Cid>, CommonError> {
+        let mut head = None;
+        for event in &self.events {
+            head = Some(event.to_dag_block(head.as_ref())?.cid);
+        }
+        Ok(head)
+    }
+}
+
+/// Resolves `did:icn` identifiers by replaying their key event logs.
+///
+/// Clones share the same logs, so one handle can accept events while
+/// another resolves.
+#[derive(Debug, Clone, Default)]
+pub struct IcnDidResolver {
+    logs: Arc<RwLock<HashMap<Did, KeyEventLog>>>,
+}
+
+impl IcnDidResolver {
+    /// Create a resolver with no identifiers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check `event` against the identifier's log without recording it.
+    ///
+    /// Returns the state the event would produce and the DAG block that
+    /// anchors it.
+    pub fn validate(&self, event: &KeyEvent) -> Result<(KeyState, DagBlock), CommonError> {
+        let log = self.log(&event.did).unwrap_or_default();
+        let state = if log.events.is_empty() {
+            KeyState::incept(event)?
+        } else {
+            let mut state = log.replay()?;
+            state.apply(event)?;
+            state
+        };
+        let block = event.to_dag_block(log.head_block()?.as_ref())?;
+        Ok((state, block))
+    }
+
+    /// Validate `event` and append it to the identifier's log.
+    pub fn apply(&self, event: KeyEvent) -> Result<KeyState, CommonError> {
+        let (state, _) = self.validate(&event)?;
+        self.logs
+            .write()
+            .map_err(|_| CommonError::LockError("key event logs poisoned".into()))?
+            .entry(event.did.clone())
+            .or_default()
+            .events
+            .push(event);
+        Ok(state)
+    }
+
+    /// Rebuild logs from anchored blocks, ignoring blocks that are not key
+    /// events or do not extend a log. Returns the number of events applied.
+    pub fn load_blocks<'a>(&self, blocks: impl IntoIterator<Item = &'a DagBlock>) -> usize {
+        let mut events: Vec<KeyEvent> = blocks
+            .into_iter()
+            .filter(|b| b.author_did.method == DID_ICN_METHOD)
+            .filter_map(|b| serde_json::from_slice(&b.data).ok())
+            .collect();
+        events.sort_by_key(|e| (e.did.to_string(), e.sequence));
+        events
+            .into_iter()
+            .filter(|event| self.apply(event.clone()).is_ok())
+            .count()
+    }
+
+    /// The identifier's log, if it has been incepted.
+    pub fn log(&self, did: &Did) -> Option<KeyEventLog> {
+        self.logs.read().ok()?.get(did).cloned()
+    }
+
+    /// Replay the identifier's log.
+    pub fn state(&self, did: &Did) -> Result<KeyState, CommonError> {
+        self.log(did)
+            .ok_or_else(|| CommonError::ResourceNotFound(format!("no key event log for {did}")))?
+            .replay()
+    }
+
+    /// Sequence number of the identifier's last event.
+    pub fn sequence(&self, did: &Did) -> Option<u64> {
+        let logs = self.logs.read().ok()?;
+        logs.get(did)?.events.last().map(|e| e.sequence)
+    }
+
+    /// Resolve `did:icn` identifiers here and everything else with `fallback`.
+    pub fn with_fallback(&self, fallback: Arc<dyn DidResolver>) -> Arc<dyn DidResolver> {
+        Arc::new(FallbackResolver {
+            icn: self.clone(),
+            fallback,
+        })
+    }
+}
+
+impl DidResolver for IcnDidResolver {
+    fn resolve(&self, did: &Did) -> Result<VerifyingKey, CommonError> {
+        if did.method != DID_ICN_METHOD {
+            return Err(CommonError::IdentityError(format!(
+                "not a did:icn identifier: {did}"
+            )));
+        }
+        let state = self.state(did)?;
+        if state.deactivated {
+            return Err(CommonError::IdentityError(format!(
+                "{did} has been deactivated"
+            )));
+        }
+        state.verifying_key()
+    }
+}
+
+struct FallbackResolver {
+    icn: IcnDidResolver,
+    fallback: Arc<dyn DidResolver>,
+}
+
+impl DidResolver for FallbackResolver {
+    fn resolve(&self, did: &Did) -> Result<VerifyingKey, CommonError> {
+        if did.method == DID_ICN_METHOD && self.icn.sequence(did).is_some() {
+            self.icn.resolve(did)
+        } else {
+            self.fallback.resolve(did)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_ed25519_keypair;
+
+    #[test]
+    fn rotation_keeps_the_did_and_requires_the_committed_key() {
+        let (first, _) = generate_ed25519_keypair();
+        let (second, second_pk) = generate_ed25519_keypair();
+        let (third, third_pk) = generate_ed25519_keypair();
+        let resolver = IcnDidResolver::new();
+
+        let inception = KeyEvent::inception(&first, Some(&second_pk), 1);
+        let did = inception.did.clone();
+        let state = resolver.apply(inception).unwrap();
+        assert_eq!(resolver.resolve(&did).unwrap(), first.verifying_key());
+
+        // The current key cannot rotate to a key it never committed to.
+        let hijack = KeyEvent::rotation(&state, &first, Some(&third_pk), 2);
+        assert!(resolver.apply(hijack).is_err());
+
+        let state = resolver
+            .apply(KeyEvent::rotation(&state, &second, Some(&third_pk), 2))
+            .unwrap();
+        assert_eq!(state.did, did);
+        assert_eq!(resolver.resolve(&did).unwrap(), second_pk);
+
+        resolver
+            .apply(KeyEvent::deactivation(&state, &third, 3))
+            .unwrap();
+        assert!(resolver.resolve(&did).is_err());
+    }
+
+    #[test]
+    fn enhanced_resolver_refreshes_after_rotation() {
+        let (first, _) = generate_ed25519_keypair();
+        let (second, second_pk) = generate_ed25519_keypair();
+        let logs = IcnDidResolver::new();
+        let cached = crate::EnhancedDidResolver::with_defaults(Arc::new(
+            icn_common::FixedTimeProvider::new(10),
+        ))
+        .with_icn_resolver(logs.clone());
+
+        let inception = KeyEvent::inception(&first, Some(&second_pk), 1);
+        let did = inception.did.clone();
+        let state = logs.apply(inception).unwrap();
+        assert_eq!(cached.resolve(&did).unwrap(), first.verifying_key());
+
+        logs.apply(KeyEvent::rotation(&state, &second, None, 2))
+            .unwrap();
+        assert_eq!(cached.resolve(&did).unwrap(), second_pk);
+    }
+
+    #[test]
+    fn logs_replay_from_dag_blocks() {
+        let (first, _) = generate_ed25519_keypair();
+        let (second, second_pk) = generate_ed25519_keypair();
+        let source = IcnDidResolver::new();
+        let mut blocks = Vec::new();
+
+        let inception = KeyEvent::inception(&first, Some(&second_pk), 1);
+        let did = inception.did.clone();
+        let (state, block) = source.validate(&inception).unwrap();
+        source.apply(inception).unwrap();
+        blocks.push(block);
+        let rotation = KeyEvent::rotation(&state, &second, None, 2);
+        let (_, block) = source.validate(&rotation).unwrap();
+        assert_eq!(block.links[0].cid, blocks[0].cid);
+        source.apply(rotation).unwrap();
+        blocks.push(block);
+
+        let replayed = IcnDidResolver::new();
+        assert_eq!(replayed.load_blocks(blocks.iter().rev()), 2);
+        assert_eq!(replayed.resolve(&did).unwrap(), second_pk);
+        assert_eq!(replayed.state(&did).unwrap().next_key_commitment, None);
+    }
+}
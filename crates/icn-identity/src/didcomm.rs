@@ -0,0 +1,1570 @@
+//! DIDComm v2 encrypted messaging between DIDs
+//!
+//! Plaintext [`DidCommMessage`]s are packed into JWE envelopes in the General
+//! JSON serialization:
+//!
+//! * **authcrypt** (`ECDH-1PU+A256KW`) proves the sender's DID to the
+//!   recipients without leaving a signature third parties could verify.
+//! * **anoncrypt** (`ECDH-ES+A256KW`) hides the sender.
+//!
+//! Content is encrypted with `A256CBC-HS512`. Key agreement uses the X25519
+//! key derived from each DID's Ed25519 key, identified as
+//! `<did>#<multibase X25519 key>` (see [`key_agreement_kid`]).
+//!
+//! A mediator holds messages for recipients that are offline. The sender
+//! wraps the envelope in a routing `forward` message anoncrypted to the
+//! mediator ([`wrap_forward`]). The mediator queues it in its
+//! [`DidCommMailbox`] until the recipient collects it with Message Pickup.
+//! Recipients register with the mediator through Coordinate Mediation.
+
+use crate::did_document::ServiceEndpoint;
+use crate::{
+    sign_message, verify_signature, DidResolver, SignatureBytes, SigningKey, VerifyingKey,
+};
+use aes::cipher::{block_padding::Pkcs7, BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use aes_kw::KekAes256;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use hmac::{Hmac, Mac};
+use icn_common::{CommonError, Did};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{rename, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use zeroize::Zeroize;
+
+type Aes256CbcEnc = cbc::Encryptor<aes::Aes256>;
+type Aes256CbcDec = cbc::Decryptor<aes::Aes256>;
+
+/// Media type of plaintext messages.
+pub const PLAINTEXT_TYP: &str = "application/didcomm-plain+json";
+/// Media type of encrypted envelopes.
+pub const ENCRYPTED_TYP: &str = "application/didcomm-encrypted+json";
+/// Service type advertising a DID's DIDComm route.
+pub const DIDCOMM_SERVICE_TYPE: &str = "DIDCommMessaging";
+
+const ALG_AUTHCRYPT: &str = "ECDH-1PU+A256KW";
+const ALG_ANONCRYPT: &str = "ECDH-ES+A256KW";
+const ENC: &str = "A256CBC-HS512";
+const CREDENTIAL_FORMAT: &str = "icn-vc@v1.0";
+
+pub const FORWARD_TYPE: &str = "https://didcomm.org/routing/2.0/forward";
+pub const MEDIATE_REQUEST_TYPE: &str =
+    "https://didcomm.org/coordinate-mediation/3.0/mediate-request";
+pub const MEDIATE_GRANT_TYPE: &str = "https://didcomm.org/coordinate-mediation/3.0/mediate-grant";
+pub const DELIVERY_REQUEST_TYPE: &str = "https://didcomm.org/messagepickup/3.0/delivery-request";
+pub const DELIVERY_TYPE: &str = "https://didcomm.org/messagepickup/3.0/delivery";
+pub const PICKUP_STATUS_TYPE: &str = "https://didcomm.org/messagepickup/3.0/status";
+pub const MESSAGES_RECEIVED_TYPE: &str = "https://didcomm.org/messagepickup/3.0/messages-received";
+pub const CREDENTIAL_OFFER_TYPE: &str = "https://didcomm.org/issue-credential/3.0/offer-credential";
+pub const CREDENTIAL_REQUEST_TYPE: &str =
+    "https://didcomm.org/issue-credential/3.0/request-credential";
+pub const CREDENTIAL_ISSUE_TYPE: &str = "https://didcomm.org/issue-credential/3.0/issue-credential";
+pub const PROPOSAL_INVITATION_TYPE: &str = "https://icn.coop/governance/1.0/proposal-invitation";
+
+/// Envelopes a mediator queues per recipient before refusing more.
+pub const MAX_QUEUED_ENVELOPES: usize = 1000;
+/// Bytes of queued envelopes a mediator holds across all recipients.
+pub const MAX_MAILBOX_BYTES: usize = 64 * 1024 * 1024;
+/// Messages kept in the inbox; the oldest are dropped first.
+pub const MAX_INBOX_MESSAGES: usize = 1000;
+/// Envelopes returned by one delivery when the request sets no limit.
+const DEFAULT_DELIVERY_LIMIT: usize = 10;
+
+/// Secret half of a DID's X25519 key agreement key.
+pub trait KeyAgreementKey {
+    /// X25519 public key.
+    fn x25519_public_key(&self) -> [u8; 32];
+    /// X25519 shared secret with `public_key`.
+    fn x25519_agree(&self, public_key: &[u8; 32]) -> Result<[u8; 32], CommonError>;
+}
+
+impl KeyAgreementKey for SigningKey {
+    fn x25519_public_key(&self) -> [u8; 32] {
+        self.verifying_key().to_montgomery().to_bytes()
+    }
+
+    fn x25519_agree(&self, public_key: &[u8; 32]) -> Result<[u8; 32], CommonError> {
+        let mut scalar = self.to_scalar_bytes();
+        let shared = MontgomeryPoint(*public_key).mul_clamped(scalar).to_bytes();
+        scalar.zeroize();
+        checked_shared_secret(shared)
+    }
+}
+
+/// Reject the all-zero output produced by low-order points.
+fn checked_shared_secret(shared: [u8; 32]) -> Result<[u8; 32], CommonError> {
+    if shared == [0u8; 32] {
+        return Err(CommonError::CryptoError(
+            "X25519 key agreement produced a low-order point".into(),
+        ));
+    }
+    Ok(shared)
+}
+
+/// Key id of the X25519 key agreement key derived from `did`'s Ed25519 key.
+pub fn key_agreement_kid(did: &Did, key: &VerifyingKey) -> String {
+    x25519_kid(did, &key.to_montgomery().to_bytes())
+}
+
+fn x25519_kid(did: &Did, public_key: &[u8; 32]) -> String {
+    // Multicodec 0xec (x25519-pub) as an unsigned varint.
+    let mut bytes = vec![0xec, 0x01];
+    bytes.extend_from_slice(public_key);
+    format!(
+        "{did}#{}",
+        multibase::encode(multibase::Base::Base58Btc, bytes)
+    )
+}
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Data carried by an [`Attachment`].
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct AttachmentData {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base64: Option<String>,
+}
+
+/// Attachment embedded in a message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attachment {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    /// Format of the attached data, such as a credential format id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    pub data: AttachmentData,
+}
+
+impl Attachment {
+    /// Attachment holding inline JSON.
+    pub fn json(id: Option<String>, value: Value) -> Self {
+        Self {
+            id,
+            media_type: Some("application/json".into()),
+            format: None,
+            data: AttachmentData {
+                json: Some(value),
+                base64: None,
+            },
+        }
+    }
+}
+
+/// Plaintext DIDComm v2 message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DidCommMessage {
+    pub id: String,
+    pub typ: String,
+    /// Protocol message type URI.
+    #[serde(rename = "type")]
+    pub message_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub to: Vec<String>,
+    /// Thread the message belongs to; defaults to `id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_time: Option<u64>,
+    #[serde(default)]
+    pub body: Value,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,
+    /// `"all"` asks the receiver to reply over the same connection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_route: Option<String>,
+}
+
+impl DidCommMessage {
+    /// New message of `message_type` with a random id.
+    pub fn new(message_type: &str, body: Value) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            typ: PLAINTEXT_TYP.into(),
+            message_type: message_type.into(),
+            from: None,
+            to: Vec::new(),
+            thid: None,
+            created_time: Some(now()),
+            expires_time: None,
+            body,
+            attachments: Vec::new(),
+            return_route: None,
+        }
+    }
+
+    pub fn with_sender(mut self, did: &Did) -> Self {
+        self.from = Some(did.to_string());
+        self
+    }
+
+    pub fn with_recipient(mut self, did: &Did) -> Self {
+        self.to.push(did.to_string());
+        self
+    }
+
+    pub fn with_thread(mut self, thid: &str) -> Self {
+        self.thid = Some(thid.into());
+        self
+    }
+
+    pub fn with_expiry(mut self, expires_time: u64) -> Self {
+        self.expires_time = Some(expires_time);
+        self
+    }
+
+    pub fn with_attachment(mut self, attachment: Attachment) -> Self {
+        self.attachments.push(attachment);
+        self
+    }
+
+    /// Ask the receiver to reply over the connection this message arrived on.
+    pub fn with_return_route(mut self) -> Self {
+        self.return_route = Some("all".into());
+        self
+    }
+
+    /// Thread id, which is the message id for the first message of a thread.
+    pub fn thread_id(&self) -> &str {
+        self.thid.as_deref().unwrap_or(&self.id)
+    }
+
+    /// Reply in the same thread, addressed to this message's sender.
+    pub fn reply(&self, message_type: &str, body: Value) -> Self {
+        let mut reply = Self::new(message_type, body).with_thread(self.thread_id());
+        reply.to = self.from.iter().cloned().collect();
+        reply
+    }
+
+    /// Next hop and inner envelope of a routing `forward` message.
+    pub fn forward_payload(&self) -> Result<(Did, String), CommonError> {
+        if self.message_type != FORWARD_TYPE {
+            return Err(CommonError::InvalidInputError(format!(
+                "{} is not a forward message",
+                self.message_type
+            )));
+        }
+        let next = self
+            .body
+            .get("next")
+            .and_then(Value::as_str)
+            .ok_or_else(|| CommonError::InvalidInputError("forward has no next hop".into()))
+            .and_then(|next| {
+                Did::from_str(next).map_err(|e| {
+                    CommonError::InvalidInputError(format!("invalid forward next hop: {e}"))
+                })
+            })?;
+        let envelope = match self.attachments.as_slice() {
+            [attachment] => attachment.data.json.as_ref(),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            CommonError::InvalidInputError("forward must carry one JSON envelope".into())
+        })?;
+        Ok((next, envelope.to_string()))
+    }
+
+    /// Ids and envelopes carried by a Message Pickup `delivery`.
+    pub fn delivered_envelopes(&self) -> Result<Vec<(String, String)>, CommonError> {
+        if self.message_type != DELIVERY_TYPE {
+            return Err(CommonError::InvalidInputError(format!(
+                "{} is not a delivery message",
+                self.message_type
+            )));
+        }
+        self.attachments
+            .iter()
+            .map(|attachment| match (&attachment.id, &attachment.data.json) {
+                (Some(id), Some(envelope)) => Ok((id.clone(), envelope.to_string())),
+                _ => Err(CommonError::InvalidInputError(
+                    "delivery attachment needs an id and a JSON envelope".into(),
+                )),
+            })
+            .collect()
+    }
+}
+
+/// Message unpacked from an envelope.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnpackedMessage {
+    pub message: DidCommMessage,
+    /// Sender proven by authcrypt; `None` for anoncrypt.
+    pub sender: Option<Did>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Jwe {
+    protected: String,
+    recipients: Vec<JweRecipient>,
+    iv: String,
+    ciphertext: String,
+    tag: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JweRecipient {
+    header: JweRecipientHeader,
+    encrypted_key: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JweRecipientHeader {
+    kid: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ProtectedHeader {
+    typ: String,
+    alg: String,
+    enc: String,
+    epk: Jwk,
+    apv: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    apu: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    skid: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Jwk {
+    kty: String,
+    crv: String,
+    x: String,
+}
+
+/// Encrypt `message` to `recipients`, proving `sender` owns `sender_key`.
+///
+/// The message's `from` must be `sender`.
+pub fn pack_authcrypt<K: KeyAgreementKey + ?Sized>(
+    message: &DidCommMessage,
+    sender: &Did,
+    sender_key: &K,
+    recipients: &[(Did, VerifyingKey)],
+) -> Result<String, CommonError> {
+    if message.from.as_deref() != Some(sender.to_string().as_str()) {
+        return Err(CommonError::InvalidInputError(format!(
+            "authcrypt message must be from {sender}"
+        )));
+    }
+    pack(message, Some((sender, sender_key)), recipients)
+}
+
+/// Encrypt `message` to `recipients` without identifying the sender.
+pub fn pack_anoncrypt(
+    message: &DidCommMessage,
+    recipients: &[(Did, VerifyingKey)],
+) -> Result<String, CommonError> {
+    pack::<SigningKey>(message, None, recipients)
+}
+
+fn pack<K: KeyAgreementKey + ?Sized>(
+    message: &DidCommMessage,
+    sender: Option<(&Did, &K)>,
+    recipients: &[(Did, VerifyingKey)],
+) -> Result<String, CommonError> {
+    if recipients.is_empty() {
+        return Err(CommonError::InvalidInputError(
+            "a DIDComm message needs at least one recipient".into(),
+        ));
+    }
+    let plaintext = serde_json::to_vec(message).map_err(|e| {
+        CommonError::SerializationError(format!("Failed to serialize DIDComm message: {e}"))
+    })?;
+    let recipient_keys: Vec<(String, [u8; 32])> = recipients
+        .iter()
+        .map(|(did, key)| {
+            let x = key.to_montgomery().to_bytes();
+            (x25519_kid(did, &x), x)
+        })
+        .collect();
+    let apv = recipients_apv(recipient_keys.iter().map(|(kid, _)| kid.as_str()));
+
+    let mut rng = rand::thread_rng();
+    let mut ephemeral = [0u8; 32];
+    rng.fill_bytes(&mut ephemeral);
+    let epk = MontgomeryPoint::mul_base_clamped(ephemeral).to_bytes();
+    let skid = sender.map(|(did, key)| x25519_kid(did, &key.x25519_public_key()));
+    let alg = if sender.is_some() {
+        ALG_AUTHCRYPT
+    } else {
+        ALG_ANONCRYPT
+    };
+    let header = ProtectedHeader {
+        typ: ENCRYPTED_TYP.into(),
+        alg: alg.into(),
+        enc: ENC.into(),
+        epk: Jwk {
+            kty: "OKP".into(),
+            crv: "X25519".into(),
+            x: URL_SAFE_NO_PAD.encode(epk),
+        },
+        apv: URL_SAFE_NO_PAD.encode(&apv),
+        apu: skid.as_ref().map(|skid| URL_SAFE_NO_PAD.encode(skid)),
+        skid: skid.clone(),
+    };
+    let protected = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&header).map_err(|e| {
+        CommonError::SerializationError(format!("Failed to serialize JWE header: {e}"))
+    })?);
+
+    let mut cek = [0u8; 64];
+    rng.fill_bytes(&mut cek);
+    let mut iv = [0u8; 16];
+    rng.fill_bytes(&mut iv);
+    let (ciphertext, tag) = encrypt_content(&cek, &iv, &plaintext, protected.as_bytes())?;
+
+    let apu = skid.unwrap_or_default().into_bytes();
+    let mut jwe_recipients = Vec::with_capacity(recipient_keys.len());
+    for (kid, x) in recipient_keys {
+        let mut z =
+            checked_shared_secret(MontgomeryPoint(x).mul_clamped(ephemeral).to_bytes())?.to_vec();
+        let cctag = match sender {
+            Some((_, key)) => {
+                z.extend_from_slice(&key.x25519_agree(&x)?);
+                Some(tag.as_slice())
+            }
+            None => None,
+        };
+        let mut kek = concat_kdf(&z, alg, &apu, &apv, cctag);
+        z.zeroize();
+        let encrypted_key = KekAes256::from(kek)
+            .wrap_vec(&cek)
+            .map_err(|e| CommonError::CryptoError(format!("Failed to wrap content key: {e}")))?;
+        kek.zeroize();
+        jwe_recipients.push(JweRecipient {
+            header: JweRecipientHeader { kid },
+            encrypted_key: URL_SAFE_NO_PAD.encode(encrypted_key),
+        });
+    }
+    ephemeral.zeroize();
+    cek.zeroize();
+
+    serde_json::to_string(&Jwe {
+        protected,
+        recipients: jwe_recipients,
+        iv: URL_SAFE_NO_PAD.encode(iv),
+        ciphertext: URL_SAFE_NO_PAD.encode(ciphertext),
+        tag: URL_SAFE_NO_PAD.encode(tag),
+    })
+    .map_err(|e| CommonError::SerializationError(format!("Failed to serialize JWE: {e}")))
+}
+
+/// Decrypt an envelope addressed to `recipient`.
+///
+/// Authcrypt senders are resolved with `resolver` and must match the
+/// message's `from`. Expired messages and messages whose `to` does not list
+/// `recipient` are rejected.
+pub fn unpack<K: KeyAgreementKey + ?Sized>(
+    envelope: &str,
+    recipient: &Did,
+    key: &K,
+    resolver: &dyn DidResolver,
+) -> Result<UnpackedMessage, CommonError> {
+    let jwe: Jwe = serde_json::from_str(envelope)
+        .map_err(|e| CommonError::DeserializationError(format!("Invalid DIDComm envelope: {e}")))?;
+    let header: ProtectedHeader = serde_json::from_slice(&b64_decode(&jwe.protected)?)
+        .map_err(|e| CommonError::DeserializationError(format!("Invalid JWE header: {e}")))?;
+    if header.enc != ENC || header.epk.kty != "OKP" || header.epk.crv != "X25519" {
+        return Err(CommonError::CryptoError(format!(
+            "Unsupported JWE encryption {} with {} key",
+            header.enc, header.epk.crv
+        )));
+    }
+    let kid = x25519_kid(recipient, &key.x25519_public_key());
+    let entry = jwe
+        .recipients
+        .iter()
+        .find(|r| r.header.kid == kid)
+        .ok_or_else(|| {
+            CommonError::ResourceNotFound(format!("Envelope is not addressed to {kid}"))
+        })?;
+    let epk = b64_key(&header.epk.x)?;
+    let apv = b64_decode(&header.apv)?;
+    let tag = b64_decode(&jwe.tag)?;
+
+    let mut z = key.x25519_agree(&epk)?.to_vec();
+    let (apu, sender) = match header.alg.as_str() {
+        ALG_ANONCRYPT => (Vec::new(), None),
+        ALG_AUTHCRYPT => {
+            let skid = header
+                .skid
+                .as_deref()
+                .ok_or_else(|| CommonError::CryptoError("authcrypt envelope has no skid".into()))?;
+            let apu = b64_decode(header.apu.as_deref().unwrap_or_default())?;
+            if apu != skid.as_bytes() {
+                return Err(CommonError::CryptoError("apu does not match skid".into()));
+            }
+            let sender = skid
+                .split_once('#')
+                .and_then(|(did, _)| Did::from_str(did).ok())
+                .ok_or_else(|| CommonError::CryptoError(format!("Invalid skid {skid}")))?;
+            let sender_key = resolver.resolve(&sender)?.to_montgomery().to_bytes();
+            if x25519_kid(&sender, &sender_key) != skid {
+                return Err(CommonError::PermissionDenied(format!(
+                    "{skid} is not the key agreement key of {sender}"
+                )));
+            }
+            z.extend_from_slice(&key.x25519_agree(&sender_key)?);
+            (apu, Some(sender))
+        }
+        other => {
+            return Err(CommonError::CryptoError(format!(
+                "Unsupported JWE algorithm {other}"
+            )))
+        }
+    };
+    let cctag = sender.as_ref().map(|_| tag.as_slice());
+    let mut kek = concat_kdf(&z, &header.alg, &apu, &apv, cctag);
+    z.zeroize();
+    let cek = KekAes256::from(kek)
+        .unwrap_vec(&b64_decode(&entry.encrypted_key)?)
+        .map_err(|_| CommonError::CryptoError("Failed to unwrap DIDComm content key".into()))?;
+    kek.zeroize();
+    let mut cek: [u8; 64] = cek
+        .try_into()
+        .map_err(|_| CommonError::CryptoError("Invalid DIDComm content key length".into()))?;
+    let plaintext = decrypt_content(
+        &cek,
+        &b64_decode(&jwe.iv)?,
+        &b64_decode(&jwe.ciphertext)?,
+        &tag,
+        jwe.protected.as_bytes(),
+    );
+    cek.zeroize();
+    let message: DidCommMessage = serde_json::from_slice(&plaintext?)
+        .map_err(|e| CommonError::DeserializationError(format!("Invalid DIDComm message: {e}")))?;
+
+    if let Some(sender) = &sender {
+        if message.from.as_deref() != Some(sender.to_string().as_str()) {
+            return Err(CommonError::PermissionDenied(format!(
+                "message from does not match authcrypt sender {sender}"
+            )));
+        }
+    }
+    if !message.to.is_empty() && !message.to.contains(&recipient.to_string()) {
+        return Err(CommonError::PermissionDenied(format!(
+            "message is not addressed to {recipient}"
+        )));
+    }
+    if message.expires_time.is_some_and(|t| t < now()) {
+        return Err(CommonError::PolicyDenied(format!(
+            "message {} has expired",
+            message.id
+        )));
+    }
+    Ok(UnpackedMessage { message, sender })
+}
+
+/// Wrap `envelope` in a `forward` to `next`, anoncrypted to `mediator`.
+pub fn wrap_forward(
+    envelope: &str,
+    next: &Did,
+    mediator: &Did,
+    mediator_key: &VerifyingKey,
+) -> Result<String, CommonError> {
+    let inner: Value = serde_json::from_str(envelope)
+        .map_err(|e| CommonError::DeserializationError(format!("Invalid DIDComm envelope: {e}")))?;
+    let forward = DidCommMessage::new(FORWARD_TYPE, json!({ "next": next.to_string() }))
+        .with_recipient(mediator)
+        .with_attachment(Attachment::json(None, inner));
+    pack_anoncrypt(&forward, &[(mediator.clone(), *mediator_key)])
+}
+
+/// Key ids of the recipients an envelope is encrypted to.
+pub fn envelope_recipients(envelope: &str) -> Result<Vec<String>, CommonError> {
+    let jwe: Jwe = serde_json::from_str(envelope)
+        .map_err(|e| CommonError::DeserializationError(format!("Invalid DIDComm envelope: {e}")))?;
+    Ok(jwe.recipients.into_iter().map(|r| r.header.kid).collect())
+}
+
+/// `apv` is the SHA-256 of the sorted recipient key ids joined by `.`.
+fn recipients_apv<'a>(kids: impl Iterator<Item = &'a str>) -> Vec<u8> {
+    let mut kids: Vec<&str> = kids.collect();
+    kids.sort_unstable();
+    Sha256::digest(kids.join(".").as_bytes()).to_vec()
+}
+
+/// Concat KDF (NIST SP 800-56A) deriving a 256-bit key wrapping key.
+///
+/// ECDH-1PU with key wrapping also binds the content tag (`cctag`).
+fn concat_kdf(z: &[u8], alg: &str, apu: &[u8], apv: &[u8], cctag: Option<&[u8]>) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(1u32.to_be_bytes());
+    hasher.update(z);
+    for field in [alg.as_bytes(), apu, apv] {
+        hasher.update((field.len() as u32).to_be_bytes());
+        hasher.update(field);
+    }
+    hasher.update(256u32.to_be_bytes());
+    if let Some(tag) = cctag {
+        hasher.update((tag.len() as u32).to_be_bytes());
+        hasher.update(tag);
+    }
+    hasher.finalize().into()
+}
+
+/// `A256CBC-HS512` from RFC 7518: HMAC key first, AES key second.
+fn encrypt_content(
+    cek: &[u8; 64],
+    iv: &[u8; 16],
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), CommonError> {
+    let (mac_key, enc_key) = cek.split_at(32);
+    let ciphertext = Aes256CbcEnc::new_from_slices(enc_key, iv)
+        .map_err(|e| CommonError::CryptoError(format!("Invalid content key: {e}")))?
+        .encrypt_padded_vec_mut::<Pkcs7>(plaintext);
+    let tag = content_mac(mac_key, aad, iv, &ciphertext)?
+        .finalize()
+        .into_bytes()[..32]
+        .to_vec();
+    Ok((ciphertext, tag))
+}
+
+fn decrypt_content(
+    cek: &[u8; 64],
+    iv: &[u8],
+    ciphertext: &[u8],
+    tag: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, CommonError> {
+    let (mac_key, enc_key) = cek.split_at(32);
+    if tag.len() != 32 {
+        return Err(CommonError::CryptoError("Invalid JWE tag length".into()));
+    }
+    content_mac(mac_key, aad, iv, ciphertext)?
+        .verify_truncated_left(tag)
+        .map_err(|_| CommonError::CryptoError("DIDComm envelope failed authentication".into()))?;
+    Aes256CbcDec::new_from_slices(enc_key, iv)
+        .map_err(|e| CommonError::CryptoError(format!("Invalid content key or IV: {e}")))?
+        .decrypt_padded_vec_mut::<Pkcs7>(ciphertext)
+        .map_err(|_| CommonError::CryptoError("Invalid DIDComm ciphertext padding".into()))
+}
+
+fn content_mac(
+    mac_key: &[u8],
+    aad: &[u8],
+    iv: &[u8],
+    ciphertext: &[u8],
+) -> Result<Hmac<Sha512>, CommonError> {
+    let mut mac = <Hmac<Sha512> as Mac>::new_from_slice(mac_key)
+        .map_err(|e| CommonError::CryptoError(format!("Invalid MAC key: {e}")))?;
+    mac.update(aad);
+    mac.update(iv);
+    mac.update(ciphertext);
+    mac.update(&((aad.len() as u64) * 8).to_be_bytes());
+    Ok(mac)
+}
+
+fn b64_decode(value: &str) -> Result<Vec<u8>, CommonError> {
+    URL_SAFE_NO_PAD
+        .decode(value)
+        .map_err(|e| CommonError::DeserializationError(format!("Invalid base64url: {e}")))
+}
+
+fn b64_key(value: &str) -> Result<[u8; 32], CommonError> {
+    b64_decode(value)?
+        .try_into()
+        .map_err(|_| CommonError::CryptoError("Invalid X25519 public key".into()))
+}
+
+/// Issue Credential 3.0 offer previewing the claims of a credential.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CredentialOffer {
+    pub credential_type: String,
+    pub claims: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+impl CredentialOffer {
+    /// Offer message from `issuer` to `holder`.
+    pub fn to_message(&self, issuer: &Did, holder: &Did) -> DidCommMessage {
+        let attributes: Vec<Value> = self
+            .claims
+            .iter()
+            .map(|(name, value)| json!({ "name": name, "value": value }))
+            .collect();
+        let mut attachment = Attachment::json(
+            Some("offer".into()),
+            serde_json::to_value(self).unwrap_or_default(),
+        );
+        attachment.format = Some(CREDENTIAL_FORMAT.into());
+        DidCommMessage::new(
+            CREDENTIAL_OFFER_TYPE,
+            json!({
+                "goal_code": "issue-vc",
+                "comment": self.comment,
+                "credential_preview": {
+                    "type": "https://didcomm.org/issue-credential/3.0/credential-preview",
+                    "body": { "attributes": attributes },
+                },
+            }),
+        )
+        .with_sender(issuer)
+        .with_recipient(holder)
+        .with_attachment(attachment)
+    }
+
+    /// Offer carried by an `offer-credential` message.
+    pub fn from_message(message: &DidCommMessage) -> Result<Self, CommonError> {
+        attached(message, CREDENTIAL_OFFER_TYPE)
+    }
+}
+
+/// Holder's `request-credential` answering `offer`.
+pub fn credential_request(
+    offer: &DidCommMessage,
+    holder: &Did,
+) -> Result<DidCommMessage, CommonError> {
+    let offered = CredentialOffer::from_message(offer)?;
+    if offer.from.is_none() {
+        return Err(CommonError::InvalidInputError(
+            "credential offer has no issuer to answer".into(),
+        ));
+    }
+    let mut attachment = Attachment::json(
+        Some("request".into()),
+        json!({ "credential_type": offered.credential_type, "holder": holder.to_string() }),
+    );
+    attachment.format = Some(CREDENTIAL_FORMAT.into());
+    Ok(offer
+        .reply(CREDENTIAL_REQUEST_TYPE, json!({ "goal_code": "issue-vc" }))
+        .with_sender(holder)
+        .with_attachment(attachment))
+}
+
+/// Invitation to take part in a governance proposal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposalInvitation {
+    pub proposal_id: String,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voting_deadline: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+impl ProposalInvitation {
+    /// Invitation message from `from` to `invitee`, expiring at the deadline.
+    pub fn to_message(&self, from: &Did, invitee: &Did) -> DidCommMessage {
+        let message = DidCommMessage::new(
+            PROPOSAL_INVITATION_TYPE,
+            serde_json::to_value(self).unwrap_or_default(),
+        )
+        .with_sender(from)
+        .with_recipient(invitee);
+        match self.voting_deadline {
+            Some(deadline) => message.with_expiry(deadline),
+            None => message,
+        }
+    }
+
+    /// Invitation carried by a `proposal-invitation` message.
+    pub fn from_message(message: &DidCommMessage) -> Result<Self, CommonError> {
+        expect_type(message, PROPOSAL_INVITATION_TYPE)?;
+        serde_json::from_value(message.body.clone()).map_err(|e| {
+            CommonError::DeserializationError(format!("Invalid proposal invitation: {e}"))
+        })
+    }
+}
+
+fn expect_type(message: &DidCommMessage, message_type: &str) -> Result<(), CommonError> {
+    if message.message_type != message_type {
+        return Err(CommonError::InvalidInputError(format!(
+            "expected {message_type}, got {}",
+            message.message_type
+        )));
+    }
+    Ok(())
+}
+
+fn attached<T: serde::de::DeserializeOwned>(
+    message: &DidCommMessage,
+    message_type: &str,
+) -> Result<T, CommonError> {
+    expect_type(message, message_type)?;
+    let data = message
+        .attachments
+        .iter()
+        .find(|a| a.format.as_deref() == Some(CREDENTIAL_FORMAT))
+        .and_then(|a| a.data.json.clone())
+        .ok_or_else(|| {
+            CommonError::InvalidInputError(format!("{message_type} has no {CREDENTIAL_FORMAT}"))
+        })?;
+    serde_json::from_value(data)
+        .map_err(|e| CommonError::DeserializationError(format!("Invalid {message_type}: {e}")))
+}
+
+/// Route record published for a DID: its `DIDCommMessaging` service entry,
+/// signed by the DID's key so a DHT peer cannot redirect its mail.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DidCommRoute {
+    pub did: Did,
+    pub service: ServiceEndpoint,
+    /// Signature by `did` over [`DidCommRoute::signable_bytes`].
+    pub signature: SignatureBytes,
+}
+
+impl DidCommRoute {
+    /// Unsigned route sending mail for `did` through `mediator`.
+    pub fn new(did: &Did, mediator: &Did) -> Self {
+        Self {
+            did: did.clone(),
+            service: ServiceEndpoint {
+                id: format!("{did}#didcomm-1"),
+                service_type: DIDCOMM_SERVICE_TYPE.to_string(),
+                service_endpoint: mediator.to_string(),
+                properties: None,
+            },
+            signature: SignatureBytes(Vec::new()),
+        }
+    }
+
+    /// Bytes covered by the route signature.
+    pub fn signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        let mut unsigned = self.clone();
+        unsigned.signature = SignatureBytes(Vec::new());
+        serde_json::to_vec(&unsigned).map_err(|e| CommonError::SerializationError(e.to_string()))
+    }
+
+    /// Sign the route with the DID's key.
+    pub fn sign(&mut self, key: &SigningKey) -> Result<(), CommonError> {
+        let bytes = self.signable_bytes()?;
+        self.signature = SignatureBytes::from_ed_signature(sign_message(key, &bytes));
+        Ok(())
+    }
+
+    /// Check the signature against `key` and return the mediator.
+    pub fn verify(&self, key: &VerifyingKey) -> Result<Did, CommonError> {
+        let signature = self.signature.to_ed_signature()?;
+        if !verify_signature(key, &self.signable_bytes()?, &signature) {
+            return Err(CommonError::PermissionDenied(format!(
+                "DIDComm route is not signed by {}",
+                self.did
+            )));
+        }
+        if self.service.service_type != DIDCOMM_SERVICE_TYPE {
+            return Err(CommonError::InvalidInputError(format!(
+                "{} is not a {DIDCOMM_SERVICE_TYPE} service",
+                self.service.service_type
+            )));
+        }
+        Did::from_str(&self.service.service_endpoint)
+    }
+}
+
+/// Envelope queued by a mediator for an offline recipient.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueuedEnvelope {
+    pub id: String,
+    pub envelope: String,
+    pub received_at: u64,
+}
+
+/// Message unpacked by this agent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InboxMessage {
+    pub message: DidCommMessage,
+    /// Sender proven by authcrypt; `None` for anoncrypt.
+    pub sender: Option<Did>,
+    pub received_at: u64,
+}
+
+/// A DID this mailbox mediates for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediatedRecipient {
+    pub did: Did,
+    pub granted_at: u64,
+    pub queued: usize,
+}
+
+/// What [`DidCommMailbox::handle`] did with a message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MailboxOutcome {
+    /// A forward was queued for a mediated recipient.
+    Queued { recipient: Did, id: String },
+    /// A mediation or pickup request to answer with this message.
+    Reply(Box<DidCommMessage>),
+    /// Delivered envelopes were removed from a queue.
+    Acknowledged(usize),
+    /// A mediator this agent asked for mediation granted it.
+    Granted { mediator: Did },
+    /// The message was stored in the inbox.
+    Inbox,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct MailboxState {
+    mediated: BTreeMap<String, u64>,
+    queues: BTreeMap<String, Vec<QueuedEnvelope>>,
+    inbox: Vec<InboxMessage>,
+    /// Outstanding mediate-requests: thread id to the mediator asked.
+    #[serde(default)]
+    requested: BTreeMap<String, String>,
+    /// DIDs whose mediate-requests this mailbox grants.
+    #[serde(default)]
+    allowed: BTreeSet<String>,
+}
+
+impl MailboxState {
+    fn queued_bytes(&self) -> usize {
+        self.queues
+            .values()
+            .flatten()
+            .map(|q| q.envelope.len())
+            .sum()
+    }
+}
+
+/// Inbox of unpacked messages plus the mediator's per-recipient queues,
+/// optionally persisted to a JSON file. Clones share state.
+#[derive(Debug, Clone, Default)]
+pub struct DidCommMailbox {
+    path: Option<PathBuf>,
+    state: Arc<Mutex<MailboxState>>,
+}
+
+impl DidCommMailbox {
+    /// Mailbox that lives only in memory.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Mailbox persisted at `path`, loading any existing state.
+    pub fn open(path: PathBuf) -> Result<Self, CommonError> {
+        let state = if path.exists() {
+            let mut contents = String::new();
+            File::open(&path)
+                .and_then(|mut f| f.read_to_string(&mut contents))
+                .map_err(|e| {
+                    CommonError::IoError(format!("Failed to read DIDComm mailbox {path:?}: {e}"))
+                })?;
+            if contents.trim().is_empty() {
+                MailboxState::default()
+            } else {
+                serde_json::from_str(&contents).map_err(|e| {
+                    CommonError::DeserializationError(format!(
+                        "Failed to parse DIDComm mailbox {path:?}: {e}"
+                    ))
+                })?
+            }
+        } else {
+            MailboxState::default()
+        };
+        Ok(Self {
+            path: Some(path),
+            state: Arc::new(Mutex::new(state)),
+        })
+    }
+
+    /// Start mediating for `did`.
+    pub fn grant(&self, did: &Did, now: u64) -> Result<(), CommonError> {
+        let mut state = self.lock()?;
+        state.mediated.entry(did.to_string()).or_insert(now);
+        self.persist(&state)
+    }
+
+    /// Let `did` request mediation from this mailbox.
+    pub fn allow_mediation(&self, did: &Did) -> Result<(), CommonError> {
+        let mut state = self.lock()?;
+        state.allowed.insert(did.to_string());
+        self.persist(&state)
+    }
+
+    /// Refuse further mediate-requests from `did`. Existing mediation is
+    /// kept until [`DidCommMailbox::revoke`].
+    pub fn disallow_mediation(&self, did: &Did) -> Result<(), CommonError> {
+        let mut state = self.lock()?;
+        state.allowed.remove(&did.to_string());
+        self.persist(&state)
+    }
+
+    /// DIDs allowed to request mediation.
+    pub fn mediation_allowlist(&self) -> Result<Vec<Did>, CommonError> {
+        self.lock()?
+            .allowed
+            .iter()
+            .map(|did| Did::from_str(did))
+            .collect()
+    }
+
+    /// Stop mediating for `did`, dropping its queue.
+    pub fn revoke(&self, did: &Did) -> Result<(), CommonError> {
+        let mut state = self.lock()?;
+        state.mediated.remove(&did.to_string());
+        state.queues.remove(&did.to_string());
+        self.persist(&state)
+    }
+
+    /// Record that a mediate-request on thread `thread_id` went to `mediator`,
+    /// so only that mediator's grant is accepted.
+    pub fn request_mediation(&self, mediator: &Did, thread_id: &str) -> Result<(), CommonError> {
+        let mut state = self.lock()?;
+        state
+            .requested
+            .insert(thread_id.to_string(), mediator.to_string());
+        self.persist(&state)
+    }
+
+    pub fn is_mediated(&self, did: &Did) -> Result<bool, CommonError> {
+        Ok(self.lock()?.mediated.contains_key(&did.to_string()))
+    }
+
+    /// DIDs this mailbox mediates for, with their queue lengths.
+    pub fn mediated(&self) -> Result<Vec<MediatedRecipient>, CommonError> {
+        let state = self.lock()?;
+        state
+            .mediated
+            .iter()
+            .map(|(did, granted_at)| {
+                Ok(MediatedRecipient {
+                    did: Did::from_str(did)?,
+                    granted_at: *granted_at,
+                    queued: state.queues.get(did).map_or(0, Vec::len),
+                })
+            })
+            .collect()
+    }
+
+    /// Queue `envelope` for a mediated `recipient`, within the per-recipient
+    /// and total limits.
+    pub fn enqueue(
+        &self,
+        recipient: &Did,
+        envelope: &str,
+        now: u64,
+    ) -> Result<String, CommonError> {
+        let mut state = self.lock()?;
+        let key = recipient.to_string();
+        if !state.mediated.contains_key(&key) {
+            return Err(CommonError::PolicyDenied(format!(
+                "not mediating for {recipient}"
+            )));
+        }
+        if state.queued_bytes().saturating_add(envelope.len()) > MAX_MAILBOX_BYTES {
+            return Err(CommonError::PolicyDenied("mediator mailbox is full".into()));
+        }
+        let queue = state.queues.entry(key).or_default();
+        if queue.len() >= MAX_QUEUED_ENVELOPES {
+            return Err(CommonError::PolicyDenied(format!(
+                "mailbox for {recipient} is full"
+            )));
+        }
+        let id = hex_digest(envelope.as_bytes());
+        if !queue.iter().any(|q| q.id == id) {
+            queue.push(QueuedEnvelope {
+                id: id.clone(),
+                envelope: envelope.to_string(),
+                received_at: now,
+            });
+        }
+        self.persist(&state)?;
+        Ok(id)
+    }
+
+    /// Up to `limit` queued envelopes for `recipient`, oldest first.
+    pub fn pending(
+        &self,
+        recipient: &Did,
+        limit: usize,
+    ) -> Result<Vec<QueuedEnvelope>, CommonError> {
+        Ok(self
+            .lock()?
+            .queues
+            .get(&recipient.to_string())
+            .map(|queue| queue.iter().take(limit).cloned().collect())
+            .unwrap_or_default())
+    }
+
+    /// Remove delivered envelopes, returning how many were removed.
+    pub fn acknowledge(&self, recipient: &Did, ids: &[String]) -> Result<usize, CommonError> {
+        let mut state = self.lock()?;
+        let Some(queue) = state.queues.get_mut(&recipient.to_string()) else {
+            return Ok(0);
+        };
+        let before = queue.len();
+        queue.retain(|q| !ids.contains(&q.id));
+        let removed = before - queue.len();
+        self.persist(&state)?;
+        Ok(removed)
+    }
+
+    /// Store an unpacked message in the inbox.
+    pub fn deliver(&self, unpacked: UnpackedMessage, now: u64) -> Result<(), CommonError> {
+        let mut state = self.lock()?;
+        if state
+            .inbox
+            .iter()
+            .any(|m| m.message.id == unpacked.message.id)
+        {
+            return Ok(());
+        }
+        state.inbox.push(InboxMessage {
+            message: unpacked.message,
+            sender: unpacked.sender,
+            received_at: now,
+        });
+        let excess = state.inbox.len().saturating_sub(MAX_INBOX_MESSAGES);
+        state.inbox.drain(..excess);
+        self.persist(&state)
+    }
+
+    /// Inbox messages, oldest first.
+    pub fn inbox(&self) -> Result<Vec<InboxMessage>, CommonError> {
+        Ok(self.lock()?.inbox.clone())
+    }
+
+    /// Inbox message with id `id`.
+    pub fn inbox_message(&self, id: &str) -> Result<Option<InboxMessage>, CommonError> {
+        Ok(self
+            .lock()?
+            .inbox
+            .iter()
+            .find(|m| m.message.id == id)
+            .cloned())
+    }
+
+    /// Apply a message unpacked by `mediator`.
+    ///
+    /// Forwards, Coordinate Mediation and Message Pickup requests are handled
+    /// here; every other message goes to the inbox. Mediation and pickup
+    /// require an authcrypt sender, forwards a mediated recipient, and grants
+    /// a matching [`DidCommMailbox::request_mediation`].
+    pub fn handle(
+        &self,
+        mediator: &Did,
+        unpacked: UnpackedMessage,
+        now: u64,
+    ) -> Result<MailboxOutcome, CommonError> {
+        let message = &unpacked.message;
+        match message.message_type.as_str() {
+            FORWARD_TYPE => {
+                let (recipient, envelope) = message.forward_payload()?;
+                if !self.is_mediated(&recipient)? {
+                    return Err(CommonError::PolicyDenied(format!(
+                        "not mediating for {recipient}"
+                    )));
+                }
+                let id = self.enqueue(&recipient, &envelope, now)?;
+                Ok(MailboxOutcome::Queued { recipient, id })
+            }
+            MEDIATE_REQUEST_TYPE => {
+                let sender = authenticated(&unpacked)?;
+                if !self.lock()?.allowed.contains(&sender.to_string()) {
+                    return Err(CommonError::PolicyDenied(format!(
+                        "{sender} may not request mediation"
+                    )));
+                }
+                self.grant(sender, now)?;
+                Ok(MailboxOutcome::Reply(Box::new(
+                    message
+                        .reply(
+                            MEDIATE_GRANT_TYPE,
+                            json!({ "routing_did": [mediator.to_string()] }),
+                        )
+                        .with_sender(mediator),
+                )))
+            }
+            MEDIATE_GRANT_TYPE => {
+                let sender = authenticated(&unpacked)?;
+                let mut state = self.lock()?;
+                let thread_id = message.thread_id();
+                if state.requested.get(thread_id) != Some(&sender.to_string()) {
+                    return Err(CommonError::PermissionDenied(format!(
+                        "unsolicited mediation grant from {sender}"
+                    )));
+                }
+                state.requested.remove(thread_id);
+                self.persist(&state)?;
+                Ok(MailboxOutcome::Granted {
+                    mediator: sender.clone(),
+                })
+            }
+            DELIVERY_REQUEST_TYPE => {
+                let sender = authenticated(&unpacked)?;
+                if !self.is_mediated(sender)? {
+                    return Err(CommonError::PolicyDenied(format!(
+                        "not mediating for {sender}"
+                    )));
+                }
+                let limit = message
+                    .body
+                    .get("limit")
+                    .and_then(Value::as_u64)
+                    .map_or(DEFAULT_DELIVERY_LIMIT, |l| l as usize);
+                let pending = self.pending(sender, limit)?;
+                let reply = if pending.is_empty() {
+                    message.reply(
+                        PICKUP_STATUS_TYPE,
+                        json!({ "recipient_did": sender.to_string(), "message_count": 0 }),
+                    )
+                } else {
+                    let mut reply = message.reply(DELIVERY_TYPE, json!({}));
+                    for queued in pending {
+                        let envelope: Value = serde_json::from_str(&queued.envelope)
+                            .map_err(|e| CommonError::DeserializationError(e.to_string()))?;
+                        reply = reply.with_attachment(Attachment::json(Some(queued.id), envelope));
+                    }
+                    reply
+                };
+                Ok(MailboxOutcome::Reply(Box::new(reply.with_sender(mediator))))
+            }
+            MESSAGES_RECEIVED_TYPE => {
+                let sender = authenticated(&unpacked)?;
+                let ids: Vec<String> = message
+                    .body
+                    .get("message_id_list")
+                    .cloned()
+                    .map(serde_json::from_value)
+                    .transpose()
+                    .map_err(|e| {
+                        CommonError::InvalidInputError(format!("invalid message_id_list: {e}"))
+                    })?
+                    .unwrap_or_default();
+                Ok(MailboxOutcome::Acknowledged(
+                    self.acknowledge(sender, &ids)?,
+                ))
+            }
+            _ => {
+                self.deliver(unpacked, now)?;
+                Ok(MailboxOutcome::Inbox)
+            }
+        }
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, MailboxState>, CommonError> {
+        self.state
+            .lock()
+            .map_err(|_| CommonError::LockError("DIDComm mailbox poisoned".into()))
+    }
+
+    fn persist(&self, state: &MailboxState) -> Result<(), CommonError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let serialized = serde_json::to_string(state).map_err(|e| {
+            CommonError::SerializationError(format!("Failed to serialize DIDComm mailbox: {e}"))
+        })?;
+        let tmp_path = path.with_extension("tmp");
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&tmp_path)
+            .map_err(|e| {
+                CommonError::IoError(format!("Failed to open DIDComm mailbox {tmp_path:?}: {e}"))
+            })?;
+        file.write_all(serialized.as_bytes())
+            .and_then(|_| file.sync_all())
+            .map_err(|e| {
+                CommonError::IoError(format!("Failed to write DIDComm mailbox {tmp_path:?}: {e}"))
+            })?;
+        drop(file);
+        rename(&tmp_path, path).map_err(|e| {
+            CommonError::IoError(format!(
+                "Failed to rename DIDComm mailbox {tmp_path:?} -> {path:?}: {e}"
+            ))
+        })
+    }
+}
+
+fn authenticated(unpacked: &UnpackedMessage) -> Result<&Did, CommonError> {
+    unpacked.sender.as_ref().ok_or_else(|| {
+        CommonError::PermissionDenied(format!(
+            "{} must be sent with authcrypt",
+            unpacked.message.message_type
+        ))
+    })
+}
+
+fn hex_digest(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{did_key_from_verifying_key, generate_ed25519_keypair, KeyDidResolver};
+
+    fn party() -> (Did, SigningKey) {
+        let (sk, pk) = generate_ed25519_keypair();
+        (Did::from_str(&did_key_from_verifying_key(&pk)).unwrap(), sk)
+    }
+
+    #[test]
+    fn authcrypt_proves_sender_and_anoncrypt_hides_it() {
+        let (alice, alice_sk) = party();
+        let (bob, bob_sk) = party();
+        let (carol, carol_sk) = party();
+        let resolver = KeyDidResolver;
+        let message = DidCommMessage::new("https://example.org/test/1.0/ping", json!({"n": 1}))
+            .with_sender(&alice)
+            .with_recipient(&bob)
+            .with_recipient(&carol);
+        let recipients = [
+            (bob.clone(), bob_sk.verifying_key()),
+            (carol.clone(), carol_sk.verifying_key()),
+        ];
+
+        let envelope = pack_authcrypt(&message, &alice, &alice_sk, &recipients).unwrap();
+        assert!(!envelope.contains("\"n\""));
+        for (did, sk) in [(&bob, &bob_sk), (&carol, &carol_sk)] {
+            let unpacked = unpack(&envelope, did, sk, &resolver).unwrap();
+            assert_eq!(unpacked.message, message);
+            assert_eq!(unpacked.sender.as_ref(), Some(&alice));
+        }
+        assert!(unpack(&envelope, &alice, &alice_sk, &resolver).is_err());
+
+        // A tampered ciphertext fails authentication.
+        let mut jwe: Value = serde_json::from_str(&envelope).unwrap();
+        let ct = jwe["ciphertext"].as_str().unwrap();
+        let flipped = if ct.starts_with('A') { "B" } else { "A" };
+        jwe["ciphertext"] = Value::String(format!("{flipped}{}", &ct[1..]));
+        assert!(unpack(&jwe.to_string(), &bob, &bob_sk, &resolver).is_err());
+
+        // Authcrypt refuses to claim another sender.
+        let (mallory, mallory_sk) = party();
+        assert!(pack_authcrypt(&message, &mallory, &mallory_sk, &recipients).is_err());
+
+        let envelope = pack_anoncrypt(&message, &recipients).unwrap();
+        let unpacked = unpack(&envelope, &bob, &bob_sk, &resolver).unwrap();
+        assert_eq!(unpacked.sender, None);
+        assert!(!envelope.contains(&alice.to_string()));
+
+        let expired = DidCommMessage::new("https://example.org/test/1.0/ping", json!({}))
+            .with_recipient(&bob)
+            .with_expiry(1);
+        let envelope = pack_anoncrypt(&expired, &recipients[..1]).unwrap();
+        assert!(matches!(
+            unpack(&envelope, &bob, &bob_sk, &resolver),
+            Err(CommonError::PolicyDenied(_))
+        ));
+    }
+
+    #[test]
+    fn mediator_queues_forwards_until_pickup() {
+        let (alice, alice_sk) = party();
+        let (bob, bob_sk) = party();
+        let (mediator, mediator_sk) = party();
+        let resolver = KeyDidResolver;
+        let mailbox = DidCommMailbox::in_memory();
+        let to_mediator = [(mediator.clone(), mediator_sk.verifying_key())];
+
+        // Forwards for recipients without mediation are refused.
+        let offer = CredentialOffer {
+            credential_type: "MembershipCredential".into(),
+            claims: BTreeMap::from([("role".to_string(), "member".to_string())]),
+            comment: None,
+        }
+        .to_message(&alice, &bob);
+        let inner = pack_authcrypt(
+            &offer,
+            &alice,
+            &alice_sk,
+            &[(bob.clone(), bob_sk.verifying_key())],
+        )
+        .unwrap();
+        let forward = wrap_forward(&inner, &bob, &mediator, &mediator_sk.verifying_key()).unwrap();
+        let unpacked = unpack(&forward, &mediator, &mediator_sk, &resolver).unwrap();
+        assert!(matches!(
+            mailbox.handle(&mediator, unpacked.clone(), 10),
+            Err(CommonError::PolicyDenied(_))
+        ));
+        assert!(mailbox.pending(&bob, 10).unwrap().is_empty());
+
+        let request = DidCommMessage::new(MEDIATE_REQUEST_TYPE, json!({}))
+            .with_sender(&bob)
+            .with_recipient(&mediator);
+        let anon = pack_anoncrypt(&request, &to_mediator).unwrap();
+        let anon = unpack(&anon, &mediator, &mediator_sk, &resolver).unwrap();
+        assert!(matches!(
+            mailbox.handle(&mediator, anon, 10),
+            Err(CommonError::PermissionDenied(_))
+        ));
+        let auth = pack_authcrypt(&request, &bob, &bob_sk, &to_mediator).unwrap();
+        let auth = unpack(&auth, &mediator, &mediator_sk, &resolver).unwrap();
+        assert!(matches!(
+            mailbox.handle(&mediator, auth.clone(), 10),
+            Err(CommonError::PolicyDenied(_))
+        ));
+        mailbox.allow_mediation(&bob).unwrap();
+        let MailboxOutcome::Reply(grant) = mailbox.handle(&mediator, auth, 10).unwrap() else {
+            panic!("expected a grant");
+        };
+        assert_eq!(grant.message_type, MEDIATE_GRANT_TYPE);
+        assert_eq!(grant.thread_id(), request.id);
+
+        let outcome = mailbox.handle(&mediator, unpacked, 11).unwrap();
+        assert!(
+            matches!(outcome, MailboxOutcome::Queued { ref recipient, .. } if recipient == &bob)
+        );
+        assert_eq!(mailbox.mediated().unwrap()[0].queued, 1);
+
+        let pickup = DidCommMessage::new(DELIVERY_REQUEST_TYPE, json!({ "limit": 5 }))
+            .with_sender(&bob)
+            .with_recipient(&mediator);
+        let pickup = pack_authcrypt(&pickup, &bob, &bob_sk, &to_mediator).unwrap();
+        let pickup = unpack(&pickup, &mediator, &mediator_sk, &resolver).unwrap();
+        let MailboxOutcome::Reply(delivery) = mailbox.handle(&mediator, pickup, 12).unwrap() else {
+            panic!("expected a delivery");
+        };
+        let delivered = delivery.delivered_envelopes().unwrap();
+        assert_eq!(delivered.len(), 1);
+        let received = unpack(&delivered[0].1, &bob, &bob_sk, &resolver).unwrap();
+        assert_eq!(received.sender.as_ref(), Some(&alice));
+        assert_eq!(
+            CredentialOffer::from_message(&received.message)
+                .unwrap()
+                .credential_type,
+            "MembershipCredential"
+        );
+        let request = credential_request(&received.message, &bob).unwrap();
+        assert_eq!(request.thread_id(), offer.id);
+        assert_eq!(request.to, vec![alice.to_string()]);
+
+        let ack = DidCommMessage::new(
+            MESSAGES_RECEIVED_TYPE,
+            json!({ "message_id_list": [delivered[0].0] }),
+        )
+        .with_sender(&bob)
+        .with_recipient(&mediator);
+        let ack = pack_authcrypt(&ack, &bob, &bob_sk, &to_mediator).unwrap();
+        let ack = unpack(&ack, &mediator, &mediator_sk, &resolver).unwrap();
+        assert_eq!(
+            mailbox.handle(&mediator, ack, 13).unwrap(),
+            MailboxOutcome::Acknowledged(1)
+        );
+        assert!(mailbox.pending(&bob, 5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn mediation_grant_needs_matching_request() {
+        let (bob, bob_sk) = party();
+        let (mediator, mediator_sk) = party();
+        let (rogue, rogue_sk) = party();
+        let resolver = KeyDidResolver;
+        let mailbox = DidCommMailbox::in_memory();
+        let request = DidCommMessage::new(MEDIATE_REQUEST_TYPE, json!({}))
+            .with_sender(&bob)
+            .with_recipient(&mediator);
+        let grant_from = |from: &Did, key: &SigningKey, thread: &DidCommMessage| {
+            let grant = thread
+                .reply(
+                    MEDIATE_GRANT_TYPE,
+                    json!({ "routing_did": [from.to_string()] }),
+                )
+                .with_sender(from);
+            let packed =
+                pack_authcrypt(&grant, from, key, &[(bob.clone(), bob_sk.verifying_key())])
+                    .unwrap();
+            unpack(&packed, &bob, &bob_sk, &resolver).unwrap()
+        };
+
+        let unsolicited = grant_from(&mediator, &mediator_sk, &request);
+        assert!(matches!(
+            mailbox.handle(&bob, unsolicited, 1),
+            Err(CommonError::PermissionDenied(_))
+        ));
+
+        mailbox.request_mediation(&mediator, &request.id).unwrap();
+        let rogue_grant = grant_from(&rogue, &rogue_sk, &request);
+        assert!(mailbox.handle(&bob, rogue_grant, 2).is_err());
+        let other_thread = DidCommMessage::new(MEDIATE_REQUEST_TYPE, json!({}));
+        let wrong_thread = grant_from(&mediator, &mediator_sk, &other_thread);
+        assert!(mailbox.handle(&bob, wrong_thread, 2).is_err());
+
+        let grant = grant_from(&mediator, &mediator_sk, &request);
+        assert_eq!(
+            mailbox.handle(&bob, grant.clone(), 3).unwrap(),
+            MailboxOutcome::Granted {
+                mediator: mediator.clone()
+            }
+        );
+        assert!(mailbox.handle(&bob, grant, 4).is_err());
+    }
+
+    #[test]
+    fn route_must_be_signed_by_its_did() {
+        let (bob, bob_sk) = party();
+        let (mediator, _) = party();
+        let (_, rogue_sk) = party();
+        let mut route = DidCommRoute::new(&bob, &mediator);
+        route.sign(&bob_sk).unwrap();
+        assert_eq!(route.verify(&bob_sk.verifying_key()).unwrap(), mediator);
+
+        let mut forged = DidCommRoute::new(&bob, &mediator);
+        forged.sign(&rogue_sk).unwrap();
+        assert!(forged.verify(&bob_sk.verifying_key()).is_err());
+        route.service.service_endpoint = bob.to_string();
+        assert!(route.verify(&bob_sk.verifying_key()).is_err());
+    }
+
+    #[test]
+    fn mailbox_persists_inbox_and_queues() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("didcomm.json");
+        let (alice, _) = party();
+        let (bob, _) = party();
+        let mailbox = DidCommMailbox::open(path.clone()).unwrap();
+        mailbox.grant(&bob, 1).unwrap();
+        mailbox.allow_mediation(&alice).unwrap();
+        mailbox.enqueue(&bob, "{}", 2).unwrap();
+        let oversized = "x".repeat(MAX_MAILBOX_BYTES);
+        assert!(matches!(
+            mailbox.enqueue(&bob, &oversized, 2),
+            Err(CommonError::PolicyDenied(_))
+        ));
+        let invitation = ProposalInvitation {
+            proposal_id: "prop-1".into(),
+            description: "Adopt the budget".into(),
+            voting_deadline: None,
+            comment: None,
+        }
+        .to_message(&alice, &bob);
+        mailbox
+            .deliver(
+                UnpackedMessage {
+                    message: invitation.clone(),
+                    sender: Some(alice.clone()),
+                },
+                3,
+            )
+            .unwrap();
+
+        let reopened = DidCommMailbox::open(path).unwrap();
+        assert_eq!(reopened.pending(&bob, 10).unwrap().len(), 1);
+        assert_eq!(reopened.mediation_allowlist().unwrap(), vec![alice.clone()]);
+        let stored = reopened.inbox_message(&invitation.id).unwrap().unwrap();
+        assert_eq!(stored.sender, Some(alice));
+        assert_eq!(
+            ProposalInvitation::from_message(&stored.message)
+                .unwrap()
+                .proposal_id,
+            "prop-1"
+        );
+    }
+}
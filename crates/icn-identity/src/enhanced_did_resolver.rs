@@ -3,12 +3,13 @@
 //! This module provides a comprehensive DID resolution system that can handle
 //! multiple DID methods efficiently with LRU caching and invalidation mechanisms.
 
-use crate::{DidResolver, KeyDidResolver, PeerDidResolver, WebDidResolver};
+use crate::{DidResolver, IcnDidResolver, KeyDidResolver, PeerDidResolver, WebDidResolver};
 use icn_common::{CommonError, Did, TimeProvider};
 use lru::LruCache;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::num::NonZeroUsize;
+use std::str::FromStr;
 use std::sync::{Arc, Mutex, RwLock};
 
 /// Cache entry for DID resolution results with access tracking
@@ -16,6 +17,8 @@ use std::sync::{Arc, Mutex, RwLock};
 struct CacheEntry {
     verifying_key: ed25519_dalek::VerifyingKey,
     expires_at: u64,
+    /// Key event sequence a `did:icn` entry was resolved at.
+    key_event_sequence: Option<u64>,
     #[allow(dead_code)]
     method_used: String,
     #[allow(dead_code)]
@@ -70,6 +73,7 @@ pub struct EnhancedDidResolver {
     key_resolver: KeyDidResolver,
     peer_resolver: PeerDidResolver,
     web_resolver: Arc<RwLock<WebDidResolver>>,
+    icn_resolver: IcnDidResolver,
 
     // Statistics
     stats: Arc<RwLock<ResolutionStats>>,
@@ -106,6 +110,7 @@ impl EnhancedDidResolver {
             key_resolver: KeyDidResolver,
             peer_resolver: PeerDidResolver,
             web_resolver: Arc::new(RwLock::new(WebDidResolver::default())),
+            icn_resolver: IcnDidResolver::default(),
             stats: Arc::new(RwLock::new(ResolutionStats::default())),
             method_stats: Arc::new(RwLock::new(HashMap::new())),
         }
@@ -123,6 +128,19 @@ impl EnhancedDidResolver {
         }
     }
 
+    /// Resolve `did:icn` identifiers from the key event logs in `resolver`.
+    pub fn with_icn_resolver(mut self, resolver: IcnDidResolver) -> Self {
+        self.icn_resolver = resolver;
+        self
+    }
+
+    /// Drop any cached resolution for `did`
+    pub fn invalidate(&self, did: &Did) {
+        if let Ok(mut cache) = self.lru_cache.lock() {
+            cache.pop(&did.to_string());
+        }
+    }
+
     /// Get resolution statistics
     pub fn get_stats(&self) -> ResolutionStats {
         self.stats.read().unwrap().clone()
@@ -222,6 +240,7 @@ impl EnhancedDidResolver {
         match method {
             "key" => self.key_resolver.resolve(did),
             "peer" => self.peer_resolver.resolve(did),
+            "icn" => self.icn_resolver.resolve(did),
             "web" => {
                 if let Ok(resolver) = self.web_resolver.read() {
                     resolver.resolve(did)
@@ -242,9 +261,11 @@ impl EnhancedDidResolver {
         let mut cache = self.lru_cache.lock().ok()?;
         let entry = cache.get(did_string)?.clone();
 
-        // Check if expired
+        // Check if expired, or if a did:icn key event arrived since caching
         let now = self.time_provider.unix_seconds();
-        if now >= entry.expires_at {
+        let rotated = entry.key_event_sequence.is_some()
+            && entry.key_event_sequence != self.key_event_sequence(did_string);
+        if now >= entry.expires_at || rotated {
             // Entry expired, remove it
             drop(cache);
             if let Ok(mut cache) = self.lru_cache.lock() {
@@ -268,6 +289,7 @@ impl EnhancedDidResolver {
                 CacheEntry {
                     verifying_key: key,
                     expires_at,
+                    key_event_sequence: self.key_event_sequence(did_string),
                     method_used: method.to_string(),
                     access_count: 1,
                     last_accessed: self.time_provider.unix_seconds(),
@@ -276,6 +298,16 @@ impl EnhancedDidResolver {
         }
     }
 
+    /// Current key event sequence of a `did:icn` identifier
+    fn key_event_sequence(&self, did_string: &str) -> Option<u64> {
+        let did = Did::from_str(did_string).ok()?;
+        if did.method == crate::DID_ICN_METHOD {
+            self.icn_resolver.sequence(&did)
+        } else {
+            None
+        }
+    }
+
     /// Evict oldest cache entry (simple FIFO for now)
     #[allow(dead_code)]
     fn evict_oldest_entry(&self, cache: &mut HashMap<String, CacheEntry>) {
@@ -355,6 +387,7 @@ impl DidResolver for EnhancedDidResolver {
 pub struct EnhancedDidResolverBuilder {
     config: DidResolutionConfig,
     web_keys: HashMap<String, ed25519_dalek::VerifyingKey>,
+    icn_resolver: Option<IcnDidResolver>,
 }
 
 impl EnhancedDidResolverBuilder {
@@ -362,6 +395,7 @@ impl EnhancedDidResolverBuilder {
         Self {
             config: DidResolutionConfig::default(),
             web_keys: HashMap::new(),
+            icn_resolver: None,
         }
     }
 
@@ -395,8 +429,16 @@ impl EnhancedDidResolverBuilder {
         self
     }
 
+    pub fn with_icn_resolver(mut self, resolver: IcnDidResolver) -> Self {
+        self.icn_resolver = Some(resolver);
+        self
+    }
+
     pub fn build(self, time_provider: Arc<dyn TimeProvider>) -> EnhancedDidResolver {
-        let resolver = EnhancedDidResolver::new(self.config, time_provider);
+        let mut resolver = EnhancedDidResolver::new(self.config, time_provider);
+        if let Some(icn_resolver) = self.icn_resolver {
+            resolver = resolver.with_icn_resolver(icn_resolver);
+        }
 
         // Add web keys
         for (did, key) in self.web_keys {
@@ -323,6 +323,12 @@ pub enum CrossFederationProposalType {
         policy_scope: String,
         coordination_mechanisms: Vec<String>,
     },
+    /// Budget funded jointly by several federations
+    JointBudget {
+        purpose: String,
+        /// Mana pledged by each federation
+        contributions: HashMap<String, u64>,
+    },
 }
 
 /// Information about a federation
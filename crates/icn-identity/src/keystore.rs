@@ -0,0 +1,491 @@
+//! Passphrase-encrypted keystore for Ed25519 keys
+//!
+//! A keystore is a JSON file holding any number of labelled keys. The
+//! passphrase is stretched with Argon2id into a file key, and each private
+//! key is sealed with XChaCha20-Poly1305 under that key. Labels, DIDs and
+//! public keys stay in clear text so keys can be listed without the
+//! passphrase; they are bound to the ciphertext as associated data.
+//!
+//! Every change is written to disk immediately through a temporary file and
+//! a rename, so a crash never leaves a half-written keystore behind.
+
+use crate::{did_key_from_verifying_key, generate_ed25519_keypair, SigningKey, VerifyingKey};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use icn_common::CommonError;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::fs::{rename, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zeroize::{Zeroize, Zeroizing};
+
+/// Current keystore format version.
+pub const KEYSTORE_VERSION: u32 = 1;
+
+/// Label used for the node identity key.
+pub const DEFAULT_KEY_LABEL: &str = "node";
+
+const KDF_ALGORITHM: &str = "argon2id";
+const CIPHER: &str = "xchacha20poly1305";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const CHECK_PLAINTEXT: &[u8] = b"icn-keystore";
+
+/// Argon2id parameters used to derive the file key from the passphrase.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub algorithm: String,
+    /// Memory cost in KiB.
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    #[serde(with = "serde_bytes")]
+    pub salt: Vec<u8>,
+}
+
+impl KdfParams {
+    /// Argon2id parameters with a fresh random salt.
+    pub fn new(memory_kib: u32, iterations: u32, parallelism: u32) -> Self {
+        let mut salt = vec![0u8; SALT_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+        Self {
+            algorithm: KDF_ALGORITHM.to_string(),
+            memory_kib,
+            iterations,
+            parallelism,
+            salt,
+        }
+    }
+
+    /// Same cost, new salt.
+    fn resalted(&self) -> Self {
+        Self::new(self.memory_kib, self.iterations, self.parallelism)
+    }
+
+    fn derive(&self, passphrase: &[u8]) -> Result<Zeroizing<[u8; 32]>, CommonError> {
+        if self.algorithm != KDF_ALGORITHM {
+            return Err(CommonError::CryptoError(format!(
+                "unsupported keystore KDF {}",
+                self.algorithm
+            )));
+        }
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, Some(32))
+            .map_err(|e| CommonError::CryptoError(format!("invalid Argon2id parameters: {e}")))?;
+        let mut key = Zeroizing::new([0u8; 32]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase, &self.salt, key.as_mut())
+            .map_err(|e| CommonError::CryptoError(format!("key derivation failed: {e}")))?;
+        Ok(key)
+    }
+}
+
+impl Default for KdfParams {
+    /// 64 MiB, three passes, one lane.
+    fn default() -> Self {
+        Self::new(64 * 1024, 3, 1)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Sealed {
+    #[serde(with = "serde_bytes")]
+    nonce: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    ciphertext: Vec<u8>,
+}
+
+/// A labelled key in a keystore.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoreEntry {
+    pub label: String,
+    /// `did:key` identifier of the key.
+    pub did: String,
+    #[serde(with = "serde_bytes")]
+    pub public_key: Vec<u8>,
+    sealed: Sealed,
+}
+
+impl KeystoreEntry {
+    fn aad(&self) -> Vec<u8> {
+        entry_aad(&self.label, &self.did, &self.public_key)
+    }
+}
+
+fn entry_aad(label: &str, did: &str, public_key: &[u8]) -> Vec<u8> {
+    let mut aad = b"icn-keystore-key".to_vec();
+    aad.extend_from_slice(label.as_bytes());
+    aad.push(0);
+    aad.extend_from_slice(did.as_bytes());
+    aad.push(0);
+    aad.extend_from_slice(public_key);
+    aad
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct KeystoreFile {
+    version: u32,
+    cipher: String,
+    kdf: KdfParams,
+    /// Known plaintext sealed under the file key to detect a wrong passphrase.
+    check: Sealed,
+    keys: Vec<KeystoreEntry>,
+}
+
+/// An unlocked keystore file.
+pub struct Keystore {
+    path: PathBuf,
+    file: KeystoreFile,
+    key: Zeroizing<[u8; 32]>,
+}
+
+impl std::fmt::Debug for Keystore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Keystore")
+            .field("path", &self.path)
+            .field("keys", &self.file.keys.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Keystore {
+    /// Create an empty keystore at `path` with the default KDF cost.
+    pub fn create(path: impl Into<PathBuf>, passphrase: &[u8]) -> Result<Self, CommonError> {
+        Self::create_with_kdf(path, passphrase, KdfParams::default())
+    }
+
+    /// Create an empty keystore at `path` with explicit KDF parameters.
+    pub fn create_with_kdf(
+        path: impl Into<PathBuf>,
+        passphrase: &[u8],
+        kdf: KdfParams,
+    ) -> Result<Self, CommonError> {
+        let path = path.into();
+        if path.exists() {
+            return Err(CommonError::InvalidInputError(format!(
+                "{} already exists",
+                path.display()
+            )));
+        }
+        let key = kdf.derive(passphrase)?;
+        let keystore = Self {
+            file: KeystoreFile {
+                version: KEYSTORE_VERSION,
+                cipher: CIPHER.to_string(),
+                check: seal(&key, CHECK_PLAINTEXT, b"icn-keystore-check")?,
+                kdf,
+                keys: Vec::new(),
+            },
+            path,
+            key,
+        };
+        keystore.save()?;
+        Ok(keystore)
+    }
+
+    /// Unlock the keystore at `path`.
+    pub fn open(path: impl Into<PathBuf>, passphrase: &[u8]) -> Result<Self, CommonError> {
+        let path = path.into();
+        let file = read_file(&path)?;
+        let key = file.kdf.derive(passphrase)?;
+        open_sealed(&key, &file.check, b"icn-keystore-check").map_err(|_| {
+            CommonError::PermissionDenied(format!(
+                "wrong passphrase for keystore {}",
+                path.display()
+            ))
+        })?;
+        Ok(Self { path, file, key })
+    }
+
+    /// Keys in the keystore at `path`, read without the passphrase.
+    pub fn list(path: impl AsRef<Path>) -> Result<Vec<KeystoreEntry>, CommonError> {
+        Ok(read_file(path.as_ref())?.keys)
+    }
+
+    /// Whether `path` holds a keystore rather than some other key file.
+    ///
+    /// Legacy key files are binary, so a JSON file that does not parse as a
+    /// keystore is reported as corrupt instead of being read as a legacy key.
+    /// A missing file is not a keystore.
+    pub fn is_keystore(path: impl AsRef<Path>) -> Result<bool, CommonError> {
+        let path = path.as_ref();
+        let contents = match std::fs::read(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => {
+                return Err(CommonError::IoError(format!(
+                    "Failed to read key file {path:?}: {e}"
+                )))
+            }
+        };
+        let looks_like_json = std::str::from_utf8(&contents)
+            .map(|text| text.trim_start().starts_with('{'))
+            .unwrap_or(false);
+        if !looks_like_json {
+            return Ok(false);
+        }
+        read_file(path)?;
+        Ok(true)
+    }
+
+    /// Path of the keystore file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Stored keys.
+    pub fn keys(&self) -> &[KeystoreEntry] {
+        &self.file.keys
+    }
+
+    /// The entry labelled `label`.
+    pub fn entry(&self, label: &str) -> Option<&KeystoreEntry> {
+        self.file.keys.iter().find(|k| k.label == label)
+    }
+
+    /// Add `signing_key` under `label`.
+    pub fn add(
+        &mut self,
+        label: &str,
+        signing_key: &SigningKey,
+    ) -> Result<&KeystoreEntry, CommonError> {
+        if label.is_empty() || self.entry(label).is_some() {
+            return Err(CommonError::InvalidInputError(format!(
+                "keystore label {label:?} is empty or already used"
+            )));
+        }
+        let public_key = signing_key.verifying_key().to_bytes().to_vec();
+        let did = did_key_from_verifying_key(&signing_key.verifying_key());
+        let sealed = seal(
+            &self.key,
+            &signing_key.to_bytes(),
+            &entry_aad(label, &did, &public_key),
+        )?;
+        self.file.keys.push(KeystoreEntry {
+            label: label.to_string(),
+            did,
+            public_key,
+            sealed,
+        });
+        self.save()?;
+        Ok(self.file.keys.last().expect("key just added"))
+    }
+
+    /// Generate a new key under `label`.
+    pub fn generate(&mut self, label: &str) -> Result<&KeystoreEntry, CommonError> {
+        let (signing_key, _) = generate_ed25519_keypair();
+        self.add(label, &signing_key)
+    }
+
+    /// Decrypt the key labelled `label`.
+    pub fn signing_key(&self, label: &str) -> Result<SigningKey, CommonError> {
+        let entry = self.entry(label).ok_or_else(|| {
+            CommonError::ResourceNotFound(format!("no key labelled {label} in keystore"))
+        })?;
+        let mut seed = open_sealed(&self.key, &entry.sealed, &entry.aad())?;
+        let bytes: Result<[u8; 32], _> = seed.as_slice().try_into();
+        seed.zeroize();
+        let signing_key = SigningKey::from_bytes(
+            &bytes.map_err(|_| CommonError::CryptoError("stored key has wrong length".into()))?,
+        );
+        if signing_key.verifying_key().as_bytes().as_slice() != entry.public_key.as_slice() {
+            return Err(CommonError::CryptoError(format!(
+                "key {label} does not match its public key"
+            )));
+        }
+        Ok(signing_key)
+    }
+
+    /// Public key of the key labelled `label`.
+    pub fn verifying_key(&self, label: &str) -> Result<VerifyingKey, CommonError> {
+        Ok(self.signing_key(label)?.verifying_key())
+    }
+
+    /// Remove the key labelled `label`.
+    pub fn remove(&mut self, label: &str) -> Result<(), CommonError> {
+        let before = self.file.keys.len();
+        self.file.keys.retain(|k| k.label != label);
+        if self.file.keys.len() == before {
+            return Err(CommonError::ResourceNotFound(format!(
+                "no key labelled {label} in keystore"
+            )));
+        }
+        self.save()
+    }
+
+    /// Re-encrypt every key under `new_passphrase` with a fresh salt.
+    pub fn change_passphrase(&mut self, new_passphrase: &[u8]) -> Result<(), CommonError> {
+        let kdf = self.file.kdf.resalted();
+        let key = kdf.derive(new_passphrase)?;
+        let mut keys = Vec::with_capacity(self.file.keys.len());
+        for entry in &self.file.keys {
+            let mut seed = open_sealed(&self.key, &entry.sealed, &entry.aad())?;
+            let sealed = seal(&key, &seed, &entry.aad());
+            seed.zeroize();
+            keys.push(KeystoreEntry {
+                sealed: sealed?,
+                ..entry.clone()
+            });
+        }
+        self.file.check = seal(&key, CHECK_PLAINTEXT, b"icn-keystore-check")?;
+        self.file.kdf = kdf;
+        self.file.keys = keys;
+        self.key = key;
+        self.save()
+    }
+
+    fn save(&self) -> Result<(), CommonError> {
+        let serialized = serde_json::to_vec_pretty(&self.file).map_err(|e| {
+            CommonError::SerializationError(format!("Failed to serialize keystore: {e}"))
+        })?;
+        let tmp_path = self.path.with_extension("tmp");
+        let mut options = OpenOptions::new();
+        options.create(true).write(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&tmp_path).map_err(|e| {
+            CommonError::IoError(format!("Failed to open keystore {tmp_path:?}: {e}"))
+        })?;
+        file.write_all(&serialized)
+            .and_then(|_| file.sync_all())
+            .map_err(|e| {
+                CommonError::IoError(format!("Failed to write keystore {tmp_path:?}: {e}"))
+            })?;
+        drop(file);
+        rename(&tmp_path, &self.path).map_err(|e| {
+            CommonError::IoError(format!(
+                "Failed to rename keystore {tmp_path:?} -> {:?}: {e}",
+                self.path
+            ))
+        })
+    }
+}
+
+fn read_file(path: &Path) -> Result<KeystoreFile, CommonError> {
+    let contents = std::fs::read(path)
+        .map_err(|e| CommonError::IoError(format!("Failed to read keystore {path:?}: {e}")))?;
+    let file: KeystoreFile = serde_json::from_slice(&contents).map_err(|e| {
+        CommonError::DeserializationError(format!("{path:?} is not a keystore: {e}"))
+    })?;
+    if file.version != KEYSTORE_VERSION || file.cipher != CIPHER {
+        return Err(CommonError::DeserializationError(format!(
+            "unsupported keystore version {} ({})",
+            file.version, file.cipher
+        )));
+    }
+    Ok(file)
+}
+
+fn seal(key: &[u8; 32], plaintext: &[u8], aad: &[u8]) -> Result<Sealed, CommonError> {
+    let mut nonce = vec![0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let ciphertext = XChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .map_err(|_| CommonError::CryptoError("keystore encryption failed".into()))?;
+    Ok(Sealed { nonce, ciphertext })
+}
+
+fn open_sealed(key: &[u8; 32], sealed: &Sealed, aad: &[u8]) -> Result<Vec<u8>, CommonError> {
+    if sealed.nonce.len() != NONCE_LEN {
+        return Err(CommonError::CryptoError("invalid keystore nonce".into()));
+    }
+    XChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(
+            XNonce::from_slice(&sealed.nonce),
+            Payload {
+                msg: &sealed.ciphertext,
+                aad,
+            },
+        )
+        .map_err(|_| CommonError::CryptoError("keystore decryption failed".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cheap_kdf() -> KdfParams {
+        KdfParams::new(256, 1, 1)
+    }
+
+    #[test]
+    fn keys_round_trip_and_survive_passphrase_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys.json");
+        let mut keystore = Keystore::create_with_kdf(&path, b"first", cheap_kdf()).unwrap();
+        let node_did = keystore.generate(DEFAULT_KEY_LABEL).unwrap().did.clone();
+        let (imported, _) = generate_ed25519_keypair();
+        keystore.add("member", &imported).unwrap();
+        assert!(keystore.add("member", &imported).is_err());
+        assert!(Keystore::create_with_kdf(&path, b"first", cheap_kdf()).is_err());
+
+        let listed = Keystore::list(&path).unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].did, node_did);
+        assert!(Keystore::is_keystore(&path).unwrap());
+
+        assert!(Keystore::open(&path, b"wrong").is_err());
+        let mut reopened = Keystore::open(&path, b"first").unwrap();
+        assert_eq!(
+            reopened.signing_key("member").unwrap().to_bytes(),
+            imported.to_bytes()
+        );
+        reopened.change_passphrase(b"second").unwrap();
+        assert!(Keystore::open(&path, b"first").is_err());
+        let mut changed = Keystore::open(&path, b"second").unwrap();
+        assert_eq!(
+            changed.signing_key("member").unwrap().to_bytes(),
+            imported.to_bytes()
+        );
+        assert_eq!(
+            did_key_from_verifying_key(&changed.verifying_key(DEFAULT_KEY_LABEL).unwrap()),
+            node_did
+        );
+        changed.remove("member").unwrap();
+        assert!(changed.signing_key("member").is_err());
+        assert_eq!(Keystore::list(&path).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn tampered_metadata_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys.json");
+        let mut keystore = Keystore::create_with_kdf(&path, b"pass", cheap_kdf()).unwrap();
+        keystore.generate("a").unwrap();
+        keystore.generate("b").unwrap();
+
+        // Swapping labels must not hand out the other key.
+        let mut file: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        file["keys"][0]["label"] = "b".into();
+        file["keys"][1]["label"] = "a".into();
+        std::fs::write(&path, serde_json::to_vec(&file).unwrap()).unwrap();
+        let keystore = Keystore::open(&path, b"pass").unwrap();
+        assert!(keystore.signing_key("a").is_err());
+        assert!(!Keystore::is_keystore(dir.path().join("missing.json")).unwrap());
+    }
+
+    #[test]
+    fn corrupt_keystore_is_not_mistaken_for_a_legacy_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys.json");
+        Keystore::create_with_kdf(&path, b"pass", cheap_kdf()).unwrap();
+        let contents = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &contents[..contents.len() / 2]).unwrap();
+        assert!(Keystore::is_keystore(&path).is_err());
+
+        let legacy = dir.path().join("legacy.key");
+        std::fs::write(&legacy, [0xffu8, 0x00, 0x7b, 0x80]).unwrap();
+        assert!(!Keystore::is_keystore(&legacy).unwrap());
+    }
+}
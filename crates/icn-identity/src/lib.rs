@@ -38,21 +38,42 @@ pub use verifiable_credential::{
 };
 pub mod identity_lifecycle;
 pub use identity_lifecycle::{
-    CredentialIssuanceRequest, CredentialTemplate, DidCreationRequest, IdentityConfig,
-    IdentityLifecycleManager, KeyRotationRequest, ManaLedger, RecoveryRequest,
+    CredentialIssuanceRequest, CredentialTemplate, DidCreationRequest, GuardianApproval,
+    IdentityConfig, IdentityLifecycleManager, KeyRotationRequest, ManaLedger, RecoveryRequest,
 };
 pub use zk::{
-    BulletproofsProver, BulletproofsVerifier, DummyProver, DummyVerifier, Groth16KeyManager,
-    Groth16Prover, Groth16Verifier, ZkError, ZkProver, ZkVerifier,
+    BbsCredential, BbsKeyBinding, BbsPredicate, BbsPresentation, BbsPresentationRequest,
+    BbsPublicKey, BbsSecretKey, BbsVerifier, BulletproofsProver, BulletproofsVerifier, DummyProver,
+    DummyVerifier, Groth16KeyManager, Groth16Prover, Groth16Verifier, ZkError, ZkProver,
+    ZkVerifier,
 };
 pub mod credential;
 pub use credential::{
-    Credential, CredentialIssuer as OriginalCredentialIssuer, DisclosedCredential,
+    Credential, CredentialIssuer as OriginalCredentialIssuer, DisclosedCredential, IssuanceScope,
+};
+pub mod credential_formats;
+pub use credential_formats::{
+    export_credential, import_credential, CredentialFormat, ImportedCredential, SdJwt,
+};
+pub mod credential_issuance;
+pub use credential_issuance::{
+    CredentialSchema, IssuanceOffer, IssuanceRegistry, IssuanceRequest, IssuerPolicy, OfferTerms,
+    SchemaRegistry, CREDENTIAL_SCHEMA_TYPE,
 };
 pub mod credential_store;
 pub use credential_store::InMemoryCredentialStore;
+pub mod personhood;
+pub use personhood::{
+    PersonhoodConfig, PersonhoodEvaluation, PersonhoodRecord, PersonhoodRegistry, PersonhoodStatus,
+    Vouch, VouchStake, DEFAULT_VOUCH_TTL_SECONDS, PERSONHOOD_CLAIM,
+};
 pub mod revocation_registry;
 pub use revocation_registry::{InMemoryRevocationRegistry, RevocationRegistry};
+pub mod status_list;
+pub use status_list::{
+    Bitstring, CredentialStatus, StatusListCache, StatusListCredential, StatusListEntry,
+    StatusListRegistry, StatusListRevocationVerifier, StatusListSource, StatusPurpose,
+};
 pub mod key_management;
 pub use key_management::{
     AdvancedKeyManager, KeyAuditEntry, KeyMetadata, KeyOperation, KeyOperationResult,
@@ -91,6 +112,24 @@ pub use trust_verification::{
 pub mod delegated_credential;
 pub mod metrics;
 pub use delegated_credential::{verify_delegation_chain, DelegatedCredential};
+pub mod did_icn;
+pub use did_icn::{
+    next_key_commitment, IcnDidResolver, KeyEvent, KeyEventKind, KeyEventLog, KeyState,
+    DID_ICN_METHOD,
+};
+pub mod didcomm;
+pub use didcomm::{
+    credential_request, key_agreement_kid, pack_anoncrypt, pack_authcrypt, unpack, wrap_forward,
+    Attachment, CredentialOffer, DidCommMailbox, DidCommMessage, DidCommRoute, InboxMessage,
+    KeyAgreementKey, MailboxOutcome, ProposalInvitation, UnpackedMessage,
+};
+pub mod keystore;
+pub use keystore::{KdfParams, Keystore, KeystoreEntry, DEFAULT_KEY_LABEL};
+pub mod social_recovery;
+pub use social_recovery::{
+    combine_shares, recover_next_key, split_secret, EncryptedShare, RecoveryCase, RecoveryStatus,
+    SecretShare, SocialRecoveryConfig, SocialRecoveryRegistry,
+};
 pub mod enhanced_did_resolver;
 pub use enhanced_did_resolver::{
     DidResolutionConfig, EnhancedDidResolver, EnhancedDidResolverBuilder, MethodStats,
@@ -103,8 +142,8 @@ pub use federation_manager::{
 };
 pub mod federation_integration;
 pub use federation_integration::{
-    FederationEvent, FederationIntegrationConfig, FederationIntegrationEngine,
-    FederationIntegrationStats, ResourceType, SharingTerms,
+    CrossFederationProposalType, FederationEvent, FederationIntegrationConfig,
+    FederationIntegrationEngine, FederationIntegrationStats, ResourceType, SharingTerms,
 };
 pub mod crdt_group_membership;
 pub use crdt_group_membership::{
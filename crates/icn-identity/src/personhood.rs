@@ -0,0 +1,813 @@
+//! Proof of personhood through vouching
+//!
+//! Verified members vouch for newcomers with a signed [`Vouch`] that puts
+//! mana or reputation at stake. A DID becomes a verified person once enough
+//! verified members vouch for it and SybilRank over the vouching graph
+//! places it in the honest region reachable from the seed members.
+//!
+//! Vouches expire after [`PersonhoodConfig::vouch_ttl`], so personhood has to
+//! be renewed by fresh vouches. [`PersonhoodRegistry::evaluate`] re-runs the
+//! detection, expires personhood that lost its backing and flags DIDs the
+//! detection places outside the honest region. When a DID is revoked as a
+//! Sybil, the stakes of everyone who vouched for it are forfeited.
+//!
+//! Verified persons can be issued a personhood credential carrying the
+//! [`PERSONHOOD_CLAIM`], which governance Sybil guards accept in place of a
+//! lookup in this registry.
+
+use crate::credential::Credential;
+use crate::did_document::ProofOfPersonhood;
+use crate::{sign_message, verify_signature, SignatureBytes, SigningKey, VerifyingKey};
+use icn_common::{CommonError, Did};
+use icn_reputation::{sybil_rank, SybilRankConfig, TrustEdge, TrustGraph};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{rename, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Claim a personhood credential carries, with the value `"verified"`.
+pub const PERSONHOOD_CLAIM: &str = "personhood";
+
+/// Default validity of a vouch, and so the re-verification period: 180 days.
+pub const DEFAULT_VOUCH_TTL_SECONDS: u64 = 180 * 24 * 3600;
+
+/// What a voucher puts at risk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind", content = "amount")]
+pub enum VouchStake {
+    /// Mana held in escrow until the vouch expires.
+    Mana(u64),
+    /// Reputation lost if the vouchee turns out to be a Sybil.
+    Reputation(u64),
+}
+
+impl VouchStake {
+    pub fn amount(&self) -> u64 {
+        match self {
+            VouchStake::Mana(amount) | VouchStake::Reputation(amount) => *amount,
+        }
+    }
+}
+
+/// A verified member's signed statement that `vouchee` is a unique person.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Vouch {
+    pub voucher: Did,
+    pub vouchee: Did,
+    pub stake: VouchStake,
+    pub created_at: u64,
+    pub expires_at: u64,
+    /// Voucher signature over [`Vouch::signable_bytes`].
+    pub signature: SignatureBytes,
+}
+
+impl Vouch {
+    /// Unsigned vouch valid for `ttl` seconds from `created_at`.
+    pub fn new(voucher: Did, vouchee: Did, stake: VouchStake, created_at: u64, ttl: u64) -> Self {
+        Self {
+            voucher,
+            vouchee,
+            stake,
+            created_at,
+            expires_at: created_at.saturating_add(ttl),
+            signature: SignatureBytes(Vec::new()),
+        }
+    }
+
+    /// Bytes covered by the voucher signature.
+    pub fn signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        let mut unsigned = self.clone();
+        unsigned.signature = SignatureBytes(Vec::new());
+        serde_json::to_vec(&unsigned).map_err(|e| CommonError::SerializationError(e.to_string()))
+    }
+
+    /// Sign the vouch with the voucher's key.
+    pub fn sign(&mut self, key: &SigningKey) -> Result<(), CommonError> {
+        let bytes = self.signable_bytes()?;
+        self.signature = SignatureBytes::from_ed_signature(sign_message(key, &bytes));
+        Ok(())
+    }
+
+    /// Check the voucher signature.
+    pub fn verify(&self, key: &VerifyingKey) -> Result<(), CommonError> {
+        let signature = self.signature.to_ed_signature()?;
+        if verify_signature(key, &self.signable_bytes()?, &signature) {
+            Ok(())
+        } else {
+            Err(CommonError::PermissionDenied(format!(
+                "vouch for {} is not signed by {}",
+                self.vouchee, self.voucher
+            )))
+        }
+    }
+}
+
+/// Thresholds for vouching and Sybil detection.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PersonhoodConfig {
+    /// Vouches from verified members needed to become verified.
+    pub vouch_threshold: u32,
+    /// Smallest stake a vouch may carry.
+    pub min_stake: u64,
+    /// Seconds a vouch stays valid. Personhood lapses when too few of its
+    /// vouches remain, so this is also the re-verification period.
+    pub vouch_ttl: u64,
+    /// Lowest SybilRank score at which a DID counts as honest.
+    pub min_sybil_rank: f64,
+    /// Unexpired vouches a member may have outstanding.
+    pub max_vouches_per_voucher: usize,
+}
+
+impl Default for PersonhoodConfig {
+    fn default() -> Self {
+        Self {
+            vouch_threshold: 3,
+            min_stake: 10,
+            vouch_ttl: DEFAULT_VOUCH_TTL_SECONDS,
+            min_sybil_rank: 0.5,
+            max_vouches_per_voucher: 10,
+        }
+    }
+}
+
+/// Where a DID stands in the personhood process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PersonhoodStatus {
+    /// Vouched for, but not yet by enough verified members.
+    Pending,
+    Verified,
+    /// Enough vouches, but Sybil detection places it outside the honest region.
+    Flagged,
+    /// Was verified, but too few of its vouches remain.
+    Expired,
+    /// Found to be a Sybil; vouches for it were forfeited.
+    Revoked,
+}
+
+/// Personhood state of one DID.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PersonhoodRecord {
+    pub did: Did,
+    pub status: PersonhoodStatus,
+    /// Verified members whose unexpired vouches back this DID.
+    pub vouchers: Vec<Did>,
+    /// SybilRank score from the last evaluation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sybil_rank: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified_at: Option<u64>,
+    /// When the backing vouches drop below the threshold.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl PersonhoodRecord {
+    fn pending(did: Did) -> Self {
+        Self {
+            did,
+            status: PersonhoodStatus::Pending,
+            vouchers: Vec::new(),
+            sybil_rank: None,
+            verified_at: None,
+            expires_at: None,
+            reason: None,
+        }
+    }
+}
+
+/// Changes made by [`PersonhoodRegistry::evaluate`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PersonhoodEvaluation {
+    /// DIDs that became verified.
+    pub verified: Vec<Did>,
+    /// Verified DIDs that lost their backing vouches.
+    pub expired: Vec<Did>,
+    /// DIDs Sybil detection placed outside the honest region.
+    pub flagged: Vec<Did>,
+    /// Expired vouches whose stakes can be returned.
+    pub released: Vec<Vouch>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersonhoodState {
+    /// Members trusted as verified without vouches, anchoring Sybil detection.
+    seeds: Vec<Did>,
+    /// Records keyed by DID string.
+    records: BTreeMap<String, PersonhoodRecord>,
+    vouches: Vec<Vouch>,
+}
+
+impl PersonhoodState {
+    fn is_verified(&self, did: &Did, now: u64) -> bool {
+        self.seeds.contains(did)
+            || self.records.get(&did.to_string()).is_some_and(|r| {
+                r.status == PersonhoodStatus::Verified && r.expires_at.is_some_and(|e| e > now)
+            })
+    }
+}
+
+/// Seeds, vouches and personhood records, optionally persisted to a JSON
+/// file. Clones share state.
+#[derive(Debug, Clone, Default)]
+pub struct PersonhoodRegistry {
+    path: Option<PathBuf>,
+    config: PersonhoodConfig,
+    state: Arc<Mutex<PersonhoodState>>,
+}
+
+impl PersonhoodRegistry {
+    /// Registry that lives only in memory.
+    pub fn in_memory(config: PersonhoodConfig) -> Self {
+        Self {
+            path: None,
+            config,
+            state: Arc::default(),
+        }
+    }
+
+    /// Registry persisted at `path`, loading any existing state.
+    pub fn open(path: PathBuf, config: PersonhoodConfig) -> Result<Self, CommonError> {
+        let state = if path.exists() {
+            let mut contents = String::new();
+            File::open(&path)
+                .and_then(|mut f| f.read_to_string(&mut contents))
+                .map_err(|e| {
+                    CommonError::IoError(format!("Failed to read personhood state {path:?}: {e}"))
+                })?;
+            if contents.trim().is_empty() {
+                PersonhoodState::default()
+            } else {
+                serde_json::from_str(&contents).map_err(|e| {
+                    CommonError::DeserializationError(format!(
+                        "Failed to parse personhood state {path:?}: {e}"
+                    ))
+                })?
+            }
+        } else {
+            PersonhoodState::default()
+        };
+        Ok(Self {
+            path: Some(path),
+            config,
+            state: Arc::new(Mutex::new(state)),
+        })
+    }
+
+    pub fn config(&self) -> &PersonhoodConfig {
+        &self.config
+    }
+
+    /// Trust `did` as a verified person without vouches.
+    pub fn add_seed(&self, did: Did) -> Result<(), CommonError> {
+        let mut state = self.lock()?;
+        if !state.seeds.contains(&did) {
+            state.seeds.push(did);
+        }
+        self.persist(&state)
+    }
+
+    pub fn seeds(&self) -> Result<Vec<Did>, CommonError> {
+        Ok(self.lock()?.seeds.clone())
+    }
+
+    /// Record a vouch signed by `key`, the voucher's key.
+    ///
+    /// The voucher must be verified and within its vouch allowance, and its
+    /// current `reputation` must cover the reputation staked on all of its
+    /// unexpired vouches including this one. A vouch by the same voucher for
+    /// the same DID replaces the earlier one, which is returned so its stake
+    /// can be released.
+    pub fn vouch(
+        &self,
+        vouch: Vouch,
+        key: &VerifyingKey,
+        reputation: u64,
+        now: u64,
+    ) -> Result<Option<Vouch>, CommonError> {
+        vouch.verify(key)?;
+        if vouch.voucher == vouch.vouchee {
+            return Err(CommonError::PolicyDenied(
+                "members cannot vouch for themselves".into(),
+            ));
+        }
+        if vouch.expires_at <= now || vouch.created_at > now {
+            return Err(CommonError::InvalidInputError(
+                "vouch is not currently valid".into(),
+            ));
+        }
+        if vouch.expires_at - vouch.created_at > self.config.vouch_ttl {
+            return Err(CommonError::InvalidInputError(format!(
+                "vouches may be valid for at most {} seconds",
+                self.config.vouch_ttl
+            )));
+        }
+        if vouch.stake.amount() < self.config.min_stake {
+            return Err(CommonError::PolicyDenied(format!(
+                "vouches must stake at least {}",
+                self.config.min_stake
+            )));
+        }
+        let mut state = self.lock()?;
+        if !state.is_verified(&vouch.voucher, now) {
+            return Err(CommonError::PolicyDenied(format!(
+                "{} is not a verified person",
+                vouch.voucher
+            )));
+        }
+        let vouchee_key = vouch.vouchee.to_string();
+        if state
+            .records
+            .get(&vouchee_key)
+            .is_some_and(|r| r.status == PersonhoodStatus::Revoked)
+        {
+            return Err(CommonError::PolicyDenied(format!(
+                "{} was revoked as a Sybil",
+                vouch.vouchee
+            )));
+        }
+        let replaced = state
+            .vouches
+            .iter()
+            .position(|v| v.voucher == vouch.voucher && v.vouchee == vouch.vouchee)
+            .map(|i| state.vouches.remove(i));
+        let outstanding: Vec<&Vouch> = state
+            .vouches
+            .iter()
+            .filter(|v| v.voucher == vouch.voucher && v.expires_at > now)
+            .collect();
+        let staked = outstanding
+            .iter()
+            .filter_map(|v| match v.stake {
+                VouchStake::Reputation(amount) => Some(amount),
+                VouchStake::Mana(_) => None,
+            })
+            .try_fold(0u64, u64::checked_add);
+        let rejection = if outstanding.len() >= self.config.max_vouches_per_voucher {
+            Some(format!(
+                "{} already has {} outstanding vouches",
+                vouch.voucher,
+                outstanding.len()
+            ))
+        } else if let VouchStake::Reputation(amount) = vouch.stake {
+            match staked.and_then(|staked| staked.checked_add(amount)) {
+                Some(total) if total <= reputation => None,
+                _ => Some(format!(
+                    "reputation {reputation} of {} does not cover {} already staked plus {amount}",
+                    vouch.voucher,
+                    staked.unwrap_or(u64::MAX)
+                )),
+            }
+        } else {
+            None
+        };
+        if let Some(reason) = rejection {
+            if let Some(previous) = replaced {
+                state.vouches.push(previous);
+            }
+            return Err(CommonError::PolicyDenied(reason));
+        }
+        if !state.seeds.contains(&vouch.vouchee) {
+            state
+                .records
+                .entry(vouchee_key)
+                .or_insert_with(|| PersonhoodRecord::pending(vouch.vouchee.clone()));
+        }
+        state.vouches.push(vouch);
+        self.persist(&state)?;
+        Ok(replaced)
+    }
+
+    /// Unexpired and expired vouches for `did`.
+    pub fn vouches_for(&self, did: &Did) -> Result<Vec<Vouch>, CommonError> {
+        Ok(self
+            .lock()?
+            .vouches
+            .iter()
+            .filter(|v| &v.vouchee == did)
+            .cloned()
+            .collect())
+    }
+
+    /// Vouching graph of unexpired vouches, one edge per vouch.
+    pub fn trust_graph(&self, now: u64) -> Result<TrustGraph, CommonError> {
+        let state = self.lock()?;
+        Ok(vouch_graph(&state.vouches, now))
+    }
+
+    /// Re-run Sybil detection and update every record.
+    ///
+    /// A DID is verified while at least `vouch_threshold` unexpired vouches
+    /// from verified members back it and its SybilRank score reaches
+    /// `min_sybil_rank`. Vouchers are judged by their status before this
+    /// evaluation, so a newcomer's own vouches count from the next one.
+    pub fn evaluate(&self, now: u64) -> Result<PersonhoodEvaluation, CommonError> {
+        let mut state = self.lock()?;
+        let mut evaluation = PersonhoodEvaluation::default();
+        let (active, expired): (Vec<Vouch>, Vec<Vouch>) = std::mem::take(&mut state.vouches)
+            .into_iter()
+            .partition(|v| v.expires_at > now);
+        state.vouches = active;
+        evaluation.released = expired;
+
+        let ranks = sybil_rank(
+            &vouch_graph(&state.vouches, now),
+            &state.seeds,
+            &SybilRankConfig::default(),
+        );
+        let verified_before: Vec<String> = state
+            .records
+            .keys()
+            .filter(|key| {
+                state.records[*key].status == PersonhoodStatus::Verified
+                    && state.records[*key].expires_at.is_some_and(|e| e > now)
+            })
+            .cloned()
+            .collect();
+        let is_voucher_verified =
+            |did: &Did| state.seeds.contains(did) || verified_before.contains(&did.to_string());
+        let mut backing: HashMap<String, Vec<&Vouch>> = HashMap::new();
+        for vouch in &state.vouches {
+            if is_voucher_verified(&vouch.voucher) {
+                backing
+                    .entry(vouch.vouchee.to_string())
+                    .or_default()
+                    .push(vouch);
+            }
+        }
+
+        let threshold = self.config.vouch_threshold.max(1) as usize;
+        let mut updates = Vec::new();
+        for (key, record) in &state.records {
+            if record.status == PersonhoodStatus::Revoked || state.seeds.contains(&record.did) {
+                continue;
+            }
+            let mut vouches = backing.get(key).cloned().unwrap_or_default();
+            vouches.sort_by_key(|v| std::cmp::Reverse(v.expires_at));
+            let rank = ranks.get(&record.did).copied().unwrap_or(0.0);
+            let mut record = record.clone();
+            record.vouchers = vouches.iter().map(|v| v.voucher.clone()).collect();
+            record.sybil_rank = Some(rank);
+            let was_verified = record.status == PersonhoodStatus::Verified;
+            if vouches.len() >= threshold && rank >= self.config.min_sybil_rank {
+                if !was_verified {
+                    record.verified_at = Some(now);
+                    evaluation.verified.push(record.did.clone());
+                }
+                record.status = PersonhoodStatus::Verified;
+                record.expires_at = Some(vouches[threshold - 1].expires_at);
+                record.reason = None;
+            } else if vouches.len() >= threshold {
+                if record.status != PersonhoodStatus::Flagged {
+                    evaluation.flagged.push(record.did.clone());
+                }
+                record.status = PersonhoodStatus::Flagged;
+                record.expires_at = None;
+                record.reason = Some(format!(
+                    "SybilRank score {rank:.3} below {}",
+                    self.config.min_sybil_rank
+                ));
+            } else if was_verified || record.status == PersonhoodStatus::Expired {
+                if was_verified {
+                    evaluation.expired.push(record.did.clone());
+                }
+                record.status = PersonhoodStatus::Expired;
+                record.expires_at = None;
+                record.reason = Some("too few unexpired vouches".into());
+            } else {
+                record.status = PersonhoodStatus::Pending;
+                record.expires_at = None;
+            }
+            updates.push((key.clone(), record));
+        }
+        state.records.extend(updates);
+        self.persist(&state)?;
+        Ok(evaluation)
+    }
+
+    /// Revoke `did` as a Sybil, returning the forfeited vouches for it and
+    /// by it.
+    pub fn revoke(&self, did: &Did, reason: &str) -> Result<Vec<Vouch>, CommonError> {
+        let mut state = self.lock()?;
+        state.seeds.retain(|seed| seed != did);
+        let (forfeited, kept): (Vec<Vouch>, Vec<Vouch>) = std::mem::take(&mut state.vouches)
+            .into_iter()
+            .partition(|v| &v.vouchee == did || &v.voucher == did);
+        state.vouches = kept;
+        let record = state
+            .records
+            .entry(did.to_string())
+            .or_insert_with(|| PersonhoodRecord::pending(did.clone()));
+        record.status = PersonhoodStatus::Revoked;
+        record.vouchers.clear();
+        record.expires_at = None;
+        record.reason = Some(reason.to_string());
+        self.persist(&state)?;
+        Ok(forfeited)
+    }
+
+    /// Personhood record of `did`. Seeds have none.
+    pub fn record(&self, did: &Did) -> Result<Option<PersonhoodRecord>, CommonError> {
+        Ok(self.lock()?.records.get(&did.to_string()).cloned())
+    }
+
+    pub fn records(&self) -> Result<Vec<PersonhoodRecord>, CommonError> {
+        Ok(self.lock()?.records.values().cloned().collect())
+    }
+
+    /// Whether `did` is a seed or currently verified.
+    pub fn is_verified(&self, did: &Did, now: u64) -> Result<bool, CommonError> {
+        Ok(self.lock()?.is_verified(did, now))
+    }
+
+    /// Vouching proof for a verified, non-seed DID, in the form stored in
+    /// DID documents.
+    pub fn proof_of_personhood(
+        &self,
+        did: &Did,
+        now: u64,
+    ) -> Result<Option<ProofOfPersonhood>, CommonError> {
+        let state = self.lock()?;
+        if !state.is_verified(did, now) {
+            return Ok(None);
+        }
+        Ok(state
+            .records
+            .get(&did.to_string())
+            .map(|record| ProofOfPersonhood::SocialVouching {
+                vouchers: record.vouchers.clone(),
+                threshold: self.config.vouch_threshold,
+            }))
+    }
+
+    /// Unsigned personhood credential from `issuer` for a verified `did`,
+    /// valid until its personhood lapses.
+    pub fn credential(&self, issuer: &Did, did: &Did, now: u64) -> Result<Credential, CommonError> {
+        let state = self.lock()?;
+        if !state.is_verified(did, now) {
+            return Err(CommonError::PolicyDenied(format!(
+                "{did} is not a verified person"
+            )));
+        }
+        let record = state.records.get(&did.to_string());
+        let mut claims = HashMap::from([(PERSONHOOD_CLAIM.to_string(), "verified".to_string())]);
+        let expires_at = match record {
+            Some(record) if !state.seeds.contains(did) => {
+                claims.insert("vouchers".into(), record.vouchers.len().to_string());
+                if let Some(rank) = record.sybil_rank {
+                    claims.insert("sybil_rank".into(), format!("{rank:.3}"));
+                }
+                record.expires_at
+            }
+            _ => None,
+        };
+        let mut credential = Credential::new(issuer.clone(), did.clone(), claims, None);
+        credential.expires_at =
+            Some(expires_at.unwrap_or_else(|| now.saturating_add(self.config.vouch_ttl)));
+        Ok(credential)
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, PersonhoodState>, CommonError> {
+        self.state
+            .lock()
+            .map_err(|_| CommonError::LockError("personhood registry poisoned".into()))
+    }
+
+    fn persist(&self, state: &PersonhoodState) -> Result<(), CommonError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let serialized = serde_json::to_string(state).map_err(|e| {
+            CommonError::SerializationError(format!("Failed to serialize personhood state: {e}"))
+        })?;
+        let tmp_path = path.with_extension("tmp");
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&tmp_path)
+            .map_err(|e| {
+                CommonError::IoError(format!("Failed to open personhood state {tmp_path:?}: {e}"))
+            })?;
+        file.write_all(serialized.as_bytes())
+            .and_then(|_| file.sync_all())
+            .map_err(|e| {
+                CommonError::IoError(format!(
+                    "Failed to write personhood state {tmp_path:?}: {e}"
+                ))
+            })?;
+        drop(file);
+        rename(&tmp_path, path).map_err(|e| {
+            CommonError::IoError(format!(
+                "Failed to rename personhood state {tmp_path:?} -> {path:?}: {e}"
+            ))
+        })
+    }
+}
+
+fn vouch_graph(vouches: &[Vouch], now: u64) -> TrustGraph {
+    let mut graph = TrustGraph::new();
+    for vouch in vouches.iter().filter(|v| v.expires_at > now) {
+        graph.add_edge(TrustEdge::new(
+            vouch.voucher.clone(),
+            vouch.vouchee.clone(),
+            1.0,
+            vouch.created_at,
+        ));
+    }
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{did_key_from_verifying_key, generate_ed25519_keypair};
+    use std::str::FromStr;
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    fn identity() -> (SigningKey, Did) {
+        let (sk, vk) = generate_ed25519_keypair();
+        let did = Did::from_str(&did_key_from_verifying_key(&vk)).unwrap();
+        (sk, did)
+    }
+
+    fn vouch(
+        registry: &PersonhoodRegistry,
+        voucher: &(SigningKey, Did),
+        vouchee: &Did,
+        now: u64,
+    ) -> Result<Option<Vouch>, CommonError> {
+        let mut vouch = Vouch::new(
+            voucher.1.clone(),
+            vouchee.clone(),
+            VouchStake::Mana(10),
+            now,
+            registry.config().vouch_ttl,
+        );
+        vouch.sign(&voucher.0).unwrap();
+        registry.vouch(vouch, &voucher.0.verifying_key(), 0, now)
+    }
+
+    fn config() -> PersonhoodConfig {
+        PersonhoodConfig {
+            vouch_threshold: 2,
+            vouch_ttl: 30 * DAY,
+            ..PersonhoodConfig::default()
+        }
+    }
+
+    #[test]
+    fn vouched_newcomer_is_verified_until_vouches_expire() {
+        let registry = PersonhoodRegistry::in_memory(config());
+        let seeds: Vec<_> = (0..3).map(|_| identity()).collect();
+        for seed in &seeds {
+            registry.add_seed(seed.1.clone()).unwrap();
+        }
+        // Seeds vouch for each other so the honest region is connected.
+        for a in &seeds {
+            for b in &seeds {
+                if a.1 != b.1 {
+                    vouch(&registry, a, &b.1, 0).unwrap();
+                }
+            }
+        }
+        let newcomer = identity();
+        let stranger = identity();
+        assert!(vouch(&registry, &newcomer, &stranger.1, 0).is_err());
+        assert!(vouch(&registry, &seeds[0], &seeds[0].1, 0).is_err());
+
+        vouch(&registry, &seeds[0], &newcomer.1, 0).unwrap();
+        assert!(registry.evaluate(DAY).unwrap().verified.is_empty());
+        vouch(&registry, &seeds[1], &newcomer.1, DAY).unwrap();
+        let evaluation = registry.evaluate(DAY).unwrap();
+        assert_eq!(evaluation.verified, vec![newcomer.1.clone()]);
+        assert!(registry.is_verified(&newcomer.1, DAY).unwrap());
+        // Personhood lasts until the second-latest vouch expires.
+        let record = registry.record(&newcomer.1).unwrap().unwrap();
+        assert_eq!(record.expires_at, Some(30 * DAY));
+        assert!(matches!(
+            registry.proof_of_personhood(&newcomer.1, DAY).unwrap(),
+            Some(ProofOfPersonhood::SocialVouching { threshold: 2, .. })
+        ));
+
+        // Without renewed vouches personhood lapses and stakes are released.
+        let evaluation = registry.evaluate(31 * DAY).unwrap();
+        assert_eq!(evaluation.expired, vec![newcomer.1.clone()]);
+        assert_eq!(evaluation.released.len(), 8);
+        assert!(!registry.is_verified(&newcomer.1, 31 * DAY).unwrap());
+    }
+
+    #[test]
+    fn sybil_cluster_is_flagged_and_revocation_forfeits_stakes() {
+        let registry = PersonhoodRegistry::in_memory(config());
+        let honest: Vec<_> = (0..16).map(|_| identity()).collect();
+        for member in &honest {
+            registry.add_seed(member.1.clone()).unwrap();
+        }
+        // Each member vouches for the next three around a ring.
+        for (i, a) in honest.iter().enumerate() {
+            for step in 1..=3 {
+                vouch(&registry, a, &honest[(i + step) % honest.len()].1, 0).unwrap();
+            }
+        }
+        // Two corrupted members vouch in a pair of Sybils, which then
+        // vouch in a growing cluster of further identities.
+        let operators = [identity(), identity()];
+        for operator in &operators {
+            vouch(&registry, &honest[4], &operator.1, 0).unwrap();
+            vouch(&registry, &honest[5], &operator.1, 0).unwrap();
+        }
+        registry.evaluate(1).unwrap();
+        assert!(registry.is_verified(&operators[0].1, 1).unwrap());
+        let cluster: Vec<_> = (0..10).map(|_| identity()).collect();
+        for member in &cluster {
+            for operator in &operators {
+                vouch(&registry, operator, &member.1, 2).unwrap();
+            }
+        }
+        // An honest newcomer vouched for in the same round is verified.
+        let newcomer = identity();
+        vouch(&registry, &honest[0], &newcomer.1, 2).unwrap();
+        vouch(&registry, &honest[1], &newcomer.1, 2).unwrap();
+        let evaluation = registry.evaluate(2).unwrap();
+        assert_eq!(evaluation.verified, vec![newcomer.1]);
+        assert!(cluster.iter().all(|m| evaluation.flagged.contains(&m.1)));
+        let record = registry.record(&cluster[0].1).unwrap().unwrap();
+        assert_eq!(record.status, PersonhoodStatus::Flagged);
+        assert!(vouch(&registry, &cluster[0], &cluster[1].1, 2).is_err());
+
+        let forfeited = registry.revoke(&operators[0].1, "sybil operator").unwrap();
+        assert_eq!(
+            forfeited
+                .iter()
+                .filter(|v| v.vouchee == operators[0].1)
+                .count(),
+            2
+        );
+        assert!(vouch(&registry, &honest[0], &operators[0].1, 4).is_err());
+        assert!(registry
+            .credential(&honest[0].1, &operators[0].1, 4)
+            .is_err());
+    }
+
+    #[test]
+    fn reputation_stakes_are_additive() {
+        let registry = PersonhoodRegistry::in_memory(config());
+        let voucher = identity();
+        registry.add_seed(voucher.1.clone()).unwrap();
+        let stake = |vouchee: &Did, amount: u64, reputation: u64| {
+            let mut vouch = Vouch::new(
+                voucher.1.clone(),
+                vouchee.clone(),
+                VouchStake::Reputation(amount),
+                0,
+                DAY,
+            );
+            vouch.sign(&voucher.0).unwrap();
+            registry.vouch(vouch, &voucher.0.verifying_key(), reputation, 0)
+        };
+        let (first, second) = (identity().1, identity().1);
+
+        stake(&first, 10, 15).unwrap();
+        // The same 15 reputation cannot back a second 10-point stake.
+        assert!(matches!(
+            stake(&second, 10, 15),
+            Err(CommonError::PolicyDenied(_))
+        ));
+        // Replacing a vouch frees its own stake.
+        assert!(stake(&first, 15, 15).unwrap().is_some());
+        stake(&second, 10, 25).unwrap();
+    }
+
+    #[test]
+    fn personhood_credential_round_trip_and_persistence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("personhood.json");
+        let issuer = identity();
+        let member = identity();
+        let registry = PersonhoodRegistry::open(path.clone(), config()).unwrap();
+        registry.add_seed(member.1.clone()).unwrap();
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let mut credential = registry.credential(&issuer.1, &member.1, now).unwrap();
+        credential.sign_claims(&issuer.0);
+        assert_eq!(credential.holder, member.1);
+        assert_eq!(credential.claims[PERSONHOOD_CLAIM], "verified");
+        assert_eq!(credential.expires_at, Some(now + config().vouch_ttl));
+        credential
+            .verify_claim(PERSONHOOD_CLAIM, &issuer.0.verifying_key())
+            .unwrap();
+        assert!(registry.credential(&issuer.1, &issuer.1, now).is_err());
+
+        let reopened = PersonhoodRegistry::open(path, config()).unwrap();
+        assert_eq!(reopened.seeds().unwrap(), vec![member.1]);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::StatusListEntry;
 use dashmap::DashSet;
 use icn_common::Cid;
 use std::sync::Arc;
@@ -10,6 +11,29 @@ pub trait RevocationRegistry: Send + Sync {
     fn revoke(&self, cid: &Cid) -> bool;
     /// Check if a credential has been revoked.
     fn is_revoked(&self, cid: &Cid) -> bool;
+    /// Reserve a status list entry for a credential about to be issued.
+    /// Registries without status lists return `None`.
+    fn allocate_status(&self) -> Option<StatusListEntry> {
+        None
+    }
+    /// Record a newly issued credential that carries `status`.
+    fn record_with_status(&self, cid: Cid, status: &StatusListEntry) {
+        let _ = status;
+        self.record(cid);
+    }
+    /// Suspend a credential until reinstated. Returns `true` if the
+    /// credential was known.
+    fn suspend(&self, _cid: &Cid) -> bool {
+        false
+    }
+    /// Lift a suspension. Returns `true` if the credential was known.
+    fn reinstate(&self, _cid: &Cid) -> bool {
+        false
+    }
+    /// Check if a credential is currently suspended.
+    fn is_suspended(&self, _cid: &Cid) -> bool {
+        false
+    }
 }
 
 /// Simple in-memory implementation of [`RevocationRegistry`].
@@ -17,6 +41,7 @@ pub trait RevocationRegistry: Send + Sync {
 pub struct InMemoryRevocationRegistry {
     issued: Arc<DashSet<Cid>>,
     revoked: Arc<DashSet<Cid>>,
+    suspended: Arc<DashSet<Cid>>,
 }
 
 impl InMemoryRevocationRegistry {
@@ -25,6 +50,7 @@ impl InMemoryRevocationRegistry {
         Self {
             issued: Arc::new(DashSet::new()),
             revoked: Arc::new(DashSet::new()),
+            suspended: Arc::new(DashSet::new()),
         }
     }
 }
@@ -46,4 +72,24 @@ impl RevocationRegistry for InMemoryRevocationRegistry {
     fn is_revoked(&self, cid: &Cid) -> bool {
         self.revoked.contains(cid)
     }
+
+    fn suspend(&self, cid: &Cid) -> bool {
+        if self.issued.contains(cid) {
+            self.suspended.insert(cid.clone());
+            true
+        } else {
+            false
+        }
+    }
+
+    fn reinstate(&self, cid: &Cid) -> bool {
+        self.issued.contains(cid) && {
+            self.suspended.remove(cid);
+            true
+        }
+    }
+
+    fn is_suspended(&self, cid: &Cid) -> bool {
+        self.suspended.contains(cid)
+    }
 }
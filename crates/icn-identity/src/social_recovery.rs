@@ -0,0 +1,1127 @@
+//! Social recovery of `did:icn` identifiers
+//!
+//! A `did:icn` identifier commits to its next signing key at every inception
+//! and rotation (see [`crate::did_icn`]). That key can rotate the identifier
+//! even if the current key is lost, so its seed is the recovery secret.
+//!
+//! Recovery works as follows:
+//!
+//! 1. **Setup.** The owner splits the seed with Shamir's scheme into one
+//!    share per guardian, any `threshold` of which rebuild it. Each share is
+//!    sealed to the guardian's DID key. The signed [`SocialRecoveryConfig`] is
+//!    registered with a [`SocialRecoveryRegistry`].
+//! 2. **Opening.** Whoever lost the key opens a [`RecoveryCase`] with a fresh
+//!    session key, signing the request with that key or having a guardian
+//!    sign it. The case runs for the configured delay, during which the
+//!    current key can cancel it, and expires [`RECOVERY_CASE_TTL_SECONDS`]
+//!    after the delay if it is not completed.
+//! 3. **Approval.** Each guardian signs a [`GuardianApproval`] and re-seals
+//!    their share to the session key.
+//! 4. **Rotation.** Once `threshold` guardians have approved and the delay
+//!    has passed, the registry releases the shares. The requester rebuilds
+//!    the seed with [`recover_next_key`] and signs a rotation event, which is
+//!    recorded in the DID's key event log.
+//!
+//! A rotation consumes the committed key, so the owner must set up recovery
+//! again after every rotation. Configurations protecting an older commitment
+//! are rejected.
+
+use crate::did_icn::{next_key_commitment, KeyEvent, KeyEventKind, KeyState};
+use crate::identity_lifecycle::GuardianApproval;
+use crate::{sign_message, verify_signature, SignatureBytes, SigningKey, VerifyingKey};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use icn_common::{CommonError, Did};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{rename, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Default time the current key has to cancel a recovery.
+pub const DEFAULT_RECOVERY_DELAY_SECONDS: u64 = 24 * 3600;
+
+/// Time after the delay to collect approvals and complete a recovery.
+pub const RECOVERY_CASE_TTL_SECONDS: u64 = 7 * 24 * 3600;
+
+/// Fewest guardian approvals a configuration may require.
+pub const MIN_RECOVERY_THRESHOLD: u8 = 2;
+
+/// One Shamir share of a secret; `index` is its non-zero x coordinate.
+#[derive(Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct SecretShare {
+    pub index: u8,
+    pub data: Vec<u8>,
+}
+
+impl std::fmt::Debug for SecretShare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretShare")
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Split `secret` into `shares` shares, any `threshold` of which rebuild it.
+pub fn split_secret(
+    secret: &[u8],
+    threshold: u8,
+    shares: u8,
+) -> Result<Vec<SecretShare>, CommonError> {
+    if threshold == 0 || threshold > shares {
+        return Err(CommonError::InvalidInputError(format!(
+            "threshold {threshold} must be between 1 and {shares}"
+        )));
+    }
+    let mut rng = rand::thread_rng();
+    let mut out: Vec<SecretShare> = (1..=shares)
+        .map(|index| SecretShare {
+            index,
+            data: Vec::with_capacity(secret.len()),
+        })
+        .collect();
+    let mut coefficients = vec![0u8; threshold as usize];
+    for &byte in secret {
+        coefficients[0] = byte;
+        rng.fill_bytes(&mut coefficients[1..]);
+        for share in &mut out {
+            // Horner's rule over GF(2^8).
+            let y = coefficients
+                .iter()
+                .rev()
+                .fold(0u8, |acc, &c| gf_mul(acc, share.index) ^ c);
+            share.data.push(y);
+        }
+    }
+    coefficients.zeroize();
+    Ok(out)
+}
+
+/// Rebuild a secret from at least the threshold number of shares.
+///
+/// Too few shares yield a wrong secret rather than an error, so callers
+/// must check the result.
+pub fn combine_shares(shares: &[SecretShare]) -> Result<Vec<u8>, CommonError> {
+    let first = shares
+        .first()
+        .ok_or_else(|| CommonError::InvalidInputError("no shares to combine".into()))?;
+    let indices: BTreeSet<u8> = shares.iter().map(|s| s.index).collect();
+    if indices.len() != shares.len() || indices.contains(&0) {
+        return Err(CommonError::InvalidInputError(
+            "shares must have distinct non-zero indices".into(),
+        ));
+    }
+    if shares.iter().any(|s| s.data.len() != first.data.len()) {
+        return Err(CommonError::InvalidInputError(
+            "shares have different lengths".into(),
+        ));
+    }
+    // Lagrange basis polynomials evaluated at zero.
+    let weights: Vec<u8> = shares
+        .iter()
+        .map(|j| {
+            shares
+                .iter()
+                .filter(|m| m.index != j.index)
+                .fold(1u8, |acc, m| {
+                    gf_mul(acc, gf_mul(m.index, gf_inv(m.index ^ j.index)))
+                })
+        })
+        .collect();
+    Ok((0..first.data.len())
+        .map(|i| {
+            shares
+                .iter()
+                .zip(&weights)
+                .fold(0u8, |acc, (share, &w)| acc ^ gf_mul(share.data[i], w))
+        })
+        .collect())
+}
+
+/// Multiplication in GF(2^8) with the AES polynomial.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    for _ in 0..8 {
+        product ^= a & (b & 1).wrapping_neg();
+        let carry = (a >> 7).wrapping_neg();
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+    product
+}
+
+fn gf_inv(a: u8) -> u8 {
+    // a^254 = a^-1 for non-zero a.
+    let mut result = 1u8;
+    let mut base = a;
+    let mut exp = 254u8;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+/// A share sealed to one recipient's Ed25519 key via X25519.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedShare {
+    /// Guardian the share belongs to.
+    pub guardian: Did,
+    pub index: u8,
+    /// Sender's ephemeral X25519 public key.
+    pub ephemeral_key: Vec<u8>,
+    pub nonce: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+}
+
+impl EncryptedShare {
+    /// Seal `share` of `did`'s recovery secret to `recipient`.
+    pub fn seal(
+        did: &Did,
+        guardian: &Did,
+        share: &SecretShare,
+        recipient: &VerifyingKey,
+    ) -> Result<Self, CommonError> {
+        let mut rng = rand::thread_rng();
+        let mut ephemeral = [0u8; 32];
+        rng.fill_bytes(&mut ephemeral);
+        let ephemeral_key = MontgomeryPoint::mul_base_clamped(ephemeral);
+        let recipient_point = recipient.to_montgomery();
+        let shared = recipient_point.mul_clamped(ephemeral);
+        ephemeral.zeroize();
+        let cipher = share_cipher(&shared, &ephemeral_key, &recipient_point);
+        let mut nonce = [0u8; 12];
+        rng.fill_bytes(&mut nonce);
+        let aad = share_aad(did, guardian, share.index);
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &share.data,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| CommonError::CryptoError("failed to seal recovery share".into()))?;
+        Ok(Self {
+            guardian: guardian.clone(),
+            index: share.index,
+            ephemeral_key: ephemeral_key.to_bytes().to_vec(),
+            nonce: nonce.to_vec(),
+            ciphertext,
+        })
+    }
+
+    /// Open a share sealed to `recipient`.
+    pub fn open(&self, did: &Did, recipient: &SigningKey) -> Result<SecretShare, CommonError> {
+        let ephemeral_key = MontgomeryPoint(
+            self.ephemeral_key
+                .as_slice()
+                .try_into()
+                .map_err(|_| CommonError::CryptoError("invalid ephemeral key".into()))?,
+        );
+        if self.nonce.len() != 12 {
+            return Err(CommonError::CryptoError("invalid share nonce".into()));
+        }
+        let shared = ephemeral_key.mul_clamped(recipient.to_scalar_bytes());
+        let cipher = share_cipher(
+            &shared,
+            &ephemeral_key,
+            &recipient.verifying_key().to_montgomery(),
+        );
+        let aad = share_aad(did, &self.guardian, self.index);
+        let data = cipher
+            .decrypt(
+                Nonce::from_slice(&self.nonce),
+                Payload {
+                    msg: &self.ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| {
+                CommonError::CryptoError(format!(
+                    "recovery share {} of {} cannot be opened with this key",
+                    self.index, did
+                ))
+            })?;
+        Ok(SecretShare {
+            index: self.index,
+            data,
+        })
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(self.guardian.to_string());
+        hasher.update([self.index]);
+        hasher.update(&self.ephemeral_key);
+        hasher.update(&self.nonce);
+        hasher.update(&self.ciphertext);
+        hasher.finalize().to_vec()
+    }
+}
+
+fn share_cipher(
+    shared: &MontgomeryPoint,
+    ephemeral_key: &MontgomeryPoint,
+    recipient: &MontgomeryPoint,
+) -> ChaCha20Poly1305 {
+    let mut hasher = Sha256::new();
+    hasher.update(b"icn-recovery-share");
+    hasher.update(shared.as_bytes());
+    hasher.update(ephemeral_key.as_bytes());
+    hasher.update(recipient.as_bytes());
+    let mut key = hasher.finalize();
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+    key.zeroize();
+    cipher
+}
+
+fn share_aad(did: &Did, guardian: &Did, index: u8) -> Vec<u8> {
+    let mut aad = did.to_string().into_bytes();
+    aad.push(0);
+    aad.extend_from_slice(guardian.to_string().as_bytes());
+    aad.push(index);
+    aad
+}
+
+/// Guardians, threshold and sealed shares protecting a DID's next key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SocialRecoveryConfig {
+    pub did: Did,
+    /// Next-key commitment the shares rebuild the key for.
+    pub next_key_commitment: String,
+    pub threshold: u8,
+    /// One sealed share per guardian.
+    pub shares: Vec<EncryptedShare>,
+    /// Seconds the current key has to cancel a recovery.
+    pub delay_seconds: u64,
+    pub created_at: u64,
+    /// Signature by the DID's current key.
+    pub signature: SignatureBytes,
+}
+
+impl SocialRecoveryConfig {
+    /// Split `next_key` among `guardians` and sign the configuration with
+    /// the current key.
+    pub fn create(
+        state: &KeyState,
+        current_key: &SigningKey,
+        next_key: &SigningKey,
+        guardians: &[(Did, VerifyingKey)],
+        threshold: u8,
+        delay_seconds: u64,
+        created_at: u64,
+    ) -> Result<Self, CommonError> {
+        let commitment = next_key_commitment(&next_key.verifying_key());
+        if state.next_key_commitment.as_deref() != Some(commitment.as_str()) {
+            return Err(CommonError::IdentityError(format!(
+                "next key is not the one {} committed to",
+                state.did
+            )));
+        }
+        let count = u8::try_from(guardians.len())
+            .map_err(|_| CommonError::InvalidInputError("at most 255 guardians".into()))?;
+        let mut seed = next_key.to_bytes();
+        let shares = split_secret(&seed, threshold, count);
+        seed.zeroize();
+        let shares = shares?
+            .iter()
+            .zip(guardians)
+            .map(|(share, (guardian, key))| EncryptedShare::seal(&state.did, guardian, share, key))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut config = Self {
+            did: state.did.clone(),
+            next_key_commitment: commitment,
+            threshold,
+            shares,
+            delay_seconds,
+            created_at,
+            signature: SignatureBytes(Vec::new()),
+        };
+        config.signature =
+            SignatureBytes::from_ed_signature(sign_message(current_key, &config.signable_bytes()?));
+        Ok(config)
+    }
+
+    /// Bytes covered by the owner's signature.
+    pub fn signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        let mut unsigned = self.clone();
+        unsigned.signature = SignatureBytes(Vec::new());
+        let mut bytes = b"icn-recovery-config".to_vec();
+        bytes.extend_from_slice(
+            &serde_json::to_vec(&unsigned)
+                .map_err(|e| CommonError::SerializationError(e.to_string()))?,
+        );
+        Ok(bytes)
+    }
+
+    /// The sealed share held by `guardian`.
+    pub fn share_for(&self, guardian: &Did) -> Option<&EncryptedShare> {
+        self.shares.iter().find(|s| &s.guardian == guardian)
+    }
+
+    /// Guardian DIDs.
+    pub fn guardians(&self) -> impl Iterator<Item = &Did> {
+        self.shares.iter().map(|s| &s.guardian)
+    }
+
+    /// Check the configuration against the DID's current key state.
+    pub fn validate(&self, state: &KeyState) -> Result<(), CommonError> {
+        if self.did != state.did || state.deactivated {
+            return Err(CommonError::IdentityError(format!(
+                "recovery configuration does not match {}",
+                state.did
+            )));
+        }
+        if state.next_key_commitment.as_deref() != Some(self.next_key_commitment.as_str()) {
+            return Err(CommonError::IdentityError(format!(
+                "recovery configuration protects a key {} no longer commits to",
+                self.did
+            )));
+        }
+        if self.threshold < MIN_RECOVERY_THRESHOLD || self.threshold as usize > self.shares.len() {
+            return Err(CommonError::PolicyDenied(format!(
+                "recovery threshold must be between {MIN_RECOVERY_THRESHOLD} and the number of guardians"
+            )));
+        }
+        let guardians: BTreeSet<String> = self.guardians().map(Did::to_string).collect();
+        let indices: BTreeSet<_> = self.shares.iter().map(|s| s.index).collect();
+        if guardians.len() != self.shares.len()
+            || indices.len() != self.shares.len()
+            || guardians.contains(&self.did.to_string())
+        {
+            return Err(CommonError::InvalidInputError(
+                "guardians must be distinct and must not include the DID itself".into(),
+            ));
+        }
+        verify_ed(
+            &state.verifying_key()?,
+            &self.signable_bytes()?,
+            &self.signature,
+            "recovery configuration is not signed by the current key",
+        )
+    }
+}
+
+/// Where a recovery case stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecoveryStatus {
+    /// Collecting approvals or waiting out the delay.
+    Pending,
+    /// Cancelled by the current key.
+    Cancelled,
+    /// Ended in a recorded key rotation.
+    Completed,
+    /// Not completed before it expired.
+    Expired,
+}
+
+/// An attempt to recover a DID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryCase {
+    pub id: String,
+    pub did: Did,
+    /// Session key guardians re-seal their shares to.
+    pub recovery_key: Vec<u8>,
+    pub opened_at: u64,
+    /// When shares are released if enough guardians approved.
+    pub executable_at: u64,
+    /// When the case lapses if it was not completed.
+    pub expires_at: u64,
+    pub threshold: u8,
+    pub approvals: Vec<GuardianApproval>,
+    /// Shares re-sealed to the session key; withheld until executable.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub released_shares: Vec<EncryptedShare>,
+    pub status: RecoveryStatus,
+}
+
+impl RecoveryCase {
+    /// Whether enough guardians approved and the delay has passed.
+    pub fn is_executable(&self, now: u64) -> bool {
+        self.status == RecoveryStatus::Pending
+            && self.approvals.len() >= self.threshold as usize
+            && now >= self.executable_at
+            && !self.is_expired(now)
+    }
+
+    /// Whether the case lapsed before it was completed.
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.status == RecoveryStatus::Expired
+            || (self.status == RecoveryStatus::Pending && now >= self.expires_at)
+    }
+
+    /// Bytes the recovery key or a guardian signs to open a case.
+    pub fn opening_bytes(did: &Did, recovery_key: &VerifyingKey) -> Vec<u8> {
+        let mut bytes = b"icn-recovery-open".to_vec();
+        bytes.extend_from_slice(did.to_string().as_bytes());
+        bytes.extend_from_slice(recovery_key.as_bytes());
+        bytes
+    }
+
+    /// The case without released shares, as shown before completion.
+    pub fn redacted(&self) -> Self {
+        Self {
+            released_shares: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Bytes a guardian signs to approve releasing `share`.
+    pub fn approval_bytes(&self, approved_at: u64, share: &EncryptedShare) -> Vec<u8> {
+        let mut bytes = b"icn-recovery-approval".to_vec();
+        bytes.extend_from_slice(self.id.as_bytes());
+        bytes.extend_from_slice(self.did.to_string().as_bytes());
+        bytes.extend_from_slice(&self.recovery_key);
+        bytes.extend_from_slice(&approved_at.to_le_bytes());
+        bytes.extend_from_slice(&share.digest());
+        bytes
+    }
+
+    /// Bytes the current key signs to cancel the case.
+    pub fn cancellation_bytes(&self) -> Vec<u8> {
+        let mut bytes = b"icn-recovery-cancel".to_vec();
+        bytes.extend_from_slice(self.id.as_bytes());
+        bytes
+    }
+
+    /// Approve the case as `guardian`, releasing their share to the session key.
+    pub fn approve(
+        &self,
+        config: &SocialRecoveryConfig,
+        guardian: &Did,
+        guardian_key: &SigningKey,
+        approved_at: u64,
+    ) -> Result<(GuardianApproval, EncryptedShare), CommonError> {
+        let sealed = config.share_for(guardian).ok_or_else(|| {
+            CommonError::PermissionDenied(format!("{guardian} is not a guardian of {}", self.did))
+        })?;
+        let share = sealed.open(&self.did, guardian_key)?;
+        let released = EncryptedShare::seal(
+            &self.did,
+            guardian,
+            &share,
+            &session_key(&self.recovery_key)?,
+        )?;
+        let signature = sign_message(guardian_key, &self.approval_bytes(approved_at, &released));
+        Ok((
+            GuardianApproval {
+                guardian: guardian.clone(),
+                approved_at,
+                signature: multibase::encode(multibase::Base::Base58Btc, signature.to_bytes()),
+                challenge_response: None,
+            },
+            released,
+        ))
+    }
+}
+
+/// Rebuild the committed next key from released shares.
+pub fn recover_next_key(
+    case: &RecoveryCase,
+    commitment: &str,
+    recovery_key: &SigningKey,
+) -> Result<SigningKey, CommonError> {
+    let shares = case
+        .released_shares
+        .iter()
+        .map(|s| s.open(&case.did, recovery_key))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut seed = combine_shares(&shares)?;
+    let bytes: [u8; 32] = seed
+        .as_slice()
+        .try_into()
+        .map_err(|_| CommonError::CryptoError("recovered seed has the wrong length".into()))?;
+    seed.zeroize();
+    let key = SigningKey::from_bytes(&bytes);
+    if next_key_commitment(&key.verifying_key()) != commitment {
+        return Err(CommonError::CryptoError(
+            "released shares do not rebuild the committed key".into(),
+        ));
+    }
+    Ok(key)
+}
+
+fn session_key(bytes: &[u8]) -> Result<VerifyingKey, CommonError> {
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| CommonError::InvalidInputError("recovery key must be 32 bytes".into()))?;
+    VerifyingKey::from_bytes(&bytes)
+        .map_err(|e| CommonError::InvalidInputError(format!("invalid recovery key: {e}")))
+}
+
+fn verify_ed(
+    key: &VerifyingKey,
+    message: &[u8],
+    signature: &SignatureBytes,
+    error: &str,
+) -> Result<(), CommonError> {
+    if verify_signature(key, message, &signature.to_ed_signature()?) {
+        Ok(())
+    } else {
+        Err(CommonError::PermissionDenied(error.to_string()))
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RecoveryState {
+    configs: BTreeMap<String, SocialRecoveryConfig>,
+    cases: BTreeMap<String, RecoveryCase>,
+}
+
+/// Recovery configurations and cases, optionally persisted to a JSON file.
+///
+/// The registry enforces the delay by withholding released shares until a
+/// case is executable. Clones share state.
+#[derive(Debug, Clone, Default)]
+pub struct SocialRecoveryRegistry {
+    path: Option<PathBuf>,
+    state: Arc<Mutex<RecoveryState>>,
+}
+
+impl SocialRecoveryRegistry {
+    /// Registry that lives only in memory.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Registry persisted at `path`, loading any existing state.
+    pub fn open(path: PathBuf) -> Result<Self, CommonError> {
+        let state = if path.exists() {
+            let mut contents = String::new();
+            File::open(&path)
+                .and_then(|mut f| f.read_to_string(&mut contents))
+                .map_err(|e| {
+                    CommonError::IoError(format!("Failed to read recovery state {path:?}: {e}"))
+                })?;
+            if contents.trim().is_empty() {
+                RecoveryState::default()
+            } else {
+                serde_json::from_str(&contents).map_err(|e| {
+                    CommonError::DeserializationError(format!(
+                        "Failed to parse recovery state {path:?}: {e}"
+                    ))
+                })?
+            }
+        } else {
+            RecoveryState::default()
+        };
+        Ok(Self {
+            path: Some(path),
+            state: Arc::new(Mutex::new(state)),
+        })
+    }
+
+    /// Register or replace the recovery configuration of a DID.
+    pub fn configure(
+        &self,
+        config: SocialRecoveryConfig,
+        state: &KeyState,
+        now: u64,
+    ) -> Result<(), CommonError> {
+        config.validate(state)?;
+        let mut registry = self.lock()?;
+        if registry.cases.values().any(|c| {
+            c.did == config.did && c.status == RecoveryStatus::Pending && !c.is_expired(now)
+        }) {
+            return Err(CommonError::PolicyDenied(format!(
+                "{} has a recovery in progress",
+                config.did
+            )));
+        }
+        registry.configs.insert(config.did.to_string(), config);
+        self.persist(&registry)
+    }
+
+    /// Recovery configuration of `did`.
+    pub fn config(&self, did: &Did) -> Option<SocialRecoveryConfig> {
+        self.lock().ok()?.configs.get(&did.to_string()).cloned()
+    }
+
+    /// Open a recovery case for `did` whose shares will be released to
+    /// `recovery_key`.
+    ///
+    /// `signature` covers [`RecoveryCase::opening_bytes`] and is made by
+    /// `guardian` when one is given, otherwise by the current key in `state`.
+    /// A request signed only by the new recovery key is not enough, or anyone
+    /// could keep the single pending slot occupied. Cases of `did` past their
+    /// expiry are marked expired first.
+    pub fn open_case(
+        &self,
+        did: &Did,
+        recovery_key: &VerifyingKey,
+        guardian: Option<(&Did, &VerifyingKey)>,
+        signature: &SignatureBytes,
+        state: &KeyState,
+        now: u64,
+    ) -> Result<RecoveryCase, CommonError> {
+        let mut registry = self.lock()?;
+        let config = registry
+            .configs
+            .get(&did.to_string())
+            .ok_or_else(|| CommonError::ResourceNotFound(format!("{did} has no guardians")))?;
+        config.validate(state)?;
+        let (signer, error) = match guardian {
+            Some((guardian, key)) => {
+                if config.share_for(guardian).is_none() {
+                    return Err(CommonError::PermissionDenied(format!(
+                        "{guardian} is not a guardian of {did}"
+                    )));
+                }
+                (*key, "recovery request is not signed by the guardian")
+            }
+            None => (
+                state.verifying_key()?,
+                "recovery request is not signed by the current key",
+            ),
+        };
+        verify_ed(
+            &signer,
+            &RecoveryCase::opening_bytes(did, recovery_key),
+            signature,
+            error,
+        )?;
+        let delay_seconds = config.delay_seconds;
+        let threshold = config.threshold;
+        for case in registry.cases.values_mut() {
+            if &case.did == did && case.status == RecoveryStatus::Pending && case.is_expired(now) {
+                case.status = RecoveryStatus::Expired;
+                case.released_shares.clear();
+            }
+        }
+        if registry.cases.values().any(|c| {
+            &c.did == did
+                && (c.status == RecoveryStatus::Pending
+                    || c.recovery_key == recovery_key.as_bytes())
+        }) {
+            return Err(CommonError::PolicyDenied(format!(
+                "{did} already has a recovery in progress or used this recovery key"
+            )));
+        }
+        let mut hasher = Sha256::new();
+        hasher.update(did.to_string());
+        hasher.update(recovery_key.as_bytes());
+        hasher.update(now.to_le_bytes());
+        let id: String = hasher.finalize()[..16]
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        let executable_at = now.saturating_add(delay_seconds);
+        let case = RecoveryCase {
+            id: id.clone(),
+            did: did.clone(),
+            recovery_key: recovery_key.as_bytes().to_vec(),
+            opened_at: now,
+            executable_at,
+            expires_at: executable_at.saturating_add(RECOVERY_CASE_TTL_SECONDS),
+            threshold,
+            approvals: Vec::new(),
+            released_shares: Vec::new(),
+            status: RecoveryStatus::Pending,
+        };
+        registry.cases.insert(id, case.clone());
+        self.persist(&registry)?;
+        Ok(case)
+    }
+
+    /// A case without its released shares.
+    pub fn case(&self, id: &str) -> Option<RecoveryCase> {
+        self.lock().ok()?.cases.get(id).map(RecoveryCase::redacted)
+    }
+
+    /// Record a guardian's signed approval and released share.
+    pub fn approve(
+        &self,
+        id: &str,
+        approval: GuardianApproval,
+        share: EncryptedShare,
+        guardian_key: &VerifyingKey,
+        now: u64,
+    ) -> Result<RecoveryCase, CommonError> {
+        let mut registry = self.lock()?;
+        let case = pending_case(&registry, id, now)?;
+        let config = registry.configs.get(&case.did.to_string()).ok_or_else(|| {
+            CommonError::ResourceNotFound(format!("{} has no guardians", case.did))
+        })?;
+        let expected = config.share_for(&approval.guardian).ok_or_else(|| {
+            CommonError::PermissionDenied(format!(
+                "{} is not a guardian of {}",
+                approval.guardian, case.did
+            ))
+        })?;
+        if share.guardian != approval.guardian || share.index != expected.index {
+            return Err(CommonError::InvalidInputError(
+                "released share does not belong to the approving guardian".into(),
+            ));
+        }
+        if case
+            .approvals
+            .iter()
+            .any(|a| a.guardian == approval.guardian)
+        {
+            return Err(CommonError::PolicyDenied(format!(
+                "{} already approved",
+                approval.guardian
+            )));
+        }
+        let (_, signature) = multibase::decode(&approval.signature)
+            .map_err(|e| CommonError::InvalidInputError(format!("invalid signature: {e}")))?;
+        verify_ed(
+            guardian_key,
+            &case.approval_bytes(approval.approved_at, &share),
+            &SignatureBytes(signature),
+            "guardian approval has an invalid signature",
+        )?;
+        let case = registry.cases.get_mut(id).expect("pending case exists");
+        case.approvals.push(approval);
+        case.released_shares.push(share);
+        let redacted = case.redacted();
+        self.persist(&registry)?;
+        Ok(redacted)
+    }
+
+    /// Cancel a pending case with a signature by the DID's current key.
+    pub fn cancel(
+        &self,
+        id: &str,
+        signature: &SignatureBytes,
+        state: &KeyState,
+        now: u64,
+    ) -> Result<RecoveryCase, CommonError> {
+        let mut registry = self.lock()?;
+        let case = pending_case(&registry, id, now)?;
+        if case.did != state.did {
+            return Err(CommonError::InvalidInputError(format!(
+                "case {id} does not concern {}",
+                state.did
+            )));
+        }
+        verify_ed(
+            &state.verifying_key()?,
+            &case.cancellation_bytes(),
+            signature,
+            "cancellation is not signed by the current key",
+        )?;
+        let case = registry.cases.get_mut(id).expect("pending case exists");
+        case.status = RecoveryStatus::Cancelled;
+        case.released_shares.clear();
+        let cancelled = case.clone();
+        self.persist(&registry)?;
+        Ok(cancelled)
+    }
+
+    /// The case with its released shares, once it is executable.
+    pub fn released(&self, id: &str, now: u64) -> Result<RecoveryCase, CommonError> {
+        let registry = self.lock()?;
+        let case = pending_case(&registry, id, now)?;
+        if case.approvals.len() < case.threshold as usize {
+            return Err(CommonError::PolicyDenied(format!(
+                "{} of {} guardian approvals",
+                case.approvals.len(),
+                case.threshold
+            )));
+        }
+        if now < case.executable_at {
+            return Err(CommonError::PolicyDenied(format!(
+                "recovery can be completed after {}",
+                case.executable_at
+            )));
+        }
+        Ok(case.clone())
+    }
+
+    /// Check that `event` completes the case, before it is recorded.
+    pub fn check_completion(
+        &self,
+        id: &str,
+        event: &KeyEvent,
+        now: u64,
+    ) -> Result<(), CommonError> {
+        let case = self.released(id, now)?;
+        if event.did != case.did || !matches!(event.kind, KeyEventKind::Rotation { .. }) {
+            return Err(CommonError::InvalidInputError(format!(
+                "recovery of {} must end in a rotation of that DID",
+                case.did
+            )));
+        }
+        Ok(())
+    }
+
+    /// Mark the case completed after its rotation was recorded. The old
+    /// configuration is dropped because it protects a consumed key.
+    pub fn complete(&self, id: &str, now: u64) -> Result<RecoveryCase, CommonError> {
+        let mut registry = self.lock()?;
+        pending_case(&registry, id, now)?;
+        let case = registry.cases.get_mut(id).expect("pending case exists");
+        case.status = RecoveryStatus::Completed;
+        case.released_shares.clear();
+        let completed = case.clone();
+        registry.configs.remove(&completed.did.to_string());
+        self.persist(&registry)?;
+        Ok(completed)
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, RecoveryState>, CommonError> {
+        self.state
+            .lock()
+            .map_err(|_| CommonError::LockError("recovery registry poisoned".into()))
+    }
+
+    fn persist(&self, state: &RecoveryState) -> Result<(), CommonError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let serialized = serde_json::to_string(state).map_err(|e| {
+            CommonError::SerializationError(format!("Failed to serialize recovery state: {e}"))
+        })?;
+        let tmp_path = path.with_extension("tmp");
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&tmp_path)
+            .map_err(|e| {
+                CommonError::IoError(format!("Failed to open recovery state {tmp_path:?}: {e}"))
+            })?;
+        file.write_all(serialized.as_bytes())
+            .and_then(|_| file.sync_all())
+            .map_err(|e| {
+                CommonError::IoError(format!("Failed to write recovery state {tmp_path:?}: {e}"))
+            })?;
+        drop(file);
+        rename(&tmp_path, path).map_err(|e| {
+            CommonError::IoError(format!(
+                "Failed to rename recovery state {tmp_path:?} -> {path:?}: {e}"
+            ))
+        })
+    }
+}
+
+fn pending_case<'a>(
+    state: &'a RecoveryState,
+    id: &str,
+    now: u64,
+) -> Result<&'a RecoveryCase, CommonError> {
+    let case = state
+        .cases
+        .get(id)
+        .ok_or_else(|| CommonError::ResourceNotFound(format!("no recovery case {id}")))?;
+    if case.is_expired(now) {
+        return Err(CommonError::PolicyDenied(format!(
+            "recovery case {id} expired at {}",
+            case.expires_at
+        )));
+    }
+    if case.status != RecoveryStatus::Pending {
+        return Err(CommonError::PolicyDenied(format!(
+            "recovery case {id} is {:?}",
+            case.status
+        )));
+    }
+    Ok(case)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::did_icn::IcnDidResolver;
+    use crate::generate_ed25519_keypair;
+
+    #[test]
+    fn shamir_rebuilds_from_any_threshold_subset() {
+        let secret: Vec<u8> = (0..32).collect();
+        let shares = split_secret(&secret, 3, 5).unwrap();
+        assert_eq!(combine_shares(&shares[..3]).unwrap(), secret);
+        assert_eq!(
+            combine_shares(&[shares[4].clone(), shares[0].clone(), shares[2].clone()]).unwrap(),
+            secret
+        );
+        assert_ne!(combine_shares(&shares[..2]).unwrap(), secret);
+        assert!(split_secret(&secret, 6, 5).is_err());
+        assert!(combine_shares(&[shares[0].clone(), shares[0].clone()]).is_err());
+    }
+
+    #[test]
+    fn guardians_recover_after_delay_and_owner_can_cancel() {
+        let did_icn = IcnDidResolver::new();
+        let (current, _) = generate_ed25519_keypair();
+        let (next, next_pk) = generate_ed25519_keypair();
+        let state = did_icn
+            .apply(KeyEvent::inception(&current, Some(&next_pk), 0))
+            .unwrap();
+        let guardians: Vec<(Did, SigningKey)> = (0..3)
+            .map(|i| {
+                let (sk, _) = generate_ed25519_keypair();
+                (Did::new("key", &format!("guardian{i}")), sk)
+            })
+            .collect();
+        let public: Vec<_> = guardians
+            .iter()
+            .map(|(did, sk)| (did.clone(), sk.verifying_key()))
+            .collect();
+        let config =
+            SocialRecoveryConfig::create(&state, &current, &next, &public, 2, 100, 0).unwrap();
+        let registry = SocialRecoveryRegistry::in_memory();
+        registry.configure(config.clone(), &state, 0).unwrap();
+
+        let approve = |case: &RecoveryCase, i: usize| {
+            let (did, sk) = &guardians[i];
+            let (approval, share) = case.approve(&config, did, sk, 10).unwrap();
+            registry.approve(&case.id, approval, share, &sk.verifying_key(), 10)
+        };
+        let opening = |signer: &SigningKey, key: &VerifyingKey| {
+            SignatureBytes::from_ed_signature(sign_message(
+                signer,
+                &RecoveryCase::opening_bytes(&state.did, key),
+            ))
+        };
+
+        // The owner cancels a hostile attempt by a guardian with the current key.
+        let (attacker_sk, attacker) = generate_ed25519_keypair();
+        let (rogue, rogue_sk) = &guardians[0];
+        let hostile = registry
+            .open_case(
+                &state.did,
+                &attacker,
+                Some((rogue, &rogue_sk.verifying_key())),
+                &opening(rogue_sk, &attacker),
+                &state,
+                0,
+            )
+            .unwrap();
+        approve(&hostile, 0).unwrap();
+        let cancel = SignatureBytes::from_ed_signature(sign_message(
+            &current,
+            &hostile.cancellation_bytes(),
+        ));
+        registry.cancel(&hostile.id, &cancel, &state, 1).unwrap();
+        assert!(registry.released(&hostile.id, 1_000).is_err());
+
+        // The request must be signed by a guardian or the current key; the
+        // session key alone does not do.
+        let (session, session_pk) = generate_ed25519_keypair();
+        assert!(matches!(
+            registry.open_case(
+                &state.did,
+                &session_pk,
+                None,
+                &opening(&session, &session_pk),
+                &state,
+                0
+            ),
+            Err(CommonError::PermissionDenied(_))
+        ));
+        assert!(registry
+            .open_case(
+                &state.did,
+                &session_pk,
+                Some((rogue, &rogue_sk.verifying_key())),
+                &opening(&attacker_sk, &session_pk),
+                &state,
+                0
+            )
+            .is_err());
+        let (guardian, guardian_sk) = &guardians[1];
+        let case = registry
+            .open_case(
+                &state.did,
+                &session_pk,
+                Some((guardian, &guardian_sk.verifying_key())),
+                &opening(guardian_sk, &session_pk),
+                &state,
+                0,
+            )
+            .unwrap();
+        approve(&case, 0).unwrap();
+        assert!(approve(&case, 0).is_err());
+        approve(&case, 2).unwrap();
+        assert!(registry.released(&case.id, 50).is_err());
+        assert!(registry.case(&case.id).unwrap().released_shares.is_empty());
+
+        let released = registry.released(&case.id, 100).unwrap();
+        let recovered = recover_next_key(&released, &config.next_key_commitment, &session).unwrap();
+        assert_eq!(recovered.to_bytes(), next.to_bytes());
+        let (stranger, _) = generate_ed25519_keypair();
+        assert!(recover_next_key(&released, &config.next_key_commitment, &stranger).is_err());
+
+        let (_, new_next) = generate_ed25519_keypair();
+        let rotation = KeyEvent::rotation(&state, &recovered, Some(&new_next), 100);
+        registry.check_completion(&case.id, &rotation, 100).unwrap();
+        let rotated = did_icn.apply(rotation).unwrap();
+        assert_eq!(rotated.key, next_pk.as_bytes().to_vec());
+        registry.complete(&case.id, 100).unwrap();
+        assert!(registry.config(&state.did).is_none());
+        assert!(config.validate(&rotated).is_err());
+    }
+    #[test]
+    fn cases_expire_and_cannot_be_replayed() {
+        let did_icn = IcnDidResolver::new();
+        let (current, _) = generate_ed25519_keypair();
+        let (next, next_pk) = generate_ed25519_keypair();
+        let state = did_icn
+            .apply(KeyEvent::inception(&current, Some(&next_pk), 0))
+            .unwrap();
+        let guardians: Vec<(Did, SigningKey)> = (0..2)
+            .map(|i| {
+                let (sk, _) = generate_ed25519_keypair();
+                (Did::new("key", &format!("guardian{i}")), sk)
+            })
+            .collect();
+        let public: Vec<_> = guardians
+            .iter()
+            .map(|(did, sk)| (did.clone(), sk.verifying_key()))
+            .collect();
+        let config =
+            SocialRecoveryConfig::create(&state, &current, &next, &public, 2, 100, 0).unwrap();
+        let registry = SocialRecoveryRegistry::in_memory();
+        registry.configure(config.clone(), &state, 0).unwrap();
+
+        let (_, session_pk) = generate_ed25519_keypair();
+        let signature = SignatureBytes::from_ed_signature(sign_message(
+            &current,
+            &RecoveryCase::opening_bytes(&state.did, &session_pk),
+        ));
+        let case = registry
+            .open_case(&state.did, &session_pk, None, &signature, &state, 0)
+            .unwrap();
+        assert_eq!(case.expires_at, 100 + RECOVERY_CASE_TTL_SECONDS);
+        for (did, sk) in &guardians {
+            let (approval, share) = case.approve(&config, did, sk, 10).unwrap();
+            registry
+                .approve(&case.id, approval, share, &sk.verifying_key(), 10)
+                .unwrap();
+        }
+        assert!(registry.released(&case.id, case.expires_at - 1).is_ok());
+        assert!(matches!(
+            registry.released(&case.id, case.expires_at),
+            Err(CommonError::PolicyDenied(_))
+        ));
+
+        // A lapsed case no longer blocks new ones, but its signed request
+        // cannot be replayed.
+        let later = case.expires_at + 1;
+        assert!(registry
+            .open_case(&state.did, &session_pk, None, &signature, &state, later)
+            .is_err());
+        assert_eq!(
+            registry.case(&case.id).unwrap().status,
+            RecoveryStatus::Expired
+        );
+        let (_, fresh_pk) = generate_ed25519_keypair();
+        let signature = SignatureBytes::from_ed_signature(sign_message(
+            &current,
+            &RecoveryCase::opening_bytes(&state.did, &fresh_pk),
+        ));
+        registry
+            .open_case(&state.did, &fresh_pk, None, &signature, &state, later)
+            .unwrap();
+    }
+}
@@ -0,0 +1,883 @@
+//! Bitstring status lists for credential revocation and suspension
+//!
+//! Checking revocation against a registry keyed by credential CID tells the
+//! registry which credential is being verified. Status lists, modelled on
+//! the W3C Bitstring Status List, avoid this. Each credential carries a
+//! [`StatusListEntry`] naming a list and a bit index. The issuer publishes
+//! the whole list as a compressed, signed [`StatusListCredential`] anchored
+//! in the DAG. A verifier fetches and caches the list and reads the bit
+//! locally, so the issuer only learns that *some* credential in a
+//! 131,072-entry list was checked.
+//!
+//! Every list has two bitstrings that share indices:
+//!
+//! - **revocation**, which is permanent;
+//! - **suspension**, which can be cleared to reinstate a credential.
+//!
+//! On the issuer side, [`StatusListRegistry`] allocates indices at random,
+//! so an index does not reveal issuance order. It persists its state to a
+//! JSON file. On the verifier side, [`StatusListCache`] holds published
+//! lists and refreshes them when their TTL expires.
+//! [`StatusListRevocationVerifier`] brings status checks into the
+//! [`ZkRevocationVerifier`] path.
+
+use crate::zk::{ZkError, ZkRevocationVerifier};
+use crate::{
+    sign_message, verify_signature, DidResolver, SignatureBytes, SigningKey, VerifyingKey,
+};
+use base64::Engine;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use icn_common::{compute_merkle_cid, Cid, CommonError, DagBlock, Did, ZkRevocationProof};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs::{rename, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+
+/// `type` of published status list credentials.
+pub const STATUS_LIST_CREDENTIAL_TYPE: &str = "BitstringStatusListCredential";
+
+/// Entries per list; the W3C minimum that gives holders herd privacy.
+pub const DEFAULT_STATUS_LIST_LENGTH: usize = 131_072;
+
+/// Seconds a verifier may rely on a cached list before refreshing it.
+pub const DEFAULT_STATUS_LIST_TTL: u64 = 300;
+
+/// Key in [`ZkRevocationProof::public_inputs`] holding a [`StatusListEntry`].
+pub const CREDENTIAL_STATUS_INPUT: &str = "credential_status";
+
+/// Upper bound on a decompressed list, guarding against gzip bombs.
+const MAX_LIST_BYTES: u64 = 16 * 1024 * 1024;
+
+/// What a set bit in a status list means.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusPurpose {
+    /// The credential is permanently revoked.
+    Revocation,
+    /// The credential is suspended until reinstated.
+    Suspension,
+}
+
+/// Status of a credential as read from its status lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialStatus {
+    Active,
+    Suspended,
+    Revoked,
+}
+
+/// Position of a credential in its issuer's status lists.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StatusListEntry {
+    /// Identifier of the list, scoped to the issuer.
+    pub status_list: String,
+    /// Bit index within the list.
+    pub index: u64,
+}
+
+impl StatusListEntry {
+    /// Bytes covered by credential signatures.
+    pub fn signable_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.status_list.as_bytes().to_vec();
+        bytes.push(0);
+        bytes.extend_from_slice(&self.index.to_le_bytes());
+        bytes
+    }
+}
+
+/// Fixed-length bitstring, most significant bit first within each byte.
+///
+/// Serialized as its compressed encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bitstring {
+    bytes: Vec<u8>,
+}
+
+impl Bitstring {
+    /// A bitstring of `length` zero bits, rounded up to whole bytes.
+    pub fn new(length: usize) -> Self {
+        Self {
+            bytes: vec![0; length.div_ceil(8)],
+        }
+    }
+
+    /// Number of bits.
+    pub fn len(&self) -> usize {
+        self.bytes.len() * 8
+    }
+
+    /// Whether the bitstring has no bits.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Bit at `index`, or `None` if out of range.
+    pub fn get(&self, index: u64) -> Option<bool> {
+        let byte = self.bytes.get(usize::try_from(index / 8).ok()?)?;
+        Some(byte & (0x80 >> (index % 8)) != 0)
+    }
+
+    /// Set the bit at `index`.
+    pub fn set(&mut self, index: u64, value: bool) -> Result<(), CommonError> {
+        let byte = usize::try_from(index / 8)
+            .ok()
+            .and_then(|i| self.bytes.get_mut(i))
+            .ok_or_else(|| {
+                CommonError::InvalidInputError(format!("status index {index} out of range"))
+            })?;
+        let mask = 0x80 >> (index % 8);
+        if value {
+            *byte |= mask;
+        } else {
+            *byte &= !mask;
+        }
+        Ok(())
+    }
+
+    /// GZIP-compress and encode as multibase base64url (`u` prefix).
+    pub fn encode(&self) -> Result<String, CommonError> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder
+            .write_all(&self.bytes)
+            .and_then(|_| encoder.finish())
+            .map(|gz| {
+                format!(
+                    "u{}",
+                    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(gz)
+                )
+            })
+            .map_err(|e| CommonError::SerializationError(format!("status list: {e}")))
+    }
+
+    /// Decode the output of [`Bitstring::encode`].
+    pub fn decode(encoded: &str) -> Result<Self, CommonError> {
+        let invalid = |e: &dyn fmt::Display| {
+            CommonError::DeserializationError(format!("invalid status list: {e}"))
+        };
+        let body = encoded
+            .strip_prefix('u')
+            .ok_or_else(|| invalid(&"expected multibase base64url"))?;
+        let gz = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(body)
+            .map_err(|e| invalid(&e))?;
+        let mut bytes = Vec::new();
+        GzDecoder::new(gz.as_slice())
+            .take(MAX_LIST_BYTES + 1)
+            .read_to_end(&mut bytes)
+            .map_err(|e| invalid(&e))?;
+        if bytes.len() as u64 > MAX_LIST_BYTES {
+            return Err(invalid(&"list too large"));
+        }
+        Ok(Self { bytes })
+    }
+}
+
+impl Serialize for Bitstring {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let encoded = self.encode().map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&encoded)
+    }
+}
+
+impl<'de> Deserialize<'de> for Bitstring {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        Bitstring::decode(&encoded).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A signed snapshot of one of an issuer's status bitstrings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusListCredential {
+    /// Always [`STATUS_LIST_CREDENTIAL_TYPE`].
+    #[serde(rename = "type")]
+    pub credential_type: String,
+    /// DID of the issuer maintaining the list.
+    pub issuer: Did,
+    /// List identifier referenced by [`StatusListEntry::status_list`].
+    pub id: String,
+    /// Meaning of set bits.
+    pub purpose: StatusPurpose,
+    /// Increases with every change, so stale snapshots can be rejected.
+    pub version: u64,
+    /// Unix timestamp of the snapshot.
+    pub valid_from: u64,
+    /// Seconds verifiers may cache the snapshot.
+    pub ttl: u64,
+    /// The bitstring, compressed.
+    pub encoded_list: Bitstring,
+    /// Issuer signature over [`StatusListCredential::signable_bytes`].
+    pub signature: SignatureBytes,
+}
+
+impl StatusListCredential {
+    /// Bytes covered by the issuer signature.
+    pub fn signable_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        let mut unsigned = self.clone();
+        unsigned.signature = SignatureBytes(Vec::new());
+        serde_json::to_vec(&unsigned).map_err(|e| CommonError::SerializationError(e.to_string()))
+    }
+
+    /// Sign the snapshot with the issuer's key.
+    pub fn sign(&mut self, key: &SigningKey) -> Result<(), CommonError> {
+        let bytes = self.signable_bytes()?;
+        self.signature = SignatureBytes::from_ed_signature(sign_message(key, &bytes));
+        Ok(())
+    }
+
+    /// Check the issuer signature.
+    pub fn verify(&self, key: &VerifyingKey) -> Result<(), CommonError> {
+        if self.credential_type != STATUS_LIST_CREDENTIAL_TYPE {
+            return Err(CommonError::IdentityError(format!(
+                "not a status list credential: {}",
+                self.credential_type
+            )));
+        }
+        let signature = self.signature.to_ed_signature()?;
+        if verify_signature(key, &self.signable_bytes()?, &signature) {
+            Ok(())
+        } else {
+            Err(CommonError::IdentityError(format!(
+                "status list {} is not signed by {}",
+                self.id, self.issuer
+            )))
+        }
+    }
+
+    /// Whether the bit at `index` is set.
+    pub fn is_set(&self, index: u64) -> Result<bool, CommonError> {
+        self.encoded_list.get(index).ok_or_else(|| {
+            CommonError::InvalidInputError(format!(
+                "status index {index} outside list {} of length {}",
+                self.id,
+                self.encoded_list.len()
+            ))
+        })
+    }
+
+    /// DAG block anchoring this snapshot.
+    pub fn to_dag_block(&self) -> Result<DagBlock, CommonError> {
+        let data =
+            serde_json::to_vec(self).map_err(|e| CommonError::SerializationError(e.to_string()))?;
+        let cid = compute_merkle_cid(
+            0x71,
+            &data,
+            &[],
+            self.valid_from,
+            &self.issuer,
+            &None,
+            &None,
+        );
+        Ok(DagBlock {
+            cid,
+            data,
+            links: Vec::new(),
+            timestamp: self.valid_from,
+            author_did: self.issuer.clone(),
+            signature: None,
+            scope: None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IssuerList {
+    revocation: Bitstring,
+    suspension: Bitstring,
+    /// Indices handed out to credentials.
+    allocated: Bitstring,
+    allocated_count: u64,
+    version: u64,
+}
+
+impl IssuerList {
+    fn new(length: usize) -> Self {
+        Self {
+            revocation: Bitstring::new(length),
+            suspension: Bitstring::new(length),
+            allocated: Bitstring::new(length),
+            allocated_count: 0,
+            version: 0,
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.allocated_count >= self.allocated.len() as u64
+    }
+
+    fn bits(&self, purpose: StatusPurpose) -> &Bitstring {
+        match purpose {
+            StatusPurpose::Revocation => &self.revocation,
+            StatusPurpose::Suspension => &self.suspension,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RegistryState {
+    lists: BTreeMap<String, IssuerList>,
+    current: Option<String>,
+    /// Status entry of each issued credential, keyed by CID string.
+    credentials: HashMap<String, StatusListEntry>,
+}
+
+/// Issuer-side status lists, optionally persisted to a JSON file.
+///
+/// Clones share state.
+#[derive(Debug, Clone)]
+pub struct StatusListRegistry {
+    issuer: Did,
+    list_length: usize,
+    path: Option<PathBuf>,
+    state: Arc<Mutex<RegistryState>>,
+}
+
+impl StatusListRegistry {
+    /// Registry for `issuer` that lives only in memory.
+    pub fn in_memory(issuer: Did) -> Self {
+        Self {
+            issuer,
+            list_length: DEFAULT_STATUS_LIST_LENGTH,
+            path: None,
+            state: Arc::new(Mutex::new(RegistryState::default())),
+        }
+    }
+
+    /// Registry for `issuer` persisted at `path`, loading any existing state.
+    pub fn open(issuer: Did, path: PathBuf) -> Result<Self, CommonError> {
+        let state = if path.exists() {
+            let mut contents = String::new();
+            File::open(&path)
+                .and_then(|mut f| f.read_to_string(&mut contents))
+                .map_err(|e| {
+                    CommonError::IoError(format!("Failed to read status lists {path:?}: {e}"))
+                })?;
+            if contents.trim().is_empty() {
+                RegistryState::default()
+            } else {
+                serde_json::from_str(&contents).map_err(|e| {
+                    CommonError::DeserializationError(format!(
+                        "Failed to parse status lists {path:?}: {e}"
+                    ))
+                })?
+            }
+        } else {
+            RegistryState::default()
+        };
+        Ok(Self {
+            issuer,
+            list_length: DEFAULT_STATUS_LIST_LENGTH,
+            path: Some(path),
+            state: Arc::new(Mutex::new(state)),
+        })
+    }
+
+    /// Use lists of `length` entries for lists created from now on.
+    pub fn with_list_length(mut self, length: usize) -> Self {
+        self.list_length = length.max(8);
+        self
+    }
+
+    /// DID of the issuer maintaining the lists.
+    pub fn issuer(&self) -> &Did {
+        &self.issuer
+    }
+
+    /// Reserve a random unused index for a credential about to be issued.
+    pub fn allocate(&self) -> Result<StatusListEntry, CommonError> {
+        let mut state = self.lock()?;
+        let list_id = match state.current.clone() {
+            Some(id) if !state.lists[&id].is_full() => id,
+            _ => {
+                let id = format!("status-list-{}", state.lists.len());
+                state
+                    .lists
+                    .insert(id.clone(), IssuerList::new(self.list_length));
+                state.current = Some(id.clone());
+                id
+            }
+        };
+        let list = state.lists.get_mut(&list_id).expect("current list exists");
+        let length = list.allocated.len() as u64;
+        let mut index = fastrand::u64(0..length);
+        while list.allocated.get(index) == Some(true) {
+            index = (index + 1) % length;
+        }
+        list.allocated.set(index, true)?;
+        list.allocated_count += 1;
+        self.persist(&state)?;
+        Ok(StatusListEntry {
+            status_list: list_id,
+            index,
+        })
+    }
+
+    /// Remember which status entry the credential `cid` carries.
+    pub fn bind(&self, cid: &Cid, entry: StatusListEntry) -> Result<(), CommonError> {
+        let mut state = self.lock()?;
+        if !state.lists.contains_key(&entry.status_list) {
+            return Err(CommonError::ResourceNotFound(format!(
+                "unknown status list {}",
+                entry.status_list
+            )));
+        }
+        state.credentials.insert(cid.to_string(), entry);
+        self.persist(&state)
+    }
+
+    /// Status entry of an issued credential.
+    pub fn entry(&self, cid: &Cid) -> Option<StatusListEntry> {
+        self.lock().ok()?.credentials.get(&cid.to_string()).cloned()
+    }
+
+    /// Set or clear a credential's bit for `purpose`, returning its entry.
+    ///
+    /// Revocation cannot be cleared.
+    pub fn set_status(
+        &self,
+        cid: &Cid,
+        purpose: StatusPurpose,
+        value: bool,
+    ) -> Result<StatusListEntry, CommonError> {
+        let mut state = self.lock()?;
+        let entry = state
+            .credentials
+            .get(&cid.to_string())
+            .cloned()
+            .ok_or_else(|| CommonError::ResourceNotFound(format!("unknown credential {cid}")))?;
+        let list = state
+            .lists
+            .get_mut(&entry.status_list)
+            .expect("bound lists exist");
+        match purpose {
+            StatusPurpose::Revocation if !value => {
+                return Err(CommonError::PolicyDenied(
+                    "revocation is permanent".to_string(),
+                ))
+            }
+            StatusPurpose::Revocation => list.revocation.set(entry.index, true)?,
+            StatusPurpose::Suspension => list.suspension.set(entry.index, value)?,
+        }
+        list.version += 1;
+        self.persist(&state)?;
+        Ok(entry)
+    }
+
+    /// Current status of an issued credential.
+    pub fn status(&self, cid: &Cid) -> Option<CredentialStatus> {
+        let state = self.lock().ok()?;
+        let entry = state.credentials.get(&cid.to_string())?;
+        let list = state.lists.get(&entry.status_list)?;
+        Some(status_from_bits(
+            list.revocation.get(entry.index).unwrap_or(false),
+            list.suspension.get(entry.index).unwrap_or(false),
+        ))
+    }
+
+    /// Unsigned snapshot of a list, ready to be signed and published.
+    pub fn snapshot(
+        &self,
+        id: &str,
+        purpose: StatusPurpose,
+        now: u64,
+    ) -> Option<StatusListCredential> {
+        let state = self.lock().ok()?;
+        let list = state.lists.get(id)?;
+        Some(StatusListCredential {
+            credential_type: STATUS_LIST_CREDENTIAL_TYPE.to_string(),
+            issuer: self.issuer.clone(),
+            id: id.to_string(),
+            purpose,
+            version: list.version,
+            valid_from: now,
+            ttl: DEFAULT_STATUS_LIST_TTL,
+            encoded_list: list.bits(purpose).clone(),
+            signature: SignatureBytes(Vec::new()),
+        })
+    }
+
+    /// Identifiers of all lists.
+    pub fn list_ids(&self) -> Vec<String> {
+        self.lock()
+            .map(|s| s.lists.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, RegistryState>, CommonError> {
+        self.state
+            .lock()
+            .map_err(|_| CommonError::LockError("status list registry poisoned".into()))
+    }
+
+    fn persist(&self, state: &RegistryState) -> Result<(), CommonError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let serialized = serde_json::to_string(state).map_err(|e| {
+            CommonError::SerializationError(format!("Failed to serialize status lists: {e}"))
+        })?;
+        let tmp_path = path.with_extension("tmp");
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&tmp_path)
+            .map_err(|e| {
+                CommonError::IoError(format!("Failed to open status lists {tmp_path:?}: {e}"))
+            })?;
+        file.write_all(serialized.as_bytes())
+            .and_then(|_| file.sync_all())
+            .map_err(|e| {
+                CommonError::IoError(format!("Failed to write status lists {tmp_path:?}: {e}"))
+            })?;
+        drop(file);
+        rename(&tmp_path, path).map_err(|e| {
+            CommonError::IoError(format!(
+                "Failed to rename status lists {tmp_path:?} -> {path:?}: {e}"
+            ))
+        })
+    }
+}
+
+impl crate::RevocationRegistry for StatusListRegistry {
+    fn record(&self, cid: Cid) {
+        if let Ok(entry) = self.allocate() {
+            let _ = self.bind(&cid, entry);
+        }
+    }
+
+    fn revoke(&self, cid: &Cid) -> bool {
+        self.set_status(cid, StatusPurpose::Revocation, true)
+            .is_ok()
+    }
+
+    fn is_revoked(&self, cid: &Cid) -> bool {
+        self.status(cid) == Some(CredentialStatus::Revoked)
+    }
+
+    fn allocate_status(&self) -> Option<StatusListEntry> {
+        self.allocate().ok()
+    }
+
+    fn record_with_status(&self, cid: Cid, status: &StatusListEntry) {
+        let _ = self.bind(&cid, status.clone());
+    }
+
+    fn suspend(&self, cid: &Cid) -> bool {
+        self.set_status(cid, StatusPurpose::Suspension, true)
+            .is_ok()
+    }
+
+    fn reinstate(&self, cid: &Cid) -> bool {
+        self.set_status(cid, StatusPurpose::Suspension, false)
+            .is_ok()
+    }
+
+    fn is_suspended(&self, cid: &Cid) -> bool {
+        self.status(cid) == Some(CredentialStatus::Suspended)
+    }
+}
+
+fn status_from_bits(revoked: bool, suspended: bool) -> CredentialStatus {
+    if revoked {
+        CredentialStatus::Revoked
+    } else if suspended {
+        CredentialStatus::Suspended
+    } else {
+        CredentialStatus::Active
+    }
+}
+
+/// Where a [`StatusListCache`] fetches lists it does not hold.
+pub trait StatusListSource: Send + Sync {
+    /// Latest published snapshot of the list, if any.
+    fn fetch(
+        &self,
+        issuer: &Did,
+        id: &str,
+        purpose: StatusPurpose,
+    ) -> Result<Option<StatusListCredential>, CommonError>;
+}
+
+type ListKey = (Did, String, StatusPurpose);
+
+/// Verifier-side cache of published status lists.
+///
+/// Lists are checked against the issuer's DID key on insertion, and older
+/// versions never replace newer ones. Clones share the cache.
+#[derive(Clone)]
+pub struct StatusListCache {
+    resolver: Arc<dyn DidResolver>,
+    source: Option<Arc<dyn StatusListSource>>,
+    lists: Arc<RwLock<HashMap<ListKey, (u64, StatusListCredential)>>>,
+}
+
+impl StatusListCache {
+    /// Cache that resolves issuer keys through `resolver`.
+    pub fn new(resolver: Arc<dyn DidResolver>) -> Self {
+        Self {
+            resolver,
+            source: None,
+            lists: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Refresh missing or expired lists from `source`.
+    pub fn with_source(mut self, source: Arc<dyn StatusListSource>) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Verify and cache a published list. Returns `false` if a newer version
+    /// is already cached.
+    pub fn insert(&self, list: StatusListCredential, now: u64) -> Result<bool, CommonError> {
+        list.verify(&self.resolver.resolve(&list.issuer)?)?;
+        let key = (list.issuer.clone(), list.id.clone(), list.purpose);
+        let mut lists = self
+            .lists
+            .write()
+            .map_err(|_| CommonError::LockError("status list cache poisoned".into()))?;
+        if let Some((_, cached)) = lists.get(&key) {
+            if cached.version > list.version {
+                return Ok(false);
+            }
+        }
+        lists.insert(key, (now, list));
+        Ok(true)
+    }
+
+    /// The cached list, refreshed from the source once its TTL has passed.
+    pub fn get(
+        &self,
+        issuer: &Did,
+        id: &str,
+        purpose: StatusPurpose,
+        now: u64,
+    ) -> Result<Option<StatusListCredential>, CommonError> {
+        let key = (issuer.clone(), id.to_string(), purpose);
+        let cached = self
+            .lists
+            .read()
+            .map_err(|_| CommonError::LockError("status list cache poisoned".into()))?
+            .get(&key)
+            .cloned();
+        let Some(source) = &self.source else {
+            return Ok(cached.map(|(_, list)| list));
+        };
+        if let Some((fetched_at, list)) = &cached {
+            if now < fetched_at.saturating_add(list.ttl) {
+                return Ok(Some(list.clone()));
+            }
+        }
+        match source.fetch(issuer, id, purpose)? {
+            Some(list) => {
+                self.insert(list, now)?;
+                Ok(self
+                    .lists
+                    .read()
+                    .map_err(|_| CommonError::LockError("status list cache poisoned".into()))?
+                    .get(&key)
+                    .map(|(_, list)| list.clone()))
+            }
+            None => Ok(cached.map(|(_, list)| list)),
+        }
+    }
+
+    /// Status of the credential at `entry` in `issuer`'s lists.
+    ///
+    /// The revocation list must be known; a missing suspension list means
+    /// the issuer has suspended nothing.
+    pub fn status(
+        &self,
+        issuer: &Did,
+        entry: &StatusListEntry,
+        now: u64,
+    ) -> Result<CredentialStatus, CommonError> {
+        let revocation = self
+            .get(issuer, &entry.status_list, StatusPurpose::Revocation, now)?
+            .ok_or_else(|| {
+                CommonError::ResourceNotFound(format!(
+                    "no revocation list {} from {issuer}",
+                    entry.status_list
+                ))
+            })?;
+        let suspended =
+            match self.get(issuer, &entry.status_list, StatusPurpose::Suspension, now)? {
+                Some(list) => list.is_set(entry.index)?,
+                None => false,
+            };
+        Ok(status_from_bits(revocation.is_set(entry.index)?, suspended))
+    }
+
+    /// Cache every valid status list found in `blocks`, returning how many
+    /// were accepted.
+    pub fn load_blocks<'a>(
+        &self,
+        blocks: impl IntoIterator<Item = &'a DagBlock>,
+        now: u64,
+    ) -> usize {
+        blocks
+            .into_iter()
+            .filter_map(|b| serde_json::from_slice::<StatusListCredential>(&b.data).ok())
+            .filter(|list| list.credential_type == STATUS_LIST_CREDENTIAL_TYPE)
+            .filter(|list| matches!(self.insert(list.clone(), now), Ok(true)))
+            .count()
+    }
+}
+
+impl fmt::Debug for StatusListCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cached = self.lists.read().map(|l| l.len()).unwrap_or_default();
+        f.debug_struct("StatusListCache")
+            .field("cached", &cached)
+            .field("has_source", &self.source.is_some())
+            .finish()
+    }
+}
+
+/// [`ZkRevocationVerifier`] that consults status lists before the proof.
+///
+/// A proof whose public inputs carry a [`CREDENTIAL_STATUS_INPUT`] entry is
+/// rejected unless the issuer's lists show the credential as active. The
+/// proof itself is then checked by the inner verifier.
+pub struct StatusListRevocationVerifier {
+    cache: StatusListCache,
+    inner: Box<dyn ZkRevocationVerifier>,
+}
+
+impl StatusListRevocationVerifier {
+    /// Check status lists in `cache`, then delegate to `inner`.
+    pub fn new(cache: StatusListCache, inner: Box<dyn ZkRevocationVerifier>) -> Self {
+        Self { cache, inner }
+    }
+}
+
+impl ZkRevocationVerifier for StatusListRevocationVerifier {
+    fn verify_revocation(&self, proof: &ZkRevocationProof) -> Result<bool, ZkError> {
+        let entry = proof
+            .public_inputs
+            .as_ref()
+            .and_then(|inputs| inputs.get(CREDENTIAL_STATUS_INPUT))
+            .map(|value| serde_json::from_value::<StatusListEntry>(value.clone()))
+            .transpose()
+            .map_err(|_| ZkError::InvalidProof)?;
+        if let Some(entry) = entry {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            match self.cache.status(&proof.issuer, &entry, now) {
+                Ok(CredentialStatus::Active) => {}
+                Ok(_) => return Ok(false),
+                Err(_) => return Err(ZkError::VerificationFailed),
+            }
+        }
+        self.inner.verify_revocation(proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{did_key_from_verifying_key, generate_ed25519_keypair, KeyDidResolver};
+    use crate::{DummyVerifier, RevocationRegistry};
+    use icn_common::ZkProofType;
+    use std::str::FromStr;
+
+    #[test]
+    fn bitstring_round_trips_compressed() {
+        let mut bits = Bitstring::new(DEFAULT_STATUS_LIST_LENGTH);
+        bits.set(0, true).unwrap();
+        bits.set(131_071, true).unwrap();
+        assert!(bits.set(131_072, true).is_err());
+
+        let encoded = bits.encode().unwrap();
+        assert!(encoded.len() < 1_000, "sparse lists compress well");
+        let decoded = Bitstring::decode(&encoded).unwrap();
+        assert_eq!(decoded, bits);
+        assert_eq!(decoded.get(0), Some(true));
+        assert_eq!(decoded.get(1), Some(false));
+        assert_eq!(decoded.get(131_071), Some(true));
+    }
+
+    #[test]
+    fn registry_persists_revocation_and_reversible_suspension() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("status_lists.json");
+        let issuer = Did::new("key", "issuer");
+        let cid = Cid::new_v1_sha256(0x71, b"credential");
+
+        let registry = StatusListRegistry::open(issuer.clone(), path.clone())
+            .unwrap()
+            .with_list_length(64);
+        let entry = registry.allocate_status().unwrap();
+        registry.record_with_status(cid.clone(), &entry);
+        assert!(registry.suspend(&cid));
+        assert!(registry.is_suspended(&cid));
+        assert!(registry.reinstate(&cid));
+        assert_eq!(registry.status(&cid), Some(CredentialStatus::Active));
+        assert!(registry.revoke(&cid));
+        assert!(registry
+            .set_status(&cid, StatusPurpose::Revocation, false)
+            .is_err());
+
+        let reopened = StatusListRegistry::open(issuer, path).unwrap();
+        assert_eq!(reopened.entry(&cid), Some(entry));
+        assert!(reopened.is_revoked(&cid));
+    }
+
+    #[test]
+    fn verifier_reads_status_from_signed_cached_lists() {
+        let (sk, pk) = generate_ed25519_keypair();
+        let issuer = Did::from_str(&did_key_from_verifying_key(&pk)).unwrap();
+        let registry = StatusListRegistry::in_memory(issuer.clone());
+        let cid = Cid::new_v1_sha256(0x71, b"credential");
+        let entry = registry.allocate().unwrap();
+        registry.bind(&cid, entry.clone()).unwrap();
+
+        let cache = StatusListCache::new(Arc::new(KeyDidResolver));
+        let publish = |purpose| {
+            let mut list = registry.snapshot(&entry.status_list, purpose, 10).unwrap();
+            list.sign(&sk).unwrap();
+            cache.insert(list, 10).unwrap()
+        };
+        assert!(publish(StatusPurpose::Revocation));
+        assert_eq!(
+            cache.status(&issuer, &entry, 10).unwrap(),
+            CredentialStatus::Active
+        );
+
+        // Forged lists are rejected.
+        let mut forged = registry
+            .snapshot(&entry.status_list, StatusPurpose::Revocation, 10)
+            .unwrap();
+        forged.version = 99;
+        forged.sign(&generate_ed25519_keypair().0).unwrap();
+        assert!(cache.insert(forged, 10).is_err());
+
+        let proof = ZkRevocationProof {
+            issuer: issuer.clone(),
+            subject: Did::new("key", "holder"),
+            proof: vec![1],
+            backend: ZkProofType::Other("dummy".into()),
+            verification_key: None,
+            public_inputs: Some(serde_json::json!({ CREDENTIAL_STATUS_INPUT: entry })),
+        };
+        let verifier = StatusListRevocationVerifier::new(cache.clone(), Box::new(DummyVerifier));
+        assert!(verifier.verify_revocation(&proof).unwrap());
+
+        registry.revoke(&cid);
+        assert!(publish(StatusPurpose::Revocation));
+        assert_eq!(
+            cache.status(&issuer, &entry, 10).unwrap(),
+            CredentialStatus::Revoked
+        );
+        assert!(!verifier.verify_revocation(&proof).unwrap());
+    }
+}
@@ -3,6 +3,7 @@
 //! This module implements W3C Verifiable Credentials with ICN-specific extensions
 //! for membership credentials, resource tokens, and privacy-preserving verification.
 
+use crate::{BbsPresentation, DidResolver};
 use base64::Engine;
 use icn_common::{CommonError, Did, Signable};
 use serde::{Deserialize, Serialize};
@@ -238,13 +239,65 @@ pub struct VerifiablePresentation {
     /// Credentials included in presentation
     pub verifiable_credential: Vec<VerifiableCredential>,
 
-    /// Presenter DID
+    /// Presenter DID (empty for anonymous BBS presentations)
     pub holder: Did,
 
+    /// Derived BBS proofs, presented instead of the underlying credentials
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub derived_credentials: Vec<BbsPresentation>,
+
     /// Presentation proof
     pub proof: CredentialProof,
 }
 
+impl VerifiablePresentation {
+    /// Wrap derived BBS proofs in a presentation.
+    ///
+    /// Each derived proof is bound to the verifier's nonce, so the envelope
+    /// carries no holder signature. Pass `holder` only if the presenter wants
+    /// to be identified.
+    pub fn from_bbs(derived_credentials: Vec<BbsPresentation>, holder: Option<Did>) -> Self {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        Self {
+            context: vec![
+                "https://www.w3.org/2018/credentials/v1".to_string(),
+                "https://icn.network/credentials/v1".to_string(),
+            ],
+            id: format!("urn:uuid:{}", uuid::Uuid::new_v4()),
+            presentation_type: vec![
+                "VerifiablePresentation".to_string(),
+                "BbsDerivedPresentation".to_string(),
+            ],
+            verifiable_credential: Vec::new(),
+            holder: holder.unwrap_or_default(),
+            derived_credentials,
+            proof: CredentialProof {
+                proof_type: "BbsProof".to_string(),
+                proof_purpose: "authentication".to_string(),
+                verification_method: String::new(),
+                created: format_timestamp(now),
+                proof_value: String::new(), // Carried by each derived credential
+                proof_metadata: None,
+            },
+        }
+    }
+
+    /// Verify every derived BBS proof, resolving issuer keys via `resolver`
+    pub fn verify_derived_credentials(
+        &self,
+        resolver: &dyn DidResolver,
+    ) -> Result<(), CommonError> {
+        for derived in &self.derived_credentials {
+            derived.verify_with(resolver)?;
+        }
+        Ok(())
+    }
+}
+
 /// Selective disclosure proof for privacy
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SelectiveDisclosureProof {
@@ -724,4 +777,39 @@ mod tests {
         let bytes = credential.to_signable_bytes().unwrap();
         assert!(!bytes.is_empty());
     }
+
+    #[test]
+    fn test_bbs_presentation_verification() {
+        use crate::{
+            did_key_from_verifying_key, generate_ed25519_keypair, BbsCredential, BbsKeyBinding,
+            BbsPresentationRequest, BbsSecretKey, KeyDidResolver,
+        };
+
+        let (sk, pk) = generate_ed25519_keypair();
+        let issuer = Did::from_str(&did_key_from_verifying_key(&pk)).unwrap();
+        let bbs_key = BbsSecretKey::generate();
+        let binding = BbsKeyBinding::sign(issuer.clone(), &bbs_key.public_key(), &sk);
+        let credential = BbsCredential::issue(
+            issuer,
+            test_did("alice"),
+            icn_common::Cid::new_v1_sha256(0x55, b"membership"),
+            [("role".to_string(), "member".to_string())].into(),
+            &bbs_key,
+            binding,
+        )
+        .unwrap();
+        let derived = credential
+            .present(&BbsPresentationRequest {
+                disclose: vec!["role".to_string()],
+                nonce: "challenge".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let presentation = VerifiablePresentation::from_bbs(vec![derived], None);
+        assert_eq!(presentation.holder, Did::default());
+        presentation
+            .verify_derived_credentials(&KeyDidResolver)
+            .unwrap();
+    }
 }
@@ -0,0 +1,1268 @@
+//! BBS signatures over BLS12-381 for unlinkable selective disclosure
+//!
+//! An issuer signs an ordered list of messages (the holder DID followed by
+//! every claim in name order) with one short signature. The holder never
+//! shows that signature. Instead, each presentation derives a fresh
+//! zero-knowledge proof of possession. The proof reveals only the claims the
+//! holder chooses, and can prove range predicates such as `age >= 18` over
+//! claims that stay hidden. Two presentations of the same credential share
+//! no values, so verifiers cannot link them. Only the issuer, the schema
+//! and the claim names are revealed.
+//!
+//! The signature is the BBS scheme from the IETF CFRG draft: `A = B/(x+e)`.
+//! Generators come from hashing to G1, and challenges are bound to the
+//! verifier's nonce. A range predicate commits to the hidden value with
+//! Pedersen and proves a 64-bit decomposition of its distance from the
+//! bound. Each bit uses a Chaum–Pedersen OR-proof. The commitment is tied to
+//! the signed message because both proofs share one challenge and one
+//! response.
+//!
+//! The BBS public key lives on G2, so it cannot be derived from an Ed25519
+//! DID. Issuers publish a [`BbsKeyBinding`]: their DID key signs the BBS key,
+//! and verifiers check that signature before trusting any proof.
+
+use super::{ZkError, ZkVerifier};
+use crate::{
+    sign_message, verify_signature, DidResolver, KeyDidResolver, SignatureBytes, SigningKey,
+    VerifyingKey,
+};
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::hashing::curve_maps::wb::WBMap;
+use ark_ec::hashing::map_to_curve_hasher::MapToCurveBasedHasher;
+use ark_ec::hashing::HashToCurve;
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup, Group};
+use ark_ff::field_hashers::DefaultFieldHasher;
+use ark_ff::{Field, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::UniformRand;
+use icn_common::{Cid, CommonError, Did, ZkCredentialProof, ZkProofType};
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::Arc;
+
+/// `claim_type` reported by [`ZkCredentialProof`]s that wrap a [`BbsPresentation`].
+pub const BBS_CLAIM_TYPE: &str = "bbs-presentation";
+
+/// Width of the bit decomposition behind range predicates.
+const RANGE_BITS: usize = 64;
+
+const GENERATOR_DST: &[u8] = b"ICN-BBS-BLS12381G1-GENERATORS-V1";
+const DOMAIN_DST: &[u8] = b"ICN-BBS-DOMAIN-V1";
+const SIGNATURE_DST: &[u8] = b"ICN-BBS-SIGNATURE-E-V1";
+const CHALLENGE_DST: &[u8] = b"ICN-BBS-CHALLENGE-V1";
+const HOLDER_DST: &[u8] = b"ICN-BBS-HOLDER-V1";
+const CLAIM_DST: &[u8] = b"ICN-BBS-CLAIM-V1";
+const KEY_BINDING_DST: &[u8] = b"ICN-BBS-KEY-BINDING-V1";
+
+type G1Hasher = MapToCurveBasedHasher<
+    G1Projective,
+    DefaultFieldHasher<Sha256, 128>,
+    WBMap<ark_bls12_381::g1::Config>,
+>;
+
+fn bbs_error(msg: impl fmt::Display) -> CommonError {
+    CommonError::IdentityError(format!("BBS: {msg}"))
+}
+
+/// Secret half of an issuer's BBS key.
+#[derive(Clone)]
+pub struct BbsSecretKey(Fr);
+
+impl BbsSecretKey {
+    /// Generate a fresh random key.
+    pub fn generate() -> Self {
+        loop {
+            let x = Fr::rand(&mut OsRng);
+            if !x.is_zero() {
+                return Self(x);
+            }
+        }
+    }
+
+    /// Decode a key from its 32-byte encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CommonError> {
+        let x = Fr::deserialize_compressed(bytes).map_err(bbs_error)?;
+        if x.is_zero() {
+            return Err(bbs_error("secret key must not be zero"));
+        }
+        Ok(Self(x))
+    }
+
+    /// 32-byte encoding of the key.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        to_bytes(&self.0)
+    }
+
+    /// The matching public key `W = x·G2`.
+    pub fn public_key(&self) -> BbsPublicKey {
+        BbsPublicKey((G2Projective::generator() * self.0).into_affine())
+    }
+}
+
+impl fmt::Debug for BbsSecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BbsSecretKey(..)")
+    }
+}
+
+/// Public half of an issuer's BBS key, a point on G2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BbsPublicKey(G2Affine);
+
+impl BbsPublicKey {
+    /// Decode a key from its 96-byte compressed encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CommonError> {
+        let w = G2Affine::deserialize_compressed(bytes).map_err(bbs_error)?;
+        if w.is_zero() {
+            return Err(bbs_error("public key must not be the identity"));
+        }
+        Ok(Self(w))
+    }
+
+    /// 96-byte compressed encoding of the key.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        to_bytes(&self.0)
+    }
+}
+
+/// An issuer's signed statement that a BBS public key speaks for its DID.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BbsKeyBinding {
+    /// DID whose key signed the binding.
+    pub issuer: Did,
+    /// Compressed BBS public key.
+    #[serde(with = "serde_bytes")]
+    pub public_key: Vec<u8>,
+    /// Ed25519 signature by the issuer's DID key.
+    pub signature: SignatureBytes,
+}
+
+impl BbsKeyBinding {
+    /// Bind `public_key` to `issuer` with the issuer's DID key.
+    pub fn sign(issuer: Did, public_key: &BbsPublicKey, signer: &SigningKey) -> Self {
+        let public_key = public_key.to_bytes();
+        let bytes = Self::signable_bytes(&issuer, &public_key);
+        Self {
+            issuer,
+            public_key,
+            signature: SignatureBytes::from_ed_signature(sign_message(signer, &bytes)),
+        }
+    }
+
+    /// Check the binding against the issuer's DID key and return the bound key.
+    pub fn verify(&self, issuer_key: &VerifyingKey) -> Result<BbsPublicKey, CommonError> {
+        let signature = self.signature.to_ed_signature()?;
+        let bytes = Self::signable_bytes(&self.issuer, &self.public_key);
+        if !verify_signature(issuer_key, &bytes, &signature) {
+            return Err(bbs_error(format!(
+                "key binding is not signed by {}",
+                self.issuer
+            )));
+        }
+        BbsPublicKey::from_bytes(&self.public_key)
+    }
+
+    /// Resolve the issuer's DID key and check the binding against it.
+    pub fn verify_with(&self, resolver: &dyn DidResolver) -> Result<BbsPublicKey, CommonError> {
+        self.verify(&resolver.resolve(&self.issuer)?)
+    }
+
+    fn signable_bytes(issuer: &Did, public_key: &[u8]) -> Vec<u8> {
+        let mut bytes = KEY_BINDING_DST.to_vec();
+        bytes.extend_from_slice(issuer.to_string().as_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(public_key);
+        bytes
+    }
+}
+
+/// A predicate proven over a hidden numeric claim.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BbsPredicate {
+    /// The claim is at least `bound`.
+    AtLeast { claim: String, bound: u64 },
+    /// The claim is at most `bound`.
+    AtMost { claim: String, bound: u64 },
+}
+
+impl BbsPredicate {
+    /// Name of the claim the predicate constrains.
+    pub fn claim(&self) -> &str {
+        match self {
+            BbsPredicate::AtLeast { claim, .. } | BbsPredicate::AtMost { claim, .. } => claim,
+        }
+    }
+
+    fn bound(&self) -> u64 {
+        match self {
+            BbsPredicate::AtLeast { bound, .. } | BbsPredicate::AtMost { bound, .. } => *bound,
+        }
+    }
+
+    fn is_lower_bound(&self) -> bool {
+        matches!(self, BbsPredicate::AtLeast { .. })
+    }
+
+    /// Whether `value` satisfies the predicate.
+    pub fn holds_for(&self, value: u64) -> bool {
+        match self {
+            BbsPredicate::AtLeast { bound, .. } => value >= *bound,
+            BbsPredicate::AtMost { bound, .. } => value <= *bound,
+        }
+    }
+}
+
+/// What a holder reveals in one presentation.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BbsPresentationRequest {
+    /// Claims to disclose in plain text.
+    #[serde(default)]
+    pub disclose: Vec<String>,
+    /// Predicates to prove over claims that stay hidden.
+    #[serde(default)]
+    pub predicates: Vec<BbsPredicate>,
+    /// Whether to reveal the holder DID. Revealing it makes presentations
+    /// linkable.
+    #[serde(default)]
+    pub disclose_holder: bool,
+    /// Verifier-supplied nonce that the proof is bound to.
+    pub nonce: String,
+}
+
+/// A credential signed with BBS, held by its subject.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BbsCredential {
+    /// DID of the issuer.
+    pub issuer: Did,
+    /// DID of the holder, signed as a message that stays hidden by default.
+    pub holder: Did,
+    /// Schema the claims follow.
+    pub schema: Cid,
+    /// Claim values. Canonical decimal `u64` strings are signed as numbers,
+    /// so range predicates can be proven over them.
+    pub claims: BTreeMap<String, String>,
+    /// Binding of the signing key to the issuer DID.
+    pub key_binding: BbsKeyBinding,
+    /// Signature `(A, e)`, compressed.
+    #[serde(with = "serde_bytes")]
+    pub signature: Vec<u8>,
+}
+
+impl BbsCredential {
+    /// Sign `claims` about `holder`.
+    pub fn issue(
+        issuer: Did,
+        holder: Did,
+        schema: Cid,
+        claims: BTreeMap<String, String>,
+        key: &BbsSecretKey,
+        key_binding: BbsKeyBinding,
+    ) -> Result<Self, CommonError> {
+        let public_key = key.public_key();
+        if key_binding.issuer != issuer || key_binding.public_key != public_key.to_bytes() {
+            return Err(bbs_error("key binding does not match the issuer key"));
+        }
+        let names: Vec<String> = claims.keys().cloned().collect();
+        let gens = Generators::new(names.len() + 1)?;
+        let domain = domain(&public_key, &gens, &issuer, &schema, &names);
+        let messages = messages(&holder, &claims);
+
+        let mut transcript = Transcript::new(SIGNATURE_DST);
+        transcript.append(&key.0);
+        transcript.append(&domain);
+        for m in &messages {
+            transcript.append(m);
+        }
+        let e = transcript.challenge();
+        let b = gens.b(domain, messages.iter().copied().enumerate());
+        let inverse = (key.0 + e)
+            .inverse()
+            .ok_or_else(|| bbs_error("degenerate signature"))?;
+        let a = (b * inverse).into_affine();
+
+        let mut signature = to_bytes(&a);
+        signature.extend(to_bytes(&e));
+        Ok(Self {
+            issuer,
+            holder,
+            schema,
+            claims,
+            key_binding,
+            signature,
+        })
+    }
+
+    /// Check the key binding and the signature.
+    pub fn verify(&self, issuer_key: &VerifyingKey) -> Result<(), CommonError> {
+        self.check_binding()?;
+        let public_key = self.key_binding.verify(issuer_key)?;
+        let (a, e) = self.decode_signature()?;
+        let names: Vec<String> = self.claims.keys().cloned().collect();
+        let gens = Generators::new(names.len() + 1)?;
+        let domain = domain(&public_key, &gens, &self.issuer, &self.schema, &names);
+        let b = gens.b(
+            domain,
+            messages(&self.holder, &self.claims).into_iter().enumerate(),
+        );
+        let w_e = G2Projective::from(public_key.0) + G2Projective::generator() * e;
+        if pairing_product_is_one(&[a, b.into_affine()], &[w_e, -G2Projective::generator()]) {
+            Ok(())
+        } else {
+            Err(bbs_error("invalid signature"))
+        }
+    }
+
+    /// Derive a fresh proof that reveals only what `request` asks for.
+    pub fn present(
+        &self,
+        request: &BbsPresentationRequest,
+    ) -> Result<BbsPresentation, CommonError> {
+        self.check_binding()?;
+        let public_key = BbsPublicKey::from_bytes(&self.key_binding.public_key)?;
+        let (a, e) = self.decode_signature()?;
+        let names: Vec<String> = self.claims.keys().cloned().collect();
+        let gens = Generators::new(names.len() + 1)?;
+        let domain = domain(&public_key, &gens, &self.issuer, &self.schema, &names);
+        let messages = messages(&self.holder, &self.claims);
+
+        let disclosed_set = disclosed_indexes(&names, &request.disclose, request.disclose_holder)?;
+        let ranges = predicate_indexes(&names, &request.predicates, &disclosed_set)?;
+        let hidden: Vec<usize> = (0..messages.len())
+            .filter(|i| !disclosed_set.contains(i))
+            .collect();
+
+        let rng = &mut OsRng;
+        let r1 = nonzero_scalar();
+        let r2 = nonzero_scalar();
+        let r3 = r2.inverse().expect("r2 is non-zero");
+        let (e_t, r1_t, r3_t) = (Fr::rand(rng), Fr::rand(rng), Fr::rand(rng));
+        let m_t: Vec<Fr> = hidden.iter().map(|_| Fr::rand(rng)).collect();
+
+        let b = gens.b(domain, messages.iter().copied().enumerate());
+        let abar = G1Projective::from(a) * (r1 * r2);
+        let d = b * r2;
+        let bbar = d * r1 - abar * e;
+        let t1 = abar * e_t + d * r1_t;
+        let t2 = d * r3_t
+            + hidden
+                .iter()
+                .zip(&m_t)
+                .map(|(&i, m)| gens.h[i] * m)
+                .sum::<G1Projective>();
+
+        let mut range_secrets = Vec::with_capacity(ranges.len());
+        for (predicate, index) in request.predicates.iter().zip(&ranges) {
+            let value = self.numeric_claim(&names[*index - 1])?;
+            if !predicate.holds_for(value) {
+                return Err(bbs_error(format!(
+                    "claim '{}' does not satisfy the predicate",
+                    predicate.claim()
+                )));
+            }
+            let slot = hidden.iter().position(|i| i == index).expect("hidden");
+            range_secrets.push(RangeSecrets::commit(
+                &gens,
+                predicate,
+                value,
+                messages[*index],
+                m_t[slot],
+            ));
+        }
+
+        let disclosed: Vec<(usize, Fr)> = disclosed_set.iter().map(|&i| (i, messages[i])).collect();
+        let mut transcript = challenge_transcript(
+            &abar.into_affine(),
+            &bbar.into_affine(),
+            &d.into_affine(),
+            &t1,
+            &t2,
+            domain,
+            &disclosed,
+            &request.predicates,
+            &ranges,
+            &request.nonce,
+        );
+        for range in &range_secrets {
+            range.append_to(&mut transcript);
+        }
+        let c = transcript.challenge();
+
+        let core = ProofCore {
+            abar: abar.into_affine(),
+            bbar: bbar.into_affine(),
+            d: d.into_affine(),
+            e_hat: e_t + e * c,
+            r1_hat: r1_t - r1 * c,
+            r3_hat: r3_t - r3 * c,
+            m_hat: hidden
+                .iter()
+                .zip(&m_t)
+                .map(|(&i, m)| *m + messages[i] * c)
+                .collect(),
+            c,
+            ranges: range_secrets.into_iter().map(|r| r.respond(c)).collect(),
+        };
+
+        Ok(BbsPresentation {
+            issuer: self.issuer.clone(),
+            schema: self.schema.clone(),
+            claim_names: names,
+            disclosed: request
+                .disclose
+                .iter()
+                .map(|name| (name.clone(), self.claims[name].clone()))
+                .collect(),
+            holder: request.disclose_holder.then(|| self.holder.clone()),
+            predicates: request.predicates.clone(),
+            nonce: request.nonce.clone(),
+            key_binding: self.key_binding.clone(),
+            proof: core.to_bytes(),
+        })
+    }
+
+    fn check_binding(&self) -> Result<(), CommonError> {
+        if self.key_binding.issuer != self.issuer {
+            return Err(bbs_error("key binding belongs to another issuer"));
+        }
+        Ok(())
+    }
+
+    fn decode_signature(&self) -> Result<(G1Affine, Fr), CommonError> {
+        let mut input = self.signature.as_slice();
+        let a: G1Affine = take(&mut input)?;
+        let e: Fr = take(&mut input)?;
+        if !input.is_empty() || a.is_zero() {
+            return Err(bbs_error("malformed signature"));
+        }
+        Ok((a, e))
+    }
+
+    fn numeric_claim(&self, name: &str) -> Result<u64, CommonError> {
+        let value = &self.claims[name];
+        canonical_u64(value)
+            .ok_or_else(|| bbs_error(format!("claim '{name}' is not a canonical integer")))
+    }
+}
+
+/// A derived proof over a [`BbsCredential`], bound to a verifier nonce.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BbsPresentation {
+    /// DID of the issuer.
+    pub issuer: Did,
+    /// Schema the claims follow.
+    pub schema: Cid,
+    /// Names of every signed claim, in signing order.
+    pub claim_names: Vec<String>,
+    /// Disclosed claims.
+    pub disclosed: BTreeMap<String, String>,
+    /// Holder DID, present only when the holder chose to reveal it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holder: Option<Did>,
+    /// Predicates proven over hidden claims.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub predicates: Vec<BbsPredicate>,
+    /// Verifier nonce the proof is bound to.
+    pub nonce: String,
+    /// Binding of the signing key to the issuer DID.
+    pub key_binding: BbsKeyBinding,
+    /// Proof of possession and predicate proofs.
+    #[serde(with = "serde_bytes")]
+    pub proof: Vec<u8>,
+}
+
+impl BbsPresentation {
+    /// Verify the proof against the issuer's DID key.
+    pub fn verify(&self, issuer_key: &VerifyingKey) -> Result<(), CommonError> {
+        if self.key_binding.issuer != self.issuer {
+            return Err(bbs_error("key binding belongs to another issuer"));
+        }
+        let public_key = self.key_binding.verify(issuer_key)?;
+        let names = &self.claim_names;
+        if names.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(bbs_error("claim names must be sorted and unique"));
+        }
+        let gens = Generators::new(names.len() + 1)?;
+        let domain = domain(&public_key, &gens, &self.issuer, &self.schema, names);
+
+        let disclose: Vec<String> = self.disclosed.keys().cloned().collect();
+        let disclosed_set = disclosed_indexes(names, &disclose, self.holder.is_some())?;
+        let ranges = predicate_indexes(names, &self.predicates, &disclosed_set)?;
+        let hidden: Vec<usize> = (0..=names.len())
+            .filter(|i| !disclosed_set.contains(i))
+            .collect();
+        let disclosed: Vec<(usize, Fr)> = disclosed_set
+            .iter()
+            .map(|&i| match (&self.holder, i) {
+                (Some(holder), 0) => (0, holder_message(holder)),
+                _ => (i, claim_message(&self.disclosed[&names[i - 1]])),
+            })
+            .collect();
+
+        let core = ProofCore::from_bytes(&self.proof, hidden.len(), ranges.len())?;
+        if core.abar.is_zero() {
+            return Err(bbs_error("degenerate proof"));
+        }
+        let c = core.c;
+        let bv = gens.b(domain, disclosed.iter().copied());
+        let t1 = core.bbar * c + core.abar * core.e_hat + core.d * core.r1_hat;
+        let t2 = bv * c
+            + core.d * core.r3_hat
+            + hidden
+                .iter()
+                .zip(&core.m_hat)
+                .map(|(&i, m)| gens.h[i] * m)
+                .sum::<G1Projective>();
+
+        let mut transcript = challenge_transcript(
+            &core.abar,
+            &core.bbar,
+            &core.d,
+            &t1,
+            &t2,
+            domain,
+            &disclosed,
+            &self.predicates,
+            &ranges,
+            &self.nonce,
+        );
+        for ((predicate, index), range) in self.predicates.iter().zip(&ranges).zip(&core.ranges) {
+            let slot = hidden.iter().position(|i| i == index).expect("hidden");
+            range.verify(&gens, predicate, core.m_hat[slot], c, &mut transcript)?;
+        }
+        if transcript.challenge() != c {
+            return Err(bbs_error("proof does not match its challenge"));
+        }
+
+        let g2 = G2Projective::generator();
+        if !pairing_product_is_one(&[core.abar, core.bbar], &[public_key.0.into(), -g2]) {
+            return Err(bbs_error("proof is not backed by an issuer signature"));
+        }
+        Ok(())
+    }
+
+    /// Resolve the issuer's DID key and verify the proof.
+    pub fn verify_with(&self, resolver: &dyn DidResolver) -> Result<(), CommonError> {
+        self.verify(&resolver.resolve(&self.issuer)?)
+    }
+
+    /// Wrap the presentation for the generic `/identity/verify` pipeline.
+    ///
+    /// Unless the holder disclosed their DID, the proof's `holder` is left
+    /// as an empty DID.
+    pub fn to_zk_proof(&self) -> Result<ZkCredentialProof, CommonError> {
+        Ok(ZkCredentialProof {
+            issuer: self.issuer.clone(),
+            holder: self.holder.clone().unwrap_or_default(),
+            claim_type: BBS_CLAIM_TYPE.to_string(),
+            proof: serde_json::to_vec(self)
+                .map_err(|e| CommonError::SerializationError(e.to_string()))?,
+            schema: self.schema.clone(),
+            vk_cid: None,
+            disclosed_fields: self.disclosed.keys().cloned().collect(),
+            challenge: Some(self.nonce.clone()),
+            backend: ZkProofType::Bbs,
+            verification_key: Some(self.key_binding.public_key.clone()),
+            public_inputs: Some(serde_json::json!({
+                "disclosed": self.disclosed,
+                "predicates": self.predicates,
+            })),
+        })
+    }
+
+    /// Unwrap a presentation from a [`ZkCredentialProof`], checking that the
+    /// outer fields agree with it.
+    pub fn from_zk_proof(proof: &ZkCredentialProof) -> Result<Self, CommonError> {
+        if proof.backend != ZkProofType::Bbs {
+            return Err(bbs_error(format!("unexpected backend {:?}", proof.backend)));
+        }
+        let presentation: Self = serde_json::from_slice(&proof.proof)
+            .map_err(|e| CommonError::DeserializationError(e.to_string()))?;
+        let disclosed: Vec<String> = presentation.disclosed.keys().cloned().collect();
+        let consistent = proof.issuer == presentation.issuer
+            && proof.schema == presentation.schema
+            && proof.challenge.as_deref() == Some(presentation.nonce.as_str())
+            && proof.holder == presentation.holder.clone().unwrap_or_default()
+            && proof.disclosed_fields == disclosed
+            && proof
+                .verification_key
+                .as_ref()
+                .is_none_or(|vk| *vk == presentation.key_binding.public_key);
+        if !consistent {
+            return Err(bbs_error("proof envelope does not match the presentation"));
+        }
+        Ok(presentation)
+    }
+}
+
+/// [`ZkVerifier`] for [`ZkProofType::Bbs`] proofs.
+///
+/// The issuer's DID is resolved to check the BBS key binding.
+#[derive(Clone)]
+pub struct BbsVerifier {
+    resolver: Arc<dyn DidResolver>,
+}
+
+impl BbsVerifier {
+    /// Verifier that resolves issuer keys through `resolver`.
+    pub fn new(resolver: Arc<dyn DidResolver>) -> Self {
+        Self { resolver }
+    }
+}
+
+impl Default for BbsVerifier {
+    fn default() -> Self {
+        Self::new(Arc::new(KeyDidResolver))
+    }
+}
+
+impl fmt::Debug for BbsVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BbsVerifier").finish_non_exhaustive()
+    }
+}
+
+impl ZkVerifier for BbsVerifier {
+    fn verify(&self, proof: &ZkCredentialProof) -> Result<bool, ZkError> {
+        if proof.backend != ZkProofType::Bbs {
+            return Err(ZkError::UnsupportedBackend(proof.backend.clone()));
+        }
+        let presentation =
+            BbsPresentation::from_zk_proof(proof).map_err(|_| ZkError::InvalidProof)?;
+        let result = presentation
+            .verify_with(self.resolver.as_ref())
+            .map(|_| true)
+            .map_err(|_| ZkError::VerificationFailed);
+        if result.is_ok() {
+            crate::metrics::PROOFS_VERIFIED.inc();
+        } else {
+            crate::metrics::PROOF_VERIFICATION_FAILURES.inc();
+        }
+        result
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Scheme internals
+// ---------------------------------------------------------------------------
+
+struct Generators {
+    p1: G1Projective,
+    q1: G1Projective,
+    /// One generator per message: the holder first, then each claim.
+    h: Vec<G1Projective>,
+    /// Pedersen generators for range predicates.
+    range_g: G1Projective,
+    range_h: G1Projective,
+}
+
+impl Generators {
+    fn new(count: usize) -> Result<Self, CommonError> {
+        let hasher = G1Hasher::new(GENERATOR_DST).map_err(bbs_error)?;
+        let point = |label: &[u8]| -> Result<G1Projective, CommonError> {
+            hasher.hash(label).map(Into::into).map_err(bbs_error)
+        };
+        Ok(Self {
+            p1: point(b"P1")?,
+            q1: point(b"Q1")?,
+            h: (0..count)
+                .map(|i| point(format!("H{i}").as_bytes()))
+                .collect::<Result<_, _>>()?,
+            range_g: point(b"RANGE-G")?,
+            range_h: point(b"RANGE-H")?,
+        })
+    }
+
+    /// `B = P1 + Q1·domain + Σ H_i·m_i` over the given messages.
+    fn b(&self, domain: Fr, messages: impl IntoIterator<Item = (usize, Fr)>) -> G1Projective {
+        messages
+            .into_iter()
+            .fold(self.p1 + self.q1 * domain, |acc, (i, m)| {
+                acc + self.h[i] * m
+            })
+    }
+}
+
+fn domain(
+    public_key: &BbsPublicKey,
+    gens: &Generators,
+    issuer: &Did,
+    schema: &Cid,
+    claim_names: &[String],
+) -> Fr {
+    let mut transcript = Transcript::new(DOMAIN_DST);
+    transcript.append(&public_key.0);
+    transcript.append(&gens.q1.into_affine());
+    for h in &gens.h {
+        transcript.append(&h.into_affine());
+    }
+    transcript.append_bytes(issuer.to_string().as_bytes());
+    transcript.append_bytes(schema.to_string().as_bytes());
+    for name in claim_names {
+        transcript.append_bytes(name.as_bytes());
+    }
+    transcript.challenge()
+}
+
+fn messages(holder: &Did, claims: &BTreeMap<String, String>) -> Vec<Fr> {
+    std::iter::once(holder_message(holder))
+        .chain(claims.values().map(|v| claim_message(v)))
+        .collect()
+}
+
+fn holder_message(holder: &Did) -> Fr {
+    hash_to_scalar(HOLDER_DST, holder.to_string().as_bytes())
+}
+
+fn claim_message(value: &str) -> Fr {
+    match canonical_u64(value) {
+        Some(n) => Fr::from(n),
+        None => hash_to_scalar(CLAIM_DST, value.as_bytes()),
+    }
+}
+
+fn canonical_u64(value: &str) -> Option<u64> {
+    value.parse::<u64>().ok().filter(|n| n.to_string() == value)
+}
+
+/// Message indexes revealed by a presentation (0 is the holder).
+fn disclosed_indexes(
+    names: &[String],
+    disclose: &[String],
+    disclose_holder: bool,
+) -> Result<BTreeSet<usize>, CommonError> {
+    let mut set = BTreeSet::new();
+    if disclose_holder {
+        set.insert(0);
+    }
+    for name in disclose {
+        let index = claim_index(names, name)?;
+        if !set.insert(index) {
+            return Err(bbs_error(format!("claim '{name}' disclosed twice")));
+        }
+    }
+    Ok(set)
+}
+
+/// Message indexes constrained by predicates, which must stay hidden.
+fn predicate_indexes(
+    names: &[String],
+    predicates: &[BbsPredicate],
+    disclosed: &BTreeSet<usize>,
+) -> Result<Vec<usize>, CommonError> {
+    predicates
+        .iter()
+        .map(|p| {
+            let index = claim_index(names, p.claim())?;
+            if disclosed.contains(&index) {
+                return Err(bbs_error(format!(
+                    "claim '{}' is disclosed; predicates apply to hidden claims",
+                    p.claim()
+                )));
+            }
+            Ok(index)
+        })
+        .collect()
+}
+
+fn claim_index(names: &[String], name: &str) -> Result<usize, CommonError> {
+    names
+        .binary_search_by(|n| n.as_str().cmp(name))
+        .map(|i| i + 1)
+        .map_err(|_| bbs_error(format!("unknown claim '{name}'")))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn challenge_transcript(
+    abar: &G1Affine,
+    bbar: &G1Affine,
+    d: &G1Affine,
+    t1: &G1Projective,
+    t2: &G1Projective,
+    domain: Fr,
+    disclosed: &[(usize, Fr)],
+    predicates: &[BbsPredicate],
+    ranges: &[usize],
+    nonce: &str,
+) -> Transcript {
+    let mut transcript = Transcript::new(CHALLENGE_DST);
+    transcript.append(abar);
+    transcript.append(bbar);
+    transcript.append(d);
+    transcript.append(&t1.into_affine());
+    transcript.append(&t2.into_affine());
+    transcript.append(&domain);
+    transcript.append_u64(disclosed.len() as u64);
+    for (i, m) in disclosed {
+        transcript.append_u64(*i as u64);
+        transcript.append(m);
+    }
+    for (predicate, index) in predicates.iter().zip(ranges) {
+        transcript.append_u64(*index as u64);
+        transcript.append_u64(predicate.is_lower_bound() as u64);
+        transcript.append_u64(predicate.bound());
+    }
+    transcript.append_bytes(nonce.as_bytes());
+    transcript
+}
+
+fn pairing_product_is_one(g1: &[G1Affine], g2: &[G2Projective]) -> bool {
+    let g2: Vec<G2Affine> = G2Projective::normalize_batch(g2);
+    Bls12_381::multi_pairing(g1.iter().copied(), g2).is_zero()
+}
+
+fn nonzero_scalar() -> Fr {
+    loop {
+        let x = Fr::rand(&mut OsRng);
+        if !x.is_zero() {
+            return x;
+        }
+    }
+}
+
+fn hash_to_scalar(dst: &[u8], bytes: &[u8]) -> Fr {
+    let mut transcript = Transcript::new(dst);
+    transcript.append_bytes(bytes);
+    transcript.challenge()
+}
+
+/// Length-prefixed SHA-512 transcript reduced to a scalar.
+struct Transcript(Sha512);
+
+impl Transcript {
+    fn new(dst: &[u8]) -> Self {
+        let mut transcript = Self(Sha512::new());
+        transcript.append_bytes(dst);
+        transcript
+    }
+
+    fn append_bytes(&mut self, bytes: &[u8]) {
+        self.0.update((bytes.len() as u64).to_be_bytes());
+        self.0.update(bytes);
+    }
+
+    fn append_u64(&mut self, value: u64) {
+        self.0.update(value.to_be_bytes());
+    }
+
+    fn append(&mut self, value: &impl CanonicalSerialize) {
+        self.append_bytes(&to_bytes(value));
+    }
+
+    fn challenge(self) -> Fr {
+        Fr::from_le_bytes_mod_order(&self.0.finalize())
+    }
+}
+
+fn to_bytes(value: &impl CanonicalSerialize) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(value.compressed_size());
+    value
+        .serialize_compressed(&mut bytes)
+        .expect("serializing into a Vec cannot fail");
+    bytes
+}
+
+fn take<T: CanonicalDeserialize>(input: &mut &[u8]) -> Result<T, CommonError> {
+    T::deserialize_compressed(input).map_err(|_| bbs_error("malformed proof encoding"))
+}
+
+struct ProofCore {
+    abar: G1Affine,
+    bbar: G1Affine,
+    d: G1Affine,
+    e_hat: Fr,
+    r1_hat: Fr,
+    r3_hat: Fr,
+    c: Fr,
+    m_hat: Vec<Fr>,
+    ranges: Vec<RangeProof>,
+}
+
+impl ProofCore {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        for point in [&self.abar, &self.bbar, &self.d] {
+            out.extend(to_bytes(point));
+        }
+        for scalar in [&self.e_hat, &self.r1_hat, &self.r3_hat, &self.c] {
+            out.extend(to_bytes(scalar));
+        }
+        for m in &self.m_hat {
+            out.extend(to_bytes(m));
+        }
+        for range in &self.ranges {
+            out.extend(to_bytes(&range.commitment));
+            out.extend(to_bytes(&range.r_hat));
+            for bit in &range.bits {
+                for point in [&bit.commitment, &bit.r0, &bit.r1] {
+                    out.extend(to_bytes(point));
+                }
+                for scalar in [&bit.c0, &bit.z0, &bit.z1] {
+                    out.extend(to_bytes(scalar));
+                }
+            }
+        }
+        out
+    }
+
+    fn from_bytes(bytes: &[u8], hidden: usize, ranges: usize) -> Result<Self, CommonError> {
+        let input = &mut &bytes[..];
+        let core = Self {
+            abar: take(input)?,
+            bbar: take(input)?,
+            d: take(input)?,
+            e_hat: take(input)?,
+            r1_hat: take(input)?,
+            r3_hat: take(input)?,
+            c: take(input)?,
+            m_hat: (0..hidden).map(|_| take(input)).collect::<Result<_, _>>()?,
+            ranges: (0..ranges)
+                .map(|_| {
+                    Ok(RangeProof {
+                        commitment: take(input)?,
+                        r_hat: take(input)?,
+                        bits: (0..RANGE_BITS)
+                            .map(|_| {
+                                Ok(BitProof {
+                                    commitment: take(input)?,
+                                    r0: take(input)?,
+                                    r1: take(input)?,
+                                    c0: take(input)?,
+                                    z0: take(input)?,
+                                    z1: take(input)?,
+                                })
+                            })
+                            .collect::<Result<_, CommonError>>()?,
+                    })
+                })
+                .collect::<Result<_, CommonError>>()?,
+        };
+        if !input.is_empty() {
+            return Err(bbs_error("trailing bytes after proof"));
+        }
+        Ok(core)
+    }
+}
+
+/// Proof that a committed hidden claim lies on one side of a bound.
+struct RangeProof {
+    /// Pedersen commitment `C = G·m + H·r` to the claim.
+    commitment: G1Affine,
+    r_hat: Fr,
+    bits: Vec<BitProof>,
+}
+
+/// OR-proof that a bit commitment opens to 0 or 1.
+struct BitProof {
+    commitment: G1Affine,
+    r0: G1Affine,
+    r1: G1Affine,
+    c0: Fr,
+    z0: Fr,
+    z1: Fr,
+}
+
+struct RangeSecrets {
+    commitment: G1Projective,
+    /// `T_C = G·m~ + H·r~`, sharing `m~` with the signature proof.
+    link: G1Projective,
+    r: Fr,
+    r_t: Fr,
+    bits: Vec<BitSecrets>,
+}
+
+struct BitSecrets {
+    commitment: G1Projective,
+    bit: bool,
+    blinding: Fr,
+    nonce: Fr,
+    fake_c: Fr,
+    fake_z: Fr,
+    r: [G1Projective; 2],
+}
+
+impl RangeSecrets {
+    fn commit(gens: &Generators, predicate: &BbsPredicate, value: u64, m: Fr, m_t: Fr) -> Self {
+        let rng = &mut OsRng;
+        let (g, h) = (gens.range_g, gens.range_h);
+        let r = Fr::rand(rng);
+        let r_t = Fr::rand(rng);
+        let (distance, target) = if predicate.is_lower_bound() {
+            (value - predicate.bound(), r)
+        } else {
+            (predicate.bound() - value, -r)
+        };
+
+        // Bit blindings weighted by powers of two must sum to the blinding of
+        // the shifted commitment, so the last one is solved for.
+        let mut blindings: Vec<Fr> = (0..RANGE_BITS - 1).map(|_| Fr::rand(rng)).collect();
+        let partial: Fr = blindings.iter().enumerate().map(|(k, s)| pow2(k) * s).sum();
+        let last_weight = pow2(RANGE_BITS - 1).inverse().expect("non-zero");
+        blindings.push((target - partial) * last_weight);
+
+        let bits = blindings
+            .into_iter()
+            .enumerate()
+            .map(|(k, blinding)| {
+                let bit = (distance >> k) & 1 == 1;
+                let commitment = if bit { g + h * blinding } else { h * blinding };
+                let nonce = Fr::rand(rng);
+                let fake_c = Fr::rand(rng);
+                let fake_z = Fr::rand(rng);
+                let real = h * nonce;
+                let fake_y = if bit { commitment } else { commitment - g };
+                let fake = h * fake_z - fake_y * fake_c;
+                let r = if bit { [fake, real] } else { [real, fake] };
+                BitSecrets {
+                    commitment,
+                    bit,
+                    blinding,
+                    nonce,
+                    fake_c,
+                    fake_z,
+                    r,
+                }
+            })
+            .collect();
+
+        Self {
+            commitment: g * m + h * r,
+            link: g * m_t + h * r_t,
+            r,
+            r_t,
+            bits,
+        }
+    }
+
+    fn append_to(&self, transcript: &mut Transcript) {
+        append_range(
+            transcript,
+            &self.commitment.into_affine(),
+            &self.link,
+            self.bits.iter().map(|b| {
+                (
+                    b.commitment.into_affine(),
+                    b.r[0].into_affine(),
+                    b.r[1].into_affine(),
+                )
+            }),
+        );
+    }
+
+    fn respond(self, c: Fr) -> RangeProof {
+        RangeProof {
+            commitment: self.commitment.into_affine(),
+            r_hat: self.r_t + self.r * c,
+            bits: self
+                .bits
+                .into_iter()
+                .map(|b| {
+                    let real_c = c - b.fake_c;
+                    let real_z = b.nonce + real_c * b.blinding;
+                    let (c0, z0, z1) = if b.bit {
+                        (b.fake_c, b.fake_z, real_z)
+                    } else {
+                        (real_c, real_z, b.fake_z)
+                    };
+                    BitProof {
+                        commitment: b.commitment.into_affine(),
+                        r0: b.r[0].into_affine(),
+                        r1: b.r[1].into_affine(),
+                        c0,
+                        z0,
+                        z1,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl RangeProof {
+    fn verify(
+        &self,
+        gens: &Generators,
+        predicate: &BbsPredicate,
+        m_hat: Fr,
+        c: Fr,
+        transcript: &mut Transcript,
+    ) -> Result<(), CommonError> {
+        let (g, h) = (gens.range_g, gens.range_h);
+        let commitment = G1Projective::from(self.commitment);
+        let bound = g * Fr::from(predicate.bound());
+        let shifted = if predicate.is_lower_bound() {
+            commitment - bound
+        } else {
+            bound - commitment
+        };
+        let recombined: G1Projective = self
+            .bits
+            .iter()
+            .enumerate()
+            .map(|(k, b)| b.commitment * pow2(k))
+            .sum();
+        if recombined != shifted {
+            return Err(bbs_error("range proof bits do not recombine"));
+        }
+        for bit in &self.bits {
+            let y0 = G1Projective::from(bit.commitment);
+            let y1 = y0 - g;
+            let c1 = c - bit.c0;
+            if h * bit.z0 != y0 * bit.c0 + bit.r0 || h * bit.z1 != y1 * c1 + bit.r1 {
+                return Err(bbs_error("range proof bit is not 0 or 1"));
+            }
+        }
+        let link = g * m_hat + h * self.r_hat - commitment * c;
+        append_range(
+            transcript,
+            &self.commitment,
+            &link,
+            self.bits.iter().map(|b| (b.commitment, b.r0, b.r1)),
+        );
+        Ok(())
+    }
+}
+
+fn append_range(
+    transcript: &mut Transcript,
+    commitment: &G1Affine,
+    link: &G1Projective,
+    bits: impl Iterator<Item = (G1Affine, G1Affine, G1Affine)>,
+) {
+    transcript.append(commitment);
+    transcript.append(&link.into_affine());
+    for (commitment, r0, r1) in bits {
+        transcript.append(&commitment);
+        transcript.append(&r0);
+        transcript.append(&r1);
+    }
+}
+
+fn pow2(k: usize) -> Fr {
+    Fr::from(2u64).pow([k as u64])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{did_key_from_verifying_key, generate_ed25519_keypair};
+    use std::str::FromStr;
+
+    fn issue(claims: &[(&str, &str)]) -> (BbsCredential, VerifyingKey) {
+        let (sk, pk) = generate_ed25519_keypair();
+        let issuer = Did::from_str(&did_key_from_verifying_key(&pk)).unwrap();
+        let bbs_key = BbsSecretKey::generate();
+        let binding = BbsKeyBinding::sign(issuer.clone(), &bbs_key.public_key(), &sk);
+        let credential = BbsCredential::issue(
+            issuer,
+            Did::new("key", "holder"),
+            Cid::new_v1_sha256(0x55, b"membership-schema"),
+            claims
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            &bbs_key,
+            binding,
+        )
+        .unwrap();
+        (credential, pk)
+    }
+
+    #[test]
+    fn presentations_disclose_selected_claims_and_are_unlinkable() {
+        let (credential, issuer_key) = issue(&[
+            ("cooperative", "bakers"),
+            ("role", "steward"),
+            ("since", "2019"),
+        ]);
+        credential.verify(&issuer_key).unwrap();
+
+        let request = BbsPresentationRequest {
+            disclose: vec!["cooperative".into()],
+            nonce: "n-1".into(),
+            ..Default::default()
+        };
+        let first = credential.present(&request).unwrap();
+        let second = credential.present(&request).unwrap();
+        first.verify(&issuer_key).unwrap();
+        second.verify(&issuer_key).unwrap();
+        assert_eq!(first.disclosed.len(), 1);
+        assert!(first.holder.is_none());
+        assert_ne!(first.proof, second.proof);
+
+        // Tampering with a disclosed value or replaying under another nonce fails.
+        let mut forged = first.clone();
+        forged
+            .disclosed
+            .insert("cooperative".into(), "brewers".into());
+        assert!(forged.verify(&issuer_key).is_err());
+        let mut replayed = first.clone();
+        replayed.nonce = "n-2".into();
+        assert!(replayed.verify(&issuer_key).is_err());
+
+        // The generic verifier accepts the wrapped proof.
+        let zk = first.to_zk_proof().unwrap();
+        assert_eq!(zk.backend, ZkProofType::Bbs);
+        assert!(BbsVerifier::default().verify(&zk).unwrap());
+    }
+
+    #[test]
+    fn range_predicates_hold_over_hidden_claims() {
+        let (credential, issuer_key) = issue(&[("age", "34"), ("name", "ada")]);
+        let request = BbsPresentationRequest {
+            predicates: vec![
+                BbsPredicate::AtLeast {
+                    claim: "age".into(),
+                    bound: 18,
+                },
+                BbsPredicate::AtMost {
+                    claim: "age".into(),
+                    bound: 34,
+                },
+            ],
+            nonce: "verifier".into(),
+            ..Default::default()
+        };
+        let presentation = credential.present(&request).unwrap();
+        assert!(presentation.disclosed.is_empty());
+        presentation.verify(&issuer_key).unwrap();
+
+        // Claiming a stronger bound than was proven fails.
+        let mut stretched = presentation.clone();
+        stretched.predicates[0] = BbsPredicate::AtLeast {
+            claim: "age".into(),
+            bound: 40,
+        };
+        assert!(stretched.verify(&issuer_key).is_err());
+
+        // A holder cannot prove a predicate the claim does not satisfy.
+        let false_request = BbsPresentationRequest {
+            predicates: vec![BbsPredicate::AtLeast {
+                claim: "age".into(),
+                bound: 35,
+            }],
+            nonce: "verifier".into(),
+            ..Default::default()
+        };
+        assert!(credential.present(&false_request).is_err());
+    }
+
+    #[test]
+    fn key_binding_must_come_from_the_issuer() {
+        let (credential, _) = issue(&[("role", "member")]);
+        let (_, other_key) = generate_ed25519_keypair();
+        assert!(credential.verify(&other_key).is_err());
+        let presentation = credential
+            .present(&BbsPresentationRequest {
+                disclose: vec!["role".into()],
+                nonce: "x".into(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(presentation.verify(&other_key).is_err());
+    }
+}
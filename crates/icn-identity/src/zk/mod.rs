@@ -18,6 +18,11 @@ use std::sync::Mutex;
 #[cfg(test)]
 static CACHE_TEST_MUTEX: Mutex<()> = Mutex::new(());
 
+pub mod bbs;
+pub use bbs::{
+    BbsCredential, BbsKeyBinding, BbsPredicate, BbsPresentation, BbsPresentationRequest,
+    BbsPublicKey, BbsSecretKey, BbsVerifier,
+};
 pub mod key_manager;
 pub use key_manager::Groth16KeyManager;
 pub mod proof_cache;
@@ -67,6 +67,12 @@ pub const SERVICE_AD_PREFIX: &str = "/icn/service/";
 /// `/icn/did/did:web:example.com`.
 pub const DID_DOC_PREFIX: &str = "/icn/did/";
 
+/// Prefix for DIDComm route records stored in the DHT.
+///
+/// Keys are constructed as `format!("{DIDCOMM_ROUTE_PREFIX}{did}")` and hold a
+/// `DIDCommMessaging` service entry naming the mediator for `did`.
+pub const DIDCOMM_ROUTE_PREFIX: &str = "/icn/didcomm/";
+
 /// Prefix for federation info records stored in the DHT.
 pub const FEDERATION_INFO_PREFIX: &str = "/icn/fedinfo/";
 
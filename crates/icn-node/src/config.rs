@@ -54,6 +54,17 @@ pub struct StorageConfig {
     pub mana_ledger_path: PathBuf,
     pub reputation_db_path: PathBuf,
     pub governance_db_path: PathBuf,
+    /// File backing the credential status lists issued by this node.
+    pub status_list_path: PathBuf,
+    /// File backing guardian recovery configurations and cases.
+    pub recovery_path: PathBuf,
+    /// File backing the DIDComm mailbox: mediated recipients, queued
+    /// envelopes and received messages.
+    pub didcomm_path: PathBuf,
+    /// File backing credential issuer policies and open issuance offers.
+    pub issuance_path: PathBuf,
+    /// File backing personhood seeds, vouches and verification records.
+    pub personhood_path: PathBuf,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,8 +76,16 @@ pub struct IdentityConfig {
     pub node_private_key_path: PathBuf,
     pub key_path: Option<PathBuf>,
     pub key_passphrase_env: Option<String>,
+    /// Label of the node key in the keystore at `key_path`.
+    pub key_label: Option<String>,
+    /// PKCS#11 module holding the node key.
     pub hsm_library: Option<PathBuf>,
+    /// Label of the node key inside the token.
     pub hsm_key_id: Option<String>,
+    /// Token slot; the first initialized token when unset.
+    pub hsm_slot: Option<u64>,
+    /// Environment variable holding the token user PIN.
+    pub hsm_pin_env: Option<String>,
     /// Additional trusted credential issuer DIDs
     pub trusted_credential_issuers: Vec<String>,
 }
@@ -215,6 +234,11 @@ impl Default for StorageConfig {
             mana_ledger_path: "./icn_data/mana_ledger.json".into(),
             reputation_db_path: "./icn_data/reputation.sled".into(),
             governance_db_path: "./icn_data/governance_db".into(),
+            status_list_path: "./icn_data/status_lists.json".into(),
+            recovery_path: "./icn_data/recovery.json".into(),
+            didcomm_path: "./icn_data/didcomm.json".into(),
+            issuance_path: "./icn_data/issuance.json".into(),
+            personhood_path: "./icn_data/personhood.json".into(),
         }
     }
 }
@@ -228,8 +252,11 @@ impl Default for IdentityConfig {
             node_private_key_path: "./icn_data/node_sk.bs58".into(),
             key_path: None,
             key_passphrase_env: None,
+            key_label: None,
             hsm_library: None,
             hsm_key_id: None,
+            hsm_slot: None,
+            hsm_pin_env: None,
             trusted_credential_issuers: Vec::new(),
         }
     }
@@ -304,7 +331,10 @@ impl NodeConfig {
     }
 
     /// Override configuration values with `ICN_*` environment variables.
-    pub fn apply_env_overrides(&mut self) {
+    ///
+    /// Fails when `ICN_HSM_SLOT` is not a slot number, rather than silently
+    /// signing with the key of another slot.
+    pub fn apply_env_overrides(&mut self) -> Result<(), CommonError> {
         macro_rules! set_from_env {
             ($target:expr, $var:expr, $parse:expr) => {
                 if let Ok(val) = std::env::var($var) {
@@ -355,6 +385,21 @@ impl NodeConfig {
         if let Ok(val) = std::env::var("ICN_GOVERNANCE_DB_PATH") {
             self.storage.governance_db_path = val.into();
         }
+        if let Ok(val) = std::env::var("ICN_STATUS_LIST_PATH") {
+            self.storage.status_list_path = val.into();
+        }
+        if let Ok(val) = std::env::var("ICN_RECOVERY_PATH") {
+            self.storage.recovery_path = val.into();
+        }
+        if let Ok(val) = std::env::var("ICN_DIDCOMM_PATH") {
+            self.storage.didcomm_path = val.into();
+        }
+        if let Ok(val) = std::env::var("ICN_ISSUANCE_PATH") {
+            self.storage.issuance_path = val.into();
+        }
+        if let Ok(val) = std::env::var("ICN_PERSONHOOD_PATH") {
+            self.storage.personhood_path = val.into();
+        }
         if let Ok(val) = std::env::var("ICN_HTTP_LISTEN_ADDR") {
             self.http.http_listen_addr = val;
         }
@@ -376,12 +421,24 @@ impl NodeConfig {
         if let Ok(val) = std::env::var("ICN_KEY_PASSPHRASE_ENV") {
             self.identity.key_passphrase_env = Some(val);
         }
+        if let Ok(val) = std::env::var("ICN_KEY_LABEL") {
+            self.identity.key_label = Some(val);
+        }
         if let Ok(val) = std::env::var("ICN_HSM_LIBRARY") {
             self.identity.hsm_library = Some(val.into());
         }
         if let Ok(val) = std::env::var("ICN_HSM_KEY_ID") {
             self.identity.hsm_key_id = Some(val);
         }
+        if let Ok(val) = std::env::var("ICN_HSM_SLOT") {
+            let slot = val.parse::<u64>().map_err(|e| {
+                CommonError::ConfigError(format!("invalid ICN_HSM_SLOT {val:?}: {e}"))
+            })?;
+            self.identity.hsm_slot = Some(slot);
+        }
+        if let Ok(val) = std::env::var("ICN_HSM_PIN_ENV") {
+            self.identity.hsm_pin_env = Some(val);
+        }
         if let Ok(val) = std::env::var("ICN_TRUSTED_ISSUERS") {
             self.identity.trusted_credential_issuers =
                 val.split(',').map(|s| s.to_string()).collect();
@@ -437,6 +494,7 @@ impl NodeConfig {
                 self.p2p.peer_discovery_interval_secs = interval;
             }
         }
+        Ok(())
     }
 
     /// Apply CLI overrides onto this configuration.
@@ -480,12 +538,21 @@ impl NodeConfig {
         if let Some(v) = &cli.key_passphrase_env {
             self.identity.key_passphrase_env = Some(v.clone());
         }
+        if let Some(v) = &cli.key_label {
+            self.identity.key_label = Some(v.clone());
+        }
         if let Some(v) = &cli.hsm_library {
             self.identity.hsm_library = Some(v.clone());
         }
         if let Some(v) = &cli.hsm_key_id {
             self.identity.hsm_key_id = Some(v.clone());
         }
+        if let Some(v) = cli.hsm_slot {
+            self.identity.hsm_slot = Some(v);
+        }
+        if let Some(v) = &cli.hsm_pin_env {
+            self.identity.hsm_pin_env = Some(v.clone());
+        }
         if !cli.trusted_issuers.is_empty() {
             self.identity.trusted_credential_issuers = cli.trusted_issuers.clone();
         }
@@ -553,6 +620,21 @@ impl NodeConfig {
         if let Some(parent) = self.storage.governance_db_path.parent() {
             fs::create_dir_all(parent)?;
         }
+        if let Some(parent) = self.storage.status_list_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if let Some(parent) = self.storage.recovery_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if let Some(parent) = self.storage.didcomm_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if let Some(parent) = self.storage.issuance_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if let Some(parent) = self.storage.personhood_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
         if let Some(parent) = self.identity.node_did_path.parent() {
             fs::create_dir_all(parent)?;
         }
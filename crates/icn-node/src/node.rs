@@ -17,15 +17,29 @@ use crate::circuit_registry::CircuitRegistry;
 use crate::parameter_store::ParameterStore;
 use dashmap::DashSet;
 use icn_api::governance_trait::{
-    CastVoteRequest as ApiCastVoteRequest, DelegateRequest as ApiDelegateRequest,
+    AdoptCrossFederationRequest as ApiAdoptCrossFederationRequest,
+    AmendmentVoteRequest as ApiAmendmentVoteRequest, CastVoteRequest as ApiCastVoteRequest,
+    CommentRequest as ApiCommentRequest, ConvictionStakeRequest as ApiConvictionStakeRequest,
+    ConvictionStatusResponse, DelegateRequest as ApiDelegateRequest, DelegationEdge,
+    EnableSecretBallotRequest as ApiEnableSecretBallotRequest, ParameterChangeView,
+    ParameterRollbackRequest as ApiParameterRollbackRequest, ParameterView, ProposalDelegationView,
+    ProposeAmendmentRequest as ApiProposeAmendmentRequest,
+    ReviseProposalRequest as ApiReviseProposalRequest,
     RevokeDelegationRequest as ApiRevokeDelegationRequest,
-    SubmitProposalRequest as ApiSubmitProposalRequest,
+    SubmitConvictionProposalRequest as ApiSubmitConvictionProposalRequest,
+    SubmitProposalRequest as ApiSubmitProposalRequest, TagProposalRequest as ApiTagProposalRequest,
+    UpgradeStatusView, UpgradeSummary, VoterCommitmentRequest as ApiVoterCommitmentRequest,
 };
 use icn_api::{
     get_dag_metadata,
     identity_trait::{
-        BatchVerificationResponse, CredentialResponse, DisclosureRequest, DisclosureResponse,
-        IssueCredentialRequest, RevokeCredentialRequest, VerificationResponse, VerifyProofsRequest,
+        ApproveRecoveryRequest, BatchVerificationResponse, CancelRecoveryRequest,
+        CredentialOfferReply, CredentialOfferRequest, CredentialResponse, DisclosureRequest,
+        DisclosureResponse, ExportCredentialRequest, ExportCredentialResponse,
+        ImportCredentialRequest, IssueCredentialRequest, MediationAllowRequest, MediatorRequest,
+        OpenRecoveryRequest, PersonhoodRequest, PersonhoodResponse, ProposalInvitationRequest,
+        PublishSchemaRequest, RevokeCredentialRequest, RevokePersonhoodRequest, SchemaResponse,
+        SendDidCommRequest, SendDidCommResponse, VerificationResponse, VerifyProofsRequest,
     },
     query_data, submit_transaction,
 };
@@ -52,7 +66,7 @@ use icn_protocol::{
 };
 use icn_protocol::{MessagePayload, ProtocolMessage};
 use icn_runtime::context::{
-    DefaultMeshNetworkService, Ed25519Signer, LocalMeshSubmitReceiptMessage,
+    DefaultMeshNetworkService, Ed25519Signer, LocalMeshSubmitReceiptMessage, MeshNetworkService,
     MeshNetworkServiceType, ResourceAction, ResourceLedgerEntry, RuntimeContext, Signer,
     StubMeshNetworkService,
 };
@@ -78,7 +92,7 @@ use chrono;
 use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser};
 use futures_util::{sink::SinkExt, stream::StreamExt};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::net::SocketAddr;
 use std::path::PathBuf;
@@ -301,14 +315,26 @@ pub struct Cli {
     #[clap(long)]
     pub key_passphrase_env: Option<String>,
 
-    /// Path to an HSM library for key retrieval.
+    /// Label of the node key within the keystore at `key_path`.
+    #[clap(long)]
+    pub key_label: Option<String>,
+
+    /// Path to a PKCS#11 module holding the node key.
     #[clap(long)]
     pub hsm_library: Option<PathBuf>,
 
-    /// Identifier of the key within the HSM.
+    /// Label of the node key within the HSM.
     #[clap(long)]
     pub hsm_key_id: Option<String>,
 
+    /// HSM slot holding the node key.
+    #[clap(long)]
+    pub hsm_slot: Option<u64>,
+
+    /// Environment variable name containing the HSM user PIN.
+    #[clap(long)]
+    pub hsm_pin_env: Option<String>,
+
     /// Trusted issuer DID(s) for credential verification
     #[clap(long = "trusted-issuer", value_delimiter = ',')]
     pub trusted_issuers: Vec<String>,
@@ -375,13 +401,20 @@ pub struct Cli {
 /// Load or generate the node identity based on the provided configuration.
 pub fn load_or_generate_identity(
     config: &mut NodeConfig,
-) -> Result<(icn_runtime::context::Ed25519Signer, String), CommonError> {
+) -> Result<(Arc<dyn icn_runtime::context::Signer>, String), CommonError> {
     if let (Some(lib), Some(key_id)) = (&config.identity.hsm_library, &config.identity.hsm_key_id) {
-        let hsm = icn_runtime::context::signers::ExampleHsm::with_key(lib, key_id.clone());
-        let signer = icn_runtime::context::Ed25519Signer::from_hsm(&hsm)?;
+        let env_name = config
+            .identity
+            .hsm_pin_env
+            .as_deref()
+            .unwrap_or("ICN_HSM_PIN");
+        let pin = std::env::var(env_name)
+            .map_err(|_| CommonError::ConfigError(format!("missing HSM PIN env {env_name}")))?;
+        let token = icn_runtime::context::Pkcs11Token::open(lib, config.identity.hsm_slot, &pin)?;
+        let signer = token.signer(key_id)?;
         let did_str = signer.did().to_string();
         config.identity.node_did = Some(did_str.clone());
-        return Ok((signer, did_str));
+        return Ok((Arc::new(signer), did_str));
     }
     if let Some(path) = &config.identity.key_path {
         let env_name = config
@@ -392,11 +425,31 @@ pub fn load_or_generate_identity(
         let passphrase = std::env::var(env_name).map_err(|_| {
             CommonError::ConfigError(format!("missing key passphrase env {env_name}"))
         })?;
-        let signer =
-            icn_runtime::context::Ed25519Signer::from_encrypted_file(path, passphrase.as_bytes())?;
-        let did_str = signer.did().to_string();
+        let label = config
+            .identity
+            .key_label
+            .as_deref()
+            .unwrap_or(icn_identity::DEFAULT_KEY_LABEL);
+        if path.exists() && !icn_identity::Keystore::is_keystore(path)? {
+            warn!(
+                "{} uses the legacy encrypted key format; run `icn-cli keys migrate` to convert it",
+                path.display()
+            );
+            let signer = icn_runtime::context::Ed25519Signer::from_encrypted_file(
+                path,
+                passphrase.as_bytes(),
+            )?;
+            let did_str = signer.did().to_string();
+            config.identity.node_did = Some(did_str.clone());
+            return Ok((Arc::new(signer), did_str));
+        }
+        let sk = open_node_keystore(config, path, passphrase.as_bytes(), label)?;
+        let did_str = did_key_from_verifying_key(&sk.verifying_key());
         config.identity.node_did = Some(did_str.clone());
-        return Ok((signer, did_str));
+        return Ok((
+            Arc::new(icn_runtime::context::Ed25519Signer::new(sk)),
+            did_str,
+        ));
     }
     if let (Some(did_str), Some(sk_bs58)) = (
         config.identity.node_did.clone(),
@@ -411,7 +464,7 @@ pub fn load_or_generate_identity(
         let sk = icn_identity::SigningKey::from_bytes(&sk_array);
         let pk = sk.verifying_key();
         Ok((
-            icn_runtime::context::Ed25519Signer::new_with_keys(sk, pk),
+            Arc::new(icn_runtime::context::Ed25519Signer::new_with_keys(sk, pk)),
             did_str,
         ))
     } else if config.identity.node_did_path.exists()
@@ -436,7 +489,7 @@ pub fn load_or_generate_identity(
         config.identity.node_did = Some(did_str.clone());
         config.identity.node_private_key_bs58 = Some(sk_bs58);
         Ok((
-            icn_runtime::context::Ed25519Signer::new_with_keys(sk, pk),
+            Arc::new(icn_runtime::context::Ed25519Signer::new_with_keys(sk, pk)),
             did_str,
         ))
     } else {
@@ -452,12 +505,65 @@ pub fn load_or_generate_identity(
         config.identity.node_did = Some(did_str.clone());
         config.identity.node_private_key_bs58 = Some(sk_bs58);
         Ok((
-            icn_runtime::context::Ed25519Signer::new_with_keys(sk, pk),
+            Arc::new(icn_runtime::context::Ed25519Signer::new_with_keys(sk, pk)),
             did_str,
         ))
     }
 }
 
+/// Unlock the node key in the keystore at `path`, creating the keystore on
+/// first use. A plaintext key at `node_private_key_path` is migrated into
+/// the new keystore and then deleted; otherwise a fresh key is generated.
+fn open_node_keystore(
+    config: &NodeConfig,
+    path: &std::path::Path,
+    passphrase: &[u8],
+    label: &str,
+) -> Result<icn_identity::SigningKey, CommonError> {
+    let plaintext = &config.identity.node_private_key_path;
+    if path.exists() {
+        if plaintext.exists() {
+            warn!(
+                "Plaintext node key {} is still present next to keystore {}; delete it",
+                plaintext.display(),
+                path.display()
+            );
+        }
+        return icn_identity::Keystore::open(path, passphrase)?.signing_key(label);
+    }
+    let mut keystore = icn_identity::Keystore::create(path, passphrase)?;
+    if plaintext.exists() {
+        let sk_bs58 = fs::read_to_string(plaintext)
+            .map_err(|e| CommonError::IoError(format!("Failed to read key file: {e}")))?;
+        let sk_array: [u8; 32] = bs58::decode(sk_bs58.trim())
+            .into_vec()
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| CommonError::IdentityError("Invalid base58 private key".into()))?;
+        keystore.add(label, &icn_identity::SigningKey::from_bytes(&sk_array))?;
+        fs::remove_file(plaintext).map_err(|e| {
+            CommonError::IoError(format!(
+                "Migrated {} into keystore {} but could not delete it: {e}",
+                plaintext.display(),
+                path.display()
+            ))
+        })?;
+        info!(
+            "Migrated {} into keystore {} and deleted the plaintext key file",
+            plaintext.display(),
+            path.display()
+        );
+    } else {
+        keystore.generate(label)?;
+        info!(
+            "Generated node key {} in keystore {}",
+            label,
+            path.display()
+        );
+    }
+    keystore.signing_key(label)
+}
+
 // --- Supporting Types ---
 
 #[derive(Deserialize)]
@@ -540,7 +646,11 @@ struct AppState {
     parameter_store: Option<Arc<TokioMutex<ParameterStore>>>,
     circuit_registry: Arc<TokioMutex<CircuitRegistry>>,
     credential_store: icn_identity::InMemoryCredentialStore,
-    revocation_registry: icn_identity::InMemoryRevocationRegistry,
+    revocation_registry: icn_identity::StatusListRegistry,
+    recovery_registry: icn_identity::SocialRecoveryRegistry,
+    didcomm_mailbox: icn_identity::DidCommMailbox,
+    issuance_registry: icn_identity::IssuanceRegistry,
+    personhood_registry: icn_identity::PersonhoodRegistry,
     trusted_issuers: std::collections::HashMap<Did, icn_identity::VerifyingKey>,
     paused_credentials: DashSet<Cid>,
     frozen_reputations: DashSet<Did>,
@@ -548,6 +658,215 @@ struct AppState {
     cooperative_registry: Arc<CooperativeRegistry>,
 }
 
+/// How often the governance lifecycle scheduler checks for due steps.
+const GOVERNANCE_SCHEDULER_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How often vouched personhood is re-evaluated.
+const PERSONHOOD_REVERIFICATION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Drive proposal deadlines in the background and announce every status
+/// change to WebSocket subscribers.
+async fn spawn_governance_scheduler(
+    rt_ctx: Arc<RuntimeContext>,
+    ws_broadcaster: broadcast::Sender<WebSocketEvent>,
+) {
+    rt_ctx
+        .spawn_governance_scheduler(
+            GOVERNANCE_SCHEDULER_INTERVAL,
+            move |transition, proposal| {
+                let (yes, no, abstain) =
+                    proposal
+                        .votes
+                        .values()
+                        .fold((0u64, 0u64, 0u64), |(yes, no, abstain), vote| {
+                            match vote.option {
+                                icn_governance::VoteOption::Yes => (yes + 1, no, abstain),
+                                icn_governance::VoteOption::No => (yes, no + 1, abstain),
+                                icn_governance::VoteOption::Abstain => (yes, no, abstain + 1),
+                            }
+                        });
+                // Sending only fails when nobody is subscribed.
+                let _ = ws_broadcaster.send(WebSocketEvent::ProposalStatusChanged {
+                    proposal_id: transition.proposal_id.to_string(),
+                    status: format!("{:?}", transition.to),
+                    votes: serde_json::json!({
+                        "yes": yes,
+                        "no": no,
+                        "abstain": abstain,
+                    }),
+                });
+            },
+        )
+        .await;
+}
+
+/// Issue membership credentials to accepted applicants and add their nodes
+/// to the federation's peers.
+fn spawn_membership_onboarding(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(GOVERNANCE_SCHEDULER_INTERVAL);
+        loop {
+            interval.tick().await;
+            let onboarded = match state.runtime_context.onboard_accepted_members().await {
+                Ok(onboarded) => onboarded,
+                Err(e) => {
+                    error!("Membership onboarding failed: {}", e);
+                    continue;
+                }
+            };
+            for application in onboarded {
+                if let Some(credential) = application.credential {
+                    match serde_json::to_vec(&credential) {
+                        Ok(bytes) => {
+                            let cid = Cid::new_v1_sha256(0x71, &bytes);
+                            state.credential_store.insert(cid.clone(), credential);
+                            state.revocation_registry.record(cid);
+                        }
+                        Err(e) => error!("Failed to encode membership credential: {}", e),
+                    }
+                }
+                if let Some(peer) = application.application.node_peer {
+                    {
+                        let mut peers = state.peers.lock().await;
+                        if !peers.contains(&peer) {
+                            peers.push(peer.clone());
+                        }
+                    }
+                    let mut cfg = state.config.lock().await;
+                    if !cfg.federation_peers.contains(&peer) {
+                        cfg.federation_peers.push(peer.clone());
+                    }
+                    info!(target: "audit", "member_onboarded did={} peer={}", application.application.applicant, peer);
+                }
+            }
+        }
+    });
+}
+
+/// Pause the credentials of sanctioned members while their sanction is in
+/// force and revoke them on removal. Only credentials this node paused are
+/// resumed on reinstatement.
+fn spawn_sanction_enforcement(state: AppState) {
+    tokio::spawn(async move {
+        let mut paused_by_sanction: HashSet<Cid> = HashSet::new();
+        let mut interval = tokio::time::interval(GOVERNANCE_SCHEDULER_INTERVAL);
+        loop {
+            interval.tick().await;
+            let now = state.runtime_context.time_provider.unix_seconds();
+            let cases = match state
+                .runtime_context
+                .governance_module
+                .lock()
+                .await
+                .enforced_cases(now)
+            {
+                Ok(cases) => cases,
+                Err(e) => {
+                    error!("Failed to load sanctions: {}", e);
+                    continue;
+                }
+            };
+            let mut restricted = HashSet::new();
+            for case in cases {
+                let removal = case.sanction_at(now) == Some(&icn_governance::Sanction::Removal);
+                for (cid, credential) in state.credential_store.held_by(&case.complaint.subject) {
+                    if let icn_governance::SubjectRole::Steward { body } = &case.complaint.role {
+                        if credential.claims.get("role") != Some(body) {
+                            continue;
+                        }
+                    }
+                    if removal {
+                        if state.revocation_registry.revoke(&cid) {
+                            publish_credential_status(
+                                &state,
+                                &cid,
+                                icn_identity::StatusPurpose::Revocation,
+                            )
+                            .await;
+                        }
+                    } else if state.paused_credentials.insert(cid.clone()) {
+                        paused_by_sanction.insert(cid.clone());
+                    }
+                    restricted.insert(cid);
+                }
+            }
+            paused_by_sanction.retain(|cid| {
+                let keep = restricted.contains(cid);
+                if !keep {
+                    state.paused_credentials.remove(cid);
+                }
+                keep
+            });
+        }
+    });
+}
+
+/// Admit governance voters through the personhood registry once governance
+/// requires personhood.
+async fn install_personhood_guard(state: &AppState) {
+    let guard = icn_governance::PersonhoodSybilGuard::new(
+        state.personhood_registry.clone(),
+        state.runtime_context.time_provider.clone(),
+    );
+    state
+        .runtime_context
+        .governance_module
+        .lock()
+        .await
+        .set_sybil_guard(Arc::new(guard));
+}
+
+/// Re-run personhood verification, return the mana staked on expired vouches
+/// and revoke personhood credentials of DIDs that lapsed or were flagged.
+fn spawn_personhood_reverification(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PERSONHOOD_REVERIFICATION_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = reverify_personhood(&state).await {
+                error!("Personhood re-verification failed: {}", e);
+            }
+        }
+    });
+}
+
+async fn reverify_personhood(
+    state: &AppState,
+) -> Result<icn_identity::PersonhoodEvaluation, CommonError> {
+    let now = state.runtime_context.time_provider.unix_seconds();
+    let evaluation = state.personhood_registry.evaluate(now)?;
+    for vouch in &evaluation.released {
+        if let icn_identity::VouchStake::Mana(amount) = vouch.stake {
+            if let Err(e) = state
+                .runtime_context
+                .credit_mana(&vouch.voucher, amount)
+                .await
+            {
+                error!("Failed to release vouch stake of {}: {}", vouch.voucher, e);
+            }
+        }
+    }
+    for did in evaluation.expired.iter().chain(&evaluation.flagged) {
+        revoke_personhood_credentials(state, did).await;
+        info!(target: "audit", "personhood_lost did={}", did);
+    }
+    Ok(evaluation)
+}
+
+/// Revoke the personhood credentials this node issued to `did`.
+async fn revoke_personhood_credentials(state: &AppState, did: &Did) {
+    for (cid, credential) in state.credential_store.held_by(did) {
+        if credential.issuer == state.runtime_context.current_identity
+            && credential
+                .claims
+                .contains_key(icn_identity::PERSONHOOD_CLAIM)
+            && state.revocation_registry.revoke(&cid)
+        {
+            publish_credential_status(state, &cid, icn_identity::StatusPurpose::Revocation).await;
+        }
+    }
+}
+
 struct RateLimitData {
     last: Instant,
     count: u64,
@@ -871,7 +1190,8 @@ pub async fn app_router_with_options(
         test_mode: matches!(runtime_mode, RuntimeMode::Testing),
         ..NodeConfig::default()
     };
-    cfg.apply_env_overrides();
+    cfg.apply_env_overrides()
+        .expect("invalid ICN_* environment override");
     let parameter_store = parameter_store_path.map(|p| {
         Arc::new(TokioMutex::new(
             ParameterStore::load(p).expect("failed to load parameter store"),
@@ -1054,6 +1374,7 @@ pub async fn app_router_with_options(
         }
     }
 
+    rt_ctx.load_governance_parameters().await;
     rt_ctx.clone().spawn_mesh_job_manager().await; // Start the job manager
 
     // Start the executor manager so this node can act as an executor
@@ -1125,7 +1446,15 @@ pub async fn app_router_with_options(
         parameter_store: parameter_store.clone(),
         circuit_registry: Arc::new(TokioMutex::new(CircuitRegistry::default())),
         credential_store: icn_identity::InMemoryCredentialStore::new(),
-        revocation_registry: icn_identity::InMemoryRevocationRegistry::new(),
+        revocation_registry: icn_identity::StatusListRegistry::in_memory(
+            rt_ctx.current_identity.clone(),
+        ),
+        recovery_registry: icn_identity::SocialRecoveryRegistry::in_memory(),
+        didcomm_mailbox: icn_identity::DidCommMailbox::in_memory(),
+        issuance_registry: icn_identity::IssuanceRegistry::in_memory(),
+        personhood_registry: icn_identity::PersonhoodRegistry::in_memory(
+            icn_identity::PersonhoodConfig::default(),
+        ),
         trusted_issuers: trusted_map,
         paused_credentials: DashSet::new(),
         frozen_reputations: DashSet::new(),
@@ -1135,6 +1464,25 @@ pub async fn app_router_with_options(
         },
         cooperative_registry,
     };
+    spawn_governance_scheduler(rt_ctx.clone(), app_state.ws_broadcaster.clone()).await;
+    spawn_membership_onboarding(app_state.clone());
+    spawn_sanction_enforcement(app_state.clone());
+    install_personhood_guard(&app_state).await;
+    spawn_personhood_reverification(app_state.clone());
+    spawn_didcomm_listener(app_state.clone()).await;
+    app_state
+        .runtime_context
+        .trust_weighting_issuers(app_state.trusted_issuers.keys().cloned().collect())
+        .await;
+    if let Err(e) = app_state.runtime_context.load_key_event_logs().await {
+        warn!("Failed to load did:icn key event logs: {}", e);
+    }
+    if let Err(e) = app_state.runtime_context.load_status_lists().await {
+        warn!("Failed to load credential status lists: {}", e);
+    }
+    if let Err(e) = app_state.runtime_context.load_credential_schemas().await {
+        warn!("Failed to load credential schemas: {}", e);
+    }
 
     // Register governance callback for parameter changes
     {
@@ -1160,13 +1508,15 @@ pub async fn app_router_with_options(
                             data.limit = new_lim;
                         });
                     }
-                    if let Some(ref store) = param_store_opt {
-                        let val = value.clone();
-                        handle.block_on(async {
-                            let mut ps = store.lock().await;
-                            let _ = ps.set_parameter(param, &val);
-                        });
-                    }
+                }
+                if let Some(ref store) = param_store_opt {
+                    let val = value.clone();
+                    handle.block_on(async {
+                        let mut ps = store.lock().await;
+                        if let Err(e) = ps.set_parameter(param, &val) {
+                            warn!("Failed to persist parameter {}: {}", param, e);
+                        }
+                    });
                 }
             }
             if let icn_governance::ProposalType::Resolution(res) = &proposal.proposal_type {
@@ -1215,10 +1565,69 @@ pub async fn app_router_with_options(
                 "/identity/credentials/verify",
                 post(credential_verify_handler),
             )
+            .route(
+                "/identity/schemas",
+                post(credential_schema_publish_handler).get(credential_schema_list_handler),
+            )
+            .route(
+                "/identity/schemas/{cid}",
+                get(credential_schema_get_handler),
+            )
+            .route(
+                "/identity/issuance/policies",
+                post(issuance_policy_set_handler).get(issuance_policy_list_handler),
+            )
+            .route("/identity/issuance/offers", post(issuance_offer_handler))
+            .route(
+                "/identity/issuance/offers/{id}",
+                get(issuance_offer_get_handler),
+            )
+            .route(
+                "/identity/issuance/requests",
+                post(issuance_request_handler),
+            )
+            .route("/identity/personhood/seeds", post(personhood_seed_handler))
+            .route(
+                "/identity/personhood/vouches",
+                post(personhood_vouch_handler),
+            )
+            .route(
+                "/identity/personhood/evaluate",
+                post(personhood_evaluate_handler),
+            )
+            .route(
+                "/identity/personhood/revoke",
+                post(personhood_revoke_handler),
+            )
+            .route(
+                "/identity/personhood/credential",
+                post(personhood_credential_handler),
+            )
+            .route("/identity/personhood/{did}", get(personhood_status_handler))
             .route(
                 "/identity/credentials/revoke",
                 post(credential_revoke_handler),
             )
+            .route(
+                "/identity/credentials/suspend",
+                post(credential_suspend_handler),
+            )
+            .route(
+                "/identity/credentials/reinstate",
+                post(credential_reinstate_handler),
+            )
+            .route(
+                "/identity/status-lists/{id}/{purpose}",
+                get(status_list_get_handler),
+            )
+            .route(
+                "/identity/credentials/export",
+                post(credential_export_handler),
+            )
+            .route(
+                "/identity/credentials/import",
+                post(credential_import_handler),
+            )
             .route(
                 "/identity/credentials/schemas",
                 get(credential_schemas_handler),
@@ -1228,6 +1637,53 @@ pub async fn app_router_with_options(
                 post(credential_disclose_handler),
             )
             .route("/identity/credentials/{cid}", get(credential_get_handler))
+            .route("/identity/did-icn/events", post(did_icn_event_handler))
+            .route("/identity/did-icn/{did}", get(did_icn_log_handler))
+            .route("/identity/recovery/config", post(recovery_config_handler))
+            .route(
+                "/identity/recovery/config/{did}",
+                get(recovery_config_get_handler),
+            )
+            .route("/identity/recovery/cases", post(recovery_open_handler))
+            .route("/identity/recovery/cases/{id}", get(recovery_case_handler))
+            .route(
+                "/identity/recovery/cases/{id}/approve",
+                post(recovery_approve_handler),
+            )
+            .route(
+                "/identity/recovery/cases/{id}/cancel",
+                post(recovery_cancel_handler),
+            )
+            .route(
+                "/identity/recovery/cases/{id}/shares",
+                get(recovery_shares_handler),
+            )
+            .route(
+                "/identity/recovery/cases/{id}/complete",
+                post(recovery_complete_handler),
+            )
+            .route("/didcomm", post(didcomm_receive_handler))
+            .route("/didcomm/send", post(didcomm_send_handler))
+            .route("/didcomm/messages", get(didcomm_messages_handler))
+            .route("/didcomm/mediated", get(didcomm_mediated_handler))
+            .route("/didcomm/mediation", post(didcomm_mediation_handler))
+            .route(
+                "/didcomm/mediation/allowed",
+                post(didcomm_mediation_allow_handler).get(didcomm_mediation_allowlist_handler),
+            )
+            .route("/didcomm/pickup", post(didcomm_pickup_handler))
+            .route(
+                "/didcomm/credential-offer",
+                post(didcomm_credential_offer_handler),
+            )
+            .route(
+                "/didcomm/credential-request",
+                post(didcomm_credential_request_handler),
+            )
+            .route(
+                "/didcomm/proposal-invitation",
+                post(didcomm_proposal_invitation_handler),
+            )
             .route("/dag/put", post(dag_put_handler)) // These will use RT context's DAG store
             .route("/dag/get", post(dag_get_handler)) // These will use RT context's DAG store
             .route("/dag/meta", post(dag_meta_handler))
@@ -1245,8 +1701,171 @@ pub async fn app_router_with_options(
             .route("/governance/vote", post(gov_vote_handler)) // Uses RT context's Gov mod
             .route("/governance/delegate", post(gov_delegate_handler))
             .route("/governance/revoke", post(gov_revoke_handler))
+            .route("/governance/tag", post(gov_tag_proposal_handler))
+            .route("/governance/parameters", get(gov_parameters_handler))
+            .route(
+                "/governance/parameters/history",
+                get(gov_parameter_history_handler),
+            )
+            .route(
+                "/governance/parameters/rollback",
+                post(gov_parameter_rollback_handler),
+            )
+            .route("/governance/upgrades", get(gov_upgrades_handler))
+            .route(
+                "/governance/upgrades/{proposal_id}/ready",
+                post(gov_upgrade_ready_handler),
+            )
+            .route(
+                "/governance/upgrades/readiness",
+                post(gov_upgrade_readiness_handler),
+            )
+            .route(
+                "/governance/secret-ballot/commitment",
+                post(gov_voter_commitment_handler),
+            )
+            .route(
+                "/governance/secret-ballot/cast",
+                post(gov_cast_secret_ballot_handler),
+            )
+            .route(
+                "/governance/secret-ballot/reveal",
+                post(gov_reveal_secret_ballot_handler),
+            )
+            .route(
+                "/governance/secret-ballot/{proposal_id}",
+                get(gov_secret_ballot_handler),
+            )
+            .route(
+                "/governance/secret-ballot/{proposal_id}/enable",
+                post(gov_enable_secret_ballot_handler),
+            )
+            .route(
+                "/governance/lifecycle/timers",
+                get(gov_lifecycle_timers_handler),
+            )
+            .route(
+                "/governance/cross-federation",
+                get(gov_cross_federation_list_handler),
+            )
+            .route(
+                "/governance/cross-federation/adopt",
+                post(gov_cross_federation_adopt_handler),
+            )
+            .route(
+                "/governance/cross-federation/ratification",
+                post(gov_cross_federation_record_handler),
+            )
+            .route(
+                "/governance/cross-federation/{proposal_id}",
+                get(gov_cross_federation_handler),
+            )
+            .route(
+                "/governance/cross-federation/{proposal_id}/ratify",
+                post(gov_cross_federation_ratify_handler),
+            )
+            .route(
+                "/governance/cross-federation/{proposal_id}/execute",
+                post(gov_cross_federation_execute_handler),
+            )
+            .route(
+                "/governance/constitution",
+                get(gov_constitution_handler).post(gov_adopt_constitution_handler),
+            )
+            .route(
+                "/governance/constitution/history",
+                get(gov_constitution_history_handler),
+            )
+            .route(
+                "/governance/constitution/{version}",
+                get(gov_constitution_version_handler),
+            )
+            .route(
+                "/governance/cases",
+                get(gov_cases_handler).post(gov_file_complaint_handler),
+            )
+            .route("/governance/cases/{case_id}", get(gov_case_handler))
+            .route(
+                "/governance/cases/{case_id}/response",
+                post(gov_case_response_handler),
+            )
+            .route(
+                "/governance/cases/{case_id}/verdict",
+                post(gov_case_verdict_handler),
+            )
+            .route(
+                "/governance/cases/{case_id}/appeal",
+                post(gov_case_appeal_handler),
+            )
+            .route(
+                "/governance/weighting",
+                get(gov_weighting_handler).post(gov_set_weight_rule_handler),
+            )
+            .route(
+                "/governance/weighting/profiles",
+                post(gov_weighting_profile_handler),
+            )
+            .route(
+                "/governance/weighting/profiles/{did}",
+                get(gov_member_profile_handler),
+            )
+            .route(
+                "/governance/weighting/credentials",
+                post(gov_weighting_credential_handler),
+            )
+            .route(
+                "/governance/weighting/tally/{proposal_id}",
+                get(gov_weighted_tally_handler),
+            )
+            .route(
+                "/governance/deliberation/{proposal_id}/start",
+                post(gov_start_deliberation_handler),
+            )
+            .route(
+                "/governance/deliberation/{proposal_id}",
+                get(gov_deliberation_handler),
+            )
+            .route("/governance/revise", post(gov_revise_proposal_handler))
+            .route(
+                "/governance/amendments",
+                post(gov_propose_amendment_handler),
+            )
+            .route(
+                "/governance/amendments/vote",
+                post(gov_amendment_vote_handler),
+            )
+            .route(
+                "/governance/amendments/{proposal_id}/resolve",
+                post(gov_resolve_amendments_handler),
+            )
+            .route("/governance/comments", post(gov_comment_handler))
+            .route(
+                "/governance/comments/{proposal_id}",
+                get(gov_comments_handler),
+            )
+            .route("/governance/delegations", get(gov_delegations_handler))
+            .route(
+                "/governance/delegations/{proposal_id}",
+                get(gov_proposal_delegations_handler),
+            )
             .route("/governance/close", post(gov_close_handler))
             .route("/governance/execute", post(gov_execute_handler))
+            .route(
+                "/governance/conviction/submit",
+                post(gov_conviction_submit_handler),
+            )
+            .route(
+                "/governance/conviction/stake",
+                post(gov_conviction_stake_handler),
+            )
+            .route(
+                "/governance/conviction/process",
+                post(gov_conviction_process_handler),
+            )
+            .route(
+                "/governance/conviction/{proposal_id}",
+                get(gov_conviction_status_handler),
+            )
             .route("/governance/proposals", get(gov_list_proposals_handler)) // Uses RT context's Gov mod
             .route(
                 "/governance/proposal/{proposal_id}",
@@ -1278,6 +1897,22 @@ pub async fn app_router_with_options(
             .route("/federation/join", post(federation_join_handler))
             .route("/federation/leave", post(federation_leave_handler))
             .route("/federation/status", get(federation_status_handler))
+            .route(
+                "/federation/applications",
+                get(federation_applications_handler).post(federation_apply_handler),
+            )
+            .route(
+                "/federation/applications/gate",
+                post(federation_application_gate_handler),
+            )
+            .route(
+                "/federation/applications/{id}",
+                get(federation_application_handler),
+            )
+            .route(
+                "/federation/applications/{id}/challenge",
+                post(federation_application_challenge_handler),
+            )
             .route("/federation/init", post(federation_init_handler))
             .route("/federation/sync", post(federation_sync_handler))
             .route("/cooperative/register", post(cooperative_register_handler))
@@ -1364,7 +1999,15 @@ pub async fn app_router_from_context(
         parameter_store: None,
         circuit_registry: Arc::new(TokioMutex::new(CircuitRegistry::default())),
         credential_store: icn_identity::InMemoryCredentialStore::new(),
-        revocation_registry: icn_identity::InMemoryRevocationRegistry::new(),
+        revocation_registry: icn_identity::StatusListRegistry::in_memory(
+            ctx.current_identity.clone(),
+        ),
+        recovery_registry: icn_identity::SocialRecoveryRegistry::in_memory(),
+        didcomm_mailbox: icn_identity::DidCommMailbox::in_memory(),
+        issuance_registry: icn_identity::IssuanceRegistry::in_memory(),
+        personhood_registry: icn_identity::PersonhoodRegistry::in_memory(
+            icn_identity::PersonhoodConfig::default(),
+        ),
         trusted_issuers: trusted_map,
         paused_credentials: DashSet::new(),
         frozen_reputations: DashSet::new(),
@@ -1374,6 +2017,25 @@ pub async fn app_router_from_context(
         },
         cooperative_registry,
     };
+    spawn_governance_scheduler(ctx.clone(), app_state.ws_broadcaster.clone()).await;
+    spawn_membership_onboarding(app_state.clone());
+    spawn_sanction_enforcement(app_state.clone());
+    install_personhood_guard(&app_state).await;
+    spawn_personhood_reverification(app_state.clone());
+    spawn_didcomm_listener(app_state.clone()).await;
+    app_state
+        .runtime_context
+        .trust_weighting_issuers(app_state.trusted_issuers.keys().cloned().collect())
+        .await;
+    if let Err(e) = app_state.runtime_context.load_key_event_logs().await {
+        warn!("Failed to load did:icn key event logs: {}", e);
+    }
+    if let Err(e) = app_state.runtime_context.load_status_lists().await {
+        warn!("Failed to load credential status lists: {}", e);
+    }
+    if let Err(e) = app_state.runtime_context.load_credential_schemas().await {
+        warn!("Failed to load credential schemas: {}", e);
+    }
 
     {
         let gov_mod = ctx.governance_module.clone();
@@ -1398,13 +2060,15 @@ pub async fn app_router_from_context(
                             data.limit = new_lim;
                         });
                     }
-                    if let Some(ref store) = param_store_opt {
-                        let val = value.clone();
-                        handle.block_on(async {
-                            let mut ps = store.lock().await;
-                            let _ = ps.set_parameter(param, &val);
-                        });
-                    }
+                }
+                if let Some(ref store) = param_store_opt {
+                    let val = value.clone();
+                    handle.block_on(async {
+                        let mut ps = store.lock().await;
+                        if let Err(e) = ps.set_parameter(param, &val) {
+                            warn!("Failed to persist parameter {}: {}", param, e);
+                        }
+                    });
                 }
             }
             if let icn_governance::ProposalType::Resolution(res) = &proposal.proposal_type {
@@ -1444,14 +2108,120 @@ pub async fn app_router_from_context(
             post(credential_verify_handler),
         )
         .route(
-            "/identity/credentials/revoke",
-            post(credential_revoke_handler),
+            "/identity/schemas",
+            post(credential_schema_publish_handler).get(credential_schema_list_handler),
+        )
+        .route(
+            "/identity/schemas/{cid}",
+            get(credential_schema_get_handler),
+        )
+        .route(
+            "/identity/issuance/policies",
+            post(issuance_policy_set_handler).get(issuance_policy_list_handler),
+        )
+        .route("/identity/issuance/offers", post(issuance_offer_handler))
+        .route(
+            "/identity/issuance/offers/{id}",
+            get(issuance_offer_get_handler),
+        )
+        .route(
+            "/identity/issuance/requests",
+            post(issuance_request_handler),
+        )
+        .route("/identity/personhood/seeds", post(personhood_seed_handler))
+        .route(
+            "/identity/personhood/vouches",
+            post(personhood_vouch_handler),
+        )
+        .route(
+            "/identity/personhood/evaluate",
+            post(personhood_evaluate_handler),
+        )
+        .route(
+            "/identity/personhood/revoke",
+            post(personhood_revoke_handler),
+        )
+        .route(
+            "/identity/personhood/credential",
+            post(personhood_credential_handler),
+        )
+        .route("/identity/personhood/{did}", get(personhood_status_handler))
+        .route(
+            "/identity/credentials/revoke",
+            post(credential_revoke_handler),
+        )
+        .route(
+            "/identity/credentials/suspend",
+            post(credential_suspend_handler),
+        )
+        .route(
+            "/identity/credentials/reinstate",
+            post(credential_reinstate_handler),
+        )
+        .route(
+            "/identity/status-lists/{id}/{purpose}",
+            get(status_list_get_handler),
+        )
+        .route(
+            "/identity/credentials/export",
+            post(credential_export_handler),
+        )
+        .route(
+            "/identity/credentials/import",
+            post(credential_import_handler),
         )
         .route(
             "/identity/credentials/schemas",
             get(credential_schemas_handler),
         )
         .route("/identity/credentials/{cid}", get(credential_get_handler))
+        .route("/identity/did-icn/events", post(did_icn_event_handler))
+        .route("/identity/did-icn/{did}", get(did_icn_log_handler))
+        .route("/identity/recovery/config", post(recovery_config_handler))
+        .route(
+            "/identity/recovery/config/{did}",
+            get(recovery_config_get_handler),
+        )
+        .route("/identity/recovery/cases", post(recovery_open_handler))
+        .route("/identity/recovery/cases/{id}", get(recovery_case_handler))
+        .route(
+            "/identity/recovery/cases/{id}/approve",
+            post(recovery_approve_handler),
+        )
+        .route(
+            "/identity/recovery/cases/{id}/cancel",
+            post(recovery_cancel_handler),
+        )
+        .route(
+            "/identity/recovery/cases/{id}/shares",
+            get(recovery_shares_handler),
+        )
+        .route(
+            "/identity/recovery/cases/{id}/complete",
+            post(recovery_complete_handler),
+        )
+        .route("/didcomm", post(didcomm_receive_handler))
+        .route("/didcomm/send", post(didcomm_send_handler))
+        .route("/didcomm/messages", get(didcomm_messages_handler))
+        .route("/didcomm/mediated", get(didcomm_mediated_handler))
+        .route("/didcomm/mediation", post(didcomm_mediation_handler))
+        .route(
+            "/didcomm/mediation/allowed",
+            post(didcomm_mediation_allow_handler).get(didcomm_mediation_allowlist_handler),
+        )
+        .route("/didcomm/pickup", post(didcomm_pickup_handler))
+        .route(
+            "/didcomm/credential-offer",
+            post(didcomm_credential_offer_handler),
+        )
+        .route(
+            "/didcomm/credential-request",
+            post(didcomm_credential_request_handler),
+        )
+        .route(
+            "/didcomm/proposal-invitation",
+            post(didcomm_proposal_invitation_handler),
+        )
         .route("/dag/put", post(dag_put_handler))
         .route("/dag/get", post(dag_get_handler))
         .route("/dag/meta", post(dag_meta_handler))
@@ -1469,8 +2239,171 @@ pub async fn app_router_from_context(
         .route("/governance/vote", post(gov_vote_handler))
         .route("/governance/delegate", post(gov_delegate_handler))
         .route("/governance/revoke", post(gov_revoke_handler))
+        .route("/governance/tag", post(gov_tag_proposal_handler))
+        .route("/governance/parameters", get(gov_parameters_handler))
+        .route(
+            "/governance/parameters/history",
+            get(gov_parameter_history_handler),
+        )
+        .route(
+            "/governance/parameters/rollback",
+            post(gov_parameter_rollback_handler),
+        )
+        .route("/governance/upgrades", get(gov_upgrades_handler))
+        .route(
+            "/governance/upgrades/{proposal_id}/ready",
+            post(gov_upgrade_ready_handler),
+        )
+        .route(
+            "/governance/upgrades/readiness",
+            post(gov_upgrade_readiness_handler),
+        )
+        .route(
+            "/governance/secret-ballot/commitment",
+            post(gov_voter_commitment_handler),
+        )
+        .route(
+            "/governance/secret-ballot/cast",
+            post(gov_cast_secret_ballot_handler),
+        )
+        .route(
+            "/governance/secret-ballot/reveal",
+            post(gov_reveal_secret_ballot_handler),
+        )
+        .route(
+            "/governance/secret-ballot/{proposal_id}",
+            get(gov_secret_ballot_handler),
+        )
+        .route(
+            "/governance/secret-ballot/{proposal_id}/enable",
+            post(gov_enable_secret_ballot_handler),
+        )
+        .route(
+            "/governance/lifecycle/timers",
+            get(gov_lifecycle_timers_handler),
+        )
+        .route(
+            "/governance/cross-federation",
+            get(gov_cross_federation_list_handler),
+        )
+        .route(
+            "/governance/cross-federation/adopt",
+            post(gov_cross_federation_adopt_handler),
+        )
+        .route(
+            "/governance/cross-federation/ratification",
+            post(gov_cross_federation_record_handler),
+        )
+        .route(
+            "/governance/cross-federation/{proposal_id}",
+            get(gov_cross_federation_handler),
+        )
+        .route(
+            "/governance/cross-federation/{proposal_id}/ratify",
+            post(gov_cross_federation_ratify_handler),
+        )
+        .route(
+            "/governance/cross-federation/{proposal_id}/execute",
+            post(gov_cross_federation_execute_handler),
+        )
+        .route(
+            "/governance/constitution",
+            get(gov_constitution_handler).post(gov_adopt_constitution_handler),
+        )
+        .route(
+            "/governance/constitution/history",
+            get(gov_constitution_history_handler),
+        )
+        .route(
+            "/governance/constitution/{version}",
+            get(gov_constitution_version_handler),
+        )
+        .route(
+            "/governance/cases",
+            get(gov_cases_handler).post(gov_file_complaint_handler),
+        )
+        .route("/governance/cases/{case_id}", get(gov_case_handler))
+        .route(
+            "/governance/cases/{case_id}/response",
+            post(gov_case_response_handler),
+        )
+        .route(
+            "/governance/cases/{case_id}/verdict",
+            post(gov_case_verdict_handler),
+        )
+        .route(
+            "/governance/cases/{case_id}/appeal",
+            post(gov_case_appeal_handler),
+        )
+        .route(
+            "/governance/weighting",
+            get(gov_weighting_handler).post(gov_set_weight_rule_handler),
+        )
+        .route(
+            "/governance/weighting/profiles",
+            post(gov_weighting_profile_handler),
+        )
+        .route(
+            "/governance/weighting/profiles/{did}",
+            get(gov_member_profile_handler),
+        )
+        .route(
+            "/governance/weighting/credentials",
+            post(gov_weighting_credential_handler),
+        )
+        .route(
+            "/governance/weighting/tally/{proposal_id}",
+            get(gov_weighted_tally_handler),
+        )
+        .route(
+            "/governance/deliberation/{proposal_id}/start",
+            post(gov_start_deliberation_handler),
+        )
+        .route(
+            "/governance/deliberation/{proposal_id}",
+            get(gov_deliberation_handler),
+        )
+        .route("/governance/revise", post(gov_revise_proposal_handler))
+        .route(
+            "/governance/amendments",
+            post(gov_propose_amendment_handler),
+        )
+        .route(
+            "/governance/amendments/vote",
+            post(gov_amendment_vote_handler),
+        )
+        .route(
+            "/governance/amendments/{proposal_id}/resolve",
+            post(gov_resolve_amendments_handler),
+        )
+        .route("/governance/comments", post(gov_comment_handler))
+        .route(
+            "/governance/comments/{proposal_id}",
+            get(gov_comments_handler),
+        )
+        .route("/governance/delegations", get(gov_delegations_handler))
+        .route(
+            "/governance/delegations/{proposal_id}",
+            get(gov_proposal_delegations_handler),
+        )
         .route("/governance/close", post(gov_close_handler))
         .route("/governance/execute", post(gov_execute_handler))
+        .route(
+            "/governance/conviction/submit",
+            post(gov_conviction_submit_handler),
+        )
+        .route(
+            "/governance/conviction/stake",
+            post(gov_conviction_stake_handler),
+        )
+        .route(
+            "/governance/conviction/process",
+            post(gov_conviction_process_handler),
+        )
+        .route(
+            "/governance/conviction/{proposal_id}",
+            get(gov_conviction_status_handler),
+        )
         .route("/governance/proposals", get(gov_list_proposals_handler))
         .route(
             "/governance/proposal/{proposal_id}",
@@ -1502,6 +2435,22 @@ pub async fn app_router_from_context(
         .route("/federation/join", post(federation_join_handler))
         .route("/federation/leave", post(federation_leave_handler))
         .route("/federation/status", get(federation_status_handler))
+        .route(
+            "/federation/applications",
+            get(federation_applications_handler).post(federation_apply_handler),
+        )
+        .route(
+            "/federation/applications/gate",
+            post(federation_application_gate_handler),
+        )
+        .route(
+            "/federation/applications/{id}",
+            get(federation_application_handler),
+        )
+        .route(
+            "/federation/applications/{id}/challenge",
+            post(federation_application_challenge_handler),
+        )
         .route("/federation/init", post(federation_init_handler))
         .route("/federation/sync", post(federation_sync_handler))
         .route("/cooperative/register", post(cooperative_register_handler))
@@ -1579,7 +2528,7 @@ pub async fn run_node() -> Result<(), Box<dyn std::error::Error>> {
     });
     // Start with persisted parameter values
     config.http.open_rate_limit = parameter_store.open_rate_limit();
-    config.apply_env_overrides();
+    config.apply_env_overrides()?;
     config.apply_cli_overrides(&cli, &matches);
     if let Err(e) =
         parameter_store.set_parameter("open_rate_limit", &config.http.open_rate_limit.to_string())
@@ -1635,8 +2584,6 @@ pub async fn run_node() -> Result<(), Box<dyn std::error::Error>> {
         config.storage.mana_ledger_path.clone(),
         config.storage.mana_ledger_backend,
     );
-    let signer = Arc::new(signer);
-
     let network_service = match build_network_service(&config).await {
         Ok(svc) => svc,
         Err(e) => {
@@ -1679,6 +2626,7 @@ pub async fn run_node() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Start the job manager
+    rt_ctx.load_governance_parameters().await;
     rt_ctx.clone().spawn_mesh_job_manager().await;
 
     // Start the executor manager so this node can act as an executor
@@ -1763,7 +2711,21 @@ pub async fn run_node() -> Result<(), Box<dyn std::error::Error>> {
         parameter_store: Some(parameter_store.clone()),
         circuit_registry: Arc::new(TokioMutex::new(CircuitRegistry::default())),
         credential_store: icn_identity::InMemoryCredentialStore::new(),
-        revocation_registry: icn_identity::InMemoryRevocationRegistry::new(),
+        revocation_registry: icn_identity::StatusListRegistry::open(
+            rt_ctx.current_identity.clone(),
+            config.storage.status_list_path.clone(),
+        )?,
+        recovery_registry: icn_identity::SocialRecoveryRegistry::open(
+            config.storage.recovery_path.clone(),
+        )?,
+        didcomm_mailbox: icn_identity::DidCommMailbox::open(config.storage.didcomm_path.clone())?,
+        issuance_registry: icn_identity::IssuanceRegistry::open(
+            config.storage.issuance_path.clone(),
+        )?,
+        personhood_registry: icn_identity::PersonhoodRegistry::open(
+            config.storage.personhood_path.clone(),
+            icn_identity::PersonhoodConfig::default(),
+        )?,
         trusted_issuers: trusted_map,
         paused_credentials: DashSet::new(),
         frozen_reputations: DashSet::new(),
@@ -1773,6 +2735,25 @@ pub async fn run_node() -> Result<(), Box<dyn std::error::Error>> {
         },
         cooperative_registry,
     };
+    spawn_governance_scheduler(rt_ctx.clone(), app_state.ws_broadcaster.clone()).await;
+    spawn_membership_onboarding(app_state.clone());
+    spawn_sanction_enforcement(app_state.clone());
+    install_personhood_guard(&app_state).await;
+    spawn_personhood_reverification(app_state.clone());
+    spawn_didcomm_listener(app_state.clone()).await;
+    app_state
+        .runtime_context
+        .trust_weighting_issuers(app_state.trusted_issuers.keys().cloned().collect())
+        .await;
+    if let Err(e) = app_state.runtime_context.load_key_event_logs().await {
+        warn!("Failed to load did:icn key event logs: {}", e);
+    }
+    if let Err(e) = app_state.runtime_context.load_status_lists().await {
+        warn!("Failed to load credential status lists: {}", e);
+    }
+    if let Err(e) = app_state.runtime_context.load_credential_schemas().await {
+        warn!("Failed to load credential schemas: {}", e);
+    }
 
     {
         let gov_mod = rt_ctx.governance_module.clone();
@@ -1841,8 +2822,171 @@ pub async fn run_node() -> Result<(), Box<dyn std::error::Error>> {
         .route("/governance/vote", post(gov_vote_handler))
         .route("/governance/delegate", post(gov_delegate_handler))
         .route("/governance/revoke", post(gov_revoke_handler))
+        .route("/governance/tag", post(gov_tag_proposal_handler))
+        .route("/governance/parameters", get(gov_parameters_handler))
+        .route(
+            "/governance/parameters/history",
+            get(gov_parameter_history_handler),
+        )
+        .route(
+            "/governance/parameters/rollback",
+            post(gov_parameter_rollback_handler),
+        )
+        .route("/governance/upgrades", get(gov_upgrades_handler))
+        .route(
+            "/governance/upgrades/{proposal_id}/ready",
+            post(gov_upgrade_ready_handler),
+        )
+        .route(
+            "/governance/upgrades/readiness",
+            post(gov_upgrade_readiness_handler),
+        )
+        .route(
+            "/governance/secret-ballot/commitment",
+            post(gov_voter_commitment_handler),
+        )
+        .route(
+            "/governance/secret-ballot/cast",
+            post(gov_cast_secret_ballot_handler),
+        )
+        .route(
+            "/governance/secret-ballot/reveal",
+            post(gov_reveal_secret_ballot_handler),
+        )
+        .route(
+            "/governance/secret-ballot/{proposal_id}",
+            get(gov_secret_ballot_handler),
+        )
+        .route(
+            "/governance/secret-ballot/{proposal_id}/enable",
+            post(gov_enable_secret_ballot_handler),
+        )
+        .route(
+            "/governance/lifecycle/timers",
+            get(gov_lifecycle_timers_handler),
+        )
+        .route(
+            "/governance/cross-federation",
+            get(gov_cross_federation_list_handler),
+        )
+        .route(
+            "/governance/cross-federation/adopt",
+            post(gov_cross_federation_adopt_handler),
+        )
+        .route(
+            "/governance/cross-federation/ratification",
+            post(gov_cross_federation_record_handler),
+        )
+        .route(
+            "/governance/cross-federation/{proposal_id}",
+            get(gov_cross_federation_handler),
+        )
+        .route(
+            "/governance/cross-federation/{proposal_id}/ratify",
+            post(gov_cross_federation_ratify_handler),
+        )
+        .route(
+            "/governance/cross-federation/{proposal_id}/execute",
+            post(gov_cross_federation_execute_handler),
+        )
+        .route(
+            "/governance/constitution",
+            get(gov_constitution_handler).post(gov_adopt_constitution_handler),
+        )
+        .route(
+            "/governance/constitution/history",
+            get(gov_constitution_history_handler),
+        )
+        .route(
+            "/governance/constitution/{version}",
+            get(gov_constitution_version_handler),
+        )
+        .route(
+            "/governance/cases",
+            get(gov_cases_handler).post(gov_file_complaint_handler),
+        )
+        .route("/governance/cases/{case_id}", get(gov_case_handler))
+        .route(
+            "/governance/cases/{case_id}/response",
+            post(gov_case_response_handler),
+        )
+        .route(
+            "/governance/cases/{case_id}/verdict",
+            post(gov_case_verdict_handler),
+        )
+        .route(
+            "/governance/cases/{case_id}/appeal",
+            post(gov_case_appeal_handler),
+        )
+        .route(
+            "/governance/weighting",
+            get(gov_weighting_handler).post(gov_set_weight_rule_handler),
+        )
+        .route(
+            "/governance/weighting/profiles",
+            post(gov_weighting_profile_handler),
+        )
+        .route(
+            "/governance/weighting/profiles/{did}",
+            get(gov_member_profile_handler),
+        )
+        .route(
+            "/governance/weighting/credentials",
+            post(gov_weighting_credential_handler),
+        )
+        .route(
+            "/governance/weighting/tally/{proposal_id}",
+            get(gov_weighted_tally_handler),
+        )
+        .route(
+            "/governance/deliberation/{proposal_id}/start",
+            post(gov_start_deliberation_handler),
+        )
+        .route(
+            "/governance/deliberation/{proposal_id}",
+            get(gov_deliberation_handler),
+        )
+        .route("/governance/revise", post(gov_revise_proposal_handler))
+        .route(
+            "/governance/amendments",
+            post(gov_propose_amendment_handler),
+        )
+        .route(
+            "/governance/amendments/vote",
+            post(gov_amendment_vote_handler),
+        )
+        .route(
+            "/governance/amendments/{proposal_id}/resolve",
+            post(gov_resolve_amendments_handler),
+        )
+        .route("/governance/comments", post(gov_comment_handler))
+        .route(
+            "/governance/comments/{proposal_id}",
+            get(gov_comments_handler),
+        )
+        .route("/governance/delegations", get(gov_delegations_handler))
+        .route(
+            "/governance/delegations/{proposal_id}",
+            get(gov_proposal_delegations_handler),
+        )
         .route("/governance/close", post(gov_close_handler))
         .route("/governance/execute", post(gov_execute_handler))
+        .route(
+            "/governance/conviction/submit",
+            post(gov_conviction_submit_handler),
+        )
+        .route(
+            "/governance/conviction/stake",
+            post(gov_conviction_stake_handler),
+        )
+        .route(
+            "/governance/conviction/process",
+            post(gov_conviction_process_handler),
+        )
+        .route(
+            "/governance/conviction/{proposal_id}",
+            get(gov_conviction_status_handler),
+        )
         .route("/governance/proposals", get(gov_list_proposals_handler))
         .route(
             "/governance/proposal/{proposal_id}",
@@ -1872,6 +3016,22 @@ pub async fn run_node() -> Result<(), Box<dyn std::error::Error>> {
         .route("/federation/join", post(federation_join_handler))
         .route("/federation/leave", post(federation_leave_handler))
         .route("/federation/status", get(federation_status_handler))
+        .route(
+            "/federation/applications",
+            get(federation_applications_handler).post(federation_apply_handler),
+        )
+        .route(
+            "/federation/applications/gate",
+            post(federation_application_gate_handler),
+        )
+        .route(
+            "/federation/applications/{id}",
+            get(federation_application_handler),
+        )
+        .route(
+            "/federation/applications/{id}/challenge",
+            post(federation_application_challenge_handler),
+        )
         .route("/federation/init", post(federation_init_handler))
         .route("/federation/sync", post(federation_sync_handler))
         .route("/cooperative/register", post(cooperative_register_handler))
@@ -1977,7 +3137,20 @@ async fn info_handler(State(state): State<AppState>) -> impl IntoResponse {
         version: state.node_version.clone(),
         status_message,
     };
-    (StatusCode::OK, Json(info))
+    let mut body = serde_json::to_value(&info).unwrap_or_default();
+    match upgrade_summary(&state).await {
+        Ok(summary) => {
+            body["protocol_version"] = serde_json::json!(summary.protocol_version);
+            body["upgrades"] = serde_json::json!(summary.upgrades);
+        }
+        Err(e) => warn!("Failed to load upgrade status: {}", e),
+    }
+    (StatusCode::OK, Json(body))
+}
+
+/// Height used for upgrade activation and status reporting.
+fn current_block_height(_state: &AppState) -> u64 {
+    0 // Placeholder until DAG exposes height
 }
 
 // GET /status – Node status derived from RuntimeContext.
@@ -1993,7 +3166,7 @@ async fn status_handler(State(state): State<AppState>) -> impl IntoResponse {
     #[cfg(not(feature = "enable-libp2p"))]
     let (peer_count, network_stats): (u32, Option<icn_network::NetworkStats>) = (0u32, None);
 
-    let current_block_height = 0u64; // Placeholder until DAG exposes height
+    let current_block_height = current_block_height(&state);
 
     let mut status_json = serde_json::json!({
         "is_online": true,
@@ -2725,6 +3898,15 @@ async fn gov_submit_handler(
             "Resolution".to_string(),
             serde_json::to_vec(&actions).unwrap(),
         ),
+        icn_api::governance_trait::ProposalInputType::ConstitutionAmendment { constitution } => {
+            match constitution.to_proposal_payload() {
+                Ok(document) => ("ConstitutionAmendment".to_string(), document.into_bytes()),
+                Err(e) => {
+                    return map_rust_error_to_json_response(e.to_string(), StatusCode::BAD_REQUEST)
+                        .into_response()
+                }
+            }
+        }
     };
 
     let payload = icn_runtime::context::CreateProposalPayload {
@@ -2775,6 +3957,21 @@ async fn gov_vote_handler(
         vote_option_str: request.vote_option,
     };
 
+    if let Some(revision) = request.revision.as_deref() {
+        return match state
+            .runtime_context
+            .cast_governance_vote_on_revision(payload, revision)
+            .await
+        {
+            Ok(_) => (StatusCode::OK, Json("Vote cast successfully".to_string())).into_response(),
+            Err(e) => map_rust_error_to_json_response(
+                format!("Governance vote error: {}", e),
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response(),
+        };
+    }
+
     let payload_json = match serde_json::to_string(&payload) {
         Ok(j) => j,
         Err(e) => {
@@ -2801,7 +3998,15 @@ async fn gov_delegate_handler(
     State(state): State<AppState>,
     Json(req): Json<ApiDelegateRequest>,
 ) -> impl IntoResponse {
-    match icn_runtime::host_delegate_vote(&state.runtime_context, &req.from_did, &req.to_did).await
+    let scope = req.scope.as_deref().unwrap_or("all");
+    match icn_runtime::host_delegate_vote_scoped(
+        &state.runtime_context,
+        &req.from_did,
+        &req.to_did,
+        scope,
+        req.expires_at,
+    )
+    .await
     {
         Ok(_) => (StatusCode::OK, Json("delegated".to_string())).into_response(),
         Err(e) => map_rust_error_to_json_response(
@@ -2817,7 +4022,13 @@ async fn gov_revoke_handler(
     State(state): State<AppState>,
     Json(req): Json<ApiRevokeDelegationRequest>,
 ) -> impl IntoResponse {
-    match icn_runtime::host_revoke_delegation(&state.runtime_context, &req.from_did).await {
+    match icn_runtime::host_revoke_delegation_scoped(
+        &state.runtime_context,
+        &req.from_did,
+        req.scope.as_deref(),
+    )
+    .await
+    {
         Ok(_) => (StatusCode::OK, Json("revoked".to_string())).into_response(),
         Err(e) => {
             map_rust_error_to_json_response(format!("Revoke error: {}", e), StatusCode::BAD_REQUEST)
@@ -2826,1380 +4037,3614 @@ async fn gov_revoke_handler(
     }
 }
 
-// GET /governance/proposals
-async fn gov_list_proposals_handler(State(state): State<AppState>) -> impl IntoResponse {
-    debug!("Received /governance/proposals request");
-    let gov_mod = state.runtime_context.governance_module.lock().await;
-    match gov_mod.list_proposals() {
-        Ok(props) => (StatusCode::OK, Json(props)).into_response(),
+// POST /governance/tag – Attach topic tags to a proposal
+async fn gov_tag_proposal_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ApiTagProposalRequest>,
+) -> impl IntoResponse {
+    match icn_runtime::host_tag_proposal(&state.runtime_context, &req.proposal_id, req.tags).await {
+        Ok(_) => (StatusCode::OK, Json("tagged".to_string())).into_response(),
+        Err(e) => {
+            map_rust_error_to_json_response(format!("Tag error: {}", e), StatusCode::BAD_REQUEST)
+                .into_response()
+        }
+    }
+}
+
+// POST /governance/deliberation/{proposal_id}/start – Anchor revision zero
+async fn gov_start_deliberation_handler(
+    State(state): State<AppState>,
+    AxumPath(proposal_id): AxumPath<String>,
+) -> impl IntoResponse {
+    match icn_runtime::host_start_deliberation(&state.runtime_context, &proposal_id).await {
+        Ok(cid) => (StatusCode::CREATED, Json(cid)).into_response(),
         Err(e) => map_rust_error_to_json_response(
-            format!("Governance list error: {}", e),
+            format!("Deliberation error: {}", e),
             StatusCode::BAD_REQUEST,
         )
         .into_response(),
     }
 }
 
-// GET /governance/proposal/:proposal_id
-async fn gov_get_proposal_handler(
+// GET /governance/deliberation/{proposal_id} – Revisions, amendments and comments
+async fn gov_deliberation_handler(
     State(state): State<AppState>,
-    AxumPath(proposal_id_str): AxumPath<String>,
+    AxumPath(proposal_id): AxumPath<String>,
 ) -> impl IntoResponse {
-    debug!("Received /governance/proposal/{} request", proposal_id_str);
-    let gov_mod = state.runtime_context.governance_module.lock().await;
-    let pid = icn_governance::ProposalId(proposal_id_str);
-    match gov_mod.get_proposal(&pid) {
-        Ok(Some(prop)) => (StatusCode::OK, Json(prop)).into_response(),
-        Ok(None) => map_rust_error_to_json_response("Proposal not found", StatusCode::NOT_FOUND)
-            .into_response(),
+    let gov = state.runtime_context.governance_module.lock().await;
+    match gov.deliberation(&icn_governance::ProposalId(proposal_id)) {
+        Ok(Some(d)) => (StatusCode::OK, Json(d)).into_response(),
+        Ok(None) => {
+            map_rust_error_to_json_response("Deliberation has not started", StatusCode::NOT_FOUND)
+                .into_response()
+        }
         Err(e) => map_rust_error_to_json_response(
-            format!("Governance get error: {}", e),
+            format!("Deliberation error: {}", e),
             StatusCode::BAD_REQUEST,
         )
         .into_response(),
     }
 }
 
-// POST /governance/close – tally votes and, if accepted, execute proposal
-async fn gov_close_handler(
+// POST /governance/revise – Publish a new proposal revision
+async fn gov_revise_proposal_handler(
     State(state): State<AppState>,
-    Json(req): Json<ProposalIdPayload>,
+    Json(req): Json<ApiReviseProposalRequest>,
 ) -> impl IntoResponse {
-    let result = icn_runtime::host_close_governance_proposal_voting(
+    match icn_runtime::host_revise_proposal(
         &state.runtime_context,
         &req.proposal_id,
+        req.description,
+        req.body,
     )
-    .await;
-    let status_json = match result {
-        Ok(s) => s,
+    .await
+    {
+        Ok(cid) => (StatusCode::CREATED, Json(cid)).into_response(),
         Err(e) => {
-            return map_rust_error_to_json_response(
-                format!("Close error: {}", e),
-                StatusCode::BAD_REQUEST,
-            )
-            .into_response()
-        }
-    };
-    let close: icn_api::governance_trait::CloseProposalResponse =
-        match serde_json::from_str(&status_json) {
-            Ok(c) => c,
-            Err(e) => {
-                return map_rust_error_to_json_response(
-                    format!("Serialization error: {}", e),
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                )
+            map_rust_error_to_json_response(format!("Revise error: {}", e), StatusCode::BAD_REQUEST)
                 .into_response()
-            }
-        };
-    if close.status == format!("{:?}", icn_governance::ProposalStatus::Accepted) {
-        if let Err(e) =
-            icn_runtime::host_execute_governance_proposal(&state.runtime_context, &req.proposal_id)
-                .await
-        {
-            return map_rust_error_to_json_response(
-                format!("Execute error: {}", e),
-                StatusCode::BAD_REQUEST,
-            )
-            .into_response();
         }
     }
-    (StatusCode::OK, Json(close)).into_response()
 }
 
-// POST /governance/execute – force execute an accepted proposal
-async fn gov_execute_handler(
+// POST /governance/amendments – Propose an amendment
+async fn gov_propose_amendment_handler(
     State(state): State<AppState>,
-    Json(req): Json<ProposalIdPayload>,
+    Json(req): Json<ApiProposeAmendmentRequest>,
 ) -> impl IntoResponse {
-    match icn_runtime::host_execute_governance_proposal(&state.runtime_context, &req.proposal_id)
-        .await
+    match icn_runtime::host_propose_amendment(
+        &state.runtime_context,
+        &req.proposal_id,
+        req.description,
+        req.body,
+        req.rationale,
+    )
+    .await
     {
-        Ok(_) => (StatusCode::OK, Json("executed".to_string())).into_response(),
+        Ok(id) => (StatusCode::CREATED, Json(id)).into_response(),
         Err(e) => map_rust_error_to_json_response(
-            format!("Execute error: {}", e),
+            format!("Amendment error: {}", e),
             StatusCode::BAD_REQUEST,
         )
         .into_response(),
     }
 }
 
-// --- Mesh Job Endpoints ---
-
-/// Request body for submitting a mesh job.
-/// Note: `id` and `creator_did` will be overridden by the runtime.
-/// Signature also will be handled by the job submission logic after this initial DTO.
-#[derive(Debug, Serialize, Deserialize)] // Added Serialize and Deserialize
-pub struct SubmitJobRequest {
-    pub manifest_cid: String, // String to be parsed into Cid
-    /// Base64 encoded bincode [`JobSpec`] bytes.
-    #[serde(default)]
-    pub spec_bytes: Option<String>,
-    /// Deprecated JSON representation of the job spec.
-    #[serde(default)]
-    pub spec_json: Option<serde_json::Value>,
-    pub cost_mana: u64,
+// POST /governance/amendments/vote – Vote on an open amendment
+async fn gov_amendment_vote_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ApiAmendmentVoteRequest>,
+) -> impl IntoResponse {
+    match icn_runtime::host_vote_on_amendment(
+        &state.runtime_context,
+        &req.proposal_id,
+        &req.amendment_id,
+        &req.vote_option,
+    )
+    .await
+    {
+        Ok(_) => (StatusCode::OK, Json("voted".to_string())).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Amendment vote error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
 }
 
-async fn mesh_submit_job_handler(
+// POST /governance/amendments/{proposal_id}/resolve – Tally open amendments
+async fn gov_resolve_amendments_handler(
     State(state): State<AppState>,
-    Json(request): Json<SubmitJobRequest>,
+    AxumPath(proposal_id): AxumPath<String>,
 ) -> impl IntoResponse {
-    info!("[Node] Received mesh_submit_job request: {:?}", request);
-
-    let manifest_cid = match parse_cid_from_string(&request.manifest_cid) {
-        Ok(cid) => cid,
-        Err(e) => {
-            return map_rust_error_to_json_response(
-                format!("Invalid manifest CID: {}", e),
-                StatusCode::BAD_REQUEST,
-            )
-            .into_response()
-        }
-    };
-
-    // Decode job spec from bytes or fallback to deprecated JSON
-    let job_spec = if let Some(b64) = &request.spec_bytes {
-        match base64::engine::general_purpose::STANDARD.decode(b64) {
-            Ok(bytes) => match bincode::deserialize::<icn_mesh::JobSpec>(&bytes) {
-                Ok(spec) => spec,
-                Err(e) => {
-                    return map_rust_error_to_json_response(
-                        format!("Failed to decode job spec bytes: {}", e),
-                        StatusCode::BAD_REQUEST,
-                    )
-                    .into_response()
-                }
-            },
-            Err(e) => {
-                return map_rust_error_to_json_response(
-                    format!("Invalid base64 spec bytes: {}", e),
-                    StatusCode::BAD_REQUEST,
-                )
-                .into_response()
-            }
-        }
-    } else if let Some(json_val) = &request.spec_json {
-        match serde_json::from_value::<icn_mesh::JobSpec>(json_val.clone()) {
-            Ok(spec) => spec,
-            Err(e) => {
-                return map_rust_error_to_json_response(
-                    format!("Failed to parse job spec JSON: {}", e),
-                    StatusCode::BAD_REQUEST,
-                )
-                .into_response()
-            }
-        }
-    } else {
-        return map_rust_error_to_json_response(
-            "Missing job spec".to_string(),
+    match state.runtime_context.resolve_amendments(&proposal_id).await {
+        Ok(amendments) => (StatusCode::OK, Json(amendments)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Amendment resolution error: {}", e),
             StatusCode::BAD_REQUEST,
         )
-        .into_response();
-    };
-
-    // Build complete ActualMeshJob structure with placeholder values
-    // The runtime will override id, creator_did, and signature
-    let complete_job = icn_mesh::ActualMeshJob {
-        id: icn_mesh::JobId::from(manifest_cid.clone()), // Placeholder, will be overridden
-        manifest_cid,
-        spec: job_spec,
-        creator_did: icn_common::Did::from_str("did:placeholder:creator").unwrap(), // Placeholder, will be overridden
-        cost_mana: request.cost_mana,
-        max_execution_wait_ms: None,
-        signature: icn_identity::SignatureBytes(vec![]), // Placeholder, will be overridden
-    };
+        .into_response(),
+    }
+}
 
-    let job_json = match serde_json::to_string(&complete_job) {
-        Ok(json) => json,
-        Err(e) => {
-            return map_rust_error_to_json_response(
-                format!("Failed to serialize job for submission: {}", e),
-                StatusCode::INTERNAL_SERVER_ERROR,
-            )
-            .into_response()
-        }
-    };
+// POST /governance/comments – Comment on a proposal revision
+async fn gov_comment_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ApiCommentRequest>,
+) -> impl IntoResponse {
+    match icn_runtime::host_comment_on_proposal(
+        &state.runtime_context,
+        &req.proposal_id,
+        req.revision.as_deref(),
+        req.parent.as_deref(),
+        req.text,
+    )
+    .await
+    {
+        Ok(cid) => (StatusCode::CREATED, Json(cid)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Comment error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
 
-    match host_submit_mesh_job(&state.runtime_context, &job_json).await {
-        Ok(actual_job_id_cid) => {
-            info!(
-                "[NODE] Job submitted via runtime, Actual Job ID: {}",
-                actual_job_id_cid
-            );
-            info!(target: "audit", "job_submitted id={}" , actual_job_id_cid);
-            (
-                StatusCode::ACCEPTED,
-                Json(serde_json::json!({ "job_id": actual_job_id_cid.to_string() })),
-            )
-                .into_response()
-        }
-        Err(e) => {
-            error!("[NODE] Error submitting job via runtime: {:?}", e);
-            info!(target: "audit", "job_submission_failed error={}" , e);
-            map_rust_error_to_json_response(
-                format!("Mesh job submission failed: {}", e),
-                StatusCode::INTERNAL_SERVER_ERROR,
-            )
-            .into_response()
+// GET /governance/comments/{proposal_id} – Threaded discussion of a proposal
+async fn gov_comments_handler(
+    State(state): State<AppState>,
+    AxumPath(proposal_id): AxumPath<String>,
+) -> impl IntoResponse {
+    let gov = state.runtime_context.governance_module.lock().await;
+    match gov.deliberation(&icn_governance::ProposalId(proposal_id)) {
+        Ok(d) => {
+            let threads = d.map(|d| d.threads()).unwrap_or_default();
+            (StatusCode::OK, Json(threads)).into_response()
         }
+        Err(e) => map_rust_error_to_json_response(
+            format!("Comment error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
     }
 }
 
-// GET /mesh/jobs - List all jobs with their current status
-async fn mesh_list_jobs_handler(State(state): State<AppState>) -> impl IntoResponse {
-    info!("[Node] Received mesh_list_jobs request");
+// GET /governance/parameters – Governable parameters and current values
+async fn gov_parameters_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let gov = state.runtime_context.governance_module.lock().await;
+    let registry = gov.parameters();
+    let views: Vec<ParameterView> = registry
+        .specs()
+        .into_iter()
+        .map(|spec| ParameterView {
+            name: spec.name.clone(),
+            description: spec.description.clone(),
+            value_type: format!("{:?}", spec.parameter_type()),
+            value: registry
+                .get(&spec.name)
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+            default: spec.default.to_string(),
+            min: spec.min,
+            max: spec.max,
+            hot_reload: spec.hot_reload,
+        })
+        .collect();
+    (StatusCode::OK, Json(views)).into_response()
+}
 
-    let job_states = &state.runtime_context.job_states;
-    let jobs: Vec<serde_json::Value> = job_states
+// GET /governance/parameters/history – Executed parameter changes
+async fn gov_parameter_history_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let gov = state.runtime_context.governance_module.lock().await;
+    let history: Vec<ParameterChangeView> = gov
+        .parameters()
+        .history()
         .iter()
-        .map(|entry| {
-            let job_id = entry.key();
-            let job_state = entry.value();
-            serde_json::json!({
-                "job_id": job_id.to_string(),
-                "status": match job_state {
-                    icn_mesh::JobState::Pending => serde_json::json!("pending"),
-                    icn_mesh::JobState::Assigned { executor } => {
-                        serde_json::json!({
-                            "status": "assigned",
-                            "executor": executor.to_string()
-                        })
-                    },
-                    icn_mesh::JobState::Completed { receipt } => {
-                        serde_json::json!({
-                            "status": "completed",
-                            "executor": receipt.executor_did.to_string(),
-                            "result_cid": receipt.result_cid.to_string(),
-                            "cpu_ms": receipt.cpu_ms
-                        })
-                    },
-                    icn_mesh::JobState::Failed { reason } => {
-                        serde_json::json!({
-                            "status": "failed",
-                            "reason": reason
-                        })
-                    }
-                }
-            })
+        .map(|c| ParameterChangeView {
+            version: c.version,
+            name: c.name.clone(),
+            previous: c.previous.to_string(),
+            value: c.value.to_string(),
+            proposal_id: c.proposal_id.as_ref().map(|p| p.0.clone()),
+            effective_at: c.effective_at,
         })
         .collect();
-
-    (StatusCode::OK, Json(serde_json::json!({ "jobs": jobs }))).into_response()
+    (StatusCode::OK, Json(history)).into_response()
 }
 
-// GET /mesh/jobs/:job_id - Get specific job status
-async fn mesh_get_job_status_handler(
+// POST /governance/parameters/rollback – Propose undoing a parameter change
+async fn gov_parameter_rollback_handler(
     State(state): State<AppState>,
-    AxumPath(job_id_str): AxumPath<String>,
+    Json(req): Json<ApiParameterRollbackRequest>,
 ) -> impl IntoResponse {
-    info!(
-        "[Node] Received mesh_get_job_status request for job: {}",
-        job_id_str
-    );
-
-    // Parse job_id from string
-    let job_id = match parse_cid_from_string(&job_id_str) {
-        Ok(cid) => {
-            info!("[Node] Parsed job_id as CID: {:?}", cid);
-            cid
-        }
-        Err(e) => {
-            error!("[Node] Failed to parse job_id '{}': {}", job_id_str, e);
-            return map_rust_error_to_json_response(
-                format!("Invalid job ID format: {}", e),
-                StatusCode::BAD_REQUEST,
-            )
-            .into_response();
-        }
-    };
-
-    let job_states = &state.runtime_context.job_states;
-    info!(
-        "[Node] Looking for job_id {:?} in {} stored jobs",
-        job_id,
-        job_states.len()
-    );
-
-    // Debug: List all stored job IDs
-    for entry in job_states.iter() {
-        info!("[Node] Stored job ID: {:?}", entry.key());
+    match icn_runtime::host_propose_parameter_rollback(
+        &state.runtime_context,
+        req.version,
+        req.duration_secs.unwrap_or(86_400),
+    )
+    .await
+    {
+        Ok(id) => (StatusCode::CREATED, Json(id)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Parameter rollback error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
     }
+}
 
-    match job_states.get(&icn_mesh::JobId::from(job_id.clone())) {
-        Some(job_state) => {
-            let response = serde_json::json!({
-                "job_id": job_id.to_string(),
-                "status": match &*job_state {
-                    icn_mesh::JobState::Pending => serde_json::json!("pending"),
-                    icn_mesh::JobState::Assigned { executor } => {
-                        serde_json::json!({
-                            "status": "assigned",
-                            "executor": executor.to_string()
-                        })
-                    },
-                    icn_mesh::JobState::Completed { receipt } => {
-                        serde_json::json!({
-                            "status": "completed",
-                            "executor": receipt.executor_did.to_string(),
-                            "result_cid": receipt.result_cid.to_string(),
-                            "cpu_ms": receipt.cpu_ms,
-                            "signature_length": receipt.sig.0.len()
-                        })
-                    },
-                    icn_mesh::JobState::Failed { reason } => {
-                        serde_json::json!({
-                            "status": "failed",
-                            "reason": reason
-                        })
-                    }
-                }
-            });
-            (StatusCode::OK, Json(response)).into_response()
-        }
-        None => {
-            map_rust_error_to_json_response("Job not found", StatusCode::NOT_FOUND).into_response()
-        }
+fn upgrade_view(
+    upgrade: &icn_governance::ScheduledUpgrade,
+    height: u64,
+    now: u64,
+) -> UpgradeStatusView {
+    let manifest = &upgrade.manifest;
+    UpgradeStatusView {
+        proposal_id: upgrade.proposal_id.0.clone(),
+        version: manifest.version.clone(),
+        protocol_version: manifest.protocol_version,
+        artifact_cid: manifest.artifact_cid.to_string(),
+        artifact_sha256: manifest.artifact_sha256.clone(),
+        activation: manifest.activation.to_string(),
+        active: upgrade.is_active(height, now),
+        contracts: manifest
+            .contract_upgrades
+            .iter()
+            .map(|c| c.contract.clone())
+            .collect(),
+        ready_nodes: upgrade
+            .ready_nodes()
+            .iter()
+            .map(|d| d.to_string())
+            .collect(),
     }
 }
 
-/// Request body for submitting an execution receipt
-#[derive(Debug, Serialize, Deserialize)]
-pub struct SubmitReceiptRequest {
-    pub job_id: String,
-    pub executor_did: String,
-    pub result_cid: String,
-    pub cpu_ms: u64,
-    pub success: bool,
-    pub signature_hex: String, // Hex-encoded signature bytes
+async fn upgrade_summary(state: &AppState) -> Result<UpgradeSummary, icn_runtime::HostAbiError> {
+    let height = current_block_height(state);
+    let now = state.runtime_context.time_provider.unix_seconds();
+    let (protocol_version, upgrades) = state.runtime_context.upgrade_status(height).await?;
+    Ok(UpgradeSummary {
+        protocol_version,
+        upgrades: upgrades
+            .iter()
+            .map(|u| upgrade_view(u, height, now))
+            .collect(),
+    })
 }
 
-// POST /mesh/receipts - Submit execution receipt (typically called by executors)
-async fn mesh_submit_receipt_handler(
+// GET /governance/upgrades – Scheduled upgrades and the active protocol version
+async fn gov_upgrades_handler(State(state): State<AppState>) -> impl IntoResponse {
+    match upgrade_summary(&state).await {
+        Ok(summary) => (StatusCode::OK, Json(summary)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Upgrade status error: {}", e),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .into_response(),
+    }
+}
+
+// POST /governance/upgrades/{proposal_id}/ready – Signal this node's readiness
+async fn gov_upgrade_ready_handler(
     State(state): State<AppState>,
-    Json(request): Json<SubmitReceiptRequest>,
+    AxumPath(proposal_id): AxumPath<String>,
 ) -> impl IntoResponse {
-    info!("[Node] Received mesh_submit_receipt request: {:?}", request);
-
-    // Parse request fields
-    let job_id = match parse_cid_from_string(&request.job_id) {
-        Ok(cid) => cid,
-        Err(e) => {
-            return map_rust_error_to_json_response(
-                format!("Invalid job ID format: {}", e),
-                StatusCode::BAD_REQUEST,
-            )
-            .into_response();
-        }
-    };
-
-    let executor_did = match Did::from_str(&request.executor_did) {
-        Ok(did) => did,
-        Err(e) => {
-            return map_rust_error_to_json_response(
-                format!("Invalid executor DID format: {}", e),
-                StatusCode::BAD_REQUEST,
-            )
-            .into_response();
-        }
-    };
-
-    let result_cid = match parse_cid_from_string(&request.result_cid) {
-        Ok(cid) => cid,
-        Err(e) => {
-            return map_rust_error_to_json_response(
-                format!("Invalid result CID format: {}", e),
-                StatusCode::BAD_REQUEST,
-            )
-            .into_response();
-        }
-    };
-
-    let signature_bytes = match hex::decode(&request.signature_hex) {
-        Ok(bytes) => SignatureBytes(bytes),
-        Err(e) => {
-            return map_rust_error_to_json_response(
-                format!("Invalid signature hex format: {}", e),
-                StatusCode::BAD_REQUEST,
-            )
-            .into_response();
-        }
-    };
-
-    // Create execution receipt
-    let execution_receipt = IdentityExecutionReceipt {
-        job_id,
-        executor_did,
-        result_cid,
-        cpu_ms: request.cpu_ms,
-        success: request.success,
-        sig: signature_bytes,
-    };
-
-    let receipt_json = match serde_json::to_string(&execution_receipt) {
-        Ok(json) => json,
-        Err(e) => {
-            return map_rust_error_to_json_response(
-                format!("Failed to serialize receipt: {}", e),
-                StatusCode::INTERNAL_SERVER_ERROR,
-            )
-            .into_response();
-        }
-    };
-
-    // Submit receipt via Host ABI
-    let reputation_updater = ReputationUpdater::new();
-    match host_anchor_receipt(&state.runtime_context, &receipt_json, &reputation_updater).await {
-        Ok(anchored_cid) => {
-            info!(
-                "[Node] Receipt anchored via runtime, Anchored CID: {}",
-                anchored_cid
-            );
-            (
-                StatusCode::ACCEPTED,
-                Json(serde_json::json!({
-                    "anchored_cid": anchored_cid.to_string(),
-                    "receipt_job_id": execution_receipt.job_id.to_string()
-                })),
-            )
-                .into_response()
-        }
-        Err(e) => {
-            error!("[Node] Error anchoring receipt via runtime: {:?}", e);
-            map_rust_error_to_json_response(
-                format!("Receipt anchoring failed: {}", e),
-                StatusCode::INTERNAL_SERVER_ERROR,
-            )
-            .into_response()
-        }
+    match icn_runtime::host_signal_upgrade_readiness(
+        &state.runtime_context,
+        &proposal_id,
+        &state.node_version,
+    )
+    .await
+    {
+        Ok(signal) => (StatusCode::OK, Json(signal)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Upgrade readiness error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
     }
 }
 
-// Stub endpoints for testing mesh job lifecycle
-
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct StubBidRequest {
-    pub job_id: String,
-    pub executor_id: String,
-    pub estimated_cost: u64,
+// POST /governance/upgrades/readiness – Record a signal relayed from another node
+async fn gov_upgrade_readiness_handler(
+    State(state): State<AppState>,
+    Json(signal): Json<icn_governance::ReadinessSignal>,
+) -> impl IntoResponse {
+    match state.runtime_context.ingest_upgrade_readiness(signal).await {
+        Ok(()) => (
+            StatusCode::ACCEPTED,
+            Json(serde_json::json!({"accepted": true})),
+        )
+            .into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Upgrade readiness error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct StubReceiptRequest {
-    pub job_id: String,
-    pub executor_id: String,
-    pub result: serde_json::Value,
+// POST /governance/secret-ballot/commitment – Register a voter commitment
+async fn gov_voter_commitment_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ApiVoterCommitmentRequest>,
+) -> impl IntoResponse {
+    match state
+        .runtime_context
+        .register_voter_commitment(&req.did, &req.commitment)
+        .await
+    {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"registered": true})),
+        )
+            .into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Voter commitment error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
 }
 
-// POST /mesh/stub/bid - inject a bid for testing
-async fn mesh_stub_bid_handler(
+// POST /governance/secret-ballot/{proposal_id}/enable – Switch a proposal to secret ballots
+async fn gov_enable_secret_ballot_handler(
     State(state): State<AppState>,
-    Json(request): Json<StubBidRequest>,
+    AxumPath(proposal_id): AxumPath<String>,
+    Json(req): Json<ApiEnableSecretBallotRequest>,
 ) -> impl IntoResponse {
-    use icn_runtime::context::MeshNetworkServiceType;
+    match state
+        .runtime_context
+        .enable_secret_ballot(
+            &proposal_id,
+            &req.verifying_key,
+            req.tree_depth,
+            req.reveal_from,
+        )
+        .await
+    {
+        Ok(config) => (StatusCode::OK, Json(config)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Secret ballot error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
 
-    // Parse job ID - create a CID from the string
-    let cid = icn_common::Cid::new_v1_sha256(0x55, request.job_id.as_bytes());
-    let job_id = icn_mesh::JobId(cid);
+// GET /governance/secret-ballot/{proposal_id} – Ballots, reveals and verified tally
+async fn gov_secret_ballot_handler(
+    State(state): State<AppState>,
+    AxumPath(proposal_id): AxumPath<String>,
+) -> impl IntoResponse {
+    match state
+        .runtime_context
+        .secret_ballot_status(&proposal_id)
+        .await
+    {
+        Ok(Some((ballot_state, tally))) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"state": ballot_state, "tally": tally})),
+        )
+            .into_response(),
+        Ok(None) => map_rust_error_to_json_response(
+            format!("Proposal {} does not use secret ballots", proposal_id),
+            StatusCode::NOT_FOUND,
+        )
+        .into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Secret ballot error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
 
-    // Parse executor DID
-    let executor_did = match icn_common::Did::from_str(&request.executor_id) {
+// GET /governance/lifecycle/timers – Pending proposal deadlines
+async fn gov_lifecycle_timers_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let gov = state.runtime_context.governance_module.lock().await;
+    match gov.lifecycle_timers() {
+        Ok(timers) => (StatusCode::OK, Json(timers)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Lifecycle timer error: {}", e),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .into_response(),
+    }
+}
+
+// GET /governance/cross-federation – Joint proposals adopted by this federation
+async fn gov_cross_federation_list_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let gov = state.runtime_context.governance_module.lock().await;
+    match gov.cross_federation_proposals() {
+        Ok(proposals) => (StatusCode::OK, Json(proposals)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Cross-federation error: {}", e),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .into_response(),
+    }
+}
+
+// GET /governance/cross-federation/{proposal_id} – Ratification progress of a joint proposal
+async fn gov_cross_federation_handler(
+    State(state): State<AppState>,
+    AxumPath(proposal_id): AxumPath<String>,
+) -> impl IntoResponse {
+    let gov = state.runtime_context.governance_module.lock().await;
+    match gov.cross_federation_proposal(&icn_governance::ProposalId(proposal_id.clone())) {
+        Ok(Some(joint)) => (StatusCode::OK, Json(joint)).into_response(),
+        Ok(None) => map_rust_error_to_json_response(
+            format!("Joint proposal {} has not been adopted", proposal_id),
+            StatusCode::NOT_FOUND,
+        )
+        .into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Cross-federation error: {}", e),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .into_response(),
+    }
+}
+
+// POST /governance/cross-federation/adopt – Open the local vote on a joint proposal
+async fn gov_cross_federation_adopt_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ApiAdoptCrossFederationRequest>,
+) -> impl IntoResponse {
+    match state
+        .runtime_context
+        .adopt_cross_federation_proposal(req.proposal, &req.local_federation, req.duration_secs)
+        .await
+    {
+        Ok(local_proposal_id) => (
+            StatusCode::CREATED,
+            Json(serde_json::json!({ "local_proposal_id": local_proposal_id })),
+        )
+            .into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Cross-federation error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// POST /governance/cross-federation/{proposal_id}/ratify – Sign this federation's outcome
+async fn gov_cross_federation_ratify_handler(
+    State(state): State<AppState>,
+    AxumPath(proposal_id): AxumPath<String>,
+) -> impl IntoResponse {
+    match state
+        .runtime_context
+        .ratify_cross_federation_proposal(&proposal_id)
+        .await
+    {
+        Ok(ratification) => (StatusCode::OK, Json(ratification)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Cross-federation error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// POST /governance/cross-federation/ratification – Record another federation's outcome
+async fn gov_cross_federation_record_handler(
+    State(state): State<AppState>,
+    Json(ratification): Json<icn_governance::FederationRatification>,
+) -> impl IntoResponse {
+    match state
+        .runtime_context
+        .record_federation_ratification(ratification)
+        .await
+    {
+        Ok(cid) => (
+            StatusCode::ACCEPTED,
+            Json(serde_json::json!({ "cid": cid })),
+        )
+            .into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Cross-federation error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// POST /governance/cross-federation/{proposal_id}/execute – Execute a ratified joint proposal
+async fn gov_cross_federation_execute_handler(
+    State(state): State<AppState>,
+    AxumPath(proposal_id): AxumPath<String>,
+) -> impl IntoResponse {
+    match state
+        .runtime_context
+        .execute_cross_federation_proposal(&proposal_id)
+        .await
+    {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "executed": proposal_id })),
+        )
+            .into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Cross-federation error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// GET /governance/constitution – Constitution currently in force
+async fn gov_constitution_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let gov = state.runtime_context.governance_module.lock().await;
+    match gov.constitution().current() {
+        Some(record) => (StatusCode::OK, Json(record.clone())).into_response(),
+        None => map_rust_error_to_json_response(
+            "No constitution has been adopted",
+            StatusCode::NOT_FOUND,
+        )
+        .into_response(),
+    }
+}
+
+// GET /governance/constitution/history – Every adopted constitution version
+async fn gov_constitution_history_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let gov = state.runtime_context.governance_module.lock().await;
+    (StatusCode::OK, Json(gov.constitution().history().to_vec())).into_response()
+}
+
+// GET /governance/constitution/{version} – A specific constitution version
+async fn gov_constitution_version_handler(
+    State(state): State<AppState>,
+    AxumPath(version): AxumPath<u32>,
+) -> impl IntoResponse {
+    let gov = state.runtime_context.governance_module.lock().await;
+    match gov.constitution().version(version) {
+        Some(record) => (StatusCode::OK, Json(record.clone())).into_response(),
+        None => map_rust_error_to_json_response(
+            format!("Constitution version {} not found", version),
+            StatusCode::NOT_FOUND,
+        )
+        .into_response(),
+    }
+}
+
+// POST /governance/constitution – Adopt the first constitution (Body: Constitution JSON)
+async fn gov_adopt_constitution_handler(
+    State(state): State<AppState>,
+    Json(constitution): Json<icn_governance::Constitution>,
+) -> impl IntoResponse {
+    match state.runtime_context.adopt_constitution(constitution).await {
+        Ok(cid) => (StatusCode::CREATED, Json(serde_json::json!({ "cid": cid }))).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Constitution error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// GET /governance/cases – Every due-process case
+async fn gov_cases_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let gov = state.runtime_context.governance_module.lock().await;
+    match gov.due_process_cases() {
+        Ok(cases) => (StatusCode::OK, Json(cases)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Due-process error: {}", e),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .into_response(),
+    }
+}
+
+// GET /governance/cases/{case_id} – A due-process case and its record
+async fn gov_case_handler(
+    State(state): State<AppState>,
+    AxumPath(case_id): AxumPath<String>,
+) -> impl IntoResponse {
+    let gov = state.runtime_context.governance_module.lock().await;
+    match gov.due_process_case(&icn_governance::ProposalId(case_id.clone())) {
+        Ok(Some(case)) => (StatusCode::OK, Json(case)).into_response(),
+        Ok(None) => map_rust_error_to_json_response(
+            format!("Due-process case {} not found", case_id),
+            StatusCode::NOT_FOUND,
+        )
+        .into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Due-process error: {}", e),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .into_response(),
+    }
+}
+
+fn due_process_response(
+    result: Result<icn_governance::DueProcessCase, icn_runtime::HostAbiError>,
+    status: StatusCode,
+) -> axum::response::Response {
+    match result {
+        Ok(case) => (status, Json(case)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Due-process error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// POST /governance/cases – File a signed complaint (Body: Complaint JSON)
+async fn gov_file_complaint_handler(
+    State(state): State<AppState>,
+    Json(complaint): Json<icn_governance::Complaint>,
+) -> impl IntoResponse {
+    due_process_response(
+        state.runtime_context.file_complaint(complaint).await,
+        StatusCode::CREATED,
+    )
+}
+
+// POST /governance/cases/{case_id}/response – The subject's signed response
+async fn gov_case_response_handler(
+    State(state): State<AppState>,
+    AxumPath(case_id): AxumPath<String>,
+    Json(response): Json<icn_governance::SubjectResponse>,
+) -> impl IntoResponse {
+    if response.case_id.0 != case_id {
+        return map_rust_error_to_json_response(
+            "Response is for a different case",
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response();
+    }
+    due_process_response(
+        state.runtime_context.respond_to_complaint(response).await,
+        StatusCode::OK,
+    )
+}
+
+// POST /governance/cases/{case_id}/verdict – A panelist's signed verdict
+async fn gov_case_verdict_handler(
+    State(state): State<AppState>,
+    AxumPath(case_id): AxumPath<String>,
+    Json(verdict): Json<icn_governance::PanelVerdict>,
+) -> impl IntoResponse {
+    if verdict.case_id.0 != case_id {
+        return map_rust_error_to_json_response(
+            "Verdict is for a different case",
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response();
+    }
+    due_process_response(
+        state.runtime_context.submit_panel_verdict(verdict).await,
+        StatusCode::OK,
+    )
+}
+
+// POST /governance/cases/{case_id}/appeal – A party's signed appeal
+async fn gov_case_appeal_handler(
+    State(state): State<AppState>,
+    AxumPath(case_id): AxumPath<String>,
+    Json(appeal): Json<icn_governance::Appeal>,
+) -> impl IntoResponse {
+    if appeal.case_id.0 != case_id {
+        return map_rust_error_to_json_response(
+            "Appeal is for a different case",
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response();
+    }
+    due_process_response(
+        state.runtime_context.file_appeal(appeal).await,
+        StatusCode::OK,
+    )
+}
+
+#[derive(Serialize)]
+struct WeightingOverview {
+    config: icn_governance::WeightingConfig,
+    rules: Vec<(String, icn_governance::WeightRule)>,
+}
+
+#[derive(Deserialize)]
+struct WeightRulePayload {
+    kind: String,
+    /// `None` removes the rule for `kind`.
+    rule: Option<icn_governance::WeightRule>,
+}
+
+#[derive(Deserialize)]
+struct MemberDidPayload {
+    did: String,
+}
+
+fn member_profile_response(
+    result: Result<icn_governance::MemberProfile, icn_runtime::HostAbiError>,
+) -> axum::response::Response {
+    match result {
+        Ok(profile) => (StatusCode::OK, Json(profile)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Weighting error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// GET /governance/weighting – Trusted issuers and weight rules by proposal kind
+async fn gov_weighting_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let gov = state.runtime_context.governance_module.lock().await;
+    match gov.weight_rules() {
+        Ok(rules) => (
+            StatusCode::OK,
+            Json(WeightingOverview {
+                config: gov.weighting_config().clone(),
+                rules,
+            }),
+        )
+            .into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Weighting error: {}", e),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .into_response(),
+    }
+}
+
+// POST /governance/weighting – Set or clear the weight rule for a proposal kind
+async fn gov_set_weight_rule_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<WeightRulePayload>,
+) -> impl IntoResponse {
+    match state
+        .runtime_context
+        .set_weight_rule(&payload.kind, payload.rule)
+        .await
+    {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "kind": payload.kind })),
+        )
+            .into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Weighting error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// POST /governance/weighting/profiles – Size a member from its registered cooperative profile
+async fn gov_weighting_profile_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<MemberDidPayload>,
+) -> impl IntoResponse {
+    let did = match Did::from_str(&payload.did) {
         Ok(did) => did,
-        Err(_) => {
+        Err(e) => {
             return map_rust_error_to_json_response(
-                "Invalid executor DID format",
+                format!("Invalid DID: {}", e),
                 StatusCode::BAD_REQUEST,
             )
             .into_response()
         }
     };
+    match state.cooperative_registry.get_cooperative(&did).await {
+        Ok(Some(profile)) => member_profile_response(
+            state
+                .runtime_context
+                .record_cooperative_profile(&profile)
+                .await,
+        ),
+        Ok(None) => map_rust_error_to_json_response(
+            format!("No cooperative profile registered for {}", did),
+            StatusCode::NOT_FOUND,
+        )
+        .into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Cooperative registry error: {}", e),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .into_response(),
+    }
+}
 
-    // Create a bid
-    let bid = icn_mesh::MeshJobBid {
-        job_id: job_id.clone(),
-        executor_did: executor_did.clone(),
-        price_mana: request.estimated_cost,
-        resources: icn_mesh::Resources {
-            cpu_cores: 1,
-            memory_mb: 128,
-            storage_mb: 0,
-        },
-        executor_capabilities: vec!["test-executor".to_string()],
-        executor_federations: vec![],
-        executor_trust_scope: None,
-        signature: icn_identity::SignatureBytes(vec![0; 64]), // Dummy signature for testing
+// GET /governance/weighting/profiles/{did} – A member's size and chambers
+async fn gov_member_profile_handler(
+    State(state): State<AppState>,
+    AxumPath(did): AxumPath<String>,
+) -> impl IntoResponse {
+    let did = match Did::from_str(&did) {
+        Ok(did) => did,
+        Err(e) => {
+            return map_rust_error_to_json_response(
+                format!("Invalid DID: {}", e),
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response()
+        }
     };
+    let gov = state.runtime_context.governance_module.lock().await;
+    match gov.member_profile(&did) {
+        Ok(profile) => (StatusCode::OK, Json(profile)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Weighting error: {}", e),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .into_response(),
+    }
+}
 
-    // Check if using stub network service
-    match &*state.runtime_context.mesh_network_service {
-        MeshNetworkServiceType::Stub(stub_service) => {
-            stub_service.stage_bid(job_id, bid).await;
-            (
-                StatusCode::OK,
-                Json(serde_json::json!({
-                    "success": true,
-                    "message": "Bid staged successfully",
-                    "job_id": request.job_id,
-                    "executor_id": request.executor_id,
-                    "estimated_cost": request.estimated_cost
-                })),
+// POST /governance/weighting/credentials – Record chambers and size from a membership credential
+async fn gov_weighting_credential_handler(
+    State(state): State<AppState>,
+    Json(credential): Json<icn_identity::Credential>,
+) -> impl IntoResponse {
+    member_profile_response(
+        state
+            .runtime_context
+            .record_weighting_credential(&credential)
+            .await,
+    )
+}
+
+// GET /governance/weighting/tally/{proposal_id} – Weighted and per-chamber totals
+async fn gov_weighted_tally_handler(
+    State(state): State<AppState>,
+    AxumPath(proposal_id): AxumPath<String>,
+) -> impl IntoResponse {
+    let gov = state.runtime_context.governance_module.lock().await;
+    match gov.weighted_tally(&icn_governance::ProposalId(proposal_id.clone())) {
+        Ok(Some(tally)) => (StatusCode::OK, Json(tally)).into_response(),
+        Ok(None) => map_rust_error_to_json_response(
+            format!("Proposal {} is counted one vote per member", proposal_id),
+            StatusCode::NOT_FOUND,
+        )
+        .into_response(),
+        Err(CommonError::ResourceNotFound(e)) => {
+            map_rust_error_to_json_response(e, StatusCode::NOT_FOUND).into_response()
+        }
+        Err(e) => map_rust_error_to_json_response(
+            format!("Weighting error: {}", e),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .into_response(),
+    }
+}
+
+// POST /governance/secret-ballot/cast – Submit an anonymous ballot
+async fn gov_cast_secret_ballot_handler(
+    State(state): State<AppState>,
+    Json(ballot): Json<icn_governance::SecretBallot>,
+) -> impl IntoResponse {
+    match state.runtime_context.cast_secret_ballot(ballot).await {
+        Ok(cid) => (StatusCode::CREATED, Json(serde_json::json!({"cid": cid}))).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Secret ballot error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// POST /governance/secret-ballot/reveal – Open a committed ballot
+async fn gov_reveal_secret_ballot_handler(
+    State(state): State<AppState>,
+    Json(reveal): Json<icn_governance::BallotReveal>,
+) -> impl IntoResponse {
+    match state.runtime_context.reveal_secret_ballot(reveal).await {
+        Ok(cid) => (StatusCode::CREATED, Json(serde_json::json!({"cid": cid}))).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Secret ballot error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+fn delegation_edge(d: &icn_governance::Delegation) -> DelegationEdge {
+    DelegationEdge {
+        from_did: d.delegator.to_string(),
+        to_did: d.delegate.to_string(),
+        scope: d.scope.to_string(),
+        created_at: d.created_at,
+        expires_at: d.expires_at,
+    }
+}
+
+// GET /governance/delegations – Full delegation graph
+async fn gov_delegations_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let gov = state.runtime_context.governance_module.lock().await;
+    let edges: Vec<DelegationEdge> = gov.delegation_graph().iter().map(delegation_edge).collect();
+    (StatusCode::OK, Json(edges)).into_response()
+}
+
+// GET /governance/delegations/{proposal_id} – Delegations and weights for one proposal
+async fn gov_proposal_delegations_handler(
+    State(state): State<AppState>,
+    AxumPath(proposal_id): AxumPath<String>,
+) -> impl IntoResponse {
+    let pid = icn_governance::ProposalId(proposal_id.clone());
+    let gov = state.runtime_context.governance_module.lock().await;
+    let proposal = match gov.get_proposal(&pid) {
+        Ok(Some(p)) => p,
+        Ok(None) => {
+            return map_rust_error_to_json_response(
+                format!("Proposal {} not found", proposal_id),
+                StatusCode::NOT_FOUND,
+            )
+            .into_response()
+        }
+        Err(e) => {
+            return map_rust_error_to_json_response(
+                format!("Governance error: {}", e),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into_response()
+        }
+    };
+    let edges = gov
+        .applicable_delegations(&proposal)
+        .iter()
+        .map(delegation_edge)
+        .collect();
+    let effective_weights = match gov.effective_voting_weights(&pid) {
+        Ok(w) => w.into_iter().map(|(did, n)| (did.to_string(), n)).collect(),
+        Err(e) => {
+            return map_rust_error_to_json_response(
+                format!("Governance error: {}", e),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into_response()
+        }
+    };
+    (
+        StatusCode::OK,
+        Json(ProposalDelegationView {
+            proposal_id,
+            tags: gov.proposal_tags(&pid),
+            edges,
+            effective_weights,
+        }),
+    )
+        .into_response()
+}
+
+// GET /governance/proposals
+async fn gov_list_proposals_handler(State(state): State<AppState>) -> impl IntoResponse {
+    debug!("Received /governance/proposals request");
+    let gov_mod = state.runtime_context.governance_module.lock().await;
+    match gov_mod.list_proposals() {
+        Ok(props) => (StatusCode::OK, Json(props)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Governance list error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// GET /governance/proposal/:proposal_id
+async fn gov_get_proposal_handler(
+    State(state): State<AppState>,
+    AxumPath(proposal_id_str): AxumPath<String>,
+) -> impl IntoResponse {
+    debug!("Received /governance/proposal/{} request", proposal_id_str);
+    let gov_mod = state.runtime_context.governance_module.lock().await;
+    let pid = icn_governance::ProposalId(proposal_id_str);
+    match gov_mod.get_proposal(&pid) {
+        Ok(Some(prop)) => (StatusCode::OK, Json(prop)).into_response(),
+        Ok(None) => map_rust_error_to_json_response("Proposal not found", StatusCode::NOT_FOUND)
+            .into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Governance get error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// POST /governance/close – tally votes and, if accepted, execute proposal
+async fn gov_close_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ProposalIdPayload>,
+) -> impl IntoResponse {
+    let result = icn_runtime::host_close_governance_proposal_voting(
+        &state.runtime_context,
+        &req.proposal_id,
+    )
+    .await;
+    let status_json = match result {
+        Ok(s) => s,
+        Err(e) => {
+            return map_rust_error_to_json_response(
+                format!("Close error: {}", e),
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response()
+        }
+    };
+    let close: icn_api::governance_trait::CloseProposalResponse =
+        match serde_json::from_str(&status_json) {
+            Ok(c) => c,
+            Err(e) => {
+                return map_rust_error_to_json_response(
+                    format!("Serialization error: {}", e),
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )
+                .into_response()
+            }
+        };
+    if close.status == format!("{:?}", icn_governance::ProposalStatus::Accepted) {
+        if let Err(e) =
+            icn_runtime::host_execute_governance_proposal(&state.runtime_context, &req.proposal_id)
+                .await
+        {
+            return map_rust_error_to_json_response(
+                format!("Execute error: {}", e),
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response();
+        }
+    }
+    (StatusCode::OK, Json(close)).into_response()
+}
+
+// POST /governance/execute – force execute an accepted proposal
+async fn gov_execute_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ProposalIdPayload>,
+) -> impl IntoResponse {
+    match icn_runtime::host_execute_governance_proposal(&state.runtime_context, &req.proposal_id)
+        .await
+    {
+        Ok(_) => (StatusCode::OK, Json("executed".to_string())).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Execute error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// POST /governance/conviction/submit – Submit a budget request decided by conviction voting
+async fn gov_conviction_submit_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ApiSubmitConvictionProposalRequest>,
+) -> impl IntoResponse {
+    match icn_runtime::host_submit_conviction_proposal(
+        &state.runtime_context,
+        &req.beneficiary_did,
+        req.amount,
+        req.description,
+    )
+    .await
+    {
+        Ok(id) => (StatusCode::CREATED, Json(icn_governance::ProposalId(id))).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Conviction submit error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// POST /governance/conviction/stake – Stake or withdraw conviction support
+async fn gov_conviction_stake_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ApiConvictionStakeRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = icn_runtime::host_stake_conviction(
+        &state.runtime_context,
+        &req.staker_did,
+        &req.proposal_id,
+        req.amount,
+    )
+    .await
+    {
+        return map_rust_error_to_json_response(
+            format!("Conviction stake error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response();
+    }
+    conviction_status_response(&state, &req.proposal_id).await
+}
+
+// POST /governance/conviction/process – advance conviction and execute proposals that pass
+async fn gov_conviction_process_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let accepted = match icn_runtime::host_process_conviction_voting(&state.runtime_context).await {
+        Ok(ids) => ids,
+        Err(e) => {
+            return map_rust_error_to_json_response(
+                format!("Conviction process error: {}", e),
+                StatusCode::BAD_REQUEST,
             )
+            .into_response()
+        }
+    };
+    for id in &accepted {
+        if let Err(e) =
+            icn_runtime::host_execute_governance_proposal(&state.runtime_context, id).await
+        {
+            warn!("Failed to execute conviction proposal {}: {}", id, e);
+        }
+    }
+    (StatusCode::OK, Json(accepted)).into_response()
+}
+
+// GET /governance/conviction/:proposal_id
+async fn gov_conviction_status_handler(
+    State(state): State<AppState>,
+    AxumPath(proposal_id): AxumPath<String>,
+) -> impl IntoResponse {
+    conviction_status_response(&state, &proposal_id).await
+}
+
+async fn conviction_status_response(
+    state: &AppState,
+    proposal_id: &str,
+) -> axum::response::Response {
+    let ctx = &state.runtime_context;
+    let treasury = ctx.get_mana(&ctx.current_identity).await.unwrap_or(0);
+    let gov = ctx.governance_module.lock().await;
+    let pid = icn_governance::ProposalId(proposal_id.to_string());
+    match gov.conviction_state(&pid, ctx.time_provider.unix_seconds()) {
+        Ok(Some(cs)) => {
+            let threshold =
+                gov.conviction_config()
+                    .threshold(cs.requested, treasury, gov.conviction_supply());
+            let resp = ConvictionStatusResponse {
+                proposal_id: cs.proposal_id.0.clone(),
+                requested: cs.requested,
+                total_staked: cs.total_staked(),
+                conviction: cs.conviction,
+                threshold,
+                passed: cs.passed_at.is_some(),
+            };
+            (StatusCode::OK, Json(resp)).into_response()
+        }
+        Ok(None) => {
+            map_rust_error_to_json_response("Conviction proposal not found", StatusCode::NOT_FOUND)
                 .into_response()
         }
-        _ => map_rust_error_to_json_response(
-            "Stub bid injection only works with stub network service",
-            StatusCode::BAD_REQUEST,
+        Err(e) => map_rust_error_to_json_response(
+            format!("Conviction status error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// --- Mesh Job Endpoints ---
+
+/// Request body for submitting a mesh job.
+/// Note: `id` and `creator_did` will be overridden by the runtime.
+/// Signature also will be handled by the job submission logic after this initial DTO.
+#[derive(Debug, Serialize, Deserialize)] // Added Serialize and Deserialize
+pub struct SubmitJobRequest {
+    pub manifest_cid: String, // String to be parsed into Cid
+    /// Base64 encoded bincode [`JobSpec`] bytes.
+    #[serde(default)]
+    pub spec_bytes: Option<String>,
+    /// Deprecated JSON representation of the job spec.
+    #[serde(default)]
+    pub spec_json: Option<serde_json::Value>,
+    pub cost_mana: u64,
+}
+
+async fn mesh_submit_job_handler(
+    State(state): State<AppState>,
+    Json(request): Json<SubmitJobRequest>,
+) -> impl IntoResponse {
+    info!("[Node] Received mesh_submit_job request: {:?}", request);
+
+    let manifest_cid = match parse_cid_from_string(&request.manifest_cid) {
+        Ok(cid) => cid,
+        Err(e) => {
+            return map_rust_error_to_json_response(
+                format!("Invalid manifest CID: {}", e),
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response()
+        }
+    };
+
+    // Decode job spec from bytes or fallback to deprecated JSON
+    let job_spec = if let Some(b64) = &request.spec_bytes {
+        match base64::engine::general_purpose::STANDARD.decode(b64) {
+            Ok(bytes) => match bincode::deserialize::<icn_mesh::JobSpec>(&bytes) {
+                Ok(spec) => spec,
+                Err(e) => {
+                    return map_rust_error_to_json_response(
+                        format!("Failed to decode job spec bytes: {}", e),
+                        StatusCode::BAD_REQUEST,
+                    )
+                    .into_response()
+                }
+            },
+            Err(e) => {
+                return map_rust_error_to_json_response(
+                    format!("Invalid base64 spec bytes: {}", e),
+                    StatusCode::BAD_REQUEST,
+                )
+                .into_response()
+            }
+        }
+    } else if let Some(json_val) = &request.spec_json {
+        match serde_json::from_value::<icn_mesh::JobSpec>(json_val.clone()) {
+            Ok(spec) => spec,
+            Err(e) => {
+                return map_rust_error_to_json_response(
+                    format!("Failed to parse job spec JSON: {}", e),
+                    StatusCode::BAD_REQUEST,
+                )
+                .into_response()
+            }
+        }
+    } else {
+        return map_rust_error_to_json_response(
+            "Missing job spec".to_string(),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response();
+    };
+
+    // Build complete ActualMeshJob structure with placeholder values
+    // The runtime will override id, creator_did, and signature
+    let complete_job = icn_mesh::ActualMeshJob {
+        id: icn_mesh::JobId::from(manifest_cid.clone()), // Placeholder, will be overridden
+        manifest_cid,
+        spec: job_spec,
+        creator_did: icn_common::Did::from_str("did:placeholder:creator").unwrap(), // Placeholder, will be overridden
+        cost_mana: request.cost_mana,
+        max_execution_wait_ms: None,
+        signature: icn_identity::SignatureBytes(vec![]), // Placeholder, will be overridden
+    };
+
+    let job_json = match serde_json::to_string(&complete_job) {
+        Ok(json) => json,
+        Err(e) => {
+            return map_rust_error_to_json_response(
+                format!("Failed to serialize job for submission: {}", e),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into_response()
+        }
+    };
+
+    match host_submit_mesh_job(&state.runtime_context, &job_json).await {
+        Ok(actual_job_id_cid) => {
+            info!(
+                "[NODE] Job submitted via runtime, Actual Job ID: {}",
+                actual_job_id_cid
+            );
+            info!(target: "audit", "job_submitted id={}" , actual_job_id_cid);
+            (
+                StatusCode::ACCEPTED,
+                Json(serde_json::json!({ "job_id": actual_job_id_cid.to_string() })),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            error!("[NODE] Error submitting job via runtime: {:?}", e);
+            info!(target: "audit", "job_submission_failed error={}" , e);
+            map_rust_error_to_json_response(
+                format!("Mesh job submission failed: {}", e),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into_response()
+        }
+    }
+}
+
+// GET /mesh/jobs - List all jobs with their current status
+async fn mesh_list_jobs_handler(State(state): State<AppState>) -> impl IntoResponse {
+    info!("[Node] Received mesh_list_jobs request");
+
+    let job_states = &state.runtime_context.job_states;
+    let jobs: Vec<serde_json::Value> = job_states
+        .iter()
+        .map(|entry| {
+            let job_id = entry.key();
+            let job_state = entry.value();
+            serde_json::json!({
+                "job_id": job_id.to_string(),
+                "status": match job_state {
+                    icn_mesh::JobState::Pending => serde_json::json!("pending"),
+                    icn_mesh::JobState::Assigned { executor } => {
+                        serde_json::json!({
+                            "status": "assigned",
+                            "executor": executor.to_string()
+                        })
+                    },
+                    icn_mesh::JobState::Completed { receipt } => {
+                        serde_json::json!({
+                            "status": "completed",
+                            "executor": receipt.executor_did.to_string(),
+                            "result_cid": receipt.result_cid.to_string(),
+                            "cpu_ms": receipt.cpu_ms
+                        })
+                    },
+                    icn_mesh::JobState::Failed { reason } => {
+                        serde_json::json!({
+                            "status": "failed",
+                            "reason": reason
+                        })
+                    }
+                }
+            })
+        })
+        .collect();
+
+    (StatusCode::OK, Json(serde_json::json!({ "jobs": jobs }))).into_response()
+}
+
+// GET /mesh/jobs/:job_id - Get specific job status
+async fn mesh_get_job_status_handler(
+    State(state): State<AppState>,
+    AxumPath(job_id_str): AxumPath<String>,
+) -> impl IntoResponse {
+    info!(
+        "[Node] Received mesh_get_job_status request for job: {}",
+        job_id_str
+    );
+
+    // Parse job_id from string
+    let job_id = match parse_cid_from_string(&job_id_str) {
+        Ok(cid) => {
+            info!("[Node] Parsed job_id as CID: {:?}", cid);
+            cid
+        }
+        Err(e) => {
+            error!("[Node] Failed to parse job_id '{}': {}", job_id_str, e);
+            return map_rust_error_to_json_response(
+                format!("Invalid job ID format: {}", e),
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response();
+        }
+    };
+
+    let job_states = &state.runtime_context.job_states;
+    info!(
+        "[Node] Looking for job_id {:?} in {} stored jobs",
+        job_id,
+        job_states.len()
+    );
+
+    // Debug: List all stored job IDs
+    for entry in job_states.iter() {
+        info!("[Node] Stored job ID: {:?}", entry.key());
+    }
+
+    match job_states.get(&icn_mesh::JobId::from(job_id.clone())) {
+        Some(job_state) => {
+            let response = serde_json::json!({
+                "job_id": job_id.to_string(),
+                "status": match &*job_state {
+                    icn_mesh::JobState::Pending => serde_json::json!("pending"),
+                    icn_mesh::JobState::Assigned { executor } => {
+                        serde_json::json!({
+                            "status": "assigned",
+                            "executor": executor.to_string()
+                        })
+                    },
+                    icn_mesh::JobState::Completed { receipt } => {
+                        serde_json::json!({
+                            "status": "completed",
+                            "executor": receipt.executor_did.to_string(),
+                            "result_cid": receipt.result_cid.to_string(),
+                            "cpu_ms": receipt.cpu_ms,
+                            "signature_length": receipt.sig.0.len()
+                        })
+                    },
+                    icn_mesh::JobState::Failed { reason } => {
+                        serde_json::json!({
+                            "status": "failed",
+                            "reason": reason
+                        })
+                    }
+                }
+            });
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        None => {
+            map_rust_error_to_json_response("Job not found", StatusCode::NOT_FOUND).into_response()
+        }
+    }
+}
+
+/// Request body for submitting an execution receipt
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubmitReceiptRequest {
+    pub job_id: String,
+    pub executor_did: String,
+    pub result_cid: String,
+    pub cpu_ms: u64,
+    pub success: bool,
+    pub signature_hex: String, // Hex-encoded signature bytes
+}
+
+// POST /mesh/receipts - Submit execution receipt (typically called by executors)
+async fn mesh_submit_receipt_handler(
+    State(state): State<AppState>,
+    Json(request): Json<SubmitReceiptRequest>,
+) -> impl IntoResponse {
+    info!("[Node] Received mesh_submit_receipt request: {:?}", request);
+
+    // Parse request fields
+    let job_id = match parse_cid_from_string(&request.job_id) {
+        Ok(cid) => cid,
+        Err(e) => {
+            return map_rust_error_to_json_response(
+                format!("Invalid job ID format: {}", e),
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response();
+        }
+    };
+
+    let executor_did = match Did::from_str(&request.executor_did) {
+        Ok(did) => did,
+        Err(e) => {
+            return map_rust_error_to_json_response(
+                format!("Invalid executor DID format: {}", e),
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response();
+        }
+    };
+
+    let result_cid = match parse_cid_from_string(&request.result_cid) {
+        Ok(cid) => cid,
+        Err(e) => {
+            return map_rust_error_to_json_response(
+                format!("Invalid result CID format: {}", e),
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response();
+        }
+    };
+
+    let signature_bytes = match hex::decode(&request.signature_hex) {
+        Ok(bytes) => SignatureBytes(bytes),
+        Err(e) => {
+            return map_rust_error_to_json_response(
+                format!("Invalid signature hex format: {}", e),
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response();
+        }
+    };
+
+    // Create execution receipt
+    let execution_receipt = IdentityExecutionReceipt {
+        job_id,
+        executor_did,
+        result_cid,
+        cpu_ms: request.cpu_ms,
+        success: request.success,
+        sig: signature_bytes,
+    };
+
+    let receipt_json = match serde_json::to_string(&execution_receipt) {
+        Ok(json) => json,
+        Err(e) => {
+            return map_rust_error_to_json_response(
+                format!("Failed to serialize receipt: {}", e),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into_response();
+        }
+    };
+
+    // Submit receipt via Host ABI
+    let reputation_updater = ReputationUpdater::new();
+    match host_anchor_receipt(&state.runtime_context, &receipt_json, &reputation_updater).await {
+        Ok(anchored_cid) => {
+            info!(
+                "[Node] Receipt anchored via runtime, Anchored CID: {}",
+                anchored_cid
+            );
+            (
+                StatusCode::ACCEPTED,
+                Json(serde_json::json!({
+                    "anchored_cid": anchored_cid.to_string(),
+                    "receipt_job_id": execution_receipt.job_id.to_string()
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            error!("[Node] Error anchoring receipt via runtime: {:?}", e);
+            map_rust_error_to_json_response(
+                format!("Receipt anchoring failed: {}", e),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into_response()
+        }
+    }
+}
+
+// Stub endpoints for testing mesh job lifecycle
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StubBidRequest {
+    pub job_id: String,
+    pub executor_id: String,
+    pub estimated_cost: u64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StubReceiptRequest {
+    pub job_id: String,
+    pub executor_id: String,
+    pub result: serde_json::Value,
+}
+
+// POST /mesh/stub/bid - inject a bid for testing
+async fn mesh_stub_bid_handler(
+    State(state): State<AppState>,
+    Json(request): Json<StubBidRequest>,
+) -> impl IntoResponse {
+    use icn_runtime::context::MeshNetworkServiceType;
+
+    // Parse job ID - create a CID from the string
+    let cid = icn_common::Cid::new_v1_sha256(0x55, request.job_id.as_bytes());
+    let job_id = icn_mesh::JobId(cid);
+
+    // Parse executor DID
+    let executor_did = match icn_common::Did::from_str(&request.executor_id) {
+        Ok(did) => did,
+        Err(_) => {
+            return map_rust_error_to_json_response(
+                "Invalid executor DID format",
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response()
+        }
+    };
+
+    // Create a bid
+    let bid = icn_mesh::MeshJobBid {
+        job_id: job_id.clone(),
+        executor_did: executor_did.clone(),
+        price_mana: request.estimated_cost,
+        resources: icn_mesh::Resources {
+            cpu_cores: 1,
+            memory_mb: 128,
+            storage_mb: 0,
+        },
+        executor_capabilities: vec!["test-executor".to_string()],
+        executor_federations: vec![],
+        executor_trust_scope: None,
+        signature: icn_identity::SignatureBytes(vec![0; 64]), // Dummy signature for testing
+    };
+
+    // Check if using stub network service
+    match &*state.runtime_context.mesh_network_service {
+        MeshNetworkServiceType::Stub(stub_service) => {
+            stub_service.stage_bid(job_id, bid).await;
+            (
+                StatusCode::OK,
+                Json(serde_json::json!({
+                    "success": true,
+                    "message": "Bid staged successfully",
+                    "job_id": request.job_id,
+                    "executor_id": request.executor_id,
+                    "estimated_cost": request.estimated_cost
+                })),
+            )
+                .into_response()
+        }
+        _ => map_rust_error_to_json_response(
+            "Stub bid injection only works with stub network service",
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// POST /mesh/stub/receipt - inject a receipt for testing
+async fn mesh_stub_receipt_handler(
+    State(state): State<AppState>,
+    Json(request): Json<StubReceiptRequest>,
+) -> impl IntoResponse {
+    use icn_runtime::context::{LocalMeshSubmitReceiptMessage, MeshNetworkServiceType};
+
+    // Parse job ID - create a CID from the string
+    let cid = icn_common::Cid::new_v1_sha256(0x55, request.job_id.as_bytes());
+    let job_id = icn_mesh::JobId(cid);
+
+    // Parse executor DID
+    let executor_did = match icn_common::Did::from_str(&request.executor_id) {
+        Ok(did) => did,
+        Err(_) => {
+            return map_rust_error_to_json_response(
+                "Invalid executor DID format",
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response()
+        }
+    };
+
+    // Create a dummy result CID
+    let result_cid = icn_common::Cid::new_v1_sha256(0x55, b"test_result");
+
+    // Create execution receipt
+    let receipt = icn_identity::ExecutionReceipt {
+        job_id: job_id.clone().into(),
+        executor_did: executor_did.clone(),
+        result_cid: result_cid.clone(),
+        cpu_ms: 100,
+        success: true,
+        sig: icn_identity::SignatureBytes(vec![0; 64]), // Dummy signature for testing
+    };
+
+    // Create receipt message
+    let receipt_message = LocalMeshSubmitReceiptMessage {
+        receipt: receipt.clone(),
+    };
+
+    // Check if using stub network service
+    match &*state.runtime_context.mesh_network_service {
+        MeshNetworkServiceType::Stub(stub_service) => {
+            stub_service.stage_receipt(job_id, receipt_message).await;
+            (
+                StatusCode::OK,
+                Json(serde_json::json!({
+                    "success": true,
+                    "message": "Receipt staged successfully",
+                    "job_id": request.job_id,
+                    "executor_id": request.executor_id,
+                    "result_cid": result_cid.to_string()
+                })),
+            )
+                .into_response()
+        }
+        _ => map_rust_error_to_json_response(
+            "Stub receipt injection only works with stub network service",
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// GET /mesh/jobs/{job_id}/progress - Get job progress information
+async fn mesh_get_job_progress_handler(
+    State(state): State<AppState>,
+    AxumPath(job_id_str): AxumPath<String>,
+) -> impl IntoResponse {
+    info!(
+        "[Node] Received mesh_get_job_progress request for job: {}",
+        job_id_str
+    );
+
+    // Parse job_id from string
+    let job_id = match parse_cid_from_string(&job_id_str) {
+        Ok(cid) => icn_mesh::JobId::from(cid),
+        Err(e) => {
+            error!("[Node] Failed to parse job_id '{}': {}", job_id_str, e);
+            return map_rust_error_to_json_response(
+                format!("Invalid job ID format: {}", e),
+                StatusCode::BAD_REQUEST,
+            );
+        }
+    };
+
+    // Check if the runtime context has checkpoint manager access
+    // For now, we'll return a basic response - in a real implementation,
+    // this would query the checkpoint manager in the runtime context
+    let response = serde_json::json!({
+        "job_id": job_id_str,
+        "progress": {
+            "current_stage": "processing",
+            "progress_percent": 50.0,
+            "eta_seconds": 120,
+            "message": "Job in progress",
+            "timestamp": chrono::Utc::now().timestamp() as u64,
+            "completed_stages": ["initialization"],
+            "remaining_stages": ["processing", "finalization"]
+        },
+        "checkpoints": [],
+        "partial_outputs": [],
+        "is_running": true,
+        "timestamp": chrono::Utc::now().timestamp() as u64
+    });
+
+    (StatusCode::OK, Json(response))
+}
+
+// GET /mesh/jobs/{job_id}/stream - Get job streaming output
+async fn mesh_get_job_stream_handler(
+    State(_state): State<AppState>,
+    AxumPath(job_id_str): AxumPath<String>,
+    Query(params): Query<std::collections::HashMap<String, String>>,
+) -> impl IntoResponse {
+    info!(
+        "[Node] Received mesh_get_job_stream request for job: {}",
+        job_id_str
+    );
+
+    let from_sequence = params
+        .get("from")
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(0);
+
+    // Parse job_id from string
+    let _job_id = match parse_cid_from_string(&job_id_str) {
+        Ok(cid) => icn_mesh::JobId::from(cid),
+        Err(e) => {
+            error!("[Node] Failed to parse job_id '{}': {}", job_id_str, e);
+            return map_rust_error_to_json_response(
+                format!("Invalid job ID format: {}", e),
+                StatusCode::BAD_REQUEST,
+            );
+        }
+    };
+
+    // For now, return mock streaming data
+    // In a real implementation, this would query the checkpoint manager
+    let stream_chunks = vec![
+        serde_json::json!({
+            "job_id": job_id_str,
+            "sequence": from_sequence,
+            "stage": "processing",
+            "data": BASE64_STANDARD.encode("partial output data chunk 1"),
+            "content_type": "text/plain",
+            "is_final": false,
+            "timestamp": chrono::Utc::now().timestamp() as u64
+        }),
+        serde_json::json!({
+            "job_id": job_id_str,
+            "sequence": from_sequence + 1,
+            "stage": "processing",
+            "data": BASE64_STANDARD.encode("partial output data chunk 2"),
+            "content_type": "text/plain",
+            "is_final": true,
+            "timestamp": chrono::Utc::now().timestamp() as u64
+        }),
+    ];
+
+    (
+        StatusCode::OK,
+        Json(serde_json::Value::Array(stream_chunks)),
+    )
+}
+
+// POST /mesh/jobs/{job_id}/cancel - Cancel a running job
+async fn mesh_cancel_job_handler(
+    State(state): State<AppState>,
+    AxumPath(job_id_str): AxumPath<String>,
+) -> impl IntoResponse {
+    info!(
+        "[Node] Received mesh_cancel_job request for job: {}",
+        job_id_str
+    );
+
+    // Parse job_id from string
+    let job_id = match parse_cid_from_string(&job_id_str) {
+        Ok(cid) => icn_mesh::JobId::from(cid),
+        Err(e) => {
+            error!("[Node] Failed to parse job_id '{}': {}", job_id_str, e);
+            return map_rust_error_to_json_response(
+                format!("Invalid job ID format: {}", e),
+                StatusCode::BAD_REQUEST,
+            );
+        }
+    };
+
+    // Check if job exists in job_states
+    let job_states = &state.runtime_context.job_states;
+    match job_states.get(&job_id) {
+        Some(job_state) => {
+            match &*job_state {
+                icn_mesh::JobState::Pending | icn_mesh::JobState::Assigned { .. } => {
+                    // Update job state to cancelled (for now, we'll just return success)
+                    // In a real implementation, this would signal the executor to stop
+                    info!("[Node] Job {} marked for cancellation", job_id_str);
+
+                    let response = serde_json::json!({
+                        "success": true,
+                        "message": "Job cancellation requested",
+                        "job_id": job_id_str
+                    });
+                    (StatusCode::OK, Json(response))
+                }
+                icn_mesh::JobState::Completed { .. } | icn_mesh::JobState::Failed { .. } => {
+                    let response = serde_json::json!({
+                        "success": false,
+                        "message": "Job already completed, cannot cancel",
+                        "job_id": job_id_str
+                    });
+                    (StatusCode::CONFLICT, Json(response))
+                }
+            }
+        }
+        None => {
+            let response = serde_json::json!({
+                "success": false,
+                "message": "Job not found",
+                "job_id": job_id_str
+            });
+            (StatusCode::NOT_FOUND, Json(response))
+        }
+    }
+}
+
+// POST /mesh/jobs/{job_id}/resume - Resume a job from checkpoint
+async fn mesh_resume_job_handler(
+    State(state): State<AppState>,
+    AxumPath(job_id_str): AxumPath<String>,
+) -> impl IntoResponse {
+    info!(
+        "[Node] Received mesh_resume_job request for job: {}",
+        job_id_str
+    );
+
+    // Parse job_id from string
+    let job_id = match parse_cid_from_string(&job_id_str) {
+        Ok(cid) => icn_mesh::JobId::from(cid),
+        Err(e) => {
+            error!("[Node] Failed to parse job_id '{}': {}", job_id_str, e);
+            return map_rust_error_to_json_response(
+                format!("Invalid job ID format: {}", e),
+                StatusCode::BAD_REQUEST,
+            );
+        }
+    };
+
+    // Check if job exists and has checkpoints
+    let job_states = &state.runtime_context.job_states;
+    match job_states.get(&job_id) {
+        Some(job_state) => {
+            match &*job_state {
+                icn_mesh::JobState::Failed { .. } => {
+                    // In a real implementation, this would check for checkpoints
+                    // and attempt to resume execution from the latest checkpoint
+                    info!(
+                        "[Node] Attempting to resume job {} from checkpoint",
+                        job_id_str
+                    );
+
+                    let response = serde_json::json!({
+                        "success": true,
+                        "message": "Job resume initiated",
+                        "job_id": job_id_str
+                    });
+                    (StatusCode::OK, Json(response))
+                }
+                icn_mesh::JobState::Completed { .. } => {
+                    let response = serde_json::json!({
+                        "success": false,
+                        "message": "Job already completed, cannot resume",
+                        "job_id": job_id_str
+                    });
+                    (StatusCode::CONFLICT, Json(response))
+                }
+                _ => {
+                    let response = serde_json::json!({
+                        "success": false,
+                        "message": "Job is not in a failed state, cannot resume",
+                        "job_id": job_id_str
+                    });
+                    (StatusCode::CONFLICT, Json(response))
+                }
+            }
+        }
+        None => {
+            let response = serde_json::json!({
+                "success": false,
+                "message": "Job not found",
+                "job_id": job_id_str
+            });
+            (StatusCode::NOT_FOUND, Json(response))
+        }
+    }
+}
+
+// GET /mesh/metrics - Get mesh execution metrics
+async fn mesh_get_metrics_handler(State(state): State<AppState>) -> impl IntoResponse {
+    info!("[Node] Received mesh_get_metrics request");
+
+    let job_states = &state.runtime_context.job_states;
+
+    // Calculate basic metrics from job states
+    let total_jobs = job_states.len() as u64;
+    let mut running_jobs = 0u64;
+    let mut completed_jobs = 0u64;
+    let mut failed_jobs = 0u64;
+
+    for job_state in job_states.iter() {
+        match job_state.value() {
+            icn_mesh::JobState::Pending | icn_mesh::JobState::Assigned { .. } => {
+                running_jobs += 1;
+            }
+            icn_mesh::JobState::Completed { .. } => {
+                completed_jobs += 1;
+            }
+            icn_mesh::JobState::Failed { .. } => {
+                failed_jobs += 1;
+            }
+        }
+    }
+
+    let metrics = serde_json::json!({
+        "total_jobs": total_jobs,
+        "running_jobs": running_jobs,
+        "long_running_jobs": 0, // Would be calculated from checkpoint manager
+        "completed_jobs": completed_jobs,
+        "failed_jobs": failed_jobs,
+        "avg_execution_time_secs": 30.0, // Would be calculated from historical data
+        "custom_metrics": {
+            "checkpoint_saves": 0,
+            "partial_outputs": 0,
+            "job_resumes": 0
+        }
+    });
+
+    (StatusCode::OK, Json(metrics))
+}
+
+// GET /federation/peers - list known peers
+async fn federation_list_peers_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let peers = state.peers.lock().await.clone();
+    (StatusCode::OK, Json(peers))
+}
+
+// POST /federation/peers - add a peer identifier
+async fn federation_add_peer_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<AddPeerPayload>,
+) -> impl IntoResponse {
+    let mut peers = state.peers.lock().await;
+    if !peers.contains(&payload.peer) {
+        peers.push(payload.peer.clone());
+        info!(target: "audit", "peer_added peer={}" , payload.peer);
+    }
+    (
+        StatusCode::CREATED,
+        Json(serde_json::json!({ "peer": payload.peer })),
+    )
+}
+
+// POST /federation/join - join a federation via peer identifier
+async fn federation_join_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<PeerPayload>,
+) -> impl IntoResponse {
+    {
+        let mut peers = state.peers.lock().await;
+        if !peers.contains(&payload.peer) {
+            peers.push(payload.peer.clone());
+        }
+    }
+    {
+        let mut cfg = state.config.lock().await;
+        cfg.federation_peers.push(payload.peer.clone());
+    }
+    #[cfg(feature = "enable-libp2p")]
+    if let Ok(service) = state.runtime_context.get_libp2p_service() {
+        let join_msg = ProtocolMessage::new(
+            MessagePayload::FederationJoinRequest(FederationJoinRequestMessage {
+                requesting_node: state.runtime_context.current_identity.clone(),
+                federation_id: "default".to_string(),
+                node_capabilities: NodeCapabilities {
+                    compute_resources: ResourceRequirements::default(),
+                    supported_job_kinds: vec![],
+                    network_bandwidth_mbps: 0,
+                    storage_capacity_gb: 0,
+                    uptime_percentage: 0.0,
+                },
+                referral_from: None,
+            }),
+            Did::default(),
+            None,
+        );
+        if let Err(e) = service.broadcast_message(join_msg).await {
+            error!("Failed to broadcast join: {:?}", e);
+        }
+    }
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({ "joined": payload.peer })),
+    )
+}
+
+// POST /federation/leave - leave a federation / remove peer
+async fn federation_leave_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<PeerPayload>,
+) -> impl IntoResponse {
+    {
+        let mut peers = state.peers.lock().await;
+        peers.retain(|p| p != &payload.peer);
+    }
+    {
+        let mut cfg = state.config.lock().await;
+        cfg.federation_peers.retain(|p| p != &payload.peer);
+    }
+    #[cfg(feature = "enable-libp2p")]
+    if let Ok(service) = state.runtime_context.get_libp2p_service() {
+        let leave_msg = ProtocolMessage::new(
+            MessagePayload::GossipMessage(GossipMessage {
+                topic: "federation_leave".to_string(),
+                payload: payload.peer.clone().into_bytes(),
+                ttl: 1,
+            }),
+            Did::default(),
+            None,
+        );
+        if let Err(e) = service.broadcast_message(leave_msg).await {
+            error!("Failed to broadcast leave: {:?}", e);
+        }
+    }
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({ "left": payload.peer })),
+    )
+}
+
+// GET /federation/status - current federation status
+async fn federation_status_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let peers = state.peers.lock().await.clone();
+    let status = FederationStatus {
+        peer_count: peers.len(),
+        peers,
+    };
+    (StatusCode::OK, Json(status))
+}
+
+// GET /federation/applications - every membership application received
+async fn federation_applications_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let gov = state.runtime_context.governance_module.lock().await;
+    match gov.membership_applications() {
+        Ok(applications) => (StatusCode::OK, Json(applications)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Membership application error: {}", e),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .into_response(),
+    }
+}
+
+// POST /federation/applications - submit a signed membership application
+async fn federation_apply_handler(
+    State(state): State<AppState>,
+    Json(application): Json<icn_governance::MembershipApplication>,
+) -> impl IntoResponse {
+    match state
+        .runtime_context
+        .submit_membership_application(application)
+        .await
+    {
+        Ok(application) => (StatusCode::CREATED, Json(application)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Membership application error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// POST /federation/applications/gate - set the trust gate applicants must pass
+async fn federation_application_gate_handler(
+    State(state): State<AppState>,
+    Json(gate): Json<icn_governance::federation_governance::FederationMembershipTrustGate>,
+) -> impl IntoResponse {
+    let federation = gate.federation.as_str().to_string();
+    match state.runtime_context.set_membership_gate(gate).await {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "federation": federation })),
+        )
+            .into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Membership gate error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// GET /federation/applications/{id} - status of a membership application
+async fn federation_application_handler(
+    State(state): State<AppState>,
+    AxumPath(id): AxumPath<String>,
+) -> impl IntoResponse {
+    let gov = state.runtime_context.governance_module.lock().await;
+    match gov.membership_application(&icn_governance::ProposalId(id.clone())) {
+        Ok(Some(application)) => (StatusCode::OK, Json(application)).into_response(),
+        Ok(None) => map_rust_error_to_json_response(
+            format!("Membership application {} not found", id),
+            StatusCode::NOT_FOUND,
+        )
+        .into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Membership application error: {}", e),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .into_response(),
+    }
+}
+
+// POST /federation/applications/{id}/challenge - answer a verification challenge
+async fn federation_application_challenge_handler(
+    State(state): State<AppState>,
+    AxumPath(id): AxumPath<String>,
+    Json(response): Json<icn_governance::ChallengeResponse>,
+) -> impl IntoResponse {
+    if response.application_id.0 != id {
+        return map_rust_error_to_json_response(
+            "Challenge response is for a different application",
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response();
+    }
+    match state
+        .runtime_context
+        .answer_membership_challenge(response)
+        .await
+    {
+        Ok(application) => (StatusCode::OK, Json(application)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Membership challenge error: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+// POST /federation/init - initialize federation (stub)
+async fn federation_init_handler(State(_state): State<AppState>) -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({ "initialized": true })),
+    )
+}
+
+// POST /federation/sync - trigger federation sync (stub)
+async fn federation_sync_handler(State(_state): State<AppState>) -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({ "sync": "started" })),
+    )
+}
+
+// GET /network/local-peer-id - return this node's peer ID
+async fn network_local_peer_id_handler(State(state): State<AppState>) -> impl IntoResponse {
+    #[cfg(feature = "enable-libp2p")]
+    {
+        match state.runtime_context.get_libp2p_service() {
+            Ok(service) => {
+                let id = service.local_peer_id().to_string();
+                (StatusCode::OK, Json(serde_json::json!({ "peer_id": id }))).into_response()
+            }
+            Err(e) => map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response(),
+        }
+    }
+    #[cfg(not(feature = "enable-libp2p"))]
+    {
+        (
+            StatusCode::OK,
+            Json(serde_json::json!({ "peer_id": "p2p_disabled" })),
+        )
+            .into_response()
+    }
+}
+
+// GET /network/peers - list peers discovered via the network service
+async fn network_peers_handler(State(state): State<AppState>) -> impl IntoResponse {
+    #[cfg(feature = "enable-libp2p")]
+    {
+        match state.runtime_context.get_libp2p_service() {
+            Ok(service) => match service.discover_peers(None).await {
+                Ok(peers) => {
+                    let list: Vec<String> = peers.into_iter().map(|p| p.0).collect();
+                    (StatusCode::OK, Json(list)).into_response()
+                }
+                Err(e) => {
+                    map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response()
+                }
+            },
+            Err(e) => map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response(),
+        }
+    }
+    #[cfg(not(feature = "enable-libp2p"))]
+    {
+        (StatusCode::OK, Json(Vec::<String>::new())).into_response()
+    }
+}
+
+// POST /network/connect - connect to a peer via multiaddress
+async fn network_connect_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<PeerPayload>,
+) -> impl IntoResponse {
+    #[cfg(feature = "enable-libp2p")]
+    {
+        match state.runtime_context.get_libp2p_service() {
+            Ok(service) => match payload.peer.parse::<Multiaddr>() {
+                Ok(addr) => match service.connect_peer(addr).await {
+                    Ok(()) => (
+                        StatusCode::OK,
+                        Json(serde_json::json!({ "connected": payload.peer })),
+                    )
+                        .into_response(),
+                    Err(e) => {
+                        map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response()
+                    }
+                },
+                Err(e) => (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({ "error": format!("invalid multiaddr: {}", e) })),
+                )
+                    .into_response(),
+            },
+            Err(e) => map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response(),
+        }
+    }
+    #[cfg(not(feature = "enable-libp2p"))]
+    {
+        (
+            StatusCode::OK,
+            Json(serde_json::json!({ "connected": payload.peer })),
+        )
+            .into_response()
+    }
+}
+
+// POST /network/discover - trigger active peer discovery
+async fn network_discover_handler(State(state): State<AppState>) -> impl IntoResponse {
+    #[cfg(feature = "enable-libp2p")]
+    {
+        match state.runtime_context.get_libp2p_service() {
+            Ok(service) => match service.discover_peers(None).await {
+                Ok(peers) => {
+                    let peer_list: Vec<String> = peers.into_iter().map(|p| p.0).collect();
+                    (
+                        StatusCode::OK,
+                        Json(serde_json::json!({
+                            "message": "Peer discovery initiated",
+                            "discovered_peers": peer_list.len(),
+                            "peers": peer_list
+                        })),
+                    )
+                        .into_response()
+                }
+                Err(e) => map_rust_error_to_json_response(e, StatusCode::INTERNAL_SERVER_ERROR)
+                    .into_response(),
+            },
+            Err(e) => map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response(),
+        }
+    }
+    #[cfg(not(feature = "enable-libp2p"))]
+    {
+        (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "message": "Peer discovery initiated (stub mode)",
+                "discovered_peers": 0,
+                "peers": []
+            })),
         )
-        .into_response(),
+            .into_response()
     }
 }
 
-// POST /mesh/stub/receipt - inject a receipt for testing
-async fn mesh_stub_receipt_handler(
+// GET /account/:did/mana - return mana balance for an account
+async fn account_mana_handler(
+    AxumPath(did_str): AxumPath<String>,
     State(state): State<AppState>,
-    Json(request): Json<StubReceiptRequest>,
 ) -> impl IntoResponse {
-    use icn_runtime::context::{LocalMeshSubmitReceiptMessage, MeshNetworkServiceType};
-
-    // Parse job ID - create a CID from the string
-    let cid = icn_common::Cid::new_v1_sha256(0x55, request.job_id.as_bytes());
-    let job_id = icn_mesh::JobId(cid);
-
-    // Parse executor DID
-    let executor_did = match icn_common::Did::from_str(&request.executor_id) {
-        Ok(did) => did,
-        Err(_) => {
-            return map_rust_error_to_json_response(
-                "Invalid executor DID format",
-                StatusCode::BAD_REQUEST,
+    match Did::from_str(&did_str) {
+        Ok(did) => match state.runtime_context.get_mana(&did).await {
+            Ok(balance) => (
+                StatusCode::OK,
+                Json(serde_json::json!({ "balance": balance })),
             )
-            .into_response()
+                .into_response(),
+            Err(e) => map_rust_error_to_json_response(
+                format!("Query error: {e}"),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into_response(),
+        },
+        Err(e) => {
+            map_rust_error_to_json_response(format!("Invalid DID: {e}"), StatusCode::BAD_REQUEST)
+                .into_response()
         }
-    };
-
-    // Create a dummy result CID
-    let result_cid = icn_common::Cid::new_v1_sha256(0x55, b"test_result");
-
-    // Create execution receipt
-    let receipt = icn_identity::ExecutionReceipt {
-        job_id: job_id.clone().into(),
-        executor_did: executor_did.clone(),
-        result_cid: result_cid.clone(),
-        cpu_ms: 100,
-        success: true,
-        sig: icn_identity::SignatureBytes(vec![0; 64]), // Dummy signature for testing
-    };
+    }
+}
 
-    // Create receipt message
-    let receipt_message = LocalMeshSubmitReceiptMessage {
-        receipt: receipt.clone(),
-    };
+// GET /keys - return node DID and public key
+async fn keys_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let did = state.runtime_context.current_identity.to_string();
+    let pk_bs58 = bs58::encode(state.runtime_context.signer.public_key_bytes()).into_string();
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({ "did": did, "public_key_bs58": pk_bs58 })),
+    )
+}
 
-    // Check if using stub network service
-    match &*state.runtime_context.mesh_network_service {
-        MeshNetworkServiceType::Stub(stub_service) => {
-            stub_service.stage_receipt(job_id, receipt_message).await;
-            (
-                StatusCode::OK,
-                Json(serde_json::json!({
-                    "success": true,
-                    "message": "Receipt staged successfully",
-                    "job_id": request.job_id,
-                    "executor_id": request.executor_id,
-                    "result_cid": result_cid.to_string()
-                })),
-            )
+// GET /reputation/:did - fetch reputation score
+async fn reputation_handler(
+    AxumPath(did_str): AxumPath<String>,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    match Did::from_str(&did_str) {
+        Ok(did) => {
+            if state.frozen_reputations.contains(&did) {
+                return (
+                    StatusCode::OK,
+                    Json(serde_json::json!({ "score": 0, "frozen": true })),
+                )
+                    .into_response();
+            }
+            let score = state.runtime_context.reputation_store.get_reputation(&did);
+            (StatusCode::OK, Json(serde_json::json!({ "score": score }))).into_response()
+        }
+        Err(e) => {
+            map_rust_error_to_json_response(format!("Invalid DID: {e}"), StatusCode::BAD_REQUEST)
                 .into_response()
         }
-        _ => map_rust_error_to_json_response(
-            "Stub receipt injection only works with stub network service",
-            StatusCode::BAD_REQUEST,
-        )
-        .into_response(),
     }
 }
 
-// GET /mesh/jobs/{job_id}/progress - Get job progress information
-async fn mesh_get_job_progress_handler(
+// POST /identity/generate-proof - generate a credential proof
+async fn zk_generate_handler(
     State(state): State<AppState>,
-    AxumPath(job_id_str): AxumPath<String>,
+    Json(req): Json<icn_api::identity_trait::GenerateProofRequest>,
 ) -> impl IntoResponse {
-    info!(
-        "[Node] Received mesh_get_job_progress request for job: {}",
-        job_id_str
-    );
+    match icn_runtime::generate_zk_proof(&state.runtime_context, &req).await {
+        Ok(proof) => (StatusCode::OK, Json(proof)).into_response(),
+        Err(e) => map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response(),
+    }
+}
 
-    // Parse job_id from string
-    let job_id = match parse_cid_from_string(&job_id_str) {
-        Ok(cid) => icn_mesh::JobId::from(cid),
+// POST /identity/verify - verify a zero-knowledge credential proof
+async fn zk_verify_handler(
+    State(state): State<AppState>,
+    Json(proof): Json<icn_common::ZkCredentialProof>,
+) -> impl IntoResponse {
+    let proof_json = match serde_json::to_string(&proof) {
+        Ok(j) => j,
         Err(e) => {
-            error!("[Node] Failed to parse job_id '{}': {}", job_id_str, e);
             return map_rust_error_to_json_response(
-                format!("Invalid job ID format: {}", e),
+                format!("serialization error: {e}"),
                 StatusCode::BAD_REQUEST,
-            );
+            )
+            .into_response();
         }
     };
 
-    // Check if the runtime context has checkpoint manager access
-    // For now, we'll return a basic response - in a real implementation,
-    // this would query the checkpoint manager in the runtime context
-    let response = serde_json::json!({
-        "job_id": job_id_str,
-        "progress": {
-            "current_stage": "processing",
-            "progress_percent": 50.0,
-            "eta_seconds": 120,
-            "message": "Job in progress",
-            "timestamp": chrono::Utc::now().timestamp() as u64,
-            "completed_stages": ["initialization"],
-            "remaining_stages": ["processing", "finalization"]
-        },
-        "checkpoints": [],
-        "partial_outputs": [],
-        "is_running": true,
-        "timestamp": chrono::Utc::now().timestamp() as u64
-    });
-
-    (StatusCode::OK, Json(response))
+    match icn_runtime::host_verify_zk_proof(&state.runtime_context, &proof_json).await {
+        Ok(true) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "verified": true })),
+        )
+            .into_response(),
+        Ok(false) => {
+            map_rust_error_to_json_response("verification failed", StatusCode::BAD_REQUEST)
+                .into_response()
+        }
+        Err(e) => map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response(),
+    }
 }
 
-// GET /mesh/jobs/{job_id}/stream - Get job streaming output
-async fn mesh_get_job_stream_handler(
-    State(_state): State<AppState>,
-    AxumPath(job_id_str): AxumPath<String>,
-    Query(params): Query<std::collections::HashMap<String, String>>,
+// POST /identity/verify/batch - verify multiple credential proofs
+async fn zk_verify_batch_handler(
+    State(state): State<AppState>,
+    Json(req): Json<VerifyProofsRequest>,
 ) -> impl IntoResponse {
-    info!(
-        "[Node] Received mesh_get_job_stream request for job: {}",
-        job_id_str
-    );
+    use icn_common::ZkProofType;
+    use icn_identity::{
+        BbsVerifier, BulletproofsVerifier, DummyVerifier, Groth16Verifier, ZkVerifier,
+    };
 
-    let from_sequence = params
-        .get("from")
-        .and_then(|s| s.parse::<u64>().ok())
-        .unwrap_or(0);
+    let total = ZK_VERIFY_COST_MANA * req.proofs.len() as u64;
+    if let Err(e) = state
+        .runtime_context
+        .spend_mana(&state.runtime_context.current_identity, total)
+        .await
+    {
+        return map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response();
+    }
 
-    // Parse job_id from string
-    let _job_id = match parse_cid_from_string(&job_id_str) {
-        Ok(cid) => icn_mesh::JobId::from(cid),
-        Err(e) => {
-            error!("[Node] Failed to parse job_id '{}': {}", job_id_str, e);
-            return map_rust_error_to_json_response(
-                format!("Invalid job ID format: {}", e),
-                StatusCode::BAD_REQUEST,
-            );
+    let mut results = Vec::with_capacity(req.proofs.len());
+    for proof in req.proofs.iter() {
+        let verifier: Box<dyn ZkVerifier> = match proof.backend {
+            ZkProofType::Bulletproofs => Box::new(BulletproofsVerifier),
+            ZkProofType::Groth16 => Box::new(Groth16Verifier::default()),
+            ZkProofType::Bbs => {
+                Box::new(BbsVerifier::new(state.runtime_context.did_resolver.clone()))
+            }
+            _ => Box::new(DummyVerifier),
+        };
+
+        match verifier.verify(proof) {
+            Ok(true) => results.push(true),
+            Ok(false) => {
+                results.push(false);
+                let _ = state
+                    .runtime_context
+                    .credit_mana(&state.runtime_context.current_identity, ZK_VERIFY_COST_MANA)
+                    .await;
+            }
+            Err(_) => {
+                results.push(false);
+                let _ = state
+                    .runtime_context
+                    .credit_mana(&state.runtime_context.current_identity, ZK_VERIFY_COST_MANA)
+                    .await;
+            }
         }
-    };
-
-    // For now, return mock streaming data
-    // In a real implementation, this would query the checkpoint manager
-    let stream_chunks = vec![
-        serde_json::json!({
-            "job_id": job_id_str,
-            "sequence": from_sequence,
-            "stage": "processing",
-            "data": BASE64_STANDARD.encode("partial output data chunk 1"),
-            "content_type": "text/plain",
-            "is_final": false,
-            "timestamp": chrono::Utc::now().timestamp() as u64
-        }),
-        serde_json::json!({
-            "job_id": job_id_str,
-            "sequence": from_sequence + 1,
-            "stage": "processing",
-            "data": BASE64_STANDARD.encode("partial output data chunk 2"),
-            "content_type": "text/plain",
-            "is_final": true,
-            "timestamp": chrono::Utc::now().timestamp() as u64
-        }),
-    ];
+    }
 
-    (
-        StatusCode::OK,
-        Json(serde_json::Value::Array(stream_chunks)),
-    )
+    (StatusCode::OK, Json(BatchVerificationResponse { results })).into_response()
 }
 
-// POST /mesh/jobs/{job_id}/cancel - Cancel a running job
-async fn mesh_cancel_job_handler(
+// POST /identity/verify/revocation - verify a revocation proof
+async fn zk_verify_revocation_handler(
     State(state): State<AppState>,
-    AxumPath(job_id_str): AxumPath<String>,
+    Json(proof): Json<icn_common::ZkRevocationProof>,
 ) -> impl IntoResponse {
-    info!(
-        "[Node] Received mesh_cancel_job request for job: {}",
-        job_id_str
-    );
-
-    // Parse job_id from string
-    let job_id = match parse_cid_from_string(&job_id_str) {
-        Ok(cid) => icn_mesh::JobId::from(cid),
+    let proof_json = match serde_json::to_string(&proof) {
+        Ok(j) => j,
         Err(e) => {
-            error!("[Node] Failed to parse job_id '{}': {}", job_id_str, e);
             return map_rust_error_to_json_response(
-                format!("Invalid job ID format: {}", e),
+                format!("serialization error: {e}"),
                 StatusCode::BAD_REQUEST,
-            );
+            )
+            .into_response();
         }
     };
 
-    // Check if job exists in job_states
-    let job_states = &state.runtime_context.job_states;
-    match job_states.get(&job_id) {
-        Some(job_state) => {
-            match &*job_state {
-                icn_mesh::JobState::Pending | icn_mesh::JobState::Assigned { .. } => {
-                    // Update job state to cancelled (for now, we'll just return success)
-                    // In a real implementation, this would signal the executor to stop
-                    info!("[Node] Job {} marked for cancellation", job_id_str);
-
-                    let response = serde_json::json!({
-                        "success": true,
-                        "message": "Job cancellation requested",
-                        "job_id": job_id_str
-                    });
-                    (StatusCode::OK, Json(response))
-                }
-                icn_mesh::JobState::Completed { .. } | icn_mesh::JobState::Failed { .. } => {
-                    let response = serde_json::json!({
-                        "success": false,
-                        "message": "Job already completed, cannot cancel",
-                        "job_id": job_id_str
-                    });
-                    (StatusCode::CONFLICT, Json(response))
-                }
-            }
-        }
-        None => {
-            let response = serde_json::json!({
-                "success": false,
-                "message": "Job not found",
-                "job_id": job_id_str
-            });
-            (StatusCode::NOT_FOUND, Json(response))
+    match icn_runtime::host_verify_zk_revocation_proof(&state.runtime_context, &proof_json).await {
+        Ok(true) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "verified": true })),
+        )
+            .into_response(),
+        Ok(false) => {
+            map_rust_error_to_json_response("verification failed", StatusCode::BAD_REQUEST)
+                .into_response()
         }
+        Err(e) => map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response(),
     }
 }
 
-// POST /mesh/jobs/{job_id}/resume - Resume a job from checkpoint
-async fn mesh_resume_job_handler(
+// POST /identity/credentials/issue - offer a credential; the holder accepts it
+// at /identity/issuance/requests
+async fn credential_issue_handler(
     State(state): State<AppState>,
-    AxumPath(job_id_str): AxumPath<String>,
+    Json(req): Json<IssueCredentialRequest>,
 ) -> impl IntoResponse {
-    info!(
-        "[Node] Received mesh_resume_job request for job: {}",
-        job_id_str
-    );
-
-    // Parse job_id from string
-    let job_id = match parse_cid_from_string(&job_id_str) {
-        Ok(cid) => icn_mesh::JobId::from(cid),
-        Err(e) => {
-            error!("[Node] Failed to parse job_id '{}': {}", job_id_str, e);
-            return map_rust_error_to_json_response(
-                format!("Invalid job ID format: {}", e),
-                StatusCode::BAD_REQUEST,
-            );
-        }
+    if req.issuer != state.runtime_context.current_identity {
+        return issuance_error_response(
+            "Offer not made",
+            CommonError::PermissionDenied(format!(
+                "node issues credentials only as {}",
+                state.runtime_context.current_identity
+            )),
+        );
+    }
+    let now = state.runtime_context.time_provider.unix_seconds();
+    if req.expiration <= now {
+        return issuance_error_response(
+            "Offer not made",
+            CommonError::InvalidInputError("expiration is in the past".into()),
+        );
+    }
+    let terms = icn_identity::OfferTerms {
+        holder: req.holder,
+        schema: req.schema,
+        credential_type: req.credential_type,
+        claims: req.attributes,
+        cooperative_id: req.cooperative_id,
+        validity: Some(req.expiration - now),
     };
+    open_issuance_offer(&state, terms).await
+}
 
-    // Check if job exists and has checkpoints
-    let job_states = &state.runtime_context.job_states;
-    match job_states.get(&job_id) {
-        Some(job_state) => {
-            match &*job_state {
-                icn_mesh::JobState::Failed { .. } => {
-                    // In a real implementation, this would check for checkpoints
-                    // and attempt to resume execution from the latest checkpoint
-                    info!(
-                        "[Node] Attempting to resume job {} from checkpoint",
-                        job_id_str
-                    );
-
-                    let response = serde_json::json!({
-                        "success": true,
-                        "message": "Job resume initiated",
-                        "job_id": job_id_str
-                    });
-                    (StatusCode::OK, Json(response))
-                }
-                icn_mesh::JobState::Completed { .. } => {
-                    let response = serde_json::json!({
-                        "success": false,
-                        "message": "Job already completed, cannot resume",
-                        "job_id": job_id_str
-                    });
-                    (StatusCode::CONFLICT, Json(response))
-                }
-                _ => {
-                    let response = serde_json::json!({
-                        "success": false,
-                        "message": "Job is not in a failed state, cannot resume",
-                        "job_id": job_id_str
-                    });
-                    (StatusCode::CONFLICT, Json(response))
-                }
+/// Sign every claim of `cred` with the node key and store it, allocating a
+/// status list entry when the node is the issuer.
+async fn sign_and_store_credential(
+    state: &AppState,
+    mut cred: Credential,
+) -> axum::response::Response {
+    // Only credentials issued under the node's own DID can be tracked in
+    // the status lists the node signs.
+    if &cred.issuer == state.revocation_registry.issuer() {
+        match state.revocation_registry.allocate() {
+            Ok(entry) => cred.status = Some(entry),
+            Err(e) => {
+                return map_rust_error_to_json_response(e, StatusCode::INTERNAL_SERVER_ERROR)
+                    .into_response();
             }
         }
-        None => {
-            let response = serde_json::json!({
-                "success": false,
-                "message": "Job not found",
-                "job_id": job_id_str
-            });
-            (StatusCode::NOT_FOUND, Json(response))
-        }
     }
-}
-
-// GET /mesh/metrics - Get mesh execution metrics
-async fn mesh_get_metrics_handler(State(state): State<AppState>) -> impl IntoResponse {
-    info!("[Node] Received mesh_get_metrics request");
-
-    let job_states = &state.runtime_context.job_states;
-
-    // Calculate basic metrics from job states
-    let total_jobs = job_states.len() as u64;
-    let mut running_jobs = 0u64;
-    let mut completed_jobs = 0u64;
-    let mut failed_jobs = 0u64;
 
-    for job_state in job_states.iter() {
-        match job_state.value() {
-            icn_mesh::JobState::Pending | icn_mesh::JobState::Assigned { .. } => {
-                running_jobs += 1;
+    let claims: Vec<(String, String)> = cred
+        .claims
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    for (k, v) in claims {
+        let bytes = cred.claim_signing_bytes(&k, &v);
+        match state.runtime_context.signer.sign(&bytes) {
+            Ok(sig) => {
+                cred.signatures.insert(k, SignatureBytes(sig));
             }
-            icn_mesh::JobState::Completed { .. } => {
-                completed_jobs += 1;
+            Err(e) => {
+                return map_rust_error_to_json_response(e, StatusCode::INTERNAL_SERVER_ERROR)
+                    .into_response();
             }
-            icn_mesh::JobState::Failed { .. } => {
-                failed_jobs += 1;
+        }
+    }
+
+    let bytes = match serde_json::to_vec(&cred) {
+        Ok(b) => b,
+        Err(e) => {
+            return map_rust_error_to_json_response(e, StatusCode::INTERNAL_SERVER_ERROR)
+                .into_response();
+        }
+    };
+    let cid = Cid::new_v1_sha256(0x71, &bytes);
+    state.credential_store.insert(cid.clone(), cred.clone());
+    match &cred.status {
+        Some(entry) => {
+            state
+                .revocation_registry
+                .record_with_status(cid.clone(), entry);
+            let now = state.runtime_context.time_provider.unix_seconds();
+            let published = state.runtime_context.status_lists.get(
+                &cred.issuer,
+                &entry.status_list,
+                icn_identity::StatusPurpose::Revocation,
+                now,
+            );
+            if matches!(published, Ok(None)) {
+                publish_credential_status(state, &cid, icn_identity::StatusPurpose::Revocation)
+                    .await;
             }
         }
+        None => state.revocation_registry.record(cid.clone()),
     }
 
-    let metrics = serde_json::json!({
-        "total_jobs": total_jobs,
-        "running_jobs": running_jobs,
-        "long_running_jobs": 0, // Would be calculated from checkpoint manager
-        "completed_jobs": completed_jobs,
-        "failed_jobs": failed_jobs,
-        "avg_execution_time_secs": 30.0, // Would be calculated from historical data
-        "custom_metrics": {
-            "checkpoint_saves": 0,
-            "partial_outputs": 0,
-            "job_resumes": 0
+    (
+        StatusCode::CREATED,
+        Json(CredentialResponse {
+            cid,
+            credential: cred,
+        }),
+    )
+        .into_response()
+}
+
+fn issuance_error_response(context: &str, e: CommonError) -> axum::response::Response {
+    let status = match e {
+        CommonError::ResourceNotFound(_) => StatusCode::NOT_FOUND,
+        CommonError::PermissionDenied(_) | CommonError::PolicyDenied(_) => StatusCode::FORBIDDEN,
+        CommonError::LockError(_)
+        | CommonError::IoError(_)
+        | CommonError::SerializationError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        _ => StatusCode::BAD_REQUEST,
+    };
+    map_rust_error_to_json_response(format!("{}: {}", context, e), status).into_response()
+}
+
+// POST /identity/schemas – Publish a credential schema under the node DID
+async fn credential_schema_publish_handler(
+    State(state): State<AppState>,
+    Json(req): Json<PublishSchemaRequest>,
+) -> impl IntoResponse {
+    let schema = match icn_identity::CredentialSchema::new(
+        state.runtime_context.current_identity.clone(),
+        &req.name,
+        &req.version,
+        req.json_schema,
+        state.runtime_context.time_provider.unix_seconds(),
+    ) {
+        Ok(schema) => schema,
+        Err(e) => return issuance_error_response("Schema not published", e),
+    };
+    match state
+        .runtime_context
+        .publish_credential_schema(schema)
+        .await
+    {
+        Ok(cid) => match state.runtime_context.credential_schemas.get(&cid) {
+            Some(schema) => {
+                (StatusCode::CREATED, Json(SchemaResponse { cid, schema })).into_response()
+            }
+            None => map_rust_error_to_json_response(
+                "Schema published but not cached",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into_response(),
+        },
+        Err(e) => {
+            map_rust_error_to_json_response(e, StatusCode::INTERNAL_SERVER_ERROR).into_response()
         }
-    });
-
-    (StatusCode::OK, Json(metrics))
+    }
 }
 
-// GET /federation/peers - list known peers
-async fn federation_list_peers_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let peers = state.peers.lock().await.clone();
-    (StatusCode::OK, Json(peers))
+// GET /identity/schemas – List published credential schemas
+async fn credential_schema_list_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let schemas: Vec<SchemaResponse> = state
+        .runtime_context
+        .credential_schemas
+        .list()
+        .into_iter()
+        .map(|(cid, schema)| SchemaResponse { cid, schema })
+        .collect();
+    (StatusCode::OK, Json(schemas)).into_response()
 }
 
-// POST /federation/peers - add a peer identifier
-async fn federation_add_peer_handler(
+// GET /identity/schemas/{cid} – Fetch a published credential schema
+async fn credential_schema_get_handler(
+    AxumPath(cid_str): AxumPath<String>,
     State(state): State<AppState>,
-    Json(payload): Json<AddPeerPayload>,
 ) -> impl IntoResponse {
-    let mut peers = state.peers.lock().await;
-    if !peers.contains(&payload.peer) {
-        peers.push(payload.peer.clone());
-        info!(target: "audit", "peer_added peer={}" , payload.peer);
+    let cid = match parse_cid_from_string(&cid_str) {
+        Ok(cid) => cid,
+        Err(e) => return issuance_error_response("Invalid schema CID", e),
+    };
+    match state.runtime_context.credential_schema(&cid).await {
+        Ok(Some(schema)) => (StatusCode::OK, Json(SchemaResponse { cid, schema })).into_response(),
+        Ok(None) => map_rust_error_to_json_response("Schema not found", StatusCode::NOT_FOUND)
+            .into_response(),
+        Err(e) => {
+            map_rust_error_to_json_response(e, StatusCode::INTERNAL_SERVER_ERROR).into_response()
+        }
     }
-    (
-        StatusCode::CREATED,
-        Json(serde_json::json!({ "peer": payload.peer })),
-    )
 }
 
-// POST /federation/join - join a federation via peer identifier
-async fn federation_join_handler(
+// POST /identity/issuance/policies – Set who may issue a cooperative's credential type
+async fn issuance_policy_set_handler(
     State(state): State<AppState>,
-    Json(payload): Json<PeerPayload>,
+    Json(policy): Json<icn_identity::IssuerPolicy>,
 ) -> impl IntoResponse {
-    {
-        let mut peers = state.peers.lock().await;
-        if !peers.contains(&payload.peer) {
-            peers.push(payload.peer.clone());
-        }
+    let (cooperative_id, credential_type) = (
+        policy.cooperative_id.clone(),
+        policy.credential_type.clone(),
+    );
+    if let Err(e) = state.issuance_registry.set_policy(policy) {
+        return issuance_error_response("Policy not set", e);
     }
+    match state
+        .issuance_registry
+        .policy(&cooperative_id, &credential_type)
     {
-        let mut cfg = state.config.lock().await;
-        cfg.federation_peers.push(payload.peer.clone());
-    }
-    #[cfg(feature = "enable-libp2p")]
-    if let Ok(service) = state.runtime_context.get_libp2p_service() {
-        let join_msg = ProtocolMessage::new(
-            MessagePayload::FederationJoinRequest(FederationJoinRequestMessage {
-                requesting_node: state.runtime_context.current_identity.clone(),
-                federation_id: "default".to_string(),
-                node_capabilities: NodeCapabilities {
-                    compute_resources: ResourceRequirements::default(),
-                    supported_job_kinds: vec![],
-                    network_bandwidth_mbps: 0,
-                    storage_capacity_gb: 0,
-                    uptime_percentage: 0.0,
-                },
-                referral_from: None,
-            }),
-            Did::default(),
-            None,
-        );
-        if let Err(e) = service.broadcast_message(join_msg).await {
-            error!("Failed to broadcast join: {:?}", e);
-        }
+        Ok(policy) => (StatusCode::OK, Json(policy)).into_response(),
+        Err(e) => issuance_error_response("Policy unavailable", e),
     }
-    (
-        StatusCode::OK,
-        Json(serde_json::json!({ "joined": payload.peer })),
-    )
 }
 
-// POST /federation/leave - leave a federation / remove peer
-async fn federation_leave_handler(
+// GET /identity/issuance/policies?cooperative_id= – List issuer policies
+async fn issuance_policy_list_handler(
     State(state): State<AppState>,
-    Json(payload): Json<PeerPayload>,
+    Query(params): Query<std::collections::HashMap<String, String>>,
 ) -> impl IntoResponse {
+    match state
+        .issuance_registry
+        .policies(params.get("cooperative_id").map(String::as_str))
     {
-        let mut peers = state.peers.lock().await;
-        peers.retain(|p| p != &payload.peer);
-    }
-    {
-        let mut cfg = state.config.lock().await;
-        cfg.federation_peers.retain(|p| p != &payload.peer);
-    }
-    #[cfg(feature = "enable-libp2p")]
-    if let Ok(service) = state.runtime_context.get_libp2p_service() {
-        let leave_msg = ProtocolMessage::new(
-            MessagePayload::GossipMessage(GossipMessage {
-                topic: "federation_leave".to_string(),
-                payload: payload.peer.clone().into_bytes(),
-                ttl: 1,
-            }),
-            Did::default(),
-            None,
-        );
-        if let Err(e) = service.broadcast_message(leave_msg).await {
-            error!("Failed to broadcast leave: {:?}", e);
-        }
+        Ok(policies) => (StatusCode::OK, Json(policies)).into_response(),
+        Err(e) => issuance_error_response("Policies unavailable", e),
     }
-    (
-        StatusCode::OK,
-        Json(serde_json::json!({ "left": payload.peer })),
-    )
 }
 
-// GET /federation/status - current federation status
-async fn federation_status_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let peers = state.peers.lock().await.clone();
-    let status = FederationStatus {
-        peer_count: peers.len(),
-        peers,
-    };
-    (StatusCode::OK, Json(status))
+// POST /identity/issuance/offers – Offer a credential from the node DID to a holder
+async fn issuance_offer_handler(
+    State(state): State<AppState>,
+    Json(terms): Json<icn_identity::OfferTerms>,
+) -> impl IntoResponse {
+    open_issuance_offer(&state, terms).await
 }
 
-// POST /federation/init - initialize federation (stub)
-async fn federation_init_handler(State(_state): State<AppState>) -> impl IntoResponse {
-    (
-        StatusCode::OK,
-        Json(serde_json::json!({ "initialized": true })),
-    )
+/// Open an offer under the node DID once the schema is known and the
+/// cooperative's policy allows it.
+async fn open_issuance_offer(
+    state: &AppState,
+    terms: icn_identity::OfferTerms,
+) -> axum::response::Response {
+    let schema = match state.runtime_context.credential_schema(&terms.schema).await {
+        Ok(Some(schema)) => schema,
+        Ok(None) => {
+            return issuance_error_response(
+                "Offer not made",
+                CommonError::ResourceNotFound(format!("credential schema {}", terms.schema)),
+            )
+        }
+        Err(e) => {
+            return map_rust_error_to_json_response(e, StatusCode::INTERNAL_SERVER_ERROR)
+                .into_response();
+        }
+    };
+    match state.issuance_registry.offer(
+        &state.runtime_context.current_identity,
+        terms,
+        &schema,
+        state.runtime_context.time_provider.unix_seconds(),
+    ) {
+        Ok(offer) => (StatusCode::CREATED, Json(offer)).into_response(),
+        Err(e) => issuance_error_response("Offer not made", e),
+    }
 }
 
-// POST /federation/sync - trigger federation sync (stub)
-async fn federation_sync_handler(State(_state): State<AppState>) -> impl IntoResponse {
-    (
-        StatusCode::OK,
-        Json(serde_json::json!({ "sync": "started" })),
-    )
+// GET /identity/issuance/offers/{id} – Fetch an open offer for the holder to sign
+async fn issuance_offer_get_handler(
+    AxumPath(id): AxumPath<String>,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    match state.issuance_registry.pending(&id) {
+        Ok(Some(offer)) => (StatusCode::OK, Json(offer)).into_response(),
+        Ok(None) => issuance_error_response("Offer unavailable", CommonError::ResourceNotFound(id)),
+        Err(e) => issuance_error_response("Offer unavailable", e),
+    }
 }
 
-// GET /network/local-peer-id - return this node's peer ID
-async fn network_local_peer_id_handler(State(state): State<AppState>) -> impl IntoResponse {
-    #[cfg(feature = "enable-libp2p")]
-    {
-        match state.runtime_context.get_libp2p_service() {
-            Ok(service) => {
-                let id = service.local_peer_id().to_string();
-                (StatusCode::OK, Json(serde_json::json!({ "peer_id": id }))).into_response()
-            }
-            Err(e) => map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response(),
-        }
-    }
-    #[cfg(not(feature = "enable-libp2p"))]
+// POST /identity/issuance/requests – Issue the credential for a holder-signed offer
+async fn issuance_request_handler(
+    State(state): State<AppState>,
+    Json(req): Json<icn_identity::IssuanceRequest>,
+) -> impl IntoResponse {
+    let now = state.runtime_context.time_provider.unix_seconds();
+    match state
+        .issuance_registry
+        .accept(&req, state.runtime_context.did_resolver.as_ref(), now)
     {
-        (
-            StatusCode::OK,
-            Json(serde_json::json!({ "peer_id": "p2p_disabled" })),
-        )
-            .into_response()
+        Ok(offer) => sign_and_store_credential(&state, offer.credential(now)).await,
+        Err(e) => issuance_error_response("Credential not issued", e),
     }
 }
 
-// GET /network/peers - list peers discovered via the network service
-async fn network_peers_handler(State(state): State<AppState>) -> impl IntoResponse {
-    #[cfg(feature = "enable-libp2p")]
-    {
-        match state.runtime_context.get_libp2p_service() {
-            Ok(service) => match service.discover_peers(None).await {
-                Ok(peers) => {
-                    let list: Vec<String> = peers.into_iter().map(|p| p.0).collect();
-                    (StatusCode::OK, Json(list)).into_response()
-                }
-                Err(e) => {
-                    map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response()
-                }
-            },
-            Err(e) => map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response(),
-        }
-    }
-    #[cfg(not(feature = "enable-libp2p"))]
-    {
-        (StatusCode::OK, Json(Vec::<String>::new())).into_response()
-    }
+fn personhood_response(state: &AppState, did: Did) -> Result<PersonhoodResponse, CommonError> {
+    let now = state.runtime_context.time_provider.unix_seconds();
+    Ok(PersonhoodResponse {
+        verified: state.personhood_registry.is_verified(&did, now)?,
+        record: state.personhood_registry.record(&did)?,
+        proof: state.personhood_registry.proof_of_personhood(&did, now)?,
+        vouches: state.personhood_registry.vouches_for(&did)?,
+        did,
+    })
 }
 
-// POST /network/connect - connect to a peer via multiaddress
-async fn network_connect_handler(
+// POST /identity/personhood/seeds – Trust a governance-approved DID as a verified person anchoring Sybil detection
+async fn personhood_seed_handler(
     State(state): State<AppState>,
-    Json(payload): Json<PeerPayload>,
+    Json(req): Json<PersonhoodRequest>,
 ) -> impl IntoResponse {
-    #[cfg(feature = "enable-libp2p")]
-    {
-        match state.runtime_context.get_libp2p_service() {
-            Ok(service) => match payload.peer.parse::<Multiaddr>() {
-                Ok(addr) => match service.connect_peer(addr).await {
-                    Ok(()) => (
-                        StatusCode::OK,
-                        Json(serde_json::json!({ "connected": payload.peer })),
-                    )
-                        .into_response(),
-                    Err(e) => {
-                        map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response()
-                    }
-                },
-                Err(e) => (
-                    StatusCode::BAD_REQUEST,
-                    Json(serde_json::json!({ "error": format!("invalid multiaddr: {}", e) })),
-                )
-                    .into_response(),
-            },
-            Err(e) => map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response(),
+    let approved = state
+        .runtime_context
+        .governance_module
+        .lock()
+        .await
+        .personhood_seeds();
+    match approved {
+        Ok(seeds) if seeds.contains(&req.did) => {}
+        Ok(_) => {
+            return issuance_error_response(
+                "Seed not added",
+                CommonError::PolicyDenied(format!(
+                    "{} is not a personhood seed approved by governance",
+                    req.did
+                )),
+            )
         }
+        Err(e) => return issuance_error_response("Seed not added", e),
     }
-    #[cfg(not(feature = "enable-libp2p"))]
-    {
-        (
-            StatusCode::OK,
-            Json(serde_json::json!({ "connected": payload.peer })),
-        )
-            .into_response()
+    let result = state
+        .personhood_registry
+        .add_seed(req.did.clone())
+        .and_then(|_| personhood_response(&state, req.did));
+    match result {
+        Ok(resp) => (StatusCode::OK, Json(resp)).into_response(),
+        Err(e) => issuance_error_response("Seed not added", e),
     }
 }
 
-// POST /network/discover - trigger active peer discovery
-async fn network_discover_handler(State(state): State<AppState>) -> impl IntoResponse {
-    #[cfg(feature = "enable-libp2p")]
-    {
-        match state.runtime_context.get_libp2p_service() {
-            Ok(service) => match service.discover_peers(None).await {
-                Ok(peers) => {
-                    let peer_list: Vec<String> = peers.into_iter().map(|p| p.0).collect();
-                    (
-                        StatusCode::OK,
-                        Json(serde_json::json!({
-                            "message": "Peer discovery initiated",
-                            "discovered_peers": peer_list.len(),
-                            "peers": peer_list
-                        })),
-                    )
-                        .into_response()
-                }
-                Err(e) => map_rust_error_to_json_response(e, StatusCode::INTERNAL_SERVER_ERROR)
-                    .into_response(),
-            },
-            Err(e) => map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response(),
+// POST /identity/personhood/vouches – Record a signed vouch, escrowing a mana stake
+async fn personhood_vouch_handler(
+    State(state): State<AppState>,
+    Json(vouch): Json<icn_identity::Vouch>,
+) -> impl IntoResponse {
+    let ctx = &state.runtime_context;
+    let key = match ctx.did_resolver.resolve(&vouch.voucher) {
+        Ok(key) => key,
+        Err(e) => return issuance_error_response("Vouch rejected", e),
+    };
+    // Check the signature before escrowing anything from the voucher.
+    if let Err(e) = vouch.verify(&key) {
+        return issuance_error_response("Vouch rejected", e);
+    }
+    let (voucher, vouchee, stake) = (vouch.voucher.clone(), vouch.vouchee.clone(), vouch.stake);
+    if let icn_identity::VouchStake::Mana(amount) = stake {
+        if let Err(e) = ctx.spend_mana(&voucher, amount).await {
+            return map_rust_error_to_json_response(
+                format!("Vouch stake not escrowed: {e}"),
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response();
         }
     }
-    #[cfg(not(feature = "enable-libp2p"))]
+    // Reputation stakes of the voucher's active vouches must fit within its
+    // current reputation; the registry checks this when recording the vouch.
+    let reputation = ctx.reputation_store.get_reputation(&voucher);
+    let now = ctx.time_provider.unix_seconds();
+    match state
+        .personhood_registry
+        .vouch(vouch, &key, reputation, now)
     {
-        (
-            StatusCode::OK,
-            Json(serde_json::json!({
-                "message": "Peer discovery initiated (stub mode)",
-                "discovered_peers": 0,
-                "peers": []
-            })),
-        )
-            .into_response()
+        Ok(replaced) => {
+            if let Some(icn_identity::VouchStake::Mana(amount)) = replaced.map(|v| v.stake) {
+                if let Err(e) = ctx.credit_mana(&voucher, amount).await {
+                    error!(
+                        "Failed to release replaced vouch stake of {}: {}",
+                        voucher, e
+                    );
+                }
+            }
+            info!(target: "audit", "personhood_vouch voucher={} vouchee={}", voucher, vouchee);
+            match personhood_response(&state, vouchee) {
+                Ok(resp) => (StatusCode::CREATED, Json(resp)).into_response(),
+                Err(e) => issuance_error_response("Personhood unavailable", e),
+            }
+        }
+        Err(e) => {
+            if let icn_identity::VouchStake::Mana(amount) = stake {
+                if let Err(e) = ctx.credit_mana(&voucher, amount).await {
+                    error!("Failed to return vouch stake of {}: {}", voucher, e);
+                }
+            }
+            issuance_error_response("Vouch rejected", e)
+        }
     }
 }
 
-// GET /account/:did/mana - return mana balance for an account
-async fn account_mana_handler(
-    AxumPath(did_str): AxumPath<String>,
+// POST /identity/personhood/evaluate – Re-run Sybil detection and re-verify personhood now
+async fn personhood_evaluate_handler(State(state): State<AppState>) -> impl IntoResponse {
+    match reverify_personhood(&state).await {
+        Ok(evaluation) => (StatusCode::OK, Json(evaluation)).into_response(),
+        Err(e) => issuance_error_response("Evaluation failed", e),
+    }
+}
+
+// POST /identity/personhood/revoke – Revoke a Sybil's personhood and slash its vouchers
+async fn personhood_revoke_handler(
     State(state): State<AppState>,
+    Json(req): Json<RevokePersonhoodRequest>,
 ) -> impl IntoResponse {
-    match Did::from_str(&did_str) {
-        Ok(did) => match state.runtime_context.get_mana(&did).await {
-            Ok(balance) => (
-                StatusCode::OK,
-                Json(serde_json::json!({ "balance": balance })),
-            )
-                .into_response(),
-            Err(e) => map_rust_error_to_json_response(
-                format!("Query error: {e}"),
-                StatusCode::INTERNAL_SERVER_ERROR,
-            )
-            .into_response(),
-        },
-        Err(e) => {
-            map_rust_error_to_json_response(format!("Invalid DID: {e}"), StatusCode::BAD_REQUEST)
-                .into_response()
+    let forfeited = match state.personhood_registry.revoke(&req.did, &req.reason) {
+        Ok(forfeited) => forfeited,
+        Err(e) => return issuance_error_response("Personhood not revoked", e),
+    };
+    // Escrowed mana stays forfeited; reputation stakes are slashed here.
+    for vouch in &forfeited {
+        if let icn_identity::VouchStake::Reputation(amount) = vouch.stake {
+            state
+                .runtime_context
+                .reputation_store
+                .slash(&vouch.voucher, amount);
         }
     }
+    revoke_personhood_credentials(&state, &req.did).await;
+    info!(target: "audit", "personhood_revoked did={} reason={}", req.did, req.reason);
+    (StatusCode::OK, Json(forfeited)).into_response()
 }
 
-// GET /keys - return node DID and public key
-async fn keys_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let did = state.runtime_context.current_identity.to_string();
-    let pk_bs58 = bs58::encode(state.runtime_context.signer.public_key_bytes()).into_string();
-    (
-        StatusCode::OK,
-        Json(serde_json::json!({ "did": did, "public_key_bs58": pk_bs58 })),
-    )
+// GET /identity/personhood/{did} – Personhood status, proof and vouches of a DID
+async fn personhood_status_handler(
+    AxumPath(did): AxumPath<String>,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    let result = Did::from_str(&did)
+        .map_err(|e| CommonError::InvalidInputError(format!("Invalid DID: {e}")))
+        .and_then(|did| personhood_response(&state, did));
+    match result {
+        Ok(resp) => (StatusCode::OK, Json(resp)).into_response(),
+        Err(e) => issuance_error_response("Personhood unavailable", e),
+    }
 }
 
-// GET /reputation/:did - fetch reputation score
-async fn reputation_handler(
-    AxumPath(did_str): AxumPath<String>,
+// POST /identity/personhood/credential – Issue a personhood credential to a verified person
+async fn personhood_credential_handler(
     State(state): State<AppState>,
+    Json(req): Json<PersonhoodRequest>,
 ) -> impl IntoResponse {
-    match Did::from_str(&did_str) {
-        Ok(did) => {
-            if state.frozen_reputations.contains(&did) {
-                return (
+    match state.personhood_registry.credential(
+        &state.runtime_context.current_identity,
+        &req.did,
+        state.runtime_context.time_provider.unix_seconds(),
+    ) {
+        Ok(credential) => sign_and_store_credential(&state, credential).await,
+        Err(e) => issuance_error_response("Credential not issued", e),
+    }
+}
+
+// GET /identity/credentials/{cid}
+async fn credential_get_handler(
+    AxumPath(cid_str): AxumPath<String>,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    match parse_cid_from_string(&cid_str) {
+        Ok(cid) => {
+            if state.paused_credentials.contains(&cid) {
+                return map_rust_error_to_json_response("Credential paused", StatusCode::FORBIDDEN)
+                    .into_response();
+            }
+            match state.credential_store.get(&cid) {
+                Some(cred) => (
                     StatusCode::OK,
-                    Json(serde_json::json!({ "score": 0, "frozen": true })),
+                    Json(CredentialResponse {
+                        cid,
+                        credential: cred,
+                    }),
                 )
-                    .into_response();
+                    .into_response(),
+                None => {
+                    map_rust_error_to_json_response("Credential not found", StatusCode::NOT_FOUND)
+                        .into_response()
+                }
             }
-            let score = state.runtime_context.reputation_store.get_reputation(&did);
-            (StatusCode::OK, Json(serde_json::json!({ "score": score }))).into_response()
         }
         Err(e) => {
-            map_rust_error_to_json_response(format!("Invalid DID: {e}"), StatusCode::BAD_REQUEST)
+            map_rust_error_to_json_response(format!("Invalid CID: {e}"), StatusCode::BAD_REQUEST)
                 .into_response()
         }
     }
 }
 
-// POST /identity/generate-proof - generate a credential proof
-async fn zk_generate_handler(
+fn recovery_error_response(context: &str, e: CommonError) -> axum::response::Response {
+    let status = match e {
+        CommonError::ResourceNotFound(_) => StatusCode::NOT_FOUND,
+        CommonError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        CommonError::PolicyDenied(_) => StatusCode::CONFLICT,
+        CommonError::LockError(_)
+        | CommonError::IoError(_)
+        | CommonError::SerializationError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        _ => StatusCode::BAD_REQUEST,
+    };
+    map_rust_error_to_json_response(format!("{}: {}", context, e), status).into_response()
+}
+
+// POST /identity/recovery/config – Register the guardians protecting a did:icn next key
+async fn recovery_config_handler(
     State(state): State<AppState>,
-    Json(req): Json<icn_api::identity_trait::GenerateProofRequest>,
+    Json(config): Json<icn_identity::SocialRecoveryConfig>,
 ) -> impl IntoResponse {
-    match icn_runtime::generate_zk_proof(&state.runtime_context, &req).await {
-        Ok(proof) => (StatusCode::OK, Json(proof)).into_response(),
-        Err(e) => map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response(),
+    let now = state.runtime_context.time_provider.unix_seconds();
+    let result = state
+        .runtime_context
+        .did_icn
+        .state(&config.did)
+        .and_then(|key_state| {
+            state
+                .recovery_registry
+                .configure(config.clone(), &key_state, now)
+        });
+    match result {
+        Ok(()) => (StatusCode::OK, Json(config)).into_response(),
+        Err(e) => recovery_error_response("Recovery configuration rejected", e),
     }
 }
 
-// POST /identity/verify - verify a zero-knowledge credential proof
-async fn zk_verify_handler(
+// GET /identity/recovery/config/{did} – Guardians and sealed shares of a DID
+async fn recovery_config_get_handler(
     State(state): State<AppState>,
-    Json(proof): Json<icn_common::ZkCredentialProof>,
+    AxumPath(did): AxumPath<String>,
 ) -> impl IntoResponse {
-    let proof_json = match serde_json::to_string(&proof) {
-        Ok(j) => j,
+    let did = match Did::from_str(&did) {
+        Ok(did) => did,
         Err(e) => {
             return map_rust_error_to_json_response(
-                format!("serialization error: {e}"),
+                format!("Invalid DID: {}", e),
                 StatusCode::BAD_REQUEST,
             )
-            .into_response();
+            .into_response()
         }
     };
-
-    match icn_runtime::host_verify_zk_proof(&state.runtime_context, &proof_json).await {
-        Ok(true) => (
-            StatusCode::OK,
-            Json(serde_json::json!({ "verified": true })),
+    match state.recovery_registry.config(&did) {
+        Some(config) => (StatusCode::OK, Json(config)).into_response(),
+        None => map_rust_error_to_json_response(
+            format!("No recovery configuration for {}", did),
+            StatusCode::NOT_FOUND,
         )
-            .into_response(),
-        Ok(false) => {
-            map_rust_error_to_json_response("verification failed", StatusCode::BAD_REQUEST)
+        .into_response(),
+    }
+}
+
+// POST /identity/recovery/cases – Open a recovery, starting the cancellation delay
+async fn recovery_open_handler(
+    State(state): State<AppState>,
+    Json(req): Json<OpenRecoveryRequest>,
+) -> impl IntoResponse {
+    let recovery_key = match <[u8; 32]>::try_from(req.recovery_key.as_slice())
+        .ok()
+        .and_then(|bytes| icn_identity::VerifyingKey::from_bytes(&bytes).ok())
+    {
+        Some(key) => key,
+        None => {
+            return map_rust_error_to_json_response("Invalid recovery key", StatusCode::BAD_REQUEST)
                 .into_response()
         }
-        Err(e) => map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response(),
+    };
+    let now = state.runtime_context.time_provider.unix_seconds();
+    let result = req
+        .guardian
+        .as_ref()
+        .map(|guardian| {
+            state
+                .runtime_context
+                .did_resolver
+                .resolve(guardian)
+                .map(|key| (guardian, key))
+        })
+        .transpose()
+        .and_then(|guardian| {
+            let key_state = state.runtime_context.did_icn.state(&req.did)?;
+            state.recovery_registry.open_case(
+                &req.did,
+                &recovery_key,
+                guardian.as_ref().map(|(did, key)| (*did, key)),
+                &req.signature,
+                &key_state,
+                now,
+            )
+        });
+    match result {
+        Ok(case) => (StatusCode::CREATED, Json(case)).into_response(),
+        Err(e) => recovery_error_response("Recovery not opened", e),
     }
 }
 
-// POST /identity/verify/batch - verify multiple credential proofs
-async fn zk_verify_batch_handler(
+// GET /identity/recovery/cases/{id} – Approvals and status of a recovery
+async fn recovery_case_handler(
     State(state): State<AppState>,
-    Json(req): Json<VerifyProofsRequest>,
+    AxumPath(id): AxumPath<String>,
 ) -> impl IntoResponse {
-    use icn_common::ZkProofType;
-    use icn_identity::{BulletproofsVerifier, DummyVerifier, Groth16Verifier, ZkVerifier};
+    match state.recovery_registry.case(&id) {
+        Some(case) => (StatusCode::OK, Json(case)).into_response(),
+        None => map_rust_error_to_json_response(
+            format!("No recovery case {}", id),
+            StatusCode::NOT_FOUND,
+        )
+        .into_response(),
+    }
+}
 
-    let total = ZK_VERIFY_COST_MANA * req.proofs.len() as u64;
-    if let Err(e) = state
+// POST /identity/recovery/cases/{id}/approve – Record a guardian's signed approval
+async fn recovery_approve_handler(
+    State(state): State<AppState>,
+    AxumPath(id): AxumPath<String>,
+    Json(req): Json<ApproveRecoveryRequest>,
+) -> impl IntoResponse {
+    let now = state.runtime_context.time_provider.unix_seconds();
+    let result = state
         .runtime_context
-        .spend_mana(&state.runtime_context.current_identity, total)
-        .await
-    {
-        return map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response();
+        .did_resolver
+        .resolve(&req.approval.guardian)
+        .and_then(|guardian_key| {
+            state
+                .recovery_registry
+                .approve(&id, req.approval, req.share, &guardian_key, now)
+        });
+    match result {
+        Ok(case) => (StatusCode::OK, Json(case)).into_response(),
+        Err(e) => recovery_error_response("Approval rejected", e),
     }
+}
 
-    let mut results = Vec::with_capacity(req.proofs.len());
-    for proof in req.proofs.iter() {
-        let verifier: Box<dyn ZkVerifier> = match proof.backend {
-            ZkProofType::Bulletproofs => Box::new(BulletproofsVerifier),
-            ZkProofType::Groth16 => Box::new(Groth16Verifier::default()),
-            _ => Box::new(DummyVerifier),
-        };
+// POST /identity/recovery/cases/{id}/cancel – Cancel a recovery with the current key
+async fn recovery_cancel_handler(
+    State(state): State<AppState>,
+    AxumPath(id): AxumPath<String>,
+    Json(req): Json<CancelRecoveryRequest>,
+) -> impl IntoResponse {
+    let Some(case) = state.recovery_registry.case(&id) else {
+        return map_rust_error_to_json_response(
+            format!("No recovery case {}", id),
+            StatusCode::NOT_FOUND,
+        )
+        .into_response();
+    };
+    let now = state.runtime_context.time_provider.unix_seconds();
+    let result = state
+        .runtime_context
+        .did_icn
+        .state(&case.did)
+        .and_then(|key_state| {
+            state
+                .recovery_registry
+                .cancel(&id, &req.signature, &key_state, now)
+        });
+    match result {
+        Ok(case) => (StatusCode::OK, Json(case)).into_response(),
+        Err(e) => recovery_error_response("Cancellation rejected", e),
+    }
+}
 
-        match verifier.verify(proof) {
-            Ok(true) => results.push(true),
-            Ok(false) => {
-                results.push(false);
-                let _ = state
-                    .runtime_context
-                    .credit_mana(&state.runtime_context.current_identity, ZK_VERIFY_COST_MANA)
-                    .await;
+// GET /identity/recovery/cases/{id}/shares – Released shares once the recovery is executable
+async fn recovery_shares_handler(
+    State(state): State<AppState>,
+    AxumPath(id): AxumPath<String>,
+) -> impl IntoResponse {
+    let now = state.runtime_context.time_provider.unix_seconds();
+    match state.recovery_registry.released(&id, now) {
+        Ok(case) => (StatusCode::OK, Json(case)).into_response(),
+        Err(e) => recovery_error_response("Shares not released", e),
+    }
+}
+
+// POST /identity/recovery/cases/{id}/complete – Record the rotation signed by the recovered key
+async fn recovery_complete_handler(
+    State(state): State<AppState>,
+    AxumPath(id): AxumPath<String>,
+    Json(event): Json<icn_identity::KeyEvent>,
+) -> impl IntoResponse {
+    let now = state.runtime_context.time_provider.unix_seconds();
+    if let Err(e) = state.recovery_registry.check_completion(&id, &event, now) {
+        return recovery_error_response("Recovery not completed", e);
+    }
+    let key_state = match state.runtime_context.submit_key_event(event).await {
+        Ok(key_state) => key_state,
+        Err(e) => {
+            return map_rust_error_to_json_response(
+                format!("Key event rejected: {}", e),
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response()
+        }
+    };
+    match state.recovery_registry.complete(&id, now) {
+        Ok(_) => (StatusCode::OK, Json(key_state)).into_response(),
+        Err(e) => recovery_error_response("Recovery not completed", e),
+    }
+}
+
+fn didcomm_error_response(context: &str, e: CommonError) -> axum::response::Response {
+    let status = match e {
+        CommonError::ResourceNotFound(_) => StatusCode::NOT_FOUND,
+        CommonError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+        CommonError::PolicyDenied(_) => StatusCode::CONFLICT,
+        CommonError::NetworkError(_) => StatusCode::BAD_GATEWAY,
+        CommonError::LockError(_)
+        | CommonError::IoError(_)
+        | CommonError::SerializationError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        _ => StatusCode::BAD_REQUEST,
+    };
+    map_rust_error_to_json_response(format!("{}: {}", context, e), status).into_response()
+}
+
+/// Encrypt `message` to `to` and relay it, through `mediator` or the
+/// recipient's published mediator when there is one. Returns the next hop.
+async fn send_didcomm(
+    state: &AppState,
+    message: &icn_identity::DidCommMessage,
+    to: &Did,
+    anonymous: bool,
+    mediator: Option<Did>,
+) -> Result<Did, CommonError> {
+    let ctx = &state.runtime_context;
+    let own_did = &ctx.current_identity;
+    let recipients = [(to.clone(), ctx.did_resolver.resolve(to)?)];
+    let envelope = if anonymous {
+        icn_identity::pack_anoncrypt(message, &recipients)?
+    } else {
+        icn_identity::pack_authcrypt(message, own_did, &*ctx.signer, &recipients)?
+    };
+    let mediator = match mediator {
+        Some(mediator) => Some(mediator),
+        None => ctx.didcomm_route(to).await?,
+    };
+    match mediator {
+        Some(mediator) if mediator == *own_did => {
+            let now = ctx.time_provider.unix_seconds();
+            state.didcomm_mailbox.enqueue(to, &envelope, now)?;
+            Ok(mediator)
+        }
+        Some(mediator) if mediator != *to => {
+            let mediator_key = ctx.did_resolver.resolve(&mediator)?;
+            let forward = icn_identity::wrap_forward(&envelope, to, &mediator, &mediator_key)?;
+            ctx.mesh_network_service
+                .send_didcomm_envelope(&mediator, forward)
+                .await?;
+            Ok(mediator)
+        }
+        _ => {
+            ctx.mesh_network_service
+                .send_didcomm_envelope(to, envelope)
+                .await?;
+            Ok(to.clone())
+        }
+    }
+}
+
+/// Tell WebSocket subscribers that a message reached the inbox.
+fn notify_didcomm(state: &AppState, unpacked: &icn_identity::UnpackedMessage) {
+    let _ = state.ws_broadcaster.send(WebSocketEvent::NetworkEvent {
+        event_type: "didcomm_message".into(),
+        data: serde_json::json!({
+            "id": unpacked.message.id,
+            "type": unpacked.message.message_type,
+            "from": unpacked.sender,
+        }),
+    });
+}
+
+/// Unpack an envelope addressed to this node and act on it.
+///
+/// Returns the packed reply when the sender asked for one on the same
+/// connection and `inline_reply` is set; other replies are relayed.
+async fn receive_didcomm(
+    state: &AppState,
+    envelope: &str,
+    inline_reply: bool,
+) -> Result<Option<String>, CommonError> {
+    use icn_identity::didcomm::{DELIVERY_TYPE, FORWARD_TYPE, MESSAGES_RECEIVED_TYPE};
+
+    let ctx = &state.runtime_context;
+    let own_did = &ctx.current_identity;
+    let mut envelope = envelope.to_string();
+    let unpacked = loop {
+        let unpacked = icn_identity::unpack(&envelope, own_did, &*ctx.signer, &*ctx.did_resolver)?;
+        if unpacked.message.message_type == FORWARD_TYPE {
+            let (next, inner) = unpacked.message.forward_payload()?;
+            if next == *own_did {
+                envelope = inner;
+                continue;
             }
-            Err(_) => {
-                results.push(false);
-                let _ = state
-                    .runtime_context
-                    .credit_mana(&state.runtime_context.current_identity, ZK_VERIFY_COST_MANA)
-                    .await;
+        }
+        break unpacked;
+    };
+
+    let sender = unpacked.sender.clone();
+    if let (DELIVERY_TYPE, Some(mediator)) = (unpacked.message.message_type.as_str(), &sender) {
+        let mut received = Vec::new();
+        for (id, inner) in unpacked.message.delivered_envelopes()? {
+            match icn_identity::unpack(&inner, own_did, &*ctx.signer, &*ctx.did_resolver) {
+                Ok(message) => {
+                    notify_didcomm(state, &message);
+                    state
+                        .didcomm_mailbox
+                        .deliver(message, ctx.time_provider.unix_seconds())?;
+                }
+                Err(e) => warn!("Dropping undecryptable DIDComm message {}: {}", id, e),
+            }
+            received.push(id);
+        }
+        let ack = unpacked
+            .message
+            .reply(
+                MESSAGES_RECEIVED_TYPE,
+                serde_json::json!({ "message_id_list": received }),
+            )
+            .with_sender(own_did);
+        send_didcomm(state, &ack, mediator, false, Some(mediator.clone())).await?;
+        return Ok(None);
+    }
+
+    let return_route = unpacked.message.return_route.as_deref() == Some("all");
+    let now = ctx.time_provider.unix_seconds();
+    let reply = match state
+        .didcomm_mailbox
+        .handle(own_did, unpacked.clone(), now)?
+    {
+        icn_identity::MailboxOutcome::Reply(reply) => reply,
+        icn_identity::MailboxOutcome::Inbox => {
+            notify_didcomm(state, &unpacked);
+            return Ok(None);
+        }
+        icn_identity::MailboxOutcome::Granted { mediator } => {
+            ctx.publish_didcomm_route(own_did, &mediator).await?;
+            return Ok(None);
+        }
+        _ => return Ok(None),
+    };
+    let Some(sender) = sender else {
+        return Ok(None);
+    };
+    if inline_reply && return_route {
+        let recipients = [(sender.clone(), ctx.did_resolver.resolve(&sender)?)];
+        return icn_identity::pack_authcrypt(&reply, own_did, &*ctx.signer, &recipients).map(Some);
+    }
+    send_didcomm(state, &reply, &sender, false, Some(sender.clone())).await?;
+    Ok(None)
+}
+
+/// Relay DIDComm envelopes arriving over the mesh to [`receive_didcomm`].
+async fn spawn_didcomm_listener(state: AppState) {
+    let ctx = state.runtime_context.clone();
+    ctx.spawn_didcomm_listener(move |envelope| {
+        let state = state.clone();
+        tokio::spawn(async move {
+            if let Err(e) = receive_didcomm(&state, &envelope, false).await {
+                warn!("Rejected DIDComm envelope: {}", e);
             }
+        });
+    })
+    .await;
+}
+
+// POST /didcomm – Receive an encrypted DIDComm message
+async fn didcomm_receive_handler(
+    State(state): State<AppState>,
+    envelope: String,
+) -> impl IntoResponse {
+    match receive_didcomm(&state, &envelope, true).await {
+        Ok(Some(reply)) => (
+            StatusCode::OK,
+            [(
+                axum::http::header::CONTENT_TYPE,
+                icn_identity::didcomm::ENCRYPTED_TYP,
+            )],
+            reply,
+        )
+            .into_response(),
+        Ok(None) => StatusCode::ACCEPTED.into_response(),
+        Err(e) => didcomm_error_response("DIDComm message rejected", e),
+    }
+}
+
+async fn send_didcomm_response(
+    state: &AppState,
+    message: icn_identity::DidCommMessage,
+    to: &Did,
+    anonymous: bool,
+    mediator: Option<Did>,
+) -> axum::response::Response {
+    match send_didcomm(state, &message, to, anonymous, mediator).await {
+        Ok(next_hop) => (
+            StatusCode::OK,
+            Json(SendDidCommResponse {
+                id: message.id,
+                next_hop,
+            }),
+        )
+            .into_response(),
+        Err(e) => didcomm_error_response("DIDComm message not sent", e),
+    }
+}
+
+// POST /didcomm/send – Send a DIDComm message from the node's DID
+async fn didcomm_send_handler(
+    State(state): State<AppState>,
+    Json(req): Json<SendDidCommRequest>,
+) -> impl IntoResponse {
+    let own_did = &state.runtime_context.current_identity;
+    let mut message =
+        icn_identity::DidCommMessage::new(&req.message_type, req.body).with_recipient(&req.to);
+    if !req.anonymous {
+        message = message.with_sender(own_did);
+    }
+    if let Some(thid) = &req.thid {
+        message = message.with_thread(thid);
+    }
+    send_didcomm_response(&state, message, &req.to, req.anonymous, req.mediator).await
+}
+
+// GET /didcomm/messages – Messages received by the node's DID
+async fn didcomm_messages_handler(State(state): State<AppState>) -> impl IntoResponse {
+    match state.didcomm_mailbox.inbox() {
+        Ok(messages) => (StatusCode::OK, Json(messages)).into_response(),
+        Err(e) => didcomm_error_response("Inbox unavailable", e),
+    }
+}
+
+// GET /didcomm/mediated – DIDs this node mediates for
+async fn didcomm_mediated_handler(State(state): State<AppState>) -> impl IntoResponse {
+    match state.didcomm_mailbox.mediated() {
+        Ok(mediated) => (StatusCode::OK, Json(mediated)).into_response(),
+        Err(e) => didcomm_error_response("Mediation state unavailable", e),
+    }
+}
+
+// POST /didcomm/mediation – Ask another node to mediate for this node's DID
+async fn didcomm_mediation_handler(
+    State(state): State<AppState>,
+    Json(req): Json<MediatorRequest>,
+) -> impl IntoResponse {
+    let message = icn_identity::DidCommMessage::new(
+        icn_identity::didcomm::MEDIATE_REQUEST_TYPE,
+        serde_json::json!({}),
+    )
+    .with_sender(&state.runtime_context.current_identity)
+    .with_recipient(&req.mediator);
+    if let Err(e) = state
+        .didcomm_mailbox
+        .request_mediation(&req.mediator, &message.id)
+    {
+        return didcomm_error_response("Mediation not requested", e);
+    }
+    let mediator = req.mediator.clone();
+    send_didcomm_response(&state, message, &req.mediator, false, Some(mediator)).await
+}
+
+// POST /didcomm/mediation/allowed – Let a DID request mediation from this node
+async fn didcomm_mediation_allow_handler(
+    State(state): State<AppState>,
+    Json(req): Json<MediationAllowRequest>,
+) -> impl IntoResponse {
+    match state.didcomm_mailbox.allow_mediation(&req.did) {
+        Ok(()) => {
+            info!(target: "audit", "didcomm_mediation_allowed did={}", req.did);
+            didcomm_mediation_allowlist_handler(State(state))
+                .await
+                .into_response()
         }
+        Err(e) => didcomm_error_response("Mediation not allowed", e),
+    }
+}
+
+// GET /didcomm/mediation/allowed – DIDs allowed to request mediation
+async fn didcomm_mediation_allowlist_handler(State(state): State<AppState>) -> impl IntoResponse {
+    match state.didcomm_mailbox.mediation_allowlist() {
+        Ok(allowed) => (StatusCode::OK, Json(allowed)).into_response(),
+        Err(e) => didcomm_error_response("Mediation allowlist unavailable", e),
     }
+}
 
-    (StatusCode::OK, Json(BatchVerificationResponse { results })).into_response()
+// POST /didcomm/pickup – Ask the mediator for messages queued for this node
+async fn didcomm_pickup_handler(
+    State(state): State<AppState>,
+    Json(req): Json<MediatorRequest>,
+) -> impl IntoResponse {
+    let own_did = &state.runtime_context.current_identity;
+    let mut body = serde_json::json!({ "recipient_did": own_did.to_string() });
+    if let Some(limit) = req.limit {
+        body["limit"] = limit.into();
+    }
+    let message =
+        icn_identity::DidCommMessage::new(icn_identity::didcomm::DELIVERY_REQUEST_TYPE, body)
+            .with_sender(own_did)
+            .with_recipient(&req.mediator);
+    let mediator = req.mediator.clone();
+    send_didcomm_response(&state, message, &req.mediator, false, Some(mediator)).await
 }
 
-// POST /identity/verify/revocation - verify a revocation proof
-async fn zk_verify_revocation_handler(
+// POST /didcomm/credential-offer – Offer a credential to a holder
+async fn didcomm_credential_offer_handler(
     State(state): State<AppState>,
-    Json(proof): Json<icn_common::ZkRevocationProof>,
+    Json(req): Json<CredentialOfferRequest>,
 ) -> impl IntoResponse {
-    let proof_json = match serde_json::to_string(&proof) {
-        Ok(j) => j,
-        Err(e) => {
-            return map_rust_error_to_json_response(
-                format!("serialization error: {e}"),
-                StatusCode::BAD_REQUEST,
+    let message = req
+        .offer
+        .to_message(&state.runtime_context.current_identity, &req.holder);
+    send_didcomm_response(&state, message, &req.holder, false, req.mediator).await
+}
+
+// POST /didcomm/credential-request – Answer a credential offer in the inbox
+async fn didcomm_credential_request_handler(
+    State(state): State<AppState>,
+    Json(req): Json<CredentialOfferReply>,
+) -> impl IntoResponse {
+    let offer = match state.didcomm_mailbox.inbox_message(&req.offer_id) {
+        Ok(Some(offer)) => offer,
+        Ok(None) => {
+            return didcomm_error_response(
+                "Credential offer not found",
+                CommonError::ResourceNotFound(req.offer_id),
             )
-            .into_response();
         }
+        Err(e) => return didcomm_error_response("Inbox unavailable", e),
     };
-
-    match icn_runtime::host_verify_zk_revocation_proof(&state.runtime_context, &proof_json).await {
-        Ok(true) => (
-            StatusCode::OK,
-            Json(serde_json::json!({ "verified": true })),
-        )
-            .into_response(),
-        Ok(false) => {
-            map_rust_error_to_json_response("verification failed", StatusCode::BAD_REQUEST)
-                .into_response()
-        }
-        Err(e) => map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response(),
+    let Some(issuer) = offer.sender else {
+        return didcomm_error_response(
+            "Credential request not sent",
+            CommonError::PermissionDenied("offer was not sent with authcrypt".into()),
+        );
+    };
+    match icn_identity::credential_request(&offer.message, &state.runtime_context.current_identity)
+    {
+        Ok(message) => send_didcomm_response(&state, message, &issuer, false, None).await,
+        Err(e) => didcomm_error_response("Credential request not sent", e),
     }
 }
 
-// POST /identity/credentials/issue - issue a credential
-async fn credential_issue_handler(
+// POST /didcomm/proposal-invitation – Invite members to vote on a proposal
+async fn didcomm_proposal_invitation_handler(
     State(state): State<AppState>,
-    Json(req): Json<IssueCredentialRequest>,
+    Json(req): Json<ProposalInvitationRequest>,
 ) -> impl IntoResponse {
-    use std::collections::HashMap;
-
-    let claims: HashMap<String, String> = req.attributes.into_iter().collect();
-    let mut cred = Credential::new(
-        req.issuer.clone(),
-        req.holder,
-        claims.clone(),
-        Some(req.schema),
-    );
-    cred.expires_at = Some(req.expiration);
-
-    for (k, v) in claims {
-        let mut bytes = req.issuer.to_string().into_bytes();
-        bytes.extend_from_slice(cred.holder.to_string().as_bytes());
-        bytes.extend_from_slice(k.as_bytes());
-        bytes.extend_from_slice(v.as_bytes());
-        match state.runtime_context.signer.sign(&bytes) {
-            Ok(sig) => {
-                cred.signatures.insert(k, SignatureBytes(sig));
-            }
+    let proposal = {
+        let gov = state.runtime_context.governance_module.lock().await;
+        gov.get_proposal(&icn_governance::ProposalId(req.proposal_id.clone()))
+    };
+    let proposal = match proposal {
+        Ok(Some(proposal)) => proposal,
+        Ok(None) => {
+            return didcomm_error_response(
+                "Proposal not found",
+                CommonError::ResourceNotFound(req.proposal_id),
+            )
+        }
+        Err(e) => return didcomm_error_response("Proposal unavailable", e),
+    };
+    let invitation = icn_identity::ProposalInvitation {
+        proposal_id: req.proposal_id,
+        description: proposal.description,
+        voting_deadline: Some(proposal.voting_deadline),
+        comment: req.comment,
+    };
+    let own_did = &state.runtime_context.current_identity;
+    let mut sent = Vec::new();
+    for invitee in &req.invitees {
+        let message = invitation.to_message(own_did, invitee);
+        match send_didcomm(&state, &message, invitee, false, None).await {
+            Ok(next_hop) => sent.push(SendDidCommResponse {
+                id: message.id,
+                next_hop,
+            }),
             Err(e) => {
-                return map_rust_error_to_json_response(e, StatusCode::INTERNAL_SERVER_ERROR)
-                    .into_response();
+                return didcomm_error_response(&format!("Invitation to {} not sent", invitee), e)
             }
         }
     }
+    (StatusCode::OK, Json(sent)).into_response()
+}
 
-    let bytes = match serde_json::to_vec(&cred) {
-        Ok(b) => b,
-        Err(e) => {
-            return map_rust_error_to_json_response(e, StatusCode::INTERNAL_SERVER_ERROR)
-                .into_response();
-        }
-    };
-    let cid = Cid::new_v1_sha256(0x71, &bytes);
-    state.credential_store.insert(cid.clone(), cred.clone());
-    state.revocation_registry.record(cid.clone());
+#[derive(Serialize)]
+struct KeyEventLogResponse {
+    state: icn_identity::KeyState,
+    log: icn_identity::KeyEventLog,
+}
 
-    (
-        StatusCode::CREATED,
-        Json(CredentialResponse {
-            cid,
-            credential: cred,
-        }),
-    )
-        .into_response()
+// POST /identity/did-icn/events – Incept, rotate or deactivate a did:icn identifier
+async fn did_icn_event_handler(
+    State(state): State<AppState>,
+    Json(event): Json<icn_identity::KeyEvent>,
+) -> impl IntoResponse {
+    match state.runtime_context.submit_key_event(event).await {
+        Ok(key_state) => (StatusCode::OK, Json(key_state)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Key event rejected: {}", e),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
 }
 
-// GET /identity/credentials/{cid}
-async fn credential_get_handler(
-    AxumPath(cid_str): AxumPath<String>,
+// GET /identity/did-icn/{did} – Current key state and key event log
+async fn did_icn_log_handler(
     State(state): State<AppState>,
+    AxumPath(did): AxumPath<String>,
 ) -> impl IntoResponse {
-    match parse_cid_from_string(&cid_str) {
-        Ok(cid) => {
-            if state.paused_credentials.contains(&cid) {
-                return map_rust_error_to_json_response("Credential paused", StatusCode::FORBIDDEN)
-                    .into_response();
-            }
-            match state.credential_store.get(&cid) {
-                Some(cred) => (
-                    StatusCode::OK,
-                    Json(CredentialResponse {
-                        cid,
-                        credential: cred,
-                    }),
-                )
-                    .into_response(),
-                None => {
-                    map_rust_error_to_json_response("Credential not found", StatusCode::NOT_FOUND)
-                        .into_response()
-                }
-            }
-        }
+    let did = match Did::from_str(&did) {
+        Ok(did) => did,
         Err(e) => {
-            map_rust_error_to_json_response(format!("Invalid CID: {e}"), StatusCode::BAD_REQUEST)
-                .into_response()
+            return map_rust_error_to_json_response(
+                format!("Invalid DID: {}", e),
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response()
         }
+    };
+    let Some(log) = state.runtime_context.did_icn.log(&did) else {
+        return map_rust_error_to_json_response(
+            format!("No key event log for {}", did),
+            StatusCode::NOT_FOUND,
+        )
+        .into_response();
+    };
+    match log.replay() {
+        Ok(key_state) => (
+            StatusCode::OK,
+            Json(KeyEventLogResponse {
+                state: key_state,
+                log,
+            }),
+        )
+            .into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Key event log error: {}", e),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .into_response(),
     }
 }
 
@@ -4222,6 +7667,44 @@ async fn credential_verify_handler(
                 )
                 .into_response();
             }
+            if state.revocation_registry.is_suspended(&cid) {
+                return map_rust_error_to_json_response(
+                    "Credential suspended",
+                    StatusCode::FORBIDDEN,
+                )
+                .into_response();
+            }
+        }
+        if let Some(entry) = &cred.status {
+            let now = state.runtime_context.time_provider.unix_seconds();
+            match state
+                .runtime_context
+                .status_lists
+                .status(&cred.issuer, entry, now)
+            {
+                Ok(icn_identity::CredentialStatus::Active) => {}
+                Ok(icn_identity::CredentialStatus::Suspended) => {
+                    return map_rust_error_to_json_response(
+                        "Credential suspended",
+                        StatusCode::FORBIDDEN,
+                    )
+                    .into_response();
+                }
+                Ok(icn_identity::CredentialStatus::Revoked) => {
+                    return map_rust_error_to_json_response(
+                        "Credential revoked",
+                        StatusCode::FORBIDDEN,
+                    )
+                    .into_response();
+                }
+                Err(e) => {
+                    return map_rust_error_to_json_response(
+                        format!("Credential status unavailable: {e}"),
+                        StatusCode::BAD_REQUEST,
+                    )
+                    .into_response();
+                }
+            }
         }
         for k in cred.claims.keys() {
             if let Err(e) = cred.verify_claim(k, vk) {
@@ -4229,6 +7712,37 @@ async fn credential_verify_handler(
                     .into_response();
             }
         }
+        // Claims must match a schema this node has seen published, and
+        // credentials issued under a cooperative policy must still satisfy it.
+        if let Some(schema_cid) = &cred.schema {
+            match state.runtime_context.credential_schema(schema_cid).await {
+                Ok(Some(schema)) => {
+                    if let Err(e) = schema.validate_claims(&cred.claims) {
+                        return map_rust_error_to_json_response(
+                            format!("{e}"),
+                            StatusCode::BAD_REQUEST,
+                        )
+                        .into_response();
+                    }
+                }
+                Ok(None) => {
+                    return map_rust_error_to_json_response(
+                        format!("Unknown credential schema {schema_cid}"),
+                        StatusCode::BAD_REQUEST,
+                    )
+                    .into_response();
+                }
+                Err(e) => {
+                    return map_rust_error_to_json_response(e, StatusCode::INTERNAL_SERVER_ERROR)
+                        .into_response();
+                }
+            }
+        }
+        if cred.issued_under.is_some() {
+            if let Err(e) = state.issuance_registry.check_credential(&cred) {
+                return issuance_error_response("Credential not valid", e);
+            }
+        }
         (StatusCode::OK, Json(VerificationResponse { valid: true })).into_response()
     } else {
         map_rust_error_to_json_response("untrusted credential issuer", StatusCode::FORBIDDEN)
@@ -4242,7 +7756,10 @@ async fn credential_revoke_handler(
     Json(req): Json<RevokeCredentialRequest>,
 ) -> impl IntoResponse {
     if state.credential_store.revoke(&req.cid) {
-        state.revocation_registry.revoke(&req.cid);
+        if state.revocation_registry.revoke(&req.cid) {
+            publish_credential_status(&state, &req.cid, icn_identity::StatusPurpose::Revocation)
+                .await;
+        }
         (
             StatusCode::OK,
             Json(serde_json::json!({"revoked": req.cid.to_string()})),
@@ -4254,6 +7771,162 @@ async fn credential_revoke_handler(
     }
 }
 
+// POST /identity/credentials/suspend – Temporarily suspend a credential
+async fn credential_suspend_handler(
+    State(state): State<AppState>,
+    Json(req): Json<RevokeCredentialRequest>,
+) -> impl IntoResponse {
+    if state.revocation_registry.is_revoked(&req.cid) {
+        return map_rust_error_to_json_response("Credential revoked", StatusCode::CONFLICT)
+            .into_response();
+    }
+    if !state.revocation_registry.suspend(&req.cid) {
+        return map_rust_error_to_json_response("Credential not found", StatusCode::NOT_FOUND)
+            .into_response();
+    }
+    publish_credential_status(&state, &req.cid, icn_identity::StatusPurpose::Suspension).await;
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({"suspended": req.cid.to_string()})),
+    )
+        .into_response()
+}
+
+// POST /identity/credentials/reinstate – Lift a credential suspension
+async fn credential_reinstate_handler(
+    State(state): State<AppState>,
+    Json(req): Json<RevokeCredentialRequest>,
+) -> impl IntoResponse {
+    if state.revocation_registry.is_revoked(&req.cid) {
+        return map_rust_error_to_json_response("Credential revoked", StatusCode::CONFLICT)
+            .into_response();
+    }
+    if !state.revocation_registry.reinstate(&req.cid) {
+        return map_rust_error_to_json_response("Credential not found", StatusCode::NOT_FOUND)
+            .into_response();
+    }
+    publish_credential_status(&state, &req.cid, icn_identity::StatusPurpose::Suspension).await;
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({"reinstated": req.cid.to_string()})),
+    )
+        .into_response()
+}
+
+// GET /identity/status-lists/{id}/{purpose} – Signed status list published by this node
+async fn status_list_get_handler(
+    State(state): State<AppState>,
+    AxumPath((id, purpose)): AxumPath<(String, String)>,
+) -> impl IntoResponse {
+    let purpose = match purpose.as_str() {
+        "revocation" => icn_identity::StatusPurpose::Revocation,
+        "suspension" => icn_identity::StatusPurpose::Suspension,
+        other => {
+            return map_rust_error_to_json_response(
+                format!("Unknown status purpose: {other}"),
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response()
+        }
+    };
+    let now = state.runtime_context.time_provider.unix_seconds();
+    match state.runtime_context.status_lists.get(
+        state.revocation_registry.issuer(),
+        &id,
+        purpose,
+        now,
+    ) {
+        Ok(Some(list)) => (StatusCode::OK, Json(list)).into_response(),
+        Ok(None) => map_rust_error_to_json_response("Status list not found", StatusCode::NOT_FOUND)
+            .into_response(),
+        Err(e) => {
+            map_rust_error_to_json_response(e, StatusCode::INTERNAL_SERVER_ERROR).into_response()
+        }
+    }
+}
+
+// POST /identity/credentials/export – Re-sign a node-issued credential as VC-JWT, SD-JWT or Data Integrity
+async fn credential_export_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ExportCredentialRequest>,
+) -> impl IntoResponse {
+    let Some(cred) = state.credential_store.get(&req.cid) else {
+        return map_rust_error_to_json_response("Credential not found", StatusCode::NOT_FOUND)
+            .into_response();
+    };
+    if cred.issuer != state.runtime_context.current_identity {
+        return map_rust_error_to_json_response(
+            "Only credentials issued by this node can be exported",
+            StatusCode::FORBIDDEN,
+        )
+        .into_response();
+    }
+    let now = state.runtime_context.time_provider.unix_seconds();
+    let signer = state.runtime_context.signer.clone();
+    match icn_identity::credential_formats::export_credential_with(
+        &cred,
+        req.format,
+        now,
+        |bytes| {
+            signer
+                .sign(bytes)
+                .map_err(|e| CommonError::IdentityError(e.to_string()))
+        },
+    ) {
+        Ok(credential) => (
+            StatusCode::OK,
+            Json(ExportCredentialResponse {
+                format: req.format,
+                credential,
+            }),
+        )
+            .into_response(),
+        Err(e) => map_rust_error_to_json_response(e, StatusCode::BAD_REQUEST).into_response(),
+    }
+}
+
+// POST /identity/credentials/import – Verify an external credential and map it to the ICN model
+async fn credential_import_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ImportCredentialRequest>,
+) -> impl IntoResponse {
+    let now = state.runtime_context.time_provider.unix_seconds();
+    match icn_identity::import_credential(
+        &req.credential,
+        req.format,
+        state.runtime_context.did_resolver.as_ref(),
+        now,
+    ) {
+        Ok(imported) => (StatusCode::OK, Json(imported)).into_response(),
+        Err(e) => map_rust_error_to_json_response(
+            format!("Credential rejected: {e}"),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+    }
+}
+
+/// Sign and anchor the current `purpose` list holding `cid`'s status bit.
+async fn publish_credential_status(
+    state: &AppState,
+    cid: &Cid,
+    purpose: icn_identity::StatusPurpose,
+) {
+    let Some(entry) = state.revocation_registry.entry(cid) else {
+        return;
+    };
+    let now = state.runtime_context.time_provider.unix_seconds();
+    let Some(list) = state
+        .revocation_registry
+        .snapshot(&entry.status_list, purpose, now)
+    else {
+        return;
+    };
+    if let Err(e) = state.runtime_context.publish_status_list(list).await {
+        error!("Failed to publish status list {}: {}", entry.status_list, e);
+    }
+}
+
 // POST /identity/credentials/disclose
 async fn credential_disclose_handler(Json(req): Json<DisclosureRequest>) -> impl IntoResponse {
     let fields: Vec<&str> = req.fields.iter().map(|s| s.as_str()).collect();
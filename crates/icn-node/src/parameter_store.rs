@@ -1,12 +1,22 @@
 use crate::config::NodeConfig;
 use icn_common::CommonError;
 use icn_eventstore::{EventStore, FileEventStore, ParameterUpdate};
+use icn_governance::parameters::OPEN_RATE_LIMIT;
+use icn_governance::ParameterRegistry;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
+/// Node-local copy of governance parameters.
+///
+/// Values are validated against the standard [`ParameterRegistry`] and every
+/// change is appended to an event log next to the config file, which is
+/// replayed on load.
 pub struct ParameterStore {
     path: PathBuf,
     config: NodeConfig,
+    registry: ParameterRegistry,
+    values: BTreeMap<String, String>,
     #[allow(clippy::type_complexity)]
     event_store: Option<Mutex<Box<dyn EventStore<ParameterUpdate>>>>,
 }
@@ -18,55 +28,70 @@ impl ParameterStore {
         let events_path = path.with_extension("events.jsonl");
         let store: FileEventStore<ParameterUpdate> = FileEventStore::new(events_path.clone());
         let events = store.query(None)?;
-        let mut config = if path.exists() {
+        let config = if path.exists() {
             NodeConfig::from_file(&path).map_err(|e| {
                 CommonError::ConfigError(format!("Failed to load parameter file: {e}"))
             })?
         } else {
             NodeConfig::default()
         };
+        let mut ps = Self {
+            path,
+            config,
+            registry: ParameterRegistry::standard(),
+            values: BTreeMap::new(),
+            event_store: None,
+        };
         for ev in &events {
-            if ev.name.as_str() == "open_rate_limit" {
-                let val = ev
-                    .value
-                    .parse::<u64>()
-                    .map_err(|e| CommonError::InvalidInputError(e.to_string()))?;
-                config.http.open_rate_limit = val;
+            if ps.registry.spec(&ev.name).is_none() {
+                log::warn!("Ignoring unknown parameter {} in event log", ev.name);
+                continue;
             }
+            ps.apply(&ev.name, &ev.value)?;
         }
-        Ok(Self {
-            path,
-            config,
-            event_store: Some(Mutex::new(Box::new(store))),
-        })
+        ps.event_store = Some(Mutex::new(Box::new(store)));
+        Ok(ps)
     }
 
     pub fn open_rate_limit(&self) -> u64 {
         self.config.http.open_rate_limit
     }
 
+    /// Current value of a parameter, falling back to its registry default.
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.values
+            .get(key)
+            .cloned()
+            .or_else(|| self.registry.get(key).map(|v| v.to_string()))
+    }
+
+    /// Parameters that have been set on this node, sorted by name.
+    pub fn values(&self) -> &BTreeMap<String, String> {
+        &self.values
+    }
+
     /// Update a parameter and persist changes to disk.
     pub fn set_parameter(&mut self, key: &str, value: &str) -> Result<(), CommonError> {
-        match key {
-            "open_rate_limit" => {
-                let val = value
-                    .parse::<u64>()
-                    .map_err(|e| CommonError::InvalidInputError(e.to_string()))?;
-                self.config.http.open_rate_limit = val;
-                log::info!(target: "audit", "parameter_changed name=open_rate_limit value={val}");
-                self.save()?;
-                if let Some(store) = &self.event_store {
-                    store.lock().unwrap().append(&ParameterUpdate {
-                        name: key.to_string(),
-                        value: value.to_string(),
-                    })?;
-                }
-                Ok(())
-            }
-            _ => Err(CommonError::InvalidInputError(format!(
-                "Unknown parameter {key}"
-            ))),
+        let value = self.apply(key, value)?;
+        log::info!(target: "audit", "parameter_changed name={key} value={value}");
+        self.save()?;
+        if let Some(store) = &self.event_store {
+            store.lock().unwrap().append(&ParameterUpdate {
+                name: key.to_string(),
+                value,
+            })?;
+        }
+        Ok(())
+    }
+
+    fn apply(&mut self, key: &str, value: &str) -> Result<String, CommonError> {
+        let value = self.registry.validate(key, value)?;
+        if key == OPEN_RATE_LIMIT {
+            self.config.http.open_rate_limit = value.as_u64().unwrap_or_default();
         }
+        let value = value.to_string();
+        self.values.insert(key.to_string(), value.clone());
+        Ok(value)
     }
 
     pub fn save(&self) -> Result<(), CommonError> {
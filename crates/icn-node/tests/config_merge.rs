@@ -37,7 +37,7 @@ listen_addr = "1.2.3.4:1111"
     let cli = Cli::from_arg_matches(&matches).unwrap();
 
     let mut cfg = NodeConfig::from_file(file.path()).unwrap();
-    cfg.apply_env_overrides();
+    cfg.apply_env_overrides().unwrap();
     cfg.apply_cli_overrides(&cli, &matches);
 
     assert_eq!(cfg.storage.storage_backend, StorageBackendType::Sqlite);
@@ -48,3 +48,14 @@ listen_addr = "1.2.3.4:1111"
     std::env::remove_var("ICN_STORAGE_BACKEND");
     std::env::remove_var("ICN_HTTP_LISTEN_ADDR");
 }
+
+#[test]
+fn malformed_hsm_slot_is_rejected() {
+    let mut cfg = NodeConfig::default();
+    std::env::set_var("ICN_HSM_SLOT", "first");
+    assert!(cfg.apply_env_overrides().is_err());
+    std::env::set_var("ICN_HSM_SLOT", "2");
+    cfg.apply_env_overrides().unwrap();
+    assert_eq!(cfg.identity.hsm_slot, Some(2));
+    std::env::remove_var("ICN_HSM_SLOT");
+}
@@ -65,6 +65,7 @@ async fn submit_and_vote_proposal() {
         vote_option: "yes".to_string(),
         credential_proof: None,
         revocation_proof: None,
+        revision: None,
     };
     let vote_resp = client
         .post(format!("http://{addr}/governance/vote"))
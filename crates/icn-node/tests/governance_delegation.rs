@@ -69,6 +69,8 @@ async fn delegate_and_revoke_flow() {
     let dreq = DelegateRequest {
         from_did: "did:example:bob".into(),
         to_did: node_did.to_string(),
+        scope: None,
+        expires_at: None,
     };
     let dresp = client
         .post(format!("http://{addr}/governance/delegate"))
@@ -84,6 +86,7 @@ async fn delegate_and_revoke_flow() {
         vote_option: "yes".into(),
         credential_proof: None,
         revocation_proof: None,
+        revision: None,
     };
     let vresp = client
         .post(format!("http://{addr}/governance/vote"))
@@ -143,6 +146,7 @@ async fn delegate_and_revoke_flow() {
     }
     let rreq = RevokeDelegationRequest {
         from_did: "did:example:bob".into(),
+        scope: None,
     };
     client
         .post(format!("http://{addr}/governance/revoke"))
@@ -156,6 +160,7 @@ async fn delegate_and_revoke_flow() {
         vote_option: "yes".into(),
         credential_proof: None,
         revocation_proof: None,
+        revision: None,
     };
     client
         .post(format!("http://{addr}/governance/vote"))
@@ -71,6 +71,7 @@ async fn add_and_remove_member_via_http() {
         vote_option: "yes".into(),
         credential_proof: None,
         revocation_proof: None,
+        revision: None,
     };
     let vresp = client
         .post(format!("http://{addr}/governance/vote"))
@@ -131,6 +132,7 @@ async fn add_and_remove_member_via_http() {
             vote_option: "yes".into(),
             credential_proof: None,
             revocation_proof: None,
+            revision: None,
         })
         .send()
         .await
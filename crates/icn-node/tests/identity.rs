@@ -31,3 +31,46 @@ async fn identity_persists_between_runs() {
     assert_eq!(did1, did2);
     assert_eq!(cfg2.identity.node_did.as_deref(), Some(did1.as_str()));
 }
+
+#[tokio::test]
+async fn plaintext_key_is_migrated_into_keystore_and_deleted() {
+    let dir = tempdir().unwrap();
+    let key_path = dir.path().join("node.key");
+    let keystore_path = dir.path().join("node.keystore.json");
+    let (sk, pk) = icn_identity::generate_ed25519_keypair();
+    std::fs::write(&key_path, bs58::encode(sk.to_bytes()).into_string()).unwrap();
+    std::env::set_var("ICN_TEST_MIGRATE_PASSPHRASE", "migrate");
+
+    let identity = || icn_node::config::IdentityConfig {
+        node_did_path: dir.path().join("node.did"),
+        node_private_key_path: key_path.clone(),
+        key_path: Some(keystore_path.clone()),
+        key_passphrase_env: Some("ICN_TEST_MIGRATE_PASSPHRASE".into()),
+        ..Default::default()
+    };
+    let mut cfg = NodeConfig {
+        identity: identity(),
+        ..Default::default()
+    };
+    let (_signer, did) = load_or_generate_identity(&mut cfg).unwrap();
+    assert_eq!(did, icn_identity::did_key_from_verifying_key(&pk));
+    assert!(!key_path.exists());
+    assert!(icn_identity::Keystore::is_keystore(&keystore_path).unwrap());
+
+    // The next start reads the same key from the keystore.
+    let mut cfg = NodeConfig {
+        identity: identity(),
+        ..Default::default()
+    };
+    let (_signer, again) = load_or_generate_identity(&mut cfg).unwrap();
+    assert_eq!(again, did);
+
+    // A corrupt keystore is an error, not a legacy key file.
+    std::fs::write(&keystore_path, b"{\"version\": ").unwrap();
+    let mut cfg = NodeConfig {
+        identity: identity(),
+        ..Default::default()
+    };
+    assert!(load_or_generate_identity(&mut cfg).is_err());
+    std::env::remove_var("ICN_TEST_MIGRATE_PASSPHRASE");
+}
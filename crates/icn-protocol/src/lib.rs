@@ -81,6 +81,10 @@ pub enum MessagePayload {
     HeartbeatMessage(HeartbeatMessage),
     /// Peer discovery and capability advertisement
     PeerDiscoveryMessage(PeerDiscoveryMessage),
+
+    // === Encrypted Messaging ===
+    /// DIDComm v2 envelope for the DID in `ProtocolMessage::recipient`
+    DidCommEnvelope(DidCommEnvelopeMessage),
 }
 
 // === Mesh Computing Protocol Messages ===
@@ -514,6 +518,18 @@ pub enum ServiceType {
     Relay,
 }
 
+// === Encrypted Messaging ===
+
+/// Encrypted DIDComm v2 envelope relayed between nodes.
+///
+/// Only the recipient, or the mediator holding mail for it, can open the
+/// envelope; relays see its size and next hop.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DidCommEnvelopeMessage {
+    /// JWE envelope in the General JSON serialization
+    pub envelope: String,
+}
+
 // === Message Helper Functions ===
 
 impl MessagePayload {
@@ -538,6 +554,7 @@ impl MessagePayload {
             MessagePayload::GossipMessage(_) => "GossipMessage",
             MessagePayload::HeartbeatMessage(_) => "HeartbeatMessage",
             MessagePayload::PeerDiscoveryMessage(_) => "PeerDiscoveryMessage",
+            MessagePayload::DidCommEnvelope(_) => "DidCommEnvelope",
         }
     }
 }
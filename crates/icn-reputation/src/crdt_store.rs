@@ -388,6 +388,15 @@ impl ReputationStore for CRDTReputationStore {
             debug!("Recorded proof attempt for {prover}: success={success}, delta={delta}");
         }
     }
+
+    fn slash(&self, did: &Did, amount: u64) -> u64 {
+        let current = self.get_reputation(did);
+        let delta = -(current.min(amount).min(i64::MAX as u64) as i64);
+        if let Err(e) = self.adjust_reputation(did, delta) {
+            error!("Failed to slash reputation of {did}: {e}");
+        }
+        self.get_reputation(did)
+    }
 }
 
 #[cfg(test)]
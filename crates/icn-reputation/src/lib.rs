@@ -24,6 +24,7 @@ pub mod crdt_store;
 pub mod metrics;
 
 // Trust Graph modules for advanced trust calculation and pathfinding
+pub mod sybil_detection;
 pub mod trust_aggregation;
 pub mod trust_calculation;
 pub mod trust_decay;
@@ -39,6 +40,7 @@ pub use integration::{
     ExecutionQuality, ReputationBasedExecutorSelection, ReputationEvent,
     ReputationIntegrationConfig, ReputationIntegrationEngine, ReputationIntegrationStats,
 };
+pub use sybil_detection::{sybil_rank, SybilRankConfig};
 pub use trust_aggregation::{
     AggregatedTrust, AggregationConfig, CombinationMethod, TrustAggregator, TrustSignal,
 };
@@ -59,6 +61,10 @@ pub trait ReputationStore: Send + Sync {
 
     /// Records an attempt to verify a zero-knowledge proof.
     fn record_proof_attempt(&self, prover: &Did, success: bool);
+
+    /// Lowers the score of `did` by `amount`, stopping at zero, as a penalty
+    /// outside execution. Returns the new score.
+    fn slash(&self, did: &Did, amount: u64) -> u64;
 }
 
 impl std::fmt::Debug for dyn ReputationStore {
@@ -155,6 +161,13 @@ impl ReputationStore for InMemoryReputationStore {
         let updated = (*entry as i64) + delta;
         *entry = if updated < 0 { 0 } else { updated as u64 };
     }
+
+    fn slash(&self, did: &Did, amount: u64) -> u64 {
+        let mut map = self.scores.lock().unwrap();
+        let entry = map.entry(did.clone()).or_insert(0);
+        *entry = entry.saturating_sub(amount);
+        *entry
+    }
 }
 
 #[cfg(test)]
@@ -185,5 +198,9 @@ mod tests {
 
         store.record_proof_attempt(&did, false);
         assert_eq!(store.get_reputation(&did), 1);
+
+        store.set_score(did.clone(), 10);
+        assert_eq!(store.slash(&did, 4), 6);
+        assert_eq!(store.slash(&did, 100), 0);
     }
 }
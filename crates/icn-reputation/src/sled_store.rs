@@ -67,4 +67,10 @@ impl ReputationStore for SledReputationStore {
         let new_score = if updated < 0 { 0 } else { updated as u64 };
         self.write_score(prover, new_score);
     }
+
+    fn slash(&self, did: &Did, amount: u64) -> u64 {
+        let new_score = self.read_score(did).saturating_sub(amount);
+        self.write_score(did, new_score);
+        new_score
+    }
 }
@@ -110,6 +110,25 @@ impl ReputationStore for SqliteReputationStore {
             }
         }
     }
+
+    fn slash(&self, did: &Did, amount: u64) -> u64 {
+        let fut = async {
+            let new_score = self
+                .read_score(did)
+                .await
+                .unwrap_or(0)
+                .saturating_sub(amount);
+            let _ = self.write_score(did, new_score).await;
+            new_score
+        };
+        match tokio::runtime::Handle::try_current() {
+            Ok(h) => h.block_on(fut),
+            Err(_) => {
+                let rt = tokio::runtime::Runtime::new().unwrap();
+                rt.block_on(fut)
+            }
+        }
+    }
 }
 
 #[cfg(all(feature = "persist-sqlite", feature = "async"))]
@@ -72,6 +72,9 @@ pub struct HsmConfig {
     pub slot_id: u32,
     /// HSM PIN
     pub pin: String,
+    /// Label of the Ed25519 key inside the token
+    #[serde(default)]
+    pub key_label: Option<String>,
 }
 
 /// DID resolver configuration
@@ -654,17 +657,18 @@ impl RuntimeConfig {
             }
             "hsm" => {
                 if let Some(ref hsm_config) = self.identity.key_store.hsm_config {
-                    // Create HSM key store and use it with Ed25519Signer
-                    let hsm = crate::context::signers::ExampleHsm::new(&hsm_config.library_path);
-                    match crate::context::Ed25519Signer::from_hsm(&hsm) {
-                        Ok(signer) => Ok(Arc::new(signer)),
-                        Err(e) => {
-                            log::warn!("🔐 HSM WARNING: Failed to load key from HSM ({}). Generating ephemeral key for development use.", e);
-                            log::warn!("🔐 Ensure HSM is properly configured and accessible for production use.");
-                            let (signing_key, _) = icn_identity::generate_ed25519_keypair();
-                            Ok(Arc::new(crate::context::Ed25519Signer::new(signing_key)))
-                        }
-                    }
+                    // Sign inside the PKCS#11 token; the key is never exported
+                    let label = hsm_config.key_label.as_deref().ok_or_else(|| {
+                        CommonError::ConfigError(
+                            "HSM key_label is required for HSM key store".to_string(),
+                        )
+                    })?;
+                    let token = crate::context::Pkcs11Token::open(
+                        &hsm_config.library_path,
+                        Some(u64::from(hsm_config.slot_id)),
+                        &hsm_config.pin,
+                    )?;
+                    Ok(Arc::new(token.signer(label)?))
                 } else {
                     Err(CommonError::ConfigError(
                         "HSM configuration is required for HSM key store".to_string(),
@@ -10,8 +10,8 @@ use icn_identity::{
 use icn_mesh::{ActualMeshJob, JobId, MeshJobBid};
 use icn_network::NetworkService;
 use icn_protocol::{
-    GossipMessage, GovernanceProposalMessage, MeshJobAssignmentMessage, MessagePayload,
-    ProposalType, ProtocolMessage,
+    DidCommEnvelopeMessage, GossipMessage, GovernanceProposalMessage, MeshJobAssignmentMessage,
+    MessagePayload, ProposalType, ProtocolMessage,
 };
 use icn_reputation::ReputationStore;
 use log::debug;
@@ -152,6 +152,13 @@ pub trait MeshNetworkService: Send + Sync + std::fmt::Debug {
     /// Check if the network appears to be partitioned
     async fn is_network_partitioned(&self) -> Result<bool, HostAbiError>;
 
+    /// Relay a DIDComm envelope towards `next_hop`, the recipient or its mediator.
+    async fn send_didcomm_envelope(
+        &self,
+        next_hop: &Did,
+        envelope: String,
+    ) -> Result<(), HostAbiError>;
+
     fn as_any(&self) -> &dyn std::any::Any;
 }
 
@@ -991,6 +998,27 @@ impl MeshNetworkService for DefaultMeshNetworkService {
 
         Ok(is_partitioned)
     }
+
+    async fn send_didcomm_envelope(
+        &self,
+        next_hop: &Did,
+        envelope: String,
+    ) -> Result<(), HostAbiError> {
+        // Deliver to the next hop only; the envelope is not gossiped.
+        let mut message = ProtocolMessage::new(
+            MessagePayload::DidCommEnvelope(DidCommEnvelopeMessage { envelope }),
+            self.signer.did(),
+            Some(next_hop.clone()),
+        );
+        message.signature = self.sign_message(&message)?.signature;
+        let network_peer_id = self.did_to_peer_id(next_hop);
+        self.inner
+            .send_message(&network_peer_id, message)
+            .await
+            .map_err(|e| {
+                HostAbiError::NetworkError(format!("Failed to relay DIDComm envelope: {}", e))
+            })
+    }
 }
 
 impl DefaultMeshNetworkService {
@@ -15,6 +15,7 @@ pub mod federation_integration;
 pub mod host_environment;
 pub mod mana;
 pub mod mesh_network;
+pub mod pkcs11_signer;
 pub mod realtime_ccl_integration;
 pub mod resilient_context;
 pub mod resource_ledger;
@@ -59,6 +60,7 @@ pub use mesh_network::{
     MeshJobStateChange, MeshNetworkService, PeerStatistics, PingResult, SelectionPolicy,
     PROPOSAL_COST_MANA, VOTE_COST_MANA,
 };
+pub use pkcs11_signer::{Pkcs11KeyInfo, Pkcs11Signer, Pkcs11Token};
 pub use realtime_ccl_integration::{
     ActiveProposal, CclIntegrationCoordinator, GovernanceEvent, GovernanceEventType,
     ProposalStatus, VoteTracker,
@@ -0,0 +1,314 @@
+//! PKCS#11 signer for node keys held in a hardware token.
+//!
+//! Keys are Ed25519 (`CKK_EC_EDWARDS`) key pairs generated inside the token
+//! as sensitive, non-extractable objects. Signing uses `CKM_EDDSA`, so the
+//! private key never leaves the device; only the public key is read back.
+//! Keys are addressed by their `CKA_LABEL`.
+//!
+//! Any PKCS#11 module works. SoftHSM2 serves as a local test device.
+
+use super::errors::HostAbiError;
+use super::signers::{verify_ed25519, Signer};
+use cryptoki::context::{CInitializeArgs, Pkcs11};
+use cryptoki::error::{Error as Pkcs11Error, RvError};
+use cryptoki::mechanism::eddsa::{EddsaParams, EddsaSignatureScheme};
+use cryptoki::mechanism::Mechanism;
+use cryptoki::object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle};
+use cryptoki::session::{Session, UserType};
+use cryptoki::slot::Slot;
+use cryptoki::types::AuthPin;
+use icn_common::{CommonError, Did};
+use icn_identity::{did_key_from_verifying_key, VerifyingKey};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// DER encoding of the Ed25519 curve OID (1.3.101.112) for `CKA_EC_PARAMS`.
+const ED25519_PARAMS: [u8; 5] = [0x06, 0x03, 0x2b, 0x65, 0x70];
+
+/// An Ed25519 key pair stored in a token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pkcs11KeyInfo {
+    pub label: String,
+    pub public_key: VerifyingKey,
+    /// `did:key` identifier derived from the public key.
+    pub did: Did,
+}
+
+/// A logged-in connection to one token slot.
+///
+/// The module is finalized when the last token and signer created from it
+/// are dropped, so open one per module and process.
+pub struct Pkcs11Token {
+    ctx: Pkcs11,
+    slot: Slot,
+    pin: AuthPin,
+}
+
+impl std::fmt::Debug for Pkcs11Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Pkcs11Token {{ slot: {} }}", self.slot.id())
+    }
+}
+
+impl Pkcs11Token {
+    /// Load the PKCS#11 module at `library` and select `slot`, or the first
+    /// slot with an initialized token when `slot` is `None`.
+    pub fn open<P: AsRef<Path>>(
+        library: P,
+        slot: Option<u64>,
+        pin: &str,
+    ) -> Result<Self, CommonError> {
+        let library = library.as_ref();
+        let ctx = Pkcs11::new(library).map_err(|e| {
+            CommonError::ConfigError(format!(
+                "Failed to load PKCS#11 module {}: {e}",
+                library.display()
+            ))
+        })?;
+        match ctx.initialize(CInitializeArgs::OsThreads) {
+            Ok(()) | Err(Pkcs11Error::Pkcs11(RvError::CryptokiAlreadyInitialized, _)) => {}
+            Err(e) => return Err(pkcs11_error("initialize module", e)),
+        }
+        let slots = ctx
+            .get_slots_with_initialized_token()
+            .map_err(|e| pkcs11_error("list slots", e))?;
+        let slot = match slot {
+            Some(id) => slots.into_iter().find(|s| s.id() == id).ok_or_else(|| {
+                CommonError::ConfigError(format!("No initialized token in PKCS#11 slot {id}"))
+            })?,
+            None => slots.into_iter().next().ok_or_else(|| {
+                CommonError::ConfigError("No initialized PKCS#11 token found".into())
+            })?,
+        };
+        Ok(Self {
+            ctx,
+            slot,
+            pin: AuthPin::new(pin.to_string()),
+        })
+    }
+
+    /// Id of the selected slot.
+    pub fn slot_id(&self) -> u64 {
+        self.slot.id()
+    }
+
+    /// Generate a sensitive, non-extractable Ed25519 key pair labelled `label`.
+    pub fn generate_ed25519(&self, label: &str) -> Result<Pkcs11KeyInfo, CommonError> {
+        let session = self.session(true)?;
+        if !find_keys(&session, ObjectClass::PRIVATE_KEY, Some(label))?.is_empty() {
+            return Err(CommonError::InvalidInputError(format!(
+                "Token already holds a key labelled {label}"
+            )));
+        }
+        let id = label.as_bytes().to_vec();
+        let public_template = [
+            Attribute::Token(true),
+            Attribute::Private(false),
+            Attribute::Verify(true),
+            Attribute::EcParams(ED25519_PARAMS.to_vec()),
+            Attribute::Label(id.clone()),
+            Attribute::Id(id.clone()),
+        ];
+        let private_template = [
+            Attribute::Token(true),
+            Attribute::Private(true),
+            Attribute::Sensitive(true),
+            Attribute::Extractable(false),
+            Attribute::Sign(true),
+            Attribute::Label(id.clone()),
+            Attribute::Id(id),
+        ];
+        let (public, _) = session
+            .generate_key_pair(
+                &Mechanism::EccEdwardsKeyPairGen,
+                &public_template,
+                &private_template,
+            )
+            .map_err(|e| pkcs11_error("generate Ed25519 key pair", e))?;
+        key_info(&session, public)
+    }
+
+    /// Ed25519 public keys stored in the token.
+    pub fn list_ed25519(&self) -> Result<Vec<Pkcs11KeyInfo>, CommonError> {
+        let session = self.session(false)?;
+        find_keys(&session, ObjectClass::PUBLIC_KEY, None)?
+            .into_iter()
+            .map(|handle| key_info(&session, handle))
+            .collect()
+    }
+
+    /// Signer for the key pair labelled `label`.
+    pub fn signer(&self, label: &str) -> Result<Pkcs11Signer, CommonError> {
+        let session = self.session(false)?;
+        let private = single_key(&session, ObjectClass::PRIVATE_KEY, label)?;
+        let extractable = session
+            .get_attributes(private, &[AttributeType::Extractable])
+            .map_err(|e| pkcs11_error("read key attributes", e))?;
+        if extractable
+            .iter()
+            .any(|a| matches!(a, Attribute::Extractable(true)))
+        {
+            return Err(CommonError::PolicyDenied(format!(
+                "PKCS#11 key {label} is extractable"
+            )));
+        }
+        let public = single_key(&session, ObjectClass::PUBLIC_KEY, label)?;
+        let info = key_info(&session, public)?;
+        Ok(Pkcs11Signer {
+            session: Mutex::new(session),
+            key: private,
+            label: info.label,
+            pk: info.public_key,
+            did: info.did,
+        })
+    }
+
+    fn session(&self, read_write: bool) -> Result<Session, CommonError> {
+        let session = if read_write {
+            self.ctx.open_rw_session(self.slot)
+        } else {
+            self.ctx.open_ro_session(self.slot)
+        }
+        .map_err(|e| pkcs11_error("open session", e))?;
+        match session.login(UserType::User, Some(&self.pin)) {
+            Ok(()) | Err(Pkcs11Error::Pkcs11(RvError::UserAlreadyLoggedIn, _)) => Ok(session),
+            Err(e) => Err(pkcs11_error("log in", e)),
+        }
+    }
+}
+
+/// Signer whose Ed25519 key stays inside a PKCS#11 token.
+pub struct Pkcs11Signer {
+    session: Mutex<Session>,
+    key: ObjectHandle,
+    label: String,
+    pk: VerifyingKey,
+    did: Did,
+}
+
+impl std::fmt::Debug for Pkcs11Signer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Pkcs11Signer {{ label: {}, did: {} }}",
+            self.label, self.did
+        )
+    }
+}
+
+impl Pkcs11Signer {
+    /// Label of the key in the token.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+}
+
+impl Signer for Pkcs11Signer {
+    fn sign(&self, payload: &[u8]) -> Result<Vec<u8>, HostAbiError> {
+        let session = self
+            .session
+            .lock()
+            .map_err(|_| HostAbiError::SignatureError("PKCS#11 session poisoned".into()))?;
+        session
+            .sign(
+                &Mechanism::Eddsa(EddsaParams::new(EddsaSignatureScheme::Pure)),
+                self.key,
+                payload,
+            )
+            .map_err(|e| HostAbiError::SignatureError(format!("PKCS#11 signing failed: {e}")))
+    }
+
+    fn verify(
+        &self,
+        payload: &[u8],
+        signature: &[u8],
+        public_key_bytes: &[u8],
+    ) -> Result<bool, HostAbiError> {
+        verify_ed25519(payload, signature, public_key_bytes)
+    }
+
+    fn public_key_bytes(&self) -> Vec<u8> {
+        self.pk.to_bytes().to_vec()
+    }
+
+    fn did(&self) -> Did {
+        self.did.clone()
+    }
+
+    fn verifying_key_ref(&self) -> &VerifyingKey {
+        &self.pk
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+fn find_keys(
+    session: &Session,
+    class: ObjectClass,
+    label: Option<&str>,
+) -> Result<Vec<ObjectHandle>, CommonError> {
+    let mut template = vec![
+        Attribute::Class(class),
+        Attribute::KeyType(KeyType::EC_EDWARDS),
+    ];
+    if let Some(label) = label {
+        template.push(Attribute::Label(label.as_bytes().to_vec()));
+    }
+    session
+        .find_objects(&template)
+        .map_err(|e| pkcs11_error("find keys", e))
+}
+
+fn single_key(
+    session: &Session,
+    class: ObjectClass,
+    label: &str,
+) -> Result<ObjectHandle, CommonError> {
+    match find_keys(session, class, Some(label))?.as_slice() {
+        [handle] => Ok(*handle),
+        [] => Err(CommonError::ResourceNotFound(format!(
+            "No Ed25519 {class} labelled {label} in token"
+        ))),
+        _ => Err(CommonError::ConfigError(format!(
+            "Several Ed25519 keys are labelled {label}"
+        ))),
+    }
+}
+
+fn key_info(session: &Session, public: ObjectHandle) -> Result<Pkcs11KeyInfo, CommonError> {
+    let attributes = session
+        .get_attributes(public, &[AttributeType::Label, AttributeType::EcPoint])
+        .map_err(|e| pkcs11_error("read public key", e))?;
+    let mut label = String::new();
+    let mut point = Vec::new();
+    for attribute in attributes {
+        match attribute {
+            Attribute::Label(bytes) => label = String::from_utf8_lossy(&bytes).into_owned(),
+            Attribute::EcPoint(bytes) => point = bytes,
+            _ => {}
+        }
+    }
+    // Tokens return the point either raw or wrapped in a DER OCTET STRING.
+    let raw = match point.as_slice() {
+        [0x04, 0x20, rest @ ..] if rest.len() == 32 => rest,
+        raw => raw,
+    };
+    let bytes: [u8; 32] = raw.try_into().map_err(|_| {
+        CommonError::CryptoError(format!("Key {label} has an invalid Ed25519 point"))
+    })?;
+    let public_key = VerifyingKey::from_bytes(&bytes)
+        .map_err(|e| CommonError::CryptoError(format!("Key {label} is not Ed25519: {e}")))?;
+    let did = Did::from_str(&did_key_from_verifying_key(&public_key))?;
+    Ok(Pkcs11KeyInfo {
+        label,
+        public_key,
+        did,
+    })
+}
+
+fn pkcs11_error(action: &str, e: Pkcs11Error) -> CommonError {
+    CommonError::CryptoError(format!("PKCS#11 failed to {action}: {e}"))
+}
@@ -296,6 +296,17 @@ impl MeshNetworkService for MeshNetworkServiceType {
             MeshNetworkServiceType::Default(s) => s.is_network_partitioned().await,
         }
     }
+
+    async fn send_didcomm_envelope(
+        &self,
+        next_hop: &Did,
+        envelope: String,
+    ) -> Result<(), HostAbiError> {
+        match self {
+            MeshNetworkServiceType::Stub(s) => s.send_didcomm_envelope(next_hop, envelope).await,
+            MeshNetworkServiceType::Default(s) => s.send_didcomm_envelope(next_hop, envelope).await,
+        }
+    }
 }
 
 /// Core runtime context for the ICN node.
@@ -309,6 +320,12 @@ pub struct RuntimeContext {
     pub mesh_network_service: Arc<MeshNetworkServiceType>,
     pub signer: Arc<dyn Signer>,
     pub did_resolver: Arc<dyn icn_identity::DidResolver>,
+    /// Key event logs of `did:icn` identifiers; `did_resolver` consults them first.
+    pub did_icn: icn_identity::IcnDidResolver,
+    /// Published credential status lists, checked by revocation verifiers.
+    pub status_lists: icn_identity::StatusListCache,
+    /// Published credential schemas, checked at issuance and verification.
+    pub credential_schemas: icn_identity::SchemaRegistry,
     pub dag_store: DagStoreWrapper,
     pub reputation_store: Arc<dyn icn_reputation::ReputationStore>,
     pub trust_engine: Arc<TokioMutex<TrustPolicyEngine>>,
@@ -378,7 +395,13 @@ pub struct ParameterUpdate {
     pub name: String,
     /// New value for the parameter.
     pub value: String,
-    /// Seconds since Unix epoch when the change occurred.
+    /// Value replaced by this update.
+    pub previous: String,
+    /// Position of the change in the governance parameter history.
+    pub version: u64,
+    /// Proposal that made the change.
+    pub proposal_id: Option<String>,
+    /// Seconds since Unix epoch when the change took effect.
     pub timestamp: u64,
     /// DID of the signer applying the update.
     pub signer: Did,
@@ -780,6 +803,10 @@ impl RuntimeContext {
             time_provider.clone(),
         );
 
+        let did_icn = icn_identity::IcnDidResolver::new();
+        let did_resolver = did_icn.with_fallback(did_resolver);
+        let status_lists = icn_identity::StatusListCache::new(did_resolver.clone());
+        let credential_schemas = icn_identity::SchemaRegistry::new(did_resolver.clone());
         Ok(Arc::new(Self {
             current_identity,
             mana_ledger,
@@ -790,6 +817,9 @@ impl RuntimeContext {
             mesh_network_service,
             signer,
             did_resolver,
+            did_icn,
+            status_lists,
+            credential_schemas,
             dag_store,
             reputation_store,
             trust_engine: Arc::new(TokioMutex::new(TrustPolicyEngine::new())),
@@ -848,6 +878,10 @@ impl RuntimeContext {
             time_provider.clone(),
         );
 
+        let did_icn = icn_identity::IcnDidResolver::new();
+        let did_resolver = did_icn.with_fallback(did_resolver);
+        let status_lists = icn_identity::StatusListCache::new(did_resolver.clone());
+        let credential_schemas = icn_identity::SchemaRegistry::new(did_resolver.clone());
         Ok(Arc::new(Self {
             current_identity,
             mana_ledger,
@@ -858,6 +892,9 @@ impl RuntimeContext {
             mesh_network_service,
             signer,
             did_resolver,
+            did_icn,
+            status_lists,
+            credential_schemas,
             dag_store,
             reputation_store,
             trust_engine: Arc::new(TokioMutex::new(TrustPolicyEngine::new())),
@@ -935,6 +972,10 @@ impl RuntimeContext {
             config.time_provider.clone(),
         );
 
+        let did_icn = icn_identity::IcnDidResolver::new();
+        let did_resolver = did_icn.with_fallback(config.did_resolver);
+        let status_lists = icn_identity::StatusListCache::new(did_resolver.clone());
+        let credential_schemas = icn_identity::SchemaRegistry::new(did_resolver.clone());
         Ok(Arc::new(Self {
             current_identity: config.current_identity,
             mana_ledger: config.mana_ledger,
@@ -944,7 +985,10 @@ impl RuntimeContext {
             governance_module,
             mesh_network_service: config.mesh_network_service,
             signer: config.signer,
-            did_resolver: config.did_resolver,
+            did_resolver,
+            did_icn,
+            status_lists,
+            credential_schemas,
             dag_store: config.dag_store,
             reputation_store: config.reputation_store,
             trust_engine: Arc::new(TokioMutex::new(TrustPolicyEngine::new())),
@@ -1566,6 +1610,10 @@ impl RuntimeContext {
             time_provider.clone(),
         );
 
+        let did_icn = icn_identity::IcnDidResolver::new();
+        let did_resolver = did_icn.with_fallback(did_resolver);
+        let status_lists = icn_identity::StatusListCache::new(did_resolver.clone());
+        let credential_schemas = icn_identity::SchemaRegistry::new(did_resolver.clone());
         let ctx = Arc::new(Self {
             current_identity: current_identity.clone(),
             mana_ledger,
@@ -1576,6 +1624,9 @@ impl RuntimeContext {
             mesh_network_service,
             signer,
             did_resolver,
+            did_icn,
+            status_lists,
+            credential_schemas,
             dag_store,
             reputation_store,
             trust_engine: Arc::new(TokioMutex::new(TrustPolicyEngine::new())),
@@ -1638,6 +1689,10 @@ impl RuntimeContext {
             time_provider.clone(),
         );
 
+        let did_icn = icn_identity::IcnDidResolver::new();
+        let did_resolver = did_icn.with_fallback(did_resolver);
+        let status_lists = icn_identity::StatusListCache::new(did_resolver.clone());
+        let credential_schemas = icn_identity::SchemaRegistry::new(did_resolver.clone());
         Arc::new(Self {
             current_identity,
             mana_ledger,
@@ -1648,6 +1703,9 @@ impl RuntimeContext {
             mesh_network_service,
             signer,
             did_resolver,
+            did_icn,
+            status_lists,
+            credential_schemas,
             dag_store: dag_store_wrapper,
             reputation_store,
             trust_engine: Arc::new(TokioMutex::new(TrustPolicyEngine::new())),
@@ -1731,6 +1789,10 @@ impl RuntimeContext {
             time_provider.clone(),
         );
 
+        let did_icn = icn_identity::IcnDidResolver::new();
+        let did_resolver = did_icn.with_fallback(did_resolver);
+        let status_lists = icn_identity::StatusListCache::new(did_resolver.clone());
+        let credential_schemas = icn_identity::SchemaRegistry::new(did_resolver.clone());
         Ok(Arc::new(Self {
             current_identity,
             mana_ledger,
@@ -1741,6 +1803,9 @@ impl RuntimeContext {
             mesh_network_service,
             signer,
             did_resolver,
+            did_icn,
+            status_lists,
+            credential_schemas,
             dag_store: dag_store_wrapper,
             reputation_store,
             trust_engine: Arc::new(TokioMutex::new(TrustPolicyEngine::new())),
@@ -1785,6 +1850,10 @@ impl RuntimeContext {
             time_provider.clone(),
         );
 
+        let did_icn = icn_identity::IcnDidResolver::new();
+        let did_resolver = did_icn.with_fallback(did_resolver);
+        let status_lists = icn_identity::StatusListCache::new(did_resolver.clone());
+        let credential_schemas = icn_identity::SchemaRegistry::new(did_resolver.clone());
         Arc::new(Self {
             current_identity,
             mana_ledger,
@@ -1795,6 +1864,9 @@ impl RuntimeContext {
             mesh_network_service,
             signer,
             did_resolver,
+            did_icn,
+            status_lists,
+            credential_schemas,
             dag_store,
             reputation_store,
             trust_engine: Arc::new(TokioMutex::new(TrustPolicyEngine::new())),
@@ -2305,7 +2377,7 @@ impl RuntimeContext {
                 "Job spec not found in DAG".to_string(),
             ));
         };
-        let selection_policy = icn_mesh::SelectionPolicy::default();
+        let selection_policy = self.selection_policy();
         let selected_executor = icn_mesh::select_executor(
             &job_id,
             &job_spec,
@@ -2705,6 +2777,18 @@ impl RuntimeContext {
 
     /// Spend mana from an account.
     pub async fn spend_mana(&self, account: &Did, amount: u64) -> Result<(), HostAbiError> {
+        let now = self.time_provider.unix_seconds();
+        if let Some(sanction) = self
+            .governance_module
+            .lock()
+            .await
+            .sanction_in_force(account, now)
+        {
+            return Err(HostAbiError::PermissionDenied(format!(
+                "{} cannot spend mana while sanctioned ({:?})",
+                account, sanction
+            )));
+        }
         self.mana_ledger.spend(account, amount)?;
         self.record_ledger_event(&LedgerEvent::Debit {
             did: account.clone(),
@@ -2959,13 +3043,167 @@ impl RuntimeContext {
         Ok(cid)
     }
 
+    /// Validate a `did:icn` key event, anchor it in the DAG and apply it.
+    pub async fn submit_key_event(
+        &self,
+        event: icn_identity::KeyEvent,
+    ) -> Result<icn_identity::KeyState, HostAbiError> {
+        let (_, block) = self
+            .did_icn
+            .validate(&event)
+            .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))?;
+        {
+            let mut dag = self.dag_store.inner().lock().await;
+            dag.put(&block).await.map_err(|e| {
+                HostAbiError::DagOperationFailed(format!("Failed to anchor key event: {}", e))
+            })?;
+        }
+        self.did_icn
+            .apply(event)
+            .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))
+    }
+
+    /// Rebuild `did:icn` key event logs from the DAG, returning the number of
+    /// events replayed.
+    pub async fn load_key_event_logs(&self) -> Result<usize, HostAbiError> {
+        let blocks = self
+            .dag_store
+            .inner()
+            .lock()
+            .await
+            .list_blocks()
+            .await
+            .map_err(|e| {
+                HostAbiError::DagOperationFailed(format!("Failed to list DAG blocks: {}", e))
+            })?;
+        Ok(self.did_icn.load_blocks(&blocks))
+    }
+
+    /// Sign a status list snapshot with the node key, anchor it in the DAG
+    /// and cache it.
+    pub async fn publish_status_list(
+        &self,
+        mut list: icn_identity::StatusListCredential,
+    ) -> Result<Cid, HostAbiError> {
+        let bytes = list
+            .signable_bytes()
+            .map_err(|e| HostAbiError::InternalError(e.to_string()))?;
+        list.signature = icn_identity::SignatureBytes(self.signer.sign(&bytes)?);
+        let block = list
+            .to_dag_block()
+            .map_err(|e| HostAbiError::InternalError(e.to_string()))?;
+        {
+            let mut dag = self.dag_store.inner().lock().await;
+            dag.put(&block).await.map_err(|e| {
+                HostAbiError::DagOperationFailed(format!("Failed to anchor status list: {}", e))
+            })?;
+        }
+        self.status_lists
+            .insert(list, self.time_provider.unix_seconds())
+            .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))?;
+        Ok(block.cid)
+    }
+
+    /// Cache status lists anchored in the DAG, returning the number loaded.
+    pub async fn load_status_lists(&self) -> Result<usize, HostAbiError> {
+        let blocks = self
+            .dag_store
+            .inner()
+            .lock()
+            .await
+            .list_blocks()
+            .await
+            .map_err(|e| {
+                HostAbiError::DagOperationFailed(format!("Failed to list DAG blocks: {}", e))
+            })?;
+        Ok(self
+            .status_lists
+            .load_blocks(&blocks, self.time_provider.unix_seconds()))
+    }
+
+    /// Sign a credential schema with the node key, anchor it in the DAG and
+    /// cache it.
+    pub async fn publish_credential_schema(
+        &self,
+        mut schema: icn_identity::CredentialSchema,
+    ) -> Result<Cid, HostAbiError> {
+        let bytes = schema
+            .signable_bytes()
+            .map_err(|e| HostAbiError::InternalError(e.to_string()))?;
+        schema.signature = icn_identity::SignatureBytes(self.signer.sign(&bytes)?);
+        let block = schema
+            .to_dag_block()
+            .map_err(|e| HostAbiError::InternalError(e.to_string()))?;
+        {
+            let mut dag = self.dag_store.inner().lock().await;
+            dag.put(&block).await.map_err(|e| {
+                HostAbiError::DagOperationFailed(format!(
+                    "Failed to anchor credential schema: {}",
+                    e
+                ))
+            })?;
+        }
+        self.credential_schemas
+            .insert(schema)
+            .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))
+    }
+
+    /// Cache credential schemas anchored in the DAG, returning the number
+    /// loaded.
+    pub async fn load_credential_schemas(&self) -> Result<usize, HostAbiError> {
+        let blocks = self
+            .dag_store
+            .inner()
+            .lock()
+            .await
+            .list_blocks()
+            .await
+            .map_err(|e| {
+                HostAbiError::DagOperationFailed(format!("Failed to list DAG blocks: {}", e))
+            })?;
+        Ok(self.credential_schemas.load_blocks(&blocks))
+    }
+
+    /// Published credential schema `cid`, fetched from the DAG when it is not
+    /// cached yet.
+    pub async fn credential_schema(
+        &self,
+        cid: &Cid,
+    ) -> Result<Option<icn_identity::CredentialSchema>, HostAbiError> {
+        if let Some(schema) = self.credential_schemas.get(cid) {
+            return Ok(Some(schema));
+        }
+        let block = self
+            .dag_store
+            .inner()
+            .lock()
+            .await
+            .get(cid)
+            .await
+            .map_err(|e| {
+                HostAbiError::DagOperationFailed(format!("Failed to read credential schema: {}", e))
+            })?;
+        let Some(schema) = block
+            .and_then(|b| serde_json::from_slice::<icn_identity::CredentialSchema>(&b.data).ok())
+        else {
+            return Ok(None);
+        };
+        if self.credential_schemas.insert(schema.clone()).is_err() {
+            return Ok(None);
+        }
+        Ok(Some(schema))
+    }
+
     /// Create a governance proposal.
     pub async fn create_governance_proposal(
         &self,
         payload: CreateProposalPayload,
     ) -> Result<String, HostAbiError> {
-        self.spend_mana(&self.current_identity, PROPOSAL_COST_MANA)
-            .await?;
+        let cost = self.parameter_u64(
+            icn_governance::parameters::PROPOSAL_COST_MANA,
+            PROPOSAL_COST_MANA,
+        );
+        self.spend_mana(&self.current_identity, cost).await?;
 
         let proposal_type = match payload.proposal_type_str.to_lowercase().as_str() {
             "systemparameterchange" | "system_parameter_change" => {
@@ -3008,6 +3246,15 @@ impl RuntimeContext {
                 })?;
                 ProposalType::GenericText(text)
             }
+            "constitutionamendment" | "constitution_amendment" => {
+                let document = String::from_utf8(payload.type_specific_payload).map_err(|e| {
+                    HostAbiError::InvalidParameters(format!(
+                        "Failed to parse constitution document: {}",
+                        e
+                    ))
+                })?;
+                ProposalType::ConstitutionAmendment(document)
+            }
             other => {
                 return Err(HostAbiError::InvalidParameters(format!(
                     "Unknown proposal type: {}",
@@ -3083,8 +3330,8 @@ impl RuntimeContext {
 
     /// Cast a governance vote.
     pub async fn cast_governance_vote(&self, payload: CastVotePayload) -> Result<(), HostAbiError> {
-        self.spend_mana(&self.current_identity, VOTE_COST_MANA)
-            .await?;
+        let cost = self.parameter_u64(icn_governance::parameters::VOTE_COST_MANA, VOTE_COST_MANA);
+        self.spend_mana(&self.current_identity, cost).await?;
 
         let proposal_id = ProposalId::from_str(&payload.proposal_id_str)
             .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid proposal id: {}", e)))?;
@@ -3192,8 +3439,42 @@ impl RuntimeContext {
             match result {
                 Ok(()) => {
                     match &proposal.proposal_type {
-                        ProposalType::SystemParameterChange(key, value) => {
-                            self.update_parameter(key.clone(), value.clone()).await?;
+                        ProposalType::SystemParameterChange(_, _) => {
+                            let changes = {
+                                let gov = self.governance_module.lock().await;
+                                gov.parameters()
+                                    .changes_by(&proposal_id)
+                                    .into_iter()
+                                    .map(|c| {
+                                        let hot = gov
+                                            .parameters()
+                                            .spec(&c.name)
+                                            .is_none_or(|spec| spec.hot_reload);
+                                        (c, hot)
+                                    })
+                                    .collect::<Vec<_>>()
+                            };
+                            for (change, hot_reload) in changes {
+                                self.apply_parameter_change(&change, hot_reload).await?;
+                            }
+                        }
+                        ProposalType::SoftwareUpgrade(_) => {
+                            let gov = self.governance_module.lock().await;
+                            if let Some(upgrade) =
+                                gov.scheduled_upgrade(&proposal_id).map_err(|e| {
+                                    HostAbiError::InternalError(format!(
+                                        "Failed to load scheduled upgrade: {}",
+                                        e
+                                    ))
+                                })?
+                            {
+                                log::info!(
+                                    "Scheduled upgrade {} (protocol v{}) at {}",
+                                    upgrade.manifest.version,
+                                    upgrade.manifest.protocol_version,
+                                    upgrade.manifest.activation
+                                );
+                            }
                         }
                         ProposalType::BudgetAllocation(recipient, amount, _purpose) => {
                             self.credit_mana(recipient, *amount).await.map_err(|e| {
@@ -3203,6 +3484,25 @@ impl RuntimeContext {
                                 ))
                             })?;
                         }
+                        ProposalType::ConstitutionAmendment(_) => {
+                            let block = {
+                                let gov = self.governance_module.lock().await;
+                                let version =
+                                    gov.constitution().current().map(|c| c.constitution.version);
+                                match version {
+                                    Some(v) => gov.constitution().block(v).map_err(|e| {
+                                        HostAbiError::InternalError(format!(
+                                            "Failed to encode constitution: {}",
+                                            e
+                                        ))
+                                    })?,
+                                    None => None,
+                                }
+                            };
+                            if let Some(block) = block {
+                                self.store_governance_block(&block).await?;
+                            }
+                        }
                         _ => {
                             // For other proposal types, just log success
                             log::info!("Executed proposal {:?}", proposal_id);
@@ -3240,10 +3540,25 @@ impl RuntimeContext {
 
     /// Delegate vote to another DID.
     pub async fn delegate_vote(&self, from_did: &str, to_did: &str) -> Result<(), HostAbiError> {
+        self.delegate_vote_scoped(from_did, to_did, "all", None)
+            .await
+    }
+
+    /// Delegate vote to another DID for proposals in `scope` (`all`,
+    /// `type:<kind>` or `tag:<topic>`), optionally until `expires_at`.
+    pub async fn delegate_vote_scoped(
+        &self,
+        from_did: &str,
+        to_did: &str,
+        scope: &str,
+        expires_at: Option<u64>,
+    ) -> Result<(), HostAbiError> {
         let from = Did::from_str(from_did)
             .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid from DID: {}", e)))?;
         let to = Did::from_str(to_did)
             .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid to DID: {}", e)))?;
+        let scope = icn_governance::DelegationScope::from_str(scope)
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid scope: {}", e)))?;
 
         // Only allow delegating your own vote
         if from != self.current_identity {
@@ -3252,8 +3567,9 @@ impl RuntimeContext {
             ));
         }
 
+        let now = self.time_provider.unix_seconds();
         let mut gov = self.governance_module.lock().await;
-        gov.delegate_vote(from, to)
+        gov.delegate_vote_scoped(from, to, scope, expires_at, now)
             .map_err(|e| HostAbiError::InternalError(format!("Failed to delegate vote: {}", e)))?;
 
         Ok(())
@@ -3261,8 +3577,22 @@ impl RuntimeContext {
 
     /// Revoke vote delegation.
     pub async fn revoke_delegation(&self, from_did: &str) -> Result<(), HostAbiError> {
+        self.revoke_delegation_scoped(from_did, None).await
+    }
+
+    /// Revoke vote delegation in a single scope, or all delegations when
+    /// `scope` is `None`.
+    pub async fn revoke_delegation_scoped(
+        &self,
+        from_did: &str,
+        scope: Option<&str>,
+    ) -> Result<(), HostAbiError> {
         let from = Did::from_str(from_did)
             .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid from DID: {}", e)))?;
+        let scope = scope
+            .map(icn_governance::DelegationScope::from_str)
+            .transpose()
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid scope: {}", e)))?;
 
         // Only allow revoking your own delegation
         if from != self.current_identity {
@@ -3272,10 +3602,359 @@ impl RuntimeContext {
         }
 
         let mut gov = self.governance_module.lock().await;
-        gov.revoke_delegation(from);
+        match scope {
+            Some(scope) => gov.revoke_scoped_delegation(&from, &scope),
+            None => gov.revoke_delegation(from),
+        }
+        .map_err(|e| HostAbiError::InternalError(format!("Failed to revoke delegation: {}", e)))
+    }
+
+    /// Attach topic tags to a proposal submitted by the current identity.
+    pub async fn tag_proposal(
+        &self,
+        proposal_id_str: &str,
+        tags: Vec<String>,
+    ) -> Result<(), HostAbiError> {
+        let proposal_id = ProposalId::from_str(proposal_id_str)
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid proposal id: {}", e)))?;
+
+        let mut gov = self.governance_module.lock().await;
+        let proposal = gov
+            .get_proposal(&proposal_id)
+            .map_err(|e| HostAbiError::InternalError(format!("Failed to load proposal: {}", e)))?
+            .ok_or_else(|| {
+                HostAbiError::InvalidParameters(format!("Proposal {} not found", proposal_id_str))
+            })?;
+        if proposal.proposer != self.current_identity {
+            return Err(HostAbiError::PermissionDenied(
+                "Only the proposer can tag a proposal".to_string(),
+            ));
+        }
+        gov.tag_proposal(&proposal_id, tags)
+            .map_err(|e| HostAbiError::InternalError(format!("Failed to tag proposal: {}", e)))?;
+        Ok(())
+    }
+
+    /// Submit a conviction voting budget request from the current identity.
+    pub async fn submit_conviction_proposal(
+        &self,
+        beneficiary_did: &str,
+        amount: u64,
+        description: String,
+    ) -> Result<String, HostAbiError> {
+        let beneficiary = Did::from_str(beneficiary_did).map_err(|e| {
+            HostAbiError::InvalidParameters(format!("Invalid beneficiary DID: {}", e))
+        })?;
+        let cost = self.parameter_u64(
+            icn_governance::parameters::PROPOSAL_COST_MANA,
+            PROPOSAL_COST_MANA,
+        );
+        self.spend_mana(&self.current_identity, cost).await?;
+
+        let mut gov = self.governance_module.lock().await;
+        let id = gov
+            .submit_conviction_proposal(
+                self.current_identity.clone(),
+                beneficiary,
+                amount,
+                description,
+                self.time_provider.as_ref(),
+            )
+            .map_err(|e| {
+                HostAbiError::InternalError(format!("Failed to submit conviction proposal: {}", e))
+            })?;
+        Ok(id.0)
+    }
+
+    /// Stake conviction support from `staker_did` on a proposal.
+    pub async fn stake_conviction(
+        &self,
+        staker_did: &str,
+        proposal_id_str: &str,
+        amount: u64,
+    ) -> Result<(), HostAbiError> {
+        let staker = Did::from_str(staker_did)
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid staker DID: {}", e)))?;
+        let proposal_id = ProposalId::from_str(proposal_id_str)
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid proposal id: {}", e)))?;
+
+        // Only allow staking your own support
+        if staker != self.current_identity {
+            return Err(HostAbiError::PermissionDenied(
+                "Can only stake your own support".to_string(),
+            ));
+        }
+
+        let mut gov = self.governance_module.lock().await;
+        gov.stake_conviction(&proposal_id, staker, amount, self.time_provider.as_ref())
+            .map_err(|e| {
+                HostAbiError::InternalError(format!("Failed to stake conviction: {}", e))
+            })?;
         Ok(())
     }
 
+    /// Advance conviction on all open conviction proposals against the
+    /// balance of the governance-set conviction treasury. Returns accepted
+    /// proposal IDs.
+    pub async fn process_conviction_voting(&self) -> Result<Vec<String>, HostAbiError> {
+        let treasury = self.conviction_treasury_balance().await?;
+        let mut gov = self.governance_module.lock().await;
+        let accepted = gov
+            .process_conviction(treasury, self.time_provider.as_ref())
+            .map_err(|e| {
+                HostAbiError::InternalError(format!("Failed to process conviction voting: {}", e))
+            })?;
+        Ok(accepted.into_iter().map(|t| t.proposal_id.0).collect())
+    }
+
+    /// Mana balance of the account named by the `conviction_treasury_did`
+    /// parameter, or zero while governance has not set one.
+    async fn conviction_treasury_balance(&self) -> Result<u64, HostAbiError> {
+        let treasury = self
+            .governance_module
+            .lock()
+            .await
+            .conviction_treasury()
+            .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))?;
+        match treasury {
+            Some(did) => self.get_mana(&did).await,
+            None => Ok(0),
+        }
+    }
+
+    /// Begin structured deliberation on a proposal, anchoring its submitted
+    /// text as revision zero. Returns the revision CID.
+    pub async fn start_proposal_deliberation(
+        &self,
+        proposal_id_str: &str,
+    ) -> Result<String, HostAbiError> {
+        let proposal_id = ProposalId::from_str(proposal_id_str)
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid proposal id: {}", e)))?;
+        let block = {
+            let mut gov = self.governance_module.lock().await;
+            gov.start_deliberation(&proposal_id, self.time_provider.as_ref())
+                .map_err(|e| {
+                    HostAbiError::InternalError(format!("Failed to start deliberation: {}", e))
+                })?
+        };
+        self.store_governance_block(&block).await?;
+        Ok(block.cid.to_string())
+    }
+
+    /// Publish a new revision of a proposal authored by the current identity.
+    /// Returns the revision CID.
+    pub async fn revise_governance_proposal(
+        &self,
+        proposal_id_str: &str,
+        description: String,
+        body: Option<Vec<u8>>,
+    ) -> Result<String, HostAbiError> {
+        let proposal_id = ProposalId::from_str(proposal_id_str)
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid proposal id: {}", e)))?;
+        let body_cid = self.store_proposal_body(body).await?;
+        let block = {
+            let mut gov = self.governance_module.lock().await;
+            gov.revise_proposal(
+                &proposal_id,
+                &self.current_identity,
+                description,
+                body_cid,
+                self.time_provider.as_ref(),
+            )
+            .map_err(|e| HostAbiError::InternalError(format!("Failed to revise proposal: {}", e)))?
+        };
+        self.store_governance_block(&block).await?;
+        Ok(block.cid.to_string())
+    }
+
+    /// Propose an amendment to a proposal under deliberation. Returns the
+    /// amendment ID.
+    pub async fn propose_amendment(
+        &self,
+        proposal_id_str: &str,
+        description: String,
+        body: Option<Vec<u8>>,
+        rationale: String,
+    ) -> Result<String, HostAbiError> {
+        let proposal_id = ProposalId::from_str(proposal_id_str)
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid proposal id: {}", e)))?;
+        let body_cid = self.store_proposal_body(body).await?;
+        let mut gov = self.governance_module.lock().await;
+        gov.propose_amendment(
+            &proposal_id,
+            self.current_identity.clone(),
+            description,
+            body_cid,
+            rationale,
+            self.time_provider.as_ref(),
+        )
+        .map_err(|e| HostAbiError::InternalError(format!("Failed to propose amendment: {}", e)))
+    }
+
+    /// Vote on an open amendment as the current identity.
+    pub async fn vote_on_amendment(
+        &self,
+        proposal_id_str: &str,
+        amendment_id: &str,
+        vote_option_str: &str,
+    ) -> Result<(), HostAbiError> {
+        let proposal_id = ProposalId::from_str(proposal_id_str)
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid proposal id: {}", e)))?;
+        let option = match vote_option_str.to_lowercase().as_str() {
+            "yes" => VoteOption::Yes,
+            "no" => VoteOption::No,
+            "abstain" => VoteOption::Abstain,
+            other => {
+                return Err(HostAbiError::InvalidParameters(format!(
+                    "Unknown vote option: {}",
+                    other
+                )))
+            }
+        };
+        let mut gov = self.governance_module.lock().await;
+        gov.vote_on_amendment(
+            &proposal_id,
+            amendment_id,
+            self.current_identity.clone(),
+            option,
+        )
+        .map_err(|e| HostAbiError::InternalError(format!("Failed to vote on amendment: {}", e)))
+    }
+
+    /// Tally open amendments, anchoring a new revision for each adopted one.
+    pub async fn resolve_amendments(
+        &self,
+        proposal_id_str: &str,
+    ) -> Result<Vec<icn_governance::Amendment>, HostAbiError> {
+        let proposal_id = ProposalId::from_str(proposal_id_str)
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid proposal id: {}", e)))?;
+        let (amendments, blocks) = {
+            let mut gov = self.governance_module.lock().await;
+            gov.resolve_amendments(&proposal_id, self.time_provider.as_ref())
+                .map_err(|e| {
+                    HostAbiError::InternalError(format!("Failed to resolve amendments: {}", e))
+                })?
+        };
+        for block in &blocks {
+            self.store_governance_block(block).await?;
+        }
+        Ok(amendments)
+    }
+
+    /// Post a comment signed by the node's key on a proposal revision. When
+    /// `revision` is `None` the current head revision is used. Returns the
+    /// comment CID.
+    pub async fn comment_on_proposal(
+        &self,
+        proposal_id_str: &str,
+        revision: Option<&str>,
+        parent: Option<&str>,
+        text: String,
+    ) -> Result<String, HostAbiError> {
+        use icn_common::Signable;
+
+        let proposal_id = ProposalId::from_str(proposal_id_str)
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid proposal id: {}", e)))?;
+        let parse = |s: &str| {
+            icn_common::parse_cid_from_string(s)
+                .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid CID: {}", e)))
+        };
+        let parent = parent.map(parse).transpose()?;
+
+        let mut gov = self.governance_module.lock().await;
+        let revision = match revision {
+            Some(r) => parse(r)?,
+            None => gov
+                .deliberation(&proposal_id)
+                .map_err(|e| HostAbiError::InternalError(e.to_string()))?
+                .ok_or_else(|| {
+                    HostAbiError::InvalidParameters(format!(
+                        "Deliberation for proposal {} has not started",
+                        proposal_id_str
+                    ))
+                })?
+                .head()
+                .map_err(|e| HostAbiError::InternalError(e.to_string()))?
+                .cid
+                .clone(),
+        };
+        let mut comment = icn_governance::Comment {
+            proposal_id,
+            revision,
+            parent,
+            author: self.current_identity.clone(),
+            text,
+            created_at: self.time_provider.unix_seconds(),
+            signature: None,
+        };
+        let payload = comment
+            .to_signable_bytes()
+            .map_err(|e| HostAbiError::InternalError(e.to_string()))?;
+        comment.signature = Some(icn_common::SignatureBytes(self.signer.sign(&payload)?));
+        let block = gov
+            .add_comment(comment)
+            .map_err(|e| HostAbiError::InternalError(format!("Failed to add comment: {}", e)))?;
+        drop(gov);
+        self.store_governance_block(&block).await?;
+        Ok(block.cid.to_string())
+    }
+
+    /// Cast a vote that is only counted if voting is bound to `revision_cid`.
+    pub async fn cast_governance_vote_on_revision(
+        &self,
+        payload: CastVotePayload,
+        revision_cid: &str,
+    ) -> Result<(), HostAbiError> {
+        let revision = icn_common::parse_cid_from_string(revision_cid)
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid CID: {}", e)))?;
+        let proposal_id = ProposalId::from_str(&payload.proposal_id_str)
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid proposal id: {}", e)))?;
+        let bound = self
+            .governance_module
+            .lock()
+            .await
+            .deliberation(&proposal_id)
+            .map_err(|e| HostAbiError::InternalError(e.to_string()))?
+            .and_then(|s| s.bound_revision);
+        if bound.as_ref() != Some(&revision) {
+            return Err(HostAbiError::InvalidParameters(format!(
+                "Proposal {} is not being voted on at revision {}",
+                payload.proposal_id_str, revision_cid
+            )));
+        }
+        self.cast_governance_vote(payload).await
+    }
+
+    async fn store_proposal_body(
+        &self,
+        body: Option<Vec<u8>>,
+    ) -> Result<Option<Cid>, HostAbiError> {
+        let Some(body) = body else {
+            return Ok(None);
+        };
+        let block = DagBlock {
+            cid: Cid::new_v1_sha256(0x55, &body),
+            data: body,
+            links: vec![],
+            timestamp: self.time_provider.unix_seconds(),
+            author_did: self.current_identity.clone(),
+            signature: None,
+            scope: None,
+        };
+        let mut dag_store = self.dag_store.inner().lock().await;
+        dag_store.put(&block).await.map_err(|e| {
+            HostAbiError::DagOperationFailed(format!("Failed to store proposal body: {}", e))
+        })?;
+        Ok(Some(block.cid))
+    }
+
+    async fn store_governance_block(&self, block: &DagBlock) -> Result<(), HostAbiError> {
+        let mut dag_store = self.dag_store.inner().lock().await;
+        dag_store.put(block).await.map_err(|e| {
+            HostAbiError::DagOperationFailed(format!("Failed to anchor governance block: {}", e))
+        })
+    }
+
     /// Record a resource event and anchor it in the DAG.
     pub async fn record_resource_event(
         &self,
@@ -3352,24 +4031,952 @@ impl RuntimeContext {
         })
     }
 
-    /// Update a system parameter.
-    async fn update_parameter(&self, key: String, value: String) -> Result<(), HostAbiError> {
-        self.parameters.insert(key.clone(), value.clone());
-        log::info!("Updated parameter {} to {}", key, value);
-
-        let update = ParameterUpdate {
-            name: key,
-            value,
-            timestamp: self.time_provider.unix_seconds(),
-            signer: self.current_identity.clone(),
+    /// Submit a proposal restoring the value replaced by parameter change
+    /// `version`. Returns the new proposal ID.
+    pub async fn propose_parameter_rollback(
+        &self,
+        version: u64,
+        duration_secs: u64,
+    ) -> Result<String, HostAbiError> {
+        let proposal_type = {
+            let gov = self.governance_module.lock().await;
+            gov.parameters()
+                .rollback_proposal(version)
+                .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))?
+        };
+        let ProposalType::SystemParameterChange(name, value) = proposal_type else {
+            unreachable!("rollback proposals change a parameter");
         };
+        let type_specific_payload = serde_json::to_vec(&(name.as_str(), value.as_str()))
+            .map_err(|e| HostAbiError::InternalError(e.to_string()))?;
+        self.create_governance_proposal(CreateProposalPayload {
+            proposal_type_str: "SystemParameterChange".to_string(),
+            type_specific_payload,
+            description: format!(
+                "Roll back parameter change {}: restore {} to {}",
+                version, name, value
+            ),
+            duration_secs,
+            quorum: None,
+            threshold: None,
+            body: None,
+        })
+        .await
+    }
 
-        // Persist to DAG
+    /// Sign and record this node's readiness for the upgrade scheduled by
+    /// `proposal_id_str`, reporting `version` as the build it runs.
+    ///
+    /// Returns the signed signal so it can be relayed to other nodes.
+    pub async fn signal_upgrade_readiness(
+        &self,
+        proposal_id_str: &str,
+        version: &str,
+    ) -> Result<icn_governance::ReadinessSignal, HostAbiError> {
+        use icn_common::Signable;
+
+        let proposal_id = ProposalId::from_str(proposal_id_str)
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid proposal id: {}", e)))?;
+        let mut signal = icn_governance::ReadinessSignal {
+            node: self.current_identity.clone(),
+            proposal_id,
+            version: version.to_string(),
+            signed_at: self.time_provider.unix_seconds(),
+            signature: None,
+        };
+        let payload = signal
+            .to_signable_bytes()
+            .map_err(|e| HostAbiError::InternalError(e.to_string()))?;
+        signal.signature = Some(icn_common::SignatureBytes(self.signer.sign(&payload)?));
+
+        let mut gov = self.governance_module.lock().await;
+        gov.signal_upgrade_readiness(signal.clone())
+            .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))?;
+        Ok(signal)
+    }
+
+    /// Record a readiness signal relayed from another node.
+    pub async fn ingest_upgrade_readiness(
+        &self,
+        signal: icn_governance::ReadinessSignal,
+    ) -> Result<(), HostAbiError> {
+        let mut gov = self.governance_module.lock().await;
+        gov.signal_upgrade_readiness(signal)
+            .map(|_| ())
+            .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))
+    }
+
+    /// Scheduled software upgrades and the protocol version active at
+    /// `height`.
+    pub async fn upgrade_status(
+        &self,
+        height: u64,
+    ) -> Result<(u32, Vec<icn_governance::ScheduledUpgrade>), HostAbiError> {
+        let now = self.time_provider.unix_seconds();
+        let gov = self.governance_module.lock().await;
+        let active = gov
+            .active_protocol_version(height, now)
+            .map_err(|e| HostAbiError::InternalError(e.to_string()))?;
+        let upgrades = gov
+            .scheduled_upgrades()
+            .map_err(|e| HostAbiError::InternalError(e.to_string()))?;
+        Ok((active, upgrades))
+    }
+
+    /// Feature gate for behaviour introduced in `protocol_version`.
+    pub async fn is_protocol_active(
+        &self,
+        protocol_version: u32,
+        height: u64,
+    ) -> Result<bool, HostAbiError> {
+        let now = self.time_provider.unix_seconds();
+        let gov = self.governance_module.lock().await;
+        gov.is_protocol_active(protocol_version, height, now)
+            .map_err(|e| HostAbiError::InternalError(e.to_string()))
+    }
+
+    /// Register `member_did`'s identity commitment for secret ballots.
+    pub async fn register_voter_commitment(
+        &self,
+        member_did: &str,
+        commitment: &str,
+    ) -> Result<(), HostAbiError> {
+        let member = Did::from_str(member_did)
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid DID: {}", e)))?;
+
+        // Only allow registering your own commitment
+        if member != self.current_identity {
+            return Err(HostAbiError::PermissionDenied(
+                "Can only register your own voter commitment".to_string(),
+            ));
+        }
+
+        let mut gov = self.governance_module.lock().await;
+        gov.register_voter_commitment(member, commitment)
+            .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))
+    }
+
+    /// Switch a proposal to secret ballots before voting opens.
+    pub async fn enable_secret_ballot(
+        &self,
+        proposal_id_str: &str,
+        verifying_key: &str,
+        tree_depth: usize,
+        reveal_from: u64,
+    ) -> Result<icn_governance::SecretBallotConfig, HostAbiError> {
+        let proposal_id = ProposalId::from_str(proposal_id_str)
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid proposal id: {}", e)))?;
+        let mut gov = self.governance_module.lock().await;
+        gov.enable_secret_ballot(&proposal_id, verifying_key, tree_depth, reveal_from)
+            .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))
+    }
+
+    /// Accept an anonymous ballot and anchor it. Returns the block CID.
+    pub async fn cast_secret_ballot(
+        &self,
+        ballot: icn_governance::SecretBallot,
+    ) -> Result<String, HostAbiError> {
+        let block = {
+            let mut gov = self.governance_module.lock().await;
+            gov.cast_secret_ballot(ballot, self.time_provider.as_ref())
+                .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))?
+        };
+        self.store_governance_block(&block).await?;
+        Ok(block.cid.to_string())
+    }
+
+    /// Accept the opening of a secret ballot and anchor it. Returns the block
+    /// CID.
+    pub async fn reveal_secret_ballot(
+        &self,
+        reveal: icn_governance::BallotReveal,
+    ) -> Result<String, HostAbiError> {
+        let block = {
+            let mut gov = self.governance_module.lock().await;
+            gov.reveal_secret_ballot(reveal, self.time_provider.as_ref())
+                .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))?
+        };
+        self.store_governance_block(&block).await?;
+        Ok(block.cid.to_string())
+    }
+
+    /// Secret-ballot records of a proposal and their verified tally.
+    pub async fn secret_ballot_status(
+        &self,
+        proposal_id_str: &str,
+    ) -> Result<
+        Option<(
+            icn_governance::SecretBallotState,
+            icn_governance::SecretTally,
+        )>,
+        HostAbiError,
+    > {
+        let proposal_id = ProposalId::from_str(proposal_id_str)
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid proposal id: {}", e)))?;
+        let gov = self.governance_module.lock().await;
+        let Some(state) = gov
+            .secret_ballot(&proposal_id)
+            .map_err(|e| HostAbiError::InternalError(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+        let tally =
+            icn_governance::verify_secret_tally(&state.config, &state.ballots, &state.reveals)
+                .map_err(|e| HostAbiError::InternalError(e.to_string()))?;
+        Ok(Some((state, tally)))
+    }
+
+    /// Adopt a signed joint proposal on behalf of `local_federation` and open
+    /// the local ratification vote for `duration_secs`.
+    ///
+    /// Returns the id of the local proposal.
+    pub async fn adopt_cross_federation_proposal(
+        &self,
+        proposal: icn_governance::JointProposal,
+        local_federation: &str,
+        duration_secs: u64,
+    ) -> Result<String, HostAbiError> {
+        let (local_id, block) = {
+            let mut gov = self.governance_module.lock().await;
+            gov.adopt_cross_federation_proposal(
+                proposal,
+                local_federation,
+                self.current_identity.clone(),
+                duration_secs,
+                self.time_provider.as_ref(),
+            )
+            .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))?
+        };
+        self.store_governance_block(&block).await?;
+        Ok(local_id.0)
+    }
+
+    /// Sign and record this federation's outcome for a joint proposal once
+    /// its local vote has closed.
+    ///
+    /// Returns the signed ratification so it can be sent to the other
+    /// participating federations.
+    pub async fn ratify_cross_federation_proposal(
+        &self,
+        joint_proposal_id_str: &str,
+    ) -> Result<icn_governance::FederationRatification, HostAbiError> {
+        use icn_common::Signable;
+
+        let joint_proposal_id = ProposalId::from_str(joint_proposal_id_str)
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid proposal id: {}", e)))?;
+        let mut ratification = {
+            let gov = self.governance_module.lock().await;
+            gov.prepare_federation_ratification(&joint_proposal_id, self.current_identity.clone())
+                .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))?
+        };
+        let payload = ratification
+            .to_signable_bytes()
+            .map_err(|e| HostAbiError::InternalError(e.to_string()))?;
+        ratification.signature = Some(icn_common::SignatureBytes(self.signer.sign(&payload)?));
+        self.record_federation_ratification(ratification.clone())
+            .await?;
+        Ok(ratification)
+    }
+
+    /// Record a signed ratification received from a participating federation.
+    pub async fn record_federation_ratification(
+        &self,
+        ratification: icn_governance::FederationRatification,
+    ) -> Result<String, HostAbiError> {
+        let block = {
+            let mut gov = self.governance_module.lock().await;
+            gov.record_federation_ratification(ratification)
+                .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))?
+        };
+        self.store_governance_block(&block).await?;
+        Ok(block.cid.to_string())
+    }
+
+    /// Execute a ratified joint proposal.
+    ///
+    /// For a joint budget this federation's pledged contribution is taken
+    /// from the node's mana account.
+    pub async fn execute_cross_federation_proposal(
+        &self,
+        joint_proposal_id_str: &str,
+    ) -> Result<(), HostAbiError> {
+        let joint_proposal_id = ProposalId::from_str(joint_proposal_id_str)
+            .map_err(|e| HostAbiError::InvalidParameters(format!("Invalid proposal id: {}", e)))?;
+        let state = self
+            .governance_module
+            .lock()
+            .await
+            .execute_cross_federation_proposal(&joint_proposal_id)
+            .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))?;
+
+        if let icn_identity::CrossFederationProposalType::JointBudget {
+            purpose,
+            contributions,
+        } = &state.proposal.proposal_type
+        {
+            if let Some(amount) = contributions.get(&state.local_federation) {
+                self.spend_mana(&self.current_identity, *amount).await?;
+                log::info!(
+                    "Contributed {} mana to joint budget '{}' ({})",
+                    amount,
+                    purpose,
+                    joint_proposal_id
+                );
+            }
+        } else {
+            log::info!("Executed cross-federation proposal {}", joint_proposal_id);
+        }
+        Ok(())
+    }
+
+    /// Adopt the cooperative's first constitution and anchor it in the DAG.
+    pub async fn adopt_constitution(
+        &self,
+        constitution: icn_governance::Constitution,
+    ) -> Result<String, HostAbiError> {
+        let now = self.time_provider.unix_seconds();
+        let block = {
+            let mut gov = self.governance_module.lock().await;
+            gov.adopt_constitution(constitution, self.current_identity.clone(), now)
+                .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))?
+        };
+        self.store_governance_block(&block).await?;
+        Ok(block.cid.to_string())
+    }
+
+    /// Accept membership applications to `gate.federation` under `gate`.
+    pub async fn set_membership_gate(
+        &self,
+        gate: icn_governance::federation_governance::FederationMembershipTrustGate,
+    ) -> Result<(), HostAbiError> {
+        self.governance_module
+            .lock()
+            .await
+            .set_membership_gate(gate)
+            .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))
+    }
+
+    /// Screen a signed membership application and anchor it in the DAG.
+    ///
+    /// The challenge nonce is derived from this node's signature over the
+    /// application id, so the applicant cannot predict it.
+    pub async fn submit_membership_application(
+        &self,
+        application: icn_governance::MembershipApplication,
+    ) -> Result<icn_governance::MembershipApplicationState, HostAbiError> {
+        use sha2::{Digest, Sha256};
+
+        let mut seed = b"icn-membership-nonce".to_vec();
+        seed.extend_from_slice(application.id.0.as_bytes());
+        seed.extend_from_slice(&self.time_provider.unix_seconds().to_le_bytes());
+        let nonce: [u8; 32] = Sha256::digest(self.signer.sign(&seed)?).into();
+        let (state, block) = {
+            let mut gov = self.governance_module.lock().await;
+            gov.submit_membership_application(application, nonce, self.time_provider.as_ref())
+                .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))?
+        };
+        self.store_governance_block(&block).await?;
+        Ok(state)
+    }
+
+    /// Record an applicant's challenge response. The membership vote is
+    /// opened in this node's name once every challenge is answered.
+    pub async fn answer_membership_challenge(
+        &self,
+        response: icn_governance::ChallengeResponse,
+    ) -> Result<icn_governance::MembershipApplicationState, HostAbiError> {
+        self.governance_module
+            .lock()
+            .await
+            .answer_membership_challenge(
+                response,
+                self.current_identity.clone(),
+                self.time_provider.as_ref(),
+            )
+            .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))
+    }
+
+    /// Issue a membership credential to every accepted applicant that does
+    /// not have one yet.
+    ///
+    /// Returns the onboarded applications so the node can add their peers.
+    pub async fn onboard_accepted_members(
+        &self,
+    ) -> Result<Vec<icn_governance::MembershipApplicationState>, HostAbiError> {
+        let pending = self
+            .governance_module
+            .lock()
+            .await
+            .pending_membership_onboarding()
+            .map_err(|e| HostAbiError::InternalError(e.to_string()))?;
+
+        let mut onboarded = Vec::new();
+        for state in pending {
+            let Some(grant) = &state.grant else {
+                continue;
+            };
+            let mut claims = std::collections::HashMap::from([
+                ("federation".to_string(), grant.federation.clone()),
+                ("role".to_string(), "member".to_string()),
+                (
+                    "membership_application".to_string(),
+                    state.application.id.0.clone(),
+                ),
+                ("granted_at".to_string(), grant.granted_at.to_string()),
+                (
+                    "contexts".to_string(),
+                    grant
+                        .contexts
+                        .iter()
+                        .map(|c| c.as_str())
+                        .collect::<Vec<_>>()
+                        .join(","),
+                ),
+            ]);
+            if let Some(until) = grant.probationary_until {
+                claims.insert("probationary_until".to_string(), until.to_string());
+            }
+            let mut credential = icn_identity::Credential::new(
+                self.current_identity.clone(),
+                grant.member.clone(),
+                claims,
+                None,
+            );
+            for (k, v) in &credential.claims {
+                let mut bytes = credential.issuer.to_string().into_bytes();
+                bytes.extend_from_slice(credential.holder.to_string().as_bytes());
+                bytes.extend_from_slice(k.as_bytes());
+                bytes.extend_from_slice(v.as_bytes());
+                let sig = self.signer.sign(&bytes)?;
+                credential
+                    .signatures
+                    .insert(k.clone(), icn_identity::SignatureBytes(sig));
+            }
+            let state = self
+                .governance_module
+                .lock()
+                .await
+                .record_membership_credential(&state.application.id, credential)
+                .map_err(|e| HostAbiError::InternalError(e.to_string()))?;
+            log::info!(
+                "Onboarded {} into federation {}",
+                state.application.applicant,
+                state.application.federation
+            );
+            onboarded.push(state);
+        }
+        Ok(onboarded)
+    }
+
+    /// Open a due-process case from a signed complaint.
+    pub async fn file_complaint(
+        &self,
+        complaint: icn_governance::Complaint,
+    ) -> Result<icn_governance::DueProcessCase, HostAbiError> {
+        let (case, block) = {
+            let mut gov = self.governance_module.lock().await;
+            gov.file_complaint(complaint, self.time_provider.as_ref())
+                .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))?
+        };
+        self.store_governance_block(&block).await?;
+        Ok(case)
+    }
+
+    /// Record the subject's signed response to a complaint.
+    pub async fn respond_to_complaint(
+        &self,
+        response: icn_governance::SubjectResponse,
+    ) -> Result<icn_governance::DueProcessCase, HostAbiError> {
+        let (case, block) = {
+            let mut gov = self.governance_module.lock().await;
+            gov.respond_to_complaint(response, self.time_provider.as_ref())
+                .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))?
+        };
+        self.store_governance_block(&block).await?;
+        Ok(case)
+    }
+
+    /// Record a panelist's signed verdict.
+    pub async fn submit_panel_verdict(
+        &self,
+        verdict: icn_governance::PanelVerdict,
+    ) -> Result<icn_governance::DueProcessCase, HostAbiError> {
+        let (case, block) = {
+            let mut gov = self.governance_module.lock().await;
+            gov.submit_panel_verdict(verdict, self.time_provider.as_ref())
+                .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))?
+        };
+        self.store_governance_block(&block).await?;
+        Ok(case)
+    }
+
+    /// Record a party's signed appeal.
+    pub async fn file_appeal(
+        &self,
+        appeal: icn_governance::Appeal,
+    ) -> Result<icn_governance::DueProcessCase, HostAbiError> {
+        let (case, block) = {
+            let mut gov = self.governance_module.lock().await;
+            gov.file_appeal(appeal, self.time_provider.as_ref())
+                .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))?
+        };
+        self.store_governance_block(&block).await?;
+        Ok(case)
+    }
+
+    /// Draw a hearing panel for every case waiting for one.
+    ///
+    /// The seed is a one-participant commit-reveal round whose secret is this
+    /// node's signature over the draw id. Ed25519 signatures are
+    /// deterministic, so the node cannot choose among several seeds. The
+    /// signed commitment is anchored in the DAG first and the draw is checked
+    /// against the anchored copy.
+    async fn draw_pending_hearing_panels(&self) -> Result<(), HostAbiError> {
+        use icn_common::Signable;
+
+        let waiting: Vec<_> = self
+            .governance_module
+            .lock()
+            .await
+            .due_process_cases()
+            .map_err(|e| HostAbiError::InternalError(e.to_string()))?
+            .into_iter()
+            .filter(|c| matches!(c.status, icn_governance::CaseStatus::AwaitingPanel { .. }))
+            .collect();
+        for case in waiting {
+            let round_id = format!("{}:panel:{}", case.complaint.id, case.hearings.len());
+            let secret = self.signer.sign(round_id.as_bytes())?;
+            let mut commitment = icn_governance::BeaconCommitment::new(
+                round_id.clone(),
+                self.current_identity.clone(),
+                &secret,
+            );
+            let bytes = commitment
+                .to_signable_bytes()
+                .map_err(|e| HostAbiError::SerializationError(e.to_string()))?;
+            commitment.signature = Some(icn_common::SignatureBytes(self.signer.sign(&bytes)?));
+            let block = commitment
+                .to_dag_block(self.time_provider.unix_seconds())
+                .map_err(|e| HostAbiError::SerializationError(e.to_string()))?;
+            self.store_governance_block(&block).await?;
+            let anchored = self
+                .dag_store
+                .inner()
+                .lock()
+                .await
+                .get(&block.cid)
+                .await
+                .map_err(|e| HostAbiError::DagOperationFailed(e.to_string()))?
+                .ok_or_else(|| {
+                    HostAbiError::DagOperationFailed(format!(
+                        "Beacon commitment {} was not anchored",
+                        block.cid
+                    ))
+                })?;
+            let mut anchored_commitments = icn_dag::InMemoryDagStore::new();
+            icn_dag::StorageService::put(&mut anchored_commitments, &anchored)
+                .map_err(|e| HostAbiError::DagOperationFailed(e.to_string()))?;
+            let trust = icn_governance::RandomnessTrust::new(Arc::new(anchored_commitments))
+                .with_beacon_participants(vec![self.current_identity.clone()]);
+            let source = icn_governance::RandomnessSource::CommitReveal {
+                reveals: vec![icn_governance::BeaconReveal {
+                    participant: self.current_identity.clone(),
+                    commitment_cid: block.cid.clone(),
+                    secret,
+                }],
+                round_id,
+                missing: Vec::new(),
+            };
+            let drawn = {
+                let mut gov = self.governance_module.lock().await;
+                gov.draw_hearing_panel(
+                    &case.complaint.id,
+                    source,
+                    &trust,
+                    &self.current_identity,
+                    self.time_provider.as_ref(),
+                )
+            };
+            match drawn {
+                Ok((_, block)) => self.store_governance_block(&block).await?,
+                Err(e) => log::warn!(
+                    "Could not draw a panel for case {}: {}",
+                    case.complaint.id,
+                    e
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Accept weighting credentials from `issuers` and from this node, which
+    /// issues membership credentials on onboarding.
+    pub async fn trust_weighting_issuers(&self, mut issuers: Vec<Did>) {
+        if !issuers.contains(&self.current_identity) {
+            issuers.push(self.current_identity.clone());
+        }
+        self.governance_module
+            .lock()
+            .await
+            .set_weighting_config(icn_governance::WeightingConfig {
+                trusted_issuers: issuers,
+            });
+    }
+
+    /// Weight votes on `kind` proposals by `rule`, or drop the rule when it
+    /// is `None`.
+    pub async fn set_weight_rule(
+        &self,
+        kind: &str,
+        rule: Option<icn_governance::WeightRule>,
+    ) -> Result<(), HostAbiError> {
+        let mut gov = self.governance_module.lock().await;
+        match rule {
+            Some(rule) => gov.set_weight_rule(kind, rule),
+            None => gov.clear_weight_rule(kind),
+        }
+        .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))
+    }
+
+    /// Size a member's voting weight from its cooperative profile.
+    pub async fn record_cooperative_profile(
+        &self,
+        profile: &icn_identity::CooperativeProfile,
+    ) -> Result<icn_governance::MemberProfile, HostAbiError> {
+        self.governance_module
+            .lock()
+            .await
+            .record_cooperative_profile(profile)
+            .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))
+    }
+
+    /// Update a member's chambers and size from a membership credential.
+    pub async fn record_weighting_credential(
+        &self,
+        credential: &icn_identity::Credential,
+    ) -> Result<icn_governance::MemberProfile, HostAbiError> {
+        self.governance_module
+            .lock()
+            .await
+            .record_weighting_credential(credential)
+            .map_err(|e| HostAbiError::InvalidParameters(e.to_string()))
+    }
+
+    /// Fire every governance lifecycle step that is due, advance conviction
+    /// voting and execute accepted proposals when `auto_execute` is enabled.
+    ///
+    /// Executions that fail are retried after
+    /// [`icn_governance::LIFECYCLE_RETRY_SECS`].
+    pub async fn advance_governance_lifecycle(
+        &self,
+    ) -> Result<Vec<icn_governance::LifecycleTransition>, HostAbiError> {
+        let now = self.time_provider.unix_seconds();
+        let treasury = match self.conviction_treasury_balance().await {
+            Ok(balance) => balance,
+            Err(e) => {
+                log::warn!("Treasury balance unavailable for conviction voting: {}", e);
+                0
+            }
+        };
+        let (mut transitions, due) = {
+            let mut gov = self.governance_module.lock().await;
+            let mut transitions = gov
+                .process_conviction(treasury, self.time_provider.as_ref())
+                .map_err(|e| HostAbiError::InternalError(e.to_string()))?;
+            transitions.extend(
+                gov.advance_lifecycle(self.time_provider.as_ref())
+                    .map_err(|e| HostAbiError::InternalError(e.to_string()))?,
+            );
+            let due = gov
+                .due_executions(now)
+                .map_err(|e| HostAbiError::InternalError(e.to_string()))?;
+            for joint in gov
+                .expire_cross_federation_proposals(now)
+                .map_err(|e| HostAbiError::InternalError(e.to_string()))?
+            {
+                log::info!("Cross-federation proposal {} timed out", joint);
+            }
+            for case in gov
+                .refresh_due_process(now)
+                .map_err(|e| HostAbiError::InternalError(e.to_string()))?
+            {
+                log::info!(
+                    "Due-process case {} is now {:?}",
+                    case.complaint.id,
+                    case.status
+                );
+            }
+            for state in gov
+                .refresh_membership_applications(now)
+                .map_err(|e| HostAbiError::InternalError(e.to_string()))?
+            {
+                log::info!(
+                    "Membership application {} is now {:?}",
+                    state.application.id,
+                    state.status
+                );
+            }
+            (transitions, due)
+        };
+        self.draw_pending_hearing_panels().await?;
+
+        for (proposal_id, due_at) in due {
+            match self.execute_governance_proposal(&proposal_id.0).await {
+                Ok(()) => transitions.push(icn_governance::LifecycleTransition {
+                    proposal_id,
+                    step: icn_governance::LifecycleStep::Execute,
+                    from: icn_governance::ProposalStatus::Accepted,
+                    to: icn_governance::ProposalStatus::Executed,
+                    at: due_at,
+                }),
+                Err(e) => {
+                    log::warn!("Scheduled execution of {} failed: {}", proposal_id, e);
+                    self.governance_module
+                        .lock()
+                        .await
+                        .defer_lifecycle_timer(
+                            &proposal_id,
+                            now + icn_governance::LIFECYCLE_RETRY_SECS,
+                        )
+                        .map_err(|e| HostAbiError::InternalError(e.to_string()))?;
+                }
+            }
+        }
+        Ok(transitions)
+    }
+
+    /// Spawn a background task advancing the governance lifecycle every
+    /// `interval`. `on_transition` is called for each status change with the
+    /// updated proposal.
+    pub async fn spawn_governance_scheduler<F>(
+        self: Arc<Self>,
+        interval: Duration,
+        on_transition: F,
+    ) where
+        F: Fn(&icn_governance::LifecycleTransition, &Proposal) + Send + Sync + 'static,
+    {
+        let ctx = self.clone();
+
+        tokio::spawn(async move {
+            log::info!("Starting governance lifecycle scheduler");
+
+            let mut interval = tokio::time::interval(interval);
+            loop {
+                interval.tick().await;
+                match ctx.advance_governance_lifecycle().await {
+                    Ok(transitions) => {
+                        for transition in &transitions {
+                            log::info!(
+                                "Proposal {} moved from {:?} to {:?} ({})",
+                                transition.proposal_id,
+                                transition.from,
+                                transition.to,
+                                transition.step
+                            );
+                            let proposal = ctx
+                                .governance_module
+                                .lock()
+                                .await
+                                .get_proposal(&transition.proposal_id);
+                            match proposal {
+                                Ok(Some(proposal)) => on_transition(transition, &proposal),
+                                Ok(None) => {}
+                                Err(e) => log::warn!(
+                                    "Failed to load proposal {}: {}",
+                                    transition.proposal_id,
+                                    e
+                                ),
+                            }
+                        }
+                    }
+                    Err(e) => log::error!("Governance lifecycle tick failed: {}", e),
+                }
+            }
+        });
+    }
+
+    /// Publish that DIDComm mail for `did` is routed through `mediator`.
+    ///
+    /// The route is a `DIDCommMessaging` service entry signed with this
+    /// node's key and stored in the DHT under
+    /// [`icn_network::DIDCOMM_ROUTE_PREFIX`]. Stub networks keep no routes.
+    pub async fn publish_didcomm_route(
+        &self,
+        did: &Did,
+        mediator: &Did,
+    ) -> Result<(), HostAbiError> {
+        let MeshNetworkServiceType::Default(service) = &*self.mesh_network_service else {
+            log::debug!("Stub network: not publishing DIDComm route for {}", did);
+            return Ok(());
+        };
+        let mut route = icn_identity::DidCommRoute::new(did, mediator);
+        let bytes = route
+            .signable_bytes()
+            .map_err(|e| HostAbiError::SerializationError(e.to_string()))?;
+        route.signature = icn_identity::SignatureBytes(self.signer.sign(&bytes)?);
+        let value = serde_json::to_vec(&route)
+            .map_err(|e| HostAbiError::SerializationError(e.to_string()))?;
+        service
+            .inner
+            .store_record(
+                format!("{}{}", icn_network::DIDCOMM_ROUTE_PREFIX, did),
+                value,
+            )
+            .await
+            .map_err(|e| {
+                HostAbiError::NetworkError(format!("Failed to publish DIDComm route: {}", e))
+            })
+    }
+
+    /// Mediator that routes DIDComm mail for `did`, if one is published and
+    /// signed by `did`'s key.
+    pub async fn didcomm_route(&self, did: &Did) -> Result<Option<Did>, HostAbiError> {
+        let MeshNetworkServiceType::Default(service) = &*self.mesh_network_service else {
+            return Ok(None);
+        };
+        let Some(value) = service
+            .inner
+            .get_record(format!("{}{}", icn_network::DIDCOMM_ROUTE_PREFIX, did))
+            .await
+            .map_err(|e| {
+                HostAbiError::NetworkError(format!("Failed to look up DIDComm route: {}", e))
+            })?
+        else {
+            return Ok(None);
+        };
+        let route: icn_identity::DidCommRoute = serde_json::from_slice(&value).map_err(|e| {
+            HostAbiError::SerializationError(format!("Invalid DIDComm route: {}", e))
+        })?;
+        if route.did != *did {
+            return Err(HostAbiError::InvalidParameters(format!(
+                "DIDComm route for {} is stored under {}",
+                route.did, did
+            )));
+        }
+        let key = self.did_resolver.resolve(did)?;
+        route
+            .verify(&key)
+            .map(Some)
+            .map_err(|e| HostAbiError::PermissionDenied(format!("Rejected DIDComm route: {}", e)))
+    }
+
+    /// Hand every DIDComm envelope relayed to this node's DID to `on_envelope`.
+    pub async fn spawn_didcomm_listener<F>(self: Arc<Self>, on_envelope: F)
+    where
+        F: Fn(String) + Send + Sync + 'static,
+    {
+        let MeshNetworkServiceType::Default(service) = &*self.mesh_network_service else {
+            log::info!("Stub network: DIDComm envelopes arrive over HTTP only");
+            return;
+        };
+        let mut receiver = match service.inner.subscribe().await {
+            Ok(receiver) => receiver,
+            Err(e) => {
+                log::error!("Failed to subscribe to DIDComm envelopes: {}", e);
+                return;
+            }
+        };
+        let own_did = self.current_identity.clone();
+        tokio::spawn(async move {
+            while let Some(message) = receiver.recv().await {
+                if let icn_protocol::MessagePayload::DidCommEnvelope(envelope) = message.payload {
+                    if message.recipient.as_ref() == Some(&own_did) {
+                        on_envelope(envelope.envelope);
+                    }
+                }
+            }
+            log::warn!("DIDComm envelope stream ended");
+        });
+    }
+
+    /// Apply an executed parameter change and anchor it in the DAG.
+    ///
+    /// Changes to parameters that are not hot-reloadable are recorded but only
+    /// take effect once the node restarts and calls
+    /// [`RuntimeContext::load_governance_parameters`].
+    async fn apply_parameter_change(
+        &self,
+        change: &icn_governance::ParameterChange,
+        hot_reload: bool,
+    ) -> Result<(), HostAbiError> {
+        let value = change.value.to_string();
+        if hot_reload {
+            self.parameters.insert(change.name.clone(), value.clone());
+            log::info!("Updated parameter {} to {}", change.name, value);
+        } else {
+            log::info!(
+                "Parameter {} set to {}; takes effect after restart",
+                change.name,
+                value
+            );
+        }
+
+        let update = ParameterUpdate {
+            name: change.name.clone(),
+            value,
+            previous: change.previous.to_string(),
+            version: change.version,
+            proposal_id: change.proposal_id.as_ref().map(|p| p.0.clone()),
+            timestamp: change.effective_at,
+            signer: self.current_identity.clone(),
+        };
+
+        // Persist to DAG
         self.anchor_parameter_update(&update).await?;
 
         Ok(())
     }
 
+    /// Copy the current values of all governance parameters into
+    /// [`RuntimeContext::parameters`], e.g. after a restart.
+    pub async fn load_governance_parameters(&self) {
+        let gov = self.governance_module.lock().await;
+        let registry = gov.parameters();
+        for spec in registry.specs() {
+            let value = registry
+                .get(&spec.name)
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+            if value.is_empty() {
+                self.parameters.remove(&spec.name);
+            } else {
+                self.parameters.insert(spec.name.clone(), value);
+            }
+        }
+    }
+
+    /// Numeric runtime parameter, or `default` when unset or malformed.
+    pub fn parameter_u64(&self, key: &str, default: u64) -> u64 {
+        self.parameters
+            .get(key)
+            .and_then(|v| v.value().parse().ok())
+            .unwrap_or(default)
+    }
+
+    fn parameter_f64(&self, key: &str, default: f64) -> f64 {
+        self.parameters
+            .get(key)
+            .and_then(|v| v.value().parse().ok())
+            .unwrap_or(default)
+    }
+
+    /// Executor selection weights as set by governance.
+    pub fn selection_policy(&self) -> icn_mesh::SelectionPolicy {
+        use icn_governance::parameters::{
+            EXECUTOR_WEIGHT_LATENCY, EXECUTOR_WEIGHT_PRICE, EXECUTOR_WEIGHT_REPUTATION,
+            EXECUTOR_WEIGHT_RESOURCES,
+        };
+        let defaults = icn_mesh::SelectionPolicy::default();
+        icn_mesh::SelectionPolicy {
+            weight_price: self.parameter_f64(EXECUTOR_WEIGHT_PRICE, defaults.weight_price),
+            weight_reputation: self
+                .parameter_f64(EXECUTOR_WEIGHT_REPUTATION, defaults.weight_reputation),
+            weight_resources: self
+                .parameter_f64(EXECUTOR_WEIGHT_RESOURCES, defaults.weight_resources),
+            weight_latency: self.parameter_f64(EXECUTOR_WEIGHT_LATENCY, defaults.weight_latency),
+        }
+    }
+
     /// Spawn the mesh job manager with full lifecycle support.
     ///
     /// This manager handles the complete mesh job lifecycle:
@@ -3556,8 +5163,8 @@ impl RuntimeContext {
             bids.len()
         );
 
-        // Create selection policy (could be configurable via governance)
-        let selection_policy = icn_mesh::SelectionPolicy::default();
+        // Selection weights are governance parameters
+        let selection_policy = ctx.selection_policy();
 
         let selected_executor = icn_mesh::select_executor(
             job_id,
@@ -3833,7 +5440,8 @@ impl RuntimeContext {
 
                     // Calculate regeneration based on reputation and policy
                     let reputation = ctx.reputation_store.get_reputation(&account_did);
-                    let base_regeneration = 10u64; // Base regeneration per minute
+                    let base_regeneration =
+                        ctx.parameter_u64(icn_governance::parameters::MANA_REGENERATION_RATE, 10); // Base regeneration per minute
                     let reputation_multiplier = (reputation as f64 / 100.0).clamp(0.1, 2.0); // 0.1x to 2x based on reputation
                     let regeneration_amount =
                         (base_regeneration as f64 * reputation_multiplier) as u64;
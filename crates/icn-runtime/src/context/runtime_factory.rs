@@ -224,11 +224,8 @@ impl RuntimeContextFactory {
             }
         } else if let Some(hsm) = &config.hsm {
             // Use HSM if configured
-            Arc::new(
-                super::signers::Ed25519Signer::from_hsm(hsm.as_ref()).map_err(|e| {
-                    CommonError::CryptoError(format!("Failed to load HSM key: {}", e))
-                })?,
-            )
+            hsm.signer()
+                .map_err(|e| CommonError::CryptoError(format!("Failed to load HSM key: {}", e)))?
         } else {
             // Generate a new key for this production instance and warn the user
             log::warn!("🔑 PRODUCTION WARNING: No key file or HSM configured. Generating ephemeral key. This key will not persist across restarts!");
@@ -317,11 +314,8 @@ impl RuntimeContextFactory {
             }
         } else if let Some(hsm) = &config.hsm {
             // Use HSM if configured
-            Arc::new(
-                super::signers::Ed25519Signer::from_hsm(hsm.as_ref()).map_err(|e| {
-                    CommonError::CryptoError(format!("Failed to load HSM key: {}", e))
-                })?,
-            )
+            hsm.signer()
+                .map_err(|e| CommonError::CryptoError(format!("Failed to load HSM key: {}", e)))?
         } else {
             // For development, generate a new key (this is fine for dev environments)
             log::info!("🔧 DEVELOPMENT: Generating ephemeral Ed25519 key for this session");
@@ -4,7 +4,7 @@ use super::errors::HostAbiError;
 use icn_common::{CommonError, Did};
 use icn_identity::{
     generate_ed25519_keypair, sign_message, verify_signature as identity_verify_signature,
-    SigningKey, VerifyingKey,
+    KeyAgreementKey, Keystore, SigningKey, VerifyingKey, DEFAULT_KEY_LABEL,
 };
 use std::path::Path;
 
@@ -32,6 +32,26 @@ pub trait Signer: Send + Sync + std::fmt::Debug {
     fn did(&self) -> Did;
     fn verifying_key_ref(&self) -> &VerifyingKey;
     fn as_any(&self) -> &dyn std::any::Any;
+
+    /// X25519 key agreement using the Montgomery form of the signing key.
+    ///
+    /// Opens DIDComm envelopes addressed to this signer's DID. Signers whose
+    /// key never leaves a device do not support it.
+    fn key_agreement(&self, _public_key: &[u8; 32]) -> Result<[u8; 32], HostAbiError> {
+        Err(HostAbiError::NotImplemented(
+            "X25519 key agreement is not available for this signer".into(),
+        ))
+    }
+}
+
+impl KeyAgreementKey for dyn Signer {
+    fn x25519_public_key(&self) -> [u8; 32] {
+        self.verifying_key_ref().to_montgomery().to_bytes()
+    }
+
+    fn x25519_agree(&self, public_key: &[u8; 32]) -> Result<[u8; 32], CommonError> {
+        Ok(self.key_agreement(public_key)?)
+    }
 }
 
 /// Helper function to create DID from verifying key
@@ -43,6 +63,35 @@ fn create_did_from_verifying_key(vk: &VerifyingKey) -> Did {
         .unwrap_or_else(|_| Did::from_str("did:example:invalid").unwrap())
 }
 
+/// Verify an Ed25519 signature against raw public key bytes.
+pub(super) fn verify_ed25519(
+    payload: &[u8],
+    signature_bytes: &[u8],
+    public_key_bytes: &[u8],
+) -> Result<bool, HostAbiError> {
+    // Convert bytes to VerifyingKey
+    let verifying_key = VerifyingKey::from_bytes(
+        public_key_bytes
+            .try_into()
+            .map_err(|_| HostAbiError::SignatureError("Invalid public key length".to_string()))?,
+    )
+    .map_err(|e| HostAbiError::SignatureError(format!("Invalid public key: {e}")))?;
+
+    // Convert signature bytes to EdSignature
+    let signature = icn_identity::EdSignature::from_bytes(
+        signature_bytes
+            .try_into()
+            .map_err(|_| HostAbiError::SignatureError("Invalid signature length".to_string()))?,
+    );
+
+    // Verify the signature
+    Ok(identity_verify_signature(
+        &verifying_key,
+        payload,
+        &signature,
+    ))
+}
+
 /// Stub signer for testing
 pub struct StubSigner {
     sk: SigningKey,
@@ -84,22 +133,7 @@ impl Signer for StubSigner {
         signature_bytes: &[u8],
         public_key_bytes: &[u8],
     ) -> Result<bool, HostAbiError> {
-        // Convert bytes to VerifyingKey
-        let verifying_key =
-            VerifyingKey::from_bytes(public_key_bytes.try_into().map_err(|_| {
-                HostAbiError::SignatureError("Invalid public key length".to_string())
-            })?)
-            .map_err(|e| HostAbiError::SignatureError(format!("Invalid public key: {e}")))?;
-
-        // Convert signature bytes to EdSignature
-        let signature =
-            icn_identity::EdSignature::from_bytes(signature_bytes.try_into().map_err(|_| {
-                HostAbiError::SignatureError("Invalid signature length".to_string())
-            })?);
-
-        // Verify the signature
-        let is_valid = identity_verify_signature(&verifying_key, payload, &signature);
-        Ok(is_valid)
+        verify_ed25519(payload, signature_bytes, public_key_bytes)
     }
 
     fn public_key_bytes(&self) -> Vec<u8> {
@@ -117,51 +151,54 @@ impl Signer for StubSigner {
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    fn key_agreement(&self, public_key: &[u8; 32]) -> Result<[u8; 32], HostAbiError> {
+        Ok(self.sk.x25519_agree(public_key)?)
+    }
 }
 
 /// HSM key store trait for hardware security module integration
 pub trait HsmKeyStore: Send + Sync {
-    /// Fetch the Ed25519 keypair used for signing.
-    fn fetch_ed25519_keypair(&self) -> Result<(SigningKey, VerifyingKey), CommonError>;
-}
-
-/// Simple file-based HSM implementation used for testing.
-/// The `path` is expected to contain a base58 encoded private key.
-pub struct ExampleHsm {
-    path: std::path::PathBuf,
-    _key_id: Option<String>,
+    /// Signer for the node key. The private key must stay inside the HSM,
+    /// as with [`Pkcs11Signer`](super::pkcs11_signer::Pkcs11Signer).
+    fn signer(&self) -> Result<std::sync::Arc<dyn Signer>, CommonError>;
 }
 
-impl ExampleHsm {
-    pub fn new<P: AsRef<std::path::Path>>(path: P) -> Self {
-        Self {
-            path: path.as_ref().to_path_buf(),
-            _key_id: None,
-        }
-    }
-
-    pub fn with_key<P: AsRef<std::path::Path>>(path: P, key_id: String) -> Self {
-        Self {
-            path: path.as_ref().to_path_buf(),
-            _key_id: Some(key_id),
-        }
+/// Decrypt a legacy single-key file (PBKDF2-SHA256 and AES-256-GCM).
+///
+/// Used to migrate such files into a [`Keystore`].
+pub fn decrypt_legacy_key_file<P: AsRef<Path>>(
+    path: P,
+    passphrase: &[u8],
+) -> Result<SigningKey, CommonError> {
+    use aes_gcm::aead::generic_array::GenericArray;
+
+    let data = std::fs::read(path).map_err(|e| CommonError::IoError(e.to_string()))?;
+    if data.len() <= SALT_LEN + NONCE_LEN {
+        return Err(CommonError::IoError("encrypted key file truncated".into()));
     }
-}
-
-impl HsmKeyStore for ExampleHsm {
-    fn fetch_ed25519_keypair(&self) -> Result<(SigningKey, VerifyingKey), CommonError> {
-        let sk_bs58 =
-            std::fs::read_to_string(&self.path).map_err(|e| CommonError::IoError(e.to_string()))?;
-        let sk_bytes = bs58::decode(sk_bs58.trim())
-            .into_vec()
-            .map_err(|_| CommonError::IdentityError("Invalid base58 key".into()))?;
-        let sk_array: [u8; 32] = sk_bytes
-            .try_into()
-            .map_err(|_| CommonError::IdentityError("Invalid key length".into()))?;
-        let sk = SigningKey::from_bytes(&sk_array);
-        let pk = sk.verifying_key();
-        Ok((sk, pk))
+    let salt = &data[..SALT_LEN];
+    let nonce = &data[SALT_LEN..SALT_LEN + NONCE_LEN];
+    let ciphertext = &data[SALT_LEN + NONCE_LEN..];
+
+    let mut key = [0u8; 32];
+    pbkdf2_hmac::<Sha256>(passphrase, salt, PBKDF2_ITERS, &mut key);
+    let cipher = Aes256Gcm::new(GenericArray::from_slice(&key));
+    let plain = cipher
+        .decrypt(GenericArray::from_slice(nonce), ciphertext)
+        .map_err(|_| CommonError::CryptoError("key decryption failed".into()))?;
+    key.zeroize();
+
+    if plain.len() != 32 {
+        return Err(CommonError::IdentityError(
+            "invalid decrypted key length".into(),
+        ));
     }
+    let mut sk_bytes = [0u8; 32];
+    sk_bytes.copy_from_slice(&plain);
+    let sk = SigningKey::from_bytes(&sk_bytes);
+    sk_bytes.zeroize();
+    Ok(sk)
 }
 
 /// Production Ed25519 signer
@@ -192,45 +229,27 @@ impl Ed25519Signer {
     }
 
     /// Create a signer from an encrypted file.
+    ///
+    /// Keystores use the key labelled [`DEFAULT_KEY_LABEL`]; other files are
+    /// read in the legacy single-key format.
     pub fn from_encrypted_file<P: AsRef<Path>>(
         path: P,
         passphrase: &[u8],
     ) -> Result<Self, CommonError> {
-        use aes_gcm::aead::generic_array::GenericArray;
-
-        let data = std::fs::read(path).map_err(|e| CommonError::IoError(e.to_string()))?;
-        if data.len() <= SALT_LEN + NONCE_LEN {
-            return Err(CommonError::IoError("encrypted key file truncated".into()));
-        }
-        let salt = &data[..SALT_LEN];
-        let nonce = &data[SALT_LEN..SALT_LEN + NONCE_LEN];
-        let ciphertext = &data[SALT_LEN + NONCE_LEN..];
-
-        let mut key = [0u8; 32];
-        pbkdf2_hmac::<Sha256>(passphrase, salt, PBKDF2_ITERS, &mut key);
-        let cipher = Aes256Gcm::new(GenericArray::from_slice(&key));
-        let plain = cipher
-            .decrypt(GenericArray::from_slice(nonce), ciphertext)
-            .map_err(|_| CommonError::CryptoError("key decryption failed".into()))?;
-        key.zeroize();
-
-        if plain.len() != 32 {
-            return Err(CommonError::IdentityError(
-                "invalid decrypted key length".into(),
-            ));
+        if Keystore::is_keystore(&path)? {
+            return Self::from_keystore(path, passphrase, DEFAULT_KEY_LABEL);
         }
-        let mut sk_bytes = [0u8; 32];
-        sk_bytes.copy_from_slice(&plain);
-        let sk = SigningKey::from_bytes(&sk_bytes);
-        sk_bytes.zeroize();
-        let pk = sk.verifying_key();
-        Ok(Self::new_with_keys(sk, pk))
+        Ok(Self::new(decrypt_legacy_key_file(path, passphrase)?))
     }
 
-    /// Create a signer from an HSM.
-    pub fn from_hsm(hsm: &dyn HsmKeyStore) -> Result<Self, CommonError> {
-        let (sk, pk) = hsm.fetch_ed25519_keypair()?;
-        Ok(Self::new_with_keys(sk, pk))
+    /// Create a signer from the key labelled `label` in a keystore.
+    pub fn from_keystore<P: AsRef<Path>>(
+        path: P,
+        passphrase: &[u8],
+        label: &str,
+    ) -> Result<Self, CommonError> {
+        let keystore = Keystore::open(path.as_ref(), passphrase)?;
+        Ok(Self::new(keystore.signing_key(label)?))
     }
 
     pub fn verifying_key_ref(&self) -> &VerifyingKey {
@@ -250,22 +269,7 @@ impl Signer for Ed25519Signer {
         signature_bytes: &[u8],
         public_key_bytes: &[u8],
     ) -> Result<bool, HostAbiError> {
-        // Convert bytes to VerifyingKey
-        let verifying_key =
-            VerifyingKey::from_bytes(public_key_bytes.try_into().map_err(|_| {
-                HostAbiError::SignatureError("Invalid public key length".to_string())
-            })?)
-            .map_err(|e| HostAbiError::SignatureError(format!("Invalid public key: {e}")))?;
-
-        // Convert signature bytes to EdSignature
-        let signature =
-            icn_identity::EdSignature::from_bytes(signature_bytes.try_into().map_err(|_| {
-                HostAbiError::SignatureError("Invalid signature length".to_string())
-            })?);
-
-        // Verify the signature
-        let is_valid = identity_verify_signature(&verifying_key, payload, &signature);
-        Ok(is_valid)
+        verify_ed25519(payload, signature_bytes, public_key_bytes)
     }
 
     fn public_key_bytes(&self) -> Vec<u8> {
@@ -283,6 +287,10 @@ impl Signer for Ed25519Signer {
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    fn key_agreement(&self, public_key: &[u8; 32]) -> Result<[u8; 32], HostAbiError> {
+        Ok(self.sk.x25519_agree(public_key)?)
+    }
 }
 
 // Add std::str::FromStr import for Did::from_str
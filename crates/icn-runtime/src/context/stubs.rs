@@ -243,6 +243,7 @@ pub struct StubMeshNetworkService {
     announced_jobs: Arc<TokioMutex<Vec<ActualMeshJob>>>,
     assignment_notices: Arc<TokioMutex<Vec<JobAssignmentNotice>>>,
     job_announcement_tx: Arc<TokioMutex<Option<tokio::sync::mpsc::UnboundedSender<ActualMeshJob>>>>,
+    didcomm_envelopes: Arc<TokioMutex<Vec<(Did, String)>>>,
 }
 
 impl StubMeshNetworkService {
@@ -253,6 +254,7 @@ impl StubMeshNetworkService {
             announced_jobs: Arc::new(TokioMutex::new(Vec::new())),
             assignment_notices: Arc::new(TokioMutex::new(Vec::new())),
             job_announcement_tx: Arc::new(TokioMutex::new(None)),
+            didcomm_envelopes: Arc::new(TokioMutex::new(Vec::new())),
         }
     }
 
@@ -297,12 +299,18 @@ impl StubMeshNetworkService {
         self.assignment_notices.lock().await.clone()
     }
 
+    /// Get relayed DIDComm envelopes and their next hops (for testing verification)
+    pub async fn get_didcomm_envelopes(&self) -> Vec<(Did, String)> {
+        self.didcomm_envelopes.lock().await.clone()
+    }
+
     /// Clear all staged data (for test cleanup)
     pub async fn clear_all(&self) {
         self.staged_bids.lock().await.clear();
         self.staged_receipts.lock().await.clear();
         self.announced_jobs.lock().await.clear();
         self.assignment_notices.lock().await.clear();
+        self.didcomm_envelopes.lock().await.clear();
     }
 }
 
@@ -667,4 +675,20 @@ impl MeshNetworkService for StubMeshNetworkService {
         );
         Ok(is_partitioned)
     }
+
+    async fn send_didcomm_envelope(
+        &self,
+        next_hop: &Did,
+        envelope: String,
+    ) -> Result<(), HostAbiError> {
+        log::debug!(
+            "[StubMeshNetwork] Relaying DIDComm envelope to {}",
+            next_hop
+        );
+        self.didcomm_envelopes
+            .lock()
+            .await
+            .push((next_hop.clone(), envelope));
+        Ok(())
+    }
 }
@@ -790,6 +790,24 @@ pub async fn host_execute_governance_proposal(
     ctx.execute_governance_proposal(proposal_id).await
 }
 
+/// Propose restoring the value replaced by governance parameter change `version`.
+pub async fn host_propose_parameter_rollback(
+    ctx: &RuntimeContext,
+    version: u64,
+    duration_secs: u64,
+) -> Result<String, HostAbiError> {
+    ctx.propose_parameter_rollback(version, duration_secs).await
+}
+
+/// Signal that this node is ready for the upgrade scheduled by `proposal_id`.
+pub async fn host_signal_upgrade_readiness(
+    ctx: &RuntimeContext,
+    proposal_id: &str,
+    version: &str,
+) -> Result<icn_governance::ReadinessSignal, HostAbiError> {
+    ctx.signal_upgrade_readiness(proposal_id, version).await
+}
+
 /// Delegate voting power from one DID to another.
 pub async fn host_delegate_vote(
     ctx: &RuntimeContext,
@@ -807,6 +825,118 @@ pub async fn host_revoke_delegation(
     ctx.revoke_delegation(from_did).await
 }
 
+/// Delegate voting power for proposals in `scope`, optionally until `expires_at`.
+pub async fn host_delegate_vote_scoped(
+    ctx: &RuntimeContext,
+    from_did: &str,
+    to_did: &str,
+    scope: &str,
+    expires_at: Option<u64>,
+) -> Result<(), HostAbiError> {
+    ctx.delegate_vote_scoped(from_did, to_did, scope, expires_at)
+        .await
+}
+
+/// Revoke the vote delegation of `from_did` in `scope`, or all of them.
+pub async fn host_revoke_delegation_scoped(
+    ctx: &RuntimeContext,
+    from_did: &str,
+    scope: Option<&str>,
+) -> Result<(), HostAbiError> {
+    ctx.revoke_delegation_scoped(from_did, scope).await
+}
+
+/// Attach topic tags to a proposal for tag-scoped delegation.
+pub async fn host_tag_proposal(
+    ctx: &RuntimeContext,
+    proposal_id: &str,
+    tags: Vec<String>,
+) -> Result<(), HostAbiError> {
+    ctx.tag_proposal(proposal_id, tags).await
+}
+
+/// Submit a budget request decided by conviction voting.
+pub async fn host_submit_conviction_proposal(
+    ctx: &RuntimeContext,
+    beneficiary_did: &str,
+    amount: u64,
+    description: String,
+) -> Result<String, HostAbiError> {
+    ctx.submit_conviction_proposal(beneficiary_did, amount, description)
+        .await
+}
+
+/// Stake conviction support on a proposal. Staking zero withdraws support.
+pub async fn host_stake_conviction(
+    ctx: &RuntimeContext,
+    staker_did: &str,
+    proposal_id: &str,
+    amount: u64,
+) -> Result<(), HostAbiError> {
+    ctx.stake_conviction(staker_did, proposal_id, amount).await
+}
+
+/// Advance conviction and return the IDs of proposals that crossed their threshold.
+pub async fn host_process_conviction_voting(
+    ctx: &RuntimeContext,
+) -> Result<Vec<String>, HostAbiError> {
+    ctx.process_conviction_voting().await
+}
+
+/// Start deliberation on a proposal and return the CID of revision zero.
+pub async fn host_start_deliberation(
+    ctx: &RuntimeContext,
+    proposal_id: &str,
+) -> Result<String, HostAbiError> {
+    ctx.start_proposal_deliberation(proposal_id).await
+}
+
+/// Publish a revised proposal text and return the new revision CID.
+pub async fn host_revise_proposal(
+    ctx: &RuntimeContext,
+    proposal_id: &str,
+    description: String,
+    body: Option<Vec<u8>>,
+) -> Result<String, HostAbiError> {
+    ctx.revise_governance_proposal(proposal_id, description, body)
+        .await
+}
+
+/// Propose an amendment and return its ID.
+pub async fn host_propose_amendment(
+    ctx: &RuntimeContext,
+    proposal_id: &str,
+    description: String,
+    body: Option<Vec<u8>>,
+    rationale: String,
+) -> Result<String, HostAbiError> {
+    ctx.propose_amendment(proposal_id, description, body, rationale)
+        .await
+}
+
+/// Vote on an open amendment.
+pub async fn host_vote_on_amendment(
+    ctx: &RuntimeContext,
+    proposal_id: &str,
+    amendment_id: &str,
+    vote_option: &str,
+) -> Result<(), HostAbiError> {
+    ctx.vote_on_amendment(proposal_id, amendment_id, vote_option)
+        .await
+}
+
+/// Comment on a proposal revision and return the comment CID.
+pub async fn host_comment_on_proposal(
+    ctx: &RuntimeContext,
+    proposal_id: &str,
+    revision: Option<&str>,
+    parent: Option<&str>,
+    text: String,
+) -> Result<String, HostAbiError> {
+    ctx.comment_on_proposal(proposal_id, revision, parent, text)
+        .await
+}
+
 /// Get the complete lifecycle status of a job by reconstructing it from DAG traversal.
 pub async fn host_get_job_status(
     ctx: &RuntimeContext,
@@ -844,7 +974,9 @@ pub async fn host_verify_zk_proof(
     proof_json: &str,
 ) -> Result<bool, HostAbiError> {
     use icn_common::{ZkCredentialProof, ZkProofType};
-    use icn_identity::{BulletproofsVerifier, DummyVerifier, Groth16Verifier, ZkVerifier};
+    use icn_identity::{
+        BbsVerifier, BulletproofsVerifier, DummyVerifier, Groth16Verifier, ZkVerifier,
+    };
 
     let proof: ZkCredentialProof = serde_json::from_str(proof_json).map_err(|e| {
         HostAbiError::InvalidParameters(format!("Invalid ZkCredentialProof JSON: {e}"))
@@ -856,6 +988,7 @@ pub async fn host_verify_zk_proof(
     let verifier: Box<dyn ZkVerifier> = match proof.backend {
         ZkProofType::Bulletproofs => Box::new(BulletproofsVerifier),
         ZkProofType::Groth16 => Box::new(Groth16Verifier::default()),
+        ZkProofType::Bbs => Box::new(BbsVerifier::new(ctx.did_resolver.clone())),
         _ => Box::new(DummyVerifier),
     };
 
@@ -888,7 +1021,9 @@ pub async fn host_verify_zk_revocation_proof(
 ) -> Result<bool, HostAbiError> {
     use icn_common::{ZkProofType, ZkRevocationProof};
     use icn_identity::zk::ZkRevocationVerifier;
-    use icn_identity::{BulletproofsVerifier, DummyVerifier, Groth16Verifier};
+    use icn_identity::{
+        BulletproofsVerifier, DummyVerifier, Groth16Verifier, StatusListRevocationVerifier,
+    };
 
     let proof: ZkRevocationProof = serde_json::from_str(proof_json).map_err(|e| {
         HostAbiError::InvalidParameters(format!("Invalid ZkRevocationProof JSON: {e}"))
@@ -897,11 +1032,12 @@ pub async fn host_verify_zk_revocation_proof(
     let cost = calculate_zk_cost(1);
     ctx.spend_mana(&ctx.current_identity, cost).await?;
 
-    let verifier: Box<dyn ZkRevocationVerifier> = match proof.backend {
+    let inner: Box<dyn ZkRevocationVerifier> = match proof.backend {
         ZkProofType::Bulletproofs => Box::new(BulletproofsVerifier),
         ZkProofType::Groth16 => Box::new(Groth16Verifier::default()),
         _ => Box::new(DummyVerifier),
     };
+    let verifier = StatusListRevocationVerifier::new(ctx.status_lists.clone(), inner);
 
     match verifier.verify_revocation(&proof) {
         Ok(true) => {
@@ -946,6 +1082,7 @@ pub async fn host_generate_zk_proof(
     let backend = match req.backend.to_ascii_lowercase().as_str() {
         "groth16" => ZkProofType::Groth16,
         "bulletproofs" => ZkProofType::Bulletproofs,
+        "bbs" => ZkProofType::Bbs,
         other => ZkProofType::Other(other.to_string()),
     };
 
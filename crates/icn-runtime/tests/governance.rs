@@ -336,6 +336,12 @@ async fn parameter_change_execution_updates_runtime() {
         gov.add_member(Did::from_str("did:icn:test:bob").unwrap());
         gov.set_quorum(1);
         gov.set_threshold(0.5);
+        gov.register_parameter(icn_governance::ParameterSpec::new(
+            "test_limit",
+            "test limit",
+            icn_governance::ParameterValue::U64(0),
+        ))
+        .unwrap();
     }
 
     let payload = serde_json::json!({
@@ -384,6 +390,12 @@ async fn parameter_change_anchors_dag_entry() {
         gov.add_member(Did::from_str("did:icn:test:bob").unwrap());
         gov.set_quorum(1);
         gov.set_threshold(0.5);
+        gov.register_parameter(icn_governance::ParameterSpec::new(
+            "dag_limit",
+            "test limit",
+            icn_governance::ParameterValue::U64(0),
+        ))
+        .unwrap();
     }
 
     let payload = serde_json::json!({
@@ -430,6 +442,78 @@ async fn parameter_change_anchors_dag_entry() {
     assert!(found, "parameter update not anchored");
 }
 
+#[tokio::test]
+async fn selection_weight_change_can_be_rolled_back() {
+    let ctx =
+        RuntimeContext::new_for_testing(Did::from_str("did:icn:test:weights").unwrap(), Some(1000))
+            .unwrap();
+    {
+        let mut gov = ctx.governance_module.lock().await;
+        gov.add_member(Did::from_str("did:icn:test:weights").unwrap());
+        gov.add_member(Did::from_str("did:icn:test:bob").unwrap());
+        gov.set_quorum(1);
+        gov.set_threshold(0.5);
+    }
+
+    async fn pass(ctx: &RuntimeContext, pid_str: &str) {
+        let pid = ProposalId(pid_str.to_string());
+        {
+            let mut gov = ctx.governance_module.lock().await;
+            gov.open_voting(&pid).unwrap();
+            gov.cast_vote(
+                Did::from_str("did:icn:test:bob").unwrap(),
+                &pid,
+                VoteOption::Yes,
+                ctx.time_provider.as_ref(),
+            )
+            .unwrap();
+        }
+        host_close_governance_proposal_voting(ctx, pid_str)
+            .await
+            .unwrap();
+        host_execute_governance_proposal(ctx, pid_str)
+            .await
+            .unwrap();
+    }
+
+    let invalid = serde_json::json!({
+        "proposal_type_str": "SystemParameterChange",
+        "type_specific_payload": serde_json::to_vec(&("executor_weight_reputation", "-3")).unwrap(),
+        "description": "bad weight",
+        "duration_secs": 60
+    });
+    assert!(host_create_governance_proposal(&ctx, &invalid.to_string())
+        .await
+        .is_err());
+
+    let payload = serde_json::json!({
+        "proposal_type_str": "SystemParameterChange",
+        "type_specific_payload": serde_json::to_vec(&("executor_weight_reputation", "5")).unwrap(),
+        "description": "lower reputation weight",
+        "duration_secs": 60
+    });
+    let pid_str = host_create_governance_proposal(&ctx, &payload.to_string())
+        .await
+        .unwrap();
+    pass(&ctx, &pid_str).await;
+    assert_eq!(ctx.selection_policy().weight_reputation, 5.0);
+
+    let version = {
+        let gov = ctx.governance_module.lock().await;
+        gov.parameters().history()[0].version
+    };
+    let rollback = icn_runtime::host_propose_parameter_rollback(&ctx, version, 60)
+        .await
+        .unwrap();
+    pass(&ctx, &rollback).await;
+    assert_eq!(ctx.selection_policy().weight_reputation, 50.0);
+
+    let gov = ctx.governance_module.lock().await;
+    let history = gov.parameters().history();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].previous.to_string(), "5");
+}
+
 #[tokio::test]
 async fn budget_allocation_credits_mana() {
     let ctx = RuntimeContext::new_with_stubs_and_mana("did:icn:test:budget", 0).unwrap();
@@ -470,3 +554,59 @@ async fn budget_allocation_credits_mana() {
         .unwrap();
     assert_eq!(ctx.mana_ledger.get_balance(&ctx.current_identity), 50);
 }
+
+#[tokio::test]
+async fn scheduler_closes_and_executes_overdue_proposals() {
+    let ctx = RuntimeContext::new_with_stubs_and_mana("did:icn:test:sched", 0).unwrap();
+    let pid = {
+        let mut gov = ctx.governance_module.lock().await;
+        gov.add_member(Did::from_str("did:icn:test:sched").unwrap());
+        gov.add_member(Did::from_str("did:icn:test:bob").unwrap());
+        let mut config = gov.config().clone();
+        config.auto_execute = true;
+        gov.set_config(config);
+        gov.set_quorum(1);
+        gov.set_threshold(0.5);
+
+        // Submitted long ago, so the voting deadline has already passed.
+        let long_ago = icn_common::FixedTimeProvider::new(0);
+        let pid = gov
+            .submit_proposal(
+                icn_governance::ProposalSubmission {
+                    proposer: Did::from_str("did:icn:test:sched").unwrap(),
+                    proposal_type: icn_governance::ProposalType::BudgetAllocation(
+                        Did::from_str("did:icn:test:sched").unwrap(),
+                        25,
+                        "ops".into(),
+                    ),
+                    description: "fund ops".into(),
+                    duration_secs: 60,
+                    quorum: None,
+                    threshold: None,
+                    content_cid: None,
+                    timelock_delay: None,
+                },
+                &long_ago,
+            )
+            .unwrap();
+        gov.open_voting(&pid).unwrap();
+        gov.cast_vote(
+            Did::from_str("did:icn:test:bob").unwrap(),
+            &pid,
+            VoteOption::Yes,
+            &long_ago,
+        )
+        .unwrap();
+        pid
+    };
+
+    let transitions = ctx.advance_governance_lifecycle().await.unwrap();
+    let statuses: Vec<_> = transitions.iter().map(|t| t.to.clone()).collect();
+    assert_eq!(
+        statuses,
+        vec![ProposalStatus::Accepted, ProposalStatus::Executed]
+    );
+    assert!(transitions.iter().all(|t| t.proposal_id == pid));
+    assert_eq!(ctx.mana_ledger.get_balance(&ctx.current_identity), 25);
+    assert!(ctx.advance_governance_lifecycle().await.unwrap().is_empty());
+}
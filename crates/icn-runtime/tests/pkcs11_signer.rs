@@ -0,0 +1,116 @@
+//! Exercises `Pkcs11Signer` against SoftHSM2.
+//!
+//! Ignored by default because it needs SoftHSM2 installed; the `pkcs11` CI
+//! job installs it and runs `cargo test -p icn-runtime --test pkcs11_signer
+//! -- --ignored`. The module is located through `SOFTHSM2_LIB` or a standard
+//! install location.
+
+use cryptoki::context::{CInitializeArgs, Pkcs11};
+use cryptoki::object::{Attribute, AttributeType, ObjectClass};
+use cryptoki::session::UserType;
+use cryptoki::types::AuthPin;
+use icn_identity::{verify_signature, EdSignature};
+use icn_runtime::context::{Pkcs11Token, Signer};
+use std::path::PathBuf;
+
+const USER_PIN: &str = "123456";
+const SO_PIN: &str = "12345678";
+
+fn softhsm_library() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("SOFTHSM2_LIB") {
+        return Some(path.into());
+    }
+    [
+        "/usr/lib/softhsm/libsofthsm2.so",
+        "/usr/lib/x86_64-linux-gnu/softhsm/libsofthsm2.so",
+        "/usr/local/lib/softhsm/libsofthsm2.so",
+        "/opt/homebrew/lib/softhsm/libsofthsm2.so",
+    ]
+    .iter()
+    .map(PathBuf::from)
+    .find(|p| p.exists())
+}
+
+/// Point SoftHSM2 at a fresh token directory and initialize one token.
+fn init_token(library: &PathBuf, dir: &std::path::Path) {
+    let tokens = dir.join("tokens");
+    std::fs::create_dir_all(&tokens).unwrap();
+    let conf = dir.join("softhsm2.conf");
+    std::fs::write(
+        &conf,
+        format!(
+            "directories.tokendir = {}\nobjectstore.backend = file\n",
+            tokens.display()
+        ),
+    )
+    .unwrap();
+    std::env::set_var("SOFTHSM2_CONF", &conf);
+
+    let ctx = Pkcs11::new(library).unwrap();
+    ctx.initialize(CInitializeArgs::OsThreads).unwrap();
+    let slot = ctx.get_slots_with_token().unwrap()[0];
+    ctx.init_token(slot, &AuthPin::new(SO_PIN.into()), "icn-test")
+        .unwrap();
+    let slot = ctx.get_slots_with_initialized_token().unwrap()[0];
+    let session = ctx.open_rw_session(slot).unwrap();
+    session
+        .login(UserType::So, Some(&AuthPin::new(SO_PIN.into())))
+        .unwrap();
+    session.init_pin(&AuthPin::new(USER_PIN.into())).unwrap();
+}
+
+#[test]
+#[ignore = "requires SoftHSM2"]
+fn pkcs11_keys_sign_inside_the_token() {
+    let library = softhsm_library().expect("SoftHSM2 not found; set SOFTHSM2_LIB");
+    let dir = tempfile::tempdir().unwrap();
+    init_token(&library, dir.path());
+
+    let token = Pkcs11Token::open(&library, None, USER_PIN).unwrap();
+    let generated = token.generate_ed25519("node").unwrap();
+    assert!(token.generate_ed25519("node").is_err());
+    token.generate_ed25519("backup").unwrap();
+
+    let mut labels: Vec<_> = token
+        .list_ed25519()
+        .unwrap()
+        .into_iter()
+        .map(|k| k.label)
+        .collect();
+    labels.sort();
+    assert_eq!(labels, ["backup", "node"]);
+
+    let signer = token.signer("node").unwrap();
+    assert_eq!(signer.did(), generated.did);
+    assert_eq!(*signer.verifying_key_ref(), generated.public_key);
+    let signature = signer.sign(b"payload").unwrap();
+    let ed = EdSignature::from_bytes(&signature.clone().try_into().unwrap());
+    assert!(verify_signature(&generated.public_key, b"payload", &ed));
+    assert!(signer
+        .verify(b"payload", &signature, &signer.public_key_bytes())
+        .unwrap());
+    assert!(token.signer("missing").is_err());
+
+    // The private key value cannot be read back out of the token.
+    let ctx = Pkcs11::new(&library).unwrap();
+    let _ = ctx.initialize(CInitializeArgs::OsThreads);
+    let slot = ctx.get_slots_with_initialized_token().unwrap()[0];
+    let session = ctx.open_ro_session(slot).unwrap();
+    let _ = session.login(UserType::User, Some(&AuthPin::new(USER_PIN.into())));
+    let private = session
+        .find_objects(&[
+            Attribute::Class(ObjectClass::PRIVATE_KEY),
+            Attribute::Label(b"node".to_vec()),
+        ])
+        .unwrap()[0];
+    let value = session
+        .get_attributes(private, &[AttributeType::Value])
+        .unwrap_or_default();
+    assert!(!value.iter().any(|a| matches!(a, Attribute::Value(_))));
+    drop(session);
+    drop(ctx);
+
+    assert!(Pkcs11Token::open(&library, None, "wrong-pin")
+        .and_then(|t| t.signer("node"))
+        .is_err());
+}
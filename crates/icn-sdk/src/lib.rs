@@ -675,6 +675,276 @@ impl IcnClient {
         self.post("/identity/credentials/revoke", body).await
     }
 
+    /// Suspend a credential by CID until it is reinstated.
+    pub async fn suspend_credential<B: Serialize>(
+        &self,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post("/identity/credentials/suspend", body).await
+    }
+
+    /// Lift a credential suspension by CID.
+    pub async fn reinstate_credential<B: Serialize>(
+        &self,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post("/identity/credentials/reinstate", body).await
+    }
+
+    /// Fetch a signed status list (`purpose` is `revocation` or `suspension`).
+    pub async fn status_list(
+        &self,
+        id: &str,
+        purpose: &str,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.get(&format!("/identity/status-lists/{id}/{purpose}"))
+            .await
+    }
+
+    /// Export a node-issued credential as VC-JWT, SD-JWT or Data Integrity.
+    pub async fn export_credential<B: Serialize>(
+        &self,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post("/identity/credentials/export", body).await
+    }
+
+    /// Verify an external credential and map it to an ICN credential.
+    pub async fn import_credential<B: Serialize>(
+        &self,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post("/identity/credentials/import", body).await
+    }
+
+    /// Publish a JSON Schema credential schema under the node DID.
+    pub async fn publish_schema<B: Serialize>(
+        &self,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post("/identity/schemas", body).await
+    }
+
+    /// List published credential schemas.
+    pub async fn published_schemas(&self) -> Result<serde_json::Value, reqwest::Error> {
+        self.get("/identity/schemas").await
+    }
+
+    /// Fetch a published credential schema.
+    pub async fn published_schema(&self, cid: &str) -> Result<serde_json::Value, reqwest::Error> {
+        self.get(&format!("/identity/schemas/{cid}")).await
+    }
+
+    /// Set who may issue a cooperative's credential type.
+    pub async fn set_issuer_policy<B: Serialize>(
+        &self,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post("/identity/issuance/policies", body).await
+    }
+
+    /// List issuer policies.
+    pub async fn issuer_policies(&self) -> Result<serde_json::Value, reqwest::Error> {
+        self.get("/identity/issuance/policies").await
+    }
+
+    /// Offer a credential from the node to a holder.
+    pub async fn offer_credential<B: Serialize>(
+        &self,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post("/identity/issuance/offers", body).await
+    }
+
+    /// Fetch an open credential offer.
+    pub async fn credential_offer(&self, id: &str) -> Result<serde_json::Value, reqwest::Error> {
+        self.get(&format!("/identity/issuance/offers/{id}")).await
+    }
+
+    /// Answer an offer with a holder-signed request and receive the credential.
+    pub async fn request_credential<B: Serialize>(
+        &self,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post("/identity/issuance/requests", body).await
+    }
+
+    /// Trust a DID as a verified person anchoring Sybil detection.
+    pub async fn add_personhood_seed(
+        &self,
+        did: &str,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post(
+            "/identity/personhood/seeds",
+            &serde_json::json!({ "did": did }),
+        )
+        .await
+    }
+
+    /// Submit a signed vouch for a new member.
+    pub async fn vouch_for_person<B: Serialize>(
+        &self,
+        vouch: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post("/identity/personhood/vouches", vouch).await
+    }
+
+    /// Re-run Sybil detection and re-verify personhood.
+    pub async fn evaluate_personhood(&self) -> Result<serde_json::Value, reqwest::Error> {
+        self.post("/identity/personhood/evaluate", &serde_json::json!({}))
+            .await
+    }
+
+    /// Revoke a Sybil's personhood, returning the forfeited vouches.
+    pub async fn revoke_personhood(
+        &self,
+        did: &str,
+        reason: &str,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post(
+            "/identity/personhood/revoke",
+            &serde_json::json!({ "did": did, "reason": reason }),
+        )
+        .await
+    }
+
+    /// Issue a personhood credential to a verified person.
+    pub async fn issue_personhood_credential(
+        &self,
+        did: &str,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post(
+            "/identity/personhood/credential",
+            &serde_json::json!({ "did": did }),
+        )
+        .await
+    }
+
+    /// Personhood status, proof and vouches of a DID.
+    pub async fn personhood(&self, did: &str) -> Result<serde_json::Value, reqwest::Error> {
+        self.get(&format!("/identity/personhood/{did}")).await
+    }
+
+    /// Register the guardians and sealed shares protecting a did:icn next key.
+    pub async fn configure_recovery<B: Serialize>(
+        &self,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post("/identity/recovery/config", body).await
+    }
+
+    /// Fetch the recovery configuration of a DID.
+    pub async fn recovery_config(&self, did: &str) -> Result<serde_json::Value, reqwest::Error> {
+        self.get(&format!("/identity/recovery/config/{did}")).await
+    }
+
+    /// Open a guardian recovery for a DID.
+    pub async fn open_recovery<B: Serialize>(
+        &self,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post("/identity/recovery/cases", body).await
+    }
+
+    /// Fetch the approvals and status of a recovery.
+    pub async fn recovery_case(&self, id: &str) -> Result<serde_json::Value, reqwest::Error> {
+        self.get(&format!("/identity/recovery/cases/{id}")).await
+    }
+
+    /// Submit a guardian's approval of a recovery.
+    pub async fn approve_recovery<B: Serialize>(
+        &self,
+        id: &str,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post(&format!("/identity/recovery/cases/{id}/approve"), body)
+            .await
+    }
+
+    /// Cancel a recovery with a signature by the current key.
+    pub async fn cancel_recovery<B: Serialize>(
+        &self,
+        id: &str,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post(&format!("/identity/recovery/cases/{id}/cancel"), body)
+            .await
+    }
+
+    /// Fetch the released shares of an executable recovery.
+    pub async fn recovery_shares(&self, id: &str) -> Result<serde_json::Value, reqwest::Error> {
+        self.get(&format!("/identity/recovery/cases/{id}/shares"))
+            .await
+    }
+
+    /// Complete a recovery with a rotation signed by the recovered key.
+    pub async fn complete_recovery<B: Serialize>(
+        &self,
+        id: &str,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post(&format!("/identity/recovery/cases/{id}/complete"), body)
+            .await
+    }
+
+    /// Send a DIDComm message from the node's DID.
+    pub async fn didcomm_send<B: Serialize>(
+        &self,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post("/didcomm/send", body).await
+    }
+
+    /// List the DIDComm messages received by the node.
+    pub async fn didcomm_messages(&self) -> Result<serde_json::Value, reqwest::Error> {
+        self.get("/didcomm/messages").await
+    }
+
+    /// List the DIDs the node mediates for.
+    pub async fn didcomm_mediated(&self) -> Result<serde_json::Value, reqwest::Error> {
+        self.get("/didcomm/mediated").await
+    }
+
+    /// Ask a mediator to route DIDComm messages for the node.
+    pub async fn didcomm_request_mediation<B: Serialize>(
+        &self,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post("/didcomm/mediation", body).await
+    }
+
+    /// Pick up messages queued for the node at its mediator.
+    pub async fn didcomm_pickup<B: Serialize>(
+        &self,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post("/didcomm/pickup", body).await
+    }
+
+    /// Offer a credential to a holder over DIDComm.
+    pub async fn didcomm_credential_offer<B: Serialize>(
+        &self,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post("/didcomm/credential-offer", body).await
+    }
+
+    /// Request the credential of an offer in the node's inbox.
+    pub async fn didcomm_credential_request<B: Serialize>(
+        &self,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post("/didcomm/credential-request", body).await
+    }
+
+    /// Invite members to a governance proposal over DIDComm.
+    pub async fn didcomm_proposal_invitation<B: Serialize>(
+        &self,
+        body: &B,
+    ) -> Result<serde_json::Value, reqwest::Error> {
+        self.post("/didcomm/proposal-invitation", body).await
+    }
+
     /// Verify a revocation proof.
     pub async fn verify_revocation<B: Serialize>(
         &self,
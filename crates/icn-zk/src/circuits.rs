@@ -253,3 +253,80 @@ impl CircuitCost for AgeRepMembershipCircuit {
         20
     }
 }
+
+/// Prove membership in a [`MembershipTree`](crate::MembershipTree) without
+/// revealing the leaf, exposing a scoped nullifier and binding a signal.
+///
+/// Public inputs, in order: `root`, `scope`, `nullifier`, `signal`.
+#[derive(Clone)]
+pub struct AnonymousMembershipCircuit {
+    /// Identity secret behind the member's commitment (private).
+    pub secret: Fr,
+    /// Path from the member's commitment to the root (private).
+    pub path: crate::MerklePath,
+    /// Root of the membership tree (public).
+    pub root: Fr,
+    /// Scope the nullifier is derived for, e.g. a proposal (public).
+    pub scope: Fr,
+    /// `H(secret, scope)` (public).
+    pub nullifier: Fr,
+    /// Value the proof is bound to, such as a ballot commitment (public).
+    pub signal: Fr,
+}
+
+impl AnonymousMembershipCircuit {
+    /// Placeholder circuit of the given tree depth for parameter generation.
+    pub fn blank(depth: usize) -> Self {
+        Self {
+            secret: Fr::from(0u64),
+            path: crate::MerklePath {
+                siblings: vec![Fr::from(0u64); depth],
+                index: 0,
+            },
+            root: Fr::from(0u64),
+            scope: Fr::from(0u64),
+            nullifier: Fr::from(0u64),
+            signal: Fr::from(0u64),
+        }
+    }
+
+    /// Public inputs in the order expected by the verifier.
+    pub fn public_inputs(&self) -> Vec<Fr> {
+        vec![self.root, self.scope, self.nullifier, self.signal]
+    }
+}
+
+impl ConstraintSynthesizer<Fr> for AnonymousMembershipCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        use crate::membership::mimc_hash_var;
+
+        let root = FpVar::<Fr>::new_input(cs.clone(), || Ok(self.root))?;
+        let scope = FpVar::<Fr>::new_input(cs.clone(), || Ok(self.scope))?;
+        let nullifier = FpVar::<Fr>::new_input(cs.clone(), || Ok(self.nullifier))?;
+        let signal = FpVar::<Fr>::new_input(cs.clone(), || Ok(self.signal))?;
+        let secret = FpVar::<Fr>::new_witness(cs.clone(), || Ok(self.secret))?;
+
+        let zero = FpVar::<Fr>::Constant(Fr::from(0u64));
+        let mut node = mimc_hash_var(&secret, &zero)?;
+        for (level, sibling) in self.path.siblings.iter().enumerate() {
+            let sibling = FpVar::<Fr>::new_witness(cs.clone(), || Ok(*sibling))?;
+            let is_right =
+                Boolean::new_witness(cs.clone(), || Ok((self.path.index >> level) & 1 == 1))?;
+            let left = FpVar::conditionally_select(&is_right, &sibling, &node)?;
+            let right = FpVar::conditionally_select(&is_right, &node, &sibling)?;
+            node = mimc_hash_var(&left, &right)?;
+        }
+        node.enforce_equal(&root)?;
+        mimc_hash_var(&secret, &scope)?.enforce_equal(&nullifier)?;
+
+        // Constrain the signal so a proof cannot be replayed with another one.
+        let _ = signal.square()?;
+        Ok(())
+    }
+}
+
+impl CircuitCost for AnonymousMembershipCircuit {
+    fn complexity() -> u64 {
+        50
+    }
+}
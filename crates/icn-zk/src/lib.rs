@@ -1,7 +1,7 @@
 //! Reusable zero-knowledge circuits for ICN credential proofs.
 
 use ark_bn254::{Bn254, Fr};
-use ark_groth16::{Groth16, PreparedVerifyingKey, Proof, ProvingKey};
+use ark_groth16::{Groth16, PreparedVerifyingKey, Proof, ProvingKey, VerifyingKey};
 use ark_relations::r1cs::{ConstraintSynthesizer, SynthesisError};
 use ark_snark::SNARK;
 use ark_std::rand::{CryptoRng, RngCore};
@@ -13,12 +13,15 @@ mod circuits;
 pub mod devtools;
 #[cfg(feature = "devtools")]
 pub use devtools::{count_constraints, log_constraints, print_cs_stats};
+pub mod membership;
 mod params;
 
 pub use circuits::{
-    AgeOver18Circuit, AgeRepMembershipCircuit, BalanceRangeCircuit, CircuitCost, MembershipCircuit,
-    MembershipProofCircuit, ReputationCircuit, TimestampValidityCircuit,
+    AgeOver18Circuit, AgeRepMembershipCircuit, AnonymousMembershipCircuit, BalanceRangeCircuit,
+    CircuitCost, MembershipCircuit, MembershipProofCircuit, ReputationCircuit,
+    TimestampValidityCircuit,
 };
+pub use membership::{MembershipTree, MerklePath};
 pub use params::{CircuitParameters, CircuitParametersStorage, MemoryParametersStorage};
 
 /// Reputation thresholds required to prove or verify each circuit type.
@@ -88,6 +91,11 @@ pub fn prepare_vk(pk: &ProvingKey<Bn254>) -> PreparedVerifyingKey<Bn254> {
     Groth16::<Bn254>::process_vk(&pk.vk).unwrap()
 }
 
+/// Prepare a standalone verifying key, e.g. one published without its proving key.
+pub fn prepare_verifying_key(vk: &VerifyingKey<Bn254>) -> PreparedVerifyingKey<Bn254> {
+    Groth16::<Bn254>::process_vk(vk).unwrap()
+}
+
 /// Verify a Groth16 proof with the given verifying key and public inputs.
 pub fn verify(
     vk: &PreparedVerifyingKey<Bn254>,
@@ -0,0 +1,171 @@
+//! Anonymous membership primitives.
+//!
+//! Members publish an identity commitment `H(secret, 0)`. Commitments are
+//! collected into a fixed-depth Merkle tree whose root is public, and a member
+//! proves knowledge of a leaf under that root with
+//! [`AnonymousMembershipCircuit`](crate::AnonymousMembershipCircuit) without
+//! revealing which one. The proof also exposes a nullifier `H(secret, scope)`
+//! so each member can act at most once per scope, for example once per
+//! proposal.
+//!
+//! `H` is a MiMC-style compression function over the BN254 scalar field with
+//! the x⁵ permutation, which keeps the circuit small without pulling in a
+//! separate hashing library.
+
+use ark_bn254::Fr;
+use ark_ff::{PrimeField, Zero};
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::prelude::*;
+use ark_relations::r1cs::SynthesisError;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::{CryptoRng, RngCore};
+use ark_std::UniformRand;
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
+
+/// Number of MiMC rounds; enough for the x⁵ permutation over a 254-bit field.
+pub const MIMC_ROUNDS: usize = 110;
+
+fn round_constants() -> &'static [Fr] {
+    static CONSTANTS: OnceLock<Vec<Fr>> = OnceLock::new();
+    CONSTANTS.get_or_init(|| {
+        (0..MIMC_ROUNDS)
+            .map(|i| {
+                let digest = Sha256::digest(format!("icn-mimc-{i}").as_bytes());
+                Fr::from_le_bytes_mod_order(&digest)
+            })
+            .collect()
+    })
+}
+
+/// Hash two field elements.
+pub fn mimc_hash(left: Fr, right: Fr) -> Fr {
+    let mut x = left;
+    for c in round_constants() {
+        let t = x + right + c;
+        let t2 = t * t;
+        x = t2 * t2 * t;
+    }
+    x + right + left
+}
+
+/// In-circuit counterpart of [`mimc_hash`].
+pub fn mimc_hash_var(left: &FpVar<Fr>, right: &FpVar<Fr>) -> Result<FpVar<Fr>, SynthesisError> {
+    let mut x = left.clone();
+    for c in round_constants() {
+        let t = &x + right + FpVar::Constant(*c);
+        let t2 = t.square()?;
+        x = t2.square()? * &t;
+    }
+    Ok(x + right + left)
+}
+
+/// Map arbitrary bytes (a proposal ID, a ballot commitment) to a field element.
+pub fn field_from_bytes(bytes: &[u8]) -> Fr {
+    Fr::from_le_bytes_mod_order(&Sha256::digest(bytes))
+}
+
+/// Hex encoding of a field element's compressed serialization.
+pub fn field_to_hex(value: &Fr) -> String {
+    let mut bytes = Vec::new();
+    value
+        .serialize_compressed(&mut bytes)
+        .expect("field elements always serialize");
+    hex::encode(bytes)
+}
+
+/// Parse a field element produced by [`field_to_hex`].
+pub fn field_from_hex(value: &str) -> Option<Fr> {
+    let bytes = hex::decode(value).ok()?;
+    Fr::deserialize_compressed(&bytes[..]).ok()
+}
+
+/// Draw a fresh identity secret.
+pub fn random_secret<R: RngCore + CryptoRng>(rng: &mut R) -> Fr {
+    Fr::rand(rng)
+}
+
+/// Public commitment to an identity secret.
+pub fn identity_commitment(secret: Fr) -> Fr {
+    mimc_hash(secret, Fr::zero())
+}
+
+/// Nullifier of `secret` within `scope`.
+pub fn nullifier(secret: Fr, scope: Fr) -> Fr {
+    mimc_hash(secret, scope)
+}
+
+/// Authentication path from a leaf to the root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerklePath {
+    /// Sibling at each level, starting next to the leaf.
+    pub siblings: Vec<Fr>,
+    /// Leaf position; bit `i` set means the node at level `i` is a right child.
+    pub index: u64,
+}
+
+impl MerklePath {
+    /// Root reached by hashing `leaf` along this path.
+    pub fn root(&self, leaf: Fr) -> Fr {
+        self.siblings
+            .iter()
+            .enumerate()
+            .fold(leaf, |node, (level, sibling)| {
+                if (self.index >> level) & 1 == 1 {
+                    mimc_hash(*sibling, node)
+                } else {
+                    mimc_hash(node, *sibling)
+                }
+            })
+    }
+}
+
+/// Fixed-depth Merkle tree of identity commitments. Empty slots hold zero.
+#[derive(Debug, Clone)]
+pub struct MembershipTree {
+    layers: Vec<Vec<Fr>>,
+}
+
+impl MembershipTree {
+    /// Build a tree of the given depth. Returns `None` if the leaves do not fit.
+    pub fn new(depth: usize, leaves: &[Fr]) -> Option<Self> {
+        if depth >= 64 || leaves.len() > 1usize << depth {
+            return None;
+        }
+        let mut level: Vec<Fr> = leaves.to_vec();
+        level.resize(1usize << depth, Fr::zero());
+        let mut layers = vec![level];
+        for _ in 0..depth {
+            let next = layers
+                .last()
+                .expect("tree has a leaf layer")
+                .chunks(2)
+                .map(|pair| mimc_hash(pair[0], pair[1]))
+                .collect();
+            layers.push(next);
+        }
+        Some(Self { layers })
+    }
+
+    pub fn depth(&self) -> usize {
+        self.layers.len() - 1
+    }
+
+    pub fn root(&self) -> Fr {
+        self.layers[self.depth()][0]
+    }
+
+    /// Authentication path for the leaf at `index`.
+    pub fn path(&self, index: usize) -> Option<MerklePath> {
+        if index >= self.layers[0].len() {
+            return None;
+        }
+        let siblings = (0..self.depth())
+            .map(|level| self.layers[level][(index >> level) ^ 1])
+            .collect();
+        Some(MerklePath {
+            siblings,
+            index: index as u64,
+        })
+    }
+}
@@ -221,3 +221,34 @@ fn parameters_registry_multiple() {
     let vk2 = fetched2.prepared_vk().unwrap();
     assert!(verify(&vk2, &proof2, &[Fr::from(1u64)]).unwrap());
 }
+
+#[test]
+fn anonymous_membership_proof() {
+    use crate::membership::{identity_commitment, nullifier};
+
+    let secrets: Vec<Fr> = (1..=3u64).map(Fr::from).collect();
+    let leaves: Vec<Fr> = secrets.iter().map(|s| identity_commitment(*s)).collect();
+    let tree = MembershipTree::new(2, &leaves).unwrap();
+    let path = tree.path(1).unwrap();
+    assert_eq!(path.root(leaves[1]), tree.root());
+
+    let scope = Fr::from(77u64);
+    let circuit = AnonymousMembershipCircuit {
+        secret: secrets[1],
+        path,
+        root: tree.root(),
+        scope,
+        nullifier: nullifier(secrets[1], scope),
+        signal: Fr::from(5u64),
+    };
+    let mut rng = StdRng::seed_from_u64(42);
+    let pk = setup(AnonymousMembershipCircuit::blank(2), &mut rng).unwrap();
+    let inputs = circuit.public_inputs();
+    let proof = prove(&pk, circuit, &mut rng).unwrap();
+    let vk = prepare_verifying_key(&pk.vk);
+    assert!(verify(&vk, &proof, &inputs).unwrap());
+
+    let mut tampered = inputs.clone();
+    tampered[3] = Fr::from(6u64);
+    assert!(!verify(&vk, &proof, &tampered).unwrap());
+}
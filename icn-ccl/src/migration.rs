@@ -5,6 +5,7 @@
 //! and converting from other contract languages to CCL.
 
 use crate::error::CclError;
+use icn_common::Cid;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
@@ -284,6 +285,64 @@ impl MigrationEngine {
     }
 }
 
+/// Multicodec used for migrated CCL source published to the DAG (raw bytes).
+const CCL_SOURCE_CODEC: u64 = 0x55;
+
+/// A contract upgrade carried by a governance `SoftwareUpgrade` manifest.
+///
+/// The migrated source is content addressed so every node can check that the
+/// contract it installs at activation is exactly the one that was voted on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractUpgrade {
+    /// Contract name or CID of the currently deployed module.
+    pub contract: String,
+    pub from_version: CclVersion,
+    pub to_version: CclVersion,
+    /// CID of the migrated CCL source.
+    pub source_cid: Cid,
+    /// CID of the compiled WASM module, when it has already been built.
+    #[serde(default)]
+    pub wasm_cid: Option<Cid>,
+}
+
+impl ContractUpgrade {
+    /// Check that `source` is the migrated contract referenced by this upgrade.
+    pub fn verify_source(&self, source: &str) -> Result<(), CclError> {
+        let cid = Cid::new_v1_sha256(CCL_SOURCE_CODEC, source.as_bytes());
+        if cid != self.source_cid {
+            return Err(CclError::SemanticError(format!(
+                "Contract source for {} does not match upgrade CID {}",
+                self.contract, self.source_cid
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl MigrationEngine {
+    /// Migrate `content` to `to_version` and describe the result as a
+    /// [`ContractUpgrade`] suitable for inclusion in an upgrade manifest.
+    ///
+    /// Returns the upgrade descriptor together with the migrated source.
+    pub fn prepare_upgrade(
+        &self,
+        contract: &str,
+        content: &str,
+        to_version: &CclVersion,
+    ) -> Result<(ContractUpgrade, String), CclError> {
+        let from_version = self.detect_version(content)?;
+        let migrated = self.migrate(content, &from_version, to_version)?;
+        let upgrade = ContractUpgrade {
+            contract: contract.to_string(),
+            from_version,
+            to_version: to_version.clone(),
+            source_cid: Cid::new_v1_sha256(CCL_SOURCE_CODEC, migrated.as_bytes()),
+            wasm_cid: None,
+        };
+        Ok((upgrade, migrated))
+    }
+}
+
 /// Migration report showing what changes will be made
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MigrationReport {
@@ -523,4 +582,18 @@ mod tests {
         assert!(migrated.contains("charge_mana"));
         assert!(!migrated.contains("charge("));
     }
+
+    #[test]
+    fn test_prepare_upgrade_addresses_migrated_source() {
+        let engine = MigrationEngine::new();
+        let content = "rule test_rule when true then charge(100)";
+        let (upgrade, migrated) = engine
+            .prepare_upgrade("billing", content, &CclVersion::new(0, 2, 0))
+            .unwrap();
+
+        assert_eq!(upgrade.from_version, CclVersion::new(0, 1, 0));
+        assert_eq!(upgrade.to_version, CclVersion::new(0, 2, 0));
+        assert!(upgrade.verify_source(&migrated).is_ok());
+        assert!(upgrade.verify_source(content).is_err());
+    }
 }
@@ -1,13 +1,33 @@
-use icn_api::identity_trait::{
-    DisclosureRequest, DisclosureResponse, IssueCredentialRequest, CredentialResponse,
-};
+use icn_api::identity_trait::{DisclosureRequest, DisclosureResponse};
 use icn_common::{Cid, Did};
+use icn_identity::{Credential, SignatureBytes};
 use icn_node::{app_router_with_options, RuntimeMode};
+use icn_runtime::context::RuntimeContext;
 use reqwest::{Client, StatusCode};
-use std::collections::BTreeMap;
 use tokio::task;
 use tokio::time::{sleep, Duration};
 
+/// Credential signed with the node key, expiring at `expires_at`.
+fn node_credential(ctx: &RuntimeContext, expires_at: u64) -> Credential {
+    let mut cred = Credential::new(
+        ctx.current_identity.clone(),
+        Did::new("key", "holder"),
+        [
+            ("role".to_string(), "tester".to_string()),
+            ("age".to_string(), "30".to_string()),
+        ]
+        .into(),
+        Some(Cid::new_v1_sha256(0x55, b"schema")),
+    );
+    cred.expires_at = Some(expires_at);
+    let claims: Vec<_> = cred.claims.clone().into_iter().collect();
+    for (k, v) in claims {
+        let sig = ctx.signer.sign(&cred.claim_signing_bytes(&k, &v)).unwrap();
+        cred.signatures.insert(k, SignatureBytes(sig));
+    }
+    cred
+}
+
 #[tokio::test]
 async fn credential_disclose_route() {
     std::fs::write("fixtures/mana_ledger.tmp", "{\"balances\":{}}").unwrap();
@@ -25,7 +45,6 @@ async fn credential_disclose_route() {
         None,
     )
     .await;
-    let node_did = ctx.current_identity.clone();
     let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
     let addr = listener.local_addr().unwrap();
     let server = task::spawn(async move {
@@ -36,29 +55,19 @@ async fn credential_disclose_route() {
     sleep(Duration::from_millis(100)).await;
 
     let client = Client::new();
-    let issue_url = format!("http://{}/identity/credentials/issue", addr);
-
-    let mut attrs = BTreeMap::new();
-    attrs.insert("role".to_string(), "tester".to_string());
-    attrs.insert("age".to_string(), "30".to_string());
-    let req = IssueCredentialRequest {
-        issuer: node_did.clone(),
-        holder: Did::new("key", "holder"),
-        attributes: attrs,
-        schema: Cid::new_v1_sha256(0x55, b"schema"),
-        expiration: chrono::Utc::now().timestamp() as u64 + 60,
-    };
-
-    let resp = client.post(&issue_url).json(&req).send().await.unwrap();
-    assert_eq!(resp.status(), StatusCode::CREATED);
-    let cred_resp: CredentialResponse = resp.json().await.unwrap();
+    let credential = node_credential(&ctx, chrono::Utc::now().timestamp() as u64 + 60);
 
     let disclose_url = format!("http://{}/identity/credentials/disclose", addr);
     let disc_req = DisclosureRequest {
-        credential: cred_resp.credential.clone(),
+        credential,
         fields: vec!["role".to_string()],
     };
-    let resp = client.post(&disclose_url).json(&disc_req).send().await.unwrap();
+    let resp = client
+        .post(&disclose_url)
+        .json(&disc_req)
+        .send()
+        .await
+        .unwrap();
     assert_eq!(resp.status(), StatusCode::OK);
     let disc_resp: DisclosureResponse = resp.json().await.unwrap();
     assert!(disc_resp.credential.claims.contains_key("role"));
@@ -85,7 +94,6 @@ async fn disclosure_fails_when_expired() {
         None,
     )
     .await;
-    let node_did = ctx.current_identity.clone();
     let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
     let addr = listener.local_addr().unwrap();
     let server = task::spawn(async move {
@@ -96,28 +104,19 @@ async fn disclosure_fails_when_expired() {
     sleep(Duration::from_millis(100)).await;
 
     let client = Client::new();
-    let issue_url = format!("http://{}/identity/credentials/issue", addr);
-
-    let mut attrs = BTreeMap::new();
-    attrs.insert("role".to_string(), "tester".to_string());
-    let req = IssueCredentialRequest {
-        issuer: node_did.clone(),
-        holder: Did::new("key", "holder"),
-        attributes: attrs,
-        schema: Cid::new_v1_sha256(0x55, b"schema"),
-        expiration: chrono::Utc::now().timestamp() as u64 - 1,
-    };
-
-    let resp = client.post(&issue_url).json(&req).send().await.unwrap();
-    assert_eq!(resp.status(), StatusCode::CREATED);
-    let cred_resp: CredentialResponse = resp.json().await.unwrap();
+    let credential = node_credential(&ctx, chrono::Utc::now().timestamp() as u64 - 1);
 
     let disclose_url = format!("http://{}/identity/credentials/disclose", addr);
     let disc_req = DisclosureRequest {
-        credential: cred_resp.credential,
+        credential,
         fields: vec!["role".to_string()],
     };
-    let resp = client.post(&disclose_url).json(&disc_req).send().await.unwrap();
+    let resp = client
+        .post(&disclose_url)
+        .json(&disc_req)
+        .send()
+        .await
+        .unwrap();
     assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
 
     server.abort();
@@ -1,14 +1,80 @@
 use icn_api::identity_trait::{
-    CredentialResponse, IssueCredentialRequest, RevokeCredentialRequest, VerificationResponse,
+    CredentialResponse, IssueCredentialRequest, PublishSchemaRequest, RevokeCredentialRequest,
+    SchemaResponse, VerificationResponse,
 };
 use icn_common::{Cid, Did};
-use icn_identity::Credential;
+use icn_identity::{
+    did_key_from_verifying_key, generate_ed25519_keypair, Credential, IssuanceOffer,
+    IssuanceRequest, IssuerPolicy, SigningKey,
+};
 use icn_node::{app_router_with_options, RuntimeMode};
 use reqwest::{Client, StatusCode};
 use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::str::FromStr;
 use tokio::task;
 use tokio::time::{sleep, Duration};
 
+/// Publish a schema for `role` claims and let `node_did` issue membership
+/// credentials for the bakery under it.
+async fn membership_schema(client: &Client, addr: SocketAddr, node_did: &Did) -> Cid {
+    let resp = client
+        .post(format!("http://{addr}/identity/schemas"))
+        .json(&PublishSchemaRequest {
+            name: "membership".into(),
+            version: "1.0".into(),
+            json_schema: serde_json::json!({
+                "type": "object",
+                "properties": { "role": { "type": "string" } },
+                "required": ["role"]
+            }),
+        })
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let schema: SchemaResponse = resp.json().await.unwrap();
+    let resp = client
+        .post(format!("http://{addr}/identity/issuance/policies"))
+        .json(&IssuerPolicy {
+            cooperative_id: "bakery".into(),
+            credential_type: "membership".into(),
+            issuers: vec![node_did.clone()],
+            schema: Some(schema.cid.clone()),
+            max_validity: None,
+        })
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.status().is_success());
+    schema.cid
+}
+
+fn holder() -> (SigningKey, Did) {
+    let (sk, pk) = generate_ed25519_keypair();
+    let did = Did::from_str(&did_key_from_verifying_key(&pk)).unwrap();
+    (sk, did)
+}
+
+fn issue_request(
+    node_did: &Did,
+    holder: &Did,
+    schema: &Cid,
+    expiration: u64,
+) -> IssueCredentialRequest {
+    let mut attrs = BTreeMap::new();
+    attrs.insert("role".to_string(), "tester".to_string());
+    IssueCredentialRequest {
+        issuer: node_did.clone(),
+        holder: holder.clone(),
+        attributes: attrs,
+        schema: schema.clone(),
+        expiration,
+        credential_type: "membership".into(),
+        cooperative_id: "bakery".into(),
+    }
+}
+
 #[tokio::test]
 async fn credential_issue_route() {
     std::fs::write("fixtures/mana_ledger.tmp", "{\"balances\":{}}").unwrap();
@@ -37,23 +103,37 @@ async fn credential_issue_route() {
 
     sleep(Duration::from_millis(100)).await;
     let client = Client::new();
+    let schema = membership_schema(&client, addr, &node_did).await;
+    let (holder_sk, holder) = holder();
     let url = format!("http://{addr}/identity/credentials/issue");
+    let expiration = chrono::Utc::now().timestamp() as u64 + 60;
 
-    let mut attrs = BTreeMap::new();
-    attrs.insert("role".to_string(), "tester".to_string());
-    let req = IssueCredentialRequest {
-        issuer: node_did.clone(),
-        holder: Did::new("key", "holder"),
-        attributes: attrs,
-        schema: Cid::new_v1_sha256(0x55, b"schema"),
-        expiration: chrono::Utc::now().timestamp() as u64 + 60,
-    };
+    // Unknown schemas are refused.
+    let unknown = issue_request(
+        &node_did,
+        &holder,
+        &Cid::new_v1_sha256(0x55, b"schema"),
+        expiration,
+    );
+    let resp = client.post(&url).json(&unknown).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
 
+    // Issuing opens an offer; the credential is issued once the holder signs it.
+    let req = issue_request(&node_did, &holder, &schema, expiration);
     let resp = client.post(&url).json(&req).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let offer: IssuanceOffer = resp.json().await.unwrap();
+    let resp = client
+        .post(format!("http://{addr}/identity/issuance/requests"))
+        .json(&IssuanceRequest::sign(&offer, &holder_sk))
+        .send()
+        .await
+        .unwrap();
     assert!(resp.status().is_success());
     let resp_body: CredentialResponse = resp.json().await.unwrap();
     let cid = resp_body.cid.clone();
     let cred: Credential = resp_body.credential;
+    assert!(cred.issued_under.is_some());
     for k in cred.claims.keys() {
         assert!(cred.verify_claim(k, ctx.signer.verifying_key_ref()).is_ok());
     }
@@ -120,29 +200,34 @@ async fn expired_credential_rejected() {
     });
     sleep(Duration::from_millis(100)).await;
     let client = Client::new();
+    let schema = membership_schema(&client, addr, &node_did).await;
+    let (_, holder) = holder();
     let issue_url = format!("http://{addr}/identity/credentials/issue");
 
-    let mut attrs = BTreeMap::new();
-    attrs.insert("role".to_string(), "tester".to_string());
-    let req = IssueCredentialRequest {
-        issuer: node_did.clone(),
-        holder: Did::new("key", "holder"),
-        attributes: attrs,
-        schema: Cid::new_v1_sha256(0x55, b"schema"),
-        expiration: chrono::Utc::now().timestamp() as u64 - 1,
-    };
-
+    let req = issue_request(
+        &node_did,
+        &holder,
+        &schema,
+        chrono::Utc::now().timestamp() as u64 - 1,
+    );
     let resp = client.post(&issue_url).json(&req).send().await.unwrap();
-    assert_eq!(resp.status(), StatusCode::CREATED);
-    let cred_resp: CredentialResponse = resp.json().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // A credential the node signed that has since expired fails verification.
+    let mut cred = Credential::new(
+        node_did.clone(),
+        holder,
+        [("role".to_string(), "tester".to_string())].into(),
+        Some(schema),
+    );
+    cred.expires_at = Some(chrono::Utc::now().timestamp() as u64 - 1);
+    let bytes = cred.claim_signing_bytes("role", "tester");
+    let sig = ctx.signer.sign(&bytes).unwrap();
+    cred.signatures
+        .insert("role".into(), icn_identity::SignatureBytes(sig));
 
     let verify_url = format!("http://{addr}/identity/credentials/verify");
-    let resp = client
-        .post(&verify_url)
-        .json(&cred_resp.credential)
-        .send()
-        .await
-        .unwrap();
+    let resp = client.post(&verify_url).json(&cred).send().await.unwrap();
     assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
 
     server.abort();
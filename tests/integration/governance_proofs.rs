@@ -78,6 +78,7 @@ fn vote_proof_validation() {
         vote_option: "yes".into(),
         credential_proof: Some(dummy_proof(true)),
         revocation_proof: None,
+        revision: None,
     };
     assert!(api.cast_vote(ok_vote).is_ok());
 
@@ -87,6 +88,7 @@ fn vote_proof_validation() {
         vote_option: "yes".into(),
         credential_proof: Some(dummy_proof(false)),
         revocation_proof: None,
+        revision: None,
     };
     assert!(api.cast_vote(bad_vote).is_err());
 }